@@ -1,8 +1,10 @@
 use itertools::Itertools;
 
 use crate::asm::Reg;
-use crate::identifiers::{BlockName, VarName};
-use std::collections::{BTreeSet, HashMap, HashSet};
+use crate::identifiers::{BlockName, FunName, VarName};
+use crate::ssa::{Immediate, Prim2};
+use crate::types::Type;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::hash::Hash;
 use std::{fmt, ops};
 
@@ -108,6 +110,243 @@ impl<'a> IntoIterator for &'a LiveSet {
     }
 }
 
+/* -------------------------------- Memory SSA ------------------------------ */
+
+/// A version of the memory, i.e. its state between two instructions
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Memory(pub u32);
+
+impl fmt::Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mem{}", self.0)
+    }
+}
+
+/// The version of the memory that an instruction runs in, and the version it
+/// leaves, which is a new one after a store, a call or an allocation. A block
+/// starts from the version of its entry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryAccess {
+    pub uses: Memory,
+    pub defs: Memory,
+}
+
+/// The instruction that defines a version of the memory from the previous one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MemoryDef {
+    /// The entry of a block, which merges the versions that its predecessors
+    /// branch with. A block that a function calls is entered with any memory,
+    /// and merges none.
+    Phi(BlockName, Vec<Memory>),
+    /// addr: untagged, offset: untagged, val: either
+    Store {
+        addr: Immediate<VarName>,
+        offset: Immediate<VarName>,
+        val: Immediate<VarName>,
+        prev: Memory,
+    },
+    /// A call, which may write any array, but no length
+    Call(FunName, Memory),
+    /// The allocation of `array`, which writes its length `len` and nothing else
+    Allocate { array: VarName, len: Immediate<VarName>, prev: Memory },
+}
+
+/// The definitions of the versions of the memory, which order the loads and
+/// the stores by their data dependencies
+#[derive(Clone, Debug, Default)]
+pub struct MemorySsa(pub BTreeMap<Memory, MemoryDef>);
+
+impl MemorySsa {
+    pub fn def(&self, mem: Memory) -> &MemoryDef {
+        &self.0[&mem]
+    }
+
+    /// The version that `mem` was defined from, unless it merges versions
+    pub fn prev(&self, mem: Memory) -> Option<Memory> {
+        match self.def(mem) {
+            MemoryDef::Phi(..) => None,
+            MemoryDef::Store { prev, .. }
+            | MemoryDef::Call(_, prev)
+            | MemoryDef::Allocate { prev, .. } => Some(*prev),
+        }
+    }
+}
+
+impl fmt::Display for MemoryDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoryDef::Phi(_, incoming) if incoming.is_empty() => write!(f, "entry"),
+            MemoryDef::Phi(_, incoming) => {
+                write!(f, "phi({})", incoming.iter().map(|mem| mem.to_string()).join(", "))
+            }
+            MemoryDef::Store { prev, .. } => write!(f, "store({})", prev),
+            MemoryDef::Call(_, prev) => write!(f, "call({})", prev),
+            MemoryDef::Allocate { prev, .. } => write!(f, "allocate({})", prev),
+        }
+    }
+}
+
+/* ------------------------------ Refinement Facts ------------------------- */
+
+/// What a conditional branch tells its target about a variable, which is
+/// the root of its copies
+#[derive(Clone, PartialEq, Eq)]
+pub enum Fact {
+    /// The variable has the type, as after `if isInt(x)`
+    Is(VarName, Type),
+    /// The variable has another type, as in the `else` of `if isInt(x)`
+    IsNot(VarName, Type),
+    /// The comparison of the tagged variable with the tagged immediate
+    /// holds, as after `if x < 10`
+    Cmp(VarName, Prim2, Immediate<VarName>),
+}
+
+impl Fact {
+    /// The variable that the fact is about
+    pub fn var(&self) -> &VarName {
+        match self {
+            Fact::Is(var, _) | Fact::IsNot(var, _) | Fact::Cmp(var, _, _) => var,
+        }
+    }
+}
+
+impl fmt::Display for Fact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fact::Is(var, ty) => write!(f, "{}: {}", var, ty),
+            Fact::IsNot(var, ty) => write!(f, "{}: !{}", var, ty),
+            Fact::Cmp(var, prim, imm) => write!(f, "{} {} {}", var, prim, imm),
+        }
+    }
+}
+
+/// The facts that the conditional branches establish on their edges, and
+/// those that hold on entry to each block: a block entered only from
+/// another holds the facts of that one, of the assertions of its body and
+/// of the edge between them
+#[derive(Clone, Default)]
+pub struct Facts {
+    pub edges: HashMap<(BlockName, BlockName), Vec<Fact>>,
+    pub entries: HashMap<BlockName, Vec<Fact>>,
+    /// The variable that each copy copies, through copies
+    pub copies: HashMap<VarName, VarName>,
+}
+
+impl Facts {
+    /// The variable that `var` is a copy of, or `var`
+    pub fn root<'a>(&'a self, var: &'a VarName) -> &'a VarName {
+        self.copies.get(var).unwrap_or(var)
+    }
+
+    /// The facts that the branch from `from` to `to` establishes
+    pub fn on_edge(&self, from: &BlockName, to: &BlockName) -> &[Fact] {
+        self.edges.get(&(from.clone(), to.clone())).map_or(&[], |facts| facts)
+    }
+
+    /// The facts that hold on entry to `block`
+    pub fn at(&self, block: &BlockName) -> &[Fact] {
+        self.entries.get(block).map_or(&[], |facts| facts)
+    }
+
+    /// The facts about `var` that hold on entry to `block`
+    pub fn about<'a>(
+        &'a self, block: &BlockName, var: &'a VarName,
+    ) -> impl Iterator<Item = &'a Fact> + 'a {
+        let root = self.root(var);
+        self.at(block).iter().filter(move |fact| fact.var() == root)
+    }
+
+    /// Whether `var` has type `ty` on entry to `block`
+    pub fn proves(&self, block: &BlockName, var: &VarName, ty: Type) -> bool {
+        self.about(block, var).any(|fact| *fact == Fact::Is(self.root(var).clone(), ty))
+    }
+
+    /// Whether `var` has another type than `ty` on entry to `block`
+    pub fn refutes(&self, block: &BlockName, var: &VarName, ty: Type) -> bool {
+        self.about(block, var).any(|fact| match fact {
+            Fact::Is(_, other) => *other != ty,
+            Fact::IsNot(_, other) => *other == ty,
+            Fact::Cmp(..) => false,
+        })
+    }
+}
+
+/* --------------------------- Induction Variables -------------------------- */
+
+/// How a variable of a loop changes from one round to the next, in words:
+/// `i + 1` steps the tagged `i` by 2
+#[derive(Clone, PartialEq, Eq)]
+pub enum Induction {
+    /// A parameter of the loop that starts from `init`, the values the loop
+    /// is entered with, none if calls enter it, and that each round adds
+    /// `step` to, as `loop(i + 1)` does
+    Basic { init: Vec<Immediate<VarName>>, step: i64 },
+    /// `base * num / den + offset`, computed in the loop from the basic
+    /// variable `base`, where `den` is a power of two, as the untagged
+    /// index `i >> 1`
+    Derived { base: VarName, num: i64, den: i64, offset: i64 },
+}
+
+impl fmt::Display for Induction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Induction::Basic { init, step } if init.is_empty() => write!(f, "step {}", step),
+            Induction::Basic { init, step } => {
+                write!(f, "from {}, step {}", init.iter().join(" or "), step)
+            }
+            Induction::Derived { base, num, den, offset } => {
+                write!(f, "{}", base)?;
+                match (num, den) {
+                    (1, 1) => {}
+                    (num, 1) => write!(f, " * {}", num)?,
+                    (num, den) => write!(f, " * {}/{}", num, den)?,
+                }
+                match offset {
+                    0 => Ok(()),
+                    offset if *offset < 0 => write!(f, " - {}", -offset),
+                    offset => write!(f, " + {}", offset),
+                }
+            }
+        }
+    }
+}
+
+/// The induction variables of each loop, i.e. of each block that branches
+/// to itself from its body, in the order they are defined
+#[derive(Clone, Default)]
+pub struct InductionVars(pub BTreeMap<BlockName, Vec<(VarName, Induction)>>);
+
+impl InductionVars {
+    pub fn get(&self, var: &VarName) -> Option<&Induction> {
+        self.0.values().flatten().find(|(v, _)| v == var).map(|(_, induction)| induction)
+    }
+
+    /// What each round adds to `var`, if a whole number of words
+    pub fn step(&self, var: &VarName) -> Option<i64> {
+        match self.get(var)? {
+            Induction::Basic { step, .. } => Some(*step),
+            Induction::Derived { base, num, den, .. } => {
+                let step = self.step(base)? * num;
+                (step % den == 0).then(|| step / den)
+            }
+        }
+    }
+}
+
+impl fmt::Display for InductionVars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "(no loops)");
+        }
+        let loops = self.0.iter().map(|(label, vars)| {
+            let vars = vars.iter().map(|(var, induction)| format!("\n  {}: {}", var, induction));
+            let vars: String = vars.collect();
+            format!("loop {}:{}", label, if vars.is_empty() { " (none)" } else { &vars })
+        });
+        write!(f, "{}", loops.collect::<Vec<_>>().join("\n"))
+    }
+}
+
 /* ---------------------------- UnusedBlockParam ---------------------------- */
 
 /// Used in `UnusedRemover` to keep track of the unused parameters for each block.
@@ -187,6 +426,45 @@ impl ops::DerefMut for UnusedVarSet {
     }
 }
 
+/* --------------------------- ChangedSignatures ---------------------------- */
+
+/// Used in `UnusedRemover` to report the functions whose parameters were removed,
+/// mapping each to its parameters before and after the removal.
+#[derive(Default)]
+pub struct ChangedSignatures(pub BTreeMap<FunName, (Vec<VarName>, Vec<VarName>)>);
+
+impl ChangedSignatures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Display for ChangedSignatures {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use crate::pretty::*;
+        if self.0.is_empty() {
+            return write!(f, "(none)");
+        }
+        let lines = self.0.iter().map(|(fun, (before, after))| {
+            format!("{}({}) -> {}({})", fun, Comma(&before.iter()), fun, Comma(&after.iter()))
+        });
+        write!(f, "{}", lines.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+impl ops::Deref for ChangedSignatures {
+    type Target = BTreeMap<FunName, (Vec<VarName>, Vec<VarName>)>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ops::DerefMut for ChangedSignatures {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 /* ------------------------------ Reassignments ----------------------------- */
 
 /// The fresh variables for each variable reference.
@@ -382,11 +660,63 @@ impl<V: Eq + Hash> Graph<V> {
     }
 }
 
+#[cfg(feature = "svg")]
 impl<V: Eq + Hash + Ord> Graph<V> {
     /// Generates a dot file for the graph.
     pub fn dot(&self, path: impl AsRef<std::path::Path>)
     where
         V: Clone + fmt::Display,
+    {
+        use layout::core::style::StyleAttr;
+        self.dot_styled(path, |v| (v.to_string(), StyleAttr::simple()))
+    }
+
+    /// Generates a dot file for the graph, with each vertex labeled and
+    /// filled by its register in `assignment`; spilled vertices are gray
+    /// with a thick red outline.
+    pub fn dot_colored(
+        &self, path: impl AsRef<std::path::Path>, assignment: &HashMap<V, Allocation>,
+    ) where
+        V: Clone + fmt::Display,
+    {
+        use layout::core::color::Color;
+        use layout::core::style::StyleAttr;
+        const PALETTE: [&str; 13] = [
+            "lightblue",
+            "lightgreen",
+            "lightpink",
+            "lightsalmon",
+            "khaki",
+            "plum",
+            "aquamarine",
+            "wheat",
+            "lightskyblue",
+            "palegreen",
+            "thistle",
+            "peachpuff",
+            "lightcyan",
+        ];
+        self.dot_styled(path, |v| match assignment.get(v) {
+            Some(Allocation::Reg(reg)) => {
+                let idx = Reg::ALLOCATABLE.iter().position(|r| r == reg).unwrap_or(0);
+                let fill = Color::fast(PALETTE[idx % PALETTE.len()]);
+                let style = StyleAttr::new(Color::fast("black"), 2, Some(fill), 0, 15);
+                (format!("{}: {}", v, reg), style)
+            }
+            Some(spill @ Allocation::Spill(_)) => {
+                let fill = Color::fast("lightgray");
+                let style = StyleAttr::new(Color::fast("red"), 4, Some(fill), 0, 15);
+                (format!("{}: {}", v, spill), style)
+            }
+            None => (v.to_string(), StyleAttr::simple()),
+        })
+    }
+
+    fn dot_styled(
+        &self, path: impl AsRef<std::path::Path>,
+        style: impl Fn(&V) -> (String, layout::core::style::StyleAttr),
+    ) where
+        V: Clone,
     {
         use itertools::Itertools as _;
         use layout::backends::svg::SVGWriter;
@@ -415,9 +745,10 @@ impl<V: Eq + Hash + Ord> Graph<V> {
         let mut vg = VisualGraph::new(Orientation::LeftToRight);
         let mut nodes = HashMap::new();
         for v in self.vertices() {
+            let (label, look) = style(&v);
             let handler = vg.add_node(Element::create(
-                ShapeKind::new_circle(&v.to_string()),
-                StyleAttr::simple(),
+                ShapeKind::new_circle(&label),
+                look,
                 Orientation::LeftToRight,
                 Point::new(100., 100.),
             ));
@@ -463,6 +794,7 @@ impl<V: Eq + Hash + Ord> Graph<V> {
 
 /// The coloring of the variables to the registers or stack slots.
 /// `i32` refers to the stack slot number.
+#[derive(Clone)]
 pub struct Coloring(pub HashMap<VarName, Allocation>);
 
 impl Coloring {
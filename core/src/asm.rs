@@ -0,0 +1,2009 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+/// Unadorned reg is a 64-bit reg
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Reg {
+    Rax,
+    Rbx,
+    Rdx,
+    Rcx,
+    Rsi,
+    Rdi,
+    Rsp,
+    Rbp,
+    R8,
+    R9,
+    R10,
+    R11,
+    R12,
+    R13,
+    R14,
+    R15,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Reg8 {
+    Ah,
+    Al,
+    Ch,
+    Cl,
+    Dh,
+    Dl,
+    Bh,
+    Bl,
+    Spl,
+    Bpl,
+    Sil,
+    Dil,
+    R8b,
+    R9b,
+    R10b,
+    R11b,
+    R12b,
+    R13b,
+    R14b,
+    R15b,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConditionCode {
+    E,
+    NE,
+    L,
+    LE,
+    G,
+    GE,
+    S,
+    Z,
+    NZ,
+    O,
+    NO,
+    AE,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MemRef {
+    pub reg: Reg,
+    pub offset: i32,
+}
+
+/// `[base + index * scale]`
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IndexRef {
+    pub base: Reg,
+    pub index: Reg,
+    pub scale: u8,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Arg64 {
+    Reg(Reg),
+    Signed(i64),
+    Unsigned(u64),
+    Mem(MemRef),
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Arg32 {
+    Reg(Reg),
+    Signed(i32),
+    Unsigned(u32),
+    Mem(MemRef),
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Reg32 {
+    Reg(Reg),
+    Imm(i32),
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MovArgs {
+    ToReg(Reg, Arg64),
+    ToMem(MemRef, Reg32),
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BinArgs {
+    ToReg(Reg, Arg32),
+    ToMem(MemRef, Reg32),
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ShArgs {
+    pub reg: Reg,
+    pub by: u8,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Loc {
+    Reg(Reg),
+    Mem(MemRef),
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Instr {
+    Mov(MovArgs),
+
+    Add(BinArgs),
+    Sub(BinArgs),
+    IMul(BinArgs),
+    And(BinArgs),
+    Or(BinArgs),
+    Xor(BinArgs),
+    Sal(ShArgs),
+    Sar(ShArgs),
+    Shl(ShArgs),
+    Shr(ShArgs),
+    Cmp(BinArgs),
+    Test(BinArgs),
+    Xchg(Reg, Reg),
+    // Loads the sign-extended DWORD at the address
+    Movsxd(Reg, IndexRef),
+    // Loads the address of a label, relative to rip
+    Lea(Reg, Label),
+
+    Push(Arg32),
+    Pop(Loc),
+
+    Label(Label),
+    Comment(String),
+    Section(String),
+    Global(Label),
+    Extern(Label),
+    Call(Label),
+    Ret,
+    Jmp(Label),
+    JmpReg(Reg),
+    // A DWORD holding the distance from the second label to the first
+    RelOffset(Label, Label),
+    // A QWORD holding a constant
+    Quad(i64),
+    // A QWORD holding the address of a label
+    QuadAddr(Label),
+    // The bytes of a string followed by a NUL
+    Asciz(String),
+    // Pads to a multiple of the given number of bytes
+    Align(u32),
+    // Bytes of data
+    Bytes(Vec<u8>),
+    // A DWORD holding the distance from itself to the label
+    PcRel(Label),
+    // A rule of the unwind tables for the code that follows, which takes no space
+    Cfi(Cfi),
+
+    // Conditional mov, jmp and set
+    CMovCC(ConditionCode, BinArgs),
+    JCC(ConditionCode, Label),
+    SetCC(ConditionCode, Reg8),
+}
+
+/// How to find the value that a register had in the caller, for the unwind
+/// tables. The canonical frame address that they are relative to is where
+/// rsp pointed before the call, above the return address.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Cfi {
+    /// Saved at the given number of bytes below the canonical frame address
+    Offset(Reg, i32),
+    /// Not changed yet
+    SameValue(Reg),
+}
+
+impl fmt::Display for ConditionCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConditionCode::*;
+        match self {
+            E => write!(f, "e"),
+            NE => write!(f, "ne"),
+            L => write!(f, "l"),
+            LE => write!(f, "le"),
+            G => write!(f, "g"),
+            GE => write!(f, "ge"),
+            S => write!(f, "s"),
+            Z => write!(f, "z"),
+            NZ => write!(f, "nz"),
+            O => write!(f, "o"),
+            NO => write!(f, "no"),
+            AE => write!(f, "ae"),
+        }
+    }
+}
+
+impl fmt::Display for Reg8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Reg8::*;
+        match self {
+            Al => write!(f, "al"),
+            Ah => write!(f, "ah"),
+            Cl => write!(f, "cl"),
+            Ch => write!(f, "ch"),
+            Dl => write!(f, "dl"),
+            Dh => write!(f, "dh"),
+            Bl => write!(f, "bl"),
+            Bh => write!(f, "bh"),
+            Spl => write!(f, "spl"),
+            Bpl => write!(f, "bpl"),
+            Sil => write!(f, "sil"),
+            Dil => write!(f, "dil"),
+            R8b => write!(f, "r8b"),
+            R9b => write!(f, "r9b"),
+            R10b => write!(f, "r10b"),
+            R11b => write!(f, "r11b"),
+            R12b => write!(f, "r12b"),
+            R13b => write!(f, "r13b"),
+            R14b => write!(f, "r14b"),
+            R15b => write!(f, "r15b"),
+        }
+    }
+}
+
+pub fn reg_to_string(r: Reg) -> String {
+    match r {
+        Reg::Rax => String::from("rax"),
+        Reg::Rbx => String::from("rbx"),
+        Reg::Rcx => String::from("rcx"),
+        Reg::Rdx => String::from("rdx"),
+        Reg::Rsi => String::from("rsi"),
+        Reg::Rdi => String::from("rdi"),
+        Reg::Rsp => String::from("rsp"),
+        Reg::Rbp => String::from("rbp"),
+        Reg::R8 => String::from("r8"),
+        Reg::R9 => String::from("r9"),
+        Reg::R10 => String::from("r10"),
+        Reg::R11 => String::from("r11"),
+        Reg::R12 => String::from("r12"),
+        Reg::R13 => String::from("r13"),
+        Reg::R14 => String::from("r14"),
+        Reg::R15 => String::from("r15"),
+    }
+}
+
+pub fn imm32_to_string(i: i32) -> String {
+    i.to_string()
+}
+
+pub fn mem_ref_to_string(m: MemRef) -> String {
+    format!("QWORD [{} + {}]", reg_to_string(m.reg), m.offset)
+}
+
+pub fn index_ref_to_string(m: IndexRef) -> String {
+    format!("DWORD [{} + {} * {}]", reg_to_string(m.base), reg_to_string(m.index), m.scale)
+}
+
+pub fn reg32_to_string(r_or_i: Reg32) -> String {
+    match r_or_i {
+        Reg32::Reg(r) => reg_to_string(r),
+        Reg32::Imm(i) => imm32_to_string(i),
+    }
+}
+
+pub fn arg32_to_string(arg: Arg32) -> String {
+    match arg {
+        Arg32::Reg(r) => reg_to_string(r),
+        Arg32::Signed(i) => imm32_to_string(i),
+        Arg32::Unsigned(u) => format!("0x{:08x}", u),
+        Arg32::Mem(m) => mem_ref_to_string(m),
+    }
+}
+
+pub fn arg64_to_string(arg: Arg64) -> String {
+    match arg {
+        Arg64::Reg(r) => reg_to_string(r),
+        Arg64::Signed(i) => i.to_string(),
+        Arg64::Unsigned(u) => format!("0x{:016x}", u),
+        Arg64::Mem(m) => mem_ref_to_string(m),
+    }
+}
+
+pub fn mov_args_to_string(args: MovArgs) -> String {
+    match args {
+        MovArgs::ToReg(r, arg) => {
+            format!("{}, {}", reg_to_string(r), arg64_to_string(arg))
+        }
+        MovArgs::ToMem(mem, arg) => {
+            format!("{}, {}", mem_ref_to_string(mem), reg32_to_string(arg))
+        }
+    }
+}
+
+pub fn bin_args_to_string(args: BinArgs) -> String {
+    match args {
+        BinArgs::ToReg(r, arg) => {
+            format!("{}, {}", reg_to_string(r), arg32_to_string(arg))
+        }
+        BinArgs::ToMem(mem, arg) => {
+            format!("{}, {}", mem_ref_to_string(mem), reg32_to_string(arg))
+        }
+    }
+}
+
+pub fn sh_args_to_string(args: ShArgs) -> String {
+    format!("{}, {}", reg_to_string(args.reg), args.by)
+}
+
+pub fn loc_to_string(loc: Loc) -> String {
+    match loc {
+        Loc::Reg(r) => reg_to_string(r),
+        Loc::Mem(m) => mem_ref_to_string(m),
+    }
+}
+
+pub fn instr_to_string(i: &Instr, symbols: &Symbols) -> String {
+    let name = |l: &Label| symbols.name(*l);
+    match i {
+        Instr::Mov(args) => {
+            format!("        mov {}", mov_args_to_string(*args))
+        }
+        Instr::Add(args) => {
+            format!("        add {}", bin_args_to_string(*args))
+        }
+        Instr::Sub(args) => {
+            format!("        sub {}", bin_args_to_string(*args))
+        }
+        Instr::IMul(args) => {
+            format!("        imul {}", bin_args_to_string(*args))
+        }
+        Instr::And(args) => {
+            format!("        and {}", bin_args_to_string(*args))
+        }
+        Instr::Or(args) => {
+            format!("        or {}", bin_args_to_string(*args))
+        }
+        Instr::Xor(args) => {
+            format!("        xor {}", bin_args_to_string(*args))
+        }
+        Instr::Sal(args) => {
+            format!("        sal {}", sh_args_to_string(*args))
+        }
+        Instr::Sar(args) => {
+            format!("        sar {}", sh_args_to_string(*args))
+        }
+        Instr::Shl(args) => {
+            format!("        shl {}", sh_args_to_string(*args))
+        }
+        Instr::Shr(args) => {
+            format!("        shr {}", sh_args_to_string(*args))
+        }
+        Instr::Cmp(args) => {
+            format!("        cmp {}", bin_args_to_string(*args))
+        }
+        Instr::Test(args) => {
+            format!("        test {}", bin_args_to_string(*args))
+        }
+        Instr::Xchg(r1, r2) => {
+            format!("        xchg {}, {}", reg_to_string(*r1), reg_to_string(*r2))
+        }
+        Instr::Movsxd(r, m) => {
+            format!("        movsxd {}, {}", reg_to_string(*r), index_ref_to_string(*m))
+        }
+        Instr::Lea(r, l) => {
+            format!("        lea {}, [rel {}]", reg_to_string(*r), name(l))
+        }
+        Instr::Push(arg) => {
+            format!("        push {}", arg32_to_string(*arg))
+        }
+        Instr::Pop(loc) => {
+            format!("        pop {}", loc_to_string(*loc))
+        }
+        Instr::Label(l) => {
+            format!("{}:", name(l))
+        }
+        Instr::Comment(s) => {
+            format!(";;; {}", s)
+        }
+        Instr::Section(s) => {
+            format!("section {}", s)
+        }
+        Instr::Global(l) => {
+            format!("        global {}", name(l))
+        }
+        Instr::Extern(l) => {
+            format!("        extern {}", name(l))
+        }
+
+        Instr::Call(l) => {
+            format!("        call {}", name(l))
+        }
+        Instr::Ret => {
+            format!("        ret")
+        }
+
+        Instr::CMovCC(cc, args) => {
+            format!("        cmov{} {}", cc, bin_args_to_string(*args))
+        }
+
+        Instr::Jmp(l) => {
+            format!("        jmp {}", name(l))
+        }
+        Instr::JmpReg(r) => {
+            format!("        jmp {}", reg_to_string(*r))
+        }
+        Instr::RelOffset(l, anchor) => {
+            format!("        dd {} - {}", name(l), name(anchor))
+        }
+        Instr::Quad(n) => {
+            format!("        dq {}", n)
+        }
+        Instr::QuadAddr(l) => {
+            format!("        dq {}", name(l))
+        }
+        Instr::Asciz(s) => {
+            format!("        db `{}`, 0", escape_string(s))
+        }
+        Instr::Align(n) => {
+            format!("        align {}", n)
+        }
+        Instr::Bytes(bytes) => {
+            let bytes: Vec<_> = bytes.iter().map(|b| format!("0x{:02x}", b)).collect();
+            format!("        db {}", bytes.join(", "))
+        }
+        Instr::PcRel(l) => {
+            format!("        dd {} - $", name(l))
+        }
+        // nasm has no directives for unwind tables, see `Asm::add_unwind_tables`
+        Instr::Cfi(Cfi::Offset(r, n)) => {
+            format!(";;; .cfi_offset {}, {}", reg_to_string(*r), -n)
+        }
+        Instr::Cfi(Cfi::SameValue(r)) => {
+            format!(";;; .cfi_same_value {}", reg_to_string(*r))
+        }
+        Instr::JCC(cc, l) => {
+            format!("        j{} {}", cc, name(l))
+        }
+        Instr::SetCC(cc, a) => {
+            format!("        set{} {}", cc, a)
+        }
+    }
+}
+
+/// The bytes of `s` in a backquoted nasm string, escaped where needed
+fn escape_string(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'`' | b'\\' => format!("\\{}", b as char),
+            b' '..=b'~' => (b as char).to_string(),
+            b => format!("\\x{:02x}", b),
+        })
+        .collect()
+}
+
+pub fn instrs_to_string(is: &[Instr], symbols: &Symbols) -> String {
+    let mut buf = String::new();
+    for i in is {
+        buf.push_str(&instr_to_string(i, symbols));
+        buf.push_str("\n");
+    }
+    buf
+}
+
+/* --------------------------------- Symbols -------------------------------- */
+
+/// A label of the emitted code or data, or a symbol defined elsewhere; an
+/// index into the `Symbols` that made it.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Label(u32);
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Binding {
+    /// Defined in the emitted code and only used there
+    Local,
+    /// Defined in the emitted code and exported with `global`
+    Global,
+    /// Defined outside of the emitted code, e.g. by the runtime
+    Extern,
+}
+
+/// The arena of the labels of a program. A name always makes the same
+/// label, and `fresh` makes labels whose names no other label has.
+#[derive(Clone, Debug, Default)]
+pub struct Symbols {
+    names: Vec<String>,
+    bindings: Vec<Binding>,
+    labels: HashMap<String, Label>,
+    /// The next suffix of the fresh labels of each hint
+    counts: HashMap<String, usize>,
+}
+
+impl Symbols {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn intern(&mut self, name: String, binding: Binding) -> Label {
+        if let Some(label) = self.labels.get(&name) {
+            let current = &mut self.bindings[label.0 as usize];
+            if *current == Binding::Local {
+                *current = binding;
+            }
+            return *label;
+        }
+        let label = Label(self.names.len() as u32);
+        self.names.push(name.clone());
+        self.bindings.push(binding);
+        self.labels.insert(name, label);
+        label
+    }
+
+    /// The label named `name`, defined in the emitted code
+    pub fn local(&mut self, name: impl Into<String>) -> Label {
+        self.intern(name.into(), Binding::Local)
+    }
+
+    /// The label named `name`, defined in the emitted code and exported
+    pub fn global(&mut self, name: impl Into<String>) -> Label {
+        self.intern(name.into(), Binding::Global)
+    }
+
+    /// The symbol named `name`, defined outside of the emitted code
+    pub fn external(&mut self, name: impl Into<String>) -> Label {
+        self.intern(name.into(), Binding::Extern)
+    }
+
+    /// A new local label, named `hint#n` for the first free `n`
+    pub fn fresh(&mut self, hint: &str) -> Label {
+        loop {
+            let count = self.counts.entry(hint.to_string()).or_insert(0);
+            let name = format!("{}#{}", hint, count);
+            *count += 1;
+            if !self.labels.contains_key(&name) {
+                return self.local(name);
+            }
+        }
+    }
+
+    pub fn name(&self, label: Label) -> &str {
+        &self.names[label.0 as usize]
+    }
+
+    pub fn binding(&self, label: Label) -> Binding {
+        self.bindings[label.0 as usize]
+    }
+
+    /// The label named `name`, if there is one
+    pub fn get(&self, name: &str) -> Option<Label> {
+        self.labels.get(name).copied()
+    }
+}
+
+/// Emitted instructions, with the labels they refer to
+#[derive(Clone, Debug)]
+pub struct Asm {
+    pub instrs: Vec<Instr>,
+    pub symbols: Symbols,
+}
+
+impl Asm {
+    /// Checks that every label the instructions refer to is defined exactly
+    /// once, unless it is external, and that external symbols are not
+    /// defined; the problems otherwise, one per label.
+    pub fn verify(&self) -> Result<(), Vec<String>> {
+        let mut defined = HashMap::new();
+        let mut referenced = Vec::new();
+        for instr in self.instrs.iter() {
+            match instr {
+                Instr::Label(l) => *defined.entry(*l).or_insert(0) += 1,
+                Instr::Global(l)
+                | Instr::Call(l)
+                | Instr::Jmp(l)
+                | Instr::JCC(_, l)
+                | Instr::Lea(_, l)
+                | Instr::QuadAddr(l)
+                | Instr::PcRel(l) => referenced.push(*l),
+                Instr::RelOffset(l, anchor) => referenced.extend([*l, *anchor]),
+                _ => {}
+            }
+        }
+        let mut problems = Vec::new();
+        for (label, count) in defined.iter() {
+            let name = self.symbols.name(*label);
+            if self.symbols.binding(*label) == Binding::Extern {
+                problems.push(format!("external symbol `{}` is defined", name));
+            } else if *count > 1 {
+                problems.push(format!("label `{}` is defined {} times", name, count));
+            }
+        }
+        referenced.sort();
+        referenced.dedup();
+        for label in referenced {
+            if self.symbols.binding(label) != Binding::Extern && !defined.contains_key(&label) {
+                problems.push(format!("label `{}` is never defined", self.symbols.name(label)));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            problems.sort();
+            Err(problems)
+        }
+    }
+}
+
+impl fmt::Display for Asm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&instrs_to_string(&self.instrs, &self.symbols))
+    }
+}
+
+/* -------------------------------- Fragments ------------------------------- */
+
+/// The text of a piece of the code that starts by switching to a code
+/// section, with the symbols that it defines, declares external and refers
+/// to without defining, so that it can be assembled with pieces of other
+/// compilations, e.g. of the functions that did not change.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fragment {
+    /// The first label that the fragment defines
+    pub name: String,
+    pub text: String,
+    pub defines: Vec<String>,
+    pub externs: Vec<String>,
+    pub needs: Vec<String>,
+}
+
+impl Fragment {
+    fn new(instrs: &[Instr], symbols: &Symbols) -> Self {
+        let mut defines = Vec::new();
+        let mut externs = Vec::new();
+        let mut referenced = Vec::new();
+        for instr in instrs {
+            match instr {
+                Instr::Label(l) => defines.push(*l),
+                Instr::Extern(l) => externs.push(*l),
+                Instr::Global(l)
+                | Instr::Call(l)
+                | Instr::Jmp(l)
+                | Instr::JCC(_, l)
+                | Instr::Lea(_, l)
+                | Instr::QuadAddr(l)
+                | Instr::PcRel(l) => referenced.push(*l),
+                Instr::RelOffset(l, anchor) => referenced.extend([*l, *anchor]),
+                _ => {}
+            }
+        }
+        let names = |labels: &[Label]| -> Vec<String> {
+            labels.iter().map(|l| symbols.name(*l).to_string()).collect()
+        };
+        referenced.sort();
+        referenced.dedup();
+        referenced.retain(|l| !defines.contains(l));
+        Fragment {
+            name: defines.first().map_or(String::new(), |l| symbols.name(*l).to_string()),
+            text: instrs_to_string(instrs, symbols),
+            defines: names(&defines),
+            externs: names(&externs),
+            needs: names(&referenced),
+        }
+    }
+}
+
+impl Asm {
+    /// Splits the code before each switch to a code section but the first,
+    /// as the emitter makes before each function with `fragments` or
+    /// `function_sections`: the first fragment declares the externs and
+    /// defines the error handlers, and each other one a function with its
+    /// block and jump tables, or a block that no function branches to.
+    pub fn fragments(&self) -> Vec<Fragment> {
+        let mut starts: Vec<usize> = self
+            .instrs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, instr)| matches!(instr, Instr::Section(s) if s.starts_with(".text")))
+            .map(|(i, _)| i)
+            .collect();
+        starts.insert(0, 0);
+        starts.push(self.instrs.len());
+        starts
+            .windows(2)
+            .map(|range| Fragment::new(&self.instrs[range[0]..range[1]], &self.symbols))
+            .collect()
+    }
+}
+
+/// Stitches `fragments` into the text of one program: the fragments that
+/// declare externs go first, since nasm needs them declared before they are
+/// used, and the others follow in the order given. Fails with the problems,
+/// one per symbol, if a symbol is defined more than once, or is needed but
+/// neither defined nor declared external by any of them.
+pub fn stitch(fragments: &[Fragment]) -> Result<String, Vec<String>> {
+    let mut defined: HashMap<&str, usize> = HashMap::new();
+    for name in fragments.iter().flat_map(|f| f.defines.iter()) {
+        *defined.entry(name).or_insert(0) += 1;
+    }
+    let externs: HashSet<&str> =
+        fragments.iter().flat_map(|f| f.externs.iter()).map(String::as_str).collect();
+    let mut problems = Vec::new();
+    for (name, count) in defined.iter() {
+        if externs.contains(name) {
+            problems.push(format!("external symbol `{}` is defined", name));
+        } else if *count > 1 {
+            problems.push(format!("label `{}` is defined {} times", name, count));
+        }
+    }
+    let mut needs: Vec<&str> =
+        fragments.iter().flat_map(|f| f.needs.iter()).map(String::as_str).collect();
+    needs.sort();
+    needs.dedup();
+    for name in needs {
+        if !defined.contains_key(name) && !externs.contains(name) {
+            problems.push(format!("label `{}` is never defined", name));
+        }
+    }
+    if !problems.is_empty() {
+        problems.sort();
+        return Err(problems);
+    }
+    let (first, rest): (Vec<_>, Vec<_>) = fragments.iter().partition(|f| !f.externs.is_empty());
+    Ok(first.into_iter().chain(rest).map(|f| f.text.as_str()).collect())
+}
+
+/* -------------------------- Identical code folding ------------------------- */
+
+/// The labels that `instr` refers to
+fn references_mut(instr: &mut Instr) -> Vec<&mut Label> {
+    match instr {
+        Instr::Global(l)
+        | Instr::Extern(l)
+        | Instr::Call(l)
+        | Instr::Jmp(l)
+        | Instr::JCC(_, l)
+        | Instr::Lea(_, l)
+        | Instr::QuadAddr(l)
+        | Instr::PcRel(l) => vec![l],
+        Instr::RelOffset(l, anchor) => vec![l, anchor],
+        _ => vec![],
+    }
+}
+
+/// Whether the code after `instr` may run right after it
+fn continues(instr: &Instr) -> bool {
+    !matches!(
+        instr,
+        Instr::Ret
+            | Instr::Jmp(_)
+            | Instr::JmpReg(_)
+            | Instr::Section(_)
+            | Instr::Global(_)
+            | Instr::Extern(_)
+            | Instr::RelOffset(..)
+            | Instr::Quad(_)
+            | Instr::QuadAddr(_)
+            | Instr::Asciz(_)
+            | Instr::Align(_)
+            | Instr::Bytes(_)
+            | Instr::PcRel(_)
+    )
+}
+
+/// The instructions after a label, up to the next label or section
+struct Region {
+    label: Label,
+    /// The index of the label
+    start: usize,
+    end: usize,
+    /// The label of the next region, if the code runs into it
+    fallthrough: Option<Label>,
+}
+
+impl Asm {
+    /// Identical code folding: removes the regions of code after labels that
+    /// do the same as another region, up to the labels they refer to, and
+    /// defines their labels next to the label of the region that stays. The
+    /// labels referred to are compared the same way, so that functions fold
+    /// together with their blocks, also when they are recursive.
+    ///
+    /// Data is left alone, since the offsets in jump tables depend on where
+    /// their labels are and not only on what the code there does; neither
+    /// is a region that the code kept before it runs into removed. Returns
+    /// the number of removed regions.
+    pub fn fold_identical(&mut self) -> usize {
+        let mut regions = Vec::new();
+        for (i, instr) in self.instrs.iter().enumerate() {
+            if let Instr::Label(label) = instr {
+                let end = self.instrs[i + 1..]
+                    .iter()
+                    .position(|instr| matches!(instr, Instr::Label(_) | Instr::Section(_)))
+                    .map_or(self.instrs.len(), |len| i + 1 + len);
+                regions.push(Region { label: *label, start: i, end, fallthrough: None });
+            }
+        }
+        let last = |instrs: &[Instr]| -> Option<Instr> {
+            instrs.iter().rev().find(|instr| !matches!(instr, Instr::Comment(_))).cloned()
+        };
+        for r in 0..regions.len() {
+            let Region { start, end, .. } = regions[r];
+            if last(&self.instrs[start + 1..end]).is_none_or(|i| continues(&i)) {
+                let next = regions.get(r + 1).filter(|next| next.start == end);
+                regions[r].fallthrough = next.map(|next| next.label);
+            }
+        }
+
+        // classes of labels, first by the instructions of their regions
+        // without the references, then refined by the classes of the
+        // references until no class splits
+        let mut classes = HashMap::new();
+        let mut references = Vec::new();
+        let mut keys = HashMap::new();
+        let mut section = None;
+        for (i, instr) in self.instrs.iter().enumerate() {
+            let Instr::Label(label) = instr else {
+                if let Instr::Section(name) = instr {
+                    section = Some(name.clone());
+                }
+                continue;
+            };
+            let region = &regions[references.len()];
+            let mut body = Vec::new();
+            let mut refs = Vec::new();
+            for instr in self.instrs[i + 1..region.end].iter() {
+                if matches!(instr, Instr::Comment(_)) {
+                    continue;
+                }
+                let mut instr = instr.clone();
+                for label in references_mut(&mut instr) {
+                    refs.push(*label);
+                    *label = Label(u32::MAX);
+                }
+                body.push(instr);
+            }
+            let data = body.iter().any(|instr| {
+                matches!(
+                    instr,
+                    Instr::RelOffset(..)
+                        | Instr::Quad(_)
+                        | Instr::QuadAddr(_)
+                        | Instr::Asciz(_)
+                        | Instr::Bytes(_)
+                        | Instr::PcRel(_)
+                )
+            });
+            let key = (section.clone(), body, region.fallthrough.is_some(), data.then_some(*label));
+            let count = keys.len();
+            classes.insert(*label, *keys.entry(key).or_insert(count));
+            references.push(refs);
+        }
+        let mut count = keys.len();
+        for label in references.iter().flatten() {
+            if !classes.contains_key(label) {
+                classes.insert(*label, count);
+                count += 1;
+            }
+        }
+        let region_of: HashMap<_, _> =
+            regions.iter().enumerate().map(|(r, x)| (x.label, r)).collect();
+        loop {
+            let mut signatures = HashMap::new();
+            let mut refined = HashMap::new();
+            for (label, class) in classes.iter() {
+                let signature = match region_of.get(label) {
+                    Some(r) => (
+                        *class,
+                        references[*r].iter().map(|l| classes[l]).collect(),
+                        regions[*r].fallthrough.map(|l| classes[&l]),
+                    ),
+                    None => (*class, Vec::new(), None),
+                };
+                let n = signatures.len();
+                refined.insert(*label, *signatures.entry(signature).or_insert(n));
+            }
+            if signatures.len() == count {
+                break;
+            }
+            count = signatures.len();
+            classes = refined;
+        }
+
+        // each class keeps its first region, and the ones that the code
+        // kept before them runs into
+        let mut keeps = HashMap::new();
+        let mut removed = vec![false; self.instrs.len()];
+        let mut aliases: HashMap<usize, Vec<Label>> = HashMap::new();
+        let (mut folded, mut runs_on, mut end) = (0, false, 0);
+        for region in regions.iter() {
+            if let Some(instr) = last(&self.instrs[end..region.start]) {
+                runs_on = continues(&instr);
+            }
+            end = region.end;
+            let class = classes[&region.label];
+            match keeps.get(&class) {
+                Some(keep) if !runs_on => {
+                    removed[region.start..region.end].fill(true);
+                    aliases.entry(*keep).or_default().push(region.label);
+                    folded += 1;
+                }
+                _ => {
+                    keeps.entry(class).or_insert(region.start);
+                    let body = &self.instrs[region.start + 1..region.end];
+                    runs_on = last(body).is_none_or(|instr| continues(&instr));
+                }
+            }
+        }
+        let instrs = std::mem::take(&mut self.instrs);
+        for (i, instr) in instrs.into_iter().enumerate() {
+            if let Some(labels) = aliases.get(&i) {
+                self.instrs.extend(labels.iter().map(|label| Instr::Label(*label)));
+            }
+            if !removed[i] {
+                self.instrs.push(instr);
+            }
+        }
+        folded
+    }
+}
+
+/// The memory operand of `i`, of which instructions have at most one
+pub fn mem_ref(i: &Instr) -> Option<MemRef> {
+    match i {
+        Instr::Mov(MovArgs::ToReg(_, Arg64::Mem(m)))
+        | Instr::Mov(MovArgs::ToMem(m, _))
+        | Instr::Push(Arg32::Mem(m))
+        | Instr::Pop(Loc::Mem(m)) => Some(*m),
+        Instr::Add(args)
+        | Instr::Sub(args)
+        | Instr::IMul(args)
+        | Instr::And(args)
+        | Instr::Or(args)
+        | Instr::Xor(args)
+        | Instr::Cmp(args)
+        | Instr::Test(args)
+        | Instr::CMovCC(_, args) => match args {
+            BinArgs::ToReg(_, Arg32::Mem(m)) | BinArgs::ToMem(m, _) => Some(*m),
+            BinArgs::ToReg(..) => None,
+        },
+        _ => None,
+    }
+}
+
+/* ---------------------------------- Flags --------------------------------- */
+
+/// A set of the status flags of EFLAGS
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct EFlags(u8);
+
+impl EFlags {
+    pub const NONE: EFlags = EFlags(0);
+    pub const CF: EFlags = EFlags(1);
+    pub const PF: EFlags = EFlags(1 << 1);
+    pub const AF: EFlags = EFlags(1 << 2);
+    pub const ZF: EFlags = EFlags(1 << 3);
+    pub const SF: EFlags = EFlags(1 << 4);
+    pub const OF: EFlags = EFlags(1 << 5);
+    pub const ALL: EFlags = EFlags(0b111111);
+
+    pub fn contains(self, other: EFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl std::ops::BitOr for EFlags {
+    type Output = EFlags;
+
+    fn bitor(self, other: EFlags) -> EFlags {
+        EFlags(self.0 | other.0)
+    }
+}
+
+impl ConditionCode {
+    /// The flags that the condition tests
+    pub fn reads(&self) -> EFlags {
+        match self {
+            ConditionCode::E | ConditionCode::NE | ConditionCode::Z | ConditionCode::NZ => {
+                EFlags::ZF
+            }
+            ConditionCode::L | ConditionCode::GE => EFlags::SF | EFlags::OF,
+            ConditionCode::LE | ConditionCode::G => EFlags::ZF | EFlags::SF | EFlags::OF,
+            ConditionCode::S => EFlags::SF,
+            ConditionCode::O | ConditionCode::NO => EFlags::OF,
+            ConditionCode::AE => EFlags::CF,
+        }
+    }
+}
+
+/// The flags that `instr` reads
+pub fn flags_read(instr: &Instr) -> EFlags {
+    match instr {
+        Instr::JCC(cc, _) | Instr::SetCC(cc, _) | Instr::CMovCC(cc, _) => cc.reads(),
+        _ => EFlags::NONE,
+    }
+}
+
+/// The flags that `instr` writes, including those it leaves undefined.
+/// Calls clobber them all, since the calling convention does not keep them.
+pub fn flags_written(instr: &Instr) -> EFlags {
+    match instr {
+        Instr::Add(_)
+        | Instr::Sub(_)
+        | Instr::IMul(_)
+        | Instr::And(_)
+        | Instr::Or(_)
+        | Instr::Xor(_)
+        | Instr::Cmp(_)
+        | Instr::Test(_)
+        | Instr::Call(_) => EFlags::ALL,
+        // shifts by no bits leave the flags alone
+        Instr::Sal(ShArgs { by, .. })
+        | Instr::Sar(ShArgs { by, .. })
+        | Instr::Shl(ShArgs { by, .. })
+        | Instr::Shr(ShArgs { by, .. }) => {
+            if by & 63 == 0 {
+                EFlags::NONE
+            } else {
+                EFlags::ALL
+            }
+        }
+        _ => EFlags::NONE,
+    }
+}
+
+/// The register that `instr` computes and the flags it leaves as
+/// `cmp reg, 0` would: zero, sign and parity of the result, and for the
+/// logical operations the cleared carry and overflow
+pub fn result_flags(instr: &Instr) -> Option<(Reg, EFlags)> {
+    let result = EFlags::ZF | EFlags::SF | EFlags::PF;
+    match instr {
+        Instr::Add(BinArgs::ToReg(reg, _)) | Instr::Sub(BinArgs::ToReg(reg, _)) => {
+            Some((*reg, result))
+        }
+        Instr::And(BinArgs::ToReg(reg, _))
+        | Instr::Or(BinArgs::ToReg(reg, _))
+        | Instr::Xor(BinArgs::ToReg(reg, _)) => Some((*reg, result | EFlags::CF | EFlags::OF)),
+        Instr::Sal(ShArgs { reg, by })
+        | Instr::Sar(ShArgs { reg, by })
+        | Instr::Shl(ShArgs { reg, by })
+        | Instr::Shr(ShArgs { reg, by })
+            if by & 63 != 0 =>
+        {
+            Some((*reg, result))
+        }
+        _ => None,
+    }
+}
+
+/// The condition to test instead of `cc` after `cmp reg, 0` at the end of
+/// `instrs`, if the instruction that computed the value of `reg` left the
+/// flags that it needs and nothing changed them since, so that the
+/// comparison can be left out. `reg` may have been copied from the result.
+pub fn reuse_flags(instrs: &[Instr], reg: Reg, cc: ConditionCode) -> Option<ConditionCode> {
+    let mut reg = reg;
+    for instr in instrs.iter().rev() {
+        if let Instr::Comment(_) = instr {
+            continue;
+        }
+        let (_, writes) = accesses(instr)?;
+        if !flags_written(instr).is_empty() {
+            let (_, flags) = result_flags(instr).filter(|(result, _)| *result == reg)?;
+            return if flags.contains(cc.reads()) {
+                Some(cc)
+            } else if cc == ConditionCode::L && flags.contains(EFlags::SF) {
+                // less than 0 is negative
+                Some(ConditionCode::S)
+            } else {
+                None
+            };
+        }
+        match instr {
+            Instr::Mov(MovArgs::ToReg(dst, Arg64::Reg(src))) if *dst == reg => reg = *src,
+            _ if writes.contains(&Resource::Reg(reg)) => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/* ------------------------- Instruction scheduling ------------------------- */
+
+/// What instructions read and write, which orders them
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Resource {
+    Reg(Reg),
+    Flags,
+    /// The QWORD at the offset from `rsp`. Instructions that move `rsp` write
+    /// it as a register, which keeps its slots in order around them.
+    Stack(i32),
+    /// The memory that is not on the stack, i.e. arrays and data, which any
+    /// access may alias since their addresses are only known at run time
+    Heap,
+}
+
+impl Resource {
+    fn overlaps(self, other: Resource) -> bool {
+        match (self, other) {
+            (Resource::Stack(a), Resource::Stack(b)) => (a as i64 - b as i64).abs() < 8,
+            _ => self == other,
+        }
+    }
+}
+
+/// The resources that `instr` reads and those that it writes, or `None` if
+/// the code cannot move across it, like labels, jumps and calls
+pub fn accesses(instr: &Instr) -> Option<(Vec<Resource>, Vec<Resource>)> {
+    use Resource::{Flags, Heap, Stack};
+    let memory = |m: &MemRef| if m.reg == Reg::Rsp { Stack(m.offset) } else { Heap };
+    let (mut reads, mut writes) = (Vec::new(), Vec::new());
+    // the address of the memory operand
+    if let Some(m) = mem_ref(instr) {
+        reads.push(Resource::Reg(m.reg));
+    }
+    match instr {
+        Instr::Mov(MovArgs::ToReg(dst, src)) => {
+            match src {
+                Arg64::Reg(src) => reads.push(Resource::Reg(*src)),
+                Arg64::Mem(m) => reads.push(memory(m)),
+                Arg64::Signed(_) | Arg64::Unsigned(_) => {}
+            }
+            writes.push(Resource::Reg(*dst));
+        }
+        Instr::Mov(MovArgs::ToMem(m, src)) => {
+            if let Reg32::Reg(src) = src {
+                reads.push(Resource::Reg(*src));
+            }
+            writes.push(memory(m));
+        }
+        Instr::Add(args)
+        | Instr::Sub(args)
+        | Instr::IMul(args)
+        | Instr::And(args)
+        | Instr::Or(args)
+        | Instr::Xor(args)
+        | Instr::Cmp(args)
+        | Instr::Test(args)
+        | Instr::CMovCC(_, args) => {
+            let (dst, src) = match args {
+                BinArgs::ToReg(dst, Arg32::Reg(src)) => {
+                    (Resource::Reg(*dst), Some(Resource::Reg(*src)))
+                }
+                BinArgs::ToReg(dst, Arg32::Mem(m)) => (Resource::Reg(*dst), Some(memory(m))),
+                BinArgs::ToReg(dst, _) => (Resource::Reg(*dst), None),
+                BinArgs::ToMem(m, Reg32::Reg(src)) => (memory(m), Some(Resource::Reg(*src))),
+                BinArgs::ToMem(m, Reg32::Imm(_)) => (memory(m), None),
+            };
+            reads.push(dst);
+            reads.extend(src);
+            if !matches!(instr, Instr::Cmp(_) | Instr::Test(_)) {
+                writes.push(dst);
+            }
+        }
+        Instr::Sal(ShArgs { reg, .. })
+        | Instr::Sar(ShArgs { reg, .. })
+        | Instr::Shl(ShArgs { reg, .. })
+        | Instr::Shr(ShArgs { reg, .. }) => {
+            reads.push(Resource::Reg(*reg));
+            writes.push(Resource::Reg(*reg));
+        }
+        Instr::Xchg(a, b) => {
+            reads.extend([Resource::Reg(*a), Resource::Reg(*b)]);
+            writes.extend([Resource::Reg(*a), Resource::Reg(*b)]);
+        }
+        Instr::Movsxd(dst, IndexRef { base, index, .. }) => {
+            reads.extend([Resource::Reg(*base), Resource::Reg(*index), Heap]);
+            writes.push(Resource::Reg(*dst));
+        }
+        Instr::Lea(dst, _) => writes.push(Resource::Reg(*dst)),
+        Instr::SetCC(_, reg) => {
+            // only the low byte changes, the rest of the register stays
+            let reg = Resource::Reg(widen(*reg));
+            reads.push(reg);
+            writes.push(reg);
+        }
+        _ => return None,
+    }
+    if !flags_read(instr).is_empty() {
+        reads.push(Flags);
+    }
+    if !flags_written(instr).is_empty() {
+        writes.push(Flags);
+    }
+    Some((reads, writes))
+}
+
+/// The register of which `reg` is the low or high byte
+fn widen(reg: Reg8) -> Reg {
+    match reg {
+        Reg8::Ah | Reg8::Al => Reg::Rax,
+        Reg8::Bh | Reg8::Bl => Reg::Rbx,
+        Reg8::Ch | Reg8::Cl => Reg::Rcx,
+        Reg8::Dh | Reg8::Dl => Reg::Rdx,
+        Reg8::Spl => Reg::Rsp,
+        Reg8::Bpl => Reg::Rbp,
+        Reg8::Sil => Reg::Rsi,
+        Reg8::Dil => Reg::Rdi,
+        Reg8::R8b => Reg::R8,
+        Reg8::R9b => Reg::R9,
+        Reg8::R10b => Reg::R10,
+        Reg8::R11b => Reg::R11,
+        Reg8::R12b => Reg::R12,
+        Reg8::R13b => Reg::R13,
+        Reg8::R14b => Reg::R14,
+        Reg8::R15b => Reg::R15,
+    }
+}
+
+type Accesses = (Vec<Resource>, Vec<Resource>);
+
+/// Whether the instruction of `later` must stay after that of `earlier`:
+/// one writes what the other reads or writes
+fn depends((reads, writes): &Accesses, (later_reads, later_writes): &Accesses) -> bool {
+    let any =
+        |xs: &[Resource], ys: &[Resource]| xs.iter().any(|x| ys.iter().any(|y| x.overlaps(*y)));
+    any(writes, later_reads) || any(writes, later_writes) || any(reads, later_writes)
+}
+
+/// The cycles until the result of `instr` is ready, roughly: loads and
+/// multiplications take a few, the rest one. There is no division to wait
+/// for, since the code generator does not emit `idiv`.
+fn latency(instr: &Instr) -> usize {
+    let load = match instr {
+        Instr::Mov(MovArgs::ToMem(..)) => false,
+        Instr::Movsxd(..) => true,
+        instr => mem_ref(instr).is_some(),
+    };
+    let op = if matches!(instr, Instr::IMul(_)) { 3 } else { 1 };
+    op + if load { 3 } else { 0 }
+}
+
+/// A list schedule of the instructions of a region, each with the comments
+/// before it: the indices of `accesses` in their new order. Of the
+/// instructions whose dependencies are done, it picks one whose operands
+/// are ready, first by the longest chain of latencies that waits on it, so
+/// that the instructions in the shadow of a load or a multiplication are
+/// independent of it.
+fn list_schedule(accesses: &[Accesses], latencies: &[usize]) -> Vec<usize> {
+    let n = accesses.len();
+    let mut succs = vec![Vec::new(); n];
+    let mut preds = vec![0; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            if depends(&accesses[i], &accesses[j]) {
+                succs[i].push(j);
+                preds[j] += 1;
+            }
+        }
+    }
+    let mut height = vec![0; n];
+    for i in (0..n).rev() {
+        height[i] = latencies[i] + succs[i].iter().map(|j| height[*j]).max().unwrap_or(0);
+    }
+    let mut ready_at = vec![0; n];
+    let mut scheduled = vec![false; n];
+    let (mut order, mut cycle) = (Vec::with_capacity(n), 0);
+    while order.len() < n {
+        let candidates = (0..n).filter(|i| !scheduled[*i] && preds[*i] == 0);
+        let candidates: Vec<_> = candidates.collect();
+        // stall until an operand is ready if none is
+        cycle = cycle.max(candidates.iter().map(|i| ready_at[*i]).min().unwrap());
+        let next = candidates
+            .into_iter()
+            .filter(|i| ready_at[*i] <= cycle)
+            .max_by_key(|i| (height[*i], std::cmp::Reverse(*i)))
+            .unwrap();
+        scheduled[next] = true;
+        for j in succs[next].iter() {
+            preds[*j] -= 1;
+            ready_at[*j] = ready_at[*j].max(cycle + latencies[next]);
+        }
+        order.push(next);
+        cycle += 1;
+    }
+    order
+}
+
+impl Asm {
+    /// Instruction scheduling: reorders the instructions between labels,
+    /// jumps and calls so that those that depend on a load or a
+    /// multiplication come as late as their dependencies allow, with
+    /// independent ones in between. Instructions keep their order with
+    /// those they share a register, the flags or memory with; see
+    /// [`Asm::check_schedule`]. The number of instructions that moved.
+    pub fn schedule(&mut self) -> usize {
+        let before = cfg!(debug_assertions).then(|| self.instrs.clone());
+        let mut moved = 0;
+        let mut start = 0;
+        while start < self.instrs.len() {
+            // the region and its instructions, each with the comments before it
+            let (mut units, mut accessed) = (Vec::new(), Vec::new());
+            let mut end = start;
+            while let Some(instr) = self.instrs.get(end) {
+                if let Instr::Comment(_) = instr {
+                    end += 1;
+                    continue;
+                }
+                let Some(access) = accesses(instr) else { break };
+                units.push(end + 1);
+                accessed.push(access);
+                end += 1;
+            }
+            // comments before the end of the region stay there
+            let end = units.last().copied().unwrap_or(start);
+            if units.len() > 1 {
+                let latencies: Vec<_> =
+                    units.iter().map(|i| latency(&self.instrs[i - 1])).collect();
+                let order = list_schedule(&accessed, &latencies);
+                let ranges: Vec<_> = (0..units.len())
+                    .map(|u| (if u == 0 { start } else { units[u - 1] })..units[u])
+                    .collect();
+                let region: Vec<_> = order
+                    .iter()
+                    .flat_map(|u| self.instrs[ranges[*u].clone()].iter().cloned())
+                    .collect();
+                moved += order.iter().enumerate().filter(|(at, u)| at != *u).count();
+                self.instrs.splice(start..end, region);
+            }
+            // the instruction that ends the region, and the comments before it
+            start = end;
+            while let Some(instr) = self.instrs.get(start) {
+                start += 1;
+                if !matches!(instr, Instr::Comment(_)) && accesses(instr).is_none() {
+                    break;
+                }
+            }
+        }
+        if let Some(before) = before {
+            if let Err(problem) = self.check_schedule(&before) {
+                panic!("instruction scheduling broke a dependency: {}", problem);
+            }
+        }
+        moved
+    }
+
+    /// Checks that the instructions are those of `before`, with the
+    /// instructions between labels, jumps and calls reordered only as far as
+    /// they do not read or write a register, the flags or memory that
+    /// another one on the way writes. Comments are ignored. What went wrong
+    /// otherwise.
+    pub fn check_schedule(&self, before: &[Instr]) -> Result<(), String> {
+        let show = |instr: &Instr| instr_to_string(instr, &self.symbols).trim().to_string();
+        let code = |instrs: &[Instr]| -> Vec<Instr> {
+            instrs.iter().filter(|instr| !matches!(instr, Instr::Comment(_))).cloned().collect()
+        };
+        let (before, after) = (code(before), code(&self.instrs));
+        let (mut i, mut j) = (0, 0);
+        loop {
+            let region = |instrs: &[Instr], from: usize| {
+                let len = instrs[from..].iter().position(|instr| accesses(instr).is_none());
+                from + len.unwrap_or(instrs.len() - from)
+            };
+            let (end, after_end) = (region(&before, i), region(&after, j));
+            let (old, new) = (&before[i..end], &after[j..after_end]);
+            // the k-th copy of an instruction is the k-th one before, since
+            // copies depend on each other
+            let mut used = vec![false; old.len()];
+            let mut at = vec![0; old.len()];
+            for (n, instr) in new.iter().enumerate() {
+                let Some(o) = (0..old.len()).find(|o| !used[*o] && old[*o] == *instr) else {
+                    return Err(format!("`{}` is not in the code before", show(instr)));
+                };
+                used[o] = true;
+                at[o] = n;
+            }
+            if let Some(o) = used.iter().position(|used| !used) {
+                return Err(format!("`{}` is missing", show(&old[o])));
+            }
+            let accessed: Vec<_> = old.iter().map(|instr| accesses(instr).unwrap()).collect();
+            for a in 0..old.len() {
+                for b in a + 1..old.len() {
+                    if at[b] < at[a] && depends(&accessed[a], &accessed[b]) {
+                        return Err(format!(
+                            "`{}` moved before `{}`, which it depends on",
+                            show(&old[b]),
+                            show(&old[a])
+                        ));
+                    }
+                }
+            }
+            match (before.get(end), after.get(after_end)) {
+                (None, None) => return Ok(()),
+                (Some(x), Some(y)) if x == y => (i, j) = (end + 1, after_end + 1),
+                (x, y) => {
+                    let show = |instr: Option<&Instr>| instr.map_or("the end".to_string(), show);
+                    return Err(format!("`{}` is now `{}`", show(x), show(y)));
+                }
+            }
+        }
+    }
+}
+
+/* ---------------------------- Stack discipline ---------------------------- */
+
+/// What `Asm::check_stack` knows at a label: the bytes that rsp is below
+/// where it was on entry to the function, and the slots below that which
+/// every path to the label writes, by their offset from it
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct StackState {
+    depth: i32,
+    written: BTreeSet<i32>,
+}
+
+/// The checks of `Asm::check_stack`, which walk the code from each label
+/// that a path reaches up to where the path leaves it
+struct StackChecker<'a> {
+    asm: &'a Asm,
+    /// Where each label is defined
+    at: HashMap<Label, usize>,
+    /// The labels that are called or exported, which are entered as the
+    /// calling convention leaves the stack
+    functions: HashSet<Label>,
+    /// The targets of the jump table at each label
+    tables: HashMap<Label, Vec<Label>>,
+    /// The symbols whose calls do not return
+    no_return: HashSet<Label>,
+    states: HashMap<Label, StackState>,
+    work: Vec<Label>,
+    problems: BTreeSet<String>,
+}
+
+impl StackChecker<'_> {
+    fn show(&self, instr: &Instr) -> String {
+        instr_to_string(instr, &self.asm.symbols).trim().to_string()
+    }
+
+    /// Joins `state` into that of `label`, which the code of `from` goes to
+    fn reach(&mut self, label: Label, state: &StackState, from: Label) {
+        let name = |label: Label| self.asm.symbols.name(label);
+        if self.functions.contains(&label) || !self.at.contains_key(&label) {
+            // a tail call, also to an extern, which leaves the frame as a
+            // return does
+            if state.depth != 0 {
+                self.problems.insert(format!(
+                    "{} goes to the function {} with rsp {} bytes below its entry",
+                    name(from),
+                    name(label),
+                    state.depth
+                ));
+            }
+            return;
+        }
+        match self.states.get_mut(&label) {
+            None => {
+                self.states.insert(label, state.clone());
+                self.work.push(label);
+            }
+            Some(old) if old.depth != state.depth => {
+                let problem = format!(
+                    "{} is reached from {} with rsp {} bytes below the entry, and elsewhere {}",
+                    name(label),
+                    name(from),
+                    state.depth,
+                    old.depth
+                );
+                self.problems.insert(problem);
+            }
+            Some(old) => {
+                let written: BTreeSet<i32> =
+                    old.written.intersection(&state.written).copied().collect();
+                if written != old.written {
+                    old.written = written;
+                    self.work.push(label);
+                }
+            }
+        }
+    }
+
+    /// Checks that the slot at `offset` from rsp is written before `instr` reads it
+    fn read(&mut self, state: &StackState, offset: i32, instr: &Instr, label: Label) {
+        let slot = offset - state.depth;
+        if slot < 0 && !state.written.contains(&slot) {
+            self.problems.insert(format!(
+                "`{}` in {} reads the slot {} bytes below the entry before some path writes it",
+                self.show(instr),
+                self.asm.symbols.name(label),
+                -slot
+            ));
+        }
+    }
+
+    /// Follows the code after `label` until the path leaves it
+    fn walk(&mut self, label: Label) {
+        let asm = self.asm;
+        let name = asm.symbols.name(label);
+        let mut state = self.states[&label].clone();
+        let mut table = None;
+        for instr in asm.instrs[self.at[&label] + 1..].iter() {
+            match instr {
+                Instr::Label(next) => return self.reach(*next, &state, label),
+                Instr::Call(callee) => {
+                    let off = (8 - state.depth).rem_euclid(16);
+                    if off != 0 {
+                        self.problems.insert(format!(
+                            "`{}` in {} is made with rsp {} bytes off a multiple of 16",
+                            self.show(instr),
+                            name,
+                            off
+                        ));
+                    }
+                    if self.no_return.contains(callee) {
+                        return;
+                    }
+                    // the callee overwrites what is below rsp
+                    let rsp = -state.depth;
+                    state.written.retain(|slot| *slot >= rsp);
+                }
+                Instr::Ret => {
+                    if state.depth != 0 {
+                        self.problems.insert(format!(
+                            "`ret` in {} leaves rsp {} bytes below the entry",
+                            name, state.depth
+                        ));
+                    }
+                    return;
+                }
+                Instr::Jmp(target) => return self.reach(*target, &state, label),
+                Instr::JCC(_, target) => self.reach(*target, &state, label),
+                Instr::JmpReg(_) => {
+                    match table.and_then(|table| self.tables.get(&table)) {
+                        Some(targets) => {
+                            for target in targets.clone() {
+                                self.reach(target, &state, label);
+                            }
+                        }
+                        None => {
+                            self.problems.insert(format!(
+                                "`{}` in {} has no jump table to go by",
+                                self.show(instr),
+                                name
+                            ));
+                        }
+                    }
+                    return;
+                }
+                Instr::Lea(_, target) if self.tables.contains_key(target) => table = Some(*target),
+                Instr::Sub(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(n))) => state.depth += n,
+                Instr::Add(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(n))) => state.depth -= n,
+                Instr::Push(arg) => {
+                    if let Arg32::Mem(MemRef { reg: Reg::Rsp, offset }) = arg {
+                        self.read(&state, *offset, instr, label);
+                    }
+                    state.depth += 8;
+                    state.written.insert(-state.depth);
+                }
+                Instr::Pop(loc) => {
+                    self.read(&state, 0, instr, label);
+                    state.depth -= 8;
+                    if let Loc::Mem(MemRef { reg: Reg::Rsp, offset }) = loc {
+                        state.written.insert(offset - state.depth);
+                    }
+                }
+                _ if !continues(instr) => return,
+                _ => {
+                    let Some((reads, writes)) = accesses(instr) else { continue };
+                    for read in reads {
+                        if let Resource::Stack(offset) = read {
+                            self.read(&state, offset, instr, label);
+                        }
+                    }
+                    for write in writes {
+                        if let Resource::Stack(offset) = write {
+                            let slot = offset - state.depth;
+                            if slot < 0 {
+                                state.written.insert(slot);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Asm {
+    /// Checks the stack discipline of the code on every path from the
+    /// labels that are called or exported, which are entered with rsp 8
+    /// bytes off a multiple of 16 as in the System V calling convention:
+    /// pushes, pops and the moves of rsp balance at each return and jump to
+    /// such a label, paths that join have moved rsp alike, each call is made
+    /// with rsp on a multiple of 16, and no slot below where rsp was on entry
+    /// is read before every path there writes it. A call overwrites what is
+    /// below rsp; the slots above the entry are the caller's.
+    ///
+    /// Calls to the symbols named in `no_return` end their path, and jumps
+    /// through a register go to the targets of the last jump table whose
+    /// address was loaded. The problems otherwise, each with its label.
+    pub fn check_stack(&self, no_return: &[&str]) -> Result<(), Vec<String>> {
+        let mut at = HashMap::new();
+        let mut tables: HashMap<Label, Vec<Label>> = HashMap::new();
+        for (i, instr) in self.instrs.iter().enumerate() {
+            if let Instr::Label(label) = instr {
+                at.insert(*label, i);
+                let targets: Vec<Label> = (self.instrs[i + 1..].iter())
+                    .map_while(|instr| match instr {
+                        Instr::RelOffset(target, _) => Some(*target),
+                        _ => None,
+                    })
+                    .collect();
+                if !targets.is_empty() {
+                    tables.insert(*label, targets);
+                }
+            }
+        }
+        let mut functions = Vec::new();
+        for instr in self.instrs.iter() {
+            if let Instr::Call(label) | Instr::Global(label) = instr {
+                if at.contains_key(label) && !functions.contains(label) {
+                    functions.push(*label);
+                }
+            }
+        }
+        let entry = StackState {
+            depth: 0,
+            written: BTreeSet::new(),
+        };
+        let mut checker = StackChecker {
+            asm: self,
+            at,
+            functions: functions.iter().copied().collect(),
+            tables,
+            no_return: no_return.iter().filter_map(|name| self.symbols.get(name)).collect(),
+            states: functions.iter().map(|f| (*f, entry.clone())).collect(),
+            work: functions.into_iter().rev().collect(),
+            problems: BTreeSet::new(),
+        };
+        while let Some(label) = checker.work.pop() {
+            checker.walk(label);
+        }
+        match checker.problems.is_empty() {
+            true => Ok(()),
+            false => Err(checker.problems.into_iter().collect()),
+        }
+    }
+}
+
+/* ------------------------------ Unwind tables ----------------------------- */
+
+/// The number of `reg` in the DWARF register mapping of x86-64
+fn dwarf_reg(reg: Reg) -> u8 {
+    match reg {
+        Reg::Rax => 0,
+        Reg::Rdx => 1,
+        Reg::Rcx => 2,
+        Reg::Rbx => 3,
+        Reg::Rsi => 4,
+        Reg::Rdi => 5,
+        Reg::Rbp => 6,
+        Reg::Rsp => 7,
+        Reg::R8 => 8,
+        Reg::R9 => 9,
+        Reg::R10 => 10,
+        Reg::R11 => 11,
+        Reg::R12 => 12,
+        Reg::R13 => 13,
+        Reg::R14 => 14,
+        Reg::R15 => 15,
+    }
+}
+
+/// `n` in the unsigned LEB128 encoding of DWARF
+fn uleb128(mut n: u64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// The call frame instructions of DWARF that the unwind tables use
+const DW_CFA_ADVANCE_LOC4: u8 = 0x04;
+const DW_CFA_SAME_VALUE: u8 = 0x08;
+const DW_CFA_DEF_CFA: u8 = 0x0c;
+const DW_CFA_DEF_CFA_OFFSET: u8 = 0x0e;
+const DW_CFA_OFFSET: u8 = 0x80;
+/// The register that holds the return address in the tables of x86-64
+const RETURN_ADDRESS: u8 = 16;
+
+/// The frame description entry of a section of code, as it is built
+struct Fde {
+    start: Label,
+    /// The position of the last row
+    row: Label,
+    /// Whether code follows the last row
+    advanced: bool,
+    /// The bytes of rsp above the canonical frame address
+    cfa: i32,
+    instrs: Vec<Instr>,
+    len: usize,
+}
+
+impl Fde {
+    fn push(&mut self, instr: Instr) {
+        self.len += instr_size(&instr);
+        self.instrs.push(instr);
+    }
+
+    /// Starts a row at the end of `code`, unless the last one is there
+    fn row(&mut self, code: &mut Vec<Instr>, symbols: &mut Symbols) {
+        if self.advanced {
+            let here = symbols.fresh("cfi");
+            code.push(Instr::Label(here));
+            self.push(Instr::Bytes(vec![DW_CFA_ADVANCE_LOC4]));
+            self.push(Instr::RelOffset(here, self.row));
+            self.row = here;
+            self.advanced = false;
+        }
+    }
+
+    /// Sets the canonical frame address to `cfa` bytes above rsp at the end of `code`
+    fn def_cfa(&mut self, cfa: i32, code: &mut Vec<Instr>, symbols: &mut Symbols) {
+        if cfa != self.cfa {
+            self.row(code, symbols);
+            let mut bytes = vec![DW_CFA_DEF_CFA_OFFSET];
+            bytes.extend(uleb128(cfa as u64));
+            self.push(Instr::Bytes(bytes));
+            self.cfa = cfa;
+        }
+    }
+}
+
+impl Asm {
+    /// Appends the unwind tables of the code as an `.eh_frame` section of
+    /// ELF, which nasm cannot write from directives, for the unwinders of
+    /// profilers and debuggers to find the callers without frame pointers.
+    ///
+    /// Each section of code gets an entry. The canonical frame address is
+    /// 8 bytes above rsp, past the return address, at every label and until
+    /// rsp moves; the `Cfi` pseudo-instructions of the code generator tell
+    /// where the non-volatile registers are saved.
+    pub fn add_unwind_tables(&mut self) {
+        // the common information entry: version 1, the augmentation "zR",
+        // code and data alignments 1 and -8, the return address register,
+        // the FDE pointers relative to themselves as signed DWORDs, and the
+        // return address below the canonical frame address at rsp + 8
+        let mut cie = vec![0, 0, 0, 0, 1, b'z', b'R', 0, 1, 0x78, RETURN_ADDRESS, 1, 0x1b];
+        cie.extend([DW_CFA_DEF_CFA, dwarf_reg(Reg::Rsp), 8]);
+        cie.extend([DW_CFA_OFFSET | RETURN_ADDRESS, 1]);
+        cie.resize((cie.len() + 4).next_multiple_of(8) - 4, 0);
+        let mut tables = vec![Instr::Bytes((cie.len() as u32).to_le_bytes().to_vec())];
+        let mut offset = 4 + cie.len();
+        tables.push(Instr::Bytes(cie));
+
+        let mut code = Vec::new();
+        let mut fde: Option<Fde> = None;
+        let instrs = std::mem::take(&mut self.instrs);
+        for instr in instrs.into_iter().chain([Instr::Section(String::new())]) {
+            match (&instr, &mut fde) {
+                (Instr::Section(_), _) => {
+                    if let Some(fde) = fde.take() {
+                        let end = self.symbols.fresh("cfi");
+                        code.push(Instr::Label(end));
+                        let mut entry = Vec::new();
+                        let len = 4 + 4 + 4 + 1 + fde.len;
+                        let pad = (4 + len).next_multiple_of(8) - 4 - len;
+                        entry.push(Instr::Bytes(((len + pad) as u32).to_le_bytes().to_vec()));
+                        // the distance back to the common information entry
+                        entry.push(Instr::Bytes(((offset + 4) as u32).to_le_bytes().to_vec()));
+                        entry.push(Instr::PcRel(fde.start));
+                        entry.push(Instr::RelOffset(end, fde.start));
+                        // no augmentation data
+                        entry.push(Instr::Bytes(vec![0]));
+                        entry.extend(fde.instrs);
+                        entry.push(Instr::Bytes(vec![0; pad]));
+                        offset += 4 + len + pad;
+                        tables.extend(entry);
+                    }
+                    let Instr::Section(name) = &instr else { unreachable!() };
+                    if name.is_empty() {
+                        break;
+                    }
+                    code.push(instr.clone());
+                    if name.starts_with(".text") {
+                        let start = self.symbols.fresh("cfi");
+                        code.push(Instr::Label(start));
+                        let fde_start = Fde {
+                            start,
+                            row: start,
+                            advanced: false,
+                            cfa: 8,
+                            instrs: Vec::new(),
+                            len: 0,
+                        };
+                        fde = Some(fde_start);
+                    }
+                }
+                (_, None) => code.push(instr),
+                (Instr::Label(_), Some(fde)) => {
+                    fde.def_cfa(8, &mut code, &mut self.symbols);
+                    code.push(instr);
+                }
+                (Instr::Cfi(cfi), Some(fde)) => {
+                    fde.row(&mut code, &mut self.symbols);
+                    let bytes = match cfi {
+                        Cfi::Offset(reg, n) => {
+                            let mut bytes = vec![DW_CFA_OFFSET | dwarf_reg(*reg)];
+                            bytes.extend(uleb128(*n as u64 / 8));
+                            bytes
+                        }
+                        Cfi::SameValue(reg) => vec![DW_CFA_SAME_VALUE, dwarf_reg(*reg)],
+                    };
+                    fde.push(Instr::Bytes(bytes));
+                    code.push(instr);
+                }
+                (_, Some(fde)) => {
+                    let cfa = match &instr {
+                        Instr::Sub(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(n))) => fde.cfa + n,
+                        Instr::Add(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(n))) => fde.cfa - n,
+                        Instr::Push(_) => fde.cfa + 8,
+                        Instr::Pop(_) => fde.cfa - 8,
+                        _ => fde.cfa,
+                    };
+                    fde.advanced |= instr_size(&instr) > 0;
+                    code.push(instr);
+                    fde.def_cfa(cfa, &mut code, &mut self.symbols);
+                }
+            }
+        }
+        self.instrs = code;
+        self.instrs
+            .push(Instr::Section(".eh_frame progbits alloc noexec nowrite align=8".to_string()));
+        self.instrs.extend(tables);
+    }
+}
+
+/* ---------------------------------- Sizes --------------------------------- */
+
+/// The length in bytes of the machine code for `i`, as encoded by an
+/// optimizing assembler such as `nasm -Ox`, which picks the shortest form.
+///
+/// Jumps are counted at their near (rel32) size, since their distance is
+/// only known after layout; labels and directives take no space, and
+/// neither does alignment, whose padding depends on the layout as well.
+pub fn instr_size(i: &Instr) -> usize {
+    match i {
+        Instr::Mov(MovArgs::ToReg(r, Arg64::Signed(n))) => mov_imm_size(*r, *n as i128),
+        Instr::Mov(MovArgs::ToReg(r, Arg64::Unsigned(n))) => mov_imm_size(*r, *n as i128),
+        Instr::Mov(MovArgs::ToReg(_, Arg64::Reg(_))) => 3,
+        Instr::Mov(MovArgs::ToReg(_, Arg64::Mem(m))) => 3 + mem_size(m),
+        Instr::Mov(MovArgs::ToMem(m, Reg32::Reg(_))) => 3 + mem_size(m),
+        Instr::Mov(MovArgs::ToMem(m, Reg32::Imm(_))) => 3 + mem_size(m) + 4,
+        Instr::Add(args)
+        | Instr::Sub(args)
+        | Instr::And(args)
+        | Instr::Or(args)
+        | Instr::Xor(args)
+        | Instr::Cmp(args) => alu_size(args),
+        Instr::IMul(BinArgs::ToReg(_, arg)) => match arg {
+            Arg32::Reg(_) => 4,
+            Arg32::Mem(m) => 4 + mem_size(m),
+            Arg32::Signed(n) if i8::try_from(*n).is_ok() => 4,
+            Arg32::Signed(_) | Arg32::Unsigned(_) => 7,
+        },
+        Instr::IMul(BinArgs::ToMem(m, _)) => 4 + mem_size(m),
+        Instr::Test(BinArgs::ToReg(r, arg)) => match arg {
+            Arg32::Reg(_) => 3,
+            Arg32::Mem(m) => 3 + mem_size(m),
+            Arg32::Signed(_) | Arg32::Unsigned(_) => {
+                if *r == Reg::Rax {
+                    6
+                } else {
+                    7
+                }
+            }
+        },
+        Instr::Test(BinArgs::ToMem(m, arg)) => match arg {
+            Reg32::Reg(_) => 3 + mem_size(m),
+            Reg32::Imm(_) => 3 + mem_size(m) + 4,
+        },
+        Instr::Sal(args) | Instr::Sar(args) | Instr::Shl(args) | Instr::Shr(args) => {
+            if args.by == 1 {
+                3
+            } else {
+                4
+            }
+        }
+        Instr::Xchg(r1, r2) => {
+            if *r1 == Reg::Rax || *r2 == Reg::Rax {
+                2
+            } else {
+                3
+            }
+        }
+        // rbp and r13 as bases take a zero displacement
+        Instr::Movsxd(_, m) => 4 + matches!(m.base, Reg::Rbp | Reg::R13) as usize,
+        Instr::Lea(..) => 7,
+        Instr::Push(arg) => match arg {
+            Arg32::Reg(r) => 1 + rex_size(*r),
+            Arg32::Signed(n) if i8::try_from(*n).is_ok() => 2,
+            Arg32::Signed(_) | Arg32::Unsigned(_) => 5,
+            Arg32::Mem(m) => 2 + rex_size(m.reg) + mem_size(m),
+        },
+        Instr::Pop(loc) => match loc {
+            Loc::Reg(r) => 1 + rex_size(*r),
+            Loc::Mem(m) => 2 + rex_size(m.reg) + mem_size(m),
+        },
+        Instr::Label(_)
+        | Instr::Comment(_)
+        | Instr::Section(_)
+        | Instr::Global(_)
+        | Instr::Extern(_)
+        | Instr::Align(_)
+        | Instr::Cfi(_) => 0,
+        Instr::Call(_) | Instr::Jmp(_) => 5,
+        Instr::Ret => 1,
+        Instr::JmpReg(r) => 2 + rex_size(*r),
+        Instr::RelOffset(..) | Instr::PcRel(_) => 4,
+        Instr::Bytes(bytes) => bytes.len(),
+        Instr::Quad(_) | Instr::QuadAddr(_) => 8,
+        Instr::Asciz(s) => s.len() + 1,
+        Instr::CMovCC(_, BinArgs::ToReg(_, Arg32::Mem(m))) => 4 + mem_size(m),
+        Instr::CMovCC(..) => 4,
+        Instr::JCC(..) => 6,
+        Instr::SetCC(_, r) => match r {
+            Reg8::Spl | Reg8::Bpl | Reg8::Sil | Reg8::Dil => 4,
+            Reg8::R8b
+            | Reg8::R9b
+            | Reg8::R10b
+            | Reg8::R11b
+            | Reg8::R12b
+            | Reg8::R13b
+            | Reg8::R14b
+            | Reg8::R15b => 4,
+            _ => 3,
+        },
+    }
+}
+
+/// Whether encoding `r` needs a REX prefix outside of 64-bit operations.
+fn rex_size(r: Reg) -> usize {
+    match r {
+        Reg::R8 | Reg::R9 | Reg::R10 | Reg::R11 | Reg::R12 | Reg::R13 | Reg::R14 | Reg::R15 => 1,
+        _ => 0,
+    }
+}
+
+/// The SIB byte and displacement of a memory operand, beyond the ModRM byte.
+fn mem_size(m: &MemRef) -> usize {
+    let sib = match m.reg {
+        Reg::Rsp | Reg::R12 => 1,
+        _ => 0,
+    };
+    let disp = match m.reg {
+        _ if i8::try_from(m.offset).is_err() => 4,
+        // rbp and r13 as bases always take a displacement
+        Reg::Rbp | Reg::R13 => 1,
+        _ if m.offset == 0 => 0,
+        _ => 1,
+    };
+    sib + disp
+}
+
+fn mov_imm_size(r: Reg, n: i128) -> usize {
+    if u32::try_from(n).is_ok() {
+        // mov r32, imm32, zero-extended
+        5 + rex_size(r)
+    } else if i32::try_from(n).is_ok() {
+        // mov r/m64, imm32, sign-extended
+        7
+    } else {
+        // movabs r64, imm64
+        10
+    }
+}
+
+fn alu_size(args: &BinArgs) -> usize {
+    match args {
+        BinArgs::ToReg(r, arg) => match arg {
+            Arg32::Reg(_) => 3,
+            Arg32::Mem(m) => 3 + mem_size(m),
+            Arg32::Signed(n) if i8::try_from(*n).is_ok() => 4,
+            Arg32::Signed(_) | Arg32::Unsigned(_) => {
+                if *r == Reg::Rax {
+                    6
+                } else {
+                    7
+                }
+            }
+        },
+        BinArgs::ToMem(m, arg) => match arg {
+            Reg32::Reg(_) => 3 + mem_size(m),
+            Reg32::Imm(n) if i8::try_from(*n).is_ok() => 3 + mem_size(m) + 1,
+            Reg32::Imm(_) => 3 + mem_size(m) + 4,
+        },
+    }
+}
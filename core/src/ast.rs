@@ -0,0 +1,517 @@
+use crate::bignum::Big;
+use crate::identifiers::*;
+pub use crate::span::*;
+pub use crate::types::*;
+
+/* --------------------------------- Aliases -------------------------------- */
+
+pub type SurfProg = Prog<String, String>;
+pub type SurfExtDecl = ExtDecl<String, String>;
+/// A parameter of an extern or a function, with the type that its signature
+/// declares
+pub type SurfParam = (Spanned<String>, Option<Type>);
+pub type SurfFunDecl = FunDecl<String, String>;
+pub type SurfBinding = Binding<String, String>;
+pub type SurfCase = Case<String, String>;
+pub type SurfEnumDecl = EnumDecl<String>;
+pub type SurfArm = Arm<String, String>;
+pub type SurfPattern = Pattern<String, String>;
+pub type SurfExpr = Expr<String, String>;
+
+pub type BoundProg = Prog<VarName, FunName>;
+pub type BoundExtDecl = ExtDecl<VarName, FunName>;
+pub type BoundFunDecl = FunDecl<VarName, FunName>;
+pub type BoundBinding = Binding<VarName, FunName>;
+pub type BoundCase = Case<VarName, FunName>;
+pub type BoundEnumDecl = EnumDecl<FunName>;
+pub type BoundArm = Arm<VarName, FunName>;
+pub type BoundPattern = Pattern<VarName, FunName>;
+pub type BoundExpr = Expr<VarName, FunName>;
+
+/* ----------------------------------- AST ---------------------------------- */
+
+#[derive(Clone, Debug)]
+pub struct Prog<Var, Fun> {
+    pub externs: Vec<ExtDecl<Var, Fun>>,
+    pub enums: Vec<EnumDecl<Fun>>,
+    /// The name of the main function. Should always be "main".
+    pub name: Fun,
+    /// A single parameter containing an array of commandline arguments.
+    pub param: Spanned<Var>,
+    pub body: Expr<Var, Fun>,
+    pub loc: SrcLoc,
+    /// The `##` comments before the main function, one line each.
+    pub doc: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Expr<Var, Fun> {
+    Num(i64, SrcLoc),
+    /// an integer literal beyond 64 bits, only accepted with `--bigint`
+    BigNum(Big, SrcLoc),
+    Bool(bool, SrcLoc),
+    Var(Var, SrcLoc),
+    // primitive operations
+    Prim {
+        prim: Prim,
+        args: Vec<Expr<Var, Fun>>,
+        loc: SrcLoc,
+    },
+    // let bindings
+    Let {
+        bindings: Vec<Binding<Var, Fun>>,
+        body: Box<Expr<Var, Fun>>,
+        loc: SrcLoc,
+    },
+    If {
+        cond: Box<Expr<Var, Fun>>,
+        thn: Box<Expr<Var, Fun>>,
+        els: Box<Expr<Var, Fun>>,
+        loc: SrcLoc,
+    },
+    // integer case analysis, falling back to the default
+    Switch {
+        scrutinee: Box<Expr<Var, Fun>>,
+        cases: Vec<Case<Var, Fun>>,
+        default: Box<Expr<Var, Fun>>,
+        loc: SrcLoc,
+    },
+    // case analysis of the objects of enums, by the first arm whose
+    // pattern matches
+    Match {
+        scrutinee: Box<Expr<Var, Fun>>,
+        arms: Vec<Arm<Var, Fun>>,
+        loc: SrcLoc,
+    },
+    // mutually recursive function definitions
+    FunDefs {
+        decls: Vec<FunDecl<Var, Fun>>,
+        body: Box<Expr<Var, Fun>>,
+        loc: SrcLoc,
+    },
+    // function calls
+    Call {
+        fun: Fun,
+        args: Vec<Expr<Var, Fun>>,
+        loc: SrcLoc,
+    },
+}
+
+impl<Var, Fun> Expr<Var, Fun> {
+    /// The span of the whole expression.
+    pub fn loc(&self) -> SrcLoc {
+        match self {
+            Expr::Num(_, loc) | Expr::BigNum(_, loc) | Expr::Bool(_, loc) | Expr::Var(_, loc) => {
+                *loc
+            }
+            Expr::Prim { loc, .. }
+            | Expr::Let { loc, .. }
+            | Expr::If { loc, .. }
+            | Expr::Switch { loc, .. }
+            | Expr::Match { loc, .. }
+            | Expr::FunDefs { loc, .. }
+            | Expr::Call { loc, .. } => *loc,
+        }
+    }
+
+    pub fn loc_mut(&mut self) -> &mut SrcLoc {
+        match self {
+            Expr::Num(_, loc) | Expr::BigNum(_, loc) | Expr::Bool(_, loc) | Expr::Var(_, loc) => {
+                loc
+            }
+            Expr::Prim { loc, .. }
+            | Expr::Let { loc, .. }
+            | Expr::If { loc, .. }
+            | Expr::Switch { loc, .. }
+            | Expr::Match { loc, .. }
+            | Expr::FunDefs { loc, .. }
+            | Expr::Call { loc, .. } => loc,
+        }
+    }
+
+    /// The first subexpression nested more than `limit` deep, where each
+    /// binding of a `let` nests its body. Found with a worklist, so that it
+    /// rejects programs that the recursive passes cannot take.
+    pub fn nested_beyond(&self, limit: usize) -> Option<&Self> {
+        let mut stack = vec![(self, 0)];
+        while let Some((e, depth)) = stack.pop() {
+            if depth > limit {
+                return Some(e);
+            }
+            match e {
+                Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => {}
+                Expr::Prim { args, .. } | Expr::Call { args, .. } => {
+                    stack.extend(args.iter().map(|arg| (arg, depth + 1)));
+                }
+                Expr::Let { bindings, body, .. } => {
+                    let nested = bindings.iter().enumerate();
+                    stack.extend(nested.map(|(i, b)| (&b.expr, depth + i + 1)));
+                    stack.push((body, depth + bindings.len().max(1)));
+                }
+                Expr::If { cond, thn, els, .. } => {
+                    stack.extend([cond, thn, els].map(|e| (&**e, depth + 1)));
+                }
+                Expr::Switch { scrutinee, cases, default, .. } => {
+                    stack.extend(cases.iter().map(|case| (&case.body, depth + 1)));
+                    stack.extend([(&**scrutinee, depth + 1), (&**default, depth + 1)]);
+                }
+                Expr::Match { scrutinee, arms, .. } => {
+                    stack.extend(arms.iter().map(|arm| (&arm.body, depth + 1)));
+                    stack.push((scrutinee, depth + 1));
+                }
+                Expr::FunDefs { decls, body, .. } => {
+                    stack.extend(decls.iter().map(|decl| (&decl.body, depth + 1)));
+                    stack.push((body, depth + 1));
+                }
+            }
+        }
+        None
+    }
+
+    /// The subexpressions of `self`, in the order they are evaluated, with the
+    /// bodies of functions before the body of their definitions
+    pub fn children_mut(&mut self) -> Vec<&mut Self> {
+        match self {
+            Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => Vec::new(),
+            Expr::Prim { args, .. } | Expr::Call { args, .. } => args.iter_mut().collect(),
+            Expr::Let { bindings, body, .. } => {
+                let exprs = bindings.iter_mut().map(|b| &mut b.expr);
+                exprs.chain([&mut **body]).collect()
+            }
+            Expr::If { cond, thn, els, .. } => vec![&mut **cond, &mut **thn, &mut **els],
+            Expr::Switch { scrutinee, cases, default, .. } => {
+                let bodies = cases.iter_mut().map(|case| &mut case.body);
+                [&mut **scrutinee].into_iter().chain(bodies).chain([&mut **default]).collect()
+            }
+            Expr::Match { scrutinee, arms, .. } => {
+                let bodies = arms.iter_mut().map(|arm| &mut arm.body);
+                [&mut **scrutinee].into_iter().chain(bodies).collect()
+            }
+            Expr::FunDefs { decls, body, .. } => {
+                let bodies = decls.iter_mut().map(|decl| &mut decl.body);
+                bodies.chain([&mut **body]).collect()
+            }
+        }
+    }
+
+    /// Moves every span of `self` `by` bytes later, for an expression parsed
+    /// out of a part of the source
+    pub fn shift(&mut self, by: usize) {
+        let mut stack = vec![self];
+        while let Some(e) = stack.pop() {
+            e.loc_mut().shift(by);
+            match e {
+                Expr::Let { bindings, .. } => {
+                    for binding in bindings.iter_mut() {
+                        binding.var.loc.shift(by);
+                        for elem in binding.elems.iter_mut().flatten() {
+                            elem.loc.shift(by);
+                        }
+                    }
+                }
+                Expr::Switch { cases, .. } => {
+                    for case in cases.iter_mut() {
+                        case.label.loc.shift(by);
+                    }
+                }
+                Expr::Match { arms, .. } => {
+                    let mut patterns: Vec<_> =
+                        arms.iter_mut().map(|arm| &mut arm.pattern).collect();
+                    while let Some(pattern) = patterns.pop() {
+                        match pattern {
+                            Pattern::Wildcard(loc) | Pattern::Var(_, loc) => loc.shift(by),
+                            Pattern::Ctor { args, loc, .. } => {
+                                loc.shift(by);
+                                patterns.extend(args.iter_mut());
+                            }
+                        }
+                    }
+                }
+                Expr::FunDefs { decls, .. } => {
+                    for decl in decls.iter_mut() {
+                        decl.loc.shift(by);
+                        for param in decl.params.iter_mut() {
+                            param.loc.shift(by);
+                        }
+                    }
+                }
+                Expr::Num(..)
+                | Expr::BigNum(..)
+                | Expr::Bool(..)
+                | Expr::Var(..)
+                | Expr::Prim { .. }
+                | Expr::If { .. }
+                | Expr::Call { .. } => {}
+            }
+            stack.extend(e.children_mut());
+        }
+    }
+
+    /// Drops `self` a node at a time, where dropping the boxes of a deeply
+    /// nested expression would recurse as deep
+    pub fn dismantle(self) {
+        let mut stack = vec![self];
+        while let Some(e) = stack.pop() {
+            match e {
+                Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => {}
+                Expr::Prim { args, .. } | Expr::Call { args, .. } => stack.extend(args),
+                Expr::Let { bindings, body, .. } => {
+                    stack.extend(bindings.into_iter().map(|b| b.expr));
+                    stack.push(*body);
+                }
+                Expr::If { cond, thn, els, .. } => stack.extend([*cond, *thn, *els]),
+                Expr::Switch { scrutinee, cases, default, .. } => {
+                    stack.extend(cases.into_iter().map(|case| case.body));
+                    stack.extend([*scrutinee, *default]);
+                }
+                Expr::Match { scrutinee, arms, .. } => {
+                    stack.extend(arms.into_iter().map(|arm| arm.body));
+                    stack.push(*scrutinee);
+                }
+                Expr::FunDefs { decls, body, .. } => {
+                    stack.extend(decls.into_iter().map(|decl| decl.body));
+                    stack.push(*body);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExtDecl<Var, Fun> {
+    pub name: Fun,
+    /// The parameters of an external declaration are merely
+    /// used for pretty-printing purposes.
+    pub params: Vec<Spanned<Var>>,
+    pub signature: Signature,
+    pub contract: Contract,
+    pub loc: SrcLoc,
+    pub doc: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Binding<Var, Fun> {
+    pub var: Spanned<Var>,
+    pub expr: Expr<Var, Fun>,
+    /// The type annotated after the variable, `let x: Int = expr`
+    pub ty: Option<Type>,
+    /// The variables of a destructuring `let [a, b] = expr`, bound to the
+    /// elements of the array, which `var` names as a whole
+    pub elems: Option<Vec<Spanned<Var>>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Case<Var, Fun> {
+    pub label: Spanned<i64>,
+    pub body: Expr<Var, Fun>,
+}
+
+/// `enum Shape: Circle(r) | Rect(w, h)`. The constructors of the variants
+/// are called like functions, and allocate an object of the tag of their
+/// variant followed by their arguments, laid out as an array.
+#[derive(Clone, Debug)]
+pub struct EnumDecl<Fun> {
+    pub name: String,
+    pub variants: Vec<Variant<Fun>>,
+    pub loc: SrcLoc,
+    pub doc: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Variant<Fun> {
+    /// The constructor
+    pub name: Fun,
+    /// The names of the fields, which only document them
+    pub fields: Vec<Spanned<String>>,
+    pub loc: SrcLoc,
+}
+
+#[derive(Clone, Debug)]
+pub struct Arm<Var, Fun> {
+    pub pattern: Pattern<Var, Fun>,
+    pub body: Expr<Var, Fun>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Pattern<Var, Fun> {
+    /// `_`
+    Wildcard(SrcLoc),
+    /// A variable, bound to whatever it matches. In the surface syntax, the
+    /// name of a constructor without fields stands for `Ctor()` instead.
+    Var(Var, SrcLoc),
+    Ctor {
+        ctor: Fun,
+        args: Vec<Pattern<Var, Fun>>,
+        loc: SrcLoc,
+    },
+}
+
+impl<Var, Fun> Pattern<Var, Fun> {
+    pub fn loc(&self) -> SrcLoc {
+        match self {
+            Pattern::Wildcard(loc) | Pattern::Var(_, loc) | Pattern::Ctor { loc, .. } => *loc,
+        }
+    }
+
+    /// The variables that the pattern binds, from left to right
+    pub fn vars(&self) -> Vec<&Var> {
+        match self {
+            Pattern::Wildcard(_) => vec![],
+            Pattern::Var(var, _) => vec![var],
+            Pattern::Ctor { args, .. } => args.iter().flat_map(Pattern::vars).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FunDecl<Var, Fun> {
+    pub name: Fun,
+    pub params: Vec<Spanned<Var>>,
+    /// The types annotated after the parameters and the arrow,
+    /// `def f(x: Int) -> Bool`, which the resolver checks
+    pub signature: Signature,
+    pub body: Expr<Var, Fun>,
+    pub loc: SrcLoc,
+    pub doc: Option<String>,
+    /// Whether the function is a generator, `def gen`, whose calls create a
+    /// generator object that `next` runs up to the following `yield`
+    pub gen: bool,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum Prim {
+    // unary arithmetic
+    Add1,
+    Sub1,
+    // binary arithmetic
+    Add,
+    Sub,
+    Mul,
+    // binary arithmetic that wraps around or clamps instead of overflowing
+    WrappingAdd,
+    WrappingSub,
+    WrappingMul,
+    SaturatingAdd,
+    SaturatingSub,
+    SaturatingMul,
+    // unary logical
+    Not,
+    // binary logical
+    And,
+    Or,
+    // binary comparison
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Neq,
+    // dynamic type checking
+    IsType(Type),
+    // the argument, after asserting that it has the type, for the
+    // annotations that the resolver cannot prove
+    Check(Type),
+    // optional values: the `none` sentinel, which no other value equals,
+    // and the test for it
+    None,
+    IsNone,
+    // a string literal, the same object wherever it is written, so that
+    // equal strings are equal words
+    Str(String),
+    // the string that `print` shows of a value, and the string of two
+    // values shown one after the other, which interpolation desugars into
+    ToStr,
+    Concat,
+    // array
+    NewArray,
+    MakeArray,
+    ArrayGet,
+    ArraySet,
+    Length,
+    // runtime services; strings are arrays of bytes
+    GetEnv,
+    Clock,
+    Random,
+    // generators, compiled away by `generators` after resolution
+    Yield,
+    Next,
+}
+
+/* --------------------------------- Strings -------------------------------- */
+
+/// The string of the literal `lit`, without its quotes, where `\\`, `\"`,
+/// `\n`, `\t`, `\{` and `\}` escape the characters; `None` for any other escape
+pub fn unescape(lit: &str) -> Option<String> {
+    let mut chars = lit.chars();
+    let mut s = String::new();
+    while let Some(c) = chars.next() {
+        s.push(match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                '"' => '"',
+                'n' => '\n',
+                't' => '\t',
+                '{' => '{',
+                '}' => '}',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+    Some(s)
+}
+
+/// A part of a string literal between its quotes
+pub enum Segment<'a> {
+    /// Text, still escaped
+    Text(&'a str),
+    /// The source of an expression interpolated between braces, without them
+    Interpolated(&'a str),
+}
+
+/// The segments of the string literal `lit`, without its quotes, each at
+/// its offset in `lit`. The grammar admits no braces in the interpolated
+/// expressions, so that the first closing brace ends each.
+pub fn segments(lit: &str) -> Vec<(usize, Segment<'_>)> {
+    let mut segments = Vec::new();
+    let (mut start, mut escaped) = (0, false);
+    for (ix, c) in lit.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => {
+                if start < ix {
+                    segments.push((start, Segment::Text(&lit[start..ix])));
+                }
+                start = ix + 1;
+            }
+            '}' => {
+                segments.push((start, Segment::Interpolated(&lit[start..ix])));
+                start = ix + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < lit.len() {
+        segments.push((start, Segment::Text(&lit[start..])));
+    }
+    segments
+}
+
+/// The literal of `s`, with its quotes, which `unescape` takes back
+pub fn escape(s: &str) -> String {
+    let mut lit = String::from('"');
+    for c in s.chars() {
+        match c {
+            '\\' => lit.push_str("\\\\"),
+            '"' => lit.push_str("\\\""),
+            '\n' => lit.push_str("\\n"),
+            '\t' => lit.push_str("\\t"),
+            '{' => lit.push_str("\\{"),
+            '}' => lit.push_str("\\}"),
+            c => lit.push(c),
+        }
+    }
+    lit.push('"');
+    lit
+}
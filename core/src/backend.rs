@@ -0,0 +1,3892 @@
+//! The backend of our compiler translates our intermediate
+//! representation into assembly code, mapping intermediate
+//! representation variables into concrete memory locations (registers
+//! or stack locations).
+
+use crate::ana::*;
+use crate::asm::*;
+use crate::conf::{BoolLowering, FramePointer};
+use crate::error::AllocError;
+use crate::identifiers::*;
+use crate::middle_end::CopyPropagator;
+use crate::ssa::*;
+use crate::types::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Liveness analysis is a simple iterative data flow analysis that determines
+/// which variables are live at a given point in the program.
+///
+/// It's a backwards dataflow analysis, meaning that the information
+/// flows in the reverse of the execution order.
+pub struct LivenessAnalyzer {
+    /// The live variables at the start of each block, in previous iteration.
+    /// Initially, this maps all BlockNames to the empty set
+    previous: HashMap<BlockName, HashSet<VarName>>,
+    /// The live variables at the start of each block, in current iteration.
+    current: HashMap<BlockName, HashSet<VarName>>,
+}
+
+impl LivenessAnalyzer {
+    pub fn new<T>(prog: &Program<VarName, T>) -> Self {
+        fn find_names_prog<T>(
+            p: &Program<VarName, T>,
+            blocks: &mut HashMap<BlockName, HashSet<VarName>>,
+        ) {
+            for block in p.blocks.iter() {
+                find_names_basic_block(&block, blocks);
+            }
+        }
+        fn find_names_block_body<T>(
+            b: &BlockBody<VarName, T>,
+            blocks: &mut HashMap<BlockName, HashSet<VarName>>,
+        ) {
+            use BlockBody::*;
+            match b {
+                Terminator(..) => {}
+                Operation { next, .. }
+                | AssertType { next, .. }
+                | AssertLength { next, .. }
+                | AssertInBounds { next, .. }
+                | Store { next, .. } => find_names_block_body(next, blocks),
+                SubBlocks {
+                    blocks: sub_blocks,
+                    next,
+                    ..
+                } => {
+                    for block in sub_blocks.iter() {
+                        find_names_basic_block(&block, blocks);
+                    }
+                    find_names_block_body(next, blocks)
+                }
+            }
+        }
+        fn find_names_basic_block<T>(
+            b: &BasicBlock<VarName, T>,
+            blocks: &mut HashMap<BlockName, HashSet<VarName>>,
+        ) {
+            blocks.insert(b.label.clone(), HashSet::new());
+            find_names_block_body(&b.body, blocks);
+        }
+        let mut previous = HashMap::new();
+        find_names_prog(prog, &mut previous);
+        Self {
+            previous,
+            current: HashMap::new(),
+        }
+    }
+
+    pub fn analyze<T>(mut self, prog: Program<VarName, T>) -> Program<VarName, LiveSet> {
+        //let mut prog = CopyPropagator::new().run(prog);
+        let mut prog = self.analyze_prog(prog);
+        while self.previous != self.current {
+            self.previous = std::mem::take(&mut self.current);
+            prog = self.analyze_prog(prog);
+        }
+        prog
+    }
+    pub fn get_ls(bbdy_ls: BlockBody<VarName, LiveSet>) -> LiveSet {
+        match bbdy_ls.clone() {
+            BlockBody::Terminator(_, ana) => ana.to_owned(),
+            BlockBody::Store { ana, .. }
+            | BlockBody::Operation { ana, .. }
+            | BlockBody::SubBlocks { ana, .. }
+            | BlockBody::AssertType { ana, .. }
+            | BlockBody::AssertLength { ana, .. }
+            | BlockBody::AssertInBounds { ana, .. } => ana.to_owned(),
+        }
+    }
+    pub fn analyze_block_body<T>(
+        &mut self,
+        b: &BlockBody<VarName, T>,
+    ) -> BlockBody<VarName, LiveSet> {
+        let mut ls = LiveSet::new();
+        let mut insert_var = |ls: &mut LiveSet, var: Immediate<VarName>| match var {
+            Immediate::Var(_var) => {
+                ls.insert(_var);
+            }
+            _ => {}
+        };
+        let mut union_blk = |ls: &mut LiveSet, target: &BlockName| {
+            if let Some(vars) = self.previous.get(target) {
+                for var in vars {
+                    ls.insert(var.clone());
+                }
+            }
+        };
+        match b {
+            BlockBody::Terminator(term, _) => match term {
+                Terminator::Return(r) => {
+                    insert_var(&mut ls, r.clone());
+                    BlockBody::Terminator(Terminator::Return(r.clone()), ls)
+                }
+                Terminator::Branch(Branch { target, args }) => {
+                    for arg in args {
+                        insert_var(&mut ls, arg.clone());
+                    }
+                    union_blk(&mut ls, target);
+                    BlockBody::Terminator(
+                        Terminator::Branch(Branch {
+                            target: target.clone(),
+                            args: args.to_vec(),
+                        }),
+                        ls,
+                    )
+                }
+                Terminator::ConditionalBranch { cond, thn, els } => {
+                    insert_var(&mut ls, cond.clone());
+                    union_blk(&mut ls, thn);
+                    union_blk(&mut ls, els);
+                    BlockBody::Terminator(
+                        Terminator::ConditionalBranch {
+                            cond: cond.clone(),
+                            thn: thn.clone(),
+                            els: els.clone(),
+                        },
+                        ls,
+                    )
+                }
+                Terminator::Switch {
+                    scrutinee,
+                    low,
+                    targets,
+                    default,
+                } => {
+                    insert_var(&mut ls, scrutinee.clone());
+                    for target in targets.iter().chain([default]) {
+                        union_blk(&mut ls, target);
+                    }
+                    BlockBody::Terminator(
+                        Terminator::Switch {
+                            scrutinee: scrutinee.clone(),
+                            low: *low,
+                            targets: targets.clone(),
+                            default: default.clone(),
+                        },
+                        ls,
+                    )
+                }
+            },
+            BlockBody::Operation { dest, op, next, .. } => {
+                match op {
+                    Operation::Prim1(_, imm) => {
+                        insert_var(&mut ls, imm.clone());
+                    }
+                    Operation::Prim2(_, imm1, imm2) => {
+                        insert_var(&mut ls, imm1.clone());
+                        insert_var(&mut ls, imm2.clone());
+                    }
+                    Operation::Call { fun, args } => {
+                        for arg in args {
+                            insert_var(&mut ls, arg.clone());
+                        }
+                    }
+                    Operation::Load { addr, offset } => {
+                        insert_var(&mut ls, addr.clone());
+                        insert_var(&mut ls, offset.clone());
+                    }
+                    Operation::Immediate(imm) => {
+                        insert_var(&mut ls, imm.clone());
+                    }
+                    Operation::AllocateArray { len } => {
+                        insert_var(&mut ls, len.clone());
+                    }
+                    Operation::Str(_) => {}
+                }
+                let next_bbdy = self.analyze_block_body(next.clone());
+                let mut next_ls = Self::get_ls(next_bbdy.clone());
+                ls.append(&mut next_ls);
+                ls.remove(dest);
+                BlockBody::Operation {
+                    dest: dest.clone(),
+                    op: op.clone(),
+                    next: Box::new(next_bbdy),
+                    ana: ls,
+                }
+            }
+            BlockBody::AssertType { ty, arg, next, .. } => {
+                let next_bbdy = self.analyze_block_body(next.clone());
+                let mut next_ls = Self::get_ls(next_bbdy.clone());
+                ls.append(&mut next_ls);
+                insert_var(&mut ls, arg.clone());
+                BlockBody::AssertType {
+                    ty: ty.clone(),
+                    arg: arg.clone(),
+                    next: Box::new(next_bbdy),
+                    ana: ls,
+                }
+            }
+            BlockBody::AssertLength { len, next, .. } => {
+                let next_bbdy = self.analyze_block_body(next.clone());
+                let mut next_ls = Self::get_ls(next_bbdy.clone());
+                ls.append(&mut next_ls);
+                insert_var(&mut ls, len.clone());
+                BlockBody::AssertLength {
+                    len: len.clone(),
+                    next: Box::new(next_bbdy),
+                    ana: ls,
+                }
+            }
+            BlockBody::AssertInBounds {
+                bound, arg, next, ..
+            } => {
+                let next_bbdy = self.analyze_block_body(next.clone());
+                let mut next_ls = Self::get_ls(next_bbdy.clone());
+                ls.append(&mut next_ls);
+                insert_var(&mut ls, bound.clone());
+                insert_var(&mut ls, arg.clone());
+                BlockBody::AssertInBounds {
+                    bound: bound.clone(),
+                    arg: arg.clone(),
+                    next: Box::new(next_bbdy),
+                    ana: ls,
+                }
+            }
+
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ..
+            } => {
+                let next_bbdy = self.analyze_block_body(next.clone());
+                let mut next_ls = Self::get_ls(next_bbdy.clone());
+                ls.append(&mut next_ls);
+                insert_var(&mut ls, addr.clone());
+                insert_var(&mut ls, offset.clone());
+                insert_var(&mut ls, val.clone());
+                BlockBody::Store {
+                    addr: addr.clone(),
+                    offset: offset.clone(),
+                    val: val.clone(),
+                    next: Box::new(next_bbdy),
+                    ana: ls,
+                }
+            }
+            BlockBody::SubBlocks {
+                blocks: sub_blocks,
+                next,
+                ..
+            } => {
+                let next_bbdy = self.analyze_block_body(next.clone());
+                let mut next_ls = Self::get_ls(next_bbdy.clone());
+                ls.append(&mut next_ls);
+                BlockBody::SubBlocks {
+                    blocks: sub_blocks
+                        .iter()
+                        .map(|block| {
+                            //subblock not branching to any
+                            //if let Some(vars) = self.previous.get(&block.label) {
+                            //    for var in vars {
+                            //        ls.insert(var.clone());
+                            //    }
+                            //}
+                            self.analyze_basic_block(block)
+                        })
+                        .collect(),
+                    next: Box::new(self.analyze_block_body(next)),
+                    ana: ls,
+                }
+            }
+        }
+    }
+    fn analyze_basic_block<T>(
+        &mut self,
+        b: &BasicBlock<VarName, T>,
+    ) -> BasicBlock<VarName, LiveSet> {
+        let mut bbdy_ls = self.analyze_block_body(&b.body);
+        let mut ls = Self::get_ls(bbdy_ls.clone());
+        for var in b.params.clone() {
+            ls.remove(&var);
+        }
+
+        let mut set: HashSet<VarName> = HashSet::new();
+        ls.iter().for_each(|var| {
+            set.insert(var.clone());
+        });
+        self.current.insert(b.label.clone(), set);
+        BasicBlock {
+            label: b.label.clone(),
+            params: b.params.clone(),
+            body: bbdy_ls,
+            ana: ls,
+        }
+    }
+    /// Analyzes the program, filling in the live sets. Uses the
+    /// information from self.previous for blocks in this round, but
+    /// also sets the information in self.current for the result of
+    /// this round.
+    fn analyze_prog<T>(&mut self, prog: Program<VarName, T>) -> Program<VarName, LiveSet> {
+        //this function will update self with each basic block
+
+        let mut prog_ls: Program<VarName, LiveSet> = Program {
+            externs: prog.externs,
+            funs: prog.funs,
+            blocks: vec![],
+        };
+        for block in prog.blocks {
+            prog_ls.blocks.push(self.analyze_basic_block(&block));
+        }
+        prog_ls
+    }
+}
+
+/// Queries on the liveness information attached by `LivenessAnalyzer`.
+///
+/// The `LiveSet` attached to a block body is its live-in set. Its live-out
+/// set is the live-in set of the block body that follows it, or, for a
+/// terminator, the union of the live-in sets of the blocks it may branch to.
+pub struct Liveness<'a> {
+    blocks: HashMap<BlockName, &'a BasicBlock<VarName, LiveSet>>,
+}
+
+impl<'a> Liveness<'a> {
+    pub fn new(prog: &'a Program<VarName, LiveSet>) -> Self {
+        fn collect_block_body<'a>(
+            b: &'a BlockBody<VarName, LiveSet>,
+            blocks: &mut HashMap<BlockName, &'a BasicBlock<VarName, LiveSet>>,
+        ) {
+            if let BlockBody::SubBlocks {
+                blocks: sub_blocks, ..
+            } = b
+            {
+                for block in sub_blocks.iter() {
+                    collect_basic_block(block, blocks);
+                }
+            }
+            if let Some(next) = b.successor() {
+                collect_block_body(next, blocks);
+            }
+        }
+        fn collect_basic_block<'a>(
+            b: &'a BasicBlock<VarName, LiveSet>,
+            blocks: &mut HashMap<BlockName, &'a BasicBlock<VarName, LiveSet>>,
+        ) {
+            blocks.insert(b.label.clone(), b);
+            collect_block_body(&b.body, blocks);
+        }
+        let mut blocks = HashMap::new();
+        for block in prog.blocks.iter() {
+            collect_basic_block(block, &mut blocks);
+        }
+        Self { blocks }
+    }
+
+    /// The variables live on entry to the block labeled `label`, excluding its parameters.
+    pub fn block_live_in(&self, label: &BlockName) -> Option<&'a LiveSet> {
+        self.blocks.get(label).map(|block| &block.ana)
+    }
+
+    /// The variables live right before `b` executes.
+    pub fn live_in<'b>(&self, b: &'b BlockBody<VarName, LiveSet>) -> &'b LiveSet {
+        b.analysis()
+    }
+
+    /// The variables live right after `b` executes.
+    pub fn live_out(&self, b: &BlockBody<VarName, LiveSet>) -> LiveSet {
+        match b {
+            BlockBody::Terminator(term, _) => {
+                let targets = match term {
+                    Terminator::Return(_) => vec![],
+                    Terminator::Branch(Branch { target, .. }) => vec![target],
+                    Terminator::ConditionalBranch { thn, els, .. } => vec![thn, els],
+                    Terminator::Switch {
+                        targets, default, ..
+                    } => targets.iter().chain([default]).collect(),
+                };
+                targets
+                    .into_iter()
+                    .filter_map(|target| self.block_live_in(target))
+                    .flat_map(|ls| ls.iter().cloned())
+                    .collect()
+            }
+            BlockBody::Operation { next, .. }
+            | BlockBody::SubBlocks { next, .. }
+            | BlockBody::AssertType { next, .. }
+            | BlockBody::AssertLength { next, .. }
+            | BlockBody::AssertInBounds { next, .. }
+            | BlockBody::Store { next, .. } => next.analysis().clone(),
+        }
+    }
+}
+
+/// Effect analysis over the call graph, telling which functions are pure,
+/// i.e. whose calls can be removed by `UnusedRemover` if their results are unused.
+///
+/// A function is pure if no block reachable from its body
+/// - stores into an array that it did not allocate itself,
+/// - calls an extern function with side effects (e.g. `print`),
+/// - calls a function that is not pure, or
+/// - may fail, i.e. asserts, runs arithmetic that may overflow, or calls
+///   `snake_error`, the big-integer arithmetic or an extern that may throw,
+///
+/// and it obviously terminates: the blocks reachable from its body form no
+/// loop and the functions it calls are not recursive, so that removing a
+/// call never removes the error or the divergence of the program. An extern
+/// is pure if its contract says that it is, returns and does not throw, e.g.
+/// `extern hash(x) pure(true) ret(always) throws(false)`.
+///
+/// Conservative mode does not take the contracts of the externs on trust:
+/// only the externs of the runtime known to be pure are.
+pub struct EffectAnalysis {
+    pub pure: HashSet<FunName>,
+}
+
+impl EffectAnalysis {
+    /// Extern functions that neither have side effects nor fail.
+    const PURE_EXTERNS: [&'static str; 8] = [
+        "snake_new_array",
+        "snake_equals",
+        "snake_not_equals",
+        "snake_getenv",
+        "snake_big_eq",
+        "snake_big_is_int",
+        "snake_to_str",
+        "snake_str_concat",
+    ];
+    pub fn new<T>(prog: &Program<VarName, T>, conservative: bool) -> Self {
+        fn collect_blocks<'a, T>(
+            block: &'a BasicBlock<VarName, T>,
+            blocks: &mut HashMap<&'a BlockName, &'a BasicBlock<VarName, T>>,
+        ) {
+            blocks.insert(&block.label, block);
+            let mut body = &block.body;
+            loop {
+                if let BlockBody::SubBlocks {
+                    blocks: sub_blocks, ..
+                } = body
+                {
+                    for block in sub_blocks.iter() {
+                        collect_blocks(block, blocks);
+                    }
+                }
+                match body.successor() {
+                    Some(next) => body = next,
+                    None => break,
+                }
+            }
+        }
+        let mut blocks = HashMap::new();
+        for block in prog.blocks.iter() {
+            collect_blocks(block, &mut blocks);
+        }
+        let mut analysis = Effects {
+            conservative,
+            funs: prog
+                .funs
+                .iter()
+                .map(|f| (&f.name, &f.body.target))
+                .collect(),
+            blocks,
+            externs: prog
+                .externs
+                .iter()
+                .map(|e| (&e.name, &e.contract))
+                .collect(),
+            state: HashMap::new(),
+        };
+        let mut pure = HashSet::new();
+        for fun in prog
+            .externs
+            .iter()
+            .map(|e| &e.name)
+            .chain(prog.funs.iter().map(|f| &f.name))
+        {
+            if analysis.visit_fun(fun) {
+                pure.insert(fun.clone());
+            }
+        }
+        Self { pure }
+    }
+
+    /// The functions whose calls obviously terminate, have no side effects
+    /// and cannot fail, so that code can be reordered across them without
+    /// changing the observable behavior, including which error is reported.
+    pub fn reorderable<T>(prog: &Program<VarName, T>) -> HashSet<FunName> {
+        Self::new(prog, false).pure
+    }
+}
+
+impl std::fmt::Display for EffectAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.pure.is_empty() {
+            write!(f, "(none)")
+        } else {
+            let mut pure: Vec<_> = self.pure.iter().map(|f| f.to_string()).collect();
+            pure.sort();
+            write!(f, "{}", pure.join(", "))
+        }
+    }
+}
+
+/// The working state of `EffectAnalysis`.
+struct Effects<'a, T> {
+    conservative: bool,
+    funs: HashMap<&'a FunName, &'a BlockName>,
+    blocks: HashMap<&'a BlockName, &'a BasicBlock<VarName, T>>,
+    externs: HashMap<&'a FunName, &'a Contract>,
+    /// `None` while the function is being visited, to detect recursion
+    state: HashMap<&'a FunName, Option<bool>>,
+}
+
+/// The state of a depth-first traversal of the blocks reachable from a function body.
+struct Region<'a> {
+    /// the blocks on the current path, to detect loops
+    path: HashSet<&'a BlockName>,
+    done: HashSet<&'a BlockName>,
+    /// the arrays allocated within the region
+    fresh: HashSet<&'a VarName>,
+    callees: Vec<&'a FunName>,
+}
+
+impl<'a, T> Effects<'a, T> {
+    /// Whether `fun` is pure.
+    fn visit_fun(&mut self, fun: &'a FunName) -> bool {
+        match self.state.get(fun) {
+            Some(Some(pure)) => return *pure,
+            // recursion, which may not terminate
+            Some(None) => return false,
+            None => {}
+        }
+        let pure = if let Some(contract) = self.externs.get(fun) {
+            EffectAnalysis::PURE_EXTERNS.contains(&fun.hint())
+                || (!self.conservative && contract.pure && contract.returns && contract.nothrow)
+        } else if let Some(target) = self.funs.get(fun).copied() {
+            self.state.insert(fun, None);
+            let mut region = Region {
+                path: HashSet::new(),
+                done: HashSet::new(),
+                fresh: HashSet::new(),
+                callees: Vec::new(),
+            };
+            self.pure_block(target, &mut region)
+                && region
+                    .callees
+                    .into_iter()
+                    .all(|callee| self.visit_fun(callee))
+        } else {
+            false
+        };
+        self.state.insert(fun, Some(pure));
+        pure
+    }
+
+    /// Whether the blocks reachable from `label` are free of effects, except
+    /// for the calls, which are collected for the caller to check.
+    fn pure_block(&self, label: &'a BlockName, region: &mut Region<'a>) -> bool {
+        if region.path.contains(label) {
+            // a loop, which may not terminate
+            return false;
+        }
+        if region.done.contains(label) {
+            return true;
+        }
+        let Some(block) = self.blocks.get(label).copied() else {
+            return false;
+        };
+        region.path.insert(label);
+        let mut body = &block.body;
+        let pure = loop {
+            let pure = match body {
+                BlockBody::Terminator(Terminator::Return(_), _) => true,
+                BlockBody::Terminator(Terminator::Branch(Branch { target, .. }), _) => {
+                    self.pure_block(target, region)
+                }
+                BlockBody::Terminator(Terminator::ConditionalBranch { thn, els, .. }, _) => {
+                    self.pure_block(thn, region) && self.pure_block(els, region)
+                }
+                BlockBody::Terminator(
+                    Terminator::Switch {
+                        targets, default, ..
+                    },
+                    _,
+                ) => {
+                    (targets.iter().chain([default])).all(|target| self.pure_block(target, region))
+                }
+                BlockBody::Operation { dest, op, .. } => match op {
+                    Operation::AllocateArray { .. } => {
+                        region.fresh.insert(dest);
+                        true
+                    }
+                    Operation::Call { fun, .. } => {
+                        region.callees.push(fun);
+                        true
+                    }
+                    // which may overflow
+                    Operation::Prim2(Prim2::Add | Prim2::Sub | Prim2::Mul, ..) => false,
+                    _ => true,
+                },
+                BlockBody::AssertType { .. }
+                | BlockBody::AssertLength { .. }
+                | BlockBody::AssertInBounds { .. } => false,
+                BlockBody::Store { addr, .. } => {
+                    matches!(addr, Immediate::Var(arr) if region.fresh.contains(arr))
+                }
+                BlockBody::SubBlocks { .. } => true,
+            };
+            match body.successor() {
+                Some(next) if pure => body = next,
+                _ => break pure,
+            }
+        };
+        region.path.remove(label);
+        region.done.insert(label);
+        pure
+    }
+}
+
+/// Leaf analysis, telling the functions that make no calls: no block that
+/// they may run calls a function or allocates an array. The blocks that a
+/// function may run are the toplevel block that its body branches to and the
+/// toplevel blocks that those branch to, i.e. those of its tail calls, with
+/// the blocks nested in them.
+///
+/// A leaf saves no volatile registers around calls, so that its variables
+/// can take those first, and if it then uses no non-volatile register, it
+/// can run without a frame.
+pub struct LeafAnalysis {
+    /// The toplevel blocks that each function may run, in the order of the functions
+    regions: Vec<(FunName, BTreeSet<BlockName>)>,
+    /// What each toplevel block does, with the blocks nested in it
+    scopes: HashMap<BlockName, Scope>,
+    pub leaves: HashSet<FunName>,
+}
+
+/// A toplevel block with the blocks nested in it
+#[derive(Default)]
+struct Scope {
+    /// whether it calls a function or allocates an array
+    calls: bool,
+    /// the blocks that it branches to
+    targets: Vec<BlockName>,
+    /// its labels and the labels nested in it
+    labels: Vec<BlockName>,
+    /// the variables that it defines
+    vars: Vec<VarName>,
+}
+
+impl Scope {
+    fn walk<T>(&mut self, block: &BasicBlock<VarName, T>) {
+        self.labels.push(block.label.clone());
+        self.vars.extend(block.params.iter().cloned());
+        let mut body = &block.body;
+        loop {
+            match body {
+                BlockBody::Operation { dest, op, .. } => {
+                    self.vars.push(dest.clone());
+                    self.calls |=
+                        matches!(op, Operation::Call { .. } | Operation::AllocateArray { .. });
+                }
+                BlockBody::SubBlocks { blocks, .. } => {
+                    for block in blocks.iter() {
+                        self.walk(block);
+                    }
+                }
+                BlockBody::Terminator(Terminator::Branch(branch), _) => {
+                    self.targets.push(branch.target.clone());
+                }
+                BlockBody::Terminator(Terminator::ConditionalBranch { thn, els, .. }, _) => {
+                    self.targets.extend([thn.clone(), els.clone()]);
+                }
+                BlockBody::Terminator(
+                    Terminator::Switch {
+                        targets, default, ..
+                    },
+                    _,
+                ) => {
+                    self.targets
+                        .extend(targets.iter().chain([default]).cloned());
+                }
+                _ => {}
+            }
+            match body.successor() {
+                Some(next) => body = next,
+                None => break,
+            }
+        }
+    }
+}
+
+impl LeafAnalysis {
+    pub fn new<T>(prog: &Program<VarName, T>) -> Self {
+        let mut scopes = HashMap::new();
+        for block in prog.blocks.iter() {
+            let mut scope = Scope::default();
+            scope.walk(block);
+            scopes.insert(block.label.clone(), scope);
+        }
+        let mut regions = Vec::new();
+        let mut leaves = HashSet::new();
+        for fun in prog.funs.iter() {
+            let mut region = BTreeSet::new();
+            let mut stack = vec![&fun.body.target];
+            while let Some(label) = stack.pop() {
+                if let Some(scope) = scopes.get(label) {
+                    if region.insert(label.clone()) {
+                        stack.extend(scope.targets.iter());
+                    }
+                }
+            }
+            if region.iter().all(|label| !scopes[label].calls) {
+                leaves.insert(fun.name.clone());
+            }
+            regions.push((fun.name.clone(), region));
+        }
+        Self {
+            regions,
+            scopes,
+            leaves,
+        }
+    }
+
+    pub fn is_leaf(&self, fun: &FunName) -> bool {
+        self.leaves.contains(fun)
+    }
+
+    /// The toplevel blocks that only `funs` run
+    fn run_only_by(&self, funs: &HashSet<FunName>) -> HashSet<&BlockName> {
+        let mut only: HashSet<&BlockName> = HashSet::new();
+        let mut others: HashSet<&BlockName> = HashSet::new();
+        for (fun, region) in self.regions.iter() {
+            match funs.contains(fun) {
+                true => only.extend(region.iter()),
+                false => others.extend(region.iter()),
+            }
+        }
+        only.retain(|label| !others.contains(label));
+        only
+    }
+
+    /// The variables of the blocks that only leaves run, which the register
+    /// allocator gives the volatile registers first
+    pub fn leaf_vars(&self) -> HashSet<VarName> {
+        (self.run_only_by(&self.leaves).into_iter())
+            .flat_map(|label| self.scopes[label].vars.iter().cloned())
+            .collect()
+    }
+
+    /// The labels of the blocks that only leaves without a frame run, i.e.
+    /// leaves that use no non-volatile register in `assignment` and share
+    /// no blocks with functions that have a frame, nested labels included
+    pub fn frameless(&self, assignment: &Coloring) -> HashSet<BlockName> {
+        let volatile = |label: &BlockName| {
+            self.scopes[label]
+                .vars
+                .iter()
+                .all(|x| match assignment.get(x) {
+                    Some(Allocation::Reg(reg)) => reg.is_volatile(),
+                    _ => true,
+                })
+        };
+        let mut frameless: HashSet<FunName> = (self.regions.iter())
+            .filter(|(fun, region)| self.is_leaf(fun) && region.iter().all(volatile))
+            .map(|(fun, _)| fun.clone())
+            .collect();
+        loop {
+            let only = self.run_only_by(&frameless);
+            let shared: Vec<FunName> = (self.regions.iter())
+                .filter(|(fun, region)| {
+                    frameless.contains(fun) && region.iter().any(|label| !only.contains(label))
+                })
+                .map(|(fun, _)| fun.clone())
+                .collect();
+            if shared.is_empty() {
+                break;
+            }
+            for fun in shared.iter() {
+                frameless.remove(fun);
+            }
+        }
+        (self.run_only_by(&frameless).into_iter())
+            .flat_map(|label| self.scopes[label].labels.iter().cloned())
+            .collect()
+    }
+}
+
+/// Dominator analysis of the control flow of each function, i.e. of a
+/// toplevel block and the blocks nested in it.
+///
+/// A block `d` dominates a block `b` if every path from the toplevel block
+/// to `b` passes through `d`. In the dominator tree, the parent of a block is
+/// its immediate dominator, the closest of its strict dominators, and the
+/// toplevel blocks are the roots. Unreachable blocks are not in the tree.
+pub struct DominatorAnalysis {
+    /// The immediate dominator of each reachable block, except the roots
+    pub idom: HashMap<BlockName, BlockName>,
+    /// The toplevel blocks, in program order
+    pub roots: Vec<BlockName>,
+}
+
+impl DominatorAnalysis {
+    pub fn new<T>(prog: &Program<VarName, T>) -> Self {
+        let mut idom = HashMap::new();
+        for block in prog.blocks.iter() {
+            Self::analyze_fun(block, &mut idom);
+        }
+        let roots = prog
+            .blocks
+            .iter()
+            .map(|block| block.label.clone())
+            .collect();
+        Self { idom, roots }
+    }
+
+    /// Finds the immediate dominators of the blocks nested in `root` with the
+    /// iterative algorithm of Cooper, Harvey and Kennedy.
+    fn analyze_fun<T>(root: &BasicBlock<VarName, T>, idom: &mut HashMap<BlockName, BlockName>) {
+        fn collect<'a, T>(
+            block: &'a BasicBlock<VarName, T>,
+            succs: &mut HashMap<&'a BlockName, Vec<&'a BlockName>>,
+        ) {
+            let mut body = &block.body;
+            while let Some(next) = body.successor() {
+                if let BlockBody::SubBlocks { blocks, .. } = body {
+                    blocks.iter().for_each(|block| collect(block, succs));
+                }
+                body = next;
+            }
+            let targets = match body {
+                BlockBody::Terminator(Terminator::Branch(Branch { target, .. }), _) => {
+                    vec![target]
+                }
+                BlockBody::Terminator(Terminator::ConditionalBranch { thn, els, .. }, _) => {
+                    vec![thn, els]
+                }
+                BlockBody::Terminator(
+                    Terminator::Switch {
+                        targets, default, ..
+                    },
+                    _,
+                ) => targets.iter().chain([default]).collect(),
+                _ => vec![],
+            };
+            succs.insert(&block.label, targets);
+        }
+        fn postorder<'a>(
+            label: &'a BlockName,
+            succs: &HashMap<&'a BlockName, Vec<&'a BlockName>>,
+            visited: &mut HashSet<&'a BlockName>,
+            order: &mut Vec<&'a BlockName>,
+        ) {
+            // branches to blocks of other functions are not part of this function
+            let Some(targets) = succs.get(label) else {
+                return;
+            };
+            if !visited.insert(label) {
+                return;
+            }
+            for target in targets {
+                postorder(target, succs, visited, order);
+            }
+            order.push(label);
+        }
+
+        let mut succs = HashMap::new();
+        collect(root, &mut succs);
+        let mut order = Vec::new();
+        postorder(&root.label, &succs, &mut HashSet::new(), &mut order);
+        // blocks are numbered in reverse postorder, so the root is 0
+        let rpo: Vec<_> = order.into_iter().rev().collect();
+        let index: HashMap<_, _> = rpo.iter().enumerate().map(|(i, b)| (*b, i)).collect();
+        let mut preds = vec![Vec::new(); rpo.len()];
+        for (i, label) in rpo.iter().enumerate() {
+            for target in succs[label].iter() {
+                if let Some(&j) = index.get(target) {
+                    preds[j].push(i);
+                }
+            }
+        }
+        let intersect = |doms: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while a > b {
+                    a = doms[a].unwrap();
+                }
+                while b > a {
+                    b = doms[b].unwrap();
+                }
+            }
+            a
+        };
+        let mut doms = vec![None; rpo.len()];
+        doms[0] = Some(0);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 1..rpo.len() {
+                let mut new = None;
+                for &p in preds[i].iter().filter(|&&p| doms[p].is_some()) {
+                    new = Some(new.map_or(p, |n| intersect(&doms, p, n)));
+                }
+                if doms[i] != new {
+                    doms[i] = new;
+                    changed = true;
+                }
+            }
+        }
+        for i in 1..rpo.len() {
+            idom.insert(rpo[i].clone(), rpo[doms[i].unwrap()].clone());
+        }
+    }
+
+    /// Whether `d` dominates `b`; every block dominates itself.
+    pub fn dominates(&self, d: &BlockName, b: &BlockName) -> bool {
+        let mut b = b;
+        loop {
+            if b == d {
+                return true;
+            }
+            match self.idom.get(b) {
+                Some(parent) => b = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// The blocks immediately dominated by `b`.
+    pub fn children(&self, b: &BlockName) -> Vec<&BlockName> {
+        let mut children: Vec<_> = self
+            .idom
+            .iter()
+            .filter(|(_, d)| *d == b)
+            .map(|(child, _)| child)
+            .collect();
+        children.sort();
+        children
+    }
+
+    /// Generates a dot file for the dominator trees.
+    #[cfg(feature = "svg")]
+    pub fn dot(&self, path: impl AsRef<std::path::Path>) {
+        use layout::backends::svg::SVGWriter;
+        use layout::core::base::Orientation;
+        use layout::core::geometry::Point;
+        use layout::core::style::*;
+        use layout::core::utils::save_to_file;
+        use layout::std_shapes::shapes::*;
+        use layout::topo::layout::VisualGraph;
+
+        let mut vg = VisualGraph::new(Orientation::TopToBottom);
+        let mut nodes = HashMap::new();
+        let mut labels: Vec<_> = self.roots.iter().chain(self.idom.keys()).collect();
+        labels.sort();
+        for label in labels {
+            let handler = vg.add_node(Element::create(
+                ShapeKind::new_box(&label.to_string()),
+                StyleAttr::simple(),
+                Orientation::TopToBottom,
+                Point::new(100., 100.),
+            ));
+            nodes.insert(label, handler);
+        }
+
+        let mut edges: Vec<_> = self.idom.iter().collect();
+        edges.sort();
+        for (b, d) in edges {
+            vg.add_edge(Arrow::simple(""), nodes[d], nodes[b]);
+        }
+
+        if nodes.is_empty() {
+            eprintln!("No blocks in the program, not generating dominator tree");
+            return;
+        }
+
+        let mut svg = SVGWriter::new();
+        vg.do_it(false, false, false, &mut svg);
+        let content = svg.finalize();
+
+        let path = path.as_ref().to_str().expect("Invalid path");
+        let res = save_to_file(path, &content);
+        if let Result::Err(err) = res {
+            eprintln!("Could not write the file {}", path);
+            eprintln!("Error {}", err);
+        }
+    }
+}
+
+impl std::fmt::Display for DominatorAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn fmt_tree(
+            dom: &DominatorAnalysis,
+            f: &mut std::fmt::Formatter<'_>,
+            b: &BlockName,
+            depth: usize,
+        ) -> std::fmt::Result {
+            writeln!(f, "{}{}", "  ".repeat(depth), b)?;
+            for child in dom.children(b) {
+                fmt_tree(dom, f, child, depth + 1)?;
+            }
+            Ok(())
+        }
+        for root in self.roots.iter() {
+            fmt_tree(self, f, root, 0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Remove (directly) unused parameters and variables from the program.
+///
+/// This is a simple DCE (dead code elimination) pass.
+/// It removes unused parameters and variables from the program using live set;
+/// however, during this process, it also invalidates the live set.
+///
+/// Combined with further liveness analysis, we can use an iterative approach
+/// to remove all unused parameters and variables.
+///
+/// Externally visible (unmangled) functions, such as `entry`, keep their
+/// signatures, since their callers are outside of the program; only the
+/// arguments they pass on to their block are removed. Functions are never
+/// used as values, so the callers of a mangled function are exactly the
+/// calls in the program, which are rewritten along with its signature.
+pub struct UnusedRemover {
+    /// A mapping from function names to the blocks they contain,
+    /// for functions whose signatures may change.
+    fun_to_block: HashMap<FunName, BlockName>,
+    /// Keeps a set of removed parameters for each block.
+    params: UnusedBlockParams,
+    /// Keeps a set of removed variables.
+    vars: UnusedVarSet,
+    /// Keeps the functions whose parameters were removed.
+    signatures: ChangedSignatures,
+    /// The functions whose calls may be removed if their results are unused.
+    pure: HashSet<FunName>,
+    /// Whether to keep unused operations that may fail, like overflowing arithmetic.
+    strict: bool,
+}
+
+impl UnusedRemover {
+    /// A remover that keeps all calls.
+    pub fn new() -> Self {
+        Self::with_pure(HashSet::new())
+    }
+
+    /// A remover that also removes unused calls to the `pure` functions,
+    /// e.g. as found by `EffectAnalysis`.
+    pub fn with_pure(pure: HashSet<FunName>) -> Self {
+        Self {
+            fun_to_block: HashMap::new(),
+            params: UnusedBlockParams::new(),
+            vars: UnusedVarSet::new(),
+            signatures: ChangedSignatures::new(),
+            pure,
+            strict: false,
+        }
+    }
+
+    /// Keeps unused operations that may report a runtime error when `strict`,
+    /// so that the program reports the same error as without the removal.
+    pub fn strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// Whether removing `op` may change the outcome of the program.
+    fn keeps(&self, op: &Operation<VarName>) -> bool {
+        match op {
+            Operation::Call { fun, .. } => !self.pure.contains(fun),
+            Operation::Prim2(Prim2::Add | Prim2::Sub | Prim2::Mul, ..) => self.strict,
+            _ => false,
+        }
+    }
+
+    /// The functions whose signatures changed in this run.
+    pub fn signatures(&self) -> &ChangedSignatures {
+        &self.signatures
+    }
+
+    pub fn progress(self) -> Option<(UnusedBlockParams, UnusedVarSet)> {
+        if self.params.iter().any(|(_, removed)| !removed.is_empty()) || !self.vars.is_empty() {
+            Some((self.params, self.vars))
+        } else {
+            None
+        }
+    }
+
+    pub fn run(&mut self, prog: Program<VarName, LiveSet>) -> Program<VarName, Nil> {
+        let Program {
+            externs,
+            funs,
+            blocks,
+        } = prog;
+        // first get all toplevel block parameters to be removed
+        blocks.iter().for_each(|block| self.build_block(block));
+        // then remove the parameters from the functions
+        let funs = funs.into_iter().map(|fun| self.run_fun(fun)).collect();
+        // finally remove proceed with the blocks,
+        // knowing what to do with arguments of function calls
+        let blocks = blocks
+            .into_iter()
+            .map(|block| self.run_block(block))
+            .collect();
+        Program {
+            externs,
+            funs,
+            blocks,
+        }
+    }
+
+    fn build_block(&mut self, block: &BasicBlock<VarName, LiveSet>) {
+        let BasicBlock {
+            label,
+            params,
+            body,
+            ..
+        } = block;
+        let (unused, _) =
+            self.trans_block_params(params.clone(), HashSet::from_iter(body.analysis()));
+        self.params.insert(label.clone(), unused);
+        self.build_block_body(body);
+    }
+
+    fn build_block_body(&mut self, body: &BlockBody<VarName, LiveSet>) {
+        // recursively build the block body, bottom-up
+        if let Some(succ) = body.successor() {
+            self.build_block_body(succ)
+        }
+        match body {
+            BlockBody::SubBlocks { blocks, .. } => {
+                for block in blocks.iter() {
+                    self.build_block(block);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn trans_block_params(
+        &mut self,
+        params: impl IntoIterator<Item = VarName>,
+        live: HashSet<&VarName>,
+    ) -> (HashSet<usize>, Vec<VarName>) {
+        use itertools::{Either, Itertools as _};
+        params
+            .into_iter()
+            .enumerate()
+            .map(|(i, param)| {
+                if live.contains(&param) {
+                    Either::Right(param)
+                } else {
+                    Either::Left(i)
+                }
+            })
+            .partition_map(|x| x)
+    }
+
+    fn trans_block_args(
+        &mut self,
+        target: &BlockName,
+        args: Vec<Immediate<VarName>>,
+    ) -> Vec<Immediate<VarName>> {
+        let removed = &self.params[target];
+        args.into_iter()
+            .enumerate()
+            .filter_map(|(i, arg)| (!removed.contains(&i)).then_some(arg))
+            .collect()
+    }
+
+    fn run_fun(&mut self, fun: FunBlock<VarName>) -> FunBlock<VarName> {
+        let FunBlock {
+            name,
+            params,
+            body: Branch { target, args },
+            ..
+        } = fun;
+        let block_params = &self.params[&target];
+        let params = if name.is_unmangled() {
+            params
+        } else {
+            self.fun_to_block.insert(name.clone(), target.clone());
+            let kept: Vec<_> = params
+                .iter()
+                .enumerate()
+                .filter_map(|(i, param)| (!block_params.contains(&i)).then_some(param.clone()))
+                .collect();
+            if kept.len() != params.len() {
+                self.signatures.insert(name.clone(), (params, kept.clone()));
+            }
+            kept
+        };
+        let body = Branch {
+            target,
+            args: args
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, arg)| (!block_params.contains(&i)).then_some(arg))
+                .collect(),
+        };
+        FunBlock {
+            name,
+            params,
+            body,
+            ..fun
+        }
+    }
+
+    fn run_block(&mut self, block: BasicBlock<VarName, LiveSet>) -> BasicBlock<VarName, Nil> {
+        let BasicBlock {
+            label,
+            params,
+            body,
+            ..
+        } = block;
+        let (unused, params) = self.trans_block_params(params, HashSet::from_iter(body.analysis()));
+        self.params.insert(label.clone(), unused);
+
+        let body = self.run_block_body(body);
+        BasicBlock {
+            label,
+            params,
+            body,
+            ana: Nil,
+        }
+    }
+
+    fn run_block_body(&mut self, body: BlockBody<VarName, LiveSet>) -> BlockBody<VarName, Nil> {
+        let live: HashSet<_> =
+            HashSet::from_iter(body.successor().map_or_else(HashSet::new, |succ| {
+                succ.analysis().iter().cloned().collect()
+            }));
+        match body {
+            BlockBody::Terminator(Terminator::Branch(Branch { target, args }), ..) => {
+                // check if we need to remove any arguments if it's a branch
+                let args = self.trans_block_args(&target, args);
+                BlockBody::Terminator(Terminator::Branch(Branch { target, args }), Nil)
+            }
+            BlockBody::Terminator(t, ..) => BlockBody::Terminator(t, Nil),
+            BlockBody::Operation { dest, op, next, .. } => {
+                // if the destination is not live, we can remove the operation
+                // **unless** it's a call, which may contain **side effects**,
+                // to a function not known to be pure, or it may fail in strict mode
+                if !self.keeps(&op) && self.run_imms(&live, &[&Immediate::Var(dest.clone())]) {
+                    return self.run_block_body(*next);
+                }
+                // otherwise, we need to keep the operation
+                let op = match op {
+                    Operation::Call { fun, args } => {
+                        // check if we need to remove any arguments if it's a call
+                        if let Some(block) = self.fun_to_block.get(&fun).cloned() {
+                            // if the function is internal, try to remove arguments
+                            let args = self.trans_block_args(&block, args);
+                            Operation::Call { fun, args }
+                        } else {
+                            Operation::Call { fun, args }
+                        }
+                    }
+                    op => op,
+                };
+                BlockBody::Operation {
+                    dest,
+                    op,
+                    next: Box::new(self.run_block_body(*next)),
+                    ana: Nil,
+                }
+            }
+            BlockBody::SubBlocks { blocks, next, .. } => {
+                let blocks: Vec<_> = blocks
+                    .into_iter()
+                    .map(|block| self.run_block(block))
+                    .collect();
+                BlockBody::SubBlocks {
+                    blocks,
+                    next: Box::new(self.run_block_body(*next)),
+                    ana: Nil,
+                }
+            }
+            BlockBody::AssertType { ty, arg, next, .. } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+            BlockBody::AssertLength { len, next, .. } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+            BlockBody::AssertInBounds {
+                bound, arg, next, ..
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ..
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+        }
+    }
+
+    /// if all the arguments are not live, we can remove all of them
+    /// returns true if we made progress
+    fn run_imms(&mut self, live: &HashSet<VarName>, args: &[&Immediate<VarName>]) -> bool {
+        let victims = args
+            .iter()
+            .filter_map(|arg| match arg {
+                Immediate::Var(var) if !live.contains(var) => Some(var.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let progress = victims.len() == args.len();
+        if progress {
+            self.vars.extend(victims);
+        }
+        progress
+    }
+}
+
+// Constructs the register interference graph and perfect elimination
+// order from an SSA program with liveness information.
+pub struct ConflictAnalysis {
+    /// interference graph:
+
+    /// 1. All variables in the program should be in the graph, except
+    /// for function block and extern function parameters, since their
+    /// location is pre-determined by the calling convention
+
+    /// 2. Two variables should share an edge if they cannot be in the same register:
+    ///    - If x != y are parameters to the same block, they conflict
+    ///    - If x is assigned to while y != x is live, then x, y conflict
+    pub interference: Graph<VarName>,
+
+    /// elimination order to use in Chaitin's algorithm
+    /// If a variable x syntactically dominates a variable y then it should occur *earlier* in the ordering
+    pub order: PerfectEliminationOrder,
+}
+
+impl ConflictAnalysis {
+    pub fn new(prog: &Program<VarName, LiveSet>) -> Self {
+        let mut analysis = ConflictAnalysis {
+            interference: Graph::new(),
+            order: PerfectEliminationOrder::new(),
+        };
+        analysis.build_prog(prog);
+        analysis
+    }
+
+    fn push_order(&mut self, imm: Immediate<VarName>) {
+        match imm {
+            Immediate::Var(var) => {
+                self.order.push(var.clone());
+            }
+            _ => {}
+        }
+    }
+
+    fn conflict_all(&mut self, ana: &LiveSet) {
+        for l1 in ana {
+            for l2 in ana {
+                if l1 != l2 {
+                    self.interference.insert_edge(l1.clone(), l2.clone());
+                }
+            }
+            self.interference.insert_vertex(l1.clone());
+        }
+    }
+    fn build_block_body(&mut self, b: BlockBody<VarName, LiveSet>) {
+        use BlockBody::*;
+        match b {
+            Operation {
+                dest,
+                next,
+                ana,
+                op,
+            } => {
+                self.order.push(dest.clone());
+                self.build_block_body(*next.clone());
+                self.interference.insert_vertex(dest.clone());
+                // even if dest is dead, writing it must not clobber what is live
+                for var in next.analysis().iter().filter(|var| **var != dest) {
+                    self.interference.insert_edge(var.clone(), dest.clone());
+                }
+                Get_param(op.clone()).iter().for_each(|var| {
+                    if let Immediate::Var(var) = var {
+                        if next.analysis().contains(var) {
+                            //if still alive
+                            self.interference.insert_edge(var.clone(), dest.clone());
+                        }
+                    }
+                });
+                self.conflict_all(&ana);
+            }
+            Terminator(_, ana) => {
+                self.conflict_all(&ana);
+            }
+            AssertType { next, ana, .. }
+            | AssertLength { next, ana, .. }
+            | AssertInBounds { next, ana, .. }
+            | Store { next, ana, .. } => {
+                self.conflict_all(&ana);
+                self.build_block_body(*next);
+            }
+            SubBlocks {
+                blocks: sub_blocks,
+                next,
+                ana,
+            } => {
+                self.build_block_body(*next);
+                for block in sub_blocks.iter() {
+                    self.build_basic_block(block.clone());
+                }
+                self.conflict_all(&ana);
+            }
+        }
+    }
+    fn build_basic_block(&mut self, b: BasicBlock<VarName, LiveSet>) {
+        b.params.iter().for_each(|param| {
+            self.interference.insert_vertex(param.clone());
+            b.params.iter().for_each(|param2| {
+                if param != param2 {
+                    self.interference.insert_edge(param.clone(), param2.clone());
+                }
+            });
+            self.order.push(param.clone());
+        });
+        self.conflict_all(&b.ana);
+        self.build_block_body(b.body);
+    }
+    // Traverse the program, which is annotated with liveness information, building up the interference graph as well as the elimination order.
+    fn build_prog(
+        &mut self,
+        Program {
+            externs: _,
+            funs: _,
+            blocks,
+        }: &Program<VarName, LiveSet>,
+    ) {
+        for block in blocks {
+            self.build_basic_block(block.clone());
+        }
+    }
+}
+
+/// Colors the provided interference graph using the provided
+/// elimination ordering.
+pub struct RegisterAllocator {
+    // assignment of registers or spills to variables
+    pub assignment: Coloring,
+    // reverse color mapping, to determine which registers are free
+    regs_to_vars: HashMap<Reg, HashSet<VarName>>,
+    // Spills for non-volatile registers that need to be saved, ordered so that every run
+    // emits the same code
+    callee_saves: BTreeMap<Reg, i32>,
+    /// Internal state for determining where to spill
+    max_spill: i32,
+    /// The slots before the first spill, e.g. that of the saved rbp
+    reserved_slots: i32,
+    /// The variables that take the volatile registers first
+    volatile_first: HashSet<VarName>,
+}
+
+impl RegisterAllocator {
+    pub fn new() -> Self {
+        Self {
+            max_spill: 0,
+            reserved_slots: 0,
+            assignment: Coloring::new(),
+            regs_to_vars: HashMap::new(),
+            callee_saves: BTreeMap::new(),
+            volatile_first: HashSet::new(),
+        }
+    }
+    /// Checks that `registers` are free for allocation: the code generator
+    /// reserves Rax and R10 as temporaries and Rsp as the stack pointer.
+    pub fn check_registers(registers: &[Reg]) -> Result<(), AllocError> {
+        match registers.iter().find(|reg| !Reg::ALLOCATABLE.contains(reg)) {
+            Some(reg) => Err(AllocError::ReservedRegister(*reg)),
+            None => Ok(()),
+        }
+    }
+    /// Reserves the first slot of every frame for the rbp of the caller when
+    /// `keep`, which the emitter saves there; rbp must not be allocated then.
+    pub fn keep_frame_pointer(self, keep: bool) -> Self {
+        let reserved_slots = if keep { 1 } else { self.reserved_slots };
+        Self {
+            max_spill: reserved_slots.max(self.max_spill),
+            reserved_slots,
+            ..self
+        }
+    }
+    /// Gives `vars` the volatile registers first, whatever the order of the
+    /// registers, e.g. the variables of leaves, which save none around calls.
+    pub fn prefer_volatile(self, vars: HashSet<VarName>) -> Self {
+        Self {
+            volatile_first: vars,
+            ..self
+        }
+    }
+    /// Use this function when to get the next valid spill location.
+    fn spill(&mut self) -> i32 {
+        self.max_spill += 1;
+        self.max_spill
+    }
+
+    // g: the graph to be colored
+    // remaining: the variables that still need to be colored
+    // all_regs: the registers available to use as colors
+    // log: if true, then the verbose flag is set, and feel free to print debugging information
+    fn chaitin(
+        &mut self,
+        mut g: Graph<VarName>,
+        mut remaining: Vec<VarName>,
+        all_regs: &[Reg],
+        log: bool,
+    ) {
+        //first color the rest of the graph
+        let last = remaining.last().unwrap().clone();
+        let mut g1 = g.clone();
+        g1.remove_vertex(&last);
+        remaining.pop();
+        if remaining.len() > 0 {
+            self.chaitin(g1.clone(), remaining.clone(), all_regs, log);
+        }
+        //println!("{:?}", g1);
+        let mut av_reg: Vec<Reg> = vec![];
+        //println!("last: {:?}", last);
+        //println!("remaining: {:?}", remaining);
+        for vertex in remaining {
+            if g.contains_edge(&vertex, &last) {
+                //println!("vertex: {:?}, last: {:?}", vertex, last);
+                if let Some(reg) = self.assignment.get(&vertex).unwrap().as_reg() {
+                    //println!("vertex: {:?}, reg: {:?}", vertex, reg);
+                    av_reg.push(reg);
+                }
+            }
+        }
+        let order: Vec<&Reg> = match self.volatile_first.contains(&last) {
+            true => (all_regs.iter().filter(|reg| reg.is_volatile()))
+                .chain(all_regs.iter().filter(|reg| reg.is_non_volatile()))
+                .collect(),
+            false => all_regs.iter().collect(),
+        };
+        for reg in order {
+            if !av_reg.contains(reg) {
+                self.assignment
+                    .insert(last.clone(), Allocation::Reg(reg.clone()));
+                break;
+            }
+        }
+        //spill
+        if !self.assignment.contains_key(&last) {
+            let spill = self.spill();
+            self.assignment
+                .insert(last.clone(), Allocation::Spill(spill));
+        }
+    }
+
+    pub fn graph_color(&mut self, conflicts: ConflictAnalysis, registers: &[Reg], log: bool) {
+        if log {
+            println!("Elimination order:\n{}", conflicts.order);
+            println!("Register order:\n{:?}", registers);
+        }
+        // First, color the graph
+        self.chaitin(
+            conflicts.interference,
+            conflicts.order.0.into_iter().collect(),
+            registers,
+            log,
+        );
+        // Then, spill any used non-volatile registers
+        let assns: BTreeSet<_> = self
+            .assignment
+            .0
+            .iter()
+            .map(|(_, loc)| loc.clone())
+            .collect();
+        for loc in assns.iter() {
+            match loc {
+                Allocation::Reg(reg) => {
+                    if reg.is_non_volatile() && !self.callee_saves.contains_key(reg) {
+                        let new_loc = self.spill();
+                        self.callee_saves.insert(*reg, new_loc);
+                    }
+                }
+                Allocation::Spill(_) => {}
+            }
+        }
+    }
+}
+
+/// The checks of `RegisterAllocator::verify`, which walk the blocks of the
+/// program with the labels of the blocks they are in
+struct AllocationChecker<'a> {
+    assignment: &'a Coloring,
+    problems: BTreeSet<String>,
+}
+
+impl AllocationChecker<'_> {
+    /// The allocation of `var`, which is a problem if there is none
+    fn alloc(&mut self, var: &VarName, block: &BlockName) -> Option<Allocation> {
+        let alloc = self.assignment.get(var).copied();
+        if alloc.is_none() {
+            self.problems.insert(format!("{} in block {} is not allocated", var, block));
+        }
+        alloc
+    }
+
+    /// Checks that `vars`, which are live at once, have locations of their own
+    fn apart<'v>(&mut self, vars: impl IntoIterator<Item = &'v VarName>, block: &BlockName) {
+        let mut taken: HashMap<Allocation, &VarName> = HashMap::new();
+        for var in vars {
+            let Some(alloc) = self.alloc(var, block) else { continue };
+            if let Some(other) = taken.insert(alloc, var) {
+                let (x, y) = if other < var { (other, var) } else { (var, other) };
+                self.problems.insert(format!(
+                    "{} and {} share {} while both live in block {}",
+                    x, y, alloc, block
+                ));
+            }
+        }
+    }
+
+    fn block(&mut self, block: &BasicBlock<VarName, LiveSet>) {
+        self.apart(&block.params, &block.label);
+        self.apart(block.ana.iter(), &block.label);
+        self.body(&block.body, &block.label);
+    }
+
+    fn body(&mut self, mut body: &BlockBody<VarName, LiveSet>, label: &BlockName) {
+        loop {
+            self.apart(body.analysis().iter(), label);
+            match body {
+                BlockBody::Operation { dest, next, .. } => {
+                    // even a dead dest must not overwrite what is live
+                    let live = next.analysis().iter().filter(|var| *var != dest);
+                    let Some(alloc) = self.alloc(dest, label) else { return };
+                    for var in live {
+                        if self.alloc(var, label) == Some(alloc) {
+                            self.problems.insert(format!(
+                                "{} is written to {} in block {} while {} is live there",
+                                dest, alloc, label, var
+                            ));
+                        }
+                    }
+                }
+                BlockBody::SubBlocks { blocks, .. } => {
+                    for block in blocks.iter() {
+                        self.block(block);
+                    }
+                }
+                _ => {}
+            }
+            match body.successor() {
+                Some(next) => body = next,
+                None => return,
+            }
+        }
+    }
+}
+
+impl RegisterAllocator {
+    /// Checks the allocation against the liveness of `prog`, which it was
+    /// made for, independently of `ConflictAnalysis`: every variable is
+    /// allocated, to one of `registers` or to a slot past those reserved and
+    /// those of the saved non-volatile registers, and no two variables that
+    /// are live at once, or of which one is written while the other is live,
+    /// share a location. The problems otherwise, each with the block it is in.
+    pub fn verify(
+        &self, prog: &Program<VarName, LiveSet>, registers: &[Reg],
+    ) -> Result<(), Vec<String>> {
+        let mut checker = AllocationChecker {
+            assignment: &self.assignment,
+            problems: BTreeSet::new(),
+        };
+        for block in prog.blocks.iter() {
+            checker.block(block);
+        }
+        let saves: HashMap<i32, Reg> =
+            self.callee_saves.iter().map(|(reg, slot)| (*slot, *reg)).collect();
+        for (var, alloc) in self.assignment.0.iter() {
+            let problem = match *alloc {
+                Allocation::Reg(reg) if !registers.contains(&reg) => {
+                    format!("{} is allocated to {}, which is not available", var, reg)
+                }
+                Allocation::Spill(slot) if slot <= self.reserved_slots => {
+                    format!("{} is spilled to the reserved slot {}", var, slot)
+                }
+                Allocation::Spill(slot) if saves.contains_key(&slot) => {
+                    format!("{} is spilled to the slot {} of the saved {}", var, slot, saves[&slot])
+                }
+                _ => continue,
+            };
+            checker.problems.insert(problem);
+        }
+        match checker.problems.is_empty() {
+            true => Ok(()),
+            false => Err(checker.problems.into_iter().collect()),
+        }
+    }
+}
+
+impl Reg {
+    pub const ALL: [Reg; 16] = [
+        Reg::Rax,
+        Reg::Rbx,
+        Reg::Rcx,
+        Reg::Rdx,
+        Reg::Rdi,
+        Reg::Rsi,
+        Reg::Rsp,
+        Reg::Rbp,
+        Reg::R8,
+        Reg::R9,
+        Reg::R10,
+        Reg::R11,
+        Reg::R12,
+        Reg::R13,
+        Reg::R14,
+        Reg::R15,
+    ];
+
+    pub const VOLATILE: [Reg; 10] = [
+        Reg::Rax,
+        Reg::Rcx,
+        Reg::Rdx,
+        Reg::Rdi,
+        Reg::Rsi,
+        Reg::Rsp,
+        Reg::R8,
+        Reg::R9,
+        Reg::R10,
+        Reg::R11,
+    ];
+
+    pub const NON_VOLATILE: [Reg; 6] = [Reg::Rbx, Reg::Rbp, Reg::R12, Reg::R13, Reg::R14, Reg::R15];
+
+    pub fn is_volatile(&self) -> bool {
+        match self {
+            Reg::Rax
+            | Reg::Rcx
+            | Reg::Rdx
+            | Reg::Rdi
+            | Reg::Rsi
+            | Reg::Rsp
+            | Reg::R8
+            | Reg::R9
+            | Reg::R10
+            | Reg::R11 => true,
+            Reg::Rbx | Reg::Rbp | Reg::R12 | Reg::R13 | Reg::R14 | Reg::R15 => false,
+        }
+    }
+
+    pub fn is_non_volatile(&self) -> bool {
+        !self.is_volatile()
+    }
+
+    /// The registers that can be used for allocations.
+    /// Rax and R10 are excluded because they are used as the temporary registers.
+    /// Rsp is excluded because it holds the stack pointer.
+
+    /// The ordering reflects the order in which they are
+    /// chosen. Argument registers are first as this reduces the cost
+    /// of branching in a function block
+    pub const ALLOCATABLE: [Reg; 13] = [
+        Reg::Rdi,
+        Reg::Rsi,
+        Reg::Rdx,
+        Reg::Rcx,
+        Reg::R8,
+        Reg::R9,
+        Reg::R11,
+        Reg::Rbx,
+        Reg::Rbp,
+        Reg::R12,
+        Reg::R13,
+        Reg::R14,
+        Reg::R15,
+    ];
+
+    pub const ALLOCATABLE_VOLATILE: [Reg; 7] = [
+        Reg::Rdi,
+        Reg::Rsi,
+        Reg::Rdx,
+        Reg::Rcx,
+        Reg::R8,
+        Reg::R9,
+        Reg::R11,
+    ];
+
+    pub const ALLOCATABLE_NON_VOLATILE: [Reg; 6] =
+        [Reg::Rbx, Reg::Rbp, Reg::R12, Reg::R13, Reg::R14, Reg::R15];
+
+    /// The System-V calling convention uses the following registers for the first 6 arguments.
+    pub const ARGS: [Reg; 6] = [Reg::Rdi, Reg::Rsi, Reg::Rdx, Reg::Rcx, Reg::R8, Reg::R9];
+}
+
+impl Into<Reg8> for Reg {
+    fn into(self) -> Reg8 {
+        match self {
+            Reg::Rax => Reg8::Al,
+            Reg::Rbx => Reg8::Bl,
+            Reg::Rdx => Reg8::Dl,
+            Reg::Rcx => Reg8::Cl,
+            Reg::Rsi => Reg8::Sil,
+            Reg::Rdi => Reg8::Dil,
+            Reg::Rsp => Reg8::Spl,
+            Reg::Rbp => Reg8::Bpl,
+            Reg::R8 => Reg8::R8b,
+            Reg::R9 => Reg8::R9b,
+            Reg::R10 => Reg8::R10b,
+            Reg::R11 => Reg8::R11b,
+            Reg::R12 => Reg8::R12b,
+            Reg::R13 => Reg8::R13b,
+            Reg::R14 => Reg8::R14b,
+            Reg::R15 => Reg8::R15b,
+        }
+    }
+}
+
+impl BinArgs {
+    /// Create a BinArgs from a destination register and a source allocation.
+    ///
+    /// ## Panic
+    ///
+    /// Having the same register for both dst and src (e.g. `op reg, reg`).
+    pub fn from_alloc(dst: Reg, src: Allocation) -> Self {
+        match src {
+            Allocation::Reg(src) if src == dst => unreachable!(),
+            Allocation::Reg(src) => BinArgs::ToReg(dst, Arg32::Reg(src)),
+            Allocation::Spill(spill) => BinArgs::ToReg(
+                dst,
+                Arg32::Mem(MemRef {
+                    reg: Reg::Rsp,
+                    offset: -8 * spill,
+                }),
+            ),
+        }
+    }
+
+    /// Create a BinArgs from a destination allocation and a source register.
+    ///
+    /// ## Panic
+    ///
+    /// Having the same register for both dst and src (e.g. `op reg, reg`).
+    pub fn to_alloc(dst: Allocation, src: Reg) -> Self {
+        match dst {
+            Allocation::Reg(dst) if dst == src => unreachable!(),
+            Allocation::Reg(dst) => BinArgs::ToReg(dst, Arg32::Reg(src)),
+            Allocation::Spill(spill) => BinArgs::ToMem(
+                MemRef {
+                    reg: Reg::Rsp,
+                    offset: -8 * spill,
+                },
+                Reg32::Reg(src),
+            ),
+        }
+    }
+}
+
+impl MovArgs {
+    /// Create a MovArgs from a destination allocation and a source register.
+    ///
+    /// ## Panic
+    ///
+    /// Having the same register for both dst and src (e.g. `mov reg, reg`).
+    pub fn to_alloc(dst: Allocation, src: Reg) -> Self {
+        match dst {
+            Allocation::Reg(dst) if dst == src => unreachable!(),
+            Allocation::Reg(dst) => MovArgs::ToReg(dst, Arg64::Reg(src)),
+            Allocation::Spill(spill) => MovArgs::ToMem(
+                MemRef {
+                    reg: Reg::Rsp,
+                    offset: -8 * spill,
+                },
+                Reg32::Reg(src),
+            ),
+        }
+    }
+}
+
+/// Example runtime error codes.
+#[repr(u64)]
+pub enum SnakeErr {
+    ArithmeticOverflow = 0,
+    ExpectedNum = 1,
+    ExpectedBool = 2,
+    ExpectedArray = 3,
+    NegativeLength = 4,
+    IndexOutOfBounds = 5,
+}
+
+impl SnakeErr {
+    pub const COUNT: usize = 6;
+}
+
+impl From<usize> for SnakeErr {
+    fn from(value: usize) -> Self {
+        match value {
+            0 => SnakeErr::ArithmeticOverflow,
+            1 => SnakeErr::ExpectedNum,
+            2 => SnakeErr::ExpectedBool,
+            3 => SnakeErr::ExpectedArray,
+            4 => SnakeErr::NegativeLength,
+            5 => SnakeErr::IndexOutOfBounds,
+            _ => panic!("Invalid SnakeErr value"),
+        }
+    }
+}
+
+impl std::fmt::Display for SnakeErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnakeErr::ArithmeticOverflow => write!(f, "arithmetic_overflow_err"),
+            SnakeErr::ExpectedNum => write!(f, "expected_num_err"),
+            SnakeErr::ExpectedBool => write!(f, "expected_bool_err"),
+            SnakeErr::ExpectedArray => write!(f, "expected_array_err"),
+            SnakeErr::NegativeLength => write!(f, "negative_length_err"),
+            SnakeErr::IndexOutOfBounds => write!(f, "index_out_of_bounds_err"),
+        }
+    }
+}
+
+/// A comparison whose result is only tagged as a boolean,
+/// `cmp = imm1 cc imm2; shifted = cmp << by; dest = shifted | tag`
+struct TaggedComparison<'a> {
+    dest: &'a VarName,
+    cc: ConditionCode,
+    args: (&'a Immediate<VarName>, &'a Immediate<VarName>),
+    by: u8,
+    tag: i64,
+    /// The code after the tagging
+    next: &'a BlockBody<VarName, LiveSet>,
+}
+
+impl<'a> TaggedComparison<'a> {
+    /// The comparison of `cmp = op` and the tagging in `next`, if `cmp` and
+    /// the shifted result are not used otherwise
+    fn new(
+        cmp: &VarName,
+        op: &'a Operation<VarName>,
+        next: &'a BlockBody<VarName, LiveSet>,
+    ) -> Option<Self> {
+        let Operation::Prim2(prim, imm1, imm2) = op else {
+            return None;
+        };
+        let cc = condition(*prim)?;
+        let BlockBody::Operation {
+            dest: shifted,
+            op: Operation::Prim1(Prim1::BitSal(by), Immediate::Var(x)),
+            next: or,
+            ..
+        } = next
+        else {
+            return None;
+        };
+        let BlockBody::Operation {
+            dest,
+            op: Operation::Prim2(Prim2::BitOr, Immediate::Var(y), Immediate::Const(tag)),
+            next,
+            ..
+        } = or.as_ref()
+        else {
+            return None;
+        };
+        let unused = !or.analysis().contains(cmp) && !next.analysis().contains(shifted);
+        // the or of the tag is then an add, and the tagged true fits
+        let fits = *by < 32 && (0..1 << by).contains(tag);
+        (x == cmp && y == shifted && unused && fits).then_some(TaggedComparison {
+            dest,
+            cc,
+            args: (imm1, imm2),
+            by: *by,
+            tag: *tag,
+            next,
+        })
+    }
+}
+
+/// The condition of a comparison
+fn condition(prim: Prim2) -> Option<ConditionCode> {
+    match prim {
+        Prim2::Lt => Some(ConditionCode::L),
+        Prim2::Gt => Some(ConditionCode::G),
+        Prim2::Le => Some(ConditionCode::LE),
+        Prim2::Ge => Some(ConditionCode::GE),
+        Prim2::Eq => Some(ConditionCode::E),
+        Prim2::Neq => Some(ConditionCode::NE),
+        _ => None,
+    }
+}
+
+/// The attributes of the sections of code and read-only data that nasm
+/// only gives `.text` and `.rodata`
+const TEXT_ATTRIBUTES: &str = "progbits alloc exec nowrite align=16";
+const RODATA_ATTRIBUTES: &str = "progbits alloc noexec nowrite align=8";
+
+pub struct Emitter {
+    /// The output buffer for the sequence of instructions we are generating.
+    instrs: Vec<Instr>,
+    /// Register Allocation info
+    allocation: RegisterAllocator,
+    /// Whether to lay out the code for size
+    size: bool,
+    /// Whether the program is linked with the freestanding runtime
+    freestanding: bool,
+    /// How comparisons whose result is only tagged are emitted
+    bool_lowering: BoolLowering,
+    /// Whether functions push their names on the shadow stack of the runtime
+    shadow_stack: bool,
+    /// Whether functions keep a frame pointer or describe their frames for
+    /// the unwind tables
+    frame_pointer: Option<FramePointer>,
+    /// Whether each function goes into a section of its own
+    function_sections: bool,
+    /// Whether each function switches to the code section on its own
+    fragments: bool,
+    /// Whether the calls whose result is returned jump to the callee
+    tail_calls: bool,
+    /// The jump tables of the switches, emitted as read-only data after the code
+    tables: Vec<Instr>,
+    /// The names of the functions on the shadow stack, emitted after the tables
+    fun_names: Vec<Instr>,
+    /// The labels of the string literals, one per distinct literal
+    strings: HashMap<String, Label>,
+    /// The string literals not yet emitted, after the names of the functions
+    string_data: Vec<Instr>,
+    /// The labels of the code and the symbols it refers to
+    symbols: Symbols,
+    /// The labels of the blocks run without a frame
+    frameless_blocks: HashSet<BlockName>,
+    /// Whether the block being emitted runs without a frame
+    frameless: bool,
+}
+
+impl From<RegisterAllocator> for Emitter {
+    fn from(allocation: RegisterAllocator) -> Self {
+        Emitter {
+            instrs: Vec::new(),
+            allocation,
+            size: false,
+            freestanding: false,
+            bool_lowering: BoolLowering::default(),
+            shadow_stack: false,
+            frame_pointer: None,
+            function_sections: false,
+            fragments: false,
+            tail_calls: false,
+            tables: Vec::new(),
+            fun_names: Vec::new(),
+            strings: HashMap::new(),
+            string_data: Vec::new(),
+            symbols: Symbols::new(),
+            frameless_blocks: HashSet::new(),
+            frameless: false,
+        }
+    }
+}
+
+type BlockEnv = im::HashMap<BlockName, Vec<Allocation>>;
+impl Emitter {
+    /// Lays out the code for size when `size`: the block of each function
+    /// directly follows the function, and jumps to the next label are dropped.
+    pub fn for_size(self, size: bool) -> Self {
+        Self { size, ..self }
+    }
+
+    /// Emits the comparisons that are only tagged as booleans with `lowering`.
+    pub fn bool_lowering(self, bool_lowering: BoolLowering) -> Self {
+        Self {
+            bool_lowering,
+            ..self
+        }
+    }
+
+    /// Keeps the names of the functions being run on the shadow stack of the
+    /// runtime when `shadow_stack`, which it prints as a backtrace on errors.
+    pub fn shadow_stack(self, shadow_stack: bool) -> Self {
+        Self {
+            shadow_stack,
+            ..self
+        }
+    }
+
+    /// Saves the rbp of the caller in the first slot of each frame and points
+    /// rbp at it with `Keep`, for which the allocator must have reserved the
+    /// slot; tells where the non-volatile registers are saved with `Omit`.
+    pub fn frame_pointer(self, frame_pointer: Option<FramePointer>) -> Self {
+        Self {
+            frame_pointer,
+            ..self
+        }
+    }
+
+    /// Emits each function with its block, and each block that no function
+    /// branches to, into a section `.text.<name>` of its own, and their jump
+    /// tables into `.rodata.<name>`, for the linker to discard those that
+    /// nothing refers to.
+    pub fn function_sections(self, function_sections: bool) -> Self {
+        Self {
+            function_sections,
+            ..self
+        }
+    }
+
+    /// Lays out each function with its block, and each block that no
+    /// function branches to, as `function_sections` does, but switching to
+    /// `.text` and `.rodata` before each of them instead of to sections of
+    /// their own, for `Asm::fragments` to split the code there.
+    pub fn fragments(self, fragments: bool) -> Self {
+        Self { fragments, ..self }
+    }
+
+    /// Emits the calls whose result is returned right away as jumps when
+    /// `tail_calls`: the caller takes down its frame first, and the callee
+    /// returns to the caller's caller, so that recursion through such calls
+    /// runs in constant stack. Only calls that pass all their arguments in
+    /// registers qualify, as the callee would find the others in the frame
+    /// of the caller's caller. The shadow stack keeps the callers for its
+    /// backtraces, so it emits calls throughout.
+    pub fn tail_calls(self, tail_calls: bool) -> Self {
+        Self { tail_calls, ..self }
+    }
+
+    /// Emits the functions that only run `blocks`, e.g. as
+    /// `LeafAnalysis::frameless` tells, without a frame: they neither save
+    /// the non-volatile registers nor, with `Keep`, rbp. The unwind tables
+    /// describe a single layout of the saves, so `Omit` keeps the frames.
+    pub fn frameless(self, blocks: HashSet<BlockName>) -> Self {
+        Self {
+            frameless_blocks: blocks,
+            ..self
+        }
+    }
+
+    /// Whether the block `label` runs without a frame
+    fn is_frameless(&self, label: &BlockName) -> bool {
+        self.frame_pointer != Some(FramePointer::Omit) && self.frameless_blocks.contains(label)
+    }
+
+    /// The externs implemented by the freestanding runtime.
+    pub const FREESTANDING_EXTERNS: [&'static str; 6] = [
+        "print",
+        "snake_error",
+        "snake_new_array",
+        "snake_getenv",
+        "snake_clock",
+        "snake_random",
+    ];
+
+    /// The externs whose calls do not return, for `Asm::check_stack`.
+    pub const NO_RETURN_EXTERNS: [&'static str; 1] = ["snake_error"];
+
+    /// Leaves the externs of the freestanding runtime undeclared when
+    /// `freestanding`, as the runtime is appended to the same file.
+    pub fn freestanding(self, freestanding: bool) -> Self {
+        Self {
+            freestanding,
+            ..self
+        }
+    }
+
+    /// The emitted code.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the code refers to a label that it does not define.
+    pub fn to_asm(self) -> Asm {
+        let asm = Asm {
+            instrs: self.instrs,
+            symbols: self.symbols,
+        };
+        if let Err(problems) = asm.verify() {
+            panic!("the emitted code is broken:\n{}", problems.join("\n"));
+        }
+        asm
+    }
+
+    fn emit(&mut self, instr: Instr) {
+        self.instrs.push(instr);
+    }
+
+    fn resolve(&self, x: &VarName) -> Allocation {
+        match self.allocation.assignment.get(x) {
+            Some(a) => *a,
+            None => {
+                unreachable!(
+                    "Didn't find {} in allocation {}",
+                    x, self.allocation.assignment
+                );
+            }
+        }
+    }
+
+    fn resolve_imm(&self, imm: &Immediate<VarName>) -> Immediate<Allocation> {
+        match imm {
+            Immediate::Const(n) => Immediate::Const(*n),
+            Immediate::Var(x) => Immediate::Var(self.resolve(x)),
+        }
+    }
+
+    /// Resolve to an allocation that can be used as the destination of a binary operation.
+    /// The following invariants should be checked before calling this function:
+    ///
+    /// 1. input should not be constant (e.g. 42) because we said so in the middle-end
+    /// 2. input should not be rax or r10 because they are used as temporary registers
+    ///
+    /// ## Panic
+    ///
+    /// Panics if invariants are violated.
+    fn resolve_to_alloc(&self, imm: &Immediate<VarName>) -> Allocation {
+        match imm {
+            Immediate::Var(x) => {
+                let alloc = self.resolve(x);
+                assert!(
+                    !matches!(alloc, Allocation::Reg(Reg::Rax | Reg::R10)),
+                    "{} is assigned to a temporary register {}",
+                    x,
+                    alloc
+                );
+                alloc
+            }
+            Immediate::Const(n) => unreachable!("Can't assign a constant {} to an allocation", n),
+        }
+    }
+
+    pub fn emit_prog(&mut self, prog: &Program<VarName, LiveSet>) {
+        let Program {
+            externs,
+            funs,
+            blocks,
+        } = prog;
+        // emit text section
+        self.emit(Instr::Section(".text".to_string()));
+        let entry = self.symbols.global("entry");
+        self.emit(Instr::Global(entry));
+
+        // emit the user-defined externs
+        for ext in externs.iter() {
+            self.emit_extern(ext);
+        }
+        if self.shadow_stack {
+            let sp = self.symbols.external("snake_shadow_sp");
+            self.emit(Instr::Extern(sp));
+        }
+
+        // the error handlers are only reached once the registers are saved
+        if self.frame_pointer == Some(FramePointer::Omit) {
+            for (reg, slot) in self.callee_saves() {
+                self.emit(Instr::Cfi(Cfi::Offset(reg, 8 + 8 * slot)));
+            }
+        }
+
+        // emit error handlers
+        for i in 0..SnakeErr::COUNT {
+            let handler = self.symbols.local(SnakeErr::from(i).to_string());
+            self.emit(Instr::Label(handler));
+            self.emit(Instr::Mov(MovArgs::ToReg(
+                Reg::Rdi,
+                Arg64::Signed(i as i64),
+            )));
+            match SnakeErr::from(i) {
+                SnakeErr::NegativeLength | SnakeErr::IndexOutOfBounds => {
+                    // rax = rax << 1 (encode as snake integer)
+                    self.emit(Instr::Sal(ShArgs {
+                        reg: Reg::Rax,
+                        by: 1,
+                    }));
+                }
+                SnakeErr::ArithmeticOverflow
+                | SnakeErr::ExpectedNum
+                | SnakeErr::ExpectedBool
+                | SnakeErr::ExpectedArray => {}
+            }
+            self.emit(Instr::Mov(MovArgs::ToReg(Reg::Rsi, Arg64::Reg(Reg::Rax))));
+            self.emit(Instr::Sub(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(8))));
+            let snake_error = self.symbols.external("snake_error");
+            self.emit(Instr::Call(snake_error));
+        }
+
+        // Build up the environment for the blocks
+        let mut block_env: BlockEnv = im::HashMap::new();
+        for block in blocks.iter() {
+            block_env.insert(
+                block.label.clone(),
+                block.params.iter().map(|x| self.resolve(x)).collect(),
+            );
+        }
+
+        if self.function_sections || self.fragments {
+            let own_sections = self.function_sections;
+            let sections = |name: String| match own_sections {
+                true => (
+                    format!(".text.{} {}", name, TEXT_ATTRIBUTES),
+                    format!(".rodata.{} {}", name, RODATA_ATTRIBUTES),
+                ),
+                false => (".text".to_string(), ".rodata".to_string()),
+            };
+            let mut emitted = HashSet::new();
+            for fun in funs.iter() {
+                let (text, rodata) = sections(fun.name.to_string());
+                self.emit(Instr::Section(text));
+                self.emit_fun_block(fun, block_env.clone());
+                let body = blocks.iter().find(|block| block.label == fun.body.target);
+                if let Some(block) = body.filter(|block| emitted.insert(&block.label)) {
+                    self.emit_block(block, block_env.clone());
+                }
+                self.emit_tables(&rodata);
+            }
+            for block in blocks
+                .iter()
+                .filter(|block| !emitted.contains(&block.label))
+            {
+                let (text, rodata) = sections(block.label.to_string());
+                self.emit(Instr::Section(text));
+                self.emit_block(block, block_env.clone());
+                self.emit_tables(&rodata);
+            }
+            if self.size {
+                self.remove_fallthrough_jumps();
+            }
+            return;
+        }
+
+        if self.size {
+            // emit each function followed by its block, to fall through to it
+            let mut emitted = HashSet::new();
+            for fun in funs.iter() {
+                self.emit_fun_block(fun, block_env.clone());
+                let body = blocks.iter().find(|block| block.label == fun.body.target);
+                if let Some(block) = body.filter(|block| emitted.insert(&block.label)) {
+                    self.emit_block(block, block_env.clone());
+                }
+            }
+            for block in blocks
+                .iter()
+                .filter(|block| !emitted.contains(&block.label))
+            {
+                self.emit_block(block, block_env.clone());
+            }
+            self.remove_fallthrough_jumps();
+            self.emit_tables(".rodata");
+            return;
+        }
+
+        // emit the functions
+        for fun in funs.iter() {
+            self.emit_fun_block(fun, block_env.clone());
+        }
+
+        // finally, emit the blocks
+        for block in blocks.iter() {
+            self.emit_block(block, block_env.clone());
+        }
+        self.emit_tables(".rodata");
+    }
+
+    /// Emits the jump tables, the names of the functions and the string
+    /// literals so far into the section `rodata`
+    fn emit_tables(&mut self, rodata: &str) {
+        if !self.tables.is_empty() {
+            self.emit(Instr::Section(rodata.to_string()));
+            // the entries are DWORDs
+            self.emit(Instr::Align(4));
+            let tables = std::mem::take(&mut self.tables);
+            self.instrs.extend(tables);
+        }
+        if !self.fun_names.is_empty() {
+            self.emit(Instr::Section(rodata.to_string()));
+            let fun_names = std::mem::take(&mut self.fun_names);
+            self.instrs.extend(fun_names);
+        }
+        if !self.string_data.is_empty() {
+            self.emit(Instr::Section(rodata.to_string()));
+            let string_data = std::mem::take(&mut self.string_data);
+            self.instrs.extend(string_data);
+        }
+    }
+
+    /// Removes the jumps to a label that directly follows, possibly after comments.
+    fn remove_fallthrough_jumps(&mut self) {
+        let instrs = std::mem::take(&mut self.instrs);
+        for (i, instr) in instrs.iter().enumerate() {
+            if let Instr::Jmp(target) = instr {
+                let next = instrs[i + 1..]
+                    .iter()
+                    .find(|instr| !matches!(instr, Instr::Comment(_)));
+                if matches!(next, Some(Instr::Label(label)) if label == target) {
+                    continue;
+                }
+            }
+            self.instrs.push(instr.clone());
+        }
+    }
+
+    fn emit_extern(&mut self, Extern { name, .. }: &Extern<VarName>) {
+        let symbol = self.symbols.external(name.hint());
+        if self.freestanding && Self::FREESTANDING_EXTERNS.contains(&name.hint()) {
+            return;
+        }
+        self.emit(Instr::Extern(symbol));
+    }
+
+    /// The label of a block, a function or a runtime error handler
+    fn label(&mut self, name: impl ToString) -> Label {
+        self.symbols.local(name.to_string())
+    }
+
+    /// FunBlocks implement functions that support the Sys V calling convention.
+    ///
+    /// They must
+    /// 1. Save the non-volatile registers that are used in the function.
+    /// 2. Move the values into the appropriate place on the stack.
+
+    /// FunBlocks move the arguments from their designated place in
+    /// the Sys V calling convention to negative offsets from rsp.
+    fn emit_fun_block(&mut self, f: &FunBlock<VarName>, block_env: BlockEnv) {
+        let label = self.label(&f.name);
+        self.emit(Instr::Label(label));
+        let frameless = self.is_frameless(&f.body.target);
+        match self.frame_pointer {
+            Some(FramePointer::Keep) if !frameless => {
+                // rbp points at the rbp of the caller, below the return address
+                self.emit(store_mem(1, Reg::Rbp));
+                self.emit(Instr::Mov(MovArgs::ToReg(Reg::Rbp, Arg64::Reg(Reg::Rsp))));
+                self.emit(Instr::Sub(BinArgs::ToReg(Reg::Rbp, Arg32::Signed(8))));
+            }
+            Some(FramePointer::Omit) => {
+                for (reg, _) in self.callee_saves() {
+                    self.emit(Instr::Cfi(Cfi::SameValue(reg)));
+                }
+            }
+            Some(FramePointer::Keep) | None => {}
+        }
+        if self.shadow_stack {
+            self.emit_shadow_push(f.name.hint());
+        }
+
+        // save the non-volatile registers that are used, unless a leaf uses none
+        let saves = match frameless {
+            true => BTreeMap::new(),
+            false => self.allocation.callee_saves.clone(),
+        };
+        if cfg!(debug_assertions) && !saves.is_empty() {
+            self.emit(Instr::Comment(
+                "    saving non-volatile registers..".to_string(),
+            ));
+        }
+        for (reg, slot) in saves.clone().into_iter() {
+            if cfg!(debug_assertions) {
+                self.emit(Instr::Comment(format!("        <{}> <- {}", slot, reg)));
+            }
+            self.emit(store_mem(slot, reg));
+        }
+        if cfg!(debug_assertions) && !saves.is_empty() {
+            self.emit(Instr::Comment("    ..saved".to_string()));
+        }
+        if self.frame_pointer == Some(FramePointer::Omit) {
+            for (reg, slot) in self.callee_saves() {
+                self.emit(Instr::Cfi(Cfi::Offset(reg, 8 + 8 * slot)));
+            }
+        }
+
+        // lookup the destinations for the parameters
+        let dests: Vec<Allocation> = block_env[&f.body.target].clone();
+
+        // locate the parameters as placed by the calling convention
+        let mut locs: HashMap<&VarName, Allocation> = HashMap::new();
+        for (reg, param) in Reg::ARGS.iter().zip(f.params.iter()) {
+            locs.insert(param, Allocation::Reg(*reg));
+        }
+        for (i, param) in f.params.iter().enumerate().skip(Reg::ARGS.len()) {
+            let j = i + 1;
+            let src: i32 = -1 * ((j - Reg::ARGS.len()) as i32);
+            locs.insert(param, Allocation::Spill(src));
+        }
+
+        // resolve the arguments to sources; the branch may pass on only
+        // some of the parameters, e.g. if `UnusedRemover` removed the
+        // rest from the block but kept the function's signature
+        let srcs: Vec<Immediate<Allocation>> = f
+            .body
+            .args
+            .iter()
+            .map(|arg| match arg {
+                Immediate::Var(v) => Immediate::Var(locs[v]),
+                Immediate::Const(n) => Immediate::Const(*n),
+            })
+            .collect();
+
+        // simultaneously move the arguments from their current location
+        // to the body branch parameter allocations
+        self.emit_simultaneous_move(dests, srcs);
+
+        // finally, jump to the target
+        let target = self.label(&f.body.target);
+        self.emit(Instr::Jmp(target));
+    }
+
+    /// The saved non-volatile registers and their slots, in the order of the
+    /// registers
+    fn callee_saves(&self) -> Vec<(Reg, i32)> {
+        self.allocation.callee_saves.clone().into_iter().collect()
+    }
+
+    /// Pushes the address of the name `fun` on the shadow stack, whose top
+    /// `snake_shadow_sp` points to. Only the temporary registers are
+    /// clobbered, which hold no arguments on entry.
+    fn emit_shadow_push(&mut self, fun: &str) {
+        let name = self.symbols.fresh("fun_name");
+        self.fun_names.push(Instr::Label(name));
+        self.fun_names.push(Instr::Asciz(fun.to_string()));
+        let sp = self.symbols.external("snake_shadow_sp");
+        let top = MemRef {
+            reg: Reg::R10,
+            offset: 0,
+        };
+        self.emit(Instr::Lea(Reg::R10, sp));
+        self.emit(Instr::Add(BinArgs::ToMem(top, Reg32::Imm(8))));
+        self.emit(Instr::Mov(MovArgs::ToReg(Reg::R10, Arg64::Mem(top))));
+        self.emit(Instr::Lea(Reg::Rax, name));
+        self.emit(Instr::Mov(MovArgs::ToMem(top, Reg32::Reg(Reg::Rax))));
+    }
+
+    /// Pops the name of the returning function off the shadow stack, keeping
+    /// the result in rax
+    fn emit_shadow_pop(&mut self) {
+        let sp = self.symbols.external("snake_shadow_sp");
+        let top = MemRef {
+            reg: Reg::R10,
+            offset: 0,
+        };
+        self.emit(Instr::Lea(Reg::R10, sp));
+        self.emit(Instr::Sub(BinArgs::ToMem(top, Reg32::Imm(8))));
+    }
+
+    fn emit_block(&mut self, block: &BasicBlock<VarName, LiveSet>, block_env: BlockEnv) {
+        let BasicBlock { label, body, .. } = block;
+        self.frameless = self.is_frameless(label);
+        let label = self.label(label);
+        self.emit(Instr::Label(label));
+        self.emit_block_body(body, block_env);
+    }
+
+    fn emit_block_body(&mut self, b: &BlockBody<VarName, LiveSet>, mut block_env: BlockEnv) {
+        match b {
+            BlockBody::Terminator(t, ..) => {
+                self.emit_terminator(t, block_env);
+            }
+            BlockBody::Operation { dest, op: Operation::Call { fun, args }, next, .. }
+                if self.is_tail_call(dest, args, next) =>
+            {
+                self.emit_tail_call(fun, args);
+            }
+            BlockBody::Operation { dest, op, next, .. } => {
+                let tagged = TaggedComparison::new(dest, op, next)
+                    .filter(|_| self.bool_lowering == BoolLowering::Cmov);
+                match tagged {
+                    Some(tagged) => {
+                        self.emit_cmov_comparison(&tagged);
+                        self.emit_block_body(tagged.next, block_env);
+                    }
+                    None => {
+                        self.emit_operation(dest, op, next.analysis());
+                        self.emit_block_body(next, block_env);
+                    }
+                }
+            }
+            BlockBody::SubBlocks { blocks, next, .. } => {
+                for block in blocks.iter() {
+                    block_env.insert(
+                        block.label.clone(),
+                        block.params.iter().map(|x| self.resolve(x)).collect(),
+                    );
+                }
+
+                // emit the body
+                self.emit_block_body(next, block_env.clone());
+                // and then emit the sub-blocks
+                for block in blocks.iter() {
+                    self.emit_block(block, block_env.clone());
+                }
+            }
+            BlockBody::AssertType {
+                ty, arg: of, next, ..
+            } => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!("    assert {} of type {}", of, ty)));
+                }
+                let mask = ty.mask();
+                let tag = ty.tag();
+                // rax = of
+                self.emit_imm(Allocation::Reg(Reg::Rax), of);
+                // rax = rax & mask
+                self.emit(Instr::And(BinArgs::ToReg(
+                    Reg::Rax,
+                    Arg32::Signed(mask as i32),
+                )));
+                // cmp rax, tag, which the and already did for 0
+                let cc = if tag == 0 {
+                    self.emit_cmp_zero(Reg::Rax, ConditionCode::NE)
+                } else {
+                    self.emit(Instr::Cmp(BinArgs::ToReg(
+                        Reg::Rax,
+                        Arg32::Signed(tag as i32),
+                    )));
+                    ConditionCode::NE
+                };
+                // sub-optimal but it works and the compiler is cleaner
+                // rax = of (mov will not alter the flag registers)
+                self.emit_imm(Allocation::Reg(Reg::Rax), of);
+                // raise error if not equal, assuming the argument is stored in rax
+                let handler = self.label(match ty {
+                    Type::Int => SnakeErr::ExpectedNum,
+                    Type::Bool => SnakeErr::ExpectedBool,
+                    Type::Array => SnakeErr::ExpectedArray,
+                });
+                self.emit(Instr::JCC(cc, handler));
+                self.emit_block_body(next, block_env);
+            }
+            BlockBody::AssertLength { len, next, .. } => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!(
+                        "    assert length {} is non-negative",
+                        len
+                    )));
+                }
+                // rax = len
+                self.emit_imm(Allocation::Reg(Reg::Rax), len);
+                // cmp rax, 0
+                let cc = self.emit_cmp_zero(Reg::Rax, ConditionCode::L);
+                // raise error if negative, assuming the argument is stored in rax
+                let handler = self.label(SnakeErr::NegativeLength);
+                self.emit(Instr::JCC(cc, handler));
+                self.emit_block_body(next, block_env);
+            }
+            BlockBody::AssertInBounds {
+                bound,
+                arg: of,
+                next,
+                ..
+            } => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!(
+                        "    assert {} is in bounds [0, {})",
+                        of, bound
+                    )));
+                }
+                // rax = of
+                self.emit_imm(Allocation::Reg(Reg::Rax), of);
+                // cmp rax, 0
+                let cc = self.emit_cmp_zero(Reg::Rax, ConditionCode::L);
+                // raise error if negative, assuming the argument is stored in rax
+                let handler = self.label(SnakeErr::IndexOutOfBounds);
+                self.emit(Instr::JCC(cc, handler));
+                // cmp bound, of (bound is never constant and is never temporary)
+                self.emit(Instr::Cmp(BinArgs::to_alloc(
+                    self.resolve_to_alloc(bound),
+                    Reg::Rax,
+                )));
+                // raise error if bound <= of
+                self.emit(Instr::JCC(ConditionCode::LE, handler));
+                self.emit_block_body(next, block_env);
+            }
+            BlockBody::Store {
+                addr,
+                offset: off,
+                val,
+                next,
+                ..
+            } => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!(
+                        "    store [{} + {} * 8] = {}",
+                        addr, off, val
+                    )));
+                }
+                // rax = off
+                self.emit_imm(Allocation::Reg(Reg::Rax), off);
+                // rax = 8 * rax
+                self.emit(Instr::IMul(BinArgs::ToReg(Reg::Rax, Arg32::Signed(8))));
+                // rax = rax + addr (addr is never constant and is never temporary)
+                self.emit(Instr::Add(BinArgs::from_alloc(
+                    Reg::Rax,
+                    self.resolve_to_alloc(addr),
+                )));
+                // finally meeting our Thermopylae - only one temporary register doesn't work >_<
+                // r10 = val
+                self.emit_imm(Allocation::Reg(Reg::R10), val);
+                // mov [rax], r10
+                self.emit(Instr::Mov(MovArgs::ToMem(
+                    MemRef {
+                        reg: Reg::Rax,
+                        offset: 0,
+                    },
+                    Reg32::Reg(Reg::R10),
+                )));
+                self.emit_block_body(next, block_env);
+            }
+        }
+    }
+
+    fn emit_terminator(&mut self, t: &Terminator<VarName>, block_env: BlockEnv) {
+        match t {
+            Terminator::Return(imm) => {
+                // rax = imm
+                // (this must happen before restoring callee-saved registers to avoid clobbering imm)
+                self.emit_imm(Allocation::Reg(Reg::Rax), imm);
+                self.emit_leave();
+                self.emit(Instr::Ret);
+            }
+            Terminator::Branch(branch) => {
+                // simultaneously move the arguments from their current location
+                // to where the target block wants them to be
+                self.emit_simultaneous_move(
+                    block_env[&branch.target].clone(),
+                    branch.args.iter().map(|a| self.resolve_imm(a)).collect(),
+                );
+                let target = self.label(&branch.target);
+                self.emit(Instr::Jmp(target));
+            }
+            Terminator::ConditionalBranch { cond, thn, els } => {
+                // temporary register rax
+                self.emit_imm(Allocation::Reg(Reg::Rax), cond);
+                // cmp rax, 0 (false)
+                let cc = self.emit_cmp_zero(Reg::Rax, ConditionCode::NE);
+                let (thn, els) = (self.label(thn), self.label(els));
+                self.emit(Instr::JCC(cc, thn));
+                self.emit(Instr::Jmp(els));
+            }
+            Terminator::Switch {
+                scrutinee,
+                low,
+                targets,
+                default,
+            } => {
+                // rax = scrutinee - low
+                self.emit_imm(Allocation::Reg(Reg::Rax), scrutinee);
+                match i32::try_from(*low) {
+                    Ok(0) => {}
+                    Ok(low) => self.emit(Instr::Sub(BinArgs::ToReg(Reg::Rax, Arg32::Signed(low)))),
+                    Err(_) => {
+                        self.emit(Instr::Mov(MovArgs::ToReg(Reg::R10, Arg64::Signed(*low))));
+                        self.emit(Instr::Sub(BinArgs::ToReg(Reg::Rax, Arg32::Reg(Reg::R10))));
+                    }
+                }
+                // unsigned, so that below low is out of the table as well
+                self.emit(Instr::Cmp(BinArgs::ToReg(
+                    Reg::Rax,
+                    Arg32::Signed(targets.len() as i32),
+                )));
+                let default = self.label(default);
+                self.emit(Instr::JCC(ConditionCode::AE, default));
+                // the table holds the distances of the targets from the default
+                // block, which keeps it position-independent
+                let table = self.symbols.fresh("jump_table");
+                self.tables.push(Instr::Label(table));
+                for target in targets {
+                    let target = self.label(target);
+                    self.tables.push(Instr::RelOffset(target, default));
+                }
+                self.emit(Instr::Lea(Reg::R10, table));
+                self.emit(Instr::Movsxd(
+                    Reg::Rax,
+                    IndexRef {
+                        base: Reg::R10,
+                        index: Reg::Rax,
+                        scale: 4,
+                    },
+                ));
+                self.emit(Instr::Lea(Reg::R10, default));
+                self.emit(Instr::Add(BinArgs::ToReg(Reg::Rax, Arg32::Reg(Reg::R10))));
+                self.emit(Instr::JmpReg(Reg::Rax));
+            }
+        }
+    }
+
+    /// Restores what the function saved on entry, leaving rax and the
+    /// registers of the arguments as they are
+    fn emit_leave(&mut self) {
+        // restore callee-saved registers
+        let saves = match self.frameless {
+            true => BTreeMap::new(),
+            false => self.allocation.callee_saves.clone(),
+        };
+        if cfg!(debug_assertions) && !saves.is_empty() {
+            self.emit(Instr::Comment("    restoring non-volatile registers..".to_string()));
+        }
+        for (reg, slot) in saves.clone() {
+            if cfg!(debug_assertions) {
+                self.emit(Instr::Comment(format!("        {} <- <{}>", reg, slot)));
+            }
+            self.emit(load_mem(reg, slot));
+        }
+        if cfg!(debug_assertions) && !saves.is_empty() {
+            self.emit(Instr::Comment("    ..restored".to_string()));
+        }
+        if self.shadow_stack {
+            self.emit_shadow_pop();
+        }
+        if self.frame_pointer == Some(FramePointer::Keep) && !self.frameless {
+            self.emit(load_mem(Reg::Rbp, 1));
+        }
+    }
+
+    /// Whether `dest = fun(args)` followed by `next` is a call in tail
+    /// position that `tail_calls` emits as a jump
+    fn is_tail_call(
+        &self, dest: &VarName, args: &[Immediate<VarName>], next: &BlockBody<VarName, LiveSet>,
+    ) -> bool {
+        let returned = matches!(
+            next,
+            BlockBody::Terminator(Terminator::Return(Immediate::Var(var)), _) if var == dest
+        );
+        self.tail_calls && !self.shadow_stack && returned && args.len() <= Reg::ARGS.len()
+    }
+
+    /// Jumps to `fun` with `args` in the registers of the calling convention,
+    /// once the frame is taken down as for a return
+    fn emit_tail_call(&mut self, fun: &FunName, args: &[Immediate<VarName>]) {
+        if cfg!(debug_assertions) {
+            self.emit(Instr::Comment(format!(
+                "    tail call {}({})",
+                fun,
+                args.iter()
+                    .map(|arg| arg.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        // the arguments may be in the non-volatile registers, which the
+        // leave restores, but not in the temporary ones
+        let dests = Reg::ARGS.iter().take(args.len()).map(|reg| Allocation::Reg(*reg)).collect();
+        self.emit_simultaneous_move(dests, args.iter().map(|arg| self.resolve_imm(arg)).collect());
+        self.emit_leave();
+        let fun = self.label(fun);
+        self.emit(Instr::Jmp(fun));
+    }
+
+    /// pass the live set **after** the operation
+    fn emit_operation(&mut self, dest: &VarName, oper: &Operation<VarName>, after_live: &LiveSet) {
+        match oper {
+            Operation::Immediate(imm) => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!("    operation {} = {}", dest, oper)));
+                }
+
+                self.emit_imm(self.resolve(dest), imm);
+            }
+            Operation::Prim1(op, imm) => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!("    operation {} = {}", dest, oper)));
+                }
+
+                let dst = self.resolve(dest);
+                // if the destination is a register, we can use it as a temporary register
+                // otherwise, use rax as a temporary register
+                let tmp = dst.as_reg().unwrap_or(Reg::Rax);
+                let mut emit_shift = |shift: fn(ShArgs) -> Instr, by: u8| {
+                    self.emit_imm(Allocation::Reg(tmp), imm);
+                    self.emit(shift(ShArgs { reg: tmp, by }));
+                    // mov dest, tmp if dest is not a register after the operation
+                };
+                match op {
+                    Prim1::BitNot => {
+                        // mov r10, -1
+                        self.emit(Instr::Mov(MovArgs::ToReg(Reg::R10, Arg64::Signed(-1))));
+                        // mov tmp, imm
+                        self.emit_imm(Allocation::Reg(tmp), imm);
+                        // xor tmp, r10
+                        self.emit(Instr::Xor(BinArgs::ToReg(tmp, Arg32::Reg(Reg::R10))));
+                        // mov dest, tmp if dest is not a register after the operation
+                    }
+                    Prim1::BitSal(n) => {
+                        emit_shift(Instr::Sal, *n);
+                    }
+                    Prim1::BitSar(n) => {
+                        emit_shift(Instr::Sar, *n);
+                    }
+                    Prim1::BitShl(n) => {
+                        emit_shift(Instr::Shl, *n);
+                    }
+                    Prim1::BitShr(n) => {
+                        emit_shift(Instr::Shr, *n);
+                    }
+                }
+                // if the destination is not a register, the temporary result is still in rax
+                match dst.as_spill() {
+                    Some(slot) => {
+                        // need to store the temporary result to the spilled slot
+                        self.emit(store_mem(slot, tmp));
+                    }
+                    None => {}
+                }
+            }
+            Operation::Prim2(op, imm1, imm2) => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!("    operation {} = {}", dest, oper)));
+                }
+
+                let dst = self.resolve(dest);
+                // move the second immediate to rax
+                // THIS MUST HAPPEN BEFORE overwriting dest, because imm2 might be the same as dest
+                self.emit_imm(Allocation::Reg(Reg::Rax), imm2);
+                // temporary register can either be the destination or r10
+                let tmp = dst.as_reg().unwrap_or(Reg::R10);
+                // move the first immediate to the destination
+                self.emit_imm(Allocation::Reg(tmp), imm1);
+                // create a binargs with tmp and rax
+                let ba = BinArgs::ToReg(tmp, Arg32::Reg(Reg::Rax));
+
+                let mut emit_cc = |cc: ConditionCode| {
+                    self.emit(Instr::Cmp(ba));
+                    match dst.as_reg() {
+                        Some(reg) => {
+                            self.emit(Instr::Mov(MovArgs::ToReg(reg, Arg64::Signed(0))));
+                            self.emit(Instr::SetCC(cc, reg.into()));
+                        }
+                        None => {
+                            // if the destination is not a register, emit to rax before moving it to the destination
+                            self.emit(Instr::Mov(MovArgs::ToReg(Reg::R10, Arg64::Signed(0))));
+                            self.emit(Instr::SetCC(cc, Reg8::R10b));
+                        }
+                    }
+                };
+
+                match op {
+                    Prim2::Add => self.emit_arith(Instr::Add(ba)),
+                    Prim2::Sub => self.emit_arith(Instr::Sub(ba)),
+                    Prim2::Mul => self.emit_arith(Instr::IMul(ba)),
+                    Prim2::WrappingAdd => self.emit(Instr::Add(ba)),
+                    Prim2::WrappingSub => self.emit(Instr::Sub(ba)),
+                    Prim2::WrappingMul => self.emit(Instr::IMul(ba)),
+                    Prim2::SaturatingAdd => self.emit_saturating(Instr::Add, false, tmp, dst),
+                    Prim2::SaturatingSub => self.emit_saturating(Instr::Sub, false, tmp, dst),
+                    Prim2::SaturatingMul => self.emit_saturating(Instr::IMul, true, tmp, dst),
+                    Prim2::BitAnd => self.emit(Instr::And(ba)),
+                    Prim2::BitOr => self.emit(Instr::Or(ba)),
+                    Prim2::BitXor => self.emit(Instr::Xor(ba)),
+                    Prim2::Lt => emit_cc(ConditionCode::L),
+                    Prim2::Gt => emit_cc(ConditionCode::G),
+                    Prim2::Le => emit_cc(ConditionCode::LE),
+                    Prim2::Ge => emit_cc(ConditionCode::GE),
+                    Prim2::Eq => emit_cc(ConditionCode::E),
+                    Prim2::Neq => emit_cc(ConditionCode::NE),
+                }
+
+                // if the destination is not tmp, move tmp to the destination
+                match dst.as_spill() {
+                    Some(slot) => {
+                        // need to store the temporary result to the spilled slot
+                        self.emit(store_mem(slot, tmp));
+                    }
+                    None => {}
+                }
+            }
+            Operation::Call { fun, args } => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!(
+                        "    call {} = {}({})",
+                        dest,
+                        fun,
+                        args.iter()
+                            .map(|arg| arg.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )));
+                }
+                // emit the call, with the result written to dest
+                self.emit_stack_aligned_call(self.resolve(dest), fun.clone(), &args, after_live);
+            }
+            Operation::AllocateArray { len } => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!(
+                        "    allocate array to {} of size {}",
+                        dest, len
+                    )));
+                }
+                // dest = snake_new_array(len)
+                self.emit_stack_aligned_call(
+                    self.resolve(dest),
+                    FunName::unmangled("snake_new_array"),
+                    &[len.clone()],
+                    after_live,
+                );
+            }
+            Operation::Load { addr, offset: off } => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!(
+                        "    load {} = [{} + 8 * {}]",
+                        dest, addr, off
+                    )));
+                }
+                // rax = off
+                self.emit_imm(Allocation::Reg(Reg::Rax), off);
+                // rax = 8 * rax
+                self.emit(Instr::IMul(BinArgs::ToReg(Reg::Rax, Arg32::Signed(8))));
+                // rax = rax + addr (addr is never constant and is never temporary)
+                self.emit(Instr::Add(BinArgs::from_alloc(
+                    Reg::Rax,
+                    self.resolve_to_alloc(addr),
+                )));
+                // mov rax, [rax]
+                self.emit(Instr::Mov(MovArgs::ToReg(
+                    Reg::Rax,
+                    Arg64::Mem(MemRef {
+                        reg: Reg::Rax,
+                        offset: 0,
+                    }),
+                )));
+                // dest = rax
+                self.emit_reg_to_alloc(self.resolve(dest), Reg::Rax);
+            }
+            Operation::Str(lit) => {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!("    string {} = {}", dest, oper)));
+                }
+                // rax = the literal | 0b111
+                let label = self.string_label(lit);
+                self.emit(Instr::Lea(Reg::Rax, label));
+                self.emit(Instr::Or(BinArgs::ToReg(Reg::Rax, Arg32::Signed(STR_TAG))));
+                self.emit_reg_to_alloc(self.resolve(dest), Reg::Rax);
+            }
+        }
+    }
+
+    /// The label of the string literal `lit`, laid out as the header
+    /// `STR_FLAG | len` followed by the bytes of `lit`
+    fn string_label(&mut self, lit: &str) -> Label {
+        if let Some(label) = self.strings.get(lit) {
+            return *label;
+        }
+        let label = self.symbols.fresh("str");
+        self.string_data.push(Instr::Align(8));
+        self.string_data.push(Instr::Label(label));
+        let header = STR_FLAG | lit.len() as i64;
+        self.string_data.push(Instr::Quad(header));
+        if !lit.is_empty() {
+            self.string_data.push(Instr::Bytes(lit.as_bytes().to_vec()));
+        }
+        self.strings.insert(lit.to_string(), label);
+        label
+    }
+
+    /// Emits `tagged` as a conditional move of the tagged true over the
+    /// tagged false, instead of setting, shifting and tagging a byte
+    fn emit_cmov_comparison(&mut self, tagged: &TaggedComparison) {
+        let TaggedComparison {
+            dest,
+            cc,
+            args: (imm1, imm2),
+            by,
+            tag,
+            ..
+        } = *tagged;
+        if cfg!(debug_assertions) {
+            self.emit(Instr::Comment(format!(
+                "    operation {} = set{}({}, {}) << {} | {}",
+                dest, cc, imm1, imm2, by, tag
+            )));
+        }
+        let dst = self.resolve(dest);
+        // the operands as for any comparison, imm2 first since dest may be imm2
+        self.emit_imm(Allocation::Reg(Reg::Rax), imm2);
+        let tmp = dst.as_reg().unwrap_or(Reg::R10);
+        self.emit_imm(Allocation::Reg(tmp), imm1);
+        self.emit(Instr::Cmp(BinArgs::ToReg(tmp, Arg32::Reg(Reg::Rax))));
+        // tmp = false, rax = true (mov will not alter the flags)
+        self.emit(Instr::Mov(MovArgs::ToReg(tmp, Arg64::Signed(tag))));
+        self.emit(Instr::Mov(MovArgs::ToReg(
+            Reg::Rax,
+            Arg64::Signed(1 << by | tag),
+        )));
+        self.emit(Instr::CMovCC(cc, BinArgs::ToReg(tmp, Arg32::Reg(Reg::Rax))));
+        if let Some(slot) = dst.as_spill() {
+            self.emit(store_mem(slot, tmp));
+        }
+    }
+
+    /// Emits `cmp reg, 0` for a jump on `cc`, unless the instruction that
+    /// computed `reg` left the flags for it; the condition to jump on
+    fn emit_cmp_zero(&mut self, reg: Reg, cc: ConditionCode) -> ConditionCode {
+        if let Some(cc) = reuse_flags(&self.instrs, reg, cc) {
+            return cc;
+        }
+        self.emit(Instr::Cmp(BinArgs::ToReg(reg, Arg32::Signed(0))));
+        cc
+    }
+
+    fn emit_arith(&mut self, op: Instr) {
+        self.emit(op);
+        let handler = self.label(SnakeErr::ArithmeticOverflow);
+        self.emit(Instr::JCC(ConditionCode::O, handler));
+    }
+
+    /// Emits `tmp = tmp op rax`, clamped without branching to the bounds of
+    /// the tagged integers on overflow. The bound is picked up front from the
+    /// sign the exact result would have: that of the left operand for
+    /// addition and subtraction, and that of the product for multiplication.
+    fn emit_saturating(&mut self, op: fn(BinArgs) -> Instr, mul: bool, tmp: Reg, dst: Allocation) {
+        // the bound goes to r10, unless tmp is r10 because dest is spilled;
+        // then the right operand moves to the spill slot to free rax
+        let (bound, rhs) = match dst.as_spill() {
+            None => (Reg::R10, Arg32::Reg(Reg::Rax)),
+            Some(slot) => {
+                self.emit(store_mem(slot, Reg::Rax));
+                (
+                    Reg::Rax,
+                    Arg32::Mem(MemRef {
+                        reg: Reg::Rsp,
+                        offset: -8 * slot,
+                    }),
+                )
+            }
+        };
+        // mov bound, tmp
+        self.emit(Instr::Mov(MovArgs::ToReg(bound, Arg64::Reg(tmp))));
+        if mul {
+            // xor bound, rhs
+            self.emit(Instr::Xor(BinArgs::ToReg(bound, rhs)));
+        }
+        // turn the sign {0, -1} into {0x7ff..fe, 0x800..00} with 32-bit immediates:
+        // {0, 4}, then {-8, -4}, then {0x7ff..fc, 0x7ff..fe}
+        self.emit(Instr::Sar(ShArgs { reg: bound, by: 63 }));
+        self.emit(Instr::And(BinArgs::ToReg(bound, Arg32::Signed(4))));
+        self.emit(Instr::Add(BinArgs::ToReg(bound, Arg32::Signed(-8))));
+        self.emit(Instr::Shr(ShArgs { reg: bound, by: 1 }));
+        self.emit(Instr::Add(BinArgs::ToReg(bound, Arg32::Signed(2))));
+        // op tmp, rhs
+        self.emit(op(BinArgs::ToReg(tmp, rhs)));
+        // cmovo tmp, bound
+        self.emit(Instr::CMovCC(
+            ConditionCode::O,
+            BinArgs::ToReg(tmp, Arg32::Reg(bound)),
+        ));
+    }
+
+    fn emit_stack_aligned_call(
+        &mut self,
+        dest: Allocation,
+        fun: FunName,
+        args: &[Immediate<VarName>],
+        after_live: &LiveSet,
+    ) {
+        // 1. Save the live volatiles
+        let caller_saves: BTreeSet<Reg> = after_live
+            .iter()
+            .filter_map(|x| match self.resolve(x) {
+                imm @ Allocation::Reg(reg) if imm != dest && reg.is_volatile() => Some(reg),
+                _ => None,
+            })
+            .collect();
+        if cfg!(debug_assertions) {
+            if !caller_saves.is_empty() {
+                self.emit(Instr::Comment(
+                    "    saving volatile registers..".to_string(),
+                ));
+            }
+        }
+        for (i, r) in caller_saves.iter().enumerate() {
+            let slot = self.allocation.max_spill + (i as i32) + 1;
+            if cfg!(debug_assertions) {
+                self.emit(Instr::Comment(format!("        <{}> <- {}", slot, r)));
+            }
+            self.emit(store_mem(slot, *r));
+        }
+        if cfg!(debug_assertions) {
+            self.emit(Instr::Comment("    ..saved".to_string()));
+        }
+
+        let num_stack_args = args.len().saturating_sub(Reg::ARGS.len()) as i32;
+        let frame_size = {
+            let mut f = self.allocation.max_spill + (caller_saves.len() as i32) + num_stack_args;
+            f += if f % 2 == 1 { 0 } else { 1 };
+            f
+        };
+        if frame_size % 2 == 0 {
+            panic!("We were about to misalign the stack! in call {}", fun);
+        }
+        // 2. (simultaneously) Move the arguments into the place dictated by the calling convention
+        let mut dests: Vec<Allocation> = Vec::new();
+        // first args go in registers
+        for (reg, _) in Reg::ARGS.iter().zip(args.iter()) {
+            dests.push(Allocation::Reg(*reg));
+        }
+        // remaining go on the stack
+        for (i, _) in args.iter().skip(Reg::ARGS.len()).enumerate() {
+            dests.push(Allocation::Spill(frame_size - i as i32));
+        }
+        self.emit_simultaneous_move(
+            dests,
+            args.iter().map(|arg| self.resolve_imm(arg)).collect(),
+        );
+
+        // 3. Save the locals
+        self.emit(Instr::Sub(BinArgs::ToReg(
+            Reg::Rsp,
+            Arg32::Signed(8 * frame_size),
+        )));
+        // 4. call
+        let fun = self.label(&fun);
+        self.emit(Instr::Call(fun));
+        // 5. Restore rsp
+        self.emit(Instr::Add(BinArgs::ToReg(
+            Reg::Rsp,
+            Arg32::Signed(8 * frame_size),
+        )));
+        // 6. mov result to destination
+        self.emit_resolved_imm(dest, Immediate::Var(Allocation::Reg(Reg::Rax)));
+        // 7. Restore live volatiles
+        if cfg!(debug_assertions) {
+            if !caller_saves.is_empty() {
+                self.emit(Instr::Comment(
+                    "    restoring volatile registers..".to_string(),
+                ));
+            }
+        }
+        for (i, reg) in caller_saves.iter().enumerate() {
+            let slot = self.allocation.max_spill + (i as i32) + 1;
+            if cfg!(debug_assertions) {
+                self.emit(Instr::Comment(format!("        {} <- <{}>", reg, slot)));
+            }
+            self.emit(load_mem(*reg, slot));
+        }
+        if cfg!(debug_assertions) {
+            self.emit(Instr::Comment("    ..restored".to_string()));
+        }
+    }
+
+    // params are destinations..
+    // ..and args are sources
+    fn emit_simultaneous_move(
+        &mut self,
+        params: Vec<Allocation>,
+        args: Vec<Immediate<Allocation>>,
+    ) {
+        assert_eq!(params.len(), args.len());
+
+        // if there are no parameters, we're done
+        if params.is_empty() {
+            return;
+        }
+
+        if cfg!(debug_assertions) {
+            self.emit(Instr::Comment(format!("    simultaneously moving..")));
+        }
+        for (param, arg) in params.iter().zip(args.iter()) {
+            if cfg!(debug_assertions) {
+                self.emit(Instr::Comment(format!("        {} <- {}", param, arg)));
+            }
+        }
+
+        // move the arguments:
+        //   - from their current location
+        //   - to the body branch parameter allocations
+
+        // The algorithm is based on the following observation:
+        //   - each argument location may point to none or multiple parameters
+        //     in other words, the "source" locations are not necessarily distinct
+        //     (e.g. a <- c, b <- c)
+        //   - however, each parameter has only one possible argument value pointing to it
+        //     in other words, the "destination" is always unique and distinct from each other
+        //     (i.e. there can't be two parameters of the same location)
+        //   - therefore, we hope that we can find destinations that are safe to write to
+        //     (i.e. the destination doesn't have any incoming edges)
+        //     (i.e. the parameter location is not someone else's source)
+        //     we call these destinations "finals" and move them first
+        //   - after iteratively removing all finals, we are left with only cycles
+        //     (pigeon-hole principle)
+        //   - finally, we solve the cycles one by one with swaps
+
+        // 0: remove the trivial moves
+        let (params, args): (Vec<Allocation>, Vec<Immediate<Allocation>>) = params
+            .into_iter()
+            .zip(args.into_iter())
+            .filter(|(param, arg)| match arg {
+                // if the param <- arg is identity, skip the move
+                Immediate::Var(src) if param == src => {
+                    if cfg!(debug_assertions) {
+                        self.emit(Instr::Comment(format!("    identity: {}", param)));
+                    }
+                    false
+                }
+                _ => true,
+            })
+            .unzip();
+
+        // 1. analyze the dependencies w/ two graphs represented by maps
+        //    - (a) create a inward (destination) map of map(param <- arg)
+        //    - (b) create a outward (source) map of map(arg -> { param | param <- arg }) + map(param -> {})
+
+        // (a) each parameter has only one possible argument value pointing to it
+        let mut inward: BTreeMap<Allocation, _> =
+            params.iter().copied().zip(args.iter().cloned()).collect();
+        // (b) however, each argument location may point to none or multiple parameters
+        let mut outward: BTreeMap<Allocation, _> = {
+            let mut outward = BTreeMap::new();
+            for (arg, param) in args.iter().zip(params.iter()) {
+                outward.entry(*param).or_insert_with(BTreeSet::new);
+                match arg {
+                    Immediate::Var(src) => {
+                        outward
+                            .entry(*src)
+                            .or_insert_with(BTreeSet::new)
+                            .insert(param);
+                    }
+                    _ => {}
+                }
+            }
+            outward
+        };
+
+        // 2. iteratively find locs that are not sources, which have no out edges,
+        //    i.e. no value in the out entry
+        loop {
+            let finals = outward
+                .iter()
+                .filter_map(|(loc, out)| if out.is_empty() { Some(*loc) } else { None })
+                .collect::<Vec<_>>();
+            // 2.1 if there are no more finals, we're done with this step and leave with only cycles
+            if finals.is_empty() {
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment("    finished removing finals".to_string()));
+                }
+                break;
+            }
+            // 2.2 if there are finals, emit the moves
+            for f in finals {
+                // 2.2.1 remove final from inward and outward maps
+                // should safely remove because the entry is guaranteed to be empty
+                outward.remove(&f);
+                // the inward map holds the source of the removing final
+                match inward.remove(&f) {
+                    // the final is not a destination because it's only a source
+                    None => {}
+                    // the final is a destination because it's only a source
+                    Some(arg) => {
+                        // 2.2.2 emit the move here
+                        if cfg!(debug_assertions) {
+                            self.emit(Instr::Comment(format!(
+                                "    removing final {} <- {}",
+                                f, arg
+                            )));
+                        }
+                        self.emit_resolved_imm(f, arg);
+                        // 2.2.3 remove source of final (which is a destination) from outward map
+                        match arg {
+                            Immediate::Const(_) => {}
+                            Immediate::Var(loc) => {
+                                outward.get_mut(&loc).map(|out| out.remove(&f));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // 3. after all finals are removed, we are left with only cycles
+        //    - the cycles are now stored in inward
+        //    - so we just swap them one after another
+        if !inward.is_empty() {
+            if cfg!(debug_assertions) {
+                self.emit(Instr::Comment("    remaining cycles:".to_string()));
+            }
+
+            // 3.1 we gradually transform all cycles into sequences (of cycles)
+            let mut cycles = inward;
+            let mut seqs = Vec::new();
+            while let Some((start_dst, start_src)) =
+                cycles.iter().map(|(dst, src)| (*dst, src.clone())).next()
+            {
+                let mut seq = Vec::new();
+                cycles.remove(&start_dst);
+                seq.push(start_dst);
+                let mut next = start_src;
+                loop {
+                    let Immediate::Var(src) = next else {
+                        unreachable!()
+                    };
+                    seq.push(src);
+                    match cycles.remove(&src) {
+                        Some(arg) => next = arg,
+                        None => {
+                            break;
+                        }
+                    }
+                }
+                if cfg!(debug_assertions) {
+                    self.emit(Instr::Comment(format!(
+                        "        {}",
+                        seq.iter()
+                            .map(|v| v.to_string())
+                            .collect::<Vec<_>>()
+                            .join(" <- ")
+                    )));
+                }
+                seqs.push(seq);
+            }
+
+            // 3.2 emit the sequences with temporary register rax
+            for seq in seqs {
+                // the seq has at least two elements, otherwise it's not a cycle at all
+                // a typical cycle looks like:
+                //
+                //     a <- b <- c <- d <- a
+                //
+                // where a is the [0] and [-1] of the cycle
+
+                if seq.iter().all(|x| matches!(x, Allocation::Reg(_))) {
+                    // if all the allocations are registers, we can just use xchg to swap them
+                    use itertools::Itertools as _;
+                    let mut iter = seq.into_iter();
+                    // we basically remove and ignore the start of the cycle
+                    let Some(..) = iter.next() else {
+                        unreachable!()
+                    };
+                    for (a, b) in iter.tuple_windows() {
+                        let Allocation::Reg(a) = a else {
+                            unreachable!()
+                        };
+                        let Allocation::Reg(b) = b else {
+                            unreachable!()
+                        };
+                        self.emit(Instr::Xchg(a, b));
+                    }
+                } else {
+                    // otherwise, use r10 as a temporary swap register for efficiency
+                    let mut iter = seq.into_iter();
+                    // we basically remove and ignore the start of the cycle
+                    let Some(..) = iter.next() else {
+                        unreachable!()
+                    };
+                    // ..and begin with the second to last element
+                    let Some(mut curr) = iter.next() else {
+                        unreachable!()
+                    };
+                    // (must) use r10 as a temporary register;
+                    // not using rax because it's used by emit_alloc_to_alloc
+                    // r10 = curr
+                    self.emit_alloc_to_alloc(Allocation::Reg(Reg::R10), curr.clone());
+                    while let Some(next) = iter.next() {
+                        // curr = next
+                        self.emit_alloc_to_alloc(curr, next.clone());
+                        curr = next;
+                    }
+                    // curr = r10
+                    self.emit_reg_to_alloc(curr, Reg::R10);
+                }
+            }
+        }
+        if cfg!(debug_assertions) {
+            self.emit(Instr::Comment("    ..simultaneously moving".to_string()));
+        }
+    }
+
+    fn emit_reg_to_alloc(&mut self, dst: Allocation, src: Reg) {
+        // only move if the source and destination are different;
+        if dst != Allocation::Reg(src) {
+            self.emit(Instr::Mov(MovArgs::to_alloc(dst, src)))
+        }
+    }
+
+    fn emit_alloc_to_alloc(&mut self, dst: Allocation, src: Allocation) {
+        match (dst, src) {
+            (_, Allocation::Reg(src)) => self.emit_reg_to_alloc(dst, src),
+            // move from a spilled slot to a register
+            (Allocation::Reg(reg), Allocation::Spill(src)) => self.emit(load_mem(reg, src)),
+            // move from a spilled slot to a spilled slot
+            (Allocation::Spill(slot), Allocation::Spill(src)) => {
+                // first move the spilled value to rax
+                self.emit(Instr::Mov(MovArgs::ToReg(
+                    Reg::Rax,
+                    Arg64::Mem(MemRef {
+                        reg: Reg::Rsp,
+                        offset: -8 * src,
+                    }),
+                )));
+                // then move rax to the destination
+                self.emit(Instr::Mov(MovArgs::ToMem(
+                    MemRef {
+                        reg: Reg::Rsp,
+                        offset: -8 * slot,
+                    },
+                    Reg32::Reg(Reg::Rax),
+                )));
+            }
+        }
+    }
+
+    fn emit_imm(&mut self, dst: Allocation, imm: &Immediate<VarName>) {
+        let imm2 = self.resolve_imm(imm);
+        self.emit_resolved_imm(dst, imm2);
+    }
+    /// Emit an immediate value into a destination allocation.
+    /// The immediate is either an allocation (in register or spilled) or a constant.
+    /// The destination is either a register or a spilled slot.
+    fn emit_resolved_imm(&mut self, dst: Allocation, imm: Immediate<Allocation>) {
+        match imm {
+            Immediate::Var(x) => self.emit_alloc_to_alloc(dst, x),
+            Immediate::Const(u) => {
+                match dst {
+                    Allocation::Reg(reg) => self.emit(load_signed(reg, u)),
+                    Allocation::Spill(dst) => {
+                        // first move the constant to rax
+                        self.emit(load_signed(Reg::Rax, u));
+                        // then move rax to the destination
+                        self.emit(store_mem(dst, Reg::Rax));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The size of the code emitted for each function, i.e. its `FunBlock` and
+/// the toplevel block it branches to, with the blocks nested in it. Toplevel
+/// blocks of no function and the runtime error handlers get rows of their own.
+pub struct SizeReport(pub Vec<(String, usize, usize)>);
+
+/// The function that the code after each of the labels of `prog` belongs
+/// to, as `SizeReport` and `StackReport` attribute it
+fn owners<T>(prog: &Program<VarName, T>) -> HashMap<String, String> {
+    let mut owners = HashMap::new();
+    for fun in prog.funs.iter() {
+        owners.insert(fun.name.to_string(), fun.name.to_string());
+        owners.insert(fun.body.target.to_string(), fun.name.to_string());
+    }
+    for block in prog.blocks.iter() {
+        let label = block.label.to_string();
+        owners.entry(label.clone()).or_insert(label);
+    }
+    for i in 0..SnakeErr::COUNT {
+        owners.insert(
+            SnakeErr::from(i).to_string(),
+            "(error handlers)".to_string(),
+        );
+    }
+    owners
+}
+
+impl SizeReport {
+    pub fn new<T>(prog: &Program<VarName, T>, asm: &Asm) -> Self {
+        let owners = owners(prog);
+        let mut rows: Vec<(String, usize, usize)> = Vec::new();
+        let mut current = None;
+        for instr in asm.instrs.iter() {
+            let owner = match instr {
+                Instr::Label(label) => owners.get(asm.symbols.name(*label)),
+                _ => None,
+            };
+            if let Some(owner) = owner {
+                current = match rows.iter().position(|(name, ..)| name == owner) {
+                    Some(row) => Some(row),
+                    None => {
+                        rows.push((owner.clone(), 0, 0));
+                        Some(rows.len() - 1)
+                    }
+                };
+            }
+            let size = instr_size(instr);
+            if let (Some(row), true) = (current, size > 0) {
+                rows[row].1 += 1;
+                rows[row].2 += size;
+            }
+        }
+        Self(rows)
+    }
+}
+
+impl std::fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = (self.0.iter())
+            .map(|(name, ..)| name.len())
+            .chain(["function".len()])
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "{:width$}  {:>6}  {:>6}", "function", "instrs", "bytes")?;
+        for (name, instrs, bytes) in self.0.iter() {
+            writeln!(f, "{:width$}  {:>6}  {:>6}", name, instrs, bytes)?;
+        }
+        let instrs: usize = self.0.iter().map(|(_, instrs, _)| instrs).sum();
+        let bytes: usize = self.0.iter().map(|(.., bytes)| bytes).sum();
+        write!(f, "{:width$}  {:>6}  {:>6}", "total", instrs, bytes)
+    }
+}
+
+/// A bound on the bytes of stack that each function uses, from the code
+/// emitted for it: the slots below rsp that it addresses itself, and at
+/// each call the frame that it reserves, the return address and the bound
+/// of the callee. A jump into another function, i.e. a tail call, adds the
+/// bound of its target. The stack of the externs is left out, and a
+/// function that may recurse has no bound.
+pub struct StackReport {
+    /// Each function with the bytes of its own frame, its bound and
+    /// whether it calls externs, whose stack the bound leaves out
+    pub rows: Vec<(String, usize, Option<usize>, bool)>,
+    /// The functions that may call themselves
+    pub recursive: Vec<String>,
+}
+
+/// The calls of a function in the emitted code
+#[derive(Default)]
+struct StackFrame {
+    /// the bytes below rsp that the function addresses
+    frame: usize,
+    /// the callees with the bytes that rsp moves by to call them
+    callees: Vec<(String, usize)>,
+    /// the most that rsp moves by to call an extern, if the function does
+    externs: Option<usize>,
+}
+
+impl StackReport {
+    pub fn new<T>(prog: &Program<VarName, T>, asm: &Asm) -> Self {
+        let owners = owners(prog);
+        let mut frames: Vec<(String, StackFrame)> = Vec::new();
+        let mut current = None;
+        // the bytes that rsp was moved down by for the next call
+        let mut reserved: i64 = 0;
+        for instr in asm.instrs.iter() {
+            if let Instr::Label(label) = instr {
+                // the calls are emitted without labels in between
+                reserved = 0;
+                if let Some(owner) = owners.get(asm.symbols.name(*label)) {
+                    current = match frames.iter().position(|(name, _)| name == owner) {
+                        Some(row) => Some(row),
+                        None => {
+                            frames.push((owner.clone(), StackFrame::default()));
+                            Some(frames.len() - 1)
+                        }
+                    };
+                }
+            }
+            let Some(row) = current else { continue };
+            let (owner, frame) = &mut frames[row];
+            if let Some(MemRef {
+                reg: Reg::Rsp,
+                offset,
+            }) = mem_ref(instr)
+            {
+                frame.frame = frame.frame.max(-offset.min(0) as usize);
+            }
+            let callee = |label: &Label| match asm.symbols.binding(*label) {
+                Binding::Extern => None,
+                _ => Some(owners.get(asm.symbols.name(*label)).cloned()),
+            };
+            match instr {
+                Instr::Sub(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(n))) => reserved += *n as i64,
+                Instr::Add(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(n))) => reserved -= *n as i64,
+                Instr::Call(label) => {
+                    let reserved = reserved.max(0) as usize + 8;
+                    match callee(label) {
+                        Some(Some(callee)) => frame.callees.push((callee, reserved)),
+                        Some(None) => {}
+                        None => frame.externs = frame.externs.max(Some(reserved)),
+                    }
+                }
+                Instr::Jmp(label) | Instr::JCC(_, label) => {
+                    let reserved = reserved.max(0) as usize;
+                    match callee(label) {
+                        Some(Some(callee)) if callee != *owner => {
+                            frame.callees.push((callee, reserved))
+                        }
+                        Some(_) => {}
+                        None => frame.externs = frame.externs.max(Some(reserved)),
+                    }
+                }
+                _ => {}
+            }
+        }
+        // the calls between functions, by index
+        let index: HashMap<&str, usize> = frames
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| (name.as_str(), i))
+            .collect();
+        let mut calls: Vec<(usize, usize, usize)> = Vec::new();
+        for (i, (_, frame)) in frames.iter().enumerate() {
+            for (callee, reserved) in frame.callees.iter() {
+                if let Some(j) = index.get(callee.as_str()) {
+                    calls.push((i, *j, *reserved));
+                }
+            }
+        }
+        // the functions that each function reaches, itself included
+        let reaches = |from: usize| {
+            let mut seen = vec![false; frames.len()];
+            let mut stack = vec![from];
+            while let Some(i) = stack.pop() {
+                if !std::mem::replace(&mut seen[i], true) {
+                    stack.extend(calls.iter().filter(|(f, ..)| *f == i).map(|(_, g, _)| *g));
+                }
+            }
+            seen
+        };
+        let reached: Vec<Vec<bool>> = (0..frames.len()).map(reaches).collect();
+        // a call back into the caller grows the stack each time around,
+        // while tail calls back do not
+        let mut recursive: Vec<usize> = (calls.iter())
+            .filter(|(f, g, reserved)| *reserved > 0 && reached[*g][*f])
+            .map(|(f, ..)| *f)
+            .collect();
+        recursive.sort_unstable();
+        recursive.dedup();
+        let unbounded: Vec<bool> = (0..frames.len())
+            .map(|i| recursive.iter().any(|f| reached[i][*f]))
+            .collect();
+        // the longest chains of calls, which are acyclic but for tail calls
+        let mut bounds: Vec<usize> = (frames.iter())
+            .map(|(_, frame)| frame.frame.max(frame.externs.unwrap_or(0)))
+            .collect();
+        for _ in 0..frames.len() {
+            for (f, g, reserved) in calls.iter().filter(|(f, ..)| !unbounded[*f]) {
+                bounds[*f] = bounds[*f].max(bounds[*g] + reserved);
+            }
+        }
+        let rows = (frames.iter().enumerate())
+            .map(|(i, (name, frame))| {
+                let bound = (!unbounded[i]).then_some(bounds[i]);
+                let externs =
+                    (0..frames.len()).any(|j| reached[i][j] && frames[j].1.externs.is_some());
+                (name.clone(), frame.frame, bound, externs)
+            })
+            .collect();
+        let recursive = recursive.into_iter().map(|f| frames[f].0.clone()).collect();
+        StackReport { rows, recursive }
+    }
+}
+
+impl std::fmt::Display for StackReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = (self.rows.iter())
+            .map(|(name, ..)| name.len())
+            .chain(["function".len()])
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "{:width$}  {:>6}  {:>10}", "function", "frame", "bound")?;
+        for (name, frame, bound, externs) in self.rows.iter() {
+            let bound = match bound {
+                Some(bound) => format!("{}{}", bound, if *externs { "+" } else { " " }),
+                None => "unbounded".to_string(),
+            };
+            writeln!(f, "{:width$}  {:>6}  {:>10}", name, frame, bound)?;
+        }
+        if self
+            .rows
+            .iter()
+            .any(|(.., bound, externs)| bound.is_some() && *externs)
+        {
+            writeln!(f, "(+: and the stack of the externs called)")?;
+        }
+        for fun in self.recursive.iter() {
+            writeln!(f, "warning: {} may recurse, so its stack is unbounded", fun)?;
+        }
+        Ok(())
+    }
+}
+
+/// The register pressure of each function, i.e. the most variables live at
+/// once in the blocks that it may run, and whether it is a leaf, which makes
+/// no calls, as `LeafAnalysis` tells.
+pub struct PressureReport(pub Vec<(String, usize, bool)>);
+
+impl PressureReport {
+    pub fn new(prog: &Program<VarName, LiveSet>, leaves: &LeafAnalysis) -> Self {
+        fn pressure(block: &BasicBlock<VarName, LiveSet>) -> usize {
+            let mut most = block.ana.len();
+            let mut body = &block.body;
+            loop {
+                most = most.max(body.analysis().len());
+                if let BlockBody::SubBlocks { blocks, .. } = body {
+                    most = most.max(blocks.iter().map(pressure).max().unwrap_or(0));
+                }
+                match body.successor() {
+                    Some(next) => body = next,
+                    None => break most,
+                }
+            }
+        }
+        let blocks: HashMap<&BlockName, usize> = (prog.blocks.iter())
+            .map(|block| (&block.label, pressure(block)))
+            .collect();
+        let rows = (leaves.regions.iter())
+            .map(|(fun, region)| {
+                let most = region.iter().filter_map(|label| blocks.get(label)).max();
+                (
+                    fun.to_string(),
+                    most.copied().unwrap_or(0),
+                    leaves.is_leaf(fun),
+                )
+            })
+            .collect();
+        Self(rows)
+    }
+}
+
+impl std::fmt::Display for PressureReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = (self.0.iter())
+            .map(|(name, ..)| name.len())
+            .chain(["function".len()])
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "{:width$}  {:>8}  kind", "function", "pressure")?;
+        for (name, pressure, leaf) in self.0.iter() {
+            let kind = if *leaf { "leaf" } else { "calls" };
+            writeln!(f, "{:width$}  {:>8}  {}", name, pressure, kind)?;
+        }
+        Ok(())
+    }
+}
+
+/// Put the value of a signed constant into a register.
+fn load_signed(reg: Reg, val: i64) -> Instr {
+    Instr::Mov(MovArgs::ToReg(reg, Arg64::Signed(val)))
+}
+
+/// Put the value of a memory reference into a register.
+fn load_mem(reg: Reg, src: i32) -> Instr {
+    Instr::Mov(MovArgs::ToReg(
+        reg,
+        Arg64::Mem(MemRef {
+            reg: Reg::Rsp,
+            offset: -8 * src,
+        }),
+    ))
+}
+
+/// Flush the value of a register into a memory reference.
+/// the dst is viewed as a negative offset with a stride of 8.
+fn store_mem(dst: i32, reg: Reg) -> Instr {
+    Instr::Mov(MovArgs::ToMem(
+        MemRef {
+            reg: Reg::Rsp,
+            offset: -8 * dst,
+        },
+        Reg32::Reg(reg),
+    ))
+}
+fn Get_param(op: Operation<VarName>) -> Vec<Immediate<VarName>> {
+    let mut vec = vec![];
+    match op {
+        Operation::Prim1(_, imm) => {
+            vec.push(imm.clone());
+        }
+        Operation::Prim2(_, imm1, imm2) => {
+            vec.push(imm1.clone());
+            vec.push(imm2.clone());
+        }
+        Operation::Immediate(imm) => {
+            vec.push(imm.clone());
+        }
+        Operation::Call { fun, args } => {
+            vec = args.to_vec();
+        }
+        Operation::Load { addr, offset } => {
+            vec.push(addr.clone());
+            vec.push(offset.clone());
+        }
+        Operation::AllocateArray { len } => {
+            vec.push(len.clone());
+        }
+        Operation::Str(_) => {}
+    };
+    vec
+}
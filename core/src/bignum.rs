@@ -0,0 +1,186 @@
+//! Arbitrary-precision integers for `--bigint`. The runtime and the
+//! interpreters share this file, so that they agree on every result.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A sign and a magnitude of little-endian 32-bit limbs without leading
+/// zeros; zero is never negative.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Big {
+    pub neg: bool,
+    pub mag: Vec<u32>,
+}
+
+impl Big {
+    pub fn new(neg: bool, mut mag: Vec<u32>) -> Big {
+        while mag.last() == Some(&0) {
+            mag.pop();
+        }
+        Big { neg: neg && !mag.is_empty(), mag }
+    }
+
+    pub fn from_i64(n: i64) -> Big {
+        let m = n.unsigned_abs();
+        Big::new(n < 0, vec![m as u32, (m >> 32) as u32])
+    }
+
+    /// Parses an optionally signed decimal literal.
+    pub fn parse(s: &str) -> Option<Big> {
+        let (neg, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|d| d.is_ascii_digit()) {
+            return None;
+        }
+        let mut mag = Vec::new();
+        for d in digits.bytes() {
+            mul_small_add(&mut mag, 10, (d - b'0') as u32);
+        }
+        Some(Big::new(neg, mag))
+    }
+
+    /// The value if it fits in the 63 bits of a tagged integer.
+    pub fn to_small(&self) -> Option<i64> {
+        if self.mag.len() > 2 {
+            return None;
+        }
+        let m = self.mag.iter().rev().fold(0u64, |acc, &limb| acc << 32 | limb as u64);
+        if self.neg && m <= 1 << 62 {
+            Some((m as i64).wrapping_neg())
+        } else if !self.neg && m < 1 << 62 {
+            Some(m as i64)
+        } else {
+            None
+        }
+    }
+
+    pub fn negate(&self) -> Big {
+        Big::new(!self.neg, self.mag.clone())
+    }
+
+    pub fn add(&self, other: &Big) -> Big {
+        if self.neg == other.neg {
+            return Big::new(self.neg, add_mag(&self.mag, &other.mag));
+        }
+        match cmp_mag(&self.mag, &other.mag) {
+            Ordering::Less => Big::new(other.neg, sub_mag(&other.mag, &self.mag)),
+            _ => Big::new(self.neg, sub_mag(&self.mag, &other.mag)),
+        }
+    }
+
+    pub fn sub(&self, other: &Big) -> Big {
+        self.add(&other.negate())
+    }
+
+    pub fn mul(&self, other: &Big) -> Big {
+        let mut mag = vec![0u32; self.mag.len() + other.mag.len()];
+        for (i, &a) in self.mag.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.mag.iter().enumerate() {
+                let t = mag[i + j] as u64 + a as u64 * b as u64 + carry;
+                mag[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            mag[i + other.mag.len()] = carry as u32;
+        }
+        Big::new(self.neg != other.neg, mag)
+    }
+
+    /// The digits of the magnitude in `base`, most significant first.
+    pub fn digits(&self, base: u32) -> Vec<u32> {
+        let mut mag = self.mag.clone();
+        let mut digits = Vec::new();
+        while !mag.is_empty() {
+            let mut rem = 0u64;
+            for limb in mag.iter_mut().rev() {
+                let cur = rem << 32 | *limb as u64;
+                *limb = (cur / base as u64) as u32;
+                rem = cur % base as u64;
+            }
+            while mag.last() == Some(&0) {
+                mag.pop();
+            }
+            digits.push(rem as u32);
+        }
+        if digits.is_empty() {
+            digits.push(0);
+        }
+        digits.reverse();
+        digits
+    }
+}
+
+fn mul_small_add(mag: &mut Vec<u32>, factor: u32, addend: u32) {
+    let mut carry = addend as u64;
+    for limb in mag.iter_mut() {
+        let t = *limb as u64 * factor as u64 + carry;
+        *limb = t as u32;
+        carry = t >> 32;
+    }
+    if carry != 0 {
+        mag.push(carry as u32);
+    }
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    let mut mag = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let t = limb as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        mag.push(t as u32);
+        carry = t >> 32;
+    }
+    mag.push(carry as u32);
+    mag
+}
+
+/// a - b, where |a| >= |b|
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut mag = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut t = limb as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        borrow = (t < 0) as i64;
+        if t < 0 {
+            t += 1 << 32;
+        }
+        mag.push(t as u32);
+    }
+    mag
+}
+
+impl Ord for Big {
+    fn cmp(&self, other: &Big) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for Big {
+    fn partial_cmp(&self, other: &Big) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Big {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.digits(1_000_000_000);
+        write!(f, "{}{}", if self.neg { "-" } else { "" }, digits[0])?;
+        for d in &digits[1..] {
+            write!(f, "{:09}", d)?;
+        }
+        Ok(())
+    }
+}
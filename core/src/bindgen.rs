@@ -0,0 +1,578 @@
+//! Runtime skeletons for `snake gen-runtime`, and the interfaces of programs
+//! for `snake bindgen`.
+//!
+//! A compiled program calls its externs and the services of the runtime by
+//! their symbols. The skeleton defines each of them as a Rust function with
+//! the right symbol and the types of the extern's signature, so that a custom
+//! runtime starts from code that compiles and links. The externs are left
+//! `todo!()`, while the allocator, the errors and `main` work like those of
+//! `runtime/stub.rs`. The skeleton is a single file for `--runtime`.
+//!
+//! The interface of a program declares `entry` and the same functions for a
+//! host in another language that calls the compiled code instead, or wraps
+//! the shared object of such a host for Python.
+
+use crate::ast::*;
+use crate::doc::extern_signature;
+use crate::identifiers::VarName;
+use crate::ssa::Extern;
+
+/// The runtime functions that the skeleton implements
+const IMPLEMENTED: [&str; 2] = ["snake_error", "snake_new_array"];
+
+/// The Rust keywords, which identifiers of snake may be
+const KEYWORDS: [&str; 50] = [
+    "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual",
+];
+
+/// The skeleton of the runtime of `prog`, named `title`. `externs` are the
+/// externs of its SSA, which add the services that its builtins call.
+pub fn rust_runtime(title: &str, prog: &SurfProg, externs: &[Extern<VarName>]) -> String {
+    let mut out = format!(
+        "//! The runtime of {}, generated by `snake gen-runtime`. Implement the\n\
+         //! externs below and link it with `snake -t exe --runtime <this file>`.\n",
+        title
+    );
+    out.push_str(PRELUDE);
+    out.push_str(EXTERNS);
+    out.push_str("mod externs {\n    use super::*;\n");
+    for ext in prog.externs.iter() {
+        out.push('\n');
+        for line in ext.doc.iter().flat_map(|doc| doc.lines()) {
+            out.push_str(&format!("    ///{}{}\n", if line.is_empty() { "" } else { " " }, line));
+        }
+        out.push_str(&format!("    // {}\n", extern_signature(ext)));
+        let params = ext.params.iter().enumerate().map(|(i, param)| {
+            let ty = ext.signature.params.get(i).copied().flatten();
+            format!("{}: {}", ident(&param.node), param_type(ty))
+        });
+        let params: Vec<_> = params.collect();
+        let ret = return_type(ext.signature.ret);
+        function(&mut out, "    ", &ext.name, &params.join(", "), ret);
+    }
+    out.push_str("}\n");
+    let declared = |name: &str| prog.externs.iter().any(|ext| ext.name == name);
+    let services = externs.iter().map(|ext| ext.name.hint()).filter(|name| {
+        name.starts_with("snake_") && !IMPLEMENTED.contains(name) && !declared(name)
+    });
+    let mut services: Vec<_> = services.collect();
+    if !services.is_empty() {
+        out.push_str(SERVICES);
+        services.sort_unstable();
+        services.dedup();
+        for name in services {
+            let ext = externs.iter().find(|ext| ext.name.hint() == name).unwrap();
+            let params: Vec<_> =
+                (0..ext.params.len()).map(|i| format!("arg{}: SnakeValue", i)).collect();
+            out.push('\n');
+            function(&mut out, "", name, &params.join(", "), "SnakeValue");
+        }
+    }
+    out.push_str(RUNTIME);
+    out
+}
+
+/// Defines the function of the symbol `name`, to be implemented
+fn function(out: &mut String, indent: &str, name: &str, params: &str, ret: &str) {
+    out.push_str(&format!("{}#[export_name = \"\\x01{}\"]\n", indent, name));
+    out.push_str(&format!(
+        "{0}pub extern \"sysv64\" fn {1}({2}) -> {3} {{\n{0}    todo!(\"{4}\")\n{0}}}\n",
+        indent,
+        ident(name),
+        params,
+        ret,
+        name
+    ));
+}
+
+/// `name` as a Rust identifier
+fn ident(name: &str) -> String {
+    match name {
+        // keywords that cannot be raw identifiers
+        "_" | "crate" | "self" | "Self" | "super" => format!("{}_", name),
+        name if KEYWORDS.contains(&name) => format!("r#{}", name),
+        name => name.to_string(),
+    }
+}
+
+/// The Rust type of the values that the shim of a typed extern passes
+fn param_type(ty: Option<Type>) -> &'static str {
+    match ty {
+        None => "SnakeValue",
+        Some(Type::Int | Type::Bool) => "i64",
+        Some(Type::Array) => "*const u64",
+    }
+}
+
+fn return_type(ty: Option<Type>) -> &'static str {
+    match ty {
+        None => "SnakeValue",
+        Some(Type::Int | Type::Bool) => "i64",
+        Some(Type::Array) => "*mut u64",
+    }
+}
+
+const PRELUDE: &str = r#"#![allow(unused)]
+#![allow(static_mut_refs)]
+
+/* --------------------------- Data Representation -------------------------- */
+
+/// A tagged value: integers end in 0, booleans in 01 and arrays in 011
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SnakeValue(pub u64);
+
+pub const SNAKE_TRU: SnakeValue = SnakeValue(0b101);
+pub const SNAKE_FLS: SnakeValue = SnakeValue(0b001);
+/// `none`, the tag of big integers on a null pointer
+pub const SNAKE_NONE: SnakeValue = SnakeValue(0b111);
+
+/// The integer `v`, if it is one
+pub fn as_int(v: SnakeValue) -> Option<i64> {
+    (v.0 & 0b1 == 0).then(|| v.0 as i64 >> 1)
+}
+
+/// `n` tagged, if it fits in 63 bits
+pub fn from_int(n: i64) -> Option<SnakeValue> {
+    n.checked_mul(2).map(|n| SnakeValue(n as u64))
+}
+
+/// The boolean `v`, if it is one
+pub fn as_bool(v: SnakeValue) -> Option<bool> {
+    match v {
+        SNAKE_TRU => Some(true),
+        SNAKE_FLS => Some(false),
+        _ => None,
+    }
+}
+
+pub fn from_bool(b: bool) -> SnakeValue {
+    if b {
+        SNAKE_TRU
+    } else {
+        SNAKE_FLS
+    }
+}
+
+/// The length word of the array `v`, if it is one, as typed externs take it
+pub fn as_array(v: SnakeValue) -> Option<*const u64> {
+    (v.0 & 0b111 == 0b011).then(|| (v.0 ^ 0b011) as *const u64)
+}
+
+/// The array of the length word at `ptr` tagged, as typed externs return it
+pub fn from_array(ptr: *mut u64) -> SnakeValue {
+    SnakeValue(ptr as u64 | 0b011)
+}
+
+/// The elements of the array of the length word at `ptr`
+pub fn elements<'a>(ptr: *const u64) -> &'a mut [SnakeValue] {
+    unsafe { std::slice::from_raw_parts_mut(ptr.add(1) as *mut SnakeValue, *ptr as usize) }
+}
+
+fn sprint(v: SnakeValue, parents: &mut Vec<*const u64>) -> String {
+    if let Some(n) = as_int(v) {
+        n.to_string()
+    } else if let Some(b) = as_bool(v) {
+        b.to_string()
+    } else if let Some(ptr) = as_array(v) {
+        if parents.contains(&ptr) {
+            return "<loop>".to_string();
+        }
+        parents.push(ptr);
+        let elts: Vec<_> = elements(ptr).iter().map(|elt| sprint(*elt, parents)).collect();
+        parents.pop();
+        format!("[{}]", elts.join(", "))
+    } else {
+        format!("<unknown value {:#x}>", v.0)
+    }
+}
+
+/// `v` as snake prints it
+pub fn sprint_snake_val(v: SnakeValue) -> String {
+    sprint(v, &mut Vec::new())
+}
+"#;
+
+const EXTERNS: &str = r#"
+/* --------------------------- External Functions --------------------------- */
+
+/* Typed parameters and results are untagged: integers and booleans are
+ * machine integers, where false is 0, and arrays point to their length word.
+ * The others are tagged values.
+**/
+"#;
+
+const SERVICES: &str = r#"
+/* ----------------------------- Runtime Services --------------------------- */
+
+/* The builtins of the program call these, with and on tagged values. */
+"#;
+
+const RUNTIME: &str = r#"
+/* ---------------------------- Memory and Errors --------------------------- */
+
+const HEAP_SIZE: usize = 100000;
+static mut HEAP: [u64; HEAP_SIZE] = [0; HEAP_SIZE];
+static mut HEAP_USED: usize = 0;
+
+#[export_name = "\x01snake_new_array"]
+pub extern "sysv64" fn snake_new_array(len: u64) -> *mut u64 {
+    unsafe {
+        let start = HEAP_USED;
+        if start + len as usize + 1 > HEAP_SIZE {
+            eprintln!("out of memory");
+            std::process::exit(1);
+        }
+        HEAP_USED += len as usize + 1;
+        let ptr = HEAP.as_mut_ptr().add(start);
+        *ptr = len;
+        ptr
+    }
+}
+
+#[export_name = "\x01snake_error"]
+pub extern "sysv64" fn snake_error(code: u64, v: SnakeValue) -> SnakeValue {
+    let v = sprint_snake_val(v);
+    match code {
+        0 => eprintln!("arithmetic operation overflowed"),
+        1 => eprintln!("expected a number, got {}", v),
+        2 => eprintln!("expected a boolean, got {}", v),
+        3 => eprintln!("expected an array, got {}", v),
+        4 => eprintln!("length {} is negative", v),
+        5 => eprintln!("index {} out of bounds", v),
+        _ => eprintln!("error {} on {}", code, v),
+    }
+    std::process::exit(1)
+}
+
+/* ------------------------------- Entry Point ------------------------------ */
+
+#[link(name = "compiled_code", kind = "static")]
+extern "sysv64" {
+    #[link_name = "\x01entry"]
+    fn entry(param: SnakeValue) -> SnakeValue;
+}
+
+fn main() {
+    let args: Vec<SnakeValue> = std::env::args()
+        .skip(1)
+        .map(|arg| match arg.trim() {
+            "true" => SNAKE_TRU,
+            "false" => SNAKE_FLS,
+            n => n.parse().ok().and_then(from_int).expect("invalid argument"),
+        })
+        .collect();
+    let ptr = snake_new_array(args.len() as u64);
+    elements(ptr).copy_from_slice(&args);
+    let output = unsafe { entry(from_array(ptr)) };
+    println!("{}", sprint_snake_val(output));
+}
+"#;
+
+/// The C keywords, which identifiers of snake may be
+const C_KEYWORDS: &str = "_Alignas _Alignof _Atomic _Bool _Complex _Generic _Imaginary \
+    _Noreturn _Static_assert _Thread_local auto bool break case char const continue default do \
+    double else enum extern false float for goto if inline int long register restrict return \
+    short signed sizeof static struct switch true typedef union unsigned void volatile while";
+
+/// The C header of `prog`, named `title`, for hosts that link the object
+/// assembled from its `-t asm`: the tagged values with the macros that tag
+/// and untag them, `entry`, and the externs and services that the host
+/// defines. `externs` are the externs of its SSA, as for `rust_runtime`.
+pub fn c_header(title: &str, prog: &SurfProg, externs: &[Extern<VarName>]) -> String {
+    let guard: String = title
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    let mut out = format!(
+        "/* The interface of {}, generated by `snake bindgen --lang c`.\n \
+         * Link the object assembled from `snake -t asm` with a host that\n \
+         * defines the externs below. */\n\
+         #ifndef SNAKE_{1}_H\n#define SNAKE_{1}_H\n",
+        title, guard
+    );
+    out.push_str(C_PRELUDE);
+    out.push_str(C_COMPILED);
+    for line in prog.doc.iter().flat_map(|doc| doc.lines()) {
+        out.push_str(&format!("/*{}{} */\n", if line.is_empty() { "" } else { " " }, line));
+    }
+    out.push_str(&format!(
+        "/* `{}`, on the array of the arguments */\n\
+         snake_value snake_entry(snake_value {}) __asm__(\"entry\");\n",
+        prog.name,
+        c_ident(&prog.param.node)
+    ));
+    out.push_str(C_EXTERNS);
+    for ext in prog.externs.iter() {
+        out.push('\n');
+        for line in ext.doc.iter().flat_map(|doc| doc.lines()) {
+            out.push_str(&format!("/*{}{} */\n", if line.is_empty() { "" } else { " " }, line));
+        }
+        out.push_str(&format!("/* {} */\n", extern_signature(ext)));
+        let params = ext.params.iter().enumerate().map(|(i, param)| {
+            let ty = ext.signature.params.get(i).copied().flatten();
+            c_decl(c_param_type(ty), &c_ident(&param.node))
+        });
+        let params: Vec<_> = params.collect();
+        let ret = c_return_type(ext.signature.ret);
+        c_function(&mut out, &ext.name, &params.join(", "), ret);
+    }
+    let declared = |name: &str| prog.externs.iter().any(|ext| ext.name == name);
+    let services = externs.iter().filter(|ext| {
+        let name = ext.name.hint();
+        name.starts_with("snake_") && !IMPLEMENTED.contains(&name) && !declared(name)
+    });
+    let mut services: Vec<_> = services.collect();
+    services.sort_unstable_by_key(|ext| ext.name.hint());
+    services.dedup_by_key(|ext| ext.name.hint());
+    out.push_str(C_SERVICES);
+    for ext in services {
+        let params: Vec<_> =
+            (0..ext.params.len()).map(|i| c_decl("snake_value", &format!("arg{}", i))).collect();
+        out.push('\n');
+        c_function(&mut out, ext.name.hint(), &params.join(", "), "snake_value");
+    }
+    out.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif\n");
+    out
+}
+
+/// Declares the function of the symbol `name`, which the host defines
+fn c_function(out: &mut String, name: &str, params: &str, ret: &str) {
+    let params = if params.is_empty() { "void" } else { params };
+    let decl = c_decl(ret, &format!("{}({})", c_ident(name), params));
+    out.push_str(&format!("{} __asm__(\"{}\");\n", decl, name));
+}
+
+/// Declares `declarator` of type `ty`, where pointers bind to the declarator
+fn c_decl(ty: &str, declarator: &str) -> String {
+    match ty.ends_with('*') {
+        true => format!("{}{}", ty, declarator),
+        false => format!("{} {}", ty, declarator),
+    }
+}
+
+/// `name` as a C identifier
+fn c_ident(name: &str) -> String {
+    match C_KEYWORDS.split_whitespace().any(|keyword| keyword == name) {
+        true => format!("{}_", name),
+        false => name.to_string(),
+    }
+}
+
+/// The C type of the values that the shim of a typed extern passes
+fn c_param_type(ty: Option<Type>) -> &'static str {
+    match ty {
+        None => "snake_value",
+        Some(Type::Int | Type::Bool) => "int64_t",
+        Some(Type::Array) => "const uint64_t *",
+    }
+}
+
+fn c_return_type(ty: Option<Type>) -> &'static str {
+    match ty {
+        None => "snake_value",
+        Some(Type::Int | Type::Bool) => "int64_t",
+        Some(Type::Array) => "uint64_t *",
+    }
+}
+
+const C_PRELUDE: &str = r#"
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* --------------------------- Data Representation -------------------------- */
+
+/* A tagged value: integers end in 0, booleans in 01 and arrays in 011 */
+typedef uint64_t snake_value;
+
+#define SNAKE_TRUE ((snake_value)0x5)
+#define SNAKE_FALSE ((snake_value)0x1)
+/* `none`, the tag of big integers on a null pointer */
+#define SNAKE_NONE ((snake_value)0x7)
+
+#define SNAKE_IS_INT(v) (((v) & 0x1) == 0)
+#define SNAKE_IS_BOOL(v) ((v) == SNAKE_TRUE || (v) == SNAKE_FALSE)
+#define SNAKE_IS_ARRAY(v) (((v) & 0x7) == 0x3)
+
+/* `n` tagged, which must fit in 63 bits, and back */
+#define SNAKE_FROM_INT(n) ((snake_value)((uint64_t)(int64_t)(n) << 1))
+#define SNAKE_AS_INT(v) ((int64_t)(v) >> 1)
+
+#define SNAKE_FROM_BOOL(b) ((b) ? SNAKE_TRUE : SNAKE_FALSE)
+#define SNAKE_AS_BOOL(v) ((v) == SNAKE_TRUE)
+
+/* The array of the length word at `p` tagged, as typed externs return it,
+ * and back; the elements follow the length word */
+#define SNAKE_FROM_ARRAY(p) ((snake_value)(uintptr_t)(p) | 0x3)
+#define SNAKE_AS_ARRAY(v) ((uint64_t *)(uintptr_t)((v) ^ 0x3))
+#define SNAKE_LENGTH(v) (SNAKE_AS_ARRAY(v)[0])
+#define SNAKE_ELEMENTS(v) ((snake_value *)(SNAKE_AS_ARRAY(v) + 1))
+"#;
+
+const C_COMPILED: &str = r#"
+/* --------------------------- Compiled Functions --------------------------- */
+
+"#;
+
+const C_EXTERNS: &str = r#"
+/* --------------------------- External Functions --------------------------- */
+
+/* Typed parameters and results are untagged: integers and booleans are
+ * machine integers, where false is 0, and arrays point to their length word.
+ * The others are tagged values.
+ */
+"#;
+
+const C_SERVICES: &str = r#"
+/* ----------------------------- Runtime Services --------------------------- */
+
+/* The host allocates the arrays, each a length word followed by the elements,
+ * and reports the errors, whose codes are those of `runtime/stub.rs`; the
+ * builtins of the program call the others, with and on tagged values. */
+uint64_t *snake_new_array(uint64_t len) __asm__("snake_new_array");
+snake_value snake_error(uint64_t code, snake_value v) __asm__("snake_error");
+"#;
+
+/// The Python keywords and the members of `Program`, which the names of
+/// snake programs may be
+const PYTHON_RESERVED: &str = "False None True and as assert async await break class continue \
+    def del elif else except finally for from global if import in is lambda nonlocal not or pass \
+    raise return try while with yield __init__ lib to_snake _new_array _entry";
+
+/// The Python bindings of `prog`, named `title`: a module that loads the
+/// shared object linked from its `-t asm` and a host that defines the
+/// functions of `c_header`, and calls `entry` on Python values, converting
+/// ints, bools and lists to tagged values and back. `externs` are the externs
+/// of its SSA, as for `rust_runtime`.
+pub fn python_module(title: &str, prog: &SurfProg, externs: &[Extern<VarName>]) -> String {
+    let mut out =
+        format!("\"\"\"The bindings of {}, generated by `snake bindgen --lang python`.\n", title);
+    out.push_str(PYTHON_USAGE);
+    if let Some(doc) = &prog.doc {
+        out.push('\n');
+        // a `"` before the closing `"""` would end the docstring early
+        let doc = doc.replace('\\', "\\\\").replace('"', "\\\"");
+        doc.lines().for_each(|line| out.push_str(&format!("{}\n", line)));
+    }
+    out.push_str("\"\"\"\n");
+    out.push_str(PYTHON_PRELUDE);
+    // the symbols that the host defines
+    let mut symbols: Vec<&str> = prog.externs.iter().map(|ext| ext.name.as_str()).collect();
+    symbols.extend(IMPLEMENTED);
+    symbols.extend(
+        (externs.iter().map(|ext| ext.name.hint())).filter(|name| name.starts_with("snake_")),
+    );
+    symbols.sort_unstable();
+    symbols.dedup();
+    let symbols: Vec<_> = symbols.iter().map(|name| format!("\"{}\"", name)).collect();
+    out.push_str(&format!(
+        "\n# The symbols that the host defines\nHOST = [{}]\n",
+        symbols.join(", ")
+    ));
+    out.push_str(PYTHON_PROGRAM);
+    out.push_str(&format!(
+        "\n    def {}(self, *args):\n        \"\"\"Runs `{}` on `args`, whose result it returns\"\"\"\n        \
+         return from_snake(self._entry(self.to_snake(list(args))))\n",
+        python_ident(&prog.name),
+        prog.name
+    ));
+    out
+}
+
+/// `name` as the name of a method of `Program`
+fn python_ident(name: &str) -> String {
+    match PYTHON_RESERVED.split_whitespace().any(|reserved| reserved == name) {
+        true => format!("{}_", name),
+        false => name.to_string(),
+    }
+}
+
+const PYTHON_USAGE: &str = r#"
+Assemble the program with `snake -t asm` and link it into a shared object
+with a host that defines the functions of `snake bindgen --lang c`, e.g.
+
+    nasm -felf64 prog.s -o prog.o
+    cc -shared -fPIC prog.o host.c -o libprog.so
+
+then `Program("./libprog.so").main(1, [2, True])` runs it. The host reports
+the errors of the program, as its `snake_error` does.
+"#;
+
+const PYTHON_PRELUDE: &str = r#"
+import ctypes
+
+# A tagged value: integers end in 0, booleans in 01 and arrays in 011
+TRUE = 0b101
+FALSE = 0b001
+WORD = 1 << 64
+
+
+def from_int(n):
+    """`n` tagged, if it fits in 63 bits"""
+    if not -(1 << 62) <= n < 1 << 62:
+        raise OverflowError("{} does not fit in 63 bits".format(n))
+    return (n << 1) % WORD
+
+
+def as_int(v):
+    return (v - WORD if v >= WORD >> 1 else v) >> 1
+
+
+def from_snake(v, arrays=None):
+    """The Python value of the tagged `v`, with lists for arrays; an array
+    that contains itself gives a list that does"""
+    arrays = {} if arrays is None else arrays
+    if v & 0b1 == 0:
+        return as_int(v)
+    if v == TRUE or v == FALSE:
+        return v == TRUE
+    if v & 0b111 == 0b011:
+        ptr = v ^ 0b011
+        if ptr in arrays:
+            return arrays[ptr]
+        length = ctypes.c_uint64.from_address(ptr).value
+        words = (ctypes.c_uint64 * length).from_address(ptr + 8)
+        arrays[ptr] = elements = []
+        elements.extend(from_snake(word, arrays) for word in words)
+        return elements
+    raise ValueError("unknown value {:#x}".format(v))
+"#;
+
+const PYTHON_PROGRAM: &str = r#"
+
+class Program:
+    """The program in the shared object at `path`"""
+
+    def __init__(self, path):
+        self.lib = ctypes.CDLL(path)
+        missing = [name for name in HOST + ["entry"] if not hasattr(self.lib, name)]
+        if missing:
+            raise ImportError("{} does not define {}".format(path, ", ".join(missing)))
+        self._new_array = self.lib.snake_new_array
+        self._new_array.argtypes = [ctypes.c_uint64]
+        self._new_array.restype = ctypes.c_uint64
+        self._entry = self.lib.entry
+        self._entry.argtypes = [ctypes.c_uint64]
+        self._entry.restype = ctypes.c_uint64
+
+    def to_snake(self, value):
+        """`value` tagged, allocating lists and tuples as arrays in the host"""
+        if isinstance(value, bool):
+            return TRUE if value else FALSE
+        if isinstance(value, int):
+            return from_int(value)
+        if isinstance(value, (list, tuple)):
+            ptr = self._new_array(len(value))
+            words = (ctypes.c_uint64 * len(value)).from_address(ptr + 8)
+            for i, element in enumerate(value):
+                words[i] = self.to_snake(element)
+            return ptr | 0b011
+        raise TypeError("{!r} has no snake value".format(value))
+"#;
@@ -0,0 +1,283 @@
+use crate::ana::Nil;
+use crate::asm::{Asm, Fragment};
+use crate::ast::{BoundProg, Expr, SurfProg};
+use crate::conf::{CompilerConf, FramePointer, Limits, Optimization, Verbosity};
+use crate::error::{Error, LinkError, ParseError};
+use crate::frontend::Resolver;
+use crate::identifiers::{NameSupply, VarName};
+use crate::middle_end::Lowerer;
+use crate::nodes;
+use crate::parser::{ExprParser, ProgParser};
+use crate::ssa::Program;
+use std::collections::HashSet;
+
+/// compiler pipeline
+pub fn compile(s: &str) -> Result<String, Error> {
+    let (resolver, resolved_ast) = frontend(s)?;
+    let (lowerer, ssa) = middle_end(resolver, resolved_ast)?;
+    let asm = backend(lowerer, ssa);
+    Ok(asm)
+}
+
+/// Parsing
+pub fn parse(s: &str) -> Result<SurfProg, ParseError> {
+    parse_with(s, &Limits::default())
+}
+
+/// Parsing, rejecting programs beyond `limits`, with the nodes numbered
+pub fn parse_with(s: &str, limits: &Limits) -> Result<SurfProg, ParseError> {
+    if s.len() > limits.size {
+        let message = format!("program of {} bytes exceeds the limit of {}", s.len(), limits.size);
+        return Err(ParseError { message, loc: None });
+    }
+    let mut prog = ProgParser::new().parse(s).map_err(|e| ParseError::new(e, s))?;
+    let deep = prog.body.nested_beyond(limits.depth).map(|e| e.loc());
+    if let Some(loc) = deep {
+        std::mem::replace(&mut prog.body, Expr::Bool(false, loc)).dismantle();
+        let message = format!(
+            "expression nested deeper than the limit of {} at {}",
+            limits.depth, loc.start_ix
+        );
+        return Err(ParseError { message, loc: Some(loc) });
+    }
+    nodes::number(&mut prog);
+    Ok(prog)
+}
+
+/// Frontend, parsing and validation
+pub fn frontend(s: &str) -> Result<(Resolver, BoundProg), Error> {
+    frontend_with(s, Resolver::new())
+}
+
+/// Frontend, parsing and validation with a configured `resolver`
+pub fn frontend_with(s: &str, mut resolver: Resolver) -> Result<(Resolver, BoundProg), Error> {
+    let resolved_ast = resolver.resolve_prog(parse(s)?)?;
+    Ok((resolver, resolved_ast))
+}
+
+/// Middle-end, lambda lifting and SSA construction
+pub fn middle_end(
+    resolver: Resolver, resolved_ast: BoundProg,
+) -> Result<(Lowerer, Program<VarName, Nil>), Error> {
+    use crate::middle_end::{AssertionRemover, ConditionCanonicalizer, CopyPropagator};
+    let mut lowerer = Lowerer::from(resolver);
+    let ssa = lowerer.lower_prog(resolved_ast);
+    let ssa = ConditionCanonicalizer::new().run(ssa);
+    let ssa = CopyPropagator::new().run(ssa);
+    let ssa = AssertionRemover::new(&ssa).optimize(ssa);
+    Ok((lowerer, ssa))
+}
+
+/// Middle-end, lowering followed by only the selected SSA optimizations
+pub fn middle_end_with(
+    resolver: Resolver, resolved_ast: BoundProg, optimizations: &HashSet<Optimization>,
+) -> Result<(Lowerer, Program<VarName, Nil>), Error> {
+    let mut lowerer = Lowerer::from(resolver);
+    let ssa = lowerer.lower_prog(resolved_ast);
+    Ok(optimize(lowerer, ssa, optimizations))
+}
+
+/// The selected SSA optimizations, in the order they run in
+fn optimize(
+    mut lowerer: Lowerer, mut ssa: Program<VarName, Nil>, optimizations: &HashSet<Optimization>,
+) -> (Lowerer, Program<VarName, Nil>) {
+    for pass in PIPELINE.into_iter().filter(|pass| optimizations.contains(pass)) {
+        ssa = run_pass(pass, ssa, &mut lowerer.names);
+    }
+    (lowerer, ssa)
+}
+
+/// The SSA optimizations in the order they run in; the others run in the backend
+pub const PIPELINE: [Optimization; 10] = [
+    Optimization::Loopify,
+    Optimization::ConditionCanonicalization,
+    Optimization::MemoryForwarding,
+    Optimization::InterproceduralConstantPropagation,
+    Optimization::TypeSpecialization,
+    Optimization::CopyPropagation,
+    Optimization::BoundsCheckCombining,
+    Optimization::AssertionRemoval,
+    Optimization::AssertionHoisting,
+    Optimization::ColdOutlining,
+];
+
+/// Runs the SSA optimization `pass` of `PIPELINE` on `ssa`, drawing the
+/// names it introduces from `names`
+pub fn run_pass(
+    pass: Optimization, ssa: Program<VarName, Nil>, names: &mut NameSupply,
+) -> Program<VarName, Nil> {
+    use crate::middle_end::{
+        AssertionHoister, AssertionRemover, BoundsCheckCombiner, ColdOutliner,
+        ConditionCanonicalizer, ConstantSpecializer, CopyPropagator, LoadForwarder, Loopifier,
+        TypeSpecializer,
+    };
+    match pass {
+        Optimization::Loopify => Loopifier::new().run(ssa),
+        Optimization::ConditionCanonicalization => ConditionCanonicalizer::new().run(ssa),
+        Optimization::MemoryForwarding => LoadForwarder::new(&ssa).run(ssa),
+        Optimization::InterproceduralConstantPropagation => {
+            ConstantSpecializer::new().run(ssa, names)
+        }
+        Optimization::TypeSpecialization => TypeSpecializer::new().run(ssa, names),
+        Optimization::CopyPropagation => CopyPropagator::new().run(ssa),
+        Optimization::BoundsCheckCombining => BoundsCheckCombiner::new().run(ssa, names),
+        Optimization::AssertionRemoval => AssertionRemover::new(&ssa).optimize(ssa),
+        Optimization::AssertionHoisting => AssertionHoister::new(&ssa).run(ssa),
+        Optimization::ColdOutlining => ColdOutliner::new().run(ssa, names),
+        _ => ssa,
+    }
+}
+
+/// Compiles the expression `s` into the function `entry` of `params` as
+/// configured in `conf`, for Rust programs that embed snake: linked with the
+/// runtime, `entry` takes and returns tagged values by the System V
+/// convention, e.g. as an `extern "sysv64" fn(u64, u64) -> u64` of two
+/// parameters.
+pub fn compile_expr(s: &str, params: &[String], conf: &CompilerConf) -> Result<String, Error> {
+    let (lowerer, ssa) = fragment(s, params, &conf.optimizations)?;
+    Ok(backend_with(lowerer, ssa, conf))
+}
+
+/// The SSA of the expression `s` as the function `entry` of `params`, after
+/// the selected optimizations
+pub fn fragment(
+    s: &str, params: &[String], optimizations: &HashSet<Optimization>,
+) -> Result<(Lowerer, Program<VarName, Nil>), Error> {
+    let mut expr = ExprParser::new().parse(s).map_err(|e| ParseError::new(e, s))?;
+    nodes::number_expr(&mut expr);
+    let mut resolver = Resolver::new();
+    let (params, body) = resolver.resolve_fragment(params, expr)?;
+    let mut lowerer = Lowerer::from(resolver);
+    let ssa = lowerer.lower_fragment(params, body);
+    Ok(optimize(lowerer, ssa, optimizations))
+}
+
+/// Backend, code generation
+pub fn backend(lowerer: Lowerer, ssa: Program<VarName, Nil>) -> String {
+    backend_with(lowerer, ssa, &CompilerConf::new(Optimization::all(), Verbosity::Minimalistic))
+}
+
+/// Backend, code generation as configured in `conf`
+pub fn backend_with(_lowerer: Lowerer, ssa: Program<VarName, Nil>, conf: &CompilerConf) -> String {
+    assemble(ssa, conf)
+}
+
+/// The assembly of `ssa` as configured in `conf`, which needs nothing of
+/// the lowerer
+pub(crate) fn assemble(ssa: Program<VarName, Nil>, conf: &CompilerConf) -> String {
+    let mut txt = emit(ssa, conf, false).to_string();
+    if conf.freestanding {
+        txt.push_str(FREESTANDING_RUNTIME);
+    }
+    txt
+}
+
+/// Backend, code generation as configured in `conf` into a fragment per
+/// function, which `asm::stitch` puts together into a program
+pub fn backend_fragments(
+    _lowerer: Lowerer, ssa: Program<VarName, Nil>, conf: &CompilerConf,
+) -> Vec<Fragment> {
+    use crate::backend::Emitter;
+    let mut fragments = emit(ssa, conf, true).fragments();
+    if conf.freestanding {
+        let externs = Emitter::FREESTANDING_EXTERNS.iter().map(|ext| ext.to_string());
+        fragments.push(Fragment {
+            name: "_start".to_string(),
+            text: FREESTANDING_RUNTIME.to_string(),
+            defines: std::iter::once("_start".to_string()).chain(externs).collect(),
+            externs: vec![],
+            needs: vec!["entry".to_string()],
+        });
+    }
+    fragments
+}
+
+/// The code of `ssa`, switching to the code section before each function
+/// when `fragments`
+fn emit(ssa: Program<VarName, Nil>, conf: &CompilerConf, fragments: bool) -> Asm {
+    use crate::asm::Reg;
+    use crate::backend::{
+        ConflictAnalysis, Emitter, LeafAnalysis, LivenessAnalyzer, RegisterAllocator,
+    };
+    let ssa = if conf.optimizations.contains(&Optimization::DeadCodeElimination) {
+        dead_code_elimination(ssa, conf)
+    } else {
+        ssa
+    };
+    let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+    // register allocation
+    let conflicts = ConflictAnalysis::new(&ssa);
+    let keep_fp = conf.frame_pointer == Some(FramePointer::Keep);
+    let registers: Vec<Reg> =
+        Reg::ALLOCATABLE.into_iter().filter(|reg| !keep_fp || *reg != Reg::Rbp).collect();
+    let leaf = conf.optimizations.contains(&Optimization::LeafFunctions);
+    let leaves = LeafAnalysis::new(&ssa);
+    let mut allocator = RegisterAllocator::new().keep_frame_pointer(keep_fp);
+    if leaf {
+        allocator = allocator.prefer_volatile(leaves.leaf_vars());
+    }
+    allocator.graph_color(conflicts, &registers, false);
+    if conf.verify_alloc || cfg!(debug_assertions) {
+        if let Err(problems) = allocator.verify(&ssa, &registers) {
+            panic!("the allocation is broken:\n{}", problems.join("\n"));
+        }
+    }
+    let frameless = if leaf { leaves.frameless(&allocator.assignment) } else { HashSet::new() };
+    // code generation
+    let mut emitter = Emitter::from(allocator)
+        .frameless(frameless)
+        .for_size(conf.optimizations.contains(&Optimization::Size))
+        .freestanding(conf.freestanding)
+        .bool_lowering(conf.bool_lowering)
+        .shadow_stack(conf.shadow_stack)
+        .frame_pointer(conf.frame_pointer)
+        .function_sections(conf.function_sections)
+        .tail_calls(conf.tail_calls)
+        .fragments(fragments);
+    emitter.emit_prog(&ssa);
+    let mut asm = emitter.to_asm();
+    if conf.optimizations.contains(&Optimization::Scheduling) {
+        asm.schedule();
+    }
+    if conf.optimizations.contains(&Optimization::IdenticalCodeFolding) {
+        asm.fold_identical();
+    }
+    if conf.verify_stack || cfg!(debug_assertions) {
+        if let Err(problems) = asm.check_stack(&Emitter::NO_RETURN_EXTERNS) {
+            panic!("the emitted code breaks the stack discipline:\n{}", problems.join("\n"));
+        }
+    }
+    if conf.frame_pointer == Some(FramePointer::Omit) {
+        asm.add_unwind_tables();
+    }
+    asm
+}
+
+/// The runtime of freestanding executables, appended to their assembly
+pub const FREESTANDING_RUNTIME: &str = include_str!("../../runtime/freestanding.asm");
+
+/// Checks that the freestanding runtime implements the externs of `ssa`
+pub fn check_freestanding<Ann>(ssa: &Program<VarName, Ann>) -> Result<(), LinkError> {
+    use crate::backend::Emitter;
+    match ssa.externs.iter().find(|ext| !Emitter::FREESTANDING_EXTERNS.contains(&ext.name.hint())) {
+        Some(ext) => Err(LinkError::UnavailableExtern(ext.name.hint().to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Dead code elimination, removing unused variables and parameters until a fixpoint
+pub fn dead_code_elimination(
+    ssa: Program<VarName, Nil>, conf: &CompilerConf,
+) -> Program<VarName, Nil> {
+    use crate::backend::{EffectAnalysis, LivenessAnalyzer, UnusedRemover};
+    let mut fixed = ssa;
+    loop {
+        let effects = EffectAnalysis::new(&fixed, conf.conservative_effects);
+        let live = LivenessAnalyzer::new(&fixed).analyze(fixed);
+        let mut remover = UnusedRemover::with_pure(effects.pure).strict(conf.strict_errors);
+        fixed = remover.run(live);
+        if remover.progress().is_none() {
+            break fixed;
+        }
+    }
+}
@@ -0,0 +1,648 @@
+//! The configuration of the compiler passes, shared by the command line
+//! and embedders.
+
+use crate::asm::{reg_to_string, Reg};
+use std::collections::HashSet;
+
+/// The output formats of `snake doc`
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+/// The languages of `snake bindgen`
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum BindgenLang {
+    /// A header with the tagging macros
+    C,
+    /// A ctypes module that converts Python values
+    Python,
+}
+
+/// How comparisons turn into tagged booleans, which `--bool-lowering`
+/// selects to compare the two
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum BoolLowering {
+    /// `setcc` of the condition, then shifted and tagged
+    #[default]
+    Setcc,
+    /// A conditional move between the tagged true and false
+    Cmov,
+}
+
+/// How the types of a program are checked, which `--types` selects
+#[derive(Debug, Copy, Clone, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum TypeMode {
+    /// The annotations, at runtime where their types are not evident
+    #[default]
+    Gradual,
+    /// Also a type for every value, by Hindley–Milner inference
+    Infer,
+}
+
+/// What the code does with rbp, which `--frame-pointer` selects
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum FramePointer {
+    /// Every function saves the rbp of its caller and points rbp at the
+    /// saved one, so that unwinders can follow the chain
+    Keep,
+    /// rbp is allocated as any register, and the code comes with `.eh_frame`
+    /// unwind tables instead
+    Omit,
+}
+
+pub struct CompilerConf {
+    pub optimizations: HashSet<Optimization>,
+    pub verbose: Verbosity,
+    /// Whether the effect analysis of dead code elimination distrusts the
+    /// contracts of the externs, keeping their unused calls
+    pub conservative_effects: bool,
+    /// Whether optimizations must report the same runtime error as the
+    /// unoptimized program
+    pub strict_errors: bool,
+    /// Whether the executable runs without the Rust stub, on the
+    /// syscall-only runtime of `runtime/freestanding.asm`
+    pub freestanding: bool,
+    pub bool_lowering: BoolLowering,
+    /// Whether the code keeps the functions it runs on a shadow stack, which
+    /// the runtime prints as a backtrace on errors
+    pub shadow_stack: bool,
+    /// Whether the code keeps a frame pointer or unwind tables; neither
+    /// when `None`
+    pub frame_pointer: Option<FramePointer>,
+    /// Whether each function goes into a section of its own, for the linker
+    /// to discard the unused ones
+    pub function_sections: bool,
+    /// Whether the calls whose result is returned jump to the callee, so
+    /// that recursion through them runs in constant stack
+    pub tail_calls: bool,
+    /// Whether the register allocation is checked against the liveness
+    /// before code generation, as it always is in debug builds
+    pub verify_alloc: bool,
+    /// Whether the emitted code is checked for the balance of rsp, the
+    /// alignment of calls and reads of unwritten slots, as it always is in
+    /// debug builds
+    pub verify_stack: bool,
+}
+
+impl CompilerConf {
+    pub fn new(optimizations: impl IntoIterator<Item = Optimization>, verbose: Verbosity) -> Self {
+        Self {
+            optimizations: optimizations.into_iter().collect(),
+            verbose,
+            conservative_effects: false,
+            strict_errors: false,
+            freestanding: false,
+            bool_lowering: BoolLowering::default(),
+            shadow_stack: false,
+            frame_pointer: None,
+            function_sections: false,
+            tail_calls: true,
+            verify_alloc: false,
+            verify_stack: false,
+        }
+    }
+
+    /// Leaves `disabled` out of the selected optimizations, e.g. to run a
+    /// level but for a pass under suspicion
+    pub fn disable(&mut self, disabled: impl IntoIterator<Item = Optimization>) {
+        for optimization in disabled {
+            self.optimizations.remove(&optimization);
+        }
+    }
+
+    /// Describes which runtime error an optimized program reports,
+    /// compared to the unoptimized one
+    pub fn error_ordering(&self) -> &'static str {
+        if self.strict_errors {
+            "strict: the first runtime error of the unoptimized program is reported"
+        } else {
+            "relaxed: dead code elimination may remove unused arithmetic that overflows, \
+             so that a later runtime error or none is reported, and a combined bounds check \
+             reports the first index it checks; unused calls that may fail are kept"
+        }
+    }
+}
+
+/// Hashes every field, the optimizations in order, so that equal
+/// configurations key the same memoized assembly in `query::Queries`
+impl std::hash::Hash for CompilerConf {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let CompilerConf {
+            optimizations,
+            verbose,
+            conservative_effects,
+            strict_errors,
+            freestanding,
+            bool_lowering,
+            shadow_stack,
+            frame_pointer,
+            function_sections,
+            tail_calls,
+            verify_alloc,
+            verify_stack,
+        } = self;
+        let mut optimizations: Vec<_> = optimizations.iter().collect();
+        optimizations.sort_unstable();
+        optimizations.hash(state);
+        (verbose, conservative_effects, strict_errors, freestanding, bool_lowering).hash(state);
+        (shadow_stack, frame_pointer, function_sections, tail_calls).hash(state);
+        (verify_alloc, verify_stack).hash(state);
+    }
+}
+
+/// The largest programs that the parser accepts, beyond which the passes
+/// that recurse over expressions could run out of stack
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Limits {
+    /// How deeply expressions nest; each binding of a `let` nests its body
+    pub depth: usize,
+    /// The length of the source in bytes
+    pub size: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits { depth: 1000, size: 16 << 20 }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Optimization {
+    /// Copy Propagation - replace `x` with `y` if an instruction `x = y` exists
+    CopyPropagation,
+    /// Assertion Removal - removes integer type assertions
+    AssertionRemoval,
+    /// Assertion Hoisting - move assertions up, as far as it keeps the reported errors
+    AssertionHoisting,
+    /// Dead Code Elimination - remove unused variables and parameters
+    DeadCodeElimination,
+    /// Variable Lifetime Splitting - variable lifetime splitting
+    VariableLifetimeSplitting,
+    /// Size - lay out the code for size, falling through to the block of each function;
+    /// "-Os" selects all optimizations, this one and identical code folding
+    Size,
+    /// Identical Code Folding - emit functions and blocks whose code is the same only once
+    IdenticalCodeFolding,
+    /// Instruction Scheduling - reorder the instructions between jumps so that
+    /// independent ones run while loads and multiplications complete
+    Scheduling,
+    /// Condition Canonicalization - rewrite negated comparisons, conjunctions and
+    /// disjunctions into ones without the negation, and branch on `!x` as on `x`
+    ConditionCanonicalization,
+    /// Bounds-Check Combining - check the accesses to `a[i]`, `a[i + 1]`, ... in a
+    /// row once, against the length minus the largest distance
+    BoundsCheckCombining,
+    /// Memory Forwarding - replace the loads of array elements that were stored or
+    /// loaded before by their values, as far as the arrays cannot alias
+    MemoryForwarding,
+    /// Interprocedural Constant Propagation - propagate the constants that a function is
+    /// always called with into its body, and clone it for calls with other constants
+    InterproceduralConstantPropagation,
+    /// Type Specialization - remove the type assertions on values whose types are evident,
+    /// and clone functions for the types of the arguments that they assert
+    TypeSpecialization,
+    /// Loopify - turn the tail calls of a function to itself into a loop in its body
+    Loopify,
+    /// Cold Outlining - move the code of the branches that certainly fail after all the
+    /// other code
+    ColdOutlining,
+    /// Leaf Functions - give the variables of the functions that make no calls the
+    /// volatile registers first, and run those that then use no other without a frame
+    LeafFunctions,
+}
+impl Optimization {
+    pub const VARIANTS: [Optimization; 16] = [
+        Optimization::CopyPropagation,
+        Optimization::AssertionRemoval,
+        Optimization::AssertionHoisting,
+        Optimization::DeadCodeElimination,
+        Optimization::VariableLifetimeSplitting,
+        Optimization::Size,
+        Optimization::IdenticalCodeFolding,
+        Optimization::Scheduling,
+        Optimization::ConditionCanonicalization,
+        Optimization::BoundsCheckCombining,
+        Optimization::MemoryForwarding,
+        Optimization::InterproceduralConstantPropagation,
+        Optimization::TypeSpecialization,
+        Optimization::Loopify,
+        Optimization::ColdOutlining,
+        Optimization::LeafFunctions,
+    ];
+
+    /// The name of the optimization in `-O`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Optimization::CopyPropagation => "cp",
+            Optimization::AssertionRemoval => "ar",
+            Optimization::AssertionHoisting => "ah",
+            Optimization::DeadCodeElimination => "dce",
+            Optimization::VariableLifetimeSplitting => "vls",
+            Optimization::Size => "s",
+            Optimization::IdenticalCodeFolding => "icf",
+            Optimization::Scheduling => "sched",
+            Optimization::ConditionCanonicalization => "canon",
+            Optimization::BoundsCheckCombining => "bcc",
+            Optimization::MemoryForwarding => "mem",
+            Optimization::InterproceduralConstantPropagation => "ipcp",
+            Optimization::TypeSpecialization => "tspec",
+            Optimization::Loopify => "loopify",
+            Optimization::ColdOutlining => "cold",
+            Optimization::LeafFunctions => "leaf",
+        }
+    }
+
+    /// What the optimization does, in a line
+    pub fn description(&self) -> &'static str {
+        match self {
+            Optimization::CopyPropagation => "replaces copies of variables by the originals",
+            Optimization::AssertionRemoval => "removes the integer type assertions",
+            Optimization::AssertionHoisting => "moves assertions up, keeping the reported errors",
+            Optimization::DeadCodeElimination => "removes unused variables and parameters",
+            Optimization::VariableLifetimeSplitting => "splits the lifetimes of variables",
+            Optimization::Size => "lays out each function followed by its block",
+            Optimization::IdenticalCodeFolding => "emits identical functions and blocks once",
+            Optimization::Scheduling => "reorders independent instructions within blocks",
+            Optimization::ConditionCanonicalization => "rewrites negated conditions",
+            Optimization::BoundsCheckCombining => "checks accesses in a row against one bound",
+            Optimization::MemoryForwarding => "replaces loads of known array elements",
+            Optimization::InterproceduralConstantPropagation => {
+                "clones functions for their constant arguments"
+            }
+            Optimization::TypeSpecialization => "clones functions for the types of arguments",
+            Optimization::Loopify => "turns self tail calls into loops",
+            Optimization::ColdOutlining => "moves the branches that certainly fail last",
+            Optimization::LeafFunctions => "runs functions that make no calls without a frame",
+        }
+    }
+
+    pub fn all() -> HashSet<Optimization> {
+        [
+            Optimization::AssertionRemoval,
+            Optimization::AssertionHoisting,
+            Optimization::CopyPropagation,
+            Optimization::ConditionCanonicalization,
+            Optimization::BoundsCheckCombining,
+            Optimization::MemoryForwarding,
+            Optimization::InterproceduralConstantPropagation,
+            Optimization::TypeSpecialization,
+            Optimization::Loopify,
+            Optimization::ColdOutlining,
+            Optimization::LeafFunctions,
+            Optimization::DeadCodeElimination,
+            Optimization::VariableLifetimeSplitting,
+        ]
+        .into()
+    }
+}
+
+/// The optimization levels of `-O0` to `-O3`, each running the passes of the
+/// level below and more
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptLevel {
+    O0,
+    O1,
+    O2,
+    O3,
+}
+impl OptLevel {
+    pub const VARIANTS: [OptLevel; 4] = [OptLevel::O0, OptLevel::O1, OptLevel::O2, OptLevel::O3];
+
+    /// The digit of the level in `-O`
+    pub fn name(&self) -> &'static str {
+        match self {
+            OptLevel::O0 => "0",
+            OptLevel::O1 => "1",
+            OptLevel::O2 => "2",
+            OptLevel::O3 => "3",
+        }
+    }
+
+    /// What the level is for
+    pub fn description(&self) -> &'static str {
+        match self {
+            OptLevel::O0 => "no optimization, the code as lowered",
+            OptLevel::O1 => "cheap cleanups of the lowered code",
+            OptLevel::O2 => "also the passes over loops, bounds checks, memory and layout",
+            OptLevel::O3 => "also clones of functions for their constant and typed arguments",
+        }
+    }
+
+    /// The passes that the level adds to the level below; assertion removal,
+    /// whose analysis is not implemented yet, and lifetime splitting, which
+    /// does nothing yet, only run by name
+    fn added(&self) -> &'static [Optimization] {
+        match self {
+            OptLevel::O0 => &[],
+            OptLevel::O1 => &[
+                Optimization::CopyPropagation,
+                Optimization::ConditionCanonicalization,
+                Optimization::DeadCodeElimination,
+            ],
+            OptLevel::O2 => &[
+                Optimization::AssertionHoisting,
+                Optimization::BoundsCheckCombining,
+                Optimization::MemoryForwarding,
+                Optimization::Loopify,
+                Optimization::ColdOutlining,
+                Optimization::LeafFunctions,
+                Optimization::Scheduling,
+            ],
+            OptLevel::O3 => &[
+                Optimization::InterproceduralConstantPropagation,
+                Optimization::TypeSpecialization,
+            ],
+        }
+    }
+
+    /// The passes that the level runs
+    pub fn optimizations(&self) -> HashSet<Optimization> {
+        OptLevel::VARIANTS
+            .iter()
+            .filter(|level| *level <= self)
+            .flat_map(|level| level.added().iter().copied())
+            .collect()
+    }
+}
+
+/// The levels of `-O` and the passes that each one runs, then every pass by
+/// its name, as `--explain-opts` prints them
+pub fn explain_optimizations() -> String {
+    let mut txt = String::from("levels:\n");
+    for level in OptLevel::VARIANTS {
+        let passes: Vec<_> = level.added().iter().map(|opt| opt.name()).collect();
+        txt.push_str(&format!("  -O{}  {}\n", level.name(), level.description()));
+        if !passes.is_empty() {
+            let below = match level {
+                OptLevel::O1 => "",
+                _ => "those of the level below and ",
+            };
+            txt.push_str(&format!("       runs {}{}\n", below, passes.join(", ")));
+        }
+    }
+    txt.push_str("  -Os  all, also laid out for size with identical code folding\n");
+    txt.push_str("passes, selected by name with -O=<name>,...:\n");
+    for opt in Optimization::VARIANTS {
+        txt.push_str(&format!("  {:<8} {}\n", opt.name(), opt.description()));
+    }
+    txt
+}
+
+impl std::str::FromStr for Optimization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let found =
+            Optimization::VARIANTS.into_iter().find(|opt| opt.name().eq_ignore_ascii_case(s));
+        found.ok_or_else(|| format!("Invalid optimization: invalid variant: {}", s))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OptimizationCollection {
+    optimizations: HashSet<Optimization>,
+}
+impl std::str::FromStr for OptimizationCollection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // split on commas
+        if s == "all" {
+            return Ok(OptimizationCollection { optimizations: Optimization::all() });
+        }
+        if let Some(level) = OptLevel::VARIANTS.into_iter().find(|level| level.name() == s) {
+            return Ok(OptimizationCollection { optimizations: level.optimizations() });
+        }
+        if s == "s" {
+            let mut optimizations = Optimization::all();
+            optimizations.insert(Optimization::Size);
+            optimizations.insert(Optimization::IdenticalCodeFolding);
+            return Ok(OptimizationCollection { optimizations });
+        }
+        let optimizations: Vec<&str> = s.split(',').collect();
+        let optimizations = optimizations
+            .into_iter()
+            .map(|o| o.parse::<Optimization>())
+            .collect::<Result<_, _>>()?;
+        Ok(OptimizationCollection { optimizations })
+    }
+}
+impl IntoIterator for OptimizationCollection {
+    type Item = Optimization;
+    type IntoIter = std::collections::hash_set::IntoIter<Optimization>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.optimizations.into_iter()
+    }
+}
+
+/// The sets of allocatable registers that a `RegisterSet` starts from
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RegisterCollection {
+    AllocatableAll,
+    AllocatableVolatile,
+    AllocatableNonVolatile,
+    None,
+}
+impl RegisterCollection {
+    pub const VARIANTS: [RegisterCollection; 4] = [
+        RegisterCollection::AllocatableAll,
+        RegisterCollection::AllocatableVolatile,
+        RegisterCollection::AllocatableNonVolatile,
+        RegisterCollection::None,
+    ];
+
+    /// The names of the collection in `-R`, the first one canonical
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            RegisterCollection::AllocatableAll => &["all"],
+            RegisterCollection::AllocatableVolatile => &["volatile", "caller"],
+            RegisterCollection::AllocatableNonVolatile => &["non-volatile", "callee"],
+            RegisterCollection::None => &["none"],
+        }
+    }
+
+    /// The registers of the collection, in the order the allocator picks them
+    pub fn registers(&self) -> &'static [Reg] {
+        match self {
+            RegisterCollection::AllocatableAll => &Reg::ALLOCATABLE,
+            RegisterCollection::AllocatableVolatile => &Reg::ALLOCATABLE_VOLATILE,
+            RegisterCollection::AllocatableNonVolatile => &Reg::ALLOCATABLE_NON_VOLATILE,
+            RegisterCollection::None => &[],
+        }
+    }
+}
+
+/// A set of registers in the order the allocator picks them, as `-R` spells
+/// it: a collection followed by registers to add with `+` and to remove with
+/// `-`, applied from left to right, e.g. `non-volatile+r8` or `all-r9-r11`.
+/// Reserved registers parse, for `RegisterAllocator::check_registers` to
+/// reject them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterSet {
+    registers: Vec<Reg>,
+}
+
+impl Default for RegisterSet {
+    fn default() -> Self {
+        RegisterCollection::AllocatableAll.into()
+    }
+}
+
+impl From<RegisterCollection> for RegisterSet {
+    fn from(collection: RegisterCollection) -> Self {
+        RegisterSet { registers: collection.registers().to_vec() }
+    }
+}
+
+impl FromIterator<Reg> for RegisterSet {
+    fn from_iter<I: IntoIterator<Item = Reg>>(iter: I) -> Self {
+        let mut set = RegisterSet::from(RegisterCollection::None);
+        for reg in iter {
+            set.insert(reg);
+        }
+        set
+    }
+}
+
+impl RegisterSet {
+    pub fn registers(&self) -> &[Reg] {
+        &self.registers
+    }
+
+    pub fn contains(&self, reg: Reg) -> bool {
+        self.registers.contains(&reg)
+    }
+
+    /// Adds `reg` last, unless it is in the set already
+    pub fn insert(&mut self, reg: Reg) {
+        if !self.contains(reg) {
+            self.registers.push(reg);
+        }
+    }
+
+    pub fn remove(&mut self, reg: Reg) {
+        self.registers.retain(|r| *r != reg);
+    }
+
+    /// The registers of `self`, then those of `other` that are not in `self`
+    pub fn union(&self, other: &RegisterSet) -> RegisterSet {
+        self.registers.iter().chain(other.registers.iter()).copied().collect()
+    }
+
+    /// The registers of `self` that are in `other`
+    pub fn intersection(&self, other: &RegisterSet) -> RegisterSet {
+        self.registers.iter().copied().filter(|reg| other.contains(*reg)).collect()
+    }
+
+    /// The registers of `self` that are not in `other`
+    pub fn difference(&self, other: &RegisterSet) -> RegisterSet {
+        self.registers.iter().copied().filter(|reg| !other.contains(*reg)).collect()
+    }
+
+    pub fn is_subset(&self, other: &RegisterSet) -> bool {
+        self.registers.iter().all(|reg| other.contains(*reg))
+    }
+}
+
+/// Why a register set does not parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisterSetError {
+    /// A selection that does not start with the name of a collection
+    UnknownCollection(String),
+    /// A name after `+` or `-` that is not a register
+    UnknownRegister(String),
+}
+
+impl std::fmt::Display for RegisterSetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegisterSetError::UnknownCollection(s) => {
+                let names: Vec<_> =
+                    RegisterCollection::VARIANTS.iter().flat_map(|c| c.names()).copied().collect();
+                write!(
+                    f,
+                    "unknown register collection at `{}`, expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            }
+            RegisterSetError::UnknownRegister(s) => {
+                let names: Vec<_> = Reg::ALL.iter().map(|reg| reg_to_string(*reg)).collect();
+                write!(f, "unknown register `{}`, expected one of {}", s, names.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegisterSetError {}
+
+impl std::str::FromStr for RegisterSet {
+    type Err = RegisterSetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the longest name first, as `non-volatile` starts like `none`
+        let mut names: Vec<_> = RegisterCollection::VARIANTS
+            .iter()
+            .flat_map(|c| c.names().iter().map(move |name| (*name, *c)))
+            .collect();
+        names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+        let (rest, collection) = names
+            .into_iter()
+            .find_map(|(name, c)| {
+                let prefix = s.get(..name.len())?;
+                prefix.eq_ignore_ascii_case(name).then(|| (&s[name.len()..], c))
+            })
+            .filter(|(rest, _)| rest.is_empty() || rest.starts_with(['+', '-']))
+            .ok_or_else(|| RegisterSetError::UnknownCollection(s.to_string()))?;
+        let mut set = RegisterSet::from(collection);
+        let mut rest = rest;
+        while let Some(op) = rest.chars().next() {
+            let end = rest[1..].find(['+', '-']).map_or(rest.len(), |i| i + 1);
+            let name = &rest[1..end];
+            let reg = Reg::ALL
+                .into_iter()
+                .find(|reg| reg_to_string(*reg).eq_ignore_ascii_case(name))
+                .ok_or_else(|| RegisterSetError::UnknownRegister(name.to_string()))?;
+            match op {
+                '+' => set.insert(reg),
+                _ => set.remove(reg),
+            }
+            rest = &rest[end..];
+        }
+        Ok(set)
+    }
+}
+
+impl std::fmt::Display for RegisterSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("none")?;
+        for reg in self.registers.iter() {
+            write!(f, "+{}", reg_to_string(*reg))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only print the final output.
+    ///
+    /// Provides a canonical output for autograder.
+    Minimalistic,
+    /// Print the final output and crucial intermediate steps.
+    ///
+    /// Suitable for learning and debugging.
+    Moderate,
+    /// Print the final output and all intermediate steps.
+    ///
+    /// Suitable for learning and debugging.
+    Mouthful,
+}
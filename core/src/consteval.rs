@@ -0,0 +1,100 @@
+//! Whole-program constant evaluation for `--const-arg`.
+//!
+//! With the arguments of `main` known at compile time, a program that only
+//! computes always returns the same value. It then runs in the AST
+//! interpreter during compilation, for a bounded number of steps, and its
+//! body is replaced by the value it returned, so that the executable just
+//! returns it. A program that calls externs, reads the environment, the
+//! clock or random numbers, fails, runs out of steps or returns an array is
+//! compiled as it is.
+
+use crate::ast::*;
+use crate::interp::{self, Services, Status, Value};
+use std::fmt;
+
+/// The steps the interpreter runs before giving up, unless told otherwise
+pub const DEFAULT_FUEL: usize = 1_000_000;
+
+/// Why a program was not evaluated at compile time
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NotConstant {
+    /// The program declares externs, which only the runtime implements
+    Externs,
+    /// The program uses a service of the runtime whose result is only known when it runs
+    Service(Prim),
+    /// The program did not return within the steps
+    OutOfFuel(usize),
+    /// The program failed, e.g. with an overflow that the executable reports instead
+    Failed(String),
+    /// The program returned an array, which is not a literal
+    NotALiteral(String),
+}
+
+impl fmt::Display for NotConstant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotConstant::Externs => write!(f, "the program declares externs"),
+            NotConstant::Service(prim) => write!(f, "the program calls {}", prim),
+            NotConstant::OutOfFuel(fuel) => {
+                write!(f, "the program did not return within {} steps", fuel)
+            }
+            NotConstant::Failed(err) => write!(f, "the program fails: {}", err),
+            NotConstant::NotALiteral(value) => {
+                write!(f, "the program returns {}, which is not a literal", value)
+            }
+        }
+    }
+}
+
+/// The literal that `prog` returns on `args`, run for at most `fuel` steps.
+/// With `bigint`, integers that overflow 63 bits become big literals.
+pub fn evaluate(
+    prog: &BoundProg, args: &[String], fuel: usize, bigint: bool,
+) -> Result<BoundExpr, NotConstant> {
+    if !prog.externs.is_empty() {
+        return Err(NotConstant::Externs);
+    }
+    if let Some(prim) = service(&prog.body) {
+        return Err(NotConstant::Service(prim));
+    }
+    let failed = |err: interp::InterpErr<_, _>| NotConstant::Failed(err.to_string());
+    // the services are never called, but the machine needs some
+    let services = Services::new(Some(0)).bigint(bigint);
+    let mut machine = interp::ast::Machine::start(prog, args, services).map_err(failed)?;
+    let value = match machine.resume(fuel).map_err(failed)? {
+        Status::Done(value) => value,
+        Status::Suspended => return Err(NotConstant::OutOfFuel(fuel)),
+    };
+    let loc = prog.body.loc();
+    match value {
+        Value::Int(n) => Ok(Expr::Num(n, loc)),
+        Value::Big(n) => Ok(Expr::BigNum(n.as_ref().clone(), loc)),
+        Value::Bool(b) => Ok(Expr::Bool(b, loc)),
+        value => Err(NotConstant::NotALiteral(value.to_string())),
+    }
+}
+
+/// A call of `e` to a service whose result depends on the run
+fn service(e: &BoundExpr) -> Option<Prim> {
+    match e {
+        Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => None,
+        Expr::Prim { prim: prim @ (Prim::GetEnv | Prim::Clock | Prim::Random), .. } => {
+            Some(prim.clone())
+        }
+        Expr::Prim { args, .. } | Expr::Call { args, .. } => args.iter().find_map(service),
+        Expr::Let { bindings, body, .. } => {
+            bindings.iter().map(|b| &b.expr).chain([body.as_ref()]).find_map(service)
+        }
+        Expr::If { cond, thn, els, .. } => [cond, thn, els].into_iter().find_map(|e| service(e)),
+        Expr::Switch { scrutinee, cases, default, .. } => [scrutinee.as_ref(), default.as_ref()]
+            .into_iter()
+            .chain(cases.iter().map(|c| &c.body))
+            .find_map(service),
+        Expr::Match { scrutinee, arms, .. } => {
+            [scrutinee.as_ref()].into_iter().chain(arms.iter().map(|a| &a.body)).find_map(service)
+        }
+        Expr::FunDefs { decls, body, .. } => {
+            decls.iter().map(|d| &d.body).chain([body.as_ref()]).find_map(service)
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Documentation of a program for `snake doc`.
+//!
+//! Every extern, enum and function is listed with its signature and the `##`
+//! comments before its declaration, in the order of the source. Local
+//! functions follow the function that they are defined in, one heading
+//! level deeper.
+
+use crate::ast::*;
+use crate::conf::DocFormat;
+
+pub struct Entry {
+    /// how many functions the declaration is nested in
+    pub depth: usize,
+    /// e.g. `def sum(i, acc)`
+    pub signature: String,
+    pub doc: Option<String>,
+}
+
+/// The documented declarations of `prog`, in the order of the source
+pub fn entries(prog: &SurfProg) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for ext in prog.externs.iter() {
+        entries.push(Entry { depth: 0, signature: extern_signature(ext), doc: ext.doc.clone() });
+    }
+    for decl in prog.enums.iter() {
+        entries.push(Entry { depth: 0, signature: decl.to_string(), doc: decl.doc.clone() });
+    }
+    entries.push(Entry {
+        depth: 0,
+        signature: signature(&prog.name, std::slice::from_ref(&prog.param), &Signature::default()),
+        doc: prog.doc.clone(),
+    });
+    collect(&prog.body, 1, &mut entries);
+    entries
+}
+
+/// e.g. `def f(x: Int, y) -> Bool`
+fn signature(name: &str, params: &[Spanned<String>], signature: &Signature) -> String {
+    let params = params.iter().enumerate();
+    let params: Vec<_> = params.map(|(i, p)| signature.param(i, &p.node)).collect();
+    format!("def {}({}){}", name, params.join(", "), signature)
+}
+
+/// e.g. `extern sqrt(x: Int) -> Int pure(true)`
+pub(crate) fn extern_signature(ext: &SurfExtDecl) -> String {
+    let params = ext.params.iter().enumerate();
+    let params: Vec<_> = params.map(|(i, p)| ext.signature.param(i, &p.node)).collect();
+    format!("extern {}({}){}{}", ext.name, params.join(", "), ext.signature, ext.contract)
+}
+
+fn collect(e: &SurfExpr, depth: usize, entries: &mut Vec<Entry>) {
+    match e {
+        Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => {}
+        Expr::Prim { args, .. } | Expr::Call { args, .. } => {
+            args.iter().for_each(|arg| collect(arg, depth, entries));
+        }
+        Expr::Let { bindings, body, .. } => {
+            bindings.iter().for_each(|b| collect(&b.expr, depth, entries));
+            collect(body, depth, entries);
+        }
+        Expr::If { cond, thn, els, .. } => {
+            collect(cond, depth, entries);
+            collect(thn, depth, entries);
+            collect(els, depth, entries);
+        }
+        Expr::Switch { scrutinee, cases, default, .. } => {
+            collect(scrutinee, depth, entries);
+            cases.iter().for_each(|case| collect(&case.body, depth, entries));
+            collect(default, depth, entries);
+        }
+        Expr::Match { scrutinee, arms, .. } => {
+            collect(scrutinee, depth, entries);
+            arms.iter().for_each(|arm| collect(&arm.body, depth, entries));
+        }
+        Expr::FunDefs { decls, body, .. } => {
+            for decl in decls {
+                entries.push(Entry {
+                    depth,
+                    signature: signature(&decl.name, &decl.params, &decl.signature),
+                    doc: decl.doc.clone(),
+                });
+                collect(&decl.body, depth + 1, entries);
+            }
+            collect(body, depth, entries);
+        }
+    }
+}
+
+/// Renders the documentation of `prog` under the heading `title`
+pub fn render(title: &str, prog: &SurfProg, format: DocFormat) -> String {
+    let entries = entries(prog);
+    match format {
+        DocFormat::Markdown => markdown(title, &entries),
+        DocFormat::Html => html(title, &entries),
+    }
+}
+
+/// headings go down to level 6, which takes the deeply nested functions as well
+fn level(entry: &Entry) -> usize {
+    (entry.depth + 2).min(6)
+}
+
+fn markdown(title: &str, entries: &[Entry]) -> String {
+    let mut out = format!("# {}\n", title);
+    for entry in entries {
+        out.push_str(&format!("\n{} `{}`\n", "#".repeat(level(entry)), entry.signature));
+        if let Some(doc) = &entry.doc {
+            out.push_str(&format!("\n{}\n", doc));
+        }
+    }
+    out
+}
+
+fn html(title: &str, entries: &[Entry]) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n</head>\n<body>\n", escape(title)));
+    out.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+    for entry in entries {
+        let level = level(entry);
+        out.push_str(&format!("<h{0}><code>{1}</code></h{0}>\n", level, escape(&entry.signature)));
+        // blank lines separate the paragraphs of a doc comment
+        for paragraph in entry.doc.iter().flat_map(|doc| doc.split("\n\n")) {
+            if !paragraph.trim().is_empty() {
+                out.push_str(&format!("<p>{}</p>\n", escape(paragraph.trim())));
+            }
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,638 @@
+//! Versioned dumps. Every dump of `snake -t` but the assembly starts with a
+//! header such as `# format_version: ssa 1.0`, and the loaders here parse
+//! the dumps back, so that tools built on the dumps fail clearly on a dump
+//! of another version rather than misread it.
+//!
+//! The versions are semantic: a dump loads if it is of the same format and
+//! major version as the loader, and of a minor version no newer. Bump the
+//! minor version of a format when its dumps gain what older loaders can do
+//! without, and the major version when they change otherwise.
+//!
+//! The loaders take the dumps as `snake` writes them without
+//! `--annotate` or `--readable-names`; `--width` may break the lines.
+
+use crate::ana::{Allocation, Coloring, Graph, Nil, PerfectEliminationOrder};
+use crate::asm::Reg;
+use crate::ast::{unescape, SurfProg};
+use crate::identifiers::*;
+use crate::ssa::*;
+use crate::types::{Contract, Type};
+use std::fmt;
+use std::str::FromStr;
+
+/// What a dump holds
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum Format {
+    Ast,
+    ResolvedAst,
+    Ssa,
+    Graph,
+    ElimOrder,
+    Coloring,
+}
+
+impl Format {
+    pub const ALL: [Format; 6] = [
+        Format::Ast,
+        Format::ResolvedAst,
+        Format::Ssa,
+        Format::Graph,
+        Format::ElimOrder,
+        Format::Coloring,
+    ];
+
+    /// The name in the header
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Ast => "ast",
+            Format::ResolvedAst => "resolved-ast",
+            Format::Ssa => "ssa",
+            Format::Graph => "graph",
+            Format::ElimOrder => "elim-order",
+            Format::Coloring => "coloring",
+        }
+    }
+
+    /// The version that `snake` writes and loads
+    pub fn version(self) -> Version {
+        match self {
+            Format::Ast => Version::new(1, 1),
+            Format::ResolvedAst => Version::new(1, 1),
+            Format::Ssa => Version::new(1, 1),
+            Format::Graph => Version::new(1, 0),
+            Format::ElimOrder => Version::new(1, 0),
+            Format::Coloring => Version::new(1, 0),
+        }
+    }
+
+    /// e.g. `# format_version: ssa 1.0`
+    pub fn header(self) -> String {
+        format!("{} {} {}", HEADER, self.name(), self.version())
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The start of the headers
+pub const HEADER: &str = "# format_version:";
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Version {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Version { major, minor }
+    }
+
+    /// Whether a loader of this version loads dumps of `other`
+    pub fn loads(self, other: Version) -> bool {
+        self.major == other.major && other.minor <= self.minor
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = s.split_once('.').and_then(|(major, minor)| {
+            Some(Version::new(major.parse().ok()?, minor.parse().ok()?))
+        });
+        version.ok_or_else(|| format!("`{}` is not a version `major.minor`", s))
+    }
+}
+
+/// Displays the dump `.1` after the header of the format `.0`
+pub struct Versioned<T>(pub Format, pub T);
+
+impl<T: fmt::Display> fmt::Display for Versioned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.0.header())?;
+        self.1.fmt(f)
+    }
+}
+
+/// A dump that does not load
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadError {
+    /// The line of the dump, starting from 1 at the header
+    pub line: usize,
+    pub message: String,
+}
+
+impl LoadError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        LoadError { line, message: message.into() }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<LoadError> for String {
+    fn from(err: LoadError) -> Self {
+        err.to_string()
+    }
+}
+
+/// The format and the version in the header of `dump`
+pub fn header(dump: &str) -> Result<(Format, Version), LoadError> {
+    let line = dump.lines().next().unwrap_or("");
+    let missing =
+        || LoadError::new(1, format!("missing the header `{} <format> <major>.<minor>`", HEADER));
+    let mut words = line.strip_prefix(HEADER).ok_or_else(missing)?.split_whitespace();
+    let (Some(name), Some(version), None) = (words.next(), words.next(), words.next()) else {
+        return Err(missing());
+    };
+    let format = (Format::ALL.into_iter())
+        .find(|format| format.name() == name)
+        .ok_or_else(|| LoadError::new(1, format!("unknown format `{}`", name)))?;
+    Ok((format, version.parse().map_err(|e| LoadError::new(1, e))?))
+}
+
+/// The dump after its header, if it is a dump of `format` that loads
+pub fn check(format: Format, dump: &str) -> Result<&str, LoadError> {
+    let (found, version) = header(dump)?;
+    if found != format {
+        return Err(LoadError::new(1, format!("expected a dump of {}, found {}", format, found)));
+    }
+    if !format.version().loads(version) {
+        return Err(LoadError::new(
+            1,
+            format!(
+                "{} {} is not supported: this snake reads {} {}.x up to {}",
+                format,
+                version,
+                format,
+                format.version().major,
+                format.version()
+            ),
+        ));
+    }
+    Ok(dump.split_once('\n').map_or("", |(_, body)| body))
+}
+
+/// The lines of the body of a dump that are not blank, with their numbers
+fn lines(body: &str) -> impl Iterator<Item = (usize, &str)> {
+    (body.lines().enumerate())
+        .map(|(i, line)| (i + 2, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+/// The length of the string literal that `text` starts with, up to the
+/// closing quote or the end of `text`
+fn literal_len(text: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            '"' if !escaped => return i + 1,
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    text.len()
+}
+
+fn parse<T: FromStr<Err = String>>(line: usize, s: &str) -> Result<T, LoadError> {
+    s.parse().map_err(|e| LoadError::new(line, e))
+}
+
+/* ----------------------------------- AST ---------------------------------- */
+
+impl FromStr for SurfProg {
+    type Err = LoadError;
+
+    /// The header is a comment of the program, which parses as a whole
+    fn from_str(dump: &str) -> Result<Self, Self::Err> {
+        check(Format::Ast, dump)?;
+        crate::compile::parse(dump).map_err(|err| {
+            let start = err.loc.map_or(dump.len(), |loc| loc.start_ix);
+            let line = dump[..start.min(dump.len())].matches('\n').count() + 1;
+            LoadError::new(line, err.message)
+        })
+    }
+}
+
+/* -------------------------------- Analyses -------------------------------- */
+
+impl FromStr for Graph<VarName> {
+    type Err = LoadError;
+
+    /// `vertex v` and `edge v1 v2` lines
+    fn from_str(dump: &str) -> Result<Self, Self::Err> {
+        let mut graph = Graph::new();
+        for (line, text) in lines(check(Format::Graph, dump)?) {
+            match text.split_whitespace().collect::<Vec<_>>()[..] {
+                ["vertex", v] => graph.insert_vertex(parse(line, v)?),
+                ["edge", v1, v2] => graph.insert_edge(parse(line, v1)?, parse(line, v2)?),
+                _ => {
+                    return Err(LoadError::new(
+                        line,
+                        format!("expected a vertex or an edge, found `{}`", text),
+                    ))
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
+
+impl FromStr for PerfectEliminationOrder {
+    type Err = LoadError;
+
+    /// `v1 -> v2 -> ...` on one line
+    fn from_str(dump: &str) -> Result<Self, Self::Err> {
+        let mut order = PerfectEliminationOrder::new();
+        for (line, text) in lines(check(Format::ElimOrder, dump)?) {
+            if !order.is_empty() {
+                return Err(LoadError::new(line, "expected the order on one line"));
+            }
+            for v in text.split("->") {
+                order.push(parse(line, v.trim())?);
+            }
+        }
+        Ok(order)
+    }
+}
+
+impl FromStr for Allocation {
+    type Err = String;
+
+    /// A register, e.g. `rbx`, or a stack slot, e.g. `<2>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(slot) = s.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            let slot = slot.parse().map_err(|_| format!("`{}` is not a stack slot", s))?;
+            return Ok(Allocation::Spill(slot));
+        }
+        let reg = Reg::ALL.into_iter().find(|reg| reg.to_string() == s);
+        reg.map(Allocation::Reg).ok_or_else(|| format!("`{}` is not a register", s))
+    }
+}
+
+impl FromStr for Coloring {
+    type Err = LoadError;
+
+    /// `v -> allocation` lines
+    fn from_str(dump: &str) -> Result<Self, Self::Err> {
+        let mut coloring = Coloring::new();
+        for (line, text) in lines(check(Format::Coloring, dump)?) {
+            let (v, allocation) = text.split_once("->").ok_or_else(|| {
+                LoadError::new(line, format!("expected `var -> allocation`, found `{}`", text))
+            })?;
+            coloring.0.insert(parse(line, v.trim())?, parse(line, allocation.trim())?);
+        }
+        Ok(coloring)
+    }
+}
+
+/* ----------------------------------- SSA ---------------------------------- */
+
+const PRIM2: [Prim2; 18] = [
+    Prim2::Add,
+    Prim2::Sub,
+    Prim2::Mul,
+    Prim2::WrappingAdd,
+    Prim2::WrappingSub,
+    Prim2::WrappingMul,
+    Prim2::SaturatingAdd,
+    Prim2::SaturatingSub,
+    Prim2::SaturatingMul,
+    Prim2::BitAnd,
+    Prim2::BitOr,
+    Prim2::BitXor,
+    Prim2::Lt,
+    Prim2::Le,
+    Prim2::Gt,
+    Prim2::Ge,
+    Prim2::Eq,
+    Prim2::Neq,
+];
+
+impl FromStr for Program<VarName, Nil> {
+    type Err = LoadError;
+
+    /// The tokens of SSA are separated by spaces, or are punctuation, and the
+    /// blocks nest as their bodies end with a terminator, so the parser does
+    /// without the indentation and the line breaks.
+    fn from_str(dump: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Tokens::new(check(Format::Ssa, dump)?);
+        let mut prog = Program { externs: Vec::new(), funs: Vec::new(), blocks: Vec::new() };
+        while tokens.peek() == Some("extern") {
+            prog.externs.push(tokens.ext()?);
+        }
+        while tokens.peek() == Some("fun") {
+            prog.funs.push(tokens.fun()?);
+        }
+        while tokens.peek() == Some("block") {
+            prog.blocks.push(tokens.block()?);
+        }
+        match tokens.peek() {
+            None => Ok(prog),
+            Some(token) => Err(tokens.error(format!("expected a block, found `{}`", token))),
+        }
+    }
+}
+
+struct Tokens<'a> {
+    /// The tokens with their lines
+    tokens: Vec<(usize, &'a str)>,
+    pos: usize,
+    last_line: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(body: &'a str) -> Self {
+        let mut tokens = Vec::new();
+        for (line, text) in lines(body) {
+            // string literals are single tokens, spaces and all
+            let mut text = text;
+            let mut words = Vec::new();
+            while let Some(start) = text.find('"') {
+                let len = literal_len(&text[start..]);
+                words.extend(text[..start].split_whitespace());
+                words.push(&text[start..start + len]);
+                text = &text[start + len..];
+            }
+            words.extend(text.split_whitespace());
+            for word in words {
+                if word.starts_with('"') {
+                    tokens.push((line, word));
+                    continue;
+                }
+                let mut rest = word;
+                while let Some(i) = rest.find(['(', ')', ',', '[', ']', ':']) {
+                    if i > 0 {
+                        tokens.push((line, &rest[..i]));
+                    }
+                    tokens.push((line, &rest[i..i + 1]));
+                    rest = &rest[i + 1..];
+                }
+                if !rest.is_empty() {
+                    tokens.push((line, rest));
+                }
+            }
+        }
+        let last_line = body.lines().count() + 1;
+        Tokens { tokens, pos: 0, last_line }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<&'a str> {
+        self.tokens.get(self.pos + ahead).map(|(_, token)| *token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.last_line, |(line, _)| *line)
+    }
+
+    fn error(&self, message: String) -> LoadError {
+        LoadError::new(self.line(), message)
+    }
+
+    fn next(&mut self) -> Result<&'a str, LoadError> {
+        let token = self.peek().ok_or_else(|| self.error("unexpected end of the dump".into()))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), LoadError> {
+        match self.peek() {
+            Some(token) if token == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(token) => Err(self.error(format!("expected `{}`, found `{}`", expected, token))),
+            None => Err(self.error(format!("expected `{}` at the end of the dump", expected))),
+        }
+    }
+
+    fn parse<T: FromStr<Err = String>>(&mut self) -> Result<T, LoadError> {
+        let line = self.line();
+        parse(line, self.next()?)
+    }
+
+    /// Items separated by commas up to `close`
+    fn list<T>(
+        &mut self, close: &str, mut item: impl FnMut(&mut Self) -> Result<T, LoadError>,
+    ) -> Result<Vec<T>, LoadError> {
+        let mut items = Vec::new();
+        while self.peek() != Some(close) {
+            items.push(item(self)?);
+            if self.peek() != Some(",") {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.expect(close)?;
+        Ok(items)
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T, LoadError> {
+        let line = self.line();
+        let token = self.next()?;
+        (token.parse()).map_err(|_| LoadError::new(line, format!("`{}` is not a number", token)))
+    }
+
+    fn params(&mut self) -> Result<Vec<VarName>, LoadError> {
+        self.expect("(")?;
+        self.list(")", Self::parse)
+    }
+
+    fn args(&mut self) -> Result<Vec<Immediate<VarName>>, LoadError> {
+        self.expect("(")?;
+        self.list(")", Self::imm)
+    }
+
+    fn imm(&mut self) -> Result<Immediate<VarName>, LoadError> {
+        let line = self.line();
+        let token = self.next()?;
+        match token.parse() {
+            Ok(n) => Ok(Immediate::Const(n)),
+            Err(_) => Ok(Immediate::Var(parse(line, token)?)),
+        }
+    }
+
+    fn ext(&mut self) -> Result<Extern<VarName>, LoadError> {
+        self.expect("extern")?;
+        let name = self.parse()?;
+        let params = self.params()?;
+        let mut contract = Contract::default();
+        while let Some(values) = self.peek().and_then(Contract::values) {
+            let key = self.next()?;
+            self.expect("(")?;
+            let value = self.next()?;
+            if !values.contains(&value) {
+                return Err(self.error(format!("`{}` is not a value of `{}`", value, key)));
+            }
+            self.expect(")")?;
+            contract.annotate(key, value);
+        }
+        Ok(Extern { name, params, contract })
+    }
+
+    fn fun(&mut self) -> Result<FunBlock<VarName>, LoadError> {
+        self.expect("fun")?;
+        let name = self.parse()?;
+        let params = self.params()?;
+        self.expect(":")?;
+        self.expect("br")?;
+        Ok(FunBlock { name, params, body: self.branch()? })
+    }
+
+    fn branch(&mut self) -> Result<Branch<VarName>, LoadError> {
+        Ok(Branch { target: self.parse()?, args: self.args()? })
+    }
+
+    fn block(&mut self) -> Result<BasicBlock<VarName, Nil>, LoadError> {
+        self.expect("block")?;
+        let label = self.parse()?;
+        let params = self.params()?;
+        self.expect(":")?;
+        Ok(BasicBlock { label, params, body: self.body()?, ana: Nil })
+    }
+
+    fn body(&mut self) -> Result<BlockBody<VarName, Nil>, LoadError> {
+        use BlockBody::*;
+        if self.peek_at(1) == Some("=") {
+            let dest = self.parse()?;
+            self.expect("=")?;
+            let op = self.operation()?;
+            return Ok(Operation { dest, op, next: Box::new(self.body()?), ana: Nil });
+        }
+        let token = self.peek().ok_or_else(|| self.error("expected a terminator".into()))?;
+        match token {
+            "block" => {
+                let mut blocks = Vec::new();
+                while self.peek() == Some("block") {
+                    blocks.push(self.block()?);
+                }
+                Ok(SubBlocks { blocks, next: Box::new(self.body()?), ana: Nil })
+            }
+            "ret" | "br" | "cbr" | "switch" => Ok(Terminator(self.terminator()?, Nil)),
+            "assertLength" => {
+                self.next()?;
+                let [len] = self.operands()?;
+                Ok(AssertLength { len, next: Box::new(self.body()?), ana: Nil })
+            }
+            "assertInBounds" => {
+                self.next()?;
+                let [bound, arg] = self.operands()?;
+                Ok(AssertInBounds { bound, arg, next: Box::new(self.body()?), ana: Nil })
+            }
+            "store" => {
+                self.next()?;
+                let [addr, offset, val] = self.operands()?;
+                Ok(Store { addr, offset, val, next: Box::new(self.body()?), ana: Nil })
+            }
+            token if token.starts_with("assert") => {
+                let ty = [Type::Int, Type::Bool, Type::Array]
+                    .into_iter()
+                    .find(|ty| token == format!("assert{}", ty))
+                    .ok_or_else(|| self.error(format!("`{}` is not an assertion", token)))?;
+                self.next()?;
+                let [arg] = self.operands()?;
+                Ok(AssertType { ty, arg, next: Box::new(self.body()?), ana: Nil })
+            }
+            token => Err(self.error(format!("expected a statement, found `{}`", token))),
+        }
+    }
+
+    /// `(a, b, ...)` of exactly `N` immediates
+    fn operands<const N: usize>(&mut self) -> Result<[Immediate<VarName>; N], LoadError> {
+        let line = self.line();
+        let args = self.args()?;
+        let found = args.len();
+        (args.try_into())
+            .map_err(|_| LoadError::new(line, format!("expected {} operands, found {}", N, found)))
+    }
+
+    fn terminator(&mut self) -> Result<Terminator<VarName>, LoadError> {
+        match self.next()? {
+            "ret" => Ok(Terminator::Return(self.imm()?)),
+            "br" => Ok(Terminator::Branch(self.branch()?)),
+            "cbr" => {
+                let cond = self.imm()?;
+                Ok(Terminator::ConditionalBranch { cond, thn: self.parse()?, els: self.parse()? })
+            }
+            _ => {
+                let scrutinee = self.imm()?;
+                self.expect("-")?;
+                let low = self.number()?;
+                self.expect("[")?;
+                let targets = self.list("]", Self::parse)?;
+                Ok(Terminator::Switch { scrutinee, low, targets, default: self.parse()? })
+            }
+        }
+    }
+
+    fn operation(&mut self) -> Result<Operation<VarName>, LoadError> {
+        if let Some(lit) = self.peek().filter(|token| token.starts_with('"')) {
+            let s = (lit.len() >= 2 && lit.ends_with('"'))
+                .then(|| unescape(&lit[1..lit.len() - 1]))
+                .flatten();
+            let s = s.ok_or_else(|| self.error(format!("`{}` is not a string literal", lit)))?;
+            self.next()?;
+            return Ok(Operation::Str(s));
+        }
+        if self.peek() == Some("~") {
+            self.next()?;
+            return Ok(Operation::Prim1(Prim1::BitNot, self.imm()?));
+        }
+        if self.peek_at(1) == Some("(") {
+            return match self.peek() {
+                Some("allocateArray") => {
+                    self.next()?;
+                    let [len] = self.operands()?;
+                    Ok(Operation::AllocateArray { len })
+                }
+                Some("load") => {
+                    self.next()?;
+                    let [addr, offset] = self.operands()?;
+                    Ok(Operation::Load { addr, offset })
+                }
+                _ => Ok(Operation::Call { fun: self.parse()?, args: self.args()? }),
+            };
+        }
+        let imm = self.imm()?;
+        let shift: Option<fn(u8) -> Prim1> = match self.peek() {
+            Some("<<") => Some(Prim1::BitSal),
+            Some(">>") => Some(Prim1::BitSar),
+            Some("<<<") => Some(Prim1::BitShl),
+            Some(">>>") => Some(Prim1::BitShr),
+            _ => None,
+        };
+        if let Some(shift) = shift {
+            self.next()?;
+            let n = self.number()?;
+            return Ok(Operation::Prim1(shift(n), imm));
+        }
+        let prim = PRIM2.into_iter().find(|prim| self.peek() == Some(&prim.to_string()));
+        match prim {
+            Some(prim) => {
+                self.next()?;
+                Ok(Operation::Prim2(prim, imm, self.imm()?))
+            }
+            None => Ok(Operation::Immediate(imm)),
+        }
+    }
+}
@@ -0,0 +1,729 @@
+//! An assembler for the code of the backend, which encodes it into an image
+//! that the runtime maps into memory instead of linking it. The `hermetic`
+//! feature of `snake-cli` runs executables this way, without `nasm`, `ar` or
+//! `rustc`.
+//!
+//! The image is a single block of code and data: the sections follow each
+//! other in the order that the instructions switch to them. The external
+//! symbols are reached through QWORD slots at its end, which the loader
+//! fills in with their addresses, so that the image works wherever it is.
+
+use crate::asm::*;
+use std::collections::HashMap;
+
+/// The code and data of a program, position-independent but for the slots
+/// and QWORDs that the loader fills in
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Image {
+    pub bytes: Vec<u8>,
+    /// The offset of `entry`
+    pub entry: usize,
+    /// The offsets of the slots that hold the addresses of external symbols
+    pub imports: Vec<(usize, String)>,
+    /// The offsets of the QWORDs that hold offsets into the image, to which
+    /// the loader adds the address of the image
+    pub relocations: Vec<usize>,
+}
+
+/// The magic number at the end of an executable whose image is appended
+pub const IMAGE_MAGIC: &[u8; 8] = b"SNAKEIMG";
+
+impl Image {
+    /// The image as the loader of `runtime/loader.rs` reads it: little-endian
+    /// QWORDs for the lengths, the offsets and the entry.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let quad = |out: &mut Vec<u8>, n: usize| out.extend((n as u64).to_le_bytes());
+        quad(&mut out, self.bytes.len());
+        quad(&mut out, self.entry);
+        quad(&mut out, self.imports.len());
+        quad(&mut out, self.relocations.len());
+        out.extend(&self.bytes);
+        for (offset, name) in self.imports.iter() {
+            quad(&mut out, *offset);
+            quad(&mut out, name.len());
+            out.extend(name.as_bytes());
+        }
+        for offset in self.relocations.iter() {
+            quad(&mut out, *offset);
+        }
+        out
+    }
+}
+
+/* ---------------------------------- Parser --------------------------------- */
+
+const REGS: [Reg; 16] = [
+    Reg::Rax,
+    Reg::Rbx,
+    Reg::Rdx,
+    Reg::Rcx,
+    Reg::Rsi,
+    Reg::Rdi,
+    Reg::Rsp,
+    Reg::Rbp,
+    Reg::R8,
+    Reg::R9,
+    Reg::R10,
+    Reg::R11,
+    Reg::R12,
+    Reg::R13,
+    Reg::R14,
+    Reg::R15,
+];
+
+const REG8S: [Reg8; 20] = [
+    Reg8::Ah,
+    Reg8::Al,
+    Reg8::Ch,
+    Reg8::Cl,
+    Reg8::Dh,
+    Reg8::Dl,
+    Reg8::Bh,
+    Reg8::Bl,
+    Reg8::Spl,
+    Reg8::Bpl,
+    Reg8::Sil,
+    Reg8::Dil,
+    Reg8::R8b,
+    Reg8::R9b,
+    Reg8::R10b,
+    Reg8::R11b,
+    Reg8::R12b,
+    Reg8::R13b,
+    Reg8::R14b,
+    Reg8::R15b,
+];
+
+const CONDITION_CODES: [ConditionCode; 12] = [
+    ConditionCode::E,
+    ConditionCode::NE,
+    ConditionCode::L,
+    ConditionCode::LE,
+    ConditionCode::G,
+    ConditionCode::GE,
+    ConditionCode::S,
+    ConditionCode::Z,
+    ConditionCode::NZ,
+    ConditionCode::O,
+    ConditionCode::NO,
+    ConditionCode::AE,
+];
+
+/// Parses the assembly that `Asm` prints back into instructions; only the
+/// syntax that the backend emits is understood, not that of hand-written
+/// runtimes such as `runtime/freestanding.asm`.
+pub fn parse(text: &str) -> Result<Asm, String> {
+    let mut symbols = Symbols::new();
+    let mut instrs = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let instr = parse_line(line, &mut symbols)
+            .ok_or_else(|| format!("line {}: cannot assemble `{}`", i + 1, line.trim()))?;
+        instrs.extend(instr);
+    }
+    Ok(Asm { instrs, symbols })
+}
+
+fn parse_line(line: &str, symbols: &mut Symbols) -> Option<Option<Instr>> {
+    if line.trim().is_empty() {
+        return Some(None);
+    }
+    if let Some(comment) = line.strip_prefix(";;; ") {
+        return Some(Some(Instr::Comment(comment.to_string())));
+    }
+    if let Some(section) = line.strip_prefix("section ") {
+        return Some(Some(Instr::Section(section.to_string())));
+    }
+    if !line.starts_with(' ') {
+        let label = line.strip_suffix(':')?;
+        return Some(Some(Instr::Label(symbols.local(label))));
+    }
+    let line = line.trim();
+    let (op, args) = line.split_once(' ').unwrap_or((line, ""));
+    let instr = match op {
+        "global" => Instr::Global(symbols.global(args)),
+        "extern" => Instr::Extern(symbols.external(args)),
+        "ret" => Instr::Ret,
+        "align" => Instr::Align(args.parse().ok()?),
+        "db" => parse_db(args)?,
+        "dq" => match args.parse() {
+            Ok(n) => Instr::Quad(n),
+            Err(_) => Instr::QuadAddr(symbols.local(args)),
+        },
+        "dd" => {
+            let (l, anchor) = args.split_once(" - ")?;
+            match anchor {
+                "$" => Instr::PcRel(symbols.local(l)),
+                _ => Instr::RelOffset(symbols.local(l), symbols.local(anchor)),
+            }
+        }
+        "call" => Instr::Call(symbols.local(args)),
+        // the conditional jumps, as `jmp` is no condition
+        _ if op.starts_with('j') && condition_code(&op[1..]).is_some() => {
+            Instr::JCC(condition_code(&op[1..])?, symbols.local(args))
+        }
+        _ if op.starts_with("set") => {
+            let r = REG8S.into_iter().find(|r| r.to_string() == args)?;
+            Instr::SetCC(condition_code(&op[3..])?, r)
+        }
+        "jmp" => match reg(args) {
+            Some(r) => Instr::JmpReg(r),
+            None => Instr::Jmp(symbols.local(args)),
+        },
+        "push" => Instr::Push(arg32(args)?),
+        "pop" => Instr::Pop(match reg(args) {
+            Some(r) => Loc::Reg(r),
+            None => Loc::Mem(mem(args)?),
+        }),
+        _ => {
+            let (dst, src) = args.split_once(", ")?;
+            match op {
+                "mov" => Instr::Mov(match reg(dst) {
+                    Some(r) => MovArgs::ToReg(r, arg64(src)?),
+                    None => MovArgs::ToMem(mem(dst)?, reg32(src)?),
+                }),
+                "add" => Instr::Add(bin_args(dst, src)?),
+                "sub" => Instr::Sub(bin_args(dst, src)?),
+                "imul" => Instr::IMul(bin_args(dst, src)?),
+                "and" => Instr::And(bin_args(dst, src)?),
+                "or" => Instr::Or(bin_args(dst, src)?),
+                "xor" => Instr::Xor(bin_args(dst, src)?),
+                "cmp" => Instr::Cmp(bin_args(dst, src)?),
+                "test" => Instr::Test(bin_args(dst, src)?),
+                "sal" => Instr::Sal(sh_args(dst, src)?),
+                "sar" => Instr::Sar(sh_args(dst, src)?),
+                "shl" => Instr::Shl(sh_args(dst, src)?),
+                "shr" => Instr::Shr(sh_args(dst, src)?),
+                "xchg" => Instr::Xchg(reg(dst)?, reg(src)?),
+                "movsxd" => Instr::Movsxd(reg(dst)?, index_ref(src)?),
+                "lea" => {
+                    let label = src.strip_prefix("[rel ")?.strip_suffix(']')?;
+                    Instr::Lea(reg(dst)?, symbols.local(label))
+                }
+                _ => Instr::CMovCC(condition_code(op.strip_prefix("cmov")?)?, bin_args(dst, src)?),
+            }
+        }
+    };
+    Some(Some(instr))
+}
+
+fn parse_db(args: &str) -> Option<Instr> {
+    if let Some(s) = args.strip_prefix('`') {
+        let s = s.strip_suffix("`, 0")?;
+        let mut bytes = Vec::new();
+        let mut chars = s.bytes();
+        while let Some(b) = chars.next() {
+            match b {
+                b'\\' => match chars.next()? {
+                    b'x' => {
+                        let hex = [chars.next()?, chars.next()?];
+                        bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?)
+                    }
+                    b => bytes.push(b),
+                },
+                b => bytes.push(b),
+            }
+        }
+        return Some(Instr::Asciz(String::from_utf8(bytes).ok()?));
+    }
+    if args.is_empty() {
+        return Some(Instr::Bytes(Vec::new()));
+    }
+    let bytes = args.split(", ").map(|b| u8::from_str_radix(b.strip_prefix("0x")?, 16).ok());
+    Some(Instr::Bytes(bytes.collect::<Option<_>>()?))
+}
+
+fn condition_code(s: &str) -> Option<ConditionCode> {
+    CONDITION_CODES.into_iter().find(|cc| cc.to_string() == s)
+}
+
+fn reg(s: &str) -> Option<Reg> {
+    REGS.into_iter().find(|r| reg_to_string(*r) == s)
+}
+
+fn mem(s: &str) -> Option<MemRef> {
+    let (r, offset) = s.strip_prefix("QWORD [")?.strip_suffix(']')?.split_once(" + ")?;
+    Some(MemRef { reg: reg(r)?, offset: offset.parse().ok()? })
+}
+
+fn index_ref(s: &str) -> Option<IndexRef> {
+    let (base, scaled) = s.strip_prefix("DWORD [")?.strip_suffix(']')?.split_once(" + ")?;
+    let (index, scale) = scaled.split_once(" * ")?;
+    Some(IndexRef { base: reg(base)?, index: reg(index)?, scale: scale.parse().ok()? })
+}
+
+fn arg64(s: &str) -> Option<Arg64> {
+    match s.strip_prefix("0x") {
+        Some(hex) => Some(Arg64::Unsigned(u64::from_str_radix(hex, 16).ok()?)),
+        None => reg(s)
+            .map(Arg64::Reg)
+            .or_else(|| mem(s).map(Arg64::Mem))
+            .or_else(|| s.parse().ok().map(Arg64::Signed)),
+    }
+}
+
+fn arg32(s: &str) -> Option<Arg32> {
+    match s.strip_prefix("0x") {
+        Some(hex) => Some(Arg32::Unsigned(u32::from_str_radix(hex, 16).ok()?)),
+        None => reg(s)
+            .map(Arg32::Reg)
+            .or_else(|| mem(s).map(Arg32::Mem))
+            .or_else(|| s.parse().ok().map(Arg32::Signed)),
+    }
+}
+
+fn reg32(s: &str) -> Option<Reg32> {
+    reg(s).map(Reg32::Reg).or_else(|| s.parse().ok().map(Reg32::Imm))
+}
+
+fn bin_args(dst: &str, src: &str) -> Option<BinArgs> {
+    match reg(dst) {
+        Some(r) => Some(BinArgs::ToReg(r, arg32(src)?)),
+        None => Some(BinArgs::ToMem(mem(dst)?, reg32(src)?)),
+    }
+}
+
+fn sh_args(dst: &str, src: &str) -> Option<ShArgs> {
+    Some(ShArgs { reg: reg(dst)?, by: src.parse().ok()? })
+}
+
+/* --------------------------------- Encoder --------------------------------- */
+
+/// A DWORD to fill in once the labels are placed: the distance to `target`
+/// from `anchor`
+struct Fixup {
+    at: usize,
+    target: Label,
+    anchor: Anchor,
+}
+
+enum Anchor {
+    Offset(usize),
+    Label(Label),
+}
+
+struct Encoder<'a> {
+    symbols: &'a Symbols,
+    bytes: Vec<u8>,
+    labels: HashMap<Label, usize>,
+    fixups: Vec<Fixup>,
+    /// The QWORDs holding the offsets of labels
+    addresses: Vec<(usize, Label)>,
+    /// The external symbols in the order of their first use
+    imports: Vec<Label>,
+    /// Whether the current section holds code, which is padded with nops
+    text: bool,
+}
+
+/// Encodes `asm` into an image, or fails on instructions that have no
+/// encoding, e.g. a conditional jump to an external symbol.
+pub fn encode(asm: &Asm) -> Result<Image, String> {
+    let mut encoder = Encoder {
+        symbols: &asm.symbols,
+        bytes: Vec::new(),
+        labels: HashMap::new(),
+        fixups: Vec::new(),
+        addresses: Vec::new(),
+        imports: Vec::new(),
+        text: true,
+    };
+    for instr in asm.instrs.iter() {
+        encoder.instr(instr).ok_or_else(|| {
+            format!("cannot encode `{}`", instr_to_string(instr, &asm.symbols).trim())
+        })?;
+    }
+    encoder.link()
+}
+
+/// The number of a register in the ModRM, SIB and REX bytes
+fn num(r: Reg) -> u8 {
+    match r {
+        Reg::Rax => 0,
+        Reg::Rcx => 1,
+        Reg::Rdx => 2,
+        Reg::Rbx => 3,
+        Reg::Rsp => 4,
+        Reg::Rbp => 5,
+        Reg::Rsi => 6,
+        Reg::Rdi => 7,
+        Reg::R8 => 8,
+        Reg::R9 => 9,
+        Reg::R10 => 10,
+        Reg::R11 => 11,
+        Reg::R12 => 12,
+        Reg::R13 => 13,
+        Reg::R14 => 14,
+        Reg::R15 => 15,
+    }
+}
+
+/// The number of a byte register, and whether it needs a REX prefix: spl,
+/// bpl, sil and dil share their numbers with ah, ch, dh and bh without one
+fn num8(r: Reg8) -> (u8, bool) {
+    match r {
+        Reg8::Al => (0, false),
+        Reg8::Cl => (1, false),
+        Reg8::Dl => (2, false),
+        Reg8::Bl => (3, false),
+        Reg8::Ah => (4, false),
+        Reg8::Ch => (5, false),
+        Reg8::Dh => (6, false),
+        Reg8::Bh => (7, false),
+        Reg8::Spl => (4, true),
+        Reg8::Bpl => (5, true),
+        Reg8::Sil => (6, true),
+        Reg8::Dil => (7, true),
+        Reg8::R8b => (8, true),
+        Reg8::R9b => (9, true),
+        Reg8::R10b => (10, true),
+        Reg8::R11b => (11, true),
+        Reg8::R12b => (12, true),
+        Reg8::R13b => (13, true),
+        Reg8::R14b => (14, true),
+        Reg8::R15b => (15, true),
+    }
+}
+
+/// The low nibble of the opcodes of jcc, cmovcc and setcc
+fn cc_code(cc: ConditionCode) -> u8 {
+    match cc {
+        ConditionCode::O => 0x0,
+        ConditionCode::NO => 0x1,
+        ConditionCode::AE => 0x3,
+        ConditionCode::E | ConditionCode::Z => 0x4,
+        ConditionCode::NE | ConditionCode::NZ => 0x5,
+        ConditionCode::S => 0x8,
+        ConditionCode::L => 0xc,
+        ConditionCode::GE => 0xd,
+        ConditionCode::LE => 0xe,
+        ConditionCode::G => 0xf,
+    }
+}
+
+impl Encoder<'_> {
+    fn instr(&mut self, instr: &Instr) -> Option<()> {
+        match instr {
+            Instr::Mov(MovArgs::ToReg(r, Arg64::Reg(s))) => {
+                self.rm(true, &[0x89], num(*s), Loc::Reg(*r))
+            }
+            Instr::Mov(MovArgs::ToReg(r, Arg64::Mem(m))) => {
+                self.rm(true, &[0x8b], num(*r), Loc::Mem(*m))
+            }
+            Instr::Mov(MovArgs::ToReg(r, Arg64::Signed(n))) => self.mov_imm(*r, *n as i128),
+            Instr::Mov(MovArgs::ToReg(r, Arg64::Unsigned(n))) => self.mov_imm(*r, *n as i128),
+            Instr::Mov(MovArgs::ToMem(m, Reg32::Reg(s))) => {
+                self.rm(true, &[0x89], num(*s), Loc::Mem(*m))
+            }
+            Instr::Mov(MovArgs::ToMem(m, Reg32::Imm(n))) => {
+                self.rm(true, &[0xc7], 0, Loc::Mem(*m));
+                self.bytes.extend(n.to_le_bytes());
+            }
+            Instr::Add(args) => self.alu(0, *args),
+            Instr::Or(args) => self.alu(1, *args),
+            Instr::And(args) => self.alu(4, *args),
+            Instr::Sub(args) => self.alu(5, *args),
+            Instr::Xor(args) => self.alu(6, *args),
+            Instr::Cmp(args) => self.alu(7, *args),
+            Instr::IMul(BinArgs::ToReg(r, arg)) => match *arg {
+                Arg32::Reg(s) => self.rm(true, &[0x0f, 0xaf], num(*r), Loc::Reg(s)),
+                Arg32::Mem(m) => self.rm(true, &[0x0f, 0xaf], num(*r), Loc::Mem(m)),
+                Arg32::Signed(n) => self.imul_imm(*r, n),
+                Arg32::Unsigned(n) => self.imul_imm(*r, n as i32),
+            },
+            Instr::IMul(BinArgs::ToMem(..)) => return None,
+            Instr::Test(BinArgs::ToReg(r, arg)) => match *arg {
+                Arg32::Reg(s) => self.rm(true, &[0x85], num(s), Loc::Reg(*r)),
+                Arg32::Mem(m) => self.rm(true, &[0x85], num(*r), Loc::Mem(m)),
+                Arg32::Signed(n) => self.test_imm(Loc::Reg(*r), n),
+                Arg32::Unsigned(n) => self.test_imm(Loc::Reg(*r), n as i32),
+            },
+            Instr::Test(BinArgs::ToMem(m, Reg32::Reg(s))) => {
+                self.rm(true, &[0x85], num(*s), Loc::Mem(*m))
+            }
+            Instr::Test(BinArgs::ToMem(m, Reg32::Imm(n))) => self.test_imm(Loc::Mem(*m), *n),
+            Instr::Sal(args) | Instr::Shl(args) => self.shift(4, *args),
+            Instr::Shr(args) => self.shift(5, *args),
+            Instr::Sar(args) => self.shift(7, *args),
+            Instr::Xchg(a, b) => self.rm(true, &[0x87], num(*b), Loc::Reg(*a)),
+            Instr::Movsxd(r, IndexRef { base, index, scale }) => {
+                let scale = [1, 2, 4, 8].iter().position(|s| s == scale)? as u8;
+                if *index == Reg::Rsp {
+                    return None;
+                }
+                let (r, index, base) = (num(*r), num(*index), num(*base));
+                self.rex(true, r, index, base);
+                // rbp and r13 as bases take a zero displacement
+                let disp = base & 7 == 5;
+                self.bytes.push(0x63);
+                self.bytes.push((disp as u8) << 6 | (r & 7) << 3 | 0b100);
+                self.bytes.push(scale << 6 | (index & 7) << 3 | base & 7);
+                if disp {
+                    self.bytes.push(0);
+                }
+            }
+            Instr::Lea(r, l) => {
+                // the address of an external symbol is loaded from its slot
+                let op = if self.is_extern(*l) { 0x8b } else { 0x8d };
+                self.rex(true, num(*r), 0, 0);
+                self.bytes.extend([op, (num(*r) & 7) << 3 | 0b101]);
+                self.rel32(*l);
+            }
+            Instr::Push(arg) => match *arg {
+                Arg32::Reg(r) => {
+                    self.rex(false, 0, 0, num(r));
+                    self.bytes.push(0x50 | num(r) & 7);
+                }
+                Arg32::Signed(n) => self.push_imm(n),
+                Arg32::Unsigned(n) => self.push_imm(n as i32),
+                Arg32::Mem(m) => self.rm(false, &[0xff], 6, Loc::Mem(m)),
+            },
+            Instr::Pop(Loc::Reg(r)) => {
+                self.rex(false, 0, 0, num(*r));
+                self.bytes.push(0x58 | num(*r) & 7);
+            }
+            Instr::Pop(Loc::Mem(m)) => self.rm(false, &[0x8f], 0, Loc::Mem(*m)),
+            Instr::Label(l) => {
+                if self.labels.insert(*l, self.bytes.len()).is_some() {
+                    return None;
+                }
+            }
+            Instr::Section(name) => self.text = name.starts_with(".text"),
+            Instr::Comment(_) | Instr::Global(_) | Instr::Extern(_) | Instr::Cfi(_) => {}
+            Instr::Call(l) => self.branch(&[0xe8], &[0xff, 0x15], *l),
+            Instr::Jmp(l) => self.branch(&[0xe9], &[0xff, 0x25], *l),
+            Instr::JCC(cc, l) => {
+                if self.is_extern(*l) {
+                    return None;
+                }
+                self.branch(&[0x0f, 0x80 | cc_code(*cc)], &[], *l);
+            }
+            Instr::JmpReg(r) => self.rm(false, &[0xff], 4, Loc::Reg(*r)),
+            Instr::Ret => self.bytes.push(0xc3),
+            Instr::CMovCC(cc, BinArgs::ToReg(r, Arg32::Reg(s))) => {
+                self.rm(true, &[0x0f, 0x40 | cc_code(*cc)], num(*r), Loc::Reg(*s))
+            }
+            Instr::CMovCC(cc, BinArgs::ToReg(r, Arg32::Mem(m))) => {
+                self.rm(true, &[0x0f, 0x40 | cc_code(*cc)], num(*r), Loc::Mem(*m))
+            }
+            Instr::CMovCC(..) => return None,
+            Instr::SetCC(cc, r) => {
+                let (r, rex) = num8(*r);
+                if rex {
+                    self.bytes.push(0x40 | r >> 3);
+                }
+                self.bytes.extend([0x0f, 0x90 | cc_code(*cc), 0xc0 | r & 7]);
+            }
+            Instr::RelOffset(l, anchor) => {
+                let at = self.bytes.len();
+                self.fixups.push(Fixup { at, target: *l, anchor: Anchor::Label(*anchor) });
+                self.bytes.extend([0; 4]);
+            }
+            Instr::PcRel(l) => {
+                let at = self.bytes.len();
+                self.fixups.push(Fixup { at, target: *l, anchor: Anchor::Offset(at) });
+                self.bytes.extend([0; 4]);
+            }
+            Instr::Quad(n) => self.bytes.extend(n.to_le_bytes()),
+            Instr::QuadAddr(l) => {
+                self.addresses.push((self.bytes.len(), *l));
+                self.bytes.extend([0; 8]);
+            }
+            Instr::Asciz(s) => {
+                self.bytes.extend(s.as_bytes());
+                self.bytes.push(0);
+            }
+            Instr::Bytes(bytes) => self.bytes.extend(bytes),
+            Instr::Align(n) => self.align(*n as usize),
+        }
+        Some(())
+    }
+
+    fn is_extern(&self, l: Label) -> bool {
+        self.symbols.binding(l) == Binding::Extern
+    }
+
+    fn align(&mut self, n: usize) {
+        let pad = if self.text { 0x90 } else { 0 };
+        while !self.bytes.len().is_multiple_of(n.max(1)) {
+            self.bytes.push(pad);
+        }
+    }
+
+    /// The REX prefix of the registers in the reg, index and base fields, if
+    /// it is needed
+    fn rex(&mut self, wide: bool, reg: u8, index: u8, base: u8) {
+        let rex = 0x40 | (wide as u8) << 3 | (reg >> 3) << 2 | (index >> 3) << 1 | base >> 3;
+        if rex != 0x40 {
+            self.bytes.push(rex);
+        }
+    }
+
+    /// An instruction whose ModRM byte names the register or opcode extension
+    /// `reg` and the operand `rm`
+    fn rm(&mut self, wide: bool, opcode: &[u8], reg: u8, rm: Loc) {
+        let base = match rm {
+            Loc::Reg(r) => num(r),
+            Loc::Mem(m) => num(m.reg),
+        };
+        self.rex(wide, reg, 0, base);
+        self.bytes.extend(opcode);
+        let Loc::Mem(MemRef { offset, .. }) = rm else {
+            self.bytes.push(0xc0 | (reg & 7) << 3 | base & 7);
+            return;
+        };
+        // rbp and r13 as bases always take a displacement
+        let mode = match i8::try_from(offset) {
+            Ok(0) if base & 7 != 5 => 0,
+            Ok(_) => 1,
+            Err(_) => 2,
+        };
+        self.bytes.push(mode << 6 | (reg & 7) << 3 | base & 7);
+        // rsp and r12 as bases take a SIB byte without an index
+        if base & 7 == 4 {
+            self.bytes.push(0x24);
+        }
+        match mode {
+            1 => self.bytes.push(offset as i8 as u8),
+            2 => self.bytes.extend(offset.to_le_bytes()),
+            _ => {}
+        }
+    }
+
+    fn mov_imm(&mut self, r: Reg, n: i128) {
+        if let Ok(n) = u32::try_from(n) {
+            // mov r32, imm32, zero-extended
+            self.rex(false, 0, 0, num(r));
+            self.bytes.push(0xb8 | num(r) & 7);
+            self.bytes.extend(n.to_le_bytes());
+        } else if let Ok(n) = i32::try_from(n) {
+            // mov r/m64, imm32, sign-extended
+            self.rm(true, &[0xc7], 0, Loc::Reg(r));
+            self.bytes.extend(n.to_le_bytes());
+        } else {
+            // movabs r64, imm64
+            self.rex(true, 0, 0, num(r));
+            self.bytes.push(0xb8 | num(r) & 7);
+            self.bytes.extend((n as u64).to_le_bytes());
+        }
+    }
+
+    /// The arithmetic instruction whose opcodes are `8 * op` plus 1 to write to
+    /// memory, 3 to write to a register, and whose opcode extension is `op`
+    /// with an immediate
+    fn alu(&mut self, op: u8, args: BinArgs) {
+        match args {
+            BinArgs::ToReg(r, Arg32::Reg(s)) => self.rm(true, &[op << 3 | 1], num(s), Loc::Reg(r)),
+            BinArgs::ToReg(r, Arg32::Mem(m)) => self.rm(true, &[op << 3 | 3], num(r), Loc::Mem(m)),
+            BinArgs::ToReg(r, Arg32::Signed(n)) => self.alu_imm(op, Loc::Reg(r), n),
+            BinArgs::ToReg(r, Arg32::Unsigned(n)) => self.alu_imm(op, Loc::Reg(r), n as i32),
+            BinArgs::ToMem(m, Reg32::Reg(s)) => self.rm(true, &[op << 3 | 1], num(s), Loc::Mem(m)),
+            BinArgs::ToMem(m, Reg32::Imm(n)) => self.alu_imm(op, Loc::Mem(m), n),
+        }
+    }
+
+    fn alu_imm(&mut self, op: u8, rm: Loc, n: i32) {
+        match i8::try_from(n) {
+            Ok(n) => {
+                self.rm(true, &[0x83], op, rm);
+                self.bytes.push(n as u8);
+            }
+            Err(_) => {
+                self.rm(true, &[0x81], op, rm);
+                self.bytes.extend(n.to_le_bytes());
+            }
+        }
+    }
+
+    fn imul_imm(&mut self, r: Reg, n: i32) {
+        match i8::try_from(n) {
+            Ok(n) => {
+                self.rm(true, &[0x6b], num(r), Loc::Reg(r));
+                self.bytes.push(n as u8);
+            }
+            Err(_) => {
+                self.rm(true, &[0x69], num(r), Loc::Reg(r));
+                self.bytes.extend(n.to_le_bytes());
+            }
+        }
+    }
+
+    fn test_imm(&mut self, rm: Loc, n: i32) {
+        self.rm(true, &[0xf7], 0, rm);
+        self.bytes.extend(n.to_le_bytes());
+    }
+
+    fn shift(&mut self, op: u8, ShArgs { reg, by }: ShArgs) {
+        if by == 1 {
+            self.rm(true, &[0xd1], op, Loc::Reg(reg));
+        } else {
+            self.rm(true, &[0xc1], op, Loc::Reg(reg));
+            self.bytes.push(by);
+        }
+    }
+
+    fn push_imm(&mut self, n: i32) {
+        match i8::try_from(n) {
+            Ok(n) => self.bytes.extend([0x6a, n as u8]),
+            Err(_) => {
+                self.bytes.push(0x68);
+                self.bytes.extend(n.to_le_bytes());
+            }
+        }
+    }
+
+    /// A jump or call to `l`, indirect through the slot of an external symbol
+    fn branch(&mut self, near: &[u8], indirect: &[u8], l: Label) {
+        if self.is_extern(l) {
+            self.bytes.extend(indirect);
+        } else {
+            self.bytes.extend(near);
+        }
+        self.rel32(l);
+    }
+
+    /// The distance to `l` from the end of the instruction, which the DWORD
+    /// ends
+    fn rel32(&mut self, l: Label) {
+        if self.is_extern(l) && !self.imports.contains(&l) {
+            self.imports.push(l);
+        }
+        let at = self.bytes.len();
+        self.fixups.push(Fixup { at, target: l, anchor: Anchor::Offset(at + 4) });
+        self.bytes.extend([0; 4]);
+    }
+
+    /// Places the slots of the external symbols and fills in the distances
+    fn link(mut self) -> Result<Image, String> {
+        self.text = false;
+        self.align(8);
+        let mut imports = Vec::new();
+        for l in std::mem::take(&mut self.imports) {
+            self.labels.insert(l, self.bytes.len());
+            imports.push((self.bytes.len(), self.symbols.name(l).to_string()));
+            self.bytes.extend([0; 8]);
+        }
+        let offset = |l: &Label| {
+            let name = self.symbols.name(*l);
+            self.labels.get(l).copied().ok_or_else(|| format!("label `{}` is never defined", name))
+        };
+        for Fixup { at, target, anchor } in self.fixups.iter() {
+            let from = match anchor {
+                Anchor::Offset(from) => *from,
+                Anchor::Label(l) => offset(l)?,
+            };
+            let distance = offset(target)? as i64 - from as i64;
+            let distance = i32::try_from(distance).map_err(|_| "the code is over 2 GiB")?;
+            self.bytes[*at..*at + 4].copy_from_slice(&distance.to_le_bytes());
+        }
+        let mut relocations = Vec::new();
+        for (at, l) in self.addresses.iter() {
+            if self.is_extern(*l) {
+                Err(format!("cannot take the address of `{}`", self.symbols.name(*l)))?
+            }
+            self.bytes[*at..*at + 8].copy_from_slice(&(offset(l)? as u64).to_le_bytes());
+            relocations.push(*at);
+        }
+        let entry = self.symbols.get("entry").ok_or("there is no `entry`")?;
+        Ok(Image { entry: offset(&entry)?, imports, relocations, bytes: self.bytes })
+    }
+}
@@ -0,0 +1,422 @@
+//! The errors of the stages of the pipeline.
+//!
+//! Each stage fails with its own enum, so that embedders can match on what
+//! went wrong; [`Error`] is any of them. They display without the source
+//! program, locations as byte offsets, and `render` them with lines and
+//! columns as `snake` reports them.
+
+use crate::asm::Reg;
+pub use crate::frontend::ResolveError;
+use crate::span::SrcLoc;
+use crate::txt::FileInfo;
+use std::fmt;
+
+/// A program that is not in the grammar
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// Where the parser stopped, if anywhere
+    pub loc: Option<SrcLoc>,
+}
+
+/// The built-in operators, which the grammar takes as keywords rather than
+/// as identifiers, so that they are called only as `length(a)`
+pub const BUILTINS: [&str; 21] = [
+    "clock", "add1", "sub1", "isInt", "isBool", "isArray", "isNone", "newArray", "length",
+    "getenv", "random", "yield", "next", "wrapping_add", "wrapping_sub", "wrapping_mul",
+    "saturating_add", "saturating_sub", "saturating_mul", "toString", "concat",
+];
+
+/// The words of the syntax, which cannot be identifiers either
+pub const KEYWORDS: [&str; 16] = [
+    "def", "and", "in", "let", "if", "else", "ifsome", "switch", "match", "end", "extern", "enum",
+    "gen", "none", "true", "false",
+];
+
+/// How the parser lists an identifier among the tokens it expects
+const IDENTIFIER: &str = r####"r#"[a-zA-Z_][a-zA-Z0-9_]*"#"####;
+
+/// The tokens that the parser expects where it expects a name: that of a
+/// variable, a destructuring `[a, b]`, or that of a function after `def gen`,
+/// and the ends of the lists that a trailing comma may close instead
+const NAMES: [&str; 5] = [IDENTIFIER, "\"[\"", "\"gen\"", "\"]\"", "\"in\""];
+
+impl ParseError {
+    /// The error of the parser on `src`, which names the built-in operators
+    /// and keywords used as identifiers rather than listing the tokens that
+    /// the parser expected instead
+    pub fn new<T, E>(err: lalrpop_util::ParseError<usize, T, E>, src: &str) -> Self
+    where
+        T: fmt::Display,
+        E: fmt::Display,
+    {
+        use lalrpop_util::ParseError::*;
+        let conflict = match &err {
+            // e.g. `let length = ...`, where only a name may follow
+            UnrecognizedToken { token: (l, _, r), expected }
+                if expected.iter().any(|token| token == IDENTIFIER)
+                    && expected.iter().all(|token| NAMES.contains(&token.as_str())) =>
+            {
+                let word = &src[*l..*r];
+                let what = if BUILTINS.contains(&word) {
+                    "a built-in operator"
+                } else if KEYWORDS.contains(&word) {
+                    "a keyword"
+                } else {
+                    return err.into();
+                };
+                let message = format!("`{}` is {} and cannot be used as a name here", word, what);
+                Some((message, *l, *r))
+            }
+            // e.g. `length + 1`, where only the call of the operator may follow
+            UnrecognizedToken { token: (location, ..), expected }
+            | UnrecognizedEof { location, expected }
+                if expected.len() == 1 && expected[0] == "\"(\"" =>
+            {
+                let before = src[..*location].trim_end();
+                let start = before
+                    .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .map_or(0, |ix| ix + 1);
+                let word = &before[start..];
+                BUILTINS.contains(&word).then(|| {
+                    let message = format!(
+                        "`{}` is a built-in operator and cannot be used as a name, only called \
+                         as `{}(...)`",
+                        word, word
+                    );
+                    (message, start, before.len())
+                })
+            }
+            _ => None,
+        };
+        match conflict {
+            Some((message, l, r)) => ParseError {
+                message: format!("{}, found at {}:{}", message, l, r),
+                loc: Some(SrcLoc::new(l, r)),
+            },
+            None => err.into(),
+        }
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> From<lalrpop_util::ParseError<usize, T, E>> for ParseError {
+    fn from(err: lalrpop_util::ParseError<usize, T, E>) -> Self {
+        use lalrpop_util::ParseError::*;
+        let loc = match &err {
+            InvalidToken { location } => Some(SrcLoc::new(*location, *location + 1)),
+            UnrecognizedEof { location, .. } => Some(SrcLoc::new(*location, *location)),
+            UnrecognizedToken { token: (l, _, r), .. } | ExtraToken { token: (l, _, r) } => {
+                Some(SrcLoc::new(*l, *r))
+            }
+            User { .. } => None,
+        };
+        ParseError { message: err.to_string(), loc }
+    }
+}
+
+impl ResolveError {
+    pub fn loc(&self) -> SrcLoc {
+        use ResolveError::*;
+        match self {
+            UnboundVariable(_, loc)
+            | DuplicateVariable(_, loc)
+            | UnboundFunction(_, loc)
+            | DuplicateFunction(_, loc)
+            | DuplicateParameter(_, loc)
+            | ArityMismatch { loc, .. }
+            | IntegerOverflow(_, loc)
+            | DuplicateCase(_, loc)
+            | YieldOutsideGenerator(loc)
+            | NonTailYield(_, loc)
+            | UnboundConstructor(_, loc)
+            | DuplicateConstructor(_, loc)
+            | NonExhaustiveMatch(_, loc)
+            | MixedEnums { loc, .. }
+            | SelfReference { loc, .. }
+            | TypeMismatch { loc, .. } => *loc,
+        }
+    }
+
+    /// Where the variable that the error is about is bound, if elsewhere
+    pub fn other(&self) -> Option<SrcLoc> {
+        match self {
+            ResolveError::SelfReference { binding, .. } => Some(*binding),
+            _ => None,
+        }
+    }
+
+    /// What went wrong, without where
+    pub fn message(&self) -> String {
+        use ResolveError::*;
+        match self {
+            UnboundVariable(v, _) => format!("variable \"{}\" unbound", v),
+            DuplicateVariable(v, _) => {
+                format!("variable \"{}\" defined twice in let-expression", v)
+            }
+            UnboundFunction(f, _) => format!("function \"{}\" undefined", f),
+            DuplicateFunction(f, _) => format!("multiple defined functions named \"{}\"", f),
+            DuplicateParameter(p, _) => format!("multiple parameters named \"{}\"", p),
+            ArityMismatch { name, expected, found, .. } => format!(
+                "function \"{}\" of arity {} called with {} arguments",
+                name, expected, found
+            ),
+            IntegerOverflow(i, _) => format!("integer {} overflow", i),
+            DuplicateCase(n, _) => format!("case {} appears twice in switch", n),
+            YieldOutsideGenerator(_) => "yield outside of a generator".to_string(),
+            NonTailYield(f, _) => format!(
+                "function \"{}\" yields, so only its generator may call it, and in tail position",
+                f
+            ),
+            UnboundConstructor(c, _) => format!("constructor \"{}\" undefined", c),
+            DuplicateConstructor(c, _) => format!("multiple variants named \"{}\"", c),
+            NonExhaustiveMatch(p, _) => format!("match does not cover {}", p),
+            MixedEnums { first, second, .. } => format!(
+                "patterns of enums \"{}\" and \"{}\" in the same position of a match",
+                first, second
+            ),
+            SelfReference { name, .. } => format!("binding of \"{}\" refers to itself", name),
+            TypeMismatch { expected, found, .. } => {
+                format!("expected a value of type {}, found {}", expected, found)
+            }
+        }
+    }
+
+    pub fn render(&self, file: &FileInfo) -> String {
+        let at = file.span1_to_span2(self.loc());
+        match self.other() {
+            Some(other) => {
+                format!("{}: {}, bound at {}", self.message(), at, file.span1_to_span2(other))
+            }
+            None => format!("{}: {}", self.message(), at),
+        }
+    }
+}
+
+/// A program whose values cannot have the types its operations need, as
+/// `--types infer` reports it. Gradually, every program type checks and the
+/// runtime checks the types of the values.
+///
+/// The types are shown as the inference names them. `loc` is where the value
+/// got the type it has, and `other` where the type it conflicts with comes
+/// from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeError {
+    Mismatch {
+        expected: String,
+        found: String,
+        loc: SrcLoc,
+        other: SrcLoc,
+    },
+    /// A value whose type would be `var`, with `var` in `ty`
+    Infinite {
+        var: String,
+        ty: String,
+        loc: SrcLoc,
+        other: SrcLoc,
+    },
+    /// What the inference does not cover, e.g. enums
+    Unsupported(String, SrcLoc),
+}
+
+impl TypeError {
+    pub fn loc(&self) -> SrcLoc {
+        match self {
+            TypeError::Mismatch { loc, .. }
+            | TypeError::Infinite { loc, .. }
+            | TypeError::Unsupported(_, loc) => *loc,
+        }
+    }
+
+    /// What went wrong, without where
+    pub fn message(&self) -> String {
+        match self {
+            TypeError::Mismatch { expected, found, .. } => {
+                format!("expected a value of type {}, found {}", expected, found)
+            }
+            TypeError::Infinite { var, ty, .. } => {
+                format!("expected a value of type {}, found {}, which contains it", var, ty)
+            }
+            TypeError::Unsupported(what, _) => format!("type inference does not cover {}", what),
+        }
+    }
+
+    /// Where the expected type comes from, if elsewhere
+    pub fn other(&self) -> Option<SrcLoc> {
+        match self {
+            TypeError::Mismatch { other, .. } | TypeError::Infinite { other, .. } => Some(*other),
+            TypeError::Unsupported(..) => None,
+        }
+    }
+
+    pub fn render(&self, file: &FileInfo) -> String {
+        let at = file.span1_to_span2(self.loc());
+        match self.other() {
+            Some(other) => {
+                format!("{}: {}, expected at {}", self.message(), at, file.span1_to_span2(other))
+            }
+            None => format!("{}: {}", self.message(), at),
+        }
+    }
+}
+
+/// A register allocation that cannot be carried out
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AllocError {
+    /// A register that the code generator reserves for itself, among the
+    /// registers to allocate
+    ReservedRegister(Reg),
+}
+
+/// Compiled code that cannot be linked into an executable
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkError {
+    /// An extern that the freestanding runtime does not implement
+    UnavailableExtern(String),
+    /// Options that the target does not support
+    Unsupported(String),
+    /// A tool that could not be started, e.g. because it is not installed
+    Spawn {
+        tool: String,
+        message: String,
+    },
+    /// A tool that failed, with its exit status and standard error
+    Failed {
+        tool: String,
+        status: String,
+        stderr: String,
+    },
+    Io(String),
+    /// Assembly that the embedded assembler of the hermetic build rejects
+    Encode(String),
+}
+
+/// The failure of any stage
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Resolve(ResolveError),
+    Type(TypeError),
+    Alloc(AllocError),
+    Link(LinkError),
+}
+
+impl Error {
+    /// The diagnostic of `snake` for the error in the program of `file`
+    pub fn render(&self, file: &FileInfo) -> String {
+        match self {
+            Error::Resolve(err) => format!("Error resolving ast: {}", err.render(file)),
+            Error::Type(err) => format!("Error checking types: {}", err.render(file)),
+            err => err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SrcLoc { start_ix, end_ix, .. } = self.loc();
+        write!(f, "{}: bytes {}..{}", self.message(), start_ix, end_ix)?;
+        match self.other() {
+            Some(SrcLoc { start_ix, end_ix, .. }) => {
+                write!(f, ", bound at bytes {}..{}", start_ix, end_ix)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SrcLoc { start_ix, end_ix, .. } = self.loc();
+        write!(f, "{}: bytes {}..{}", self.message(), start_ix, end_ix)?;
+        match self.other() {
+            Some(SrcLoc { start_ix, end_ix, .. }) => {
+                write!(f, ", expected at bytes {}..{}", start_ix, end_ix)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AllocError::ReservedRegister(reg) => {
+                write!(f, "register {} is reserved and cannot be allocated", reg)
+            }
+        }
+    }
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkError::UnavailableExtern(name) => {
+                write!(f, "extern `{}` is not available with --freestanding", name)
+            }
+            LinkError::Unsupported(message) => f.write_str(message),
+            LinkError::Spawn { tool, message } => write!(f, "{} err: {}", tool, message),
+            LinkError::Failed { tool, status, stderr } => {
+                write!(f, "Failure in {} call: {}\n{}", tool, status, stderr)
+            }
+            LinkError::Io(message) => write!(f, "I/O error: {}", message),
+            LinkError::Encode(message) => {
+                write!(f, "Failure in the embedded assembler: {}", message)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "Error parsing program: {}", err),
+            Error::Resolve(err) => write!(f, "Error resolving ast: {}", err),
+            Error::Type(err) => write!(f, "Error checking types: {}", err),
+            Error::Alloc(err) => write!(f, "Error allocating registers: {}", err),
+            Error::Link(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+macro_rules! stage_error {
+    ($($stage:ident($err:ty)),*) => {
+        $(
+            impl std::error::Error for $err {}
+
+            impl From<$err> for Error {
+                fn from(err: $err) -> Self {
+                    Error::$stage(err)
+                }
+            }
+
+            /// For the `String` errors of the command line
+            impl From<$err> for String {
+                fn from(err: $err) -> Self {
+                    Error::from(err).to_string()
+                }
+            }
+        )*
+    };
+}
+
+stage_error!(
+    Parse(ParseError),
+    Resolve(ResolveError),
+    Type(TypeError),
+    Alloc(AllocError),
+    Link(LinkError)
+);
+
+impl std::error::Error for Error {}
+
+impl From<Error> for String {
+    fn from(err: Error) -> Self {
+        err.to_string()
+    }
+}
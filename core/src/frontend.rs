@@ -0,0 +1,621 @@
+//! The frontend of our compiler processes source code into an
+//! abstract syntax tree (AST). During this process, the frontend
+//! ensures that variables are in scope and renames them to use unique
+//! identifiers.
+
+use crate::ast::*;
+use crate::bignum::Big;
+use crate::conf::TypeMode;
+use crate::error::TypeError;
+use crate::generators;
+use crate::identifiers::*;
+use crate::infer::{self, Inferred};
+use crate::patterns::{Enums, Mixed};
+use crate::span::SrcLoc;
+use std::collections::{HashMap, HashSet};
+use std::iter::once;
+
+pub struct Resolver {
+    /// The names of the program, handed on to the lowerer
+    pub names: NameSupply,
+    /// Whether integer literals beyond 63 bits become big integers
+    /// instead of overflowing
+    pub bigint: bool,
+    /// The variants of the enums of the program
+    enums: Enums,
+    /// What the annotations prove, handed on to the lowerer
+    pub annotations: Annotations,
+    /// Whether the program makes strings at run time, handed on to the
+    /// lowerer, which then interns its literals along with them
+    pub makes_strings: bool,
+    /// Whether the types of the program are also inferred
+    types: TypeMode,
+    /// The inferred types of the functions of the last program, with
+    /// `TypeMode::Infer`
+    pub inferred: Option<Result<Inferred, TypeError>>,
+}
+
+/// The types of the annotations of a program, which the resolver checks
+#[derive(Clone, Debug, Default)]
+pub struct Annotations {
+    /// The types annotated for the variables
+    vars: HashMap<VarName, Type>,
+    /// The annotations of the functions, whose calls check the typed
+    /// arguments. Externs check their own, and only annotate their result.
+    signatures: HashMap<FunName, Signature>,
+}
+
+/// ResolveError is an error type that is used to report errors during
+/// name resolution.
+///
+/// In the following constructors, the String argument is the original
+/// name of the variable or function and the SrcLoc argument is where
+/// in the source program the error occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    UnboundVariable(String, SrcLoc),
+    DuplicateVariable(String, SrcLoc),
+    UnboundFunction(String, SrcLoc),
+    DuplicateFunction(String, SrcLoc),
+    DuplicateParameter(String, SrcLoc),
+    ArityMismatch {
+        name: String,
+        expected: usize,
+        found: usize,
+        loc: SrcLoc,
+    },
+    IntegerOverflow(String, SrcLoc),
+    DuplicateCase(i64, SrcLoc),
+    YieldOutsideGenerator(SrcLoc),
+    /// A call to a function that yields, which is not a tail call of the
+    /// generator that defines the function
+    NonTailYield(String, SrcLoc),
+    UnboundConstructor(String, SrcLoc),
+    DuplicateConstructor(String, SrcLoc),
+    /// A match whose arms miss the values of the pattern
+    NonExhaustiveMatch(String, SrcLoc),
+    /// Patterns of two different enums in the same position of a match
+    MixedEnums {
+        first: String,
+        second: String,
+        loc: SrcLoc,
+    },
+    /// A `let` whose value uses the variable it binds, unbound before it, as
+    /// in `let x = x + 1`: `loc` is the use and `binding` the variable
+    SelfReference {
+        name: String,
+        loc: SrcLoc,
+        binding: SrcLoc,
+    },
+    /// An expression of a type other than the one annotated
+    TypeMismatch {
+        expected: Type,
+        found: Type,
+        loc: SrcLoc,
+    },
+}
+
+use env::Env;
+mod env {
+    use super::*;
+
+    #[derive(Clone)]
+    pub struct Env {
+        vars: im::HashMap<String, VarName>,
+        funs: im::HashMap<String, (FunName, usize)>,
+        ctors: im::HashMap<String, (FunName, usize)>,
+    }
+
+    impl Env {
+        pub fn new() -> Self {
+            Env { vars: im::HashMap::new(), funs: im::HashMap::new(), ctors: im::HashMap::new() }
+        }
+        pub fn insert_var(&mut self, var: String, name: VarName) {
+            self.vars.insert(var.clone(), name.clone());
+        }
+        pub fn insert_fun(&mut self, fun: String, name: FunName, arity: usize) {
+            self.funs.insert(fun, (name, arity));
+        }
+        pub fn get_var(&self, var: &str) -> Option<&VarName> {
+            self.vars.get(var)
+        }
+        pub fn get_fun(&self, fun: &str) -> Option<(&FunName, usize)> {
+            self.funs.get(fun).map(|(f, arity)| (f, *arity))
+        }
+        pub fn insert_ctor(&mut self, ctor: String, name: FunName, arity: usize) {
+            self.ctors.insert(ctor, (name, arity));
+        }
+        pub fn get_ctor(&self, ctor: &str) -> Option<(&FunName, usize)> {
+            self.ctors.get(ctor).map(|(f, arity)| (f, *arity))
+        }
+    }
+}
+
+impl Annotations {
+    /// The type of the values of `e`, if it is evident from the operations
+    /// that produce them and from the annotations
+    pub fn type_of(&self, e: &BoundExpr) -> Option<Type> {
+        let agree = |mut tails: Vec<&BoundExpr>| {
+            let ty = self.type_of(tails.pop()?)?;
+            tails.into_iter().all(|tail| self.type_of(tail) == Some(ty)).then_some(ty)
+        };
+        match e {
+            Expr::Num(..) | Expr::BigNum(..) => Some(Type::Int),
+            Expr::Bool(..) => Some(Type::Bool),
+            Expr::Var(x, _) => self.vars.get(x).copied(),
+            Expr::Prim { prim, .. } => match prim {
+                Prim::Add1
+                | Prim::Sub1
+                | Prim::Add
+                | Prim::Sub
+                | Prim::Mul
+                | Prim::WrappingAdd
+                | Prim::WrappingSub
+                | Prim::WrappingMul
+                | Prim::SaturatingAdd
+                | Prim::SaturatingSub
+                | Prim::SaturatingMul
+                | Prim::Length => Some(Type::Int),
+                Prim::Not
+                | Prim::And
+                | Prim::Or
+                | Prim::Lt
+                | Prim::Le
+                | Prim::Gt
+                | Prim::Ge
+                | Prim::Eq
+                | Prim::Neq
+                | Prim::IsType(_)
+                | Prim::IsNone => Some(Type::Bool),
+                Prim::NewArray | Prim::MakeArray => Some(Type::Array),
+                Prim::Check(ty) => Some(*ty),
+                _ => None,
+            },
+            Expr::Let { body, .. } | Expr::FunDefs { body, .. } => self.type_of(body),
+            Expr::If { thn, els, .. } => agree(vec![thn, els]),
+            Expr::Switch { cases, default, .. } => {
+                agree(cases.iter().map(|case| &case.body).chain(once(&**default)).collect())
+            }
+            Expr::Match { arms, .. } => agree(arms.iter().map(|arm| &arm.body).collect()),
+            Expr::Call { fun, .. } => self.signatures.get(fun).and_then(|sig| sig.ret),
+        }
+    }
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Resolver {
+            names: NameSupply::new(),
+            bigint: false,
+            enums: Enums::default(),
+            annotations: Annotations::default(),
+            makes_strings: false,
+            types: TypeMode::default(),
+            inferred: None,
+        }
+    }
+
+    pub fn bigint(self, bigint: bool) -> Self {
+        Resolver { bigint, ..self }
+    }
+
+    pub fn types(self, types: TypeMode) -> Self {
+        Resolver { types, ..self }
+    }
+
+    pub fn resolve_prog(&mut self, prog: SurfProg) -> Result<BoundProg, ResolveError> {
+        let SurfProg { externs, enums, name, param, body, loc, doc } = prog;
+        let mut extern_fun_names = HashSet::new();
+
+        // register the main function
+        extern_fun_names.insert(name.clone());
+        let fun = FunName::unmangled("entry");
+        let mut env = Env::new();
+        env.insert_fun(name, fun.clone(), 1);
+
+        // handle external functions
+        let externs = externs
+            .into_iter()
+            .map(|ExtDecl { name, params, signature, contract, loc, doc }| {
+                if !extern_fun_names.insert(name.clone()) {
+                    Err(ResolveError::DuplicateFunction(name.clone(), loc))?;
+                }
+                let name = self.resolve_proc(name, params.as_slice(), &mut env, true)?;
+                let params_types = vec![None; params.len()];
+                let annotated = Signature { params: params_types, ret: signature.ret };
+                self.annotations.signatures.insert(name.clone(), annotated);
+                let mut env = env.clone();
+                let params = self.resolve_params(params, &mut env)?;
+                Ok(BoundExtDecl { name, params, signature, contract, loc, doc })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // register the constructors of the enums
+        let mut ctors = HashSet::new();
+        let enums: Vec<_> = (enums.into_iter())
+            .map(|EnumDecl { name, variants, loc, doc }| {
+                let variants = (variants.into_iter())
+                    .map(|Variant { name, fields, loc }| {
+                        if !ctors.insert(name.clone()) {
+                            Err(ResolveError::DuplicateConstructor(name.clone(), loc))?;
+                        }
+                        let ctor = self.names.fresh_fun(name.clone());
+                        env.insert_ctor(name, ctor.clone(), fields.len());
+                        Ok(Variant { name: ctor, fields, loc })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(EnumDecl { name, variants, loc, doc })
+            })
+            .collect::<Result<_, _>>()?;
+        self.enums = Enums::new(&enums);
+
+        // handle the parameter
+        let param = param.map(|param| {
+            let var = self.names.fresh_var(param.clone());
+            env.insert_var(param, var.clone());
+            var
+        });
+
+        // resolve the body, infer its types while the generators are still
+        // there, then compile them into plain functions
+        let body = self.resolve_expr(body, env)?;
+        let mut prog = BoundProg { externs, enums, name: fun, param, body, loc, doc };
+        if self.types == TypeMode::Infer {
+            self.inferred = Some(infer::infer(&prog));
+        }
+        let params = std::slice::from_ref(&prog.param.node);
+        prog.body = generators::compile(&mut self.names, params, prog.body)?;
+        Ok(prog)
+    }
+    /// Resolves the expression `body` of a function of `params`, as
+    /// `compile::compile_expr` compiles it without a program around it
+    pub fn resolve_fragment(
+        &mut self, params: &[String], body: SurfExpr,
+    ) -> Result<(Vec<VarName>, BoundExpr), ResolveError> {
+        let loc = body.loc();
+        let mut dup = HashSet::new();
+        if let Some(param) = params.iter().find(|param| !dup.insert(*param)) {
+            Err(ResolveError::DuplicateParameter(param.clone(), loc))?;
+        }
+        let mut env = Env::new();
+        let params = params.iter().map(|param| Spanned::new(param.clone(), loc)).collect();
+        let params = self.resolve_params(params, &mut env)?;
+        let params: Vec<_> = params.into_iter().map(|param| param.node).collect();
+        let body = self.resolve_expr(body, env)?;
+        let body = generators::compile(&mut self.names, &params, body)?;
+        Ok((params, body))
+    }
+    fn resolve_vec_expr(
+        &mut self, exprs: Vec<SurfExpr>, env: Env,
+    ) -> Result<Vec<BoundExpr>, ResolveError> {
+        exprs.into_iter().map(|expr| self.resolve_expr(expr, env.clone())).collect()
+    }
+    fn resolve_proc(
+        &mut self, name: String, params: &[Spanned<String>], env: &mut Env, external: bool,
+    ) -> Result<FunName, ResolveError> {
+        let fun = if external {
+            FunName::unmangled(name.clone())
+        } else {
+            self.names.fresh_fun(name.clone())
+        };
+        // collect the function name
+        env.insert_fun(name, fun.clone(), params.len());
+        // check for duplicate params
+        let mut dup = HashSet::new();
+        for Spanned { node: param, loc } in params.iter() {
+            if !dup.insert(param.clone()) {
+                Err(ResolveError::DuplicateParameter(param.clone(), *loc))?;
+            }
+        }
+        Ok(fun)
+    }
+    fn resolve_params(
+        &mut self, params: Vec<Spanned<String>>, env: &mut Env,
+    ) -> Result<Vec<Spanned<VarName>>, ResolveError> {
+        Ok(Vec::from_iter(params.into_iter().map(|param| {
+            param.map(|param| {
+                let var = self.names.fresh_var(param.clone());
+                env.insert_var(param, var.clone());
+                var
+            })
+        })))
+    }
+    fn resolve_expr(&mut self, e: SurfExpr, env: Env) -> Result<BoundExpr, ResolveError> {
+        let bound_expr = match e {
+            Expr::Num(i, loc) if !((i64::MIN >> 1)..=(i64::MAX >> 1)).contains(&i) => {
+                if !self.bigint {
+                    Err(ResolveError::IntegerOverflow(i.to_string(), loc))?;
+                }
+                Expr::BigNum(Big::from_i64(i), loc)
+            }
+            Expr::Num(i, loc) => Expr::Num(i, loc),
+            Expr::BigNum(n, loc) => {
+                if !self.bigint {
+                    Err(ResolveError::IntegerOverflow(n.to_string(), loc))?;
+                }
+                Expr::BigNum(n, loc)
+            }
+            Expr::Bool(b, loc) => Expr::Bool(b, loc),
+            Expr::Var(name, loc) => match (env.get_var(&name), env.get_ctor(&name)) {
+                (Some(var), _) => Expr::Var(var.clone(), loc),
+                (None, Some((ctor, 0))) => self.construct(ctor, vec![], loc),
+                _ => Err(ResolveError::UnboundVariable(name, loc))?,
+            },
+            Expr::Prim { prim, args, loc } => {
+                self.makes_strings |= matches!(prim, Prim::ToStr | Prim::Concat);
+                let args = self.resolve_vec_expr(args, env)?;
+                Expr::Prim { prim, args, loc }
+            }
+            Expr::Let { bindings, body, loc } => {
+                // the lets in the bodies of lets, which long programs chain, in a loop
+                let mut env = env.clone();
+                let mut lets = vec![(self.resolve_bindings(bindings, &mut env, loc)?, loc)];
+                let mut body = *body;
+                while let Expr::Let { bindings, body: inner, loc } = body {
+                    lets.push((self.resolve_bindings(bindings, &mut env, loc)?, loc));
+                    body = *inner;
+                }
+                let body = self.resolve_expr(body, env)?;
+                lets.into_iter().rev().fold(body, |body, (bindings, loc)| Expr::Let {
+                    bindings,
+                    body: Box::new(body),
+                    loc,
+                })
+            }
+            Expr::If { cond, thn, els, loc } => {
+                let cond = self.resolve_expr(*cond, env.clone())?;
+                let thn = self.resolve_expr(*thn, env.clone())?;
+                let els = self.resolve_expr(*els, env.clone())?;
+                Expr::If { cond: Box::new(cond), thn: Box::new(thn), els: Box::new(els), loc }
+            }
+            Expr::Switch { scrutinee, cases, default, loc } => {
+                let scrutinee = self.resolve_expr(*scrutinee, env.clone())?;
+                let mut dup = HashSet::new();
+                let cases = cases
+                    .into_iter()
+                    .map(|Case { label: Spanned { node: n, loc: label_loc }, body }| {
+                        if !((i64::MIN >> 1)..=(i64::MAX >> 1)).contains(&n) {
+                            Err(ResolveError::IntegerOverflow(n.to_string(), label_loc))?;
+                        }
+                        if !dup.insert(n) {
+                            Err(ResolveError::DuplicateCase(n, label_loc))?;
+                        }
+                        let body = self.resolve_expr(body, env.clone())?;
+                        Ok(Case { label: Spanned::new(n, label_loc), body })
+                    })
+                    .collect::<Result<_, _>>()?;
+                let default = self.resolve_expr(*default, env)?;
+                Expr::Switch {
+                    scrutinee: Box::new(scrutinee),
+                    cases,
+                    default: Box::new(default),
+                    loc,
+                }
+            }
+            Expr::Match { scrutinee, arms, loc } => {
+                let scrutinee = self.resolve_expr(*scrutinee, env.clone())?;
+                let arms: Vec<_> = (arms.into_iter())
+                    .map(|Arm { pattern, body }| {
+                        let mut env = env.clone();
+                        let pattern =
+                            self.resolve_pattern(pattern, &mut env, &mut HashSet::new())?;
+                        let body = self.resolve_expr(body, env)?;
+                        Ok(Arm { pattern, body })
+                    })
+                    .collect::<Result<_, _>>()?;
+                let patterns: Vec<_> = arms.iter().map(|arm| &arm.pattern).collect();
+                match self.enums.missing(&patterns) {
+                    Ok(None) => {}
+                    Ok(Some(witness)) => Err(ResolveError::NonExhaustiveMatch(witness, loc))?,
+                    Err(Mixed(first, second)) => {
+                        Err(ResolveError::MixedEnums { first, second, loc })?
+                    }
+                }
+                Expr::Match { scrutinee: Box::new(scrutinee), arms, loc }
+            }
+            Expr::FunDefs { decls, body, loc } => {
+                let mut env = env.clone();
+                // to avoid duplicate function names within the same recursive definition
+                let mut local_fun_names = HashSet::new();
+                // first, collect all the function names
+                for FunDecl { name, params, signature, loc, .. } in decls.iter() {
+                    if !local_fun_names.insert(name.clone()) {
+                        Err(ResolveError::DuplicateFunction(name.clone(), *loc))?;
+                    }
+                    let fun =
+                        self.resolve_proc(name.clone(), params.as_slice(), &mut env, false)?;
+                    self.annotations.signatures.insert(fun, signature.clone());
+                }
+                // then resolve the function bodies
+                let decls = decls
+                    .into_iter()
+                    .map(|FunDecl { name, params, signature, body, loc, doc, gen }| {
+                        let name = match env.get_fun(&name) {
+                            Some((fun, _)) => fun.clone(),
+                            None => unreachable!(),
+                        };
+                        let mut env = env.clone();
+                        let params = self.resolve_params(params, &mut env)?;
+                        // the calls check the arguments
+                        for (param, ty) in params.iter().zip(signature.params.iter()) {
+                            if let Some(ty) = ty {
+                                self.annotations.vars.insert(param.node.clone(), *ty);
+                            }
+                        }
+                        let mut body = self.resolve_expr(body, env)?;
+                        if let Some(ty) = signature.ret {
+                            body = self.check(body, ty)?;
+                        }
+                        Ok(FunDecl { name, params, signature, body, loc, doc, gen })
+                    })
+                    .collect::<Result<_, _>>()?;
+                let body = self.resolve_expr(*body, env)?;
+                Expr::FunDefs { decls, body: Box::new(body), loc }
+            }
+            Expr::Call { fun: name, args, loc } => {
+                let (fun, arity) = match (env.get_fun(&name), env.get_ctor(&name)) {
+                    (Some(fa), _) | (None, Some(fa)) => fa,
+                    _ => Err(ResolveError::UnboundFunction(name.clone(), loc))?,
+                };
+                if args.len() != arity {
+                    Err(ResolveError::ArityMismatch {
+                        name: name.clone(),
+                        expected: arity,
+                        found: args.len(),
+                        loc: loc.clone(),
+                    })?;
+                }
+                let fun = fun.clone();
+                let ctor = env.get_fun(&name).is_none();
+                let mut args = self.resolve_vec_expr(args, env)?;
+                if ctor {
+                    self.construct(&fun, args, loc)
+                } else {
+                    if let Some(signature) = self.annotations.signatures.get(&fun).cloned() {
+                        args = (args.into_iter().zip(signature.params))
+                            .map(|(arg, ty)| match ty {
+                                Some(ty) => self.check(arg, ty),
+                                None => Ok(arg),
+                            })
+                            .collect::<Result<_, _>>()?;
+                    }
+                    Expr::Call { fun, args, loc }
+                }
+            }
+        };
+        Ok(bound_expr)
+    }
+
+    /// The bindings of a `let` at `loc`, each in the scope of `env` and the
+    /// bindings before it, which `env` ends up with
+    fn resolve_bindings(
+        &mut self, bindings: Vec<SurfBinding>, env: &mut Env, loc: SrcLoc,
+    ) -> Result<Vec<BoundBinding>, ResolveError> {
+        let mut dup = HashSet::new();
+        bindings
+            .into_iter()
+            .map(|Binding { var, expr, ty, elems }| {
+                let mut expr = self.resolve_expr(expr, env.clone()).map_err(|err| match err {
+                    ResolveError::UnboundVariable(name, loc) => {
+                        // the array of a destructuring binding is not in scope
+                        let names = elems.as_deref().unwrap_or(std::slice::from_ref(&var));
+                        match names.iter().find(|bound| bound.node == name) {
+                            Some(bound) => {
+                                ResolveError::SelfReference { name, loc, binding: bound.loc }
+                            }
+                            None => ResolveError::UnboundVariable(name, loc),
+                        }
+                    }
+                    err => err,
+                })?;
+                if let Some(ty) = ty {
+                    expr = self.check(expr, ty)?;
+                }
+                // the array of a destructuring binding is not in scope
+                let mut bind = |env: &mut Env, Spanned { node: name, loc: at }: Spanned<String>| {
+                    if !dup.insert(name.clone()) {
+                        Err(ResolveError::DuplicateVariable(name.clone(), loc))?;
+                    }
+                    let var = self.names.fresh_var(name.clone());
+                    env.insert_var(name, var.clone());
+                    Ok(Spanned::new(var, at))
+                };
+                let (var, elems) = match elems {
+                    None => {
+                        let var = bind(env, var)?;
+                        if let Some(ty) = ty {
+                            self.annotations.vars.insert(var.node.clone(), ty);
+                        }
+                        (var, None)
+                    }
+                    Some(elems) => {
+                        let elems = (elems.into_iter())
+                            .map(|elem| bind(env, elem))
+                            .collect::<Result<_, _>>()?;
+                        (var.map(|name| self.names.fresh_var(name)), Some(elems))
+                    }
+                };
+                Ok(Binding { var, expr, ty, elems })
+            })
+            .collect()
+    }
+
+    /// `e`, whose values must have the type `ty`, checking the values of its
+    /// tails whose type is not evident. A tail evidently of another type is
+    /// an error.
+    fn check(&self, e: BoundExpr, ty: Type) -> Result<BoundExpr, ResolveError> {
+        Ok(match e {
+            Expr::Let { bindings, body, loc } => {
+                Expr::Let { bindings, body: Box::new(self.check(*body, ty)?), loc }
+            }
+            Expr::If { cond, thn, els, loc } => Expr::If {
+                cond,
+                thn: Box::new(self.check(*thn, ty)?),
+                els: Box::new(self.check(*els, ty)?),
+                loc,
+            },
+            Expr::Switch { scrutinee, cases, default, loc } => Expr::Switch {
+                scrutinee,
+                cases: (cases.into_iter())
+                    .map(|Case { label, body }| Ok(Case { label, body: self.check(body, ty)? }))
+                    .collect::<Result<_, _>>()?,
+                default: Box::new(self.check(*default, ty)?),
+                loc,
+            },
+            Expr::Match { scrutinee, arms, loc } => Expr::Match {
+                scrutinee,
+                arms: (arms.into_iter())
+                    .map(|Arm { pattern, body }| Ok(Arm { pattern, body: self.check(body, ty)? }))
+                    .collect::<Result<_, _>>()?,
+                loc,
+            },
+            Expr::FunDefs { decls, body, loc } => {
+                Expr::FunDefs { decls, body: Box::new(self.check(*body, ty)?), loc }
+            }
+            e => match self.annotations.type_of(&e) {
+                Some(found) if found == ty => e,
+                Some(found) => {
+                    Err(ResolveError::TypeMismatch { expected: ty, found, loc: e.loc() })?
+                }
+                None => {
+                    let loc = e.loc();
+                    Expr::Prim { prim: Prim::Check(ty), args: vec![e], loc }
+                }
+            },
+        })
+    }
+    /// The object of the variant of `ctor`: its tag, then its fields
+    fn construct(&self, ctor: &FunName, args: Vec<BoundExpr>, loc: SrcLoc) -> BoundExpr {
+        let tag = Expr::Num(self.enums.get(ctor).tag, loc);
+        let args = once(tag).chain(args).collect();
+        Expr::Prim { prim: Prim::MakeArray, args, loc }
+    }
+    fn resolve_pattern(
+        &mut self, pattern: SurfPattern, env: &mut Env, dup: &mut HashSet<String>,
+    ) -> Result<BoundPattern, ResolveError> {
+        let (name, args, loc) = match pattern {
+            Pattern::Wildcard(loc) => return Ok(Pattern::Wildcard(loc)),
+            Pattern::Var(name, loc) if env.get_ctor(&name).is_none() => {
+                if !dup.insert(name.clone()) {
+                    Err(ResolveError::DuplicateVariable(name.clone(), loc))?;
+                }
+                let var = self.names.fresh_var(name.clone());
+                env.insert_var(name, var.clone());
+                return Ok(Pattern::Var(var, loc));
+            }
+            Pattern::Var(name, loc) => (name, vec![], loc),
+            Pattern::Ctor { ctor, args, loc } => (ctor, args, loc),
+        };
+        let Some((ctor, arity)) = env.get_ctor(&name) else {
+            Err(ResolveError::UnboundConstructor(name, loc))?
+        };
+        if args.len() != arity {
+            Err(ResolveError::ArityMismatch { name, expected: arity, found: args.len(), loc })?;
+        }
+        let ctor = ctor.clone();
+        let args = (args.into_iter())
+            .map(|arg| self.resolve_pattern(arg, env, dup))
+            .collect::<Result<_, _>>()?;
+        Ok(Pattern::Ctor { ctor, args, loc })
+    }
+}
@@ -0,0 +1,131 @@
+//! Entry points for fuzzing the parser and the resolver.
+//!
+//! The targets of `fuzz/`, run by `cargo fuzz`, feed their inputs here. A
+//! finding is a panic, which includes a frontend that takes longer than
+//! [`SLOW`]; errors on ill-formed programs are the expected outcome.
+//!
+//! Inputs that libFuzzer writes with
+//! `-artifact_prefix=../tests/parser_regressions/<target>-` are run again by
+//! the tests of the `snake-cli` crate, which dispatch on the prefix.
+
+use crate::compile;
+use std::time::{Duration, Instant};
+
+/// How long the frontend may take on one input before it counts as pathological
+pub const SLOW: Duration = Duration::from_secs(2);
+
+/// The tokens that [`tokens`] spells its bytes with: the terminals of the
+/// grammar and a few identifiers and literals
+pub const TOKENS: &[&str] = &[
+    "def",
+    "and",
+    "in",
+    "let",
+    "if",
+    "else",
+    "end",
+    "match",
+    "switch",
+    "enum",
+    "extern",
+    "gen",
+    "ifsome",
+    "none",
+    "tuple",
+    "entry",
+    "!entry",
+    "main",
+    "true",
+    "false",
+    "_",
+    "Int",
+    "Bool",
+    "Array",
+    "add1",
+    "sub1",
+    "isInt",
+    "isBool",
+    "isArray",
+    "isNone",
+    "newArray",
+    "length",
+    "print",
+    "clock",
+    "getenv",
+    "random",
+    "yield",
+    "next",
+    "saturating_add",
+    "saturating_mul",
+    "saturating_sub",
+    "wrapping_add",
+    "wrapping_mul",
+    "wrapping_sub",
+    "(",
+    ")",
+    "[",
+    "]",
+    ",",
+    ":",
+    ":=",
+    "=",
+    "->",
+    "|",
+    "!",
+    "+",
+    "-",
+    "*",
+    "<",
+    "<=",
+    ">",
+    ">=",
+    "==",
+    "!=",
+    "&&",
+    "||",
+    "\n",
+    "## doc\n",
+    "# comment\n",
+    "x",
+    "y",
+    "f",
+    "0",
+    "1",
+    "-1",
+    "4611686018427387904",
+    "\"s\"",
+    "\"\\q\"",
+    "{",
+    "}",
+    "toString",
+    "concat",
+    "\"a{x}b\"",
+];
+
+/// Parses and resolves `data` if it is UTF-8
+pub fn frontend(data: &[u8]) {
+    if let Ok(src) = std::str::from_utf8(data) {
+        timed(src);
+    }
+}
+
+/// Parses and resolves the program that spells every byte of `data` as one
+/// of [`TOKENS`], which gets past the lexer far more often than raw bytes
+pub fn tokens(data: &[u8]) {
+    timed(&spell(data));
+}
+
+/// The program that [`tokens`] runs on `data`
+pub fn spell(data: &[u8]) -> String {
+    let words: Vec<&str> = data.iter().map(|b| TOKENS[*b as usize % TOKENS.len()]).collect();
+    words.join(" ")
+}
+
+fn timed(src: &str) {
+    let start = Instant::now();
+    let _ = compile::frontend(src);
+    let elapsed = start.elapsed();
+    if elapsed > SLOW {
+        panic!("the frontend took {:?} on {} bytes", elapsed, src.len());
+    }
+}
@@ -0,0 +1,819 @@
+//! Generators, `def gen`, compiled into state machines over plain functions
+//! and arrays, right after resolution, so that the interpreters and the
+//! middle end never see a `yield`.
+//!
+//! A call of a generator creates a generator object, an array
+//! `[state, slot, ...]`, and `next` resumes it: one function dispatches on
+//! the state to the code that continues the generator, which runs up to the
+//! following `yield`, saves what it needs into the slots and returns the
+//! yielded value. Once the body of the generator returns, the state is 0, and
+//! `next` keeps returning the value of the body.
+//!
+//! The compilation takes two steps. The first converts the bodies of the
+//! generators to continuation-passing style with respect to `yield`: what
+//! follows a `yield` becomes a function, its continuation, which is only ever
+//! tail-called. The second lambda-lifts the generators, the continuations and
+//! whatever they call to the top of the program, with the variables in scope
+//! at their definitions as extra parameters, so that the dispatch can call
+//! them on the slots of the object.
+//!
+//! As the object saves no stack, the functions that yield may only be
+//! tail-called, from within their generator.
+
+use crate::ast::*;
+use crate::frontend::ResolveError;
+use crate::identifiers::*;
+use std::collections::{HashMap, HashSet};
+
+/// The state of a finished generator, whose slot 1 holds its value
+const DONE: i64 = 0;
+
+/// Compiles away the generators of `body`, an expression in the scope of
+/// `params`, which is left as is without any generator.
+pub fn compile(
+    names: &mut NameSupply, params: &[VarName], body: BoundExpr,
+) -> Result<BoundExpr, ResolveError> {
+    if !mentions_generators(&body) {
+        return Ok(body);
+    }
+    let mut cps = Cps { names, yielding: HashSet::new(), states: HashMap::new(), gens: Vec::new() };
+    let body = cps.walk(body, None)?;
+    let Cps { names, states, gens, .. } = cps;
+
+    let mut scopes = HashMap::new();
+    collect_scopes(&body, &mut params.to_vec(), &mut scopes);
+    let mut decls = HashMap::new();
+    collect_decls(&body, &mut decls);
+
+    // the generators, the continuations and all they call
+    let mut lift = HashSet::new();
+    let mut worklist: Vec<_> = states.keys().cloned().collect();
+    while let Some(fun) = worklist.pop() {
+        if let Some((_, calls)) = decls.get(&fun).filter(|_| lift.insert(fun.clone())) {
+            worklist.extend(calls.iter().cloned());
+        }
+    }
+    let arity = |fun: &FunName| scopes[fun].len() + decls[fun].0;
+    let gens = (gens.into_iter())
+        .map(|(gen, conts)| {
+            let slots = conts.iter().chain([&gen]).map(arity).max().unwrap_or(0);
+            (gen.clone(), (states[&gen], 1 + slots.max(1)))
+        })
+        .collect();
+
+    let loc = body.loc();
+    let mut lifter = Lifter {
+        next: names.fresh_fun("next"),
+        names,
+        scopes,
+        lift,
+        states,
+        gens,
+        lifted: Vec::new(),
+        cases: Vec::new(),
+    };
+    let body = lifter.lift(body, &HashMap::new());
+    let next = lifter.dispatch(loc);
+    let mut decls = lifter.lifted;
+    decls.push(next);
+    Ok(Expr::FunDefs { decls, body: Box::new(body), loc })
+}
+
+/// Whether `e` uses generators at all
+fn mentions_generators(e: &BoundExpr) -> bool {
+    let mut found = false;
+    visit(e, &mut |e| match e {
+        Expr::Prim { prim: Prim::Yield | Prim::Next, .. } => found = true,
+        Expr::FunDefs { decls, .. } => {
+            found |= decls.iter().any(|d| d.gen || mentions_generators(&d.body))
+        }
+        _ => {}
+    });
+    found
+}
+
+/// Calls `f` on `e` and on the expressions that evaluating `e` evaluates,
+/// which excludes the bodies of the functions that `e` defines.
+fn visit<'a>(e: &'a BoundExpr, f: &mut impl FnMut(&'a BoundExpr)) {
+    f(e);
+    match e {
+        Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => {}
+        Expr::Prim { args, .. } | Expr::Call { args, .. } => {
+            args.iter().for_each(|arg| visit(arg, f))
+        }
+        Expr::Let { bindings, body, .. } => {
+            bindings.iter().for_each(|b| visit(&b.expr, f));
+            visit(body, f)
+        }
+        Expr::If { cond, thn, els, .. } => {
+            visit(cond, f);
+            visit(thn, f);
+            visit(els, f)
+        }
+        Expr::Switch { scrutinee, cases, default, .. } => {
+            visit(scrutinee, f);
+            cases.iter().for_each(|c| visit(&c.body, f));
+            visit(default, f)
+        }
+        Expr::Match { scrutinee, arms, .. } => {
+            visit(scrutinee, f);
+            arms.iter().for_each(|arm| visit(&arm.body, f))
+        }
+        Expr::FunDefs { body, .. } => visit(body, f),
+    }
+}
+
+/// The variables in scope at the definition of each function
+fn collect_scopes(
+    e: &BoundExpr, scope: &mut Vec<VarName>, scopes: &mut HashMap<FunName, Vec<VarName>>,
+) {
+    let depth = scope.len();
+    match e {
+        Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => {}
+        Expr::Prim { args, .. } | Expr::Call { args, .. } => {
+            args.iter().for_each(|arg| collect_scopes(arg, scope, scopes))
+        }
+        Expr::Let { bindings, body, .. } => {
+            for Binding { var, expr, elems, .. } in bindings {
+                collect_scopes(expr, scope, scopes);
+                scope.push(var.node.clone());
+                scope.extend(elems.iter().flatten().map(|elem| elem.node.clone()));
+            }
+            collect_scopes(body, scope, scopes)
+        }
+        Expr::If { cond, thn, els, .. } => {
+            [cond, thn, els].into_iter().for_each(|e| collect_scopes(e, scope, scopes))
+        }
+        Expr::Switch { scrutinee, cases, default, .. } => {
+            collect_scopes(scrutinee, scope, scopes);
+            cases.iter().for_each(|c| collect_scopes(&c.body, scope, scopes));
+            collect_scopes(default, scope, scopes)
+        }
+        Expr::Match { scrutinee, arms, .. } => {
+            collect_scopes(scrutinee, scope, scopes);
+            for Arm { pattern, body } in arms {
+                scope.extend(pattern.vars().into_iter().cloned());
+                collect_scopes(body, scope, scopes);
+                scope.truncate(depth);
+            }
+        }
+        Expr::FunDefs { decls, body, .. } => {
+            for FunDecl { name, .. } in decls {
+                scopes.insert(name.clone(), scope.clone());
+            }
+            for FunDecl { params, body, .. } in decls {
+                scope.extend(params.iter().map(|p| p.node.clone()));
+                collect_scopes(body, scope, scopes);
+                scope.truncate(depth);
+            }
+            collect_scopes(body, scope, scopes)
+        }
+    }
+    scope.truncate(depth);
+}
+
+/// The number of parameters of each function, and the functions called
+/// anywhere in its body
+fn collect_decls(e: &BoundExpr, decls: &mut HashMap<FunName, (usize, HashSet<FunName>)>) {
+    fn calls(e: &BoundExpr, out: &mut HashSet<FunName>) {
+        visit(e, &mut |e| match e {
+            Expr::Call { fun, .. } => {
+                out.insert(fun.clone());
+            }
+            Expr::FunDefs { decls, .. } => decls.iter().for_each(|d| calls(&d.body, out)),
+            _ => {}
+        })
+    }
+    visit(e, &mut |e| {
+        if let Expr::FunDefs { decls: defs, .. } = e {
+            for FunDecl { name, params, body, .. } in defs {
+                let mut out = HashSet::new();
+                calls(body, &mut out);
+                decls.insert(name.clone(), (params.len(), out));
+                collect_decls(body, decls);
+            }
+        }
+    })
+}
+
+/* ------------------------- Continuation Passing -------------------------- */
+
+/// Where the value of an expression of a generator goes
+#[derive(Clone)]
+enum Kont {
+    /// the generator returns it and finishes
+    Finish,
+    /// to a continuation, which is tail-called on it
+    Call(FunName),
+}
+
+/// The generator whose body is being converted
+struct Region {
+    gen: FunName,
+    /// its object, a parameter in front of those of the generator
+    this: VarName,
+    /// the functions of the generator that yield
+    funs: HashSet<FunName>,
+}
+
+struct Cps<'a> {
+    names: &'a mut NameSupply,
+    /// the functions that yield, of all generators
+    yielding: HashSet<FunName>,
+    /// the states of the generators and of the continuations of `yield`
+    states: HashMap<FunName, i64>,
+    /// the continuations of `yield` of each generator
+    gens: Vec<(FunName, Vec<FunName>)>,
+}
+
+impl Cps<'_> {
+    /// Whether evaluating `e` may yield
+    fn suspends(&self, e: &BoundExpr) -> bool {
+        let mut found = false;
+        visit(e, &mut |e| match e {
+            Expr::Prim { prim: Prim::Yield, .. } => found = true,
+            Expr::Call { fun, .. } => found |= self.yielding.contains(fun),
+            _ => {}
+        });
+        found
+    }
+
+    /// Converts the generators defined in `e`, which does not suspend
+    fn walk(&mut self, e: BoundExpr, region: Option<&Region>) -> Result<BoundExpr, ResolveError> {
+        let walk_all = |cps: &mut Self, es: Vec<BoundExpr>| {
+            es.into_iter().map(|e| cps.walk(e, region)).collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match e {
+            Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => e,
+            Expr::Prim { prim: Prim::Yield, loc, .. } => {
+                Err(ResolveError::YieldOutsideGenerator(loc))?
+            }
+            Expr::Prim { prim, args, loc } => Expr::Prim { prim, args: walk_all(self, args)?, loc },
+            Expr::Call { fun, loc, .. } if self.yielding.contains(&fun) => {
+                Err(ResolveError::NonTailYield(fun.hint().to_string(), loc))?
+            }
+            Expr::Call { fun, args, loc } => Expr::Call { fun, args: walk_all(self, args)?, loc },
+            Expr::Let { bindings, body, loc } => {
+                let bindings = (bindings.into_iter())
+                    .map(|Binding { var, expr, ty, elems }| {
+                        Ok(Binding { var, expr: self.walk(expr, region)?, ty, elems })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Expr::Let { bindings, body: Box::new(self.walk(*body, region)?), loc }
+            }
+            Expr::If { cond, thn, els, loc } => Expr::If {
+                cond: Box::new(self.walk(*cond, region)?),
+                thn: Box::new(self.walk(*thn, region)?),
+                els: Box::new(self.walk(*els, region)?),
+                loc,
+            },
+            Expr::Switch { scrutinee, cases, default, loc } => Expr::Switch {
+                scrutinee: Box::new(self.walk(*scrutinee, region)?),
+                cases: (cases.into_iter())
+                    .map(|Case { label, body }| Ok(Case { label, body: self.walk(body, region)? }))
+                    .collect::<Result<_, _>>()?,
+                default: Box::new(self.walk(*default, region)?),
+                loc,
+            },
+            Expr::Match { scrutinee, arms, loc } => Expr::Match {
+                scrutinee: Box::new(self.walk(*scrutinee, region)?),
+                arms: (arms.into_iter())
+                    .map(|Arm { pattern, body }| {
+                        Ok(Arm { pattern, body: self.walk(body, region)? })
+                    })
+                    .collect::<Result<_, _>>()?,
+                loc,
+            },
+            Expr::FunDefs { decls, body, loc } => {
+                let decls = self.decls(decls, region)?;
+                Expr::FunDefs { decls, body: Box::new(self.walk(*body, region)?), loc }
+            }
+        })
+    }
+
+    /// Converts the generators among `decls` and the functions of `region`
+    fn decls(
+        &mut self, decls: Vec<BoundFunDecl>, region: Option<&Region>,
+    ) -> Result<Vec<BoundFunDecl>, ResolveError> {
+        (decls.into_iter())
+            .map(|decl| match region {
+                _ if decl.gen => self.generator(decl),
+                Some(region) if region.funs.contains(&decl.name) => {
+                    Ok(FunDecl { body: self.cps(decl.body, Kont::Finish, region)?, ..decl })
+                }
+                _ => Ok(FunDecl { body: self.walk(decl.body, region)?, ..decl }),
+            })
+            .collect()
+    }
+
+    fn generator(&mut self, decl: BoundFunDecl) -> Result<BoundFunDecl, ResolveError> {
+        // the functions that yield, or tail-call those that do
+        let mut funs = Vec::new();
+        fn collect<'a>(e: &'a BoundExpr, funs: &mut Vec<&'a BoundFunDecl>) {
+            visit(e, &mut |e| {
+                if let Expr::FunDefs { decls, .. } = e {
+                    for decl in decls.iter().filter(|decl| !decl.gen) {
+                        funs.push(decl);
+                        collect(&decl.body, funs);
+                    }
+                }
+            })
+        }
+        collect(&decl.body, &mut funs);
+        let mut yielding = HashSet::new();
+        loop {
+            let more: Vec<_> = (funs.iter())
+                .filter(|fun| !yielding.contains(&fun.name))
+                .filter(|fun| {
+                    let mut found = false;
+                    visit(&fun.body, &mut |e| match e {
+                        Expr::Prim { prim: Prim::Yield, .. } => found = true,
+                        Expr::Call { fun, .. } => found |= yielding.contains(fun),
+                        _ => {}
+                    });
+                    found
+                })
+                .map(|fun| fun.name.clone())
+                .collect();
+            if more.is_empty() {
+                break;
+            }
+            yielding.extend(more);
+        }
+        self.yielding.extend(yielding.iter().cloned());
+
+        let FunDecl { name, params, signature, body, loc, doc, gen } = decl;
+        let this = self.names.fresh_var("self");
+        self.states.insert(name.clone(), self.states.len() as i64 + 1);
+        self.gens.push((name.clone(), Vec::new()));
+        let region = Region { gen: name.clone(), this: this.clone(), funs: yielding };
+        let body = self.cps(body, Kont::Finish, &region)?;
+        let params = [Spanned::new(this, loc)].into_iter().chain(params).collect();
+        Ok(FunDecl { name, params, signature: signature.shifted(1), body, loc, doc, gen })
+    }
+
+    /// Converts `e` of `region`, whose value goes to `k`
+    fn cps(&mut self, e: BoundExpr, k: Kont, region: &Region) -> Result<BoundExpr, ResolveError> {
+        if !self.suspends(&e) {
+            let e = self.walk(e, Some(region))?;
+            return Ok(self.ret(e, k, region));
+        }
+        match e {
+            Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => unreachable!(),
+            Expr::Prim { prim: Prim::Yield, mut args, loc } if !self.suspends(&args[0]) => {
+                let value = self.walk(args.remove(0), Some(region))?;
+                self.suspend(value, k, region, loc)
+            }
+            Expr::Call { fun, args, loc } if self.yielding.contains(&fun) => {
+                if matches!(k, Kont::Call(_)) || !region.funs.contains(&fun) {
+                    Err(ResolveError::NonTailYield(fun.hint().to_string(), loc))?;
+                }
+                if args.iter().any(|arg| self.suspends(arg)) {
+                    self.sequence(args, |args| Expr::Call { fun, args, loc }, k, region, loc)
+                } else {
+                    let args = (args.into_iter())
+                        .map(|arg| self.walk(arg, Some(region)))
+                        .collect::<Result<_, _>>()?;
+                    Ok(Expr::Call { fun, args, loc })
+                }
+            }
+            Expr::Prim { prim, args, loc } => {
+                self.sequence(args, |args| Expr::Prim { prim, args, loc }, k, region, loc)
+            }
+            Expr::Call { fun, args, loc } => {
+                self.sequence(args, |args| Expr::Call { fun, args, loc }, k, region, loc)
+            }
+            Expr::Let { bindings, body, loc } => {
+                let mut done = Vec::new();
+                let mut bindings = bindings.into_iter();
+                while let Some(Binding { var, expr, ty, elems }) = bindings.next() {
+                    if self.suspends(&expr) {
+                        // the rest of the `let` continues the binding, after
+                        // destructuring its parameter
+                        let mut rest: Vec<_> = bindings.collect();
+                        if let Some(elems) = elems {
+                            let tuple = Spanned::new(self.names.fresh_var("tuple"), var.loc);
+                            let expr = Expr::Var(var.node.clone(), var.loc);
+                            let elems = Some(elems);
+                            rest.insert(0, Binding { var: tuple, expr, ty, elems });
+                        }
+                        let rest = match rest.is_empty() {
+                            true => *body,
+                            false => Expr::Let { bindings: rest, body, loc },
+                        };
+                        let kont = self.continuation(var, rest, k, region)?;
+                        let expr = self.cps(expr, Kont::Call(kont.name.clone()), region)?;
+                        let expr = Expr::FunDefs { decls: vec![kont], body: Box::new(expr), loc };
+                        return Ok(bind(done, expr, loc));
+                    }
+                    done.push(Binding { var, expr: self.walk(expr, Some(region))?, ty, elems });
+                }
+                let body = self.cps(*body, k, region)?;
+                Ok(bind(done, body, loc))
+            }
+            Expr::If { cond, thn, els, loc } => {
+                if self.suspends(&cond) {
+                    let rebuild = |mut args: Vec<_>| Expr::If {
+                        cond: Box::new(args.remove(0)),
+                        thn,
+                        els,
+                        loc,
+                    };
+                    return self.sequence(vec![*cond], rebuild, k, region, loc);
+                }
+                Ok(Expr::If {
+                    cond: Box::new(self.walk(*cond, Some(region))?),
+                    thn: Box::new(self.cps(*thn, k.clone(), region)?),
+                    els: Box::new(self.cps(*els, k, region)?),
+                    loc,
+                })
+            }
+            Expr::Switch { scrutinee, cases, default, loc } => {
+                if self.suspends(&scrutinee) {
+                    let rebuild = |mut args: Vec<_>| Expr::Switch {
+                        scrutinee: Box::new(args.remove(0)),
+                        cases,
+                        default,
+                        loc,
+                    };
+                    return self.sequence(vec![*scrutinee], rebuild, k, region, loc);
+                }
+                Ok(Expr::Switch {
+                    scrutinee: Box::new(self.walk(*scrutinee, Some(region))?),
+                    cases: (cases.into_iter())
+                        .map(|Case { label, body }| {
+                            Ok(Case { label, body: self.cps(body, k.clone(), region)? })
+                        })
+                        .collect::<Result<_, _>>()?,
+                    default: Box::new(self.cps(*default, k, region)?),
+                    loc,
+                })
+            }
+            Expr::Match { scrutinee, arms, loc } => {
+                if self.suspends(&scrutinee) {
+                    let rebuild = |mut args: Vec<_>| Expr::Match {
+                        scrutinee: Box::new(args.remove(0)),
+                        arms,
+                        loc,
+                    };
+                    return self.sequence(vec![*scrutinee], rebuild, k, region, loc);
+                }
+                Ok(Expr::Match {
+                    scrutinee: Box::new(self.walk(*scrutinee, Some(region))?),
+                    arms: (arms.into_iter())
+                        .map(|Arm { pattern, body }| {
+                            Ok(Arm { pattern, body: self.cps(body, k.clone(), region)? })
+                        })
+                        .collect::<Result<_, _>>()?,
+                    loc,
+                })
+            }
+            Expr::FunDefs { decls, body, loc } => {
+                let decls = self.decls(decls, Some(region))?;
+                Ok(Expr::FunDefs { decls, body: Box::new(self.cps(*body, k, region)?), loc })
+            }
+        }
+    }
+
+    /// Evaluates `args` from left to right into `rebuild`, continuing after
+    /// the first argument that suspends
+    fn sequence(
+        &mut self, args: Vec<BoundExpr>, rebuild: impl FnOnce(Vec<BoundExpr>) -> BoundExpr,
+        k: Kont, region: &Region, loc: SrcLoc,
+    ) -> Result<BoundExpr, ResolveError> {
+        let first = args.iter().position(|arg| self.suspends(arg)).expect("no argument suspends");
+        let mut args = args.into_iter();
+        let mut bindings = Vec::new();
+        let mut values = Vec::new();
+        for arg in args.by_ref().take(first) {
+            match self.walk(arg, Some(region))? {
+                value @ (Expr::Num(..) | Expr::Bool(..) | Expr::Var(..)) => values.push(value),
+                expr => {
+                    let var = self.names.fresh_var("_arg");
+                    values.push(Expr::Var(var.clone(), expr.loc()));
+                    bindings.push(Binding {
+                        var: Spanned::new(var, expr.loc()),
+                        expr,
+                        ty: None,
+                        elems: None,
+                    });
+                }
+            }
+        }
+        let arg = args.next().unwrap();
+        let var = Spanned::new(self.names.fresh_var("_arg"), arg.loc());
+        values.push(Expr::Var(var.node.clone(), arg.loc()));
+        values.extend(args);
+        let kont = self.continuation(var, rebuild(values), k, region)?;
+        let arg = self.cps(arg, Kont::Call(kont.name.clone()), region)?;
+        Ok(bind(bindings, Expr::FunDefs { decls: vec![kont], body: Box::new(arg), loc }, loc))
+    }
+
+    /// A function of `param` that continues with `body`
+    fn continuation(
+        &mut self, param: Spanned<VarName>, body: BoundExpr, k: Kont, region: &Region,
+    ) -> Result<BoundFunDecl, ResolveError> {
+        let name = self.names.fresh_fun(format!("{}_k", region.gen.hint()));
+        let loc = body.loc();
+        let body = self.cps(body, k, region)?;
+        let signature = Signature::default();
+        Ok(FunDecl { name, params: vec![param], signature, body, loc, doc: None, gen: false })
+    }
+
+    /// Yields `value`, to resume in `k`, as `let x = yield(value, this) in k(x)`,
+    /// which `Lifter::lift` expands once the continuation is lifted
+    fn suspend(
+        &mut self, value: BoundExpr, k: Kont, region: &Region, loc: SrcLoc,
+    ) -> Result<BoundExpr, ResolveError> {
+        let (kont, decls) = match k {
+            Kont::Call(kont) => (kont, Vec::new()),
+            Kont::Finish => {
+                let var = self.names.fresh_var("_last");
+                let decl = self.continuation(
+                    Spanned::new(var.clone(), loc),
+                    Expr::Var(var, loc),
+                    Kont::Finish,
+                    region,
+                )?;
+                (decl.name.clone(), vec![decl])
+            }
+        };
+        self.states.insert(kont.clone(), self.states.len() as i64 + 1);
+        let conts = self.gens.iter_mut().find(|(gen, _)| *gen == region.gen);
+        conts.expect("the generator of the region").1.push(kont.clone());
+        let var = self.names.fresh_var("_yielded");
+        let this = Expr::Var(region.this.clone(), loc);
+        let e = Expr::Let {
+            bindings: vec![Binding {
+                var: Spanned::new(var.clone(), loc),
+                expr: Expr::Prim { prim: Prim::Yield, args: vec![value, this], loc },
+                ty: None,
+                elems: None,
+            }],
+            body: Box::new(Expr::Call { fun: kont, args: vec![Expr::Var(var, loc)], loc }),
+            loc,
+        };
+        Ok(match decls.is_empty() {
+            true => e,
+            false => Expr::FunDefs { decls, body: Box::new(e), loc },
+        })
+    }
+
+    /// Hands the value of `e` on to `k`
+    fn ret(&mut self, e: BoundExpr, k: Kont, region: &Region) -> BoundExpr {
+        let loc = e.loc();
+        match k {
+            Kont::Call(kont) => Expr::Call { fun: kont, args: vec![e], loc },
+            Kont::Finish => {
+                // let value = e, _ = this[0] := DONE, _ = this[1] := value in value
+                let value = self.names.fresh_var("_value");
+                let this = Expr::Var(region.this.clone(), loc);
+                let sets = [(DONE, Expr::Num(DONE, loc)), (1, Expr::Var(value.clone(), loc))];
+                let var = Spanned::new(value.clone(), loc);
+                let bindings = [Binding { var, expr: e, ty: None, elems: None }]
+                    .into_iter()
+                    .chain(sets.into_iter().map(|(i, v)| self.set(&this, i, v)))
+                    .collect();
+                Expr::Let { bindings, body: Box::new(Expr::Var(value, loc)), loc }
+            }
+        }
+    }
+
+    fn set(&mut self, arr: &BoundExpr, idx: i64, value: BoundExpr) -> BoundBinding {
+        set(self.names, arr, idx, value)
+    }
+}
+
+/// `_ = arr[idx] := value`
+fn set(names: &mut NameSupply, arr: &BoundExpr, idx: i64, value: BoundExpr) -> BoundBinding {
+    let loc = value.loc();
+    let args = vec![arr.clone(), Expr::Num(idx, loc), value];
+    let var = Spanned::new(names.fresh_var("_"), loc);
+    Binding { var, expr: Expr::Prim { prim: Prim::ArraySet, args, loc }, ty: None, elems: None }
+}
+
+/// `let bindings in body`, or `body` without bindings
+fn bind(bindings: Vec<BoundBinding>, body: BoundExpr, loc: SrcLoc) -> BoundExpr {
+    match bindings.is_empty() {
+        true => body,
+        false => Expr::Let { bindings, body: Box::new(body), loc },
+    }
+}
+
+/* ------------------------------- Lifting --------------------------------- */
+
+/// Whether `let bindings in body` is `let x = yield(value, this) in k(x)`
+fn is_suspension(bindings: &[BoundBinding], body: &BoundExpr) -> bool {
+    match (bindings, body) {
+        (
+            [Binding { var, expr: Expr::Prim { prim: Prim::Yield, .. }, elems: None, .. }],
+            Expr::Call { args, .. },
+        ) => {
+            matches!(args.as_slice(), [Expr::Var(x, _)] if *x == var.node)
+        }
+        _ => false,
+    }
+}
+
+struct Lifter<'a> {
+    names: &'a mut NameSupply,
+    /// the dispatch of `next`
+    next: FunName,
+    scopes: HashMap<FunName, Vec<VarName>>,
+    /// the functions to lift to the top
+    lift: HashSet<FunName>,
+    states: HashMap<FunName, i64>,
+    /// the first state and the size of the object of each generator
+    gens: HashMap<FunName, (i64, usize)>,
+    lifted: Vec<BoundFunDecl>,
+    /// the states, with their functions and the number of their parameters
+    cases: Vec<(i64, FunName, usize)>,
+}
+
+impl Lifter<'_> {
+    fn lift(&mut self, e: BoundExpr, subst: &HashMap<VarName, VarName>) -> BoundExpr {
+        let lift_all = |lifter: &mut Self, es: Vec<BoundExpr>| -> Vec<_> {
+            es.into_iter().map(|e| lifter.lift(e, subst)).collect()
+        };
+        match e {
+            Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) => e,
+            Expr::Var(var, loc) => Expr::Var(subst.get(&var).cloned().unwrap_or(var), loc),
+            Expr::Prim { prim: Prim::Next, args, loc } => {
+                Expr::Call { fun: self.next.clone(), args: lift_all(self, args), loc }
+            }
+            Expr::Prim { prim, args, loc } => Expr::Prim { prim, args: lift_all(self, args), loc },
+            Expr::Let { bindings, body, loc } if is_suspension(&bindings, &body) => {
+                let Some(Binding { var, expr: Expr::Prim { args, .. }, .. }) =
+                    bindings.into_iter().next()
+                else {
+                    unreachable!()
+                };
+                let Expr::Call { fun, .. } = *body else { unreachable!() };
+                let mut args = lift_all(self, args).into_iter();
+                let (value, this) = (args.next().unwrap(), args.next().unwrap());
+                self.suspend(var, value, this, fun, subst, loc)
+            }
+            Expr::Let { bindings, body, loc } => {
+                let bindings = (bindings.into_iter())
+                    .map(|Binding { var, expr, ty, elems }| Binding {
+                        var,
+                        expr: self.lift(expr, subst),
+                        ty,
+                        elems,
+                    })
+                    .collect();
+                Expr::Let { bindings, body: Box::new(self.lift(*body, subst)), loc }
+            }
+            Expr::If { cond, thn, els, loc } => Expr::If {
+                cond: Box::new(self.lift(*cond, subst)),
+                thn: Box::new(self.lift(*thn, subst)),
+                els: Box::new(self.lift(*els, subst)),
+                loc,
+            },
+            Expr::Switch { scrutinee, cases, default, loc } => Expr::Switch {
+                scrutinee: Box::new(self.lift(*scrutinee, subst)),
+                cases: (cases.into_iter())
+                    .map(|Case { label, body }| Case { label, body: self.lift(body, subst) })
+                    .collect(),
+                default: Box::new(self.lift(*default, subst)),
+                loc,
+            },
+            Expr::Match { scrutinee, arms, loc } => Expr::Match {
+                scrutinee: Box::new(self.lift(*scrutinee, subst)),
+                arms: (arms.into_iter())
+                    .map(|Arm { pattern, body }| Arm { pattern, body: self.lift(body, subst) })
+                    .collect(),
+                loc,
+            },
+            Expr::FunDefs { decls, body, loc } => {
+                let mut kept = Vec::new();
+                for decl in decls {
+                    if self.lift.contains(&decl.name) {
+                        self.lift_decl(decl);
+                    } else {
+                        kept.push(FunDecl { body: self.lift(decl.body, subst), ..decl });
+                    }
+                }
+                let body = self.lift(*body, subst);
+                match kept.is_empty() {
+                    true => body,
+                    false => Expr::FunDefs { decls: kept, body: Box::new(body), loc },
+                }
+            }
+            Expr::Call { fun, args, loc } => {
+                let args = lift_all(self, args);
+                if self.gens.contains_key(&fun) {
+                    self.create(fun, args, subst, loc)
+                } else if self.lift.contains(&fun) {
+                    let args = self.ambient(&fun, subst, loc).chain(args).collect();
+                    Expr::Call { fun, args, loc }
+                } else {
+                    Expr::Call { fun, args, loc }
+                }
+            }
+        }
+    }
+
+    /// Moves `decl` to the top, with the variables in scope at its definition
+    /// in front of its parameters
+    fn lift_decl(&mut self, decl: BoundFunDecl) {
+        let FunDecl { name, params, signature, body, loc, doc, gen: _ } = decl;
+        let mut subst = HashMap::new();
+        let ambient: Vec<_> = (self.scopes[&name].iter())
+            .map(|var| {
+                let param = self.names.fresh_var(var.hint());
+                subst.insert(var.clone(), param.clone());
+                Spanned::new(param, loc)
+            })
+            .collect();
+        let body = self.lift(body, &subst);
+        let signature = signature.shifted(ambient.len());
+        let params: Vec<_> = ambient.into_iter().chain(params).collect();
+        if let Some(state) = self.states.get(&name) {
+            self.cases.push((*state, name.clone(), params.len()));
+        }
+        self.lifted.push(FunDecl { name, params, signature, body, loc, doc, gen: false });
+    }
+
+    /// The arguments of a call of the lifted `fun` for its ambient variables
+    fn ambient<'b>(
+        &'b self, fun: &FunName, subst: &'b HashMap<VarName, VarName>, loc: SrcLoc,
+    ) -> impl Iterator<Item = BoundExpr> + 'b {
+        (self.scopes[fun].iter())
+            .map(move |var| Expr::Var(subst.get(var).unwrap_or(var).clone(), loc))
+    }
+
+    /// A new object of `gen` on `args`, which starts in the first state of the
+    /// generator with its parameters in the slots
+    fn create(
+        &mut self, gen: FunName, args: Vec<BoundExpr>, subst: &HashMap<VarName, VarName>,
+        loc: SrcLoc,
+    ) -> BoundExpr {
+        let (state, size) = self.gens[&gen];
+        let mut bindings = Vec::new();
+        let mut slots: Vec<_> = self.ambient(&gen, subst, loc).collect();
+        let obj = self.names.fresh_var("_gen");
+        slots.push(Expr::Var(obj.clone(), loc));
+        for arg in args {
+            let var = self.names.fresh_var("_arg");
+            slots.push(Expr::Var(var.clone(), loc));
+            let var = Spanned::new(var, loc);
+            bindings.push(Binding { var, expr: arg, ty: None, elems: None });
+        }
+        let size = Expr::Num(size as i64, loc);
+        let new = Expr::Prim { prim: Prim::NewArray, args: vec![size], loc };
+        let var = Spanned::new(obj.clone(), loc);
+        bindings.push(Binding { var, expr: new, ty: None, elems: None });
+        let this = Expr::Var(obj.clone(), loc);
+        let slots = [Expr::Num(state, loc)].into_iter().chain(slots).enumerate();
+        bindings.extend(slots.map(|(i, slot)| set(self.names, &this, i as i64, slot)));
+        Expr::Let { bindings, body: Box::new(this), loc }
+    }
+
+    /// `let var = value` that saves the state of `kont` and its arguments into
+    /// the object `this`, and returns the value
+    fn suspend(
+        &mut self, var: Spanned<VarName>, value: BoundExpr, this: BoundExpr, kont: FunName,
+        subst: &HashMap<VarName, VarName>, loc: SrcLoc,
+    ) -> BoundExpr {
+        let state = Expr::Num(self.states[&kont], loc);
+        let slots: Vec<_> =
+            (self.ambient(&kont, subst, loc)).chain([Expr::Var(var.node.clone(), loc)]).collect();
+        let slots = [state].into_iter().chain(slots).enumerate();
+        let bindings = [Binding { var: var.clone(), expr: value, ty: None, elems: None }]
+            .into_iter()
+            .chain(slots.map(|(i, slot)| set(self.names, &this, i as i64, slot)))
+            .collect();
+        Expr::Let { bindings, body: Box::new(Expr::Var(var.node, loc)), loc }
+    }
+
+    /// `next(obj)`, which switches on the state of the object to the function
+    /// that continues it, on its slots
+    fn dispatch(&mut self, loc: SrcLoc) -> BoundFunDecl {
+        let obj = self.names.fresh_var("gen");
+        let this = Expr::Var(obj.clone(), loc);
+        let get = |idx: i64| Expr::Prim {
+            prim: Prim::ArrayGet,
+            args: vec![this.clone(), Expr::Num(idx, loc)],
+            loc,
+        };
+        self.cases.sort();
+        let cases = [Case { label: Spanned::new(DONE, loc), body: get(1) }]
+            .into_iter()
+            .chain(self.cases.iter().map(|(state, fun, arity)| Case {
+                label: Spanned::new(*state, loc),
+                body: Expr::Call {
+                    fun: fun.clone(),
+                    args: (1..=*arity as i64).map(get).collect(),
+                    loc,
+                },
+            }))
+            .collect();
+        // any other state fails on an index out of bounds
+        let body =
+            Expr::Switch { scrutinee: Box::new(get(0)), cases, default: Box::new(get(-1)), loc };
+        let params = vec![Spanned::new(obj, loc)];
+        let signature = Signature::default();
+        FunDecl { name: self.next.clone(), params, signature, body, loc, doc: None, gen: false }
+    }
+}
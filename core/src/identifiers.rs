@@ -0,0 +1,224 @@
+//! Define the identifiers used across the compiler.
+
+/* ------------------------------- Identifiers ------------------------------ */
+
+/// A `VarName` is a unique identifier for a variable.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VarName(usize, String);
+impl VarName {
+    pub fn hint(&self) -> &str {
+        &self.1
+    }
+}
+
+/// A `FunName` is a unique identifier for a function name.
+/// It can be either mangled or unmangled.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FunName {
+    /// A mangled function name that is unique globally.
+    Mangled(usize, String),
+    /// An unmangled function name that can be used to refer to an external
+    /// function or the entry point of the program.
+    Unmangled(String),
+}
+impl FunName {
+    pub fn unmangled(hint: impl Into<String>) -> Self {
+        Self::Unmangled(hint.into())
+    }
+    pub fn hint(&self) -> &str {
+        match self {
+            FunName::Mangled(_, hint) | FunName::Unmangled(hint) => hint,
+        }
+    }
+    pub fn is_unmangled(&self) -> bool {
+        match self {
+            FunName::Unmangled(..) => true,
+            FunName::Mangled(..) => false,
+        }
+    }
+}
+
+/// A `BlockName` is a unique identifier for a basic block in IR.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlockName(usize, String);
+impl BlockName {
+    pub fn hint(&self) -> &str {
+        &self.1
+    }
+}
+
+/* --------------------------------- Display -------------------------------- */
+
+mod impl_display {
+    use super::*;
+    use std::fmt;
+
+    impl fmt::Display for VarName {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}%{}", self.1, self.0)
+        }
+    }
+    impl fmt::Display for FunName {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                FunName::Mangled(idx, hint) => write!(f, "{}@{}", hint, idx),
+                FunName::Unmangled(hint) => write!(f, "{}", hint),
+            }
+        }
+    }
+    impl fmt::Display for BlockName {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}#{}", self.1, self.0)
+        }
+    }
+}
+
+/* -------------------------------- Parsing --------------------------------- */
+
+/// Parse the identifiers as they display, e.g. to load the dumps of `dump`.
+mod impl_from_str {
+    use super::*;
+    use std::str::FromStr;
+
+    /// The hint and the index of `hint<sep>index`
+    fn split(s: &str, sep: char) -> Option<(usize, String)> {
+        let (hint, idx) = s.rsplit_once(sep)?;
+        let idx = idx.parse().ok()?;
+        (!hint.is_empty()).then(|| (idx, hint.to_string()))
+    }
+
+    impl FromStr for VarName {
+        type Err = String;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (idx, hint) =
+                split(s, '%').ok_or_else(|| format!("`{}` is not a variable `hint%n`", s))?;
+            Ok(VarName(idx, hint))
+        }
+    }
+    impl FromStr for FunName {
+        type Err = String;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match split(s, '@') {
+                Some((idx, hint)) => Ok(FunName::Mangled(idx, hint)),
+                None if !s.is_empty() && !s.contains('@') => Ok(FunName::unmangled(s)),
+                None => Err(format!("`{}` is not a function `hint@n` or `name`", s)),
+            }
+        }
+    }
+    impl FromStr for BlockName {
+        type Err = String;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (idx, hint) =
+                split(s, '#').ok_or_else(|| format!("`{}` is not a block `hint#n`", s))?;
+            Ok(BlockName(idx, hint))
+        }
+    }
+}
+
+/* -------------------------- Identifier Generator -------------------------- */
+
+/// A `IdGen` is a generator of unique `VarName`s.
+#[derive(Clone)]
+pub struct IdGen<Id> {
+    count: usize,
+    _marker: std::marker::PhantomData<Id>,
+}
+
+mod impl_idgen {
+    use super::*;
+
+    pub trait Identifier: Clone {
+        fn new(idx: usize, hint: impl Into<String>) -> Self;
+    }
+    impl Identifier for VarName {
+        fn new(idx: usize, hint: impl Into<String>) -> Self {
+            Self(idx, hint.into())
+        }
+    }
+    impl Identifier for FunName {
+        fn new(idx: usize, hint: impl Into<String>) -> Self {
+            Self::Mangled(idx, hint.into())
+        }
+    }
+    impl Identifier for BlockName {
+        fn new(idx: usize, hint: impl Into<String>) -> Self {
+            Self(idx, hint.into())
+        }
+    }
+
+    impl<Id: Identifier> IdGen<Id> {
+        pub fn new() -> Self {
+            Self { count: 0, _marker: std::marker::PhantomData }
+        }
+        pub fn fresh(&mut self, hint: impl Into<String>) -> Id {
+            let id = Id::new(self.count, hint);
+            self.count += 1;
+            id
+        }
+    }
+}
+
+/* ------------------------------- Name Supply ------------------------------ */
+
+/// A `NameSupply` generates the unique names of a program. The resolver
+/// creates it and hands it on to the lowerer, so that a pass that introduces
+/// temporaries, e.g. after `middle_end`, draws them from `Lowerer::names` and
+/// never collides with the names the compiler generated before.
+#[derive(Clone)]
+pub struct NameSupply {
+    pub vars: IdGen<VarName>,
+    pub funs: IdGen<FunName>,
+    pub blocks: IdGen<BlockName>,
+}
+
+impl NameSupply {
+    pub fn new() -> Self {
+        NameSupply { vars: IdGen::new(), funs: IdGen::new(), blocks: IdGen::new() }
+    }
+    pub fn fresh_var(&mut self, hint: impl Into<String>) -> VarName {
+        self.vars.fresh(hint)
+    }
+    pub fn fresh_fun(&mut self, hint: impl Into<String>) -> FunName {
+        self.funs.fresh(hint)
+    }
+    pub fn fresh_block(&mut self, hint: impl Into<String>) -> BlockName {
+        self.blocks.fresh(hint)
+    }
+}
+
+impl Default for NameSupply {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* ----------------------------- Readable Names ----------------------------- */
+
+/// `ReadableNames` displays a variable by its hint and a counter among the
+/// variables with the same hint, e.g. `x.3`, which relates more easily to the
+/// source than the globally unique `x%27`.
+pub struct ReadableNames(std::collections::HashMap<VarName, String>);
+
+impl ReadableNames {
+    /// Numbers the variables with the same hint in the order of their creation.
+    pub fn new<'a>(vars: impl IntoIterator<Item = &'a VarName>) -> Self {
+        let mut vars: Vec<_> = vars.into_iter().collect();
+        vars.sort();
+        vars.dedup();
+        let mut counts = std::collections::HashMap::new();
+        let names = (vars.into_iter())
+            .map(|var| {
+                let count = counts.entry(var.hint()).or_insert(0);
+                let name = format!("{}.{}", var.hint(), count);
+                *count += 1;
+                (var.clone(), name)
+            })
+            .collect();
+        Self(names)
+    }
+
+    /// The readable name of `var`, or its unique name if it was not numbered.
+    pub fn get(&self, var: &VarName) -> String {
+        self.0.get(var).cloned().unwrap_or_else(|| var.to_string())
+    }
+}
@@ -0,0 +1,510 @@
+//! Hindley–Milner type inference over the resolved AST, for `--types infer`.
+//!
+//! Unlike the gradual checking of the annotations, every value gets a
+//! single type: arrays are homogeneous, `Array<T>`, and the objects of
+//! generators are `Gen<T>` of the values that they yield. The functions of a
+//! `def ... and def ...` group are monomorphic in their bodies, and
+//! generalized after them, so that the expressions in their scope may call
+//! them at different types. `none` has every type, and so has the result of
+//! `getenv`, which is `false` for an unset variable.
+//!
+//! The inference runs before the generators are compiled away, whose
+//! objects are arrays of anything, and does not cover enums, whose objects
+//! are arrays of their tag and their fields.
+
+use crate::ast::*;
+use crate::error::TypeError;
+use crate::identifiers::*;
+use crate::nodes::SideTable;
+use crate::span::{NodeId, SrcLoc};
+use crate::txt::FileInfo;
+use crate::types::{Signature, Type};
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::once;
+
+/// A type of the values of a program, where variables stand for any type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Ty {
+    Int,
+    Bool,
+    Str,
+    Array(Box<Ty>),
+    Gen(Box<Ty>),
+    Var(usize),
+}
+
+/// The type of a function, whose calls may take any types for the
+/// `quantified` variables
+#[derive(Clone, Debug)]
+pub struct Scheme {
+    pub quantified: Vec<usize>,
+    pub params: Vec<Ty>,
+    pub ret: Ty,
+}
+
+/// The inferred type of a function of the program
+#[derive(Clone, Debug)]
+pub struct FunType {
+    pub name: String,
+    pub params: Vec<String>,
+    pub scheme: Scheme,
+    pub loc: SrcLoc,
+}
+
+/// The types of a program
+#[derive(Clone, Debug, Default)]
+pub struct Inferred {
+    /// The types of the functions, in the order of their definitions, main
+    /// first
+    pub funs: Vec<FunType>,
+    /// The types of the expressions and of the variables they bind, by the
+    /// nodes that the parser numbered
+    pub nodes: SideTable<Ty>,
+}
+
+/// Infers the types of the functions of `prog`, or reports the first two
+/// places that need different types for the same value
+pub fn infer(prog: &BoundProg) -> Result<Inferred, TypeError> {
+    if let Some(decl) = prog.enums.first() {
+        Err(TypeError::Unsupported("enums".to_string(), decl.loc))?
+    }
+    let mut infer = Infer::default();
+    for ext in &prog.externs {
+        let scheme = infer.declared(&ext.signature, ext.params.len());
+        infer.funs.insert(ext.name.clone(), (scheme, ext.loc));
+    }
+    let args = Ty::Array(Box::new(infer.fresh()));
+    infer.bind_var(&prog.param, args.clone());
+    let ret = infer.expr(&prog.body)?;
+    infer.scope.clear();
+    let scheme = infer.generalize(&[args], &ret);
+    let params = vec![prog.param.node.hint().to_string()];
+    infer.out.push(FunType { name: "main".to_string(), params, scheme, loc: prog.loc });
+    infer.out.sort_by_key(|fun| fun.loc.start_ix);
+    let typed = std::mem::take(&mut infer.typed).into_iter();
+    let nodes = typed.map(|(id, ty)| (id, infer.resolve(&ty))).collect();
+    Ok(Inferred { funs: infer.out, nodes })
+}
+
+/// Two types that do not unify
+enum Clash {
+    Mismatch,
+    /// The variable would be bound to a type that contains it
+    Infinite(usize, Ty),
+}
+
+#[derive(Default)]
+struct Infer {
+    /// The types that the type variables are bound to, with the place that
+    /// bound them
+    bound: Vec<Option<(Ty, SrcLoc)>>,
+    vars: HashMap<VarName, Ty>,
+    /// The variables in scope, whose types are not generalized
+    scope: Vec<VarName>,
+    funs: HashMap<FunName, (Scheme, SrcLoc)>,
+    /// The functions of the groups being inferred, whose types are not
+    /// generalized either
+    pending: Vec<FunName>,
+    /// The type of the values that the innermost generator yields
+    yields: Vec<Ty>,
+    out: Vec<FunType>,
+    /// the types of the nodes, resolved once the program is inferred
+    typed: Vec<(NodeId, Ty)>,
+}
+
+impl Infer {
+    fn fresh(&mut self) -> Ty {
+        self.bound.push(None);
+        Ty::Var(self.bound.len() - 1)
+    }
+
+    fn bind_var(&mut self, var: &Spanned<VarName>, ty: Ty) {
+        self.typed.push((var.loc.node, ty.clone()));
+        self.vars.insert(var.node.clone(), ty);
+        self.scope.push(var.node.clone());
+    }
+
+    /// The type of an annotation, any type without one
+    fn annotated(&mut self, ty: Option<Type>) -> Ty {
+        match ty {
+            Some(Type::Int) => Ty::Int,
+            Some(Type::Bool) => Ty::Bool,
+            Some(Type::Array) => Ty::Array(Box::new(self.fresh())),
+            None => self.fresh(),
+        }
+    }
+
+    /// The type of an extern of `arity`, as its signature declares it
+    fn declared(&mut self, signature: &Signature, arity: usize) -> Scheme {
+        let params: Vec<_> = (0..arity)
+            .map(|i| self.annotated(signature.params.get(i).copied().flatten()))
+            .collect();
+        let ret = self.annotated(signature.ret);
+        let mut quantified = Vec::new();
+        for ty in params.iter().chain(once(&ret)) {
+            free(ty, &mut quantified);
+        }
+        Scheme { quantified, params, ret }
+    }
+
+    /// `ty`, unless it is a bound variable
+    fn walk(&self, ty: &Ty) -> Ty {
+        let mut ty = ty.clone();
+        while let Ty::Var(v) = ty {
+            match &self.bound[v] {
+                Some((bound, _)) => ty = bound.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    /// `ty` without bound variables
+    fn resolve(&self, ty: &Ty) -> Ty {
+        match self.walk(ty) {
+            Ty::Array(elem) => Ty::Array(Box::new(self.resolve(&elem))),
+            Ty::Gen(elem) => Ty::Gen(Box::new(self.resolve(&elem))),
+            ty => ty,
+        }
+    }
+
+    /// Where the variables that `ty` goes through got their type, if anywhere
+    fn origin(&self, ty: &Ty) -> Option<SrcLoc> {
+        let mut origin = None;
+        let mut ty = ty;
+        while let Ty::Var(v) = ty {
+            let Some((bound, loc)) = &self.bound[*v] else { break };
+            origin = Some(*loc);
+            ty = bound;
+        }
+        origin
+    }
+
+    /// Unifies the type `found` of the expression at `at` with the type
+    /// `expected` of the values there, because of the expression at `why`.
+    /// Where the types come from elsewhere, the places are those that gave
+    /// them.
+    fn unify(
+        &mut self, expected: &Ty, why: SrcLoc, found: &Ty, at: SrcLoc,
+    ) -> Result<(), TypeError> {
+        let why = self.origin(expected).unwrap_or(why);
+        let at = self.origin(found).unwrap_or(at);
+        self.unifies(expected, why, found, at).map_err(|clash| {
+            let mut names = Vec::new();
+            match clash {
+                Clash::Mismatch => TypeError::Mismatch {
+                    expected: show(&self.resolve(expected), &mut names),
+                    found: show(&self.resolve(found), &mut names),
+                    loc: at,
+                    other: why,
+                },
+                Clash::Infinite(var, ty) => TypeError::Infinite {
+                    var: show(&Ty::Var(var), &mut names),
+                    ty: show(&self.resolve(&ty), &mut names),
+                    loc: at,
+                    other: why,
+                },
+            }
+        })
+    }
+
+    fn unifies(&mut self, expected: &Ty, why: SrcLoc, found: &Ty, at: SrcLoc) -> Result<(), Clash> {
+        match (self.walk(expected), self.walk(found)) {
+            (Ty::Var(a), Ty::Var(b)) if a == b => Ok(()),
+            (Ty::Var(a), ty) => self.bind(a, ty, at),
+            (ty, Ty::Var(b)) => self.bind(b, ty, why),
+            (Ty::Int, Ty::Int) | (Ty::Bool, Ty::Bool) | (Ty::Str, Ty::Str) => Ok(()),
+            (Ty::Array(e), Ty::Array(f)) | (Ty::Gen(e), Ty::Gen(f)) => {
+                self.unifies(&e, why, &f, at)
+            }
+            _ => Err(Clash::Mismatch),
+        }
+    }
+
+    /// Binds the variable `var` to `ty`, which comes from the expression at
+    /// `origin`
+    fn bind(&mut self, var: usize, ty: Ty, origin: SrcLoc) -> Result<(), Clash> {
+        let mut vars = Vec::new();
+        free(&self.resolve(&ty), &mut vars);
+        if vars.contains(&var) {
+            return Err(Clash::Infinite(var, ty));
+        }
+        self.bound[var] = Some((ty, origin));
+        Ok(())
+    }
+
+    /// The scheme of a function of `params` and `ret` that quantifies the
+    /// variables that nothing in scope mentions
+    fn generalize(&self, params: &[Ty], ret: &Ty) -> Scheme {
+        let params: Vec<_> = params.iter().map(|ty| self.resolve(ty)).collect();
+        let ret = self.resolve(ret);
+        let mut env = Vec::new();
+        for var in &self.scope {
+            free(&self.resolve(&self.vars[var]), &mut env);
+        }
+        for fun in &self.pending {
+            let (scheme, _) = &self.funs[fun];
+            for ty in scheme.params.iter().chain(once(&scheme.ret)) {
+                free(&self.resolve(ty), &mut env);
+            }
+        }
+        let mut quantified = Vec::new();
+        for ty in params.iter().chain(once(&ret)) {
+            free(ty, &mut quantified);
+        }
+        quantified.retain(|var| !env.contains(var));
+        Scheme { quantified, params, ret }
+    }
+
+    /// The types of the parameters and of the result of a call of `scheme`
+    fn instantiate(&mut self, scheme: &Scheme) -> (Vec<Ty>, Ty) {
+        let fresh: HashMap<_, _> = scheme.quantified.iter().map(|v| (*v, self.fresh())).collect();
+        let params = scheme.params.iter().map(|ty| rename(&self.resolve(ty), &fresh)).collect();
+        (params, rename(&self.resolve(&scheme.ret), &fresh))
+    }
+
+    fn expr(&mut self, e: &BoundExpr) -> Result<Ty, TypeError> {
+        let ty = self.infer_expr(e)?;
+        self.typed.push((e.loc().node, ty.clone()));
+        Ok(ty)
+    }
+
+    fn infer_expr(&mut self, e: &BoundExpr) -> Result<Ty, TypeError> {
+        match e {
+            Expr::Num(..) | Expr::BigNum(..) => Ok(Ty::Int),
+            Expr::Bool(..) => Ok(Ty::Bool),
+            Expr::Var(x, _) => Ok(self.vars[x].clone()),
+            Expr::Prim { prim, args, loc } => self.prim(prim, args, *loc),
+            Expr::Let { bindings, body, .. } => {
+                let depth = self.scope.len();
+                for Binding { var, expr, ty, elems } in bindings {
+                    let found = self.expr(expr)?;
+                    if let Some(ty) = ty {
+                        let ty = self.annotated(Some(*ty));
+                        self.unify(&ty, var.loc, &found, expr.loc())?;
+                    }
+                    if let Some(elems) = elems {
+                        let elem = self.fresh();
+                        let array = Ty::Array(Box::new(elem.clone()));
+                        self.unify(&array, var.loc, &found, expr.loc())?;
+                        for x in elems {
+                            self.bind_var(x, elem.clone());
+                        }
+                    }
+                    self.bind_var(var, found);
+                }
+                let ty = self.expr(body)?;
+                self.scope.truncate(depth);
+                Ok(ty)
+            }
+            Expr::If { cond, thn, els, .. } => {
+                let found = self.expr(cond)?;
+                self.unify(&Ty::Bool, cond.loc(), &found, cond.loc())?;
+                let ty = self.expr(thn)?;
+                let found = self.expr(els)?;
+                self.unify(&ty, thn.loc(), &found, els.loc())?;
+                Ok(ty)
+            }
+            Expr::Switch { scrutinee, cases, default, .. } => {
+                let found = self.expr(scrutinee)?;
+                self.unify(&Ty::Int, scrutinee.loc(), &found, scrutinee.loc())?;
+                let ty = self.expr(default)?;
+                for case in cases {
+                    let found = self.expr(&case.body)?;
+                    self.unify(&ty, default.loc(), &found, case.body.loc())?;
+                }
+                Ok(ty)
+            }
+            Expr::Match { loc, .. } => Err(TypeError::Unsupported("match".to_string(), *loc)),
+            Expr::FunDefs { decls, body, .. } => {
+                self.group(decls)?;
+                self.expr(body)
+            }
+            Expr::Call { fun, args, .. } => {
+                let (scheme, decl) = self.funs[fun].clone();
+                let (params, ret) = self.instantiate(&scheme);
+                for (param, arg) in params.iter().zip(args) {
+                    let found = self.expr(arg)?;
+                    self.unify(param, decl, &found, arg.loc())?;
+                }
+                Ok(ret)
+            }
+        }
+    }
+
+    /// Infers the types of a group of mutually recursive functions
+    fn group(&mut self, decls: &[BoundFunDecl]) -> Result<(), TypeError> {
+        let mut values = Vec::new();
+        for decl in decls {
+            let params: Vec<_> = (0..decl.params.len())
+                .map(|i| self.annotated(decl.signature.params.get(i).copied().flatten()))
+                .collect();
+            // the calls of a generator return its object
+            let value = self.annotated(decl.signature.ret);
+            let ret = match decl.gen {
+                true => Ty::Gen(Box::new(value.clone())),
+                false => value.clone(),
+            };
+            let scheme = Scheme { quantified: Vec::new(), params, ret };
+            self.funs.insert(decl.name.clone(), (scheme, decl.loc));
+            self.pending.push(decl.name.clone());
+            values.push(value);
+        }
+        for (decl, value) in decls.iter().zip(&values) {
+            let depth = self.scope.len();
+            let params = self.funs[&decl.name].0.params.clone();
+            for (param, ty) in decl.params.iter().zip(params) {
+                self.bind_var(param, ty);
+            }
+            if decl.gen {
+                self.yields.push(value.clone());
+            }
+            let found = self.expr(&decl.body)?;
+            self.unify(value, decl.loc, &found, decl.body.loc())?;
+            if decl.gen {
+                self.yields.pop();
+            }
+            self.scope.truncate(depth);
+        }
+        self.pending.truncate(self.pending.len() - decls.len());
+        for decl in decls {
+            let (scheme, loc) = self.funs[&decl.name].clone();
+            let scheme = self.generalize(&scheme.params, &scheme.ret);
+            self.out.push(FunType {
+                name: decl.name.hint().to_string(),
+                params: decl.params.iter().map(|param| param.node.hint().to_string()).collect(),
+                scheme: scheme.clone(),
+                loc,
+            });
+            self.funs.insert(decl.name.clone(), (scheme, loc));
+        }
+        Ok(())
+    }
+
+    fn prim(&mut self, prim: &Prim, args: &[BoundExpr], loc: SrcLoc) -> Result<Ty, TypeError> {
+        let found = args.iter().map(|arg| self.expr(arg)).collect::<Result<Vec<_>, _>>()?;
+        let elem = self.fresh();
+        let array = Ty::Array(Box::new(elem.clone()));
+        // the types of the arguments and of the result
+        let (params, ret) = match prim {
+            Prim::Add1 | Prim::Sub1 => (vec![Ty::Int], Ty::Int),
+            Prim::Add
+            | Prim::Sub
+            | Prim::Mul
+            | Prim::WrappingAdd
+            | Prim::WrappingSub
+            | Prim::WrappingMul
+            | Prim::SaturatingAdd
+            | Prim::SaturatingSub
+            | Prim::SaturatingMul => (vec![Ty::Int, Ty::Int], Ty::Int),
+            Prim::Not => (vec![Ty::Bool], Ty::Bool),
+            Prim::And | Prim::Or => (vec![Ty::Bool, Ty::Bool], Ty::Bool),
+            Prim::Lt | Prim::Le | Prim::Gt | Prim::Ge => (vec![Ty::Int, Ty::Int], Ty::Bool),
+            Prim::Eq | Prim::Neq => (vec![elem.clone(), elem], Ty::Bool),
+            Prim::IsType(_) | Prim::IsNone => (vec![elem], Ty::Bool),
+            Prim::Check(ty) => {
+                let ty = self.annotated(Some(*ty));
+                (vec![ty.clone()], ty)
+            }
+            Prim::None => (vec![], elem),
+            Prim::Str(_) => (vec![], Ty::Str),
+            Prim::ToStr => (vec![elem], Ty::Str),
+            Prim::Concat => (vec![elem, self.fresh()], Ty::Str),
+            Prim::NewArray => (vec![Ty::Int], array),
+            Prim::MakeArray => (vec![elem; args.len()], array),
+            Prim::ArrayGet => (vec![array, Ty::Int], elem),
+            Prim::ArraySet => (vec![array, Ty::Int, elem.clone()], elem),
+            Prim::Length => (vec![array], Ty::Int),
+            Prim::GetEnv => (vec![Ty::Array(Box::new(Ty::Int))], elem),
+            Prim::Clock => (vec![], Ty::Int),
+            Prim::Random => (vec![Ty::Int], Ty::Int),
+            // outside of a generator, the resolution reports the yield
+            Prim::Yield => {
+                let value = self.yields.last().cloned().unwrap_or(elem);
+                (vec![value.clone()], value)
+            }
+            Prim::Next => (vec![Ty::Gen(Box::new(elem.clone()))], elem),
+        };
+        for ((param, found), arg) in params.iter().zip(&found).zip(args) {
+            self.unify(param, loc, found, arg.loc())?;
+        }
+        Ok(ret)
+    }
+}
+
+/// Adds the variables of the resolved `ty` to `vars`, once each
+fn free(ty: &Ty, vars: &mut Vec<usize>) {
+    match ty {
+        Ty::Int | Ty::Bool | Ty::Str => {}
+        Ty::Array(elem) | Ty::Gen(elem) => free(elem, vars),
+        Ty::Var(v) => {
+            if !vars.contains(v) {
+                vars.push(*v);
+            }
+        }
+    }
+}
+
+/// The resolved `ty` with the variables of `fresh` replaced
+fn rename(ty: &Ty, fresh: &HashMap<usize, Ty>) -> Ty {
+    match ty {
+        Ty::Int | Ty::Bool | Ty::Str => ty.clone(),
+        Ty::Array(elem) => Ty::Array(Box::new(rename(elem, fresh))),
+        Ty::Gen(elem) => Ty::Gen(Box::new(rename(elem, fresh))),
+        Ty::Var(v) => fresh.get(v).cloned().unwrap_or(Ty::Var(*v)),
+    }
+}
+
+/// Shows `ty`, naming its variables `'a`, `'b`, ... in the order of `names`,
+/// which the variables not named yet join
+fn show(ty: &Ty, names: &mut Vec<usize>) -> String {
+    match ty {
+        Ty::Int => "Int".to_string(),
+        Ty::Bool => "Bool".to_string(),
+        Ty::Str => "Str".to_string(),
+        Ty::Array(elem) => format!("Array<{}>", show(elem, names)),
+        Ty::Gen(elem) => format!("Gen<{}>", show(elem, names)),
+        Ty::Var(v) => {
+            let i = names.iter().position(|name| name == v).unwrap_or_else(|| {
+                names.push(*v);
+                names.len() - 1
+            });
+            let letter = (b'a' + (i % 26) as u8) as char;
+            match i / 26 {
+                0 => format!("'{}", letter),
+                n => format!("'{}{}", letter, n),
+            }
+        }
+    }
+}
+
+/// e.g. `Array<'a>`
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", show(self, &mut Vec::new()))
+    }
+}
+
+impl fmt::Display for FunType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = Vec::new();
+        let params: Vec<_> = (self.params.iter())
+            .zip(&self.scheme.params)
+            .map(|(param, ty)| format!("{}: {}", param, show(ty, &mut names)))
+            .collect();
+        let ret = show(&self.scheme.ret, &mut names);
+        write!(f, "{}({}) -> {}", self.name, params.join(", "), ret)
+    }
+}
+
+impl Inferred {
+    /// The types of the functions, one per line after the span of the
+    /// function, e.g. `2:2-3:20: sum(xs: Array<Int>, i: Int) -> Int`
+    pub fn render(&self, file: &FileInfo) -> String {
+        (self.funs.iter())
+            .map(|fun| format!("{}: {}", file.span1_to_span2(fun.loc), fun))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,1788 @@
+//! Interpreter for the snake language and its SSA form.
+
+use crate::ast::Prim;
+use crate::bignum::Big;
+use crate::identifiers::*;
+use crate::span::SrcLoc;
+use crate::txt::FileInfo;
+use crate::types::*;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    hash::Hash,
+    sync::Arc,
+};
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Int(i64),
+    /// an integer beyond the 63 bits of `Int`, only produced with `--bigint`
+    Big(Arc<Big>),
+    Bool(bool),
+    FatPtr(ArenaPtr),
+    Raw(Raw),
+    /// the sentinel of optional values
+    None,
+    /// a string literal, which points to the heap like big integers
+    Str(Arc<str>),
+}
+
+/// The tag of big integers, which point to the heap like arrays
+const BIG_TAG: i64 = 0b111;
+
+impl Value {
+    /// A small integer if `n` fits, or a big one
+    pub fn from_big(n: Big) -> Self {
+        match n.to_small() {
+            Some(n) => Value::Int(n),
+            None => Value::Big(Arc::new(n)),
+        }
+    }
+}
+
+/// The arithmetic that never overflows, on tagged integers. Like the
+/// compiled code, the multiplications take their first operand untagged.
+fn tagged_arith(prim: &crate::ssa::Prim2, n: i64, m: i64) -> i64 {
+    use crate::ssa::Prim2;
+    // the bounds of the tagged integers, keeping the tag bit clear
+    let saturate =
+        |o: Option<i64>, neg: bool| o.unwrap_or(if neg { i64::MIN } else { i64::MAX - 1 });
+    match prim {
+        Prim2::WrappingAdd => n.wrapping_add(m),
+        Prim2::WrappingSub => n.wrapping_sub(m),
+        Prim2::WrappingMul => n.wrapping_mul(m),
+        Prim2::SaturatingAdd => saturate(n.checked_add(m), n < 0),
+        Prim2::SaturatingSub => saturate(n.checked_sub(m), n < 0),
+        Prim2::SaturatingMul => saturate(n.checked_mul(m), (n ^ m) < 0),
+        _ => unreachable!("{:?} may overflow", prim),
+    }
+}
+
+trait AssertInto: Sized {
+    fn assert_into<Var, Fun>(value: Value) -> Result<Self, InterpErr<Var, Fun>>;
+}
+impl AssertInto for i64 {
+    fn assert_into<Var, Fun>(value: Value) -> Result<Self, InterpErr<Var, Fun>> {
+        match value {
+            Value::Int(n) => Ok(n),
+            _ => Err(InterpErr::AssertTypeFailed(Type::Int)),
+        }
+    }
+}
+impl AssertInto for Big {
+    fn assert_into<Var, Fun>(value: Value) -> Result<Self, InterpErr<Var, Fun>> {
+        match value {
+            Value::Int(n) => Ok(Big::from_i64(n)),
+            Value::Big(n) => Ok(n.as_ref().clone()),
+            _ => Err(InterpErr::AssertTypeFailed(Type::Int)),
+        }
+    }
+}
+impl AssertInto for bool {
+    fn assert_into<Var, Fun>(value: Value) -> Result<Self, InterpErr<Var, Fun>> {
+        match value {
+            Value::Bool(b) => Ok(b),
+            _ => Err(InterpErr::AssertTypeFailed(Type::Bool)),
+        }
+    }
+}
+impl AssertInto for ArenaPtr {
+    fn assert_into<Var, Fun>(value: Value) -> Result<Self, InterpErr<Var, Fun>> {
+        match value {
+            Value::FatPtr(ptr) => Ok(ptr),
+            _ => Err(InterpErr::AssertTypeFailed(Type::Array)),
+        }
+    }
+}
+impl AssertInto for Raw {
+    fn assert_into<Var, Fun>(value: Value) -> Result<Self, InterpErr<Var, Fun>> {
+        match value {
+            Value::Raw(n) => Ok(n),
+            _ => Err(InterpErr::AssertRawFailed),
+        }
+    }
+}
+impl Default for Value {
+    fn default() -> Self {
+        Self::Int(0)
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Big(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::FatPtr(ptr) => write!(f, "<arena@{}>", ptr.idx),
+            Value::Raw(r) => write!(f, "<raw:{}>", r),
+            Value::None => write!(f, "none"),
+            Value::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Raw(pub i64);
+impl Raw {
+    pub fn size(n: usize) -> Self {
+        Self(n as i64)
+    }
+    pub fn downcast<Var, Fun>(self) -> Result<Value, InterpErr<Var, Fun>> {
+        if self.0 & Type::Int.mask() == 0 {
+            Ok(Value::Int(self.0 >> 1))
+        } else if self.0 & Type::Array.mask() == Type::Array.tag() {
+            Ok(Value::FatPtr(ArenaPtr { idx: ((self.0 ^ Type::Array.tag()) >> 3) as usize }))
+        } else if self.0 & Type::Bool.mask() == Type::Bool.tag() {
+            Ok(Value::Bool(self.0 & 0b100 != 0))
+        } else if self.0 == NONE {
+            Ok(Value::None)
+        } else {
+            Err(InterpErr::AssertRawFailed)
+        }
+    }
+}
+impl From<Raw> for i64 {
+    fn from(raw: Raw) -> Self {
+        raw.0
+    }
+}
+impl From<Raw> for ArenaPtr {
+    fn from(raw: Raw) -> Self {
+        Self { idx: raw.0 as usize }
+    }
+}
+impl From<Value> for Raw {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Int(n) => Self(n << 1),
+            Value::Bool(b) => Self(if b { 0b101 } else { 0b001 }),
+            Value::FatPtr(ptr) => Self((ptr.idx as i64) << 3 | Type::Array.tag()),
+            Value::Raw(r) => r,
+            Value::None => Self(NONE),
+            Value::Big(_) | Value::Str(_) => {
+                unreachable!(
+                    "big integers and strings are boxed on the heap, error in our interpreter?!"
+                )
+            }
+        }
+    }
+}
+impl Display for Raw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:x}", self.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ArenaPtr {
+    idx: usize,
+}
+
+/// The words of the runtime's heap, which no array fits in with its length
+pub const HEAP_WORDS: usize = 100000;
+
+pub struct Arena<T> {
+    inner: Vec<T>,
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self { inner: Vec::new() }
+    }
+
+    pub fn get(&self, ptr: ArenaPtr, idx: usize) -> &T {
+        &self.inner[ptr.idx + idx]
+    }
+
+    pub fn set(&mut self, ptr: ArenaPtr, idx: usize, val: T) {
+        self.inner[ptr.idx + idx] = val;
+    }
+}
+
+impl Arena<Value> {
+    pub fn alloc(&mut self, size: usize) -> ArenaPtr {
+        let ptr = ArenaPtr { idx: self.inner.len() };
+        self.inner.push(Value::Raw(Raw::size(size)));
+        for _ in 0..size {
+            self.inner.push(Default::default());
+        }
+        ptr
+    }
+    pub fn equal<Var, Fun>(&self, a: &Value, b: &Value) -> Result<bool, InterpErr<Var, Fun>> {
+        match (a, b) {
+            (Value::Int(a), Value::Int(b)) => Ok(a == b),
+            (Value::Big(a), Value::Big(b)) => Ok(a == b),
+            (Value::Bool(a), Value::Bool(b)) => Ok(a == b),
+            (Value::FatPtr(a), Value::FatPtr(b)) => {
+                let a_size = Raw::assert_into(self.inner[a.idx].clone())?;
+                let b_size = Raw::assert_into(self.inner[b.idx].clone())?;
+                let a = self.inner[a.idx..=(a.idx + a_size.0 as usize)].into_iter();
+                let b = self.inner[b.idx..=(b.idx + b_size.0 as usize)].into_iter();
+                a.zip(b).try_fold(true, |acc, (a, b)| Ok(acc && self.equal(a, b)?))
+            }
+            (Value::Raw(a), Value::Raw(b)) => Ok(a == b),
+            (Value::None, Value::None) => Ok(true),
+            (Value::Str(a), Value::Str(b)) => Ok(a == b),
+            _ => Ok(false),
+        }
+    }
+
+    /// The string that the runtime prints of `value`: arrays with their
+    /// elements, or `<loop>` for an array within itself, and strings as they are
+    pub fn show(&self, value: &Value) -> String {
+        fn show_loop(heap: &Arena<Value>, value: &Value, parents: &mut Vec<usize>) -> String {
+            let Value::FatPtr(ptr) = value else {
+                return value.to_string();
+            };
+            if parents.contains(&ptr.idx) {
+                return "<loop>".to_string();
+            }
+            let len = match heap.get(*ptr, 0) {
+                Value::Raw(Raw(len)) => *len as usize,
+                _ => 0,
+            };
+            parents.push(ptr.idx);
+            let elems: Vec<_> =
+                (1..=len).map(|i| show_loop(heap, heap.get(*ptr, i), parents)).collect();
+            parents.pop();
+            format!("[{}]", elems.join(", "))
+        }
+        show_loop(self, value, &mut Vec::new())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum InterpErr<Var, Fun> {
+    Unimplemented,
+    InvalidArg(String),
+    UnboundVar(Var),
+    UnboundFun(Fun),
+    UnExpectedFun(Fun),
+    CallToConst(Value),
+    CallWrongArity { name: Fun, expected: usize, got: usize },
+    UnboundBlock(BlockName),
+    BrWrongArity { name: BlockName, expected: usize, got: usize },
+    AssertTypeFailed(Type),
+    AssertRawFailed,
+    AssertLength,
+    AssertInBoundsFailed { bound: i64, of: i64 },
+    InvalidEncoding(Raw),
+    Overflow,
+    OutOfMemory,
+}
+
+impl<Var: Display, Fun: Display> Display for InterpErr<Var, Fun> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterpErr::Unimplemented => write!(f, "unimplemented"),
+            InterpErr::InvalidArg(arg) => write!(f, "invalid argument: {}", arg),
+            InterpErr::UnboundVar(var) => write!(f, "unbound variable: {}", var),
+            InterpErr::UnboundFun(fun) => write!(f, "unbound function: {}", fun),
+            InterpErr::UnExpectedFun(fun) => write!(f, "unexpected function: {}", fun),
+            InterpErr::CallToConst(n) => write!(f, "call to constant: {}", n),
+            InterpErr::CallWrongArity { name, expected, got } => {
+                write!(
+                    f,
+                    "calling function {} with wrong arity: expected {}, got {}",
+                    name, expected, got
+                )
+            }
+            InterpErr::UnboundBlock(block) => write!(f, "unbound block: {}", block),
+            InterpErr::BrWrongArity { name, expected, got } => {
+                write!(
+                    f,
+                    "branching to block {} with wrong arity: expected {}, got {}",
+                    name, expected, got
+                )
+            }
+            InterpErr::AssertTypeFailed(ty) => write!(f, "{} assertion failed", ty),
+            InterpErr::AssertRawFailed => write!(f, "raw encoding assertion failed"),
+            InterpErr::AssertLength => write!(f, "negative length"),
+            InterpErr::AssertInBoundsFailed { bound, of } => {
+                write!(f, "{} is out of bounds of [0, {})", of, bound)
+            }
+            InterpErr::InvalidEncoding(raw) => write!(f, "invalid encoding: 0x{:x}", raw.0),
+            InterpErr::Overflow => write!(f, "arithmetic operation overflowed"),
+            InterpErr::OutOfMemory => write!(f, "out of memory"),
+        }
+    }
+}
+
+fn parse_snake_basic_val<Var, Fun>(s: String) -> Result<Value, InterpErr<Var, Fun>> {
+    let s = s.trim();
+    if s == "true" {
+        Ok(Value::Bool(true))
+    } else if s == "false" {
+        Ok(Value::Bool(false))
+    } else if let Some(x) = Big::parse(s) {
+        Ok(Value::from_big(x))
+    } else {
+        Err(InterpErr::InvalidArg(s.to_string()))
+    }
+}
+
+/// Looks up the environment variable named by the string `name`, like the
+/// runtime's `snake_getenv`: its value as an array of bytes, or `false` if it
+/// is unset or `name` holds anything but bytes.
+fn getenv<Var, Fun>(heap: &mut Arena<Value>, name: Value) -> Result<Value, InterpErr<Var, Fun>> {
+    let ptr = ArenaPtr::assert_into(name)?;
+    let Raw(len) = Raw::assert_into(heap.get(ptr, 0).clone())?;
+    let name = (1..=len as usize)
+        .map(|i| match heap.get(ptr, i) {
+            Value::Int(b @ 0..=255) => Some(*b as u8),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>();
+    let Some(bytes) = name.and_then(var_bytes) else {
+        return Ok(Value::Bool(false));
+    };
+    let arr = heap.alloc(bytes.len());
+    for (i, b) in bytes.into_iter().enumerate() {
+        heap.set(arr, i + 1, Value::Int(b as i64));
+    }
+    Ok(Value::FatPtr(arr))
+}
+
+#[cfg(unix)]
+fn var_bytes(name: Vec<u8>) -> Option<Vec<u8>> {
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+    std::env::var_os(std::ffi::OsStr::from_bytes(&name)).map(|val| val.into_vec())
+}
+
+/// Without byte strings for the environment, only UTF-8 names and values are
+/// found.
+#[cfg(not(unix))]
+fn var_bytes(name: Vec<u8>) -> Option<Vec<u8>> {
+    let name = String::from_utf8(name).ok()?;
+    std::env::var(name).ok().map(String::into_bytes)
+}
+
+/// Where a resumed interpreter stopped
+#[derive(Clone, Debug)]
+pub enum Status {
+    /// The program returned a value
+    Done(Value),
+    /// The steps ran out before the program returned
+    Suspended,
+}
+
+/// Renders the backtrace of a failed program, most recent call first, with
+/// the lines in `file` where they are known
+pub fn render_backtrace<Fun: Display>(trace: &[(Fun, Option<SrcLoc>)], file: &FileInfo) -> String {
+    let mut out = "backtrace, most recent call first:".to_string();
+    for (i, (fun, loc)) in trace.iter().enumerate() {
+        out.push_str(&format!("\n    {}: {}", i, fun));
+        if let Some(loc) = loc {
+            out.push_str(&format!(" at line {}", file.line_col(loc.start_ix).0));
+        }
+    }
+    out
+}
+
+/// The time and random-number services of the runtime, reproduced for the
+/// interpreters. Seeding them makes both deterministic: `random` draws from a
+/// splitmix64 generator started at the seed, and `clock` counts its calls
+/// instead of reading the time, so that seeded services also work where
+/// there is no clock, as on `wasm32-unknown-unknown`.
+pub struct Services {
+    rng: u64,
+    /// `None` if seeded
+    start: Option<std::time::Instant>,
+    ticks: i64,
+    /// Whether integer arithmetic promotes overflowing results to big
+    /// integers, as the runtime does under `--bigint`
+    pub bigint: bool,
+}
+
+impl Services {
+    pub fn new(seed: Option<u64>) -> Self {
+        let rng = seed.unwrap_or_else(|| {
+            let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+            now.map_or(0, |d| d.as_nanos() as u64)
+        });
+        let start = seed.is_none().then(std::time::Instant::now);
+        Services { rng, start, ticks: 0, bigint: false }
+    }
+
+    pub fn bigint(self, bigint: bool) -> Self {
+        Services { bigint, ..self }
+    }
+
+    /// Seeded by the `SNAKE_SEED` environment variable, like the runtime.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("SNAKE_SEED").ok().and_then(|s| s.parse().ok()))
+    }
+
+    /// Seeded with 0, as there is neither an environment nor a clock.
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    pub fn from_env() -> Self {
+        Self::new(Some(0))
+    }
+
+    /// The milliseconds since the start, or the number of previous calls if seeded
+    pub fn clock(&mut self) -> i64 {
+        match self.start {
+            Some(start) => start.elapsed().as_millis() as i64,
+            None => {
+                self.ticks += 1;
+                self.ticks - 1
+            }
+        }
+    }
+
+    /// A number in `[0, n)`, or 0 if `n` is not positive
+    pub fn random(&mut self, n: i64) -> i64 {
+        self.rng = self.rng.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        if n <= 0 {
+            0
+        } else {
+            (z % n as u64) as i64
+        }
+    }
+}
+
+/* ------------------------------- Primitives ------------------------------- */
+
+/// The wrapping or saturating `prim` on integers, like `tagged_arith`
+fn untagged_arith(prim: crate::ssa::Prim2, n: i64, m: i64) -> i64 {
+    use crate::ssa::Prim2;
+    let n = match prim {
+        Prim2::WrappingMul | Prim2::SaturatingMul => n,
+        _ => n << 1,
+    };
+    tagged_arith(&prim, n, m << 1) >> 1
+}
+
+/// `n` if it fits in the 63 bits of a tagged integer
+fn small(n: Option<i64>) -> Option<i64> {
+    n.filter(|n| (n << 1) >> 1 == *n)
+}
+
+/// What primitives may use besides their arguments
+pub struct PrimEnv<'a> {
+    pub heap: &'a mut Arena<Value>,
+    pub services: &'a mut Services,
+    /// Whether integer arithmetic computes on big integers
+    pub bigint: bool,
+}
+
+/// A primitive on any values, with the heap and the services
+pub type PrimFn<Var, Fun> = fn(&mut PrimEnv, Vec<Value>) -> Result<Value, InterpErr<Var, Fun>>;
+
+/// How a primitive computes its result from its arguments
+pub enum PrimEval<Var, Fun> {
+    /// On an integer, `None` on overflow, or on a big one under `--bigint` if given
+    Int(fn(i64) -> Option<i64>, Option<fn(&Big) -> Big>),
+    Bool(fn(bool) -> bool),
+    /// On two integers, `None` on overflow, or on big ones under `--bigint` if given
+    IntInt(fn(i64, i64) -> Option<i64>, Option<fn(&Big, &Big) -> Big>),
+    /// Compares two integers, or big ones under `--bigint`
+    IntBool(fn(i64, i64) -> bool, fn(&Big, &Big) -> bool),
+    BoolBool(fn(bool, bool) -> bool),
+    IsType(Type),
+    /// The value itself, if it has the type
+    Check(Type),
+    /// The value of a literal
+    Const(Value),
+    Env(PrimFn<Var, Fun>),
+}
+
+/// The semantics of a primitive, shared by both interpreters so that each
+/// primitive is defined once. The AST interpreter runs it on the values of
+/// the arguments, and the SSA interpreter on those of the calls to the
+/// runtime functions in [`RUNTIME_PRIMS`].
+pub struct PrimSemantics<Var, Fun> {
+    /// The number of arguments, or `None` for any number
+    pub arity: Option<usize>,
+    pub eval: PrimEval<Var, Fun>,
+}
+
+/// The runtime functions that the SSA calls in place of primitives, and
+/// whether they compute on big integers
+pub const RUNTIME_PRIMS: &[(&str, Prim, bool)] = &[
+    ("snake_equals", Prim::Eq, false),
+    ("snake_not_equals", Prim::Neq, false),
+    ("snake_getenv", Prim::GetEnv, false),
+    ("snake_clock", Prim::Clock, false),
+    ("snake_random", Prim::Random, false),
+    ("snake_to_str", Prim::ToStr, false),
+    ("snake_str_concat", Prim::Concat, false),
+    ("snake_big_add", Prim::Add, true),
+    ("snake_big_sub", Prim::Sub, true),
+    ("snake_big_mul", Prim::Mul, true),
+    ("snake_big_is_int", Prim::IsType(Type::Int), true),
+];
+
+/// The array of `a[i]` and the offset of the element, if `i` is in bounds
+fn element<Var, Fun>(
+    heap: &Arena<Value>, arr: Value, idx: Value,
+) -> Result<(ArenaPtr, usize), InterpErr<Var, Fun>> {
+    let ptr = ArenaPtr::assert_into(arr)?;
+    let idx = i64::assert_into(idx)?;
+    let Raw(len) = Raw::assert_into(heap.get(ptr, 0).clone())?;
+    if idx < 0 || idx >= len {
+        Err(InterpErr::AssertInBoundsFailed { bound: len, of: idx })?
+    }
+    Ok((ptr, idx as usize + 1))
+}
+
+fn has_type(value: &Value, ty: Type) -> bool {
+    matches!(
+        (value, ty),
+        (Value::Int(_) | Value::Big(_), Type::Int)
+            | (Value::Bool(_), Type::Bool)
+            | (Value::FatPtr(_), Type::Array)
+    )
+}
+
+impl<Var, Fun> PrimSemantics<Var, Fun> {
+    /// The semantics of `prim`. As the match has no wildcard, a new `Prim`
+    /// does not compile until it is given semantics here.
+    pub fn of(prim: &Prim) -> Self {
+        use crate::ssa::Prim2;
+        use PrimEval::*;
+        let (arity, eval) = match prim {
+            Prim::Add1 => {
+                (Some(1), Int(|n| small(n.checked_add(1)), Some(|n| n.add(&Big::from_i64(1)))))
+            }
+            Prim::Sub1 => {
+                (Some(1), Int(|n| small(n.checked_sub(1)), Some(|n| n.sub(&Big::from_i64(1)))))
+            }
+            Prim::Add => (Some(2), IntInt(|n, m| small(n.checked_add(m)), Some(|n, m| n.add(m)))),
+            Prim::Sub => (Some(2), IntInt(|n, m| small(n.checked_sub(m)), Some(|n, m| n.sub(m)))),
+            Prim::Mul => (Some(2), IntInt(|n, m| small(n.checked_mul(m)), Some(|n, m| n.mul(m)))),
+            Prim::WrappingAdd => {
+                (Some(2), IntInt(|n, m| Some(untagged_arith(Prim2::WrappingAdd, n, m)), None))
+            }
+            Prim::WrappingSub => {
+                (Some(2), IntInt(|n, m| Some(untagged_arith(Prim2::WrappingSub, n, m)), None))
+            }
+            Prim::WrappingMul => {
+                (Some(2), IntInt(|n, m| Some(untagged_arith(Prim2::WrappingMul, n, m)), None))
+            }
+            Prim::SaturatingAdd => {
+                (Some(2), IntInt(|n, m| Some(untagged_arith(Prim2::SaturatingAdd, n, m)), None))
+            }
+            Prim::SaturatingSub => {
+                (Some(2), IntInt(|n, m| Some(untagged_arith(Prim2::SaturatingSub, n, m)), None))
+            }
+            Prim::SaturatingMul => {
+                (Some(2), IntInt(|n, m| Some(untagged_arith(Prim2::SaturatingMul, n, m)), None))
+            }
+            Prim::Not => (Some(1), Bool(|b| !b)),
+            Prim::And => (Some(2), BoolBool(|b, c| b && c)),
+            Prim::Or => (Some(2), BoolBool(|b, c| b || c)),
+            Prim::Lt => (Some(2), IntBool(|n, m| n < m, |n, m| n < m)),
+            Prim::Le => (Some(2), IntBool(|n, m| n <= m, |n, m| n <= m)),
+            Prim::Gt => (Some(2), IntBool(|n, m| n > m, |n, m| n > m)),
+            Prim::Ge => (Some(2), IntBool(|n, m| n >= m, |n, m| n >= m)),
+            Prim::Eq => {
+                (Some(2), Env(|env, args| Ok(Value::Bool(env.heap.equal(&args[0], &args[1])?))))
+            }
+            Prim::Neq => {
+                (Some(2), Env(|env, args| Ok(Value::Bool(!env.heap.equal(&args[0], &args[1])?))))
+            }
+            Prim::IsType(ty) => (Some(1), IsType(*ty)),
+            Prim::Check(ty) => (Some(1), Check(*ty)),
+            Prim::None => (Some(0), Env(|_, _| Ok(Value::None))),
+            Prim::Str(s) => (Some(0), Const(Value::Str(s.as_str().into()))),
+            Prim::ToStr => {
+                (Some(1), Env(|env, args| Ok(Value::Str(env.heap.show(&args[0]).into()))))
+            }
+            Prim::Concat => (
+                Some(2),
+                Env(|env, args| {
+                    let s = env.heap.show(&args[0]) + &env.heap.show(&args[1]);
+                    Ok(Value::Str(s.into()))
+                }),
+            ),
+            Prim::IsNone => {
+                (Some(1), Env(|_, args| Ok(Value::Bool(matches!(args[0], Value::None)))))
+            }
+            Prim::NewArray => (
+                Some(1),
+                Env(|env, mut args| {
+                    let size = i64::assert_into(args.remove(0))?;
+                    if size < 0 {
+                        Err(InterpErr::AssertLength)?
+                    } else if size as usize >= HEAP_WORDS {
+                        Err(InterpErr::OutOfMemory)?
+                    }
+                    Ok(Value::FatPtr(env.heap.alloc(size as usize)))
+                }),
+            ),
+            Prim::MakeArray => (
+                None,
+                Env(|env, args| {
+                    let ptr = env.heap.alloc(args.len());
+                    for (i, arg) in args.into_iter().enumerate() {
+                        env.heap.set(ptr, i + 1, arg);
+                    }
+                    Ok(Value::FatPtr(ptr))
+                }),
+            ),
+            Prim::ArrayGet => (
+                Some(2),
+                Env(|env, mut args| {
+                    let idx = args.pop().unwrap();
+                    let (ptr, off) = element(env.heap, args.pop().unwrap(), idx)?;
+                    Ok(env.heap.get(ptr, off).clone())
+                }),
+            ),
+            Prim::ArraySet => (
+                Some(3),
+                Env(|env, mut args| {
+                    let val = args.pop().unwrap();
+                    let idx = args.pop().unwrap();
+                    let (ptr, off) = element(env.heap, args.pop().unwrap(), idx)?;
+                    env.heap.set(ptr, off, val.clone());
+                    Ok(val)
+                }),
+            ),
+            Prim::Length => (
+                Some(1),
+                Env(|env, mut args| {
+                    let ptr = ArenaPtr::assert_into(args.remove(0))?;
+                    let Raw(len) = Raw::assert_into(env.heap.get(ptr, 0).clone())?;
+                    Ok(Value::Int(len))
+                }),
+            ),
+            Prim::GetEnv => (Some(1), Env(|env, mut args| getenv(env.heap, args.remove(0)))),
+            Prim::Clock => (Some(0), Env(|env, _| Ok(Value::Int(env.services.clock())))),
+            Prim::Random => (
+                Some(1),
+                Env(|env, mut args| {
+                    let n = i64::assert_into(args.remove(0))?;
+                    Ok(Value::Int(env.services.random(n)))
+                }),
+            ),
+            Prim::Yield | Prim::Next => {
+                unreachable!("generators are compiled away after resolution")
+            }
+        };
+        PrimSemantics { arity, eval }
+    }
+
+    /// Runs the primitive on `args`, which must be as many as its arity
+    pub fn run(&self, env: &mut PrimEnv, args: Vec<Value>) -> Result<Value, InterpErr<Var, Fun>> {
+        if self.arity.is_some_and(|arity| arity != args.len()) {
+            unreachable!("wrong arity to primitive operator, error in our interpreter?!");
+        }
+        if let PrimEval::Env(f) = &self.eval {
+            return f(env, args);
+        }
+        let mut args = args.into_iter();
+        let mut arg = || args.next().unwrap();
+        match &self.eval {
+            PrimEval::Int(_, Some(big)) if env.bigint => {
+                Ok(Value::from_big(big(&Big::assert_into(arg())?)))
+            }
+            PrimEval::Int(f, _) => {
+                Ok(Value::Int(f(i64::assert_into(arg())?).ok_or(InterpErr::Overflow)?))
+            }
+            PrimEval::Bool(f) => Ok(Value::Bool(f(bool::assert_into(arg())?))),
+            PrimEval::IntInt(_, Some(big)) if env.bigint => {
+                let n = Big::assert_into(arg())?;
+                Ok(Value::from_big(big(&n, &Big::assert_into(arg())?)))
+            }
+            PrimEval::IntInt(f, _) => {
+                let n = i64::assert_into(arg())?;
+                Ok(Value::Int(f(n, i64::assert_into(arg())?).ok_or(InterpErr::Overflow)?))
+            }
+            PrimEval::IntBool(_, big) if env.bigint => {
+                let n = Big::assert_into(arg())?;
+                Ok(Value::Bool(big(&n, &Big::assert_into(arg())?)))
+            }
+            PrimEval::IntBool(f, _) => {
+                let n = i64::assert_into(arg())?;
+                Ok(Value::Bool(f(n, i64::assert_into(arg())?)))
+            }
+            PrimEval::BoolBool(f) => {
+                let b = bool::assert_into(arg())?;
+                Ok(Value::Bool(f(b, bool::assert_into(arg())?)))
+            }
+            PrimEval::IsType(ty) => Ok(Value::Bool(has_type(&arg(), *ty))),
+            PrimEval::Check(ty) => {
+                let value = arg();
+                match has_type(&value, *ty) {
+                    true => Ok(value),
+                    false => Err(InterpErr::AssertTypeFailed(*ty)),
+                }
+            }
+            PrimEval::Const(value) => Ok(value.clone()),
+            PrimEval::Env(_) => unreachable!(),
+        }
+    }
+}
+
+/* ---------------------------------- Snake --------------------------------- */
+
+pub mod ast {
+    use super::*;
+    use crate::ast::*;
+    use im::HashMap;
+
+    /// The AST interpreter, an abstract machine that runs a program a
+    /// bounded number of steps at a time. It refers to the expressions of
+    /// the program rather than copy them, and keeps its continuation in a
+    /// vector, so that deeply nested programs take no more of the native
+    /// stack than shallow ones.
+    pub struct Machine<'p, Var, Fun> {
+        /// `None` once a step failed
+        state: Option<State<'p, Var, Fun>>,
+        services: Services,
+        calls: Calls<Fun>,
+        /// The tags of the constructors of the enums
+        tags: HashMap<Fun, i64>,
+    }
+
+    /// The calls in progress, kept apart from the state to report a
+    /// backtrace once a step failed
+    struct Calls<Fun> {
+        /// The functions called, each with the location of its call; a tail
+        /// call takes the place of its caller
+        frames: Vec<(Fun, Option<SrcLoc>)>,
+        /// The location of the expression whose step failed
+        fault: Option<SrcLoc>,
+    }
+
+    struct State<'p, Var, Fun> {
+        redex: Redex<'p, Var, Fun>,
+        /// The continuation, innermost frame last; empty once the program returns
+        stack: Vec<Frame<'p, Var, Fun>>,
+        heap: Arena<Value>,
+    }
+
+    #[derive(Clone)]
+    enum Redex<'p, Var, Fun> {
+        Decending { expr: &'p Expr<Var, Fun>, env: Env<'p, Var, Fun> },
+        Ascending(DynValue<'p, Var, Fun>),
+    }
+
+    #[derive(Clone)]
+    struct FunDef<'p, Var, Fun> {
+        params: Vec<Var>,
+        body: &'p Expr<Var, Fun>,
+    }
+
+    #[derive(Clone)]
+    struct Closure<'p, Var, Fun> {
+        env: Env<'p, Var, Fun>,
+        decls: HashMap<Fun, FunDef<'p, Var, Fun>>,
+        name: Fun,
+    }
+
+    #[derive(Clone)]
+    enum DynValue<'p, Var, Fun> {
+        Value(Value),
+        Closure(Closure<'p, Var, Fun>),
+    }
+
+    #[derive(Clone, Hash, PartialEq, Eq)]
+    enum VarOrFun<Var, Fun> {
+        Var(Var),
+        Fun(Fun),
+    }
+
+    type Env<'p, Var, Fun> = HashMap<VarOrFun<Var, Fun>, DynValue<'p, Var, Fun>>;
+
+    #[derive(Clone)]
+    enum Operator<Fun> {
+        Prim(Prim),
+        Call(Fun),
+    }
+
+    /// The elements of a destructuring binding, and where it is
+    type Elems<Var> = Option<(Vec<Var>, SrcLoc)>;
+
+    #[derive(Clone)]
+    enum Frame<'p, Var, Fun> {
+        /// The return from a function, which ends its frame in the backtrace
+        Call,
+        Operation {
+            operator: Operator<Fun>,
+            env: Env<'p, Var, Fun>,
+            loc: SrcLoc,
+            /// evaluated arguments
+            evaluated: Vec<DynValue<'p, Var, Fun>>,
+            /// remaining arguments
+            remaining: &'p [Expr<Var, Fun>],
+        },
+        Let {
+            env: Env<'p, Var, Fun>,
+            var: Var,
+            elems: Elems<Var>,
+            /// the bindings after `var`
+            remaining: &'p [Binding<Var, Fun>],
+            body: &'p Expr<Var, Fun>,
+        },
+        If {
+            env: Env<'p, Var, Fun>,
+            thn: &'p Expr<Var, Fun>,
+            els: &'p Expr<Var, Fun>,
+            loc: SrcLoc,
+        },
+        Switch {
+            env: Env<'p, Var, Fun>,
+            cases: &'p [Case<Var, Fun>],
+            default: &'p Expr<Var, Fun>,
+            loc: SrcLoc,
+        },
+        Match {
+            env: Env<'p, Var, Fun>,
+            arms: &'p [Arm<Var, Fun>],
+            loc: SrcLoc,
+        },
+    }
+
+    impl<Var, Fun> Frame<'_, Var, Fun> {
+        /// The location of the expression that the continuation runs, if it may fail
+        fn loc(&self) -> Option<SrcLoc> {
+            match self {
+                Frame::Operation { loc, .. }
+                | Frame::If { loc, .. }
+                | Frame::Switch { loc, .. }
+                | Frame::Match { loc, .. } => Some(*loc),
+                Frame::Let { elems, .. } => elems.as_ref().map(|(_, loc)| *loc),
+                Frame::Call => None,
+            }
+        }
+    }
+
+    /// The variable of `binding`, and its elements if it destructures an array
+    fn pending<Var: Clone, Fun>(binding: &Binding<Var, Fun>) -> (Var, Elems<Var>) {
+        let Binding { var: Spanned { node: var, loc }, elems, .. } = binding;
+        let elems =
+            elems.as_ref().map(|elems| (elems.iter().map(|e| e.node.clone()).collect(), *loc));
+        (var.clone(), elems)
+    }
+
+    /// Whether `value` matches `pattern`, binding the variables of the
+    /// pattern in `bound` if so. The tag of an object is its first element.
+    fn matches<Var: Clone, Fun: Hash + Eq + Clone>(
+        pattern: &Pattern<Var, Fun>, value: Value, heap: &Arena<Value>, tags: &HashMap<Fun, i64>,
+        bound: &mut Vec<(Var, Value)>,
+    ) -> Result<bool, InterpErr<Var, Fun>> {
+        let (ctor, args) = match pattern {
+            Pattern::Wildcard(_) => return Ok(true),
+            Pattern::Var(var, _) => {
+                bound.push((var.clone(), value));
+                return Ok(true);
+            }
+            Pattern::Ctor { ctor, args, .. } => (ctor, args),
+        };
+        let (ptr, tag) = element(heap, value.clone(), Value::Int(0))?;
+        let Value::Int(tag) = heap.get(ptr, tag) else {
+            Err(InterpErr::AssertTypeFailed(Type::Int))?
+        };
+        if *tag != tags[ctor] {
+            return Ok(false);
+        }
+        for (i, arg) in args.iter().enumerate() {
+            let (ptr, field) = element(heap, value.clone(), Value::Int(i as i64 + 1))?;
+            if !matches(arg, heap.get(ptr, field).clone(), heap, tags, bound)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    impl<'p, Var, Fun> Machine<'p, Var, Fun>
+    where
+        Var: Hash + Eq + Clone,
+        Fun: Hash + Eq + Clone,
+    {
+        pub fn run<S>(
+            prog: &'p Prog<Var, Fun>, args: impl IntoIterator<Item = S>,
+        ) -> Result<Value, InterpErr<Var, Fun>>
+        where
+            S: Into<String>,
+        {
+            Self::run_with(prog, args, Services::from_env())
+        }
+
+        pub fn run_with<S>(
+            prog: &'p Prog<Var, Fun>, args: impl IntoIterator<Item = S>, services: Services,
+        ) -> Result<Value, InterpErr<Var, Fun>>
+        where
+            S: Into<String>,
+        {
+            let mut machine = Self::start(prog, args, services)?;
+            loop {
+                if let Status::Done(value) = machine.resume(usize::MAX)? {
+                    break Ok(value);
+                }
+            }
+        }
+
+        /// A machine about to run `prog` on `args`; nothing runs before `resume`
+        pub fn start<S>(
+            Prog { externs, enums, name, param: Spanned { node: param, .. }, body, .. }: &'p Prog<
+                Var,
+                Fun,
+            >,
+            args: impl IntoIterator<Item = S>, services: Services,
+        ) -> Result<Self, InterpErr<Var, Fun>>
+        where
+            S: Into<String>,
+        {
+            // Note: extern functions are not supported
+            assert!(externs.is_empty(), "extern functions are not supported");
+
+            let args: Vec<Value> = args
+                .into_iter()
+                .map(Into::into)
+                .map(parse_snake_basic_val)
+                .collect::<Result<_, _>>()?;
+            let mut env = HashMap::new();
+            let mut heap = Arena::new();
+            let ptr = heap.alloc(args.len());
+            args.into_iter().enumerate().for_each(|(i, arg)| {
+                heap.set(ptr, i + 1, arg);
+            });
+            let arr = Value::FatPtr(ptr);
+            let decls =
+                HashMap::from_iter([(name.clone(), FunDef { params: vec![param.clone()], body })]);
+            env.insert(
+                VarOrFun::Fun(name.clone()),
+                DynValue::Closure(Closure { env: HashMap::new(), decls, name: name.clone() }),
+            );
+            env.insert(VarOrFun::Var(param.clone()), DynValue::Value(arr));
+            let redex = Redex::Decending { expr: body, env };
+            let calls = Calls { frames: vec![(name.clone(), None)], fault: None };
+            // numbered as `patterns::Enums` numbers them
+            let variants = enums.iter().flat_map(|decl| decl.variants.iter());
+            let tags = variants.enumerate().map(|(tag, v)| (v.name.clone(), tag as i64)).collect();
+            Ok(Machine {
+                state: Some(State { redex, stack: Vec::new(), heap }),
+                services,
+                calls,
+                tags,
+            })
+        }
+
+        /// The functions that were running when a step failed, most recent first,
+        /// each with the location of the expression it was at
+        pub fn backtrace(&self) -> Vec<(Fun, Option<SrcLoc>)> {
+            let Calls { frames, fault } = &self.calls;
+            let locs = frames.iter().skip(1).map(|(_, loc)| *loc).chain([*fault]);
+            let mut trace: Vec<_> = frames.iter().map(|(fun, _)| fun.clone()).zip(locs).collect();
+            trace.reverse();
+            trace
+        }
+
+        /// Runs at most `steps` more steps; once done, resuming again returns the same value.
+        ///
+        /// Panics if a previous step failed.
+        pub fn resume(&mut self, steps: usize) -> Result<Status, InterpErr<Var, Fun>> {
+            let services = &mut self.services;
+            let calls = &mut self.calls;
+            let tags = &self.tags;
+            let mut state = self.state.take().expect("resumed a machine after an error");
+            for _ in 0..steps {
+                let loc = match &state.redex {
+                    Redex::Decending { expr, .. } => Some(expr.loc()),
+                    Redex::Ascending(_) => state.stack.last().and_then(Frame::loc),
+                };
+                let step = match state {
+                    State { redex: Redex::Decending { expr, env }, stack, heap } => {
+                        State::dive_expr(expr, env, stack, heap, services, calls)
+                    }
+                    State { redex: Redex::Ascending(dv), stack, heap } if stack.is_empty() => {
+                        // the termination of the interpreter
+                        let value = match &dv {
+                            DynValue::Value(v) => Ok(v.clone()),
+                            DynValue::Closure(Closure { name, .. }) => {
+                                Err(InterpErr::UnExpectedFun(name.clone()))
+                            }
+                        };
+                        self.state = Some(State { redex: Redex::Ascending(dv), stack, heap });
+                        return value.map(Status::Done);
+                    }
+                    State { redex: Redex::Ascending(dv), stack, heap } => {
+                        State::run_kont(dv, stack, heap, services, calls, tags)
+                    }
+                };
+                state = step.inspect_err(|_| calls.fault = loc)?;
+            }
+            self.state = Some(state);
+            Ok(Status::Suspended)
+        }
+    }
+
+    impl<'p, Var, Fun> State<'p, Var, Fun>
+    where
+        Var: Hash + Eq + Clone,
+        Fun: Hash + Eq + Clone,
+    {
+        fn dive_expr(
+            expr: &'p Expr<Var, Fun>, env: Env<'p, Var, Fun>, mut stack: Vec<Frame<'p, Var, Fun>>,
+            heap: Arena<Value>, services: &mut Services, calls: &mut Calls<Fun>,
+        ) -> Result<Self, InterpErr<Var, Fun>> {
+            let ret_machine = |dv: DynValue<'p, Var, Fun>, stack, heap| State {
+                redex: Redex::Ascending(dv),
+                stack,
+                heap,
+            };
+            let dive_machine = |expr, env, stack, heap| State {
+                redex: Redex::Decending { expr, env },
+                stack,
+                heap,
+            };
+            match expr {
+                Expr::Num(n, _) => Ok(ret_machine(DynValue::Value(Value::Int(*n)), stack, heap)),
+                Expr::BigNum(n, _) => {
+                    Ok(ret_machine(DynValue::Value(Value::from_big(n.clone())), stack, heap))
+                }
+                Expr::Bool(b, _) => Ok(ret_machine(DynValue::Value(Value::Bool(*b)), stack, heap)),
+                Expr::Var(v, _) => {
+                    let val = env
+                        .get(&VarOrFun::Var(v.clone()))
+                        .ok_or_else(|| InterpErr::UnboundVar(v.clone()))?;
+                    Ok(ret_machine(val.clone(), stack, heap))
+                }
+                Expr::Prim { prim, args, loc } => Self::dive_operator(
+                    Operator::Prim(prim.clone()),
+                    args,
+                    *loc,
+                    env.clone(),
+                    stack,
+                    heap,
+                    services,
+                    calls,
+                ),
+                Expr::Let { bindings, body, loc: _ } => {
+                    if let Some((binding, remaining)) = bindings.split_first() {
+                        let (var, elems) = pending(binding);
+                        stack.push(Frame::Let { env: env.clone(), var, elems, remaining, body });
+                        Ok(dive_machine(&binding.expr, env, stack, heap))
+                    } else {
+                        Ok(dive_machine(body, env, stack, heap))
+                    }
+                }
+                Expr::If { cond, thn, els, loc } => {
+                    stack.push(Frame::If { env: env.clone(), thn, els, loc: *loc });
+                    Ok(dive_machine(cond, env, stack, heap))
+                }
+                Expr::Switch { scrutinee, cases, default, loc } => {
+                    stack.push(Frame::Switch { env: env.clone(), cases, default, loc: *loc });
+                    Ok(dive_machine(scrutinee, env, stack, heap))
+                }
+                Expr::Match { scrutinee, arms, loc } => {
+                    stack.push(Frame::Match { env: env.clone(), arms, loc: *loc });
+                    Ok(dive_machine(scrutinee, env, stack, heap))
+                }
+                Expr::FunDefs { decls, body, loc: _ } => {
+                    let curr = env;
+                    let mut next = curr.clone();
+                    let decls = HashMap::from_iter(decls.iter().map(
+                        |FunDecl { name, params, body, .. }| {
+                            let params = params.iter().map(|var| var.node.clone()).collect();
+                            (name.clone(), FunDef { params, body })
+                        },
+                    ));
+                    for name in decls.keys() {
+                        next.insert(
+                            VarOrFun::Fun(name.clone()),
+                            DynValue::Closure(Closure {
+                                env: curr.clone(),
+                                decls: decls.clone(),
+                                name: name.clone(),
+                            }),
+                        );
+                    }
+                    Ok(dive_machine(body, next, stack, heap))
+                }
+                Expr::Call { fun, args, loc } => Self::dive_operator(
+                    Operator::Call(fun.clone()),
+                    args,
+                    *loc,
+                    env.clone(),
+                    stack,
+                    heap,
+                    services,
+                    calls,
+                ),
+            }
+        }
+        #[allow(clippy::too_many_arguments)]
+        fn dive_operator(
+            operator: Operator<Fun>, args: &'p [Expr<Var, Fun>], loc: SrcLoc,
+            env: Env<'p, Var, Fun>, mut stack: Vec<Frame<'p, Var, Fun>>, heap: Arena<Value>,
+            services: &mut Services, calls: &mut Calls<Fun>,
+        ) -> Result<Self, InterpErr<Var, Fun>> {
+            if let Some((expr, remaining)) = args.split_first() {
+                let evaluated = Vec::new();
+                stack.push(Frame::Operation {
+                    operator,
+                    env: env.clone(),
+                    loc,
+                    evaluated,
+                    remaining,
+                });
+                Ok(State { redex: Redex::Decending { expr, env }, stack, heap })
+            } else {
+                match operator {
+                    Operator::Prim(prim) => {
+                        Self::run_prim(&prim, Vec::new(), stack, heap, services)
+                    }
+                    Operator::Call(fun) => {
+                        Self::run_call(fun, Vec::new(), loc, env, stack, heap, calls)
+                    }
+                }
+            }
+        }
+        fn run_kont(
+            dv: DynValue<'p, Var, Fun>, mut stack: Vec<Frame<'p, Var, Fun>>, heap: Arena<Value>,
+            services: &mut Services, calls: &mut Calls<Fun>, tags: &HashMap<Fun, i64>,
+        ) -> Result<Self, InterpErr<Var, Fun>> {
+            let frame =
+                stack.pop().expect("return kont should not be run, error in our interpreter?!");
+            match frame {
+                Frame::Call => {
+                    calls.frames.pop();
+                    Ok(State { redex: Redex::Ascending(dv), stack, heap })
+                }
+                Frame::Operation { operator, env, loc, mut evaluated, remaining } => {
+                    evaluated.push(dv);
+                    if let Some((expr, remaining)) = remaining.split_first() {
+                        let frame = Frame::Operation {
+                            operator,
+                            env: env.clone(),
+                            loc,
+                            evaluated,
+                            remaining,
+                        };
+                        stack.push(frame);
+                        Ok(State { redex: Redex::Decending { expr, env }, stack, heap })
+                    } else {
+                        match operator {
+                            Operator::Prim(prim) => {
+                                Self::run_prim(&prim, evaluated, stack, heap, services)
+                            }
+                            Operator::Call(fun) => {
+                                Self::run_call(fun, evaluated, loc, env, stack, heap, calls)
+                            }
+                        }
+                    }
+                }
+                Frame::Let { mut env, var, elems, remaining, body } => {
+                    if let Some((elems, _)) = elems {
+                        let arr = match dv.clone() {
+                            DynValue::Value(arr) => arr,
+                            DynValue::Closure(Closure { name, .. }) => {
+                                Err(InterpErr::UnExpectedFun(name))?
+                            }
+                        };
+                        // one check for all of the elements, as `destructure` lowers it
+                        let last = Value::Int(elems.len() as i64 - 1);
+                        let (ptr, _) = element(&heap, arr, last)?;
+                        for (i, elem) in elems.into_iter().enumerate() {
+                            let value = DynValue::Value(heap.get(ptr, i + 1).clone());
+                            env.insert(VarOrFun::Var(elem), value);
+                        }
+                    }
+                    env.insert(VarOrFun::Var(var.clone()), dv);
+                    if let Some((binding, remaining)) = remaining.split_first() {
+                        let (var, elems) = pending(binding);
+                        let expr = &binding.expr;
+                        stack.push(Frame::Let { env: env.clone(), var, elems, remaining, body });
+                        Ok(State { redex: Redex::Decending { expr, env }, stack, heap })
+                    } else {
+                        Ok(State { redex: Redex::Decending { expr: body, env }, stack, heap })
+                    }
+                }
+                Frame::If { env, thn, els, .. } => {
+                    let n = match dv {
+                        DynValue::Value(n) => n,
+                        DynValue::Closure(Closure { name, .. }) => {
+                            Err(InterpErr::UnExpectedFun(name))?
+                        }
+                    };
+                    let Value::Bool(b) = n else { Err(InterpErr::AssertTypeFailed(Type::Bool))? };
+                    let expr = if b { thn } else { els };
+                    Ok(State { redex: Redex::Decending { expr, env }, stack, heap })
+                }
+                Frame::Switch { env, cases, default, .. } => {
+                    let expr = match dv {
+                        DynValue::Value(Value::Int(n)) => {
+                            match cases.iter().find(|case| case.label.node == n) {
+                                Some(case) => &case.body,
+                                None => default,
+                            }
+                        }
+                        // the cases all fit in 63 bits
+                        DynValue::Value(Value::Big(_)) => default,
+                        DynValue::Value(_) => Err(InterpErr::AssertTypeFailed(Type::Int))?,
+                        DynValue::Closure(Closure { name, .. }) => {
+                            Err(InterpErr::UnExpectedFun(name))?
+                        }
+                    };
+                    Ok(State { redex: Redex::Decending { expr, env }, stack, heap })
+                }
+                Frame::Match { mut env, arms, .. } => {
+                    let value = match dv {
+                        DynValue::Value(value) => value,
+                        DynValue::Closure(Closure { name, .. }) => {
+                            Err(InterpErr::UnExpectedFun(name))?
+                        }
+                    };
+                    for Arm { pattern, body } in arms {
+                        let mut bound = Vec::new();
+                        if matches(pattern, value.clone(), &heap, tags, &mut bound)? {
+                            for (var, value) in bound {
+                                env.insert(VarOrFun::Var(var), DynValue::Value(value));
+                            }
+                            let redex = Redex::Decending { expr: body, env };
+                            return Ok(State { redex, stack, heap });
+                        }
+                    }
+                    Err(InterpErr::InvalidArg(format!("no arm of the match accepts {}", value)))
+                }
+            }
+        }
+        fn run_prim(
+            prim: &Prim, args: Vec<DynValue<'p, Var, Fun>>, stack: Vec<Frame<'p, Var, Fun>>,
+            mut heap: Arena<Value>, services: &mut Services,
+        ) -> Result<Self, InterpErr<Var, Fun>> {
+            let args = args
+                .into_iter()
+                .map(|dv| match dv {
+                    DynValue::Value(n) => Ok(n),
+                    DynValue::Closure(Closure { name, .. }) => Err(InterpErr::UnExpectedFun(name)),
+                })
+                .collect::<Result<Vec<_>, InterpErr<Var, Fun>>>()?;
+            let bigint = services.bigint;
+            let mut env = PrimEnv { heap: &mut heap, services, bigint };
+            let o = PrimSemantics::of(prim).run(&mut env, args)?;
+            Ok(State { redex: Redex::Ascending(DynValue::Value(o)), stack, heap })
+        }
+        fn run_call(
+            fun: Fun, args: Vec<DynValue<'p, Var, Fun>>, loc: SrcLoc, env: Env<'p, Var, Fun>,
+            mut stack: Vec<Frame<'p, Var, Fun>>, heap: Arena<Value>, calls: &mut Calls<Fun>,
+        ) -> Result<Self, InterpErr<Var, Fun>> {
+            {
+                let dv = env
+                    .get(&VarOrFun::Fun(fun.clone()))
+                    .ok_or_else(|| InterpErr::UnboundFun(fun.clone()))?;
+                let Closure { env: clo_env, decls, name } = match dv {
+                    DynValue::Closure(closure) => closure,
+                    DynValue::Value(v) => Err(InterpErr::CallToConst(v.clone()))?,
+                };
+                let mut env = clo_env.clone();
+                for (name, _) in decls {
+                    env.insert(
+                        VarOrFun::Fun(name.clone()),
+                        DynValue::Closure(Closure {
+                            env: clo_env.clone(),
+                            decls: decls.clone(),
+                            name: name.clone(),
+                        }),
+                    );
+                }
+                let Some(FunDef { params, body }) = decls.get(name) else {
+                    unreachable!("no corresponding function in closure, error in our interpreter?!")
+                };
+                if args.len() != params.len() {
+                    Err(InterpErr::CallWrongArity {
+                        name: name.clone(),
+                        expected: params.len(),
+                        got: args.len(),
+                    })?
+                }
+                for (param, arg) in params.iter().zip(args) {
+                    env.insert(VarOrFun::Var(param.clone()), arg.clone());
+                }
+                match stack.last() {
+                    // a tail call takes the place of its caller, so that loops run in
+                    // constant space
+                    None | Some(Frame::Call) => {
+                        calls.frames.last_mut().expect("no frame of the caller").0 = name.clone();
+                    }
+                    Some(_) => {
+                        calls.frames.push((name.clone(), Some(loc)));
+                        stack.push(Frame::Call);
+                    }
+                }
+                Ok(State { redex: Redex::Decending { expr: body, env: env.clone() }, stack, heap })
+            }
+        }
+    }
+}
+
+/* ----------------------------------- SSA ---------------------------------- */
+
+pub mod ssa {
+    use super::*;
+    use crate::ssa::*;
+    use std::collections::HashMap;
+
+    struct StackEnv(Frame, Vec<Frame>);
+    impl StackEnv {
+        fn new() -> Self {
+            Self(Frame::new([]), Vec::new())
+        }
+        fn enter(&mut self) {
+            let frame = std::mem::replace(&mut self.0, Frame::new([]));
+            self.1.push(frame);
+        }
+        fn exit(&mut self) {
+            self.0 = self.1.pop().unwrap();
+        }
+        fn current(&mut self) -> &mut Frame {
+            &mut self.0
+        }
+    }
+    struct Frame(HashMap<VarName, (usize, Raw)>);
+    impl Frame {
+        fn new(param_assign: impl IntoIterator<Item = (VarName, Raw)>) -> Self {
+            Self(HashMap::from_iter(
+                param_assign.into_iter().enumerate().map(|(pos, (var, val))| (var, (pos, val))),
+            ))
+        }
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+        fn insert(&mut self, var: VarName, val: Raw) {
+            let pos = self.0.len();
+            self.0.insert(var, (pos, val));
+        }
+        fn get(&self, var: &VarName) -> Option<(usize, &Raw)> {
+            self.0.get(var).map(|(pos, val)| (*pos, val))
+        }
+        fn chop(&mut self, anchor: usize) {
+            self.0.retain(|_, (p, _)| *p < anchor);
+        }
+    }
+
+    #[derive(Clone)]
+    struct AnchorBlock<'p, Ana> {
+        /// the position on the stack indicating the start of the block
+        anchor: usize,
+        params: &'p [VarName],
+        body: &'p BlockBody<VarName, Ana>,
+    }
+
+    /// The SSA interpreter. It refers to the blocks of the program rather
+    /// than copy them, so that long blocks run in constant native stack.
+    pub struct Interp<'p, Ana> {
+        stack: StackEnv,
+        kont: Vec<(&'p VarName, &'p BlockBody<VarName, Ana>)>,
+        funs: HashMap<&'p FunName, &'p FunBlock<VarName>>,
+        blocks: HashMap<&'p BlockName, AnchorBlock<'p, Ana>>,
+        heap: Arena<Value>,
+        /// The encodings of the string literals, one per distinct literal
+        strings: HashMap<Arc<str>, Raw>,
+        services: Services,
+        /// The functions called and not returned from yet
+        calls: Vec<FunName>,
+        /// `None` before `start` and once a step failed
+        state: Option<State<'p, Ana>>,
+    }
+
+    /// Trampoline for the interpreter.
+    enum State<'p, Ana> {
+        Return(Raw),
+        Operation(&'p Operation<VarName>, &'p VarName, &'p BlockBody<VarName, Ana>),
+        OpReturn(Raw),
+        Call(FunName, Vec<Raw>),
+        Branch(Branch<VarName>),
+        BlockBody(&'p BlockBody<VarName, Ana>),
+        Terminator(&'p Terminator<VarName>),
+    }
+
+    impl<'p, Ana> Interp<'p, Ana>
+    where
+        Ana: Clone,
+    {
+        pub fn new() -> Self {
+            Self {
+                stack: StackEnv::new(),
+                kont: Vec::new(),
+                funs: HashMap::new(),
+                blocks: HashMap::new(),
+                heap: Arena::new(),
+                strings: HashMap::new(),
+                services: Services::from_env(),
+                calls: Vec::new(),
+                state: None,
+            }
+        }
+
+        pub fn with_services(self, services: Services) -> Self {
+            Self { services, ..self }
+        }
+        fn alloc(&mut self, var: VarName, val: Raw) {
+            let frame = self.stack.current();
+            frame.insert(var, val);
+        }
+
+        pub fn run<S>(
+            &mut self, prog: &'p Program<VarName, Ana>, args: impl IntoIterator<Item = S>,
+        ) -> Result<Value, InterpErr<VarName, FunName>>
+        where
+            S: Into<String>,
+        {
+            self.start(prog, args)?;
+            loop {
+                if let Status::Done(value) = self.resume(usize::MAX)? {
+                    break Ok(value);
+                }
+            }
+        }
+
+        /// Prepares to run `prog` on `args`; nothing runs before `resume`
+        pub fn start<S>(
+            &mut self, Program { externs, funs, blocks }: &'p Program<VarName, Ana>,
+            args: impl IntoIterator<Item = S>,
+        ) -> Result<(), InterpErr<VarName, FunName>>
+        where
+            S: Into<String>,
+        {
+            // Note: extern functions are not supported
+            let mut exts = externs
+                .iter()
+                .map(|Extern { name, .. }| (name.clone(), ()))
+                .collect::<HashMap<_, _>>();
+            exts.remove(&FunName::unmangled("snake_error"));
+            exts.remove(&FunName::unmangled("snake_new_array"));
+            for (runtime, ..) in RUNTIME_PRIMS {
+                exts.remove(&FunName::unmangled(*runtime));
+            }
+            for op in ["cmp", "eq"] {
+                exts.remove(&FunName::unmangled(format!("snake_big_{}", op)));
+            }
+            assert!(exts.is_empty(), "extern functions are not supported");
+
+            let args: Vec<Value> = args
+                .into_iter()
+                .map(Into::into)
+                .map(parse_snake_basic_val)
+                .collect::<Result<_, _>>()?;
+            self.funs.extend(funs.iter().map(|f| (&f.name, f)));
+            self.blocks.extend(blocks.iter().map(|BasicBlock { label, params, body, .. }| {
+                (label, AnchorBlock { anchor: 0, params, body })
+            }));
+            let arr = self.heap.alloc(args.len());
+            for (i, arg) in args.into_iter().enumerate() {
+                self.heap.set(arr, i + 1, arg);
+            }
+            let entry = FunName::unmangled("entry");
+            self.calls.push(entry.clone());
+            self.state = Some(self.run_call(&entry, vec![Raw::from(Value::FatPtr(arr))])?);
+            Ok(())
+        }
+
+        /// Runs at most `steps` more steps; once done, resuming again returns the same value.
+        ///
+        /// Panics if not started or if a previous step failed.
+        pub fn resume(&mut self, steps: usize) -> Result<Status, InterpErr<VarName, FunName>> {
+            let mut state = self.state.take().expect("resumed an interpreter that is not running");
+            for _ in 0..steps {
+                match state {
+                    State::Return(val) => match self.kont.pop() {
+                        Some((dest, next)) => {
+                            self.stack.exit();
+                            self.calls.pop();
+                            self.alloc(dest.clone(), val);
+                            state = State::BlockBody(next)
+                        }
+                        None => {
+                            self.state = Some(State::Return(val));
+                            return self.decode(val).map(Status::Done);
+                        }
+                    },
+                    State::OpReturn(val) => match self.kont.pop() {
+                        Some((dest, next)) => {
+                            self.alloc(dest.clone(), val);
+                            state = State::BlockBody(next)
+                        }
+                        None => {
+                            unreachable!("no return kont for operation, error in our interpreter?!")
+                        }
+                    },
+                    State::Operation(op, dest, next) => {
+                        self.kont.push((dest, next));
+                        state = self.run_operation(op)?
+                    }
+                    State::Call(fun, args) => {
+                        self.stack.enter();
+                        self.calls.push(fun.clone());
+                        state = self.run_call(&fun, args)?
+                    }
+                    State::Branch(branch) => state = self.run_branch(&branch)?,
+                    State::BlockBody(body) => state = self.run_block_body(body)?,
+                    State::Terminator(terminator) => state = self.run_terminator(terminator)?,
+                }
+            }
+            self.state = Some(state);
+            Ok(Status::Suspended)
+        }
+
+        /// The functions that were running when a step failed, most recent first;
+        /// the SSA keeps no source locations
+        pub fn backtrace(&self) -> Vec<(FunName, Option<SrcLoc>)> {
+            self.calls.iter().rev().map(|fun| (fun.clone(), None)).collect()
+        }
+
+        /// Run a function call. A frame is already entered before calling this.
+        fn run_call(
+            &mut self, fun: &FunName, args: Vec<Raw>,
+        ) -> Result<State<'p, Ana>, InterpErr<VarName, FunName>> {
+            if let FunName::Unmangled(f) = fun {
+                if let Some((_, prim, bigint)) = RUNTIME_PRIMS.iter().find(|(name, ..)| name == f) {
+                    let args =
+                        args.into_iter().map(|arg| self.decode(arg)).collect::<Result<_, _>>()?;
+                    let bigint = *bigint;
+                    let mut env =
+                        PrimEnv { heap: &mut self.heap, services: &mut self.services, bigint };
+                    let val = PrimSemantics::of(prim).run(&mut env, args)?;
+                    return Ok(State::Return(self.encode(val)));
+                }
+            }
+            match fun {
+                FunName::Unmangled(f) if f.starts_with("snake_big_") => self.run_big(f, args),
+                _ => {
+                    let FunBlock { name: _, params, body: branch } = self.funs[fun];
+                    for (param, arg) in params.iter().zip(args) {
+                        self.alloc(param.clone(), arg.clone());
+                    }
+                    Ok(State::Branch(branch.clone()))
+                }
+            }
+        }
+
+        /// The comparisons of big integers of the runtime under `--bigint`, which
+        /// are no primitives of their own
+        fn run_big(
+            &mut self, f: &str, args: Vec<Raw>,
+        ) -> Result<State<'p, Ana>, InterpErr<VarName, FunName>> {
+            let (a, b) = (self.decode(args[0])?, self.decode(args[1])?);
+            if f == "snake_big_eq" {
+                // equal values are either both small or both big
+                let eq = match (&a, &b) {
+                    (Value::Big(a), Value::Big(b)) => a == b,
+                    _ => args[0] == args[1],
+                };
+                return Ok(State::Return(Raw::from(Value::Bool(eq))));
+            }
+            let (a, b) = (Big::assert_into(a)?, Big::assert_into(b)?);
+            let val = match f {
+                "snake_big_cmp" => Value::Int(match a.cmp(&b) {
+                    Ordering::Less => -1,
+                    Ordering::Equal => 0,
+                    Ordering::Greater => 1,
+                }),
+                _ => Err(InterpErr::UnboundFun(FunName::unmangled(f)))?,
+            };
+            Ok(State::Return(self.encode(val)))
+        }
+
+        /// Reads a value from its encoding, following big integers and strings
+        /// to the heap
+        fn decode(&self, raw: Raw) -> Result<Value, InterpErr<VarName, FunName>> {
+            if raw.0 & BIG_TAG != BIG_TAG || raw.0 == NONE {
+                return raw.downcast();
+            }
+            match self.heap.get(ArenaPtr::from(Raw(raw.0 >> 3)), 1) {
+                boxed @ (Value::Big(_) | Value::Str(_)) => Ok(boxed.clone()),
+                _ => Err(InterpErr::InvalidEncoding(raw)),
+            }
+        }
+
+        /// Encodes a value, boxing big integers on the heap and equal strings
+        /// in the same cell
+        fn encode(&mut self, val: Value) -> Raw {
+            match val {
+                Value::Str(s) => {
+                    if let Some(raw) = self.strings.get(&s) {
+                        return *raw;
+                    }
+                    let ptr = self.heap.alloc(1);
+                    self.heap.set(ptr, 1, Value::Str(s.clone()));
+                    let raw = Raw((ptr.idx as i64) << 3 | BIG_TAG);
+                    self.strings.insert(s, raw);
+                    raw
+                }
+                Value::Big(_) => {
+                    let ptr = self.heap.alloc(1);
+                    self.heap.set(ptr, 1, val);
+                    Raw((ptr.idx as i64) << 3 | BIG_TAG)
+                }
+                _ => Raw::from(val),
+            }
+        }
+
+        fn run_branch(
+            &mut self, Branch { target, args }: &Branch<VarName>,
+        ) -> Result<State<'p, Ana>, InterpErr<VarName, FunName>> {
+            let args =
+                args.iter().map(|imm| self.run_immediate(imm)).collect::<Result<Vec<_>, _>>()?;
+            let AnchorBlock { anchor, params, body } = self.blocks[target].clone();
+            self.stack.current().chop(anchor);
+            for (param, arg) in params.iter().zip(args) {
+                self.alloc(param.clone(), arg.clone());
+            }
+            Ok(State::BlockBody(body))
+        }
+        fn run_block_body(
+            &mut self, block: &'p BlockBody<VarName, Ana>,
+        ) -> Result<State<'p, Ana>, InterpErr<VarName, FunName>> {
+            match block {
+                BlockBody::Terminator(terminator, ..) => Ok(State::Terminator(terminator)),
+                BlockBody::Operation { dest, op, next, .. } => Ok(State::Operation(op, dest, next)),
+                BlockBody::SubBlocks { blocks, next, .. } => {
+                    let anchor = self.stack.current().len();
+                    self.blocks.extend(blocks.iter().map(
+                        |BasicBlock { label, params, body, .. }| {
+                            (label, AnchorBlock { anchor, params, body })
+                        },
+                    ));
+                    Ok(State::BlockBody(next))
+                }
+                BlockBody::AssertType { ty, arg: of, next, .. } => {
+                    let Raw(n) = self.run_immediate(of)?;
+                    if n & ty.mask() != ty.tag() {
+                        Err(InterpErr::AssertTypeFailed(ty.clone()))?
+                    }
+                    Ok(State::BlockBody(next))
+                }
+                BlockBody::AssertLength { len, next, .. } => {
+                    let Raw(n) = self.run_immediate(len)?;
+                    if n < 0 {
+                        Err(InterpErr::AssertLength)?
+                    }
+                    Ok(State::BlockBody(next))
+                }
+                BlockBody::AssertInBounds { bound, arg: of, next, .. } => {
+                    let Raw(bound) = self.run_immediate(bound)?;
+                    let Raw(of) = self.run_immediate(of)?;
+                    if of < 0 || bound <= of {
+                        Err(InterpErr::AssertInBoundsFailed { bound, of })?
+                    }
+                    Ok(State::BlockBody(next))
+                }
+                BlockBody::Store { addr, offset: off, val, next, .. } => {
+                    let ptr = ArenaPtr::from(Raw(self.run_immediate(addr)?.0 >> 3));
+                    let idx = i64::from(self.run_immediate(off)?);
+                    let val = self.run_immediate(val)?;
+                    let val = self.decode(val)?;
+                    self.heap.set(ptr, idx as usize, val);
+                    Ok(State::BlockBody(next))
+                }
+            }
+        }
+
+        fn run_terminator(
+            &mut self, terminator: &Terminator<VarName>,
+        ) -> Result<State<'p, Ana>, InterpErr<VarName, FunName>> {
+            match terminator {
+                Terminator::Return(imm) => Ok(State::Return(self.run_immediate(imm)?)),
+                Terminator::Branch(br) => Ok(State::Branch(br.clone())),
+                Terminator::ConditionalBranch { cond, thn, els } => {
+                    let Raw(n) = self.run_immediate(cond)?;
+                    if n != 0 {
+                        Ok(State::Branch(Branch { target: thn.clone(), args: Vec::new() }))
+                    } else {
+                        Ok(State::Branch(Branch { target: els.clone(), args: Vec::new() }))
+                    }
+                }
+                Terminator::Switch { scrutinee, low, targets, default } => {
+                    let Raw(n) = self.run_immediate(scrutinee)?;
+                    let target = (n.checked_sub(*low))
+                        .and_then(|index| usize::try_from(index).ok())
+                        .and_then(|index| targets.get(index))
+                        .unwrap_or(default);
+                    Ok(State::Branch(Branch { target: target.clone(), args: Vec::new() }))
+                }
+            }
+        }
+
+        fn run_operation(
+            &mut self, op: &Operation<VarName>,
+        ) -> Result<State<'p, Ana>, InterpErr<VarName, FunName>> {
+            match op {
+                Operation::Immediate(imm) => Ok(State::OpReturn(self.run_immediate(imm)?)),
+                Operation::Prim1(prim, imm) => {
+                    let Raw(n) = self.run_immediate(imm)?;
+                    let o = match prim {
+                        Prim1::BitNot => Raw(!n),
+                        Prim1::BitSal(m) => Raw(n << m),
+                        Prim1::BitSar(m) => Raw(n >> m),
+                        Prim1::BitShl(m) => Raw(n << m),
+                        Prim1::BitShr(m) => Raw(n >> m),
+                    };
+                    Ok(State::OpReturn(o))
+                }
+                Operation::Prim2(prim, imm1, imm2) => {
+                    let Raw(n) = self.run_immediate(imm1)?;
+                    let Raw(m) = self.run_immediate(imm2)?;
+                    let o = match prim {
+                        Prim2::Add => n.checked_add(m).ok_or(InterpErr::Overflow)?,
+                        Prim2::Sub => n.checked_sub(m).ok_or(InterpErr::Overflow)?,
+                        Prim2::Mul => n.checked_mul(m).ok_or(InterpErr::Overflow)?,
+                        Prim2::WrappingAdd
+                        | Prim2::WrappingSub
+                        | Prim2::WrappingMul
+                        | Prim2::SaturatingAdd
+                        | Prim2::SaturatingSub
+                        | Prim2::SaturatingMul => tagged_arith(prim, n, m),
+                        Prim2::BitAnd => n & m,
+                        Prim2::BitOr => n | m,
+                        Prim2::BitXor => n ^ m,
+                        Prim2::Lt => (if n < m { 1 } else { 0 }).clone(),
+                        Prim2::Le => (if n <= m { 1 } else { 0 }).clone(),
+                        Prim2::Gt => (if n > m { 1 } else { 0 }).clone(),
+                        Prim2::Ge => (if n >= m { 1 } else { 0 }).clone(),
+                        Prim2::Eq => (if n == m { 1 } else { 0 }).clone(),
+                        Prim2::Neq => (if n != m { 1 } else { 0 }).clone(),
+                    };
+                    Ok(State::OpReturn(Raw(o)))
+                }
+                Operation::Call { fun, args } => {
+                    let args = args
+                        .iter()
+                        .map(|imm| self.run_immediate(imm))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(State::Call(fun.clone(), args))
+                }
+                Operation::AllocateArray { len } => {
+                    let Raw(len) = self.run_immediate(len)?;
+                    if len as usize >= HEAP_WORDS {
+                        Err(InterpErr::OutOfMemory)?
+                    }
+                    let arr = self.heap.alloc(len as usize);
+                    Ok(State::OpReturn(Raw((arr.idx as i64) << 3)))
+                }
+                Operation::Load { addr, offset: off } => {
+                    let ptr = ArenaPtr::from(Raw(self.run_immediate(addr)?.0 >> 3));
+                    let off = i64::from(self.run_immediate(off)?);
+                    let val = self.heap.get(ptr, off as usize).clone();
+                    Ok(State::OpReturn(self.encode(val)))
+                }
+                Operation::Str(s) => {
+                    Ok(State::OpReturn(self.encode(Value::Str(s.as_str().into()))))
+                }
+            }
+        }
+
+        fn run_immediate(
+            &mut self, imm: &Immediate<VarName>,
+        ) -> Result<Raw, InterpErr<VarName, FunName>> {
+            match imm {
+                Immediate::Var(v) => {
+                    let (_, val) =
+                        self.stack.current().get(v).ok_or(InterpErr::UnboundVar(v.clone()))?;
+                    Ok(val.clone())
+                }
+                Immediate::Const(n) => Ok(Raw(*n)),
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Wadler-style pretty printing.
+//!
+//! A [`Layout`] is a document with optional line breaks: each group is laid
+//! out on one line if what follows up to the next line break fits in the
+//! width, and otherwise breaks at each of its own lines. The printers of the
+//! surface AST and of SSA build layouts in `pretty`.
+
+use std::fmt;
+
+/// The width of `--width` if not given.
+pub const DEFAULT_WIDTH: usize = 80;
+
+#[derive(Clone, Debug)]
+pub enum Layout {
+    /// Text without line breaks
+    Text(String),
+    /// A line break, or the text when the enclosing group is on one line
+    Line(&'static str),
+    /// A line break that breaks all the enclosing groups
+    HardLine,
+    /// Indents the lines after the breaks within
+    Nest(usize, Box<Layout>),
+    /// On one line if it fits
+    Group(Box<Layout>),
+    Concat(Vec<Layout>),
+}
+
+impl Layout {
+    pub fn text(s: impl Into<String>) -> Self {
+        Layout::Text(s.into())
+    }
+
+    /// A line break, or a space on one line
+    pub fn line() -> Self {
+        Layout::Line(" ")
+    }
+
+    /// A line break, or nothing on one line
+    pub fn softline() -> Self {
+        Layout::Line("")
+    }
+
+    pub fn nest(self, indent: usize) -> Self {
+        Layout::Nest(indent, Box::new(self))
+    }
+
+    pub fn group(self) -> Self {
+        Layout::Group(Box::new(self))
+    }
+
+    pub fn concat(layouts: impl IntoIterator<Item = Layout>) -> Self {
+        Layout::Concat(layouts.into_iter().collect())
+    }
+
+    /// The layouts with `sep` between each two
+    pub fn join(layouts: impl IntoIterator<Item = Layout>, sep: Layout) -> Self {
+        let mut all = Vec::new();
+        for (i, layout) in layouts.into_iter().enumerate() {
+            if i > 0 {
+                all.push(sep.clone());
+            }
+            all.push(layout);
+        }
+        Layout::Concat(all)
+    }
+
+    /// `open`, the comma-separated `items` and `close`; if they do not fit on
+    /// one line, each item is on its own indented line
+    pub fn delimited(
+        open: &str, items: impl IntoIterator<Item = Layout>, close: &str, indent: usize,
+    ) -> Self {
+        let items = Layout::join(items, Layout::text(",") + Layout::line());
+        (Layout::text(open)
+            + (Layout::softline() + items).nest(indent)
+            + Layout::softline()
+            + Layout::text(close))
+        .group()
+    }
+
+    /// Lays out `self` in `width` columns, without trailing spaces
+    pub fn render(&self, width: usize) -> String {
+        let mut out = String::new();
+        let mut col = 0;
+        // the indentation of the current line, written before its first text
+        let mut pending = 0;
+        let mut stack = vec![(0, false, self)];
+        while let Some((indent, flat, layout)) = stack.pop() {
+            let text = match layout {
+                Layout::Text(s) => s.as_str(),
+                Layout::Line(s) if flat => s,
+                Layout::Line(_) | Layout::HardLine => {
+                    out.push('\n');
+                    col = indent;
+                    pending = indent;
+                    continue;
+                }
+                Layout::Nest(more, layout) => {
+                    stack.push((indent + more, flat, layout));
+                    continue;
+                }
+                Layout::Group(layout) => {
+                    let flat = flat || fits(width as isize - col as isize, layout, &stack);
+                    stack.push((indent, flat, layout));
+                    continue;
+                }
+                Layout::Concat(layouts) => {
+                    stack.extend(layouts.iter().rev().map(|layout| (indent, flat, layout)));
+                    continue;
+                }
+            };
+            if !text.is_empty() {
+                out.extend(std::iter::repeat_n(' ', pending));
+                pending = 0;
+                out.push_str(text);
+                col += text.chars().count();
+            }
+        }
+        out
+    }
+}
+
+/// Whether `layout` on one line and then `rest` up to its next line break
+/// fit in `room` columns
+fn fits(mut room: isize, layout: &Layout, rest: &[(usize, bool, &Layout)]) -> bool {
+    let mut rest = rest.iter().rev();
+    let mut stack = vec![(true, layout)];
+    loop {
+        let (flat, layout) = match stack.pop() {
+            Some(next) => next,
+            None => match rest.next() {
+                Some(&(_, flat, layout)) => (flat, layout),
+                None => return true,
+            },
+        };
+        match layout {
+            Layout::Text(s) => room -= s.chars().count() as isize,
+            Layout::Line(s) if flat => room -= s.len() as isize,
+            // the line break ends the line that has to fit
+            Layout::Line(_) => return room >= 0,
+            Layout::HardLine => return room >= 0 && !flat,
+            Layout::Nest(_, layout) => stack.push((flat, layout)),
+            Layout::Group(layout) => stack.push((flat, layout)),
+            Layout::Concat(layouts) => stack.extend(layouts.iter().rev().map(|l| (flat, l))),
+        }
+        if room < 0 {
+            return false;
+        }
+    }
+}
+
+impl std::ops::Add for Layout {
+    type Output = Layout;
+
+    fn add(self, other: Layout) -> Layout {
+        match self {
+            Layout::Concat(mut layouts) => {
+                layouts.push(other);
+                Layout::Concat(layouts)
+            }
+            layout => Layout::Concat(vec![layout, other]),
+        }
+    }
+}
+
+/// Values that can be laid out in any width.
+pub trait Pretty {
+    fn layout(&self) -> Layout;
+}
+
+/// Displays the layout of `T` in the given width.
+pub struct Width<'a, T: ?Sized>(pub usize, pub &'a T);
+
+impl<T: Pretty + ?Sized> fmt::Display for Width<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.1.layout().render(self.0))
+    }
+}
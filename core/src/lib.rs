@@ -0,0 +1,49 @@
+//! The compiler passes, the interpreters and the analyses of snake, without
+//! the filesystem or processes: linking and running executables is up to
+//! the `snake-cli` crate.
+//!
+//! The features pull in what is not needed to compile a program: `clap`
+//! derives the parsers of the enums that `snake` takes as arguments, `svg`
+//! writes graphs to files, `wasm` exports `playground` to JavaScript, and
+//! `fuzz` exposes the entry points of the fuzz targets.
+
+/* ----------------------------- Compiler Passes ---------------------------- */
+pub mod txt;
+pub mod frontend;
+pub mod generators;
+pub mod patterns;
+pub mod infer;
+pub mod ast;
+pub mod consteval;
+pub mod middle_end;
+pub mod ssa;
+pub mod backend;
+pub mod asm;
+pub mod encode;
+pub mod compile;
+pub mod query;
+pub mod parser;
+
+/* -------------------------------- Utilities ------------------------------- */
+pub mod identifiers;
+pub mod types;
+pub mod span;
+pub mod nodes;
+pub mod pretty;
+pub mod layout;
+pub mod interp;
+pub mod ana;
+pub mod conf;
+pub mod error;
+pub mod doc;
+pub mod dump;
+pub mod bindgen;
+pub mod lint;
+pub mod remarks;
+pub mod scopes;
+pub mod symbolic;
+pub mod playground;
+pub mod stress;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod bignum;
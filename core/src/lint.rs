@@ -0,0 +1,403 @@
+//! Lints of a resolved program for `snake lint`.
+//!
+//! The rules share one walk over the AST, which keeps track of the
+//! variables whose values are known: those bound by `let` to a constant
+//! expression, and, while looking for a missing base case, the parameters
+//! of a call with constant arguments. Variables are never reassigned, so a
+//! known value holds wherever the variable is in scope.
+
+use crate::ast::*;
+use crate::identifiers::*;
+use crate::txt::FileInfo;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Rule {
+    /// A variable bound by `let` or by a pattern that is never used
+    UnusedBinding,
+    /// An `if` or a `switch` on a value known at compile time
+    ConstantCondition,
+    /// A constant index outside of an array of known length
+    IndexOutOfBounds,
+    /// A function that calls itself on every path, either for any arguments
+    /// or for the constant arguments of a call
+    NoBaseCase,
+}
+
+impl Rule {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::UnusedBinding => "unused-binding",
+            Rule::ConstantCondition => "constant-condition",
+            Rule::IndexOutOfBounds => "index-out-of-bounds",
+            Rule::NoBaseCase => "no-base-case",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub message: String,
+    pub loc: SrcLoc,
+}
+
+impl Diagnostic {
+    /// e.g. `warning[unused-binding]: variable "x" is never used: 2:6-2:7`
+    pub fn report(&self, file_info: &FileInfo, denied: bool) -> String {
+        let level = if denied { "error" } else { "warning" };
+        let span = file_info.span1_to_span2(self.loc);
+        format!("{}[{}]: {}: {}", level, self.rule.id(), self.message, span)
+    }
+}
+
+/// What is known about the value of an expression
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+enum Value {
+    Int(i64),
+    Bool(bool),
+    /// an array of the given length
+    Array(usize),
+}
+
+type Env = im::HashMap<VarName, Value>;
+
+/// Runs all the rules on `prog`; the diagnostics are sorted by location.
+pub fn lint(prog: &BoundProg) -> Vec<Diagnostic> {
+    let mut linter = Linter::default();
+    linter.walk(&prog.body, &Env::new());
+    for Spanned { node: var, loc } in linter.bound.iter() {
+        if !linter.used.contains(var) && !var.hint().starts_with('_') {
+            linter.diagnostics.push(Diagnostic {
+                rule: Rule::UnusedBinding,
+                message: format!("variable \"{}\" is never used", var.hint()),
+                loc: *loc,
+            });
+        }
+    }
+    let mut diagnostics = linter.diagnostics;
+    diagnostics.sort_by_key(|d| (d.loc.start_ix, d.loc.end_ix, d.rule));
+    diagnostics
+}
+
+#[derive(Default)]
+struct Linter<'a> {
+    diagnostics: Vec<Diagnostic>,
+    /// the variables bound by `let` and by patterns
+    bound: Vec<Spanned<VarName>>,
+    used: HashSet<VarName>,
+    /// the functions in scope, with the known values at their definition
+    decls: HashMap<FunName, (&'a BoundFunDecl, Env)>,
+    /// the calls with constant arguments already checked for a base case
+    checked: HashSet<(FunName, Vec<Value>)>,
+}
+
+impl<'a> Linter<'a> {
+    /// Records the variables of `pattern` as bound
+    fn bind(&mut self, pattern: &BoundPattern) {
+        match pattern {
+            Pattern::Wildcard(_) => {}
+            Pattern::Var(var, loc) => self.bound.push(Spanned::new(var.clone(), *loc)),
+            Pattern::Ctor { args, .. } => args.iter().for_each(|arg| self.bind(arg)),
+        }
+    }
+
+    /// Walks `e`, returning what is known about its value
+    fn walk(&mut self, e: &'a BoundExpr, env: &Env) -> Option<Value> {
+        match e {
+            Expr::Num(n, _) => Some(Value::Int(*n)),
+            Expr::BigNum(..) => None,
+            Expr::Bool(b, _) => Some(Value::Bool(*b)),
+            Expr::Var(x, _) => {
+                self.used.insert(x.clone());
+                env.get(x).copied()
+            }
+            Expr::Prim { prim, args, loc } => {
+                let args: Vec<_> = args.iter().map(|arg| self.walk(arg, env)).collect();
+                if let (
+                    Prim::ArrayGet | Prim::ArraySet,
+                    [Some(Value::Array(len)), Some(Value::Int(i)), ..],
+                ) = (prim, args.as_slice())
+                {
+                    if *i < 0 || *i as usize >= *len {
+                        self.diagnostics.push(Diagnostic {
+                            rule: Rule::IndexOutOfBounds,
+                            message: format!(
+                                "index {} is out of bounds of an array of length {}",
+                                i, len
+                            ),
+                            loc: *loc,
+                        });
+                    }
+                }
+                eval_prim(prim, &args)
+            }
+            Expr::Let { bindings, body, .. } => {
+                let mut env = env.clone();
+                for Binding { var, expr, elems, .. } in bindings {
+                    let x = &var.node;
+                    let value = self.walk(expr, &env);
+                    if let Some(elems) = elems {
+                        // the array is only named by the compiler, its elements by the user
+                        self.bound.extend(elems.iter().cloned());
+                        if let Some(Value::Array(len)) = value {
+                            if len < elems.len() {
+                                self.diagnostics.push(Diagnostic {
+                                    rule: Rule::IndexOutOfBounds,
+                                    message: format!(
+                                        "destructures {} elements of an array of length {}",
+                                        elems.len(),
+                                        len
+                                    ),
+                                    loc: var.loc,
+                                });
+                            }
+                        }
+                    } else {
+                        self.bound.push(var.clone());
+                    }
+                    if let Some(value) = value {
+                        env.insert(x.clone(), value);
+                    }
+                }
+                self.walk(body, &env)
+            }
+            Expr::If { cond, thn, els, loc } => {
+                let cond = self.walk(cond, env);
+                if let Some(Value::Bool(b)) = cond {
+                    self.diagnostics.push(Diagnostic {
+                        rule: Rule::ConstantCondition,
+                        message: format!("condition is always {}", b),
+                        loc: *loc,
+                    });
+                }
+                let thn = self.walk(thn, env);
+                let els = self.walk(els, env);
+                match cond {
+                    Some(Value::Bool(true)) => thn,
+                    Some(Value::Bool(false)) => els,
+                    _ => None,
+                }
+            }
+            Expr::Switch { scrutinee, cases, default, loc } => {
+                if let Some(Value::Int(n)) = self.walk(scrutinee, env) {
+                    self.diagnostics.push(Diagnostic {
+                        rule: Rule::ConstantCondition,
+                        message: format!("switch on a value that is always {}", n),
+                        loc: *loc,
+                    });
+                }
+                for case in cases {
+                    self.walk(&case.body, env);
+                }
+                self.walk(default, env);
+                None
+            }
+            Expr::Match { scrutinee, arms, .. } => {
+                self.walk(scrutinee, env);
+                for Arm { pattern, body } in arms {
+                    self.bind(pattern);
+                    self.walk(body, env);
+                }
+                None
+            }
+            Expr::FunDefs { decls, body, .. } => {
+                for decl in decls {
+                    self.decls.insert(decl.name.clone(), (decl, env.clone()));
+                }
+                for decl in decls {
+                    if always_calls(&decl.body, &decl.name, None, env) {
+                        self.diagnostics.push(Diagnostic {
+                            rule: Rule::NoBaseCase,
+                            message: format!(
+                                "function \"{}\" calls itself on every path",
+                                decl.name.hint()
+                            ),
+                            loc: decl.loc,
+                        });
+                    }
+                    self.walk(&decl.body, env);
+                }
+                self.walk(body, env)
+            }
+            Expr::Call { fun, args, loc } => {
+                // every argument is walked, even after one of unknown value
+                let args: Vec<_> = args.iter().map(|arg| self.walk(arg, env)).collect();
+                let args = args.into_iter().collect::<Option<Vec<_>>>()?;
+                let (decl, decl_env) = self.decls.get(fun).cloned()?;
+                if !self.checked.insert((fun.clone(), args.clone())) {
+                    return None;
+                }
+                let mut body_env = decl_env.clone();
+                for (param, value) in decl.params.iter().zip(args.iter()) {
+                    body_env.insert(param.node.clone(), *value);
+                }
+                // calling itself on every path with the same arguments never ends;
+                // the functions that call themselves for any arguments are reported
+                // at their definition
+                if !always_calls(&decl.body, fun, None, &decl_env)
+                    && always_calls(&decl.body, fun, Some(&args), &body_env)
+                {
+                    let args: Vec<_> = args.iter().map(|value| value.to_string()).collect();
+                    self.diagnostics.push(Diagnostic {
+                        rule: Rule::NoBaseCase,
+                        message: format!(
+                            "function \"{}\" calls itself on every path for ({})",
+                            fun.hint(),
+                            args.join(", ")
+                        ),
+                        loc: *loc,
+                    });
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Whether every evaluation of `e` calls `fun`, with exactly the arguments
+/// `args` if they are given
+fn always_calls(e: &BoundExpr, fun: &FunName, args: Option<&[Value]>, env: &Env) -> bool {
+    match e {
+        Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => false,
+        Expr::Prim { args: prim_args, .. } => {
+            prim_args.iter().any(|arg| always_calls(arg, fun, args, env))
+        }
+        Expr::Let { bindings, body, .. } => {
+            let mut env = env.clone();
+            for Binding { var: Spanned { node: x, .. }, expr, .. } in bindings {
+                if always_calls(expr, fun, args, &env) {
+                    return true;
+                }
+                match value(expr, &env) {
+                    Some(value) => env.insert(x.clone(), value),
+                    None => env.remove(x),
+                };
+            }
+            always_calls(body, fun, args, &env)
+        }
+        Expr::If { cond, thn, els, .. } => {
+            always_calls(cond, fun, args, env)
+                || match value(cond, env) {
+                    Some(Value::Bool(true)) => always_calls(thn, fun, args, env),
+                    Some(Value::Bool(false)) => always_calls(els, fun, args, env),
+                    _ => always_calls(thn, fun, args, env) && always_calls(els, fun, args, env),
+                }
+        }
+        Expr::Switch { scrutinee, cases, default, .. } => {
+            always_calls(scrutinee, fun, args, env)
+                || match value(scrutinee, env) {
+                    Some(Value::Int(n)) => {
+                        let case = cases.iter().find(|case| case.label.node == n);
+                        always_calls(case.map_or(&**default, |case| &case.body), fun, args, env)
+                    }
+                    _ => {
+                        cases.iter().all(|case| always_calls(&case.body, fun, args, env))
+                            && always_calls(default, fun, args, env)
+                    }
+                }
+        }
+        Expr::Match { scrutinee, arms, .. } => {
+            always_calls(scrutinee, fun, args, env)
+                || arms.iter().all(|arm| always_calls(&arm.body, fun, args, env))
+        }
+        // the definitions are not evaluated, only the body
+        Expr::FunDefs { body, .. } => always_calls(body, fun, args, env),
+        Expr::Call { fun: callee, args: call_args, .. } => {
+            call_args.iter().any(|arg| always_calls(arg, fun, args, env))
+                || callee == fun
+                    && args.is_none_or(|args| {
+                        let values: Option<Vec<_>> =
+                            call_args.iter().map(|arg| value(arg, env)).collect();
+                        values.as_deref() == Some(args)
+                    })
+        }
+    }
+}
+
+/// What is known about the value of `e`, without calling any function
+fn value(e: &BoundExpr, env: &Env) -> Option<Value> {
+    match e {
+        Expr::Num(n, _) => Some(Value::Int(*n)),
+        Expr::Bool(b, _) => Some(Value::Bool(*b)),
+        Expr::Var(x, _) => env.get(x).copied(),
+        Expr::Prim { prim, args, .. } => {
+            let args: Vec<_> = args.iter().map(|arg| value(arg, env)).collect();
+            eval_prim(prim, &args)
+        }
+        Expr::Let { bindings, body, .. } => {
+            let mut env = env.clone();
+            for Binding { var: Spanned { node: x, .. }, expr, .. } in bindings {
+                match value(expr, &env) {
+                    Some(value) => env.insert(x.clone(), value),
+                    None => env.remove(x),
+                };
+            }
+            value(body, &env)
+        }
+        Expr::If { cond, thn, els, .. } => match value(cond, env) {
+            Some(Value::Bool(true)) => value(thn, env),
+            Some(Value::Bool(false)) => value(els, env),
+            _ => None,
+        },
+        Expr::BigNum(..)
+        | Expr::Switch { .. }
+        | Expr::Match { .. }
+        | Expr::FunDefs { .. }
+        | Expr::Call { .. } => None,
+    }
+}
+
+/// Whether `n` fits in the 63 bits of a snake integer
+fn small(n: i64) -> Option<Value> {
+    ((i64::MIN >> 1)..=(i64::MAX >> 1)).contains(&n).then_some(Value::Int(n))
+}
+
+fn eval_prim(prim: &Prim, args: &[Option<Value>]) -> Option<Value> {
+    use Value::*;
+    match (prim, args) {
+        (Prim::Add1, [Some(Int(n))]) => small(n.checked_add(1)?),
+        (Prim::Sub1, [Some(Int(n))]) => small(n.checked_sub(1)?),
+        (Prim::Add, [Some(Int(n)), Some(Int(m))]) => small(n.checked_add(*m)?),
+        (Prim::Sub, [Some(Int(n)), Some(Int(m))]) => small(n.checked_sub(*m)?),
+        (Prim::Mul, [Some(Int(n)), Some(Int(m))]) => small(n.checked_mul(*m)?),
+        (Prim::Not, [Some(Bool(b))]) => Some(Bool(!b)),
+        (Prim::And, [Some(Bool(a)), Some(Bool(b))]) => Some(Bool(*a && *b)),
+        (Prim::Or, [Some(Bool(a)), Some(Bool(b))]) => Some(Bool(*a || *b)),
+        (Prim::Lt, [Some(Int(n)), Some(Int(m))]) => Some(Bool(n < m)),
+        (Prim::Le, [Some(Int(n)), Some(Int(m))]) => Some(Bool(n <= m)),
+        (Prim::Gt, [Some(Int(n)), Some(Int(m))]) => Some(Bool(n > m)),
+        (Prim::Ge, [Some(Int(n)), Some(Int(m))]) => Some(Bool(n >= m)),
+        (Prim::Eq, [Some(Int(n)), Some(Int(m))]) => Some(Bool(n == m)),
+        (Prim::Eq, [Some(Bool(a)), Some(Bool(b))]) => Some(Bool(a == b)),
+        (Prim::Neq, [Some(Int(n)), Some(Int(m))]) => Some(Bool(n != m)),
+        (Prim::Neq, [Some(Bool(a)), Some(Bool(b))]) => Some(Bool(a != b)),
+        (Prim::IsType(ty), [Some(value)]) => Some(Bool(*ty == value.ty())),
+        (Prim::Check(ty), [Some(value)]) if *ty == value.ty() => Some(*value),
+        (Prim::MakeArray, elems) => Some(Array(elems.len())),
+        (Prim::NewArray, [Some(Int(n))]) => Some(Array(usize::try_from(*n).ok()?)),
+        (Prim::Length, [Some(Array(len))]) => small(i64::try_from(*len).ok()?),
+        _ => None,
+    }
+}
+
+impl Value {
+    fn ty(&self) -> Type {
+        match self {
+            Value::Int(_) => Type::Int,
+            Value::Bool(_) => Type::Bool,
+            Value::Array(_) => Type::Array,
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Array(len) => write!(f, "an array of length {}", len),
+        }
+    }
+}
@@ -0,0 +1,7325 @@
+//! The middle "end" of our compiler is the part that transforms our well-formed
+//! source-language abstract syntax tree (AST) into the intermediate representation
+//! As well as performs some SSA to SSA optimizations
+
+use crate::ana::{
+    Fact, Facts, Induction, InductionVars, Memory, MemoryAccess, MemoryDef, MemorySsa, Nil,
+};
+use crate::ast::{self, *};
+use crate::conf::Optimization;
+use crate::frontend::{Annotations, Resolver};
+use crate::identifiers::*;
+use crate::patterns::Enums;
+use crate::remarks::{self, Remark};
+use crate::ssa::{self, *};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::rc::Rc;
+
+pub struct Lowerer {
+    /// The names of the program, shared with the resolver and with the passes
+    /// that run after lowering
+    pub names: NameSupply,
+    /// The live variables at the start of each function.
+    fun_scopes: HashMap<FunName, Vec<VarName>>,
+    /// The functions that should be lambda lifted.
+    should_lift: HashSet<FunName>,
+    /// The name of the basic block generated for each function.
+    fun_as_block: HashMap<FunName, BlockName>,
+    /// Lifted functions. Removed after the lowering pass.
+    lifted_funs: Vec<(FunBlock<VarName>, BasicBlock<VarName, Nil>)>,
+    /// The runtime functions called by builtins, with their arity.
+    /// Declared as externs after the lowering pass.
+    runtime_calls: BTreeMap<&'static str, usize>,
+    /// The functions that marshal the arguments and the result of each
+    /// typed extern, which its calls go through instead.
+    shims: HashMap<FunName, FunName>,
+    /// Whether integer arithmetic goes through the runtime, which promotes
+    /// overflowing results to big integers.
+    bigint: bool,
+    /// Whether the program makes strings at run time, which the runtime
+    /// interns by their contents, so that the literals go through it too.
+    makes_strings: bool,
+    /// What the annotations prove, as the resolver checked them.
+    annotations: Annotations,
+    /// The arguments of primitives whose types the annotations prove, which
+    /// the primitives need not assert.
+    proven: HashMap<VarName, Type>,
+    /// The arguments of the checks that the resolver inserted where untyped
+    /// values flow into typed positions.
+    checks: HashSet<VarName>,
+}
+
+/// Switches with at least this many cases, which cover at least half of the
+/// range between their smallest and largest case, dispatch through a jump
+/// table rather than a tree of comparisons.
+const JUMP_TABLE_MIN_CASES: usize = 4;
+
+/// A helper struct for variable renaming.
+#[derive(Clone)]
+struct Substitution {
+    /// The map of old variables to new variables.
+    rename_map: HashMap<VarName, VarName>,
+}
+impl Substitution {
+    fn new() -> Self {
+        Substitution {
+            rename_map: HashMap::new(),
+        }
+    }
+    fn insert(&mut self, old: VarName, new: VarName) {
+        self.rename_map.insert(old, new);
+    }
+    fn run(&self, mut var: VarName) -> VarName {
+        while let Some(new) = self.rename_map.get(&var) {
+            var = new.to_owned();
+        }
+        var
+    }
+}
+
+/// Indicates whether the expression being compiled is in a tail position.
+#[derive(Clone)]
+enum Continuation {
+    Return,
+    Block(VarName, BlockBody<VarName, Nil>),
+}
+
+impl Continuation {
+    fn invoke(self, imm: Immediate<VarName>) -> BlockBody<VarName, Nil> {
+        match self {
+            Continuation::Return => BlockBody::Terminator(Terminator::Return(imm), Nil),
+            Continuation::Block(dest, b) => BlockBody::Operation {
+                dest,
+                op: Operation::Immediate(imm),
+                next: Box::new(b),
+                ana: Nil,
+            },
+        }
+    }
+}
+
+/// The live variables of an expression, a prefix of those of the
+/// expression it is in, shared rather than copied for each subexpression.
+#[derive(Clone)]
+struct Live {
+    vars: Rc<[VarName]>,
+    len: usize,
+}
+
+impl Live {
+    fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+}
+
+impl From<Vec<VarName>> for Live {
+    fn from(vars: Vec<VarName>) -> Self {
+        let len = vars.len();
+        Live {
+            vars: vars.into(),
+            len,
+        }
+    }
+}
+
+impl std::ops::Deref for Live {
+    type Target = [VarName];
+    fn deref(&self) -> &[VarName] {
+        &self.vars[..self.len]
+    }
+}
+
+/// A step of `Lowerer::lower_expr_kont`, on its work stack. The steps after
+/// the first pop the blocks of the subexpressions that the steps pushed
+/// after them lowered.
+enum Lowering {
+    /// Lowers `e` with the continuation `k`.
+    Expr {
+        e: BoundExpr,
+        live: Live,
+        subst: Rc<Substitution>,
+        k: Continuation,
+    },
+    /// Lowers each operand with the block of the next one as its
+    /// continuation, from the last, which continues with the lowered block.
+    Operands {
+        operands: Vec<(BoundExpr, VarName)>,
+        live: Live,
+        subst: Rc<Substitution>,
+    },
+    /// Binds each variable to its expression before the lowered block,
+    /// from the last, each in the scope of the variables before it.
+    Bindings {
+        bindings: Vec<BoundBinding>,
+        live: Live,
+        subst: Rc<Substitution>,
+    },
+    /// Lowers the branches of an `if` after its lowered condition.
+    If {
+        thn: BoundExpr,
+        els: BoundExpr,
+        labels: [BlockName; 2],
+        live: Live,
+        subst: Rc<Substitution>,
+        k: Continuation,
+    },
+    /// Lowers the arms of a `switch` after its lowered scrutinee.
+    Switch {
+        arms: Vec<(BlockName, BoundExpr)>,
+        live: Live,
+        subst: Rc<Substitution>,
+        k: Continuation,
+    },
+    /// Lowers an arm of a `switch` that continues at the join point `join`.
+    Arm {
+        e: BoundExpr,
+        join: BlockName,
+        live: Live,
+        subst: Rc<Substitution>,
+    },
+    /// Makes the lowered blocks the sub-blocks `labels` of `next`, before
+    /// the join point if any.
+    SubBlocks {
+        labels: Vec<BlockName>,
+        join: Option<BasicBlock<VarName, Nil>>,
+        next: Box<BlockBody<VarName, Nil>>,
+    },
+    /// Lowers the functions `decls`, the last first, after the lowered body
+    /// of their definitions, which is `next` once lowered.
+    Functions {
+        decls: Vec<BoundFunDecl>,
+        live: Live,
+        subst: Rc<Substitution>,
+        next: Option<Box<BlockBody<VarName, Nil>>>,
+        blocks: Vec<BasicBlock<VarName, Nil>>,
+        pending: Option<Pending>,
+    },
+}
+
+/// The function whose body `Lowering::Functions` lowers
+enum Pending {
+    /// lambda lifted, taking the ambient live variables before `params`
+    Lifted {
+        name: FunName,
+        block: BlockName,
+        params: Vec<VarName>,
+    },
+    /// a sub-block of the body of the definitions
+    SubBlock {
+        block: BlockName,
+        params: Vec<VarName>,
+    },
+}
+
+/// A step of `Lowerer::discharge`. A chain carries the asserted types of the
+/// variables that `copies` copy, and whether an inserted check asserted them
+enum Discharge {
+    Chain(BlockBody<VarName, Nil>, HashMap<(VarName, Type), bool>),
+    /// sub-blocks after `kept`, whose bodies and next are discharged first
+    SubBlocks {
+        kept: Vec<BlockBody<VarName, Nil>>,
+        blocks: Vec<BasicBlock<VarName, Nil>>,
+        ana: Nil,
+    },
+}
+
+/// The instruction that stands for the next of one moved out
+fn placeholder() -> BlockBody<VarName, Nil> {
+    BlockBody::Terminator(Terminator::Return(Immediate::Const(0)), Nil)
+}
+
+/// Links the instructions of `kept` in order before `last`
+fn relink(
+    kept: Vec<BlockBody<VarName, Nil>>,
+    last: BlockBody<VarName, Nil>,
+) -> BlockBody<VarName, Nil> {
+    kept.into_iter().rev().fold(last, |next, mut body| {
+        **body
+            .successor_mut()
+            .expect("an instruction before a terminator") = next;
+        body
+    })
+}
+
+/// The operation that defines each variable of `blocks` and their sub-blocks
+fn defs<T>(blocks: &[BasicBlock<VarName, T>]) -> HashMap<VarName, Operation<VarName>> {
+    let mut defs = HashMap::new();
+    let mut todo: Vec<_> = blocks.iter().collect();
+    while let Some(block) = todo.pop() {
+        let mut body = &block.body;
+        loop {
+            match body {
+                BlockBody::Operation { dest, op, .. } => {
+                    defs.insert(dest.clone(), op.clone());
+                }
+                BlockBody::SubBlocks { blocks, .. } => todo.extend(blocks.iter()),
+                _ => {}
+            }
+            match body.successor() {
+                Some(next) => body = next,
+                None => break,
+            }
+        }
+    }
+    defs
+}
+
+impl From<Resolver> for Lowerer {
+    fn from(resolver: Resolver) -> Self {
+        let Resolver {
+            names,
+            bigint,
+            annotations,
+            makes_strings,
+            ..
+        } = resolver;
+        Lowerer {
+            names,
+            fun_scopes: HashMap::new(),
+            should_lift: HashSet::new(),
+            fun_as_block: HashMap::new(),
+            lifted_funs: Vec::new(),
+            runtime_calls: BTreeMap::new(),
+            shims: HashMap::new(),
+            bigint,
+            makes_strings,
+            annotations,
+            proven: HashMap::new(),
+            checks: HashSet::new(),
+        }
+    }
+}
+
+/// Traverse the AST and collect the live variables at the start of each function.
+/// Also collect the functions that are non-tail called and the functions that are called
+/// by a lifted function, either tail or non-tail.
+struct Lifter {
+    /// Functions that are non-tail called.
+    non_tail_called_funs: HashSet<FunName>,
+    /// The call graph of the program.
+    /// Records all functions that are called, either tail or non-tail.
+    /// Used to lift all functions called by a lifted function.
+    fun_calls: HashMap<FunName, HashSet<FunName>>,
+}
+
+impl Lifter {
+    /// What should be lambda lifted?
+    /// 1. Any function that is called with a non-tail call.
+    /// 2. Any function that is called by a lifted function.
+    pub fn should_lift(&self) -> HashSet<FunName> {
+        let mut should_lift = HashSet::new();
+        // find all functions that should be lifted
+        let mut worklist = self
+            .non_tail_called_funs
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        while let Some(fun) = worklist.pop() {
+            // 1. include the function in worklist to the result set
+            if should_lift.insert(fun.clone()) {
+                // 2. if it's the first time met, add all functions that it calls to the worklist
+                worklist.extend(self.fun_calls.get(&fun).cloned().unwrap_or_default());
+            }
+        }
+        should_lift
+    }
+}
+
+impl Lifter {
+    fn new() -> Self {
+        Self {
+            non_tail_called_funs: HashSet::new(),
+            fun_calls: HashMap::new(),
+        }
+    }
+
+    fn lift_prog(&mut self, prog: &BoundProg) {
+        let Prog {
+            externs: _,
+            enums: _,
+            name,
+            param: _,
+            body,
+            loc: _,
+            doc: _,
+        } = prog;
+        self.lift_expr(body, &name, true);
+    }
+
+    fn lift_expr(&mut self, e: &BoundExpr, site: &FunName, tail_position: bool) {
+        // what the traversal collects does not depend on its order
+        let mut work = vec![(e, site, tail_position)];
+        while let Some((e, site, tail_position)) = work.pop() {
+            match e {
+                Expr::Num(_, _) | Expr::BigNum(_, _) | Expr::Bool(_, _) | Expr::Var(_, _) => {}
+                Expr::Prim {
+                    prim: _,
+                    args,
+                    loc: _,
+                } => {
+                    work.extend(args.iter().map(|arg| (arg, site, false)));
+                }
+                Expr::Let {
+                    bindings,
+                    body,
+                    loc: _,
+                } => {
+                    work.extend(bindings.iter().map(|b| (&b.expr, site, false)));
+                    work.push((body, site, tail_position));
+                }
+                Expr::If {
+                    cond,
+                    thn,
+                    els,
+                    loc: _,
+                } => {
+                    work.push((cond, site, false));
+                    work.push((thn, site, tail_position));
+                    work.push((els, site, tail_position));
+                }
+                Expr::Switch {
+                    scrutinee,
+                    cases,
+                    default,
+                    loc: _,
+                } => {
+                    work.push((scrutinee, site, false));
+                    for Case { label: _, body } in cases {
+                        work.push((body, site, tail_position));
+                    }
+                    work.push((default, site, tail_position));
+                }
+                Expr::Match { .. } => unreachable!("matches are compiled before lifting"),
+                Expr::FunDefs {
+                    decls,
+                    body,
+                    loc: _,
+                } => {
+                    for FunDecl { name, body, .. } in decls {
+                        work.push((body, name, true));
+                    }
+                    work.push((body, site, tail_position));
+                }
+                Expr::Call { fun, args, loc: _ } => {
+                    if !tail_position {
+                        self.non_tail_called_funs.insert(fun.clone());
+                    }
+                    self.fun_calls
+                        .entry(site.clone())
+                        .or_default()
+                        .insert(fun.clone());
+                    work.extend(args.iter().map(|arg| (arg, site, false)));
+                }
+            }
+        }
+    }
+}
+
+/// Compiles the matches of expressions into decision trees, which switch
+/// on the tags of objects and test each tag at most once on the way to the
+/// first arm that matches. The bodies of the arms that several leaves of a
+/// tree reach become functions, so that the tree does not copy them.
+struct Matches<'a> {
+    names: &'a mut NameSupply,
+    enums: Enums,
+}
+
+/// An arm, as far as it is still to be matched
+#[derive(Clone)]
+struct Row {
+    /// the patterns of constructors that the variables of the parts of the
+    /// scrutinee are yet to match
+    tests: Vec<(VarName, BoundPattern)>,
+    /// the variables of the pattern, with the parts they are bound to
+    binds: Vec<(VarName, VarName)>,
+    arm: usize,
+}
+
+enum Tree {
+    Leaf(Row),
+    /// the object of a variable, by its tag: each branch loads the fields
+    /// of the object into its variables, and the default branch tests no tag
+    Switch {
+        obj: VarName,
+        branches: Vec<(i64, Vec<VarName>, Tree)>,
+        default: (Vec<VarName>, Box<Tree>),
+    },
+}
+
+impl Tree {
+    fn leaves(&self, counts: &mut [usize]) {
+        match self {
+            Tree::Leaf(row) => counts[row.arm] += 1,
+            Tree::Switch {
+                branches, default, ..
+            } => {
+                for (_, _, tree) in branches {
+                    tree.leaves(counts);
+                }
+                default.1.leaves(counts);
+            }
+        }
+    }
+}
+
+/// A step of `Matches::compile`, which pushes the compiled expressions to
+/// `done`
+enum Compiling {
+    Expr(BoundExpr),
+    /// an expression whose children are the last expressions compiled
+    Rebuild(BoundExpr),
+    /// the arm of a match that was compiled last, with its pattern and the
+    /// number of leaves of the decision tree that reach it
+    Arm(Box<Decision>, BoundPattern, usize),
+    /// a match after its scrutinee, which `var` binds in `tree`
+    Scrutinee {
+        var: Spanned<VarName>,
+        tree: BoundExpr,
+        decls: Vec<BoundFunDecl>,
+        loc: SrcLoc,
+    },
+}
+
+/// A match whose decision tree is built, and whose arms are compiled
+struct Decision {
+    obj: VarName,
+    tree: Tree,
+    scrutinee: BoundExpr,
+    /// the arms yet to compile, last first, with their counts of leaves
+    arms: Vec<(BoundArm, usize)>,
+    decls: Vec<BoundFunDecl>,
+    bodies: Vec<Result<Option<BoundExpr>, (FunName, BoundPattern)>>,
+    loc: SrcLoc,
+}
+
+impl<'a> Matches<'a> {
+    fn new(names: &'a mut NameSupply, enums: &[BoundEnumDecl]) -> Self {
+        Matches {
+            names,
+            enums: Enums::new(enums),
+        }
+    }
+
+    /// Compiles the matches of `e`. The steps run from a work stack, and
+    /// compile the subexpressions in order, as the names they take follow it.
+    fn compile(&mut self, e: BoundExpr) -> BoundExpr {
+        let mut work = vec![Compiling::Expr(e)];
+        let mut done = Vec::new();
+        while let Some(step) = work.pop() {
+            match step {
+                Compiling::Expr(Expr::Match {
+                    scrutinee,
+                    arms,
+                    loc,
+                }) => self.decision_tree(*scrutinee, arms, loc, &mut work),
+                Compiling::Expr(mut e) => {
+                    let loc = e.loc();
+                    let children: Vec<_> = (e.children_mut().into_iter())
+                        .map(|child| std::mem::replace(child, Expr::Bool(false, loc)))
+                        .collect();
+                    work.push(Compiling::Rebuild(e));
+                    work.extend(children.into_iter().rev().map(Compiling::Expr));
+                }
+                Compiling::Rebuild(mut e) => {
+                    let children = e.children_mut();
+                    let compiled = done.split_off(done.len() - children.len());
+                    for (child, compiled) in children.into_iter().zip(compiled) {
+                        *child = compiled;
+                    }
+                    done.push(e);
+                }
+                Compiling::Arm(decision, pattern, count) => {
+                    let body = done.pop().expect("the body of the arm");
+                    self.arm(decision, pattern, count, body, &mut work)
+                }
+                Compiling::Scrutinee {
+                    var,
+                    tree,
+                    decls,
+                    loc,
+                } => {
+                    let binding = Binding {
+                        var,
+                        expr: done.pop().expect("the scrutinee"),
+                        ty: None,
+                        elems: None,
+                    };
+                    let body = Expr::Let {
+                        bindings: vec![binding],
+                        body: Box::new(tree),
+                        loc,
+                    };
+                    done.push(match decls.is_empty() {
+                        true => body,
+                        false => Expr::FunDefs {
+                            decls,
+                            body: Box::new(body),
+                            loc,
+                        },
+                    });
+                }
+            }
+        }
+        done.pop().expect("the compiled expression")
+    }
+
+    /// `let obj = scrutinee in tree`, after the functions of the arms that
+    /// the tree reaches more than once. Pushes to `work` the steps that
+    /// compile the arms in order, then the scrutinee.
+    fn decision_tree(
+        &mut self,
+        scrutinee: BoundExpr,
+        arms: Vec<BoundArm>,
+        loc: SrcLoc,
+        work: &mut Vec<Compiling>,
+    ) {
+        let obj = self.names.fresh_var("obj");
+        let rows = (arms.iter().enumerate())
+            .map(|(arm, Arm { pattern, .. })| Row {
+                tests: vec![(obj.clone(), pattern.clone())],
+                binds: Vec::new(),
+                arm,
+            })
+            .collect();
+        let tree = self.tree(rows);
+        let mut counts = vec![0; arms.len()];
+        tree.leaves(&mut counts);
+        let mut arms: Vec<_> = arms.into_iter().zip(counts).collect();
+        arms.reverse();
+        let decision = Decision {
+            obj,
+            tree,
+            scrutinee,
+            arms,
+            decls: Vec::new(),
+            bodies: Vec::new(),
+            loc,
+        };
+        Self::next_arm(Box::new(decision), work);
+    }
+
+    /// Pushes to `work` the steps that compile the next arm of `decision`,
+    /// or its scrutinee once the arms are compiled
+    fn next_arm(mut decision: Box<Decision>, work: &mut Vec<Compiling>) {
+        if let Some((Arm { pattern, body }, count)) = decision.arms.pop() {
+            work.push(Compiling::Arm(decision, pattern, count));
+            return work.push(Compiling::Expr(body));
+        }
+        let Decision {
+            obj,
+            tree,
+            scrutinee,
+            decls,
+            mut bodies,
+            loc,
+            ..
+        } = *decision;
+        let tree = Self::emit(tree, &mut bodies, loc);
+        let var = Spanned::new(obj, scrutinee.loc());
+        work.push(Compiling::Scrutinee {
+            var,
+            tree,
+            decls,
+            loc,
+        });
+        work.push(Compiling::Expr(scrutinee));
+    }
+
+    /// The step after the `body` of an arm of `decision` is compiled, which
+    /// becomes a function if `count` leaves of the tree reach it
+    fn arm(
+        &mut self,
+        mut decision: Box<Decision>,
+        pattern: BoundPattern,
+        count: usize,
+        body: BoundExpr,
+        work: &mut Vec<Compiling>,
+    ) {
+        let loc = decision.loc;
+        if count > 1 {
+            let name = self.names.fresh_fun("arm");
+            let params = (pattern.vars().into_iter())
+                .map(|var| Spanned::new(var.clone(), loc))
+                .collect();
+            decision.decls.push(FunDecl {
+                name: name.clone(),
+                params,
+                signature: Signature::default(),
+                body,
+                loc,
+                doc: None,
+                gen: false,
+            });
+            decision.bodies.push(Err((name, pattern)));
+        } else {
+            decision.bodies.push(Ok(Some(body)));
+        }
+        Self::next_arm(decision, work)
+    }
+
+    /// The decision tree of `rows`, which cover every object
+    fn tree(&mut self, rows: Vec<Row>) -> Tree {
+        // the variables and wildcards match whatever they test
+        let mut rows: Vec<_> = (rows.into_iter())
+            .map(|mut row| {
+                let mut tests = Vec::new();
+                for (obj, pattern) in row.tests {
+                    match pattern {
+                        Pattern::Wildcard(_) => {}
+                        Pattern::Var(var, _) => row.binds.push((var, obj)),
+                        Pattern::Ctor { .. } => tests.push((obj, pattern)),
+                    }
+                }
+                Row { tests, ..row }
+            })
+            .collect();
+        let first = rows
+            .first()
+            .expect("the arms of a match cover every object");
+        let Some((obj, _)) = first.tests.first() else {
+            return Tree::Leaf(rows.swap_remove(0));
+        };
+        let obj = obj.clone();
+        let test = |row: &Row| row.tests.iter().position(|(o, _)| *o == obj);
+        let mut heads = Vec::new();
+        for row in &rows {
+            if let Some(Pattern::Ctor { ctor, .. }) = test(row).map(|i| &row.tests[i].1) {
+                if !heads.contains(ctor) {
+                    heads.push(ctor.clone());
+                }
+            }
+        }
+        let siblings = self.enums.get(&heads[0]).siblings.clone();
+        let mut ctors: Vec<_> = (siblings.into_iter())
+            .filter(|ctor| heads.contains(ctor))
+            .collect();
+        let complete = ctors.len() == self.enums.get(&heads[0]).siblings.len();
+        // a test of the tag of the last variant would always succeed
+        let last = if complete { ctors.pop() } else { None };
+
+        let branch = |matches: &mut Self, ctor: &FunName| {
+            let arity = matches.enums.get(ctor).arity;
+            let fields: Vec<_> = (0..arity)
+                .map(|_| matches.names.fresh_var("field"))
+                .collect();
+            let rows = (rows.iter())
+                .filter_map(|row| {
+                    let Some(i) = test(row) else {
+                        return Some(row.clone());
+                    };
+                    let mut row = row.clone();
+                    let (_, pattern) = row.tests.remove(i);
+                    let Pattern::Ctor {
+                        ctor: head, args, ..
+                    } = pattern
+                    else {
+                        unreachable!()
+                    };
+                    if head != *ctor {
+                        return None;
+                    }
+                    let args = fields.iter().cloned().zip(args);
+                    row.tests.splice(i..i, args);
+                    Some(row)
+                })
+                .collect();
+            (fields, matches.tree(rows))
+        };
+        let branches = (ctors.iter())
+            .map(|ctor| {
+                let (fields, tree) = branch(self, ctor);
+                (self.enums.get(ctor).tag, fields, tree)
+            })
+            .collect();
+        let default = match last {
+            Some(ctor) => branch(self, &ctor),
+            None => {
+                let rows = rows
+                    .iter()
+                    .filter(|row| test(row).is_none())
+                    .cloned()
+                    .collect();
+                (Vec::new(), self.tree(rows))
+            }
+        };
+        Tree::Switch {
+            obj,
+            branches,
+            default: (default.0, Box::new(default.1)),
+        }
+    }
+
+    /// The expression of `tree`, which ends in the bodies of the arms, or
+    /// in calls to their functions along with their patterns
+    fn emit(
+        tree: Tree,
+        bodies: &mut [Result<Option<BoundExpr>, (FunName, BoundPattern)>],
+        loc: SrcLoc,
+    ) -> BoundExpr {
+        let load = |obj: &VarName, i: usize| Expr::Prim {
+            prim: Prim::ArrayGet,
+            args: vec![Expr::Var(obj.clone(), loc), Expr::Num(i as i64, loc)],
+            loc,
+        };
+        let bind = |bindings: Vec<BoundBinding>, body: BoundExpr| match bindings.is_empty() {
+            true => body,
+            false => Expr::Let {
+                bindings,
+                body: Box::new(body),
+                loc,
+            },
+        };
+        let let_var = |var: VarName, expr| Binding {
+            var: Spanned::new(var, loc),
+            expr,
+            ty: None,
+            elems: None,
+        };
+        match tree {
+            Tree::Leaf(Row { binds, arm, .. }) => match &mut bodies[arm] {
+                Ok(body) => {
+                    let body = body.take().expect("an arm of a single leaf");
+                    let bindings = (binds.into_iter())
+                        .map(|(var, obj)| let_var(var, Expr::Var(obj, loc)))
+                        .collect();
+                    bind(bindings, body)
+                }
+                Err((fun, pattern)) => {
+                    let args = (pattern.vars().into_iter())
+                        .map(|var| {
+                            let (_, obj) = binds.iter().find(|(v, _)| v == var).unwrap();
+                            Expr::Var(obj.clone(), loc)
+                        })
+                        .collect();
+                    Expr::Call {
+                        fun: fun.clone(),
+                        args,
+                        loc,
+                    }
+                }
+            },
+            Tree::Switch {
+                obj,
+                branches,
+                default: (fields, default),
+            } => {
+                let fields_of = |fields: Vec<VarName>, tree, bodies: &mut _| {
+                    let bindings = (fields.into_iter().enumerate())
+                        .map(|(i, field)| let_var(field, load(&obj, i + 1)))
+                        .collect();
+                    bind(bindings, Self::emit(tree, bodies, loc))
+                };
+                let default = fields_of(fields, *default, bodies);
+                if branches.is_empty() {
+                    return default;
+                }
+                let cases = (branches.into_iter())
+                    .map(|(tag, fields, tree)| Case {
+                        label: Spanned::new(tag, loc),
+                        body: fields_of(fields, tree, bodies),
+                    })
+                    .collect();
+                Expr::Switch {
+                    scrutinee: Box::new(load(&obj, 0)),
+                    cases,
+                    default: Box::new(default),
+                    loc,
+                }
+            }
+        }
+    }
+}
+
+impl Immediate<VarName> {
+    pub fn raw(val: usize) -> Self {
+        Self::Const(val as i64)
+    }
+    pub fn integer(val: i64) -> Self {
+        // should not overflow after frontend checks
+        Self::Const(val << 1)
+    }
+    pub fn boolean(val: bool) -> Self {
+        if val {
+            Self::Const(0b101)
+        } else {
+            Self::Const(0b001)
+        }
+    }
+}
+
+impl Lowerer {
+    pub fn lower_prog(&mut self, mut prog: BoundProg) -> Program<VarName, Nil> {
+        // first, compile the matches into decision trees
+        let mut matches = Matches::new(&mut self.names, &prog.enums);
+        prog.body = matches.compile(prog.body);
+
+        // then, collect all functions that should be lifted
+        let mut lifter = Lifter::new();
+        lifter.lift_prog(&prog);
+        self.should_lift = lifter.should_lift();
+
+        // then, lower the program
+        let Prog {
+            externs,
+            enums: _,
+            name,
+            param,
+            body,
+            loc: _,
+            doc: _,
+        } = prog;
+        self.lower_entry(externs, name, vec![param.node], body)
+    }
+
+    /// Lowers the expression `body` into the function `entry` of `params`,
+    /// which takes them by the calling convention rather than in an array
+    pub fn lower_fragment(
+        &mut self,
+        params: Vec<VarName>,
+        body: BoundExpr,
+    ) -> Program<VarName, Nil> {
+        let name = FunName::unmangled("entry");
+        let body = Matches::new(&mut self.names, &[]).compile(body);
+        let mut lifter = Lifter::new();
+        lifter.lift_expr(&body, &name, true);
+        self.should_lift = lifter.should_lift();
+        self.lower_entry(Vec::new(), name, params, body)
+    }
+
+    /// Lowers the main function `name` of `params` along with the externs
+    fn lower_entry(
+        &mut self,
+        externs: Vec<BoundExtDecl>,
+        name: FunName,
+        params: Vec<VarName>,
+        body: BoundExpr,
+    ) -> Program<VarName, Nil> {
+        // register function scope for the main function
+        self.fun_scopes.insert(name.clone(), Vec::new());
+        // create a block name for the main function
+        let block = self.names.fresh_block(name.hint());
+        self.fun_as_block.insert(name.clone(), block.clone());
+        // lower the externs, with shims for the typed ones
+        let mut shims = Vec::new();
+        let mut externs = Vec::from_iter((externs.into_iter()).map(
+            |ExtDecl {
+                 name,
+                 params,
+                 signature,
+                 contract,
+                 loc: _,
+                 doc: _,
+             }| {
+                let params: Vec<_> = params.into_iter().map(|p| p.node).collect();
+                if signature.is_typed() {
+                    shims.push(self.lower_shim(&name, &params, &signature));
+                }
+                Extern {
+                    name,
+                    params,
+                    contract,
+                }
+            },
+        ));
+        // add the built-in functions
+        externs.extend([
+            Extern {
+                name: FunName::unmangled("snake_error"),
+                params: vec![self.names.fresh_var("ecode"), self.names.fresh_var("v")],
+                contract: Contract::default(),
+            },
+            Extern {
+                name: FunName::unmangled("snake_new_array"),
+                params: vec![self.names.fresh_var("len")],
+                contract: Contract::default(),
+            },
+        ]);
+        // lower the body
+        let body = self.lower_expr_kont(body, &params, &Substitution::new(), Continuation::Return);
+        // collect the lifted functions and blocks
+        let (mut funs, mut blocks): (Vec<FunBlock<VarName>>, Vec<BasicBlock<VarName, Nil>>) =
+            std::mem::take(&mut self.lifted_funs)
+                .into_iter()
+                .chain(shims)
+                .unzip();
+        // create the entry block and function
+        blocks.push(BasicBlock {
+            label: block.clone(),
+            params: params.clone(),
+            body,
+            ana: Nil,
+        });
+        // without the assertions that the annotations discharge
+        let mut copies = HashMap::new();
+        for block in blocks.iter_mut() {
+            let body = std::mem::replace(&mut block.body, placeholder());
+            block.body = self.discharge(body, &mut copies);
+        }
+        let fun_params: Vec<_> = params
+            .iter()
+            .map(|p| self.names.fresh_var(p.hint()))
+            .collect();
+        funs.push(FunBlock {
+            name,
+            params: fun_params.clone(),
+            body: Branch {
+                target: block,
+                args: fun_params.into_iter().map(Immediate::Var).collect(),
+            },
+        });
+
+        // declare the runtime functions that the builtins call
+        for (name, arity) in std::mem::take(&mut self.runtime_calls) {
+            externs.push(Extern {
+                name: FunName::unmangled(name),
+                params: (0..arity).map(|_| self.names.fresh_var("arg")).collect(),
+                contract: Contract::default(),
+            });
+        }
+
+        Program {
+            externs,
+            funs,
+            blocks,
+        }
+    }
+
+    /// Records what the annotations prove of the arguments `vars` of `prim`:
+    /// the types of annotated variables, of the results of annotated
+    /// functions and of checks, but for integers that may be big
+    fn prove(&mut self, prim: &ast::Prim, args: &[BoundExpr], vars: &[VarName]) {
+        for (arg, var) in args.iter().zip(vars) {
+            let annotated = match arg {
+                Expr::Var(..) | Expr::Call { .. } => true,
+                Expr::Prim { prim, .. } => matches!(prim, ast::Prim::Check(_)),
+                _ => false,
+            };
+            let ty = self
+                .annotations
+                .type_of(arg)
+                .filter(|ty| !self.bigint || *ty != Type::Int);
+            if let (true, Some(ty)) = (annotated, ty) {
+                self.proven.insert(var.clone(), ty);
+            }
+        }
+        if let ast::Prim::Check(_) = prim {
+            self.checks.insert(vars[0].clone());
+        }
+    }
+
+    /// Removes the type assertions that the annotations discharge: those of
+    /// proven values, and those of values that an inserted check asserted on
+    /// the way, or that the assertions on the way make an inserted check of.
+    /// `copies` maps the copies of variables to the variables they copy.
+    fn discharge(
+        &self,
+        body: BlockBody<VarName, Nil>,
+        copies: &mut HashMap<VarName, VarName>,
+    ) -> BlockBody<VarName, Nil> {
+        let mut work = vec![Discharge::Chain(body, HashMap::new())];
+        let mut done = Vec::new();
+        while let Some(step) = work.pop() {
+            match step {
+                Discharge::Chain(body, facts) => {
+                    self.discharge_chain(body, facts, copies, &mut work, &mut done)
+                }
+                Discharge::SubBlocks { kept, blocks, ana } => {
+                    let next = Box::new(done.pop().expect("the chain after the blocks"));
+                    let bodies = done.split_off(done.len() - blocks.len());
+                    let blocks = (blocks.into_iter().zip(bodies))
+                        .map(|(block, body)| BasicBlock { body, ..block })
+                        .collect();
+                    done.push(relink(kept, BlockBody::SubBlocks { blocks, next, ana }));
+                }
+            }
+        }
+        done.pop().expect("the discharged body")
+    }
+
+    /// The step of `discharge` that walks `body` up to its terminator, or up
+    /// to sub-blocks, whose blocks and next it pushes to `work` to run first
+    fn discharge_chain(
+        &self,
+        mut body: BlockBody<VarName, Nil>,
+        mut facts: HashMap<(VarName, Type), bool>,
+        copies: &mut HashMap<VarName, VarName>,
+        work: &mut Vec<Discharge>,
+        done: &mut Vec<BlockBody<VarName, Nil>>,
+    ) {
+        let mut kept = Vec::new();
+        loop {
+            match body {
+                BlockBody::AssertType {
+                    ty,
+                    arg: Immediate::Var(ref var),
+                    ref mut next,
+                    ..
+                } => {
+                    let fact = (copies.get(var).unwrap_or(var).clone(), ty);
+                    let inserted = self.checks.contains(var);
+                    if self.proven.get(var) == Some(&ty)
+                        || facts.get(&fact).is_some_and(|checked| *checked || inserted)
+                    {
+                        body = std::mem::replace(&mut **next, placeholder());
+                        continue;
+                    }
+                    facts.insert(fact, inserted);
+                }
+                BlockBody::Operation {
+                    ref dest,
+                    op: Operation::Immediate(Immediate::Var(ref var)),
+                    ..
+                } => {
+                    let original = copies.get(var).unwrap_or(var).clone();
+                    copies.insert(dest.clone(), original);
+                }
+                // the blocks run after what runs before them, but not after
+                // what runs after them
+                BlockBody::SubBlocks {
+                    mut blocks,
+                    next,
+                    ana,
+                } => {
+                    let bodies: Vec<_> = (blocks.iter_mut())
+                        .map(|block| std::mem::replace(&mut block.body, placeholder()))
+                        .collect();
+                    work.push(Discharge::SubBlocks { kept, blocks, ana });
+                    work.push(Discharge::Chain(*next, facts.clone()));
+                    for body in bodies.into_iter().rev() {
+                        work.push(Discharge::Chain(body, facts.clone()));
+                    }
+                    return;
+                }
+                BlockBody::Terminator(..) => return done.push(relink(kept, body)),
+                _ => {}
+            }
+            let next = body
+                .successor_mut()
+                .expect("an instruction before a terminator");
+            let rest = std::mem::replace(&mut **next, placeholder());
+            kept.push(body);
+            body = rest;
+        }
+    }
+
+    /// The shim of the extern `name`, which checks the typed arguments,
+    /// untags them, calls the extern and tags its result as `signature` says
+    fn lower_shim(
+        &mut self,
+        name: &FunName,
+        params: &[VarName],
+        signature: &Signature,
+    ) -> (FunBlock<VarName>, BasicBlock<VarName, Nil>) {
+        let shim = self.names.fresh_fun(format!("{}_shim", name.hint()));
+        self.shims.insert(name.clone(), shim.clone());
+        let params: Vec<_> = params
+            .iter()
+            .map(|p| self.names.fresh_var(p.hint()))
+            .collect();
+        // tag the result
+        let result = self.names.fresh_var("result");
+        let tagged = self.names.fresh_var("tagged");
+        let tag = |op| BlockBody::Operation {
+            dest: tagged.clone(),
+            op,
+            next: Box::new(BlockBody::Terminator(
+                Terminator::Return(Immediate::Var(tagged.clone())),
+                Nil,
+            )),
+            ana: Nil,
+        };
+        let returned = Immediate::Var(result.clone());
+        let body = match signature.ret {
+            None => BlockBody::Terminator(Terminator::Return(returned), Nil),
+            // tagged = result + result, which fails if it does not fit 63 bits
+            Some(Type::Int) => tag(Operation::Prim2(Prim2::Add, returned.clone(), returned)),
+            // truth = result != 0, then tagged as a boolean
+            Some(ty @ Type::Bool) => {
+                let truth = self.names.fresh_var("truth");
+                let tagging = self.tagging(&Immediate::Var(truth.clone()), ty, Continuation::Return);
+                BlockBody::Operation {
+                    dest: truth,
+                    op: Operation::Prim2(Prim2::Neq, returned, Immediate::raw(0)),
+                    next: Box::new(tagging),
+                    ana: Nil,
+                }
+            }
+            Some(ty @ Type::Array) => self.tagging(&returned, ty, Continuation::Return),
+        };
+        // untag the typed arguments
+        let typed: Vec<_> = (0..params.len())
+            .map(|i| {
+                let ty = signature.params.get(i).copied().flatten();
+                ty.map(|ty| (ty, self.names.fresh_var("untagged")))
+            })
+            .collect();
+        let args = params
+            .iter()
+            .zip(typed.iter())
+            .map(|(param, typed)| match typed {
+                Some((_, untagged)) => Immediate::Var(untagged.clone()),
+                None => Immediate::Var(param.clone()),
+            });
+        let mut body = BlockBody::Operation {
+            dest: result,
+            op: Operation::Call {
+                fun: name.clone(),
+                args: args.collect(),
+            },
+            next: Box::new(body),
+            ana: Nil,
+        };
+        for (param, typed) in params.iter().zip(typed).rev() {
+            if let Some((ty, untagged)) = typed {
+                let param = Immediate::Var(param.clone());
+                let untag = self.untagging(ty, &param, Continuation::Block(untagged, body));
+                body = Self::assert_type(ty, &param, untag);
+            }
+        }
+        let block = self.names.fresh_block(shim.hint());
+        let fun_params: Vec<_> = params
+            .iter()
+            .map(|p| self.names.fresh_var(p.hint()))
+            .collect();
+        let fun = FunBlock {
+            name: shim,
+            params: fun_params.clone(),
+            body: Branch {
+                target: block.clone(),
+                args: fun_params.into_iter().map(Immediate::Var).collect(),
+            },
+        };
+        (
+            fun,
+            BasicBlock {
+                label: block,
+                params,
+                body,
+                ana: Nil,
+            },
+        )
+    }
+
+    fn kont_to_block(&mut self, k: Continuation) -> (VarName, BlockBody<VarName, Nil>) {
+        match k {
+            Continuation::Block(x, b) => (x, b),
+            Continuation::Return => {
+                let x = self.names.fresh_var("result");
+                (
+                    x.clone(),
+                    BlockBody::Terminator(Terminator::Return(Immediate::Var(x)), Nil),
+                )
+            }
+        }
+    }
+
+    /// Compiles an expression to a basic block that uses the continuation k on
+    /// the value e produces. The steps run from a work stack rather than
+    /// recurse, so that deeply nested expressions do not overflow the stack.
+    fn lower_expr_kont(
+        &mut self,
+        e: BoundExpr,
+        live: &[VarName],
+        subst: &Substitution,
+        k: Continuation,
+    ) -> BlockBody<VarName, Nil> {
+        let mut work = vec![Lowering::Expr {
+            e,
+            live: Live::from(live.to_vec()),
+            subst: Rc::new(subst.clone()),
+            k,
+        }];
+        let mut done = Vec::new();
+        while let Some(step) = work.pop() {
+            self.lower_step(step, &mut work, &mut done);
+        }
+        done.pop().expect("the block of the expression")
+    }
+
+    /// Runs `step`, which pops the blocks of the subexpressions lowered
+    /// before it from `done`, and pushes its own block or its next steps
+    fn lower_step(
+        &mut self,
+        step: Lowering,
+        work: &mut Vec<Lowering>,
+        done: &mut Vec<BlockBody<VarName, Nil>>,
+    ) {
+        match step {
+            Lowering::Expr { e, live, subst, k } => {
+                self.lower_expr_step(e, live, subst, k, work, done)
+            }
+            Lowering::Operands {
+                mut operands,
+                live,
+                subst,
+            } => {
+                let block = Self::lowered(done);
+                let Some((e, var)) = operands.pop() else {
+                    return done.push(block);
+                };
+                work.push(Lowering::Operands {
+                    operands,
+                    live: live.clone(),
+                    subst: subst.clone(),
+                });
+                work.push(Lowering::Expr {
+                    e,
+                    live,
+                    subst,
+                    k: Continuation::Block(var, block),
+                });
+            }
+            Lowering::Bindings {
+                mut bindings,
+                mut live,
+                subst,
+            } => {
+                let block = Self::lowered(done);
+                let Some(Binding {
+                    var: Spanned { node: var, .. },
+                    expr,
+                    elems,
+                    ..
+                }) = bindings.pop()
+                else {
+                    return done.push(block);
+                };
+                let block = match elems {
+                    Some(elems) => {
+                        live.truncate(live.len() - elems.len());
+                        self.destructure(&var, elems, block)
+                    }
+                    None => block,
+                };
+                live.truncate(live.len() - 1);
+                work.push(Lowering::Bindings {
+                    bindings,
+                    live: live.clone(),
+                    subst: subst.clone(),
+                });
+                work.push(Lowering::Expr {
+                    e: expr,
+                    live,
+                    subst,
+                    k: Continuation::Block(var, block),
+                });
+            }
+            Lowering::If {
+                thn,
+                els,
+                labels,
+                live,
+                subst,
+                k,
+            } => {
+                let next = Box::new(Self::lowered(done));
+                // optimizing to not create a join point if in tail position
+                let (join, ks) = match k {
+                    Continuation::Return => (None, [Continuation::Return, Continuation::Return]),
+                    Continuation::Block(dest, body) => {
+                        // fresh variables for return positions in kontinuations
+                        let thn_var = self.names.fresh_var("thn_res");
+                        let els_var = self.names.fresh_var("els_res");
+                        let join_name = self.names.fresh_block("jn");
+                        let ks = [
+                            Self::join_kont(thn_var, &join_name),
+                            Self::join_kont(els_var, &join_name),
+                        ];
+                        (Some(Self::join(join_name, dest, body)), ks)
+                    }
+                };
+                work.push(Lowering::SubBlocks {
+                    labels: labels.to_vec(),
+                    join,
+                    next,
+                });
+                for (e, k) in [thn, els].into_iter().zip(ks).rev() {
+                    let (live, subst) = (live.clone(), subst.clone());
+                    work.push(Lowering::Expr { e, live, subst, k });
+                }
+            }
+            Lowering::Switch {
+                arms,
+                live,
+                subst,
+                k,
+            } => {
+                let next = Box::new(Self::lowered(done));
+                let labels = arms.iter().map(|(label, _)| label.clone()).collect();
+                match k {
+                    Continuation::Return => {
+                        work.push(Lowering::SubBlocks {
+                            labels,
+                            join: None,
+                            next,
+                        });
+                        for (_, e) in arms.into_iter().rev() {
+                            let (live, subst) = (live.clone(), subst.clone());
+                            let k = Continuation::Return;
+                            work.push(Lowering::Expr { e, live, subst, k });
+                        }
+                    }
+                    // if we have a non-trivial continuation, we create a join point
+                    Continuation::Block(dest, body) => {
+                        let join_name = self.names.fresh_block("jn");
+                        work.push(Lowering::SubBlocks {
+                            labels,
+                            join: Some(Self::join(join_name.clone(), dest, body)),
+                            next,
+                        });
+                        for (_, e) in arms.into_iter().rev() {
+                            work.push(Lowering::Arm {
+                                e,
+                                join: join_name.clone(),
+                                live: live.clone(),
+                                subst: subst.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+            Lowering::Arm {
+                e,
+                join,
+                live,
+                subst,
+            } => {
+                let var = self.names.fresh_var("case_res");
+                let k = Self::join_kont(var, &join);
+                work.push(Lowering::Expr { e, live, subst, k });
+            }
+            Lowering::SubBlocks { labels, join, next } => {
+                let bodies = done.split_off(done.len() - labels.len());
+                let mut blocks: Vec<_> = (labels.into_iter().zip(bodies))
+                    .map(|(label, body)| BasicBlock {
+                        label,
+                        params: Vec::new(),
+                        body,
+                        ana: Nil,
+                    })
+                    .collect();
+                blocks.extend(join);
+                done.push(BlockBody::SubBlocks {
+                    blocks,
+                    next,
+                    ana: Nil,
+                });
+            }
+            Lowering::Functions {
+                decls,
+                live,
+                subst,
+                next,
+                blocks,
+                pending,
+            } => self.lower_functions(decls, live, subst, next, blocks, pending, work, done),
+        }
+    }
+
+    /// The step of `lower_step` that lowers the functions `decls` one at a
+    /// time, the first last, after the body of their definitions
+    #[allow(clippy::too_many_arguments)]
+    fn lower_functions(
+        &mut self,
+        mut decls: Vec<BoundFunDecl>,
+        live: Live,
+        subst: Rc<Substitution>,
+        next: Option<Box<BlockBody<VarName, Nil>>>,
+        mut blocks: Vec<BasicBlock<VarName, Nil>>,
+        pending: Option<Pending>,
+        work: &mut Vec<Lowering>,
+        done: &mut Vec<BlockBody<VarName, Nil>>,
+    ) {
+        let body = Self::lowered(done);
+        let next = match (next, pending) {
+            // the body of the definitions
+            (None, _) => Box::new(body),
+            (
+                Some(next),
+                Some(Pending::Lifted {
+                    name,
+                    block,
+                    params,
+                }),
+            ) => {
+                let funblock_params = params
+                    .iter()
+                    .map(|p| self.names.fresh_var(p.hint()))
+                    .collect::<Vec<_>>();
+                let funblock = FunBlock {
+                    name,
+                    params: funblock_params.clone(),
+                    body: Branch {
+                        target: block.clone(),
+                        args: funblock_params
+                            .clone()
+                            .into_iter()
+                            .map(Immediate::Var)
+                            .collect(),
+                    },
+                };
+                let block = BasicBlock {
+                    label: block,
+                    params,
+                    body,
+                    ana: Nil,
+                };
+                self.lifted_funs.push((funblock, block));
+                next
+            }
+            // tail recursive functions are built as sub-blocks
+            (Some(next), Some(Pending::SubBlock { block, params })) => {
+                blocks.push(BasicBlock {
+                    label: block,
+                    params,
+                    body,
+                    ana: Nil,
+                });
+                next
+            }
+            (Some(_), None) => unreachable!("a function lowered before it was pending"),
+        };
+        let Some(FunDecl {
+            name: fun,
+            params,
+            body,
+            ..
+        }) = decls.pop()
+        else {
+            return done.push(if blocks.is_empty() {
+                *next
+            } else {
+                BlockBody::SubBlocks {
+                    blocks,
+                    next,
+                    ana: Nil,
+                }
+            });
+        };
+        let inner = live
+            .iter()
+            .cloned()
+            .chain(params.iter().map(|p| p.node.clone()))
+            .collect::<Vec<_>>();
+        let block = self.fun_as_block.get(&fun).cloned().expect("fun not found");
+        let (pending, inner_subst) = if self.should_lift.contains(&fun) {
+            // Here we need to produce a fundecl in lifted_funs,
+            // but we need to add extra arguments.
+            let mut subst = subst.as_ref().clone();
+            // get ambient live variables rename the ambient variables
+            // to be unique; the ambient variables are prefixed with "@"
+            let ambient = self
+                .fun_scopes
+                .get(&fun)
+                .cloned()
+                .expect("fun not found")
+                .into_iter()
+                .map(|v| {
+                    // with a hint from the previous name
+                    let new = self.names.fresh_var(format!("@{}", v.hint()));
+                    subst.insert(v, new.clone());
+                    new
+                });
+            // get function parameters prepared
+            let fun_params = params.into_iter().map(|p| p.node);
+            // parameters are ambient live variables and the function parameters combined
+            let params = ambient.chain(fun_params).collect::<Vec<_>>();
+            let pending = Pending::Lifted {
+                name: fun,
+                block,
+                params,
+            };
+            (pending, Rc::new(subst))
+        } else {
+            let params = params.into_iter().map(|p| p.node).collect();
+            (Pending::SubBlock { block, params }, subst.clone())
+        };
+        work.push(Lowering::Functions {
+            decls,
+            live,
+            subst,
+            next: Some(next),
+            blocks,
+            pending: Some(pending),
+        });
+        work.push(Lowering::Expr {
+            e: body,
+            live: Live::from(inner),
+            subst: inner_subst,
+            k: Continuation::Return,
+        });
+    }
+
+    fn lowered(done: &mut Vec<BlockBody<VarName, Nil>>) -> BlockBody<VarName, Nil> {
+        done.pop().expect("the block of a subexpression")
+    }
+
+    /// The continuation that branches to `join` with the value as `var`
+    fn join_kont(var: VarName, join: &BlockName) -> Continuation {
+        Continuation::Block(
+            var.clone(),
+            BlockBody::Terminator(
+                Terminator::Branch(Branch {
+                    target: join.clone(),
+                    args: vec![Immediate::Var(var)],
+                }),
+                Nil,
+            ),
+        )
+    }
+
+    /// The join point `label`, which continues with `body` on `dest`
+    fn join(
+        label: BlockName,
+        dest: VarName,
+        body: BlockBody<VarName, Nil>,
+    ) -> BasicBlock<VarName, Nil> {
+        BasicBlock {
+            label,
+            params: vec![dest],
+            body,
+            ana: Nil,
+        }
+    }
+
+    /// The step of `lower_step` that lowers `e`: it pushes the block of `e`
+    /// to `done`, or to `work` the steps that lower its subexpressions
+    fn lower_expr_step(
+        &mut self,
+        e: BoundExpr,
+        live: Live,
+        subst: Rc<Substitution>,
+        k: Continuation,
+        work: &mut Vec<Lowering>,
+        done: &mut Vec<BlockBody<VarName, Nil>>,
+    ) {
+        let block = match e {
+            Expr::Num(n, _) => k.invoke(Immediate::integer(n)),
+            Expr::BigNum(n, _) => {
+                // built by the runtime from its base 10^9 digits, negated if
+                // the literal is negative: (d0 * 10^9 + d1) * 10^9 + ...
+                const BASE: u32 = 1_000_000_000;
+                let sign = if n.neg { -1 } else { 1 };
+                let mut digits = n.digits(BASE).into_iter().map(|d| sign * d as i64);
+                let mut acc = Immediate::integer(digits.next().unwrap_or(0));
+                let mut ops = Vec::new();
+                for d in digits {
+                    let scaled = self.names.fresh_var("scaled");
+                    let big = self.names.fresh_var("big");
+                    ops.push((
+                        scaled.clone(),
+                        "snake_big_mul",
+                        vec![acc, Immediate::integer(BASE as i64)],
+                    ));
+                    ops.push((
+                        big.clone(),
+                        "snake_big_add",
+                        vec![Immediate::Var(scaled), Immediate::integer(d)],
+                    ));
+                    acc = Immediate::Var(big);
+                }
+                ops.into_iter()
+                    .rev()
+                    .fold(k.invoke(acc), |next, (dest, fun, args)| {
+                        self.runtime_call(fun, args, dest, next)
+                    })
+            }
+            Expr::Bool(b, _) => k.invoke(Immediate::boolean(b)),
+            Expr::Var(v, _) => k.invoke(Immediate::Var(subst.run(v))),
+            Expr::Prim { prim, args, loc: _ } => {
+                // prepare the arguments
+                let (args_var, args_imm): (Vec<_>, Vec<_>) = args
+                    .iter()
+                    .enumerate()
+                    .map(|(i, _arg)| {
+                        // the arguments are named after the primitive name and the argument index
+                        let var = self.names.fresh_var(format!("{:?}_{}", prim, i));
+                        (var.clone(), Immediate::Var(var))
+                    })
+                    .unzip();
+                self.prove(&prim, &args, &args_var);
+                let (dest, next) = self.kont_to_block(k);
+                let prim1_integer_one = |prim: ssa::Prim2, next| {
+                    Self::assert_type(Type::Int, &args_imm[0], {
+                        let dest = dest.clone();
+                        let imm = Immediate::integer(1);
+                        let op = Operation::Prim2(prim, args_imm[0].to_owned(), imm);
+                        BlockBody::Operation {
+                            dest,
+                            op,
+                            next: Box::new(next),
+                            ana: Nil,
+                        }
+                    })
+                };
+                let prim2 = |prim: ssa::Prim2, next| {
+                    let dest = dest.clone();
+                    let op = Operation::Prim2(prim, args_imm[0].to_owned(), args_imm[1].to_owned());
+                    BlockBody::Operation {
+                        dest,
+                        op,
+                        next: Box::new(next),
+                        ana: Nil,
+                    }
+                };
+                let prim2_kont = |prim: ssa::Prim2, imms: &[Immediate<VarName>], (dest, next)| {
+                    let op = Operation::Prim2(prim, imms[0].to_owned(), imms[1].to_owned());
+                    BlockBody::Operation {
+                        dest,
+                        op,
+                        next: Box::new(next),
+                        ana: Nil,
+                    }
+                };
+                let prim2_compare = |lowerer: &mut Lowerer, prim: ssa::Prim2, next| {
+                    let tagged = lowerer.names.fresh_var("tagged");
+                    Self::assert_type_multi(
+                        Type::Int,
+                        &args_imm,
+                        prim2_kont(
+                            prim,
+                            &args_imm,
+                            (
+                                tagged.clone(),
+                                lowerer.tagging(
+                                    &Immediate::Var(tagged),
+                                    Type::Bool,
+                                    Continuation::Block(dest.clone(), next),
+                                ),
+                            ),
+                        ),
+                    )
+                };
+                let prim2_equality = |lowerer: &mut Lowerer, prim: ssa::Prim2, next| {
+                    let tagged = lowerer.names.fresh_var("tagged");
+                    prim2_kont(
+                        prim,
+                        &args_imm,
+                        (
+                            tagged.clone(),
+                            lowerer.tagging(
+                                &Immediate::Var(tagged),
+                                Type::Bool,
+                                Continuation::Block(dest.clone(), next),
+                            ),
+                        ),
+                    )
+                };
+                let block = match prim {
+                    ast::Prim::Add1
+                    | ast::Prim::Sub1
+                    | ast::Prim::Add
+                    | ast::Prim::Sub
+                    | ast::Prim::Mul
+                    | ast::Prim::Lt
+                    | ast::Prim::Le
+                    | ast::Prim::Gt
+                    | ast::Prim::Ge
+                    | ast::Prim::Eq
+                    | ast::Prim::Neq
+                    | ast::Prim::IsType(Type::Int)
+                        if self.bigint =>
+                    {
+                        self.lower_big_prim(&prim, &args_imm, dest.clone(), next)
+                    }
+                    ast::Prim::Add1 => prim1_integer_one(ssa::Prim2::Add, next),
+                    ast::Prim::Sub1 => prim1_integer_one(ssa::Prim2::Sub, next),
+                    ast::Prim::Not => Self::assert_type(
+                        Type::Bool,
+                        &args_imm[0],
+                        // dest = imm ^ 100
+                        // 0 ^ anything is itself, so the last two are 0 to maintain the tag
+                        // 1 ^ anything negates itself
+                        BlockBody::Operation {
+                            dest,
+                            op: Operation::Prim2(
+                                Prim2::BitXor,
+                                args_imm[0].to_owned(),
+                                Immediate::Const(0b100),
+                            ),
+                            next: Box::new(next),
+                            ana: Nil,
+                        },
+                    ),
+                    ast::Prim::Add => {
+                        Self::assert_type_multi(Type::Int, &args_imm, prim2(ssa::Prim2::Add, next))
+                    }
+                    ast::Prim::Sub => {
+                        Self::assert_type_multi(Type::Int, &args_imm, prim2(ssa::Prim2::Sub, next))
+                    }
+                    ast::Prim::WrappingAdd => Self::assert_type_multi(
+                        Type::Int,
+                        &args_imm,
+                        prim2(ssa::Prim2::WrappingAdd, next),
+                    ),
+                    ast::Prim::WrappingSub => Self::assert_type_multi(
+                        Type::Int,
+                        &args_imm,
+                        prim2(ssa::Prim2::WrappingSub, next),
+                    ),
+                    ast::Prim::SaturatingAdd => Self::assert_type_multi(
+                        Type::Int,
+                        &args_imm,
+                        prim2(ssa::Prim2::SaturatingAdd, next),
+                    ),
+                    ast::Prim::SaturatingSub => Self::assert_type_multi(
+                        Type::Int,
+                        &args_imm,
+                        prim2(ssa::Prim2::SaturatingSub, next),
+                    ),
+                    ast::Prim::Mul | ast::Prim::WrappingMul | ast::Prim::SaturatingMul => {
+                        let mul = match prim {
+                            ast::Prim::WrappingMul => ssa::Prim2::WrappingMul,
+                            ast::Prim::SaturatingMul => ssa::Prim2::SaturatingMul,
+                            _ => ssa::Prim2::Mul,
+                        };
+                        Self::assert_type_multi(Type::Int, &args_imm, {
+                            let half = self.names.fresh_var("half");
+                            BlockBody::Operation {
+                                // half = imm0 >> 1
+                                dest: half.clone(),
+                                op: Operation::Prim1(ssa::Prim1::BitSar(1), args_imm[0].to_owned()),
+                                next: Box::new(prim2_kont(
+                                    mul,
+                                    &[Immediate::Var(half), args_imm[1].to_owned()],
+                                    (dest, next),
+                                )),
+                                ana: Nil,
+                            }
+                        })
+                    }
+                    ast::Prim::And => Self::assert_type_multi(
+                        Type::Bool,
+                        &args_imm,
+                        prim2(ssa::Prim2::BitAnd, next),
+                    ),
+                    ast::Prim::Or => Self::assert_type_multi(
+                        Type::Bool,
+                        &args_imm,
+                        prim2(ssa::Prim2::BitOr, next),
+                    ),
+                    ast::Prim::Lt => prim2_compare(self, ssa::Prim2::Lt, next),
+                    ast::Prim::Le => prim2_compare(self, ssa::Prim2::Le, next),
+                    ast::Prim::Gt => prim2_compare(self, ssa::Prim2::Gt, next),
+                    ast::Prim::Ge => prim2_compare(self, ssa::Prim2::Ge, next),
+                    ast::Prim::Eq => prim2_equality(self, ssa::Prim2::Eq, next),
+                    ast::Prim::Neq => prim2_equality(self, ssa::Prim2::Neq, next),
+
+                    ast::Prim::None => BlockBody::Operation {
+                        // dest = none
+                        dest,
+                        op: Operation::Immediate(Immediate::Const(NONE)),
+                        next: Box::new(next),
+                        ana: Nil,
+                    },
+                    ast::Prim::Str(s) if self.makes_strings => {
+                        // dest = snake_to_str("..."), the first string of its
+                        // contents that the program made, so that equal strings
+                        // stay equal words
+                        let lit = self.names.fresh_var("lit");
+                        BlockBody::Operation {
+                            dest: lit.clone(),
+                            op: Operation::Str(s.clone()),
+                            next: Box::new(self.runtime_call(
+                                "snake_to_str",
+                                vec![Immediate::Var(lit)],
+                                dest,
+                                next,
+                            )),
+                            ana: Nil,
+                        }
+                    }
+                    ast::Prim::Str(s) => BlockBody::Operation {
+                        // dest = "..."
+                        dest,
+                        op: Operation::Str(s.clone()),
+                        next: Box::new(next),
+                        ana: Nil,
+                    },
+                    ast::Prim::IsNone => {
+                        let is_none = self.names.fresh_var("is_none");
+                        BlockBody::Operation {
+                            dest: is_none.clone(),
+                            // is_none = imm == none
+                            op: Operation::Prim2(
+                                ssa::Prim2::Eq,
+                                args_imm[0].to_owned(),
+                                Immediate::Const(NONE),
+                            ),
+                            next: Box::new(self.tagging(
+                                &Immediate::Var(is_none),
+                                Type::Bool,
+                                Continuation::Block(dest, next),
+                            )),
+                            ana: Nil,
+                        }
+                    }
+                    ast::Prim::IsType(ty) => {
+                        let dest = dest.clone();
+                        // maybe we can avoid using `test`
+                        let tag = self.names.fresh_var("tag");
+                        let is_tag = self.names.fresh_var("is_tag");
+                        BlockBody::Operation {
+                            dest: tag.clone(),
+                            // tag = imm & mask
+                            op: Operation::Prim2(
+                                ssa::Prim2::BitAnd,
+                                args_imm[0].to_owned(),
+                                Immediate::raw(ty.mask() as usize),
+                            ),
+                            next: Box::new(BlockBody::Operation {
+                                dest: is_tag.clone(),
+                                // is_tag = tag == <tag>
+                                op: Operation::Prim2(
+                                    ssa::Prim2::Eq,
+                                    Immediate::Var(tag),
+                                    Immediate::raw(ty.tag() as usize),
+                                ),
+                                next: Box::new(self.tagging(
+                                    &Immediate::Var(is_tag),
+                                    Type::Bool,
+                                    Continuation::Block(dest, next),
+                                )),
+                                ana: Nil,
+                            }),
+                            ana: Nil,
+                        }
+                    }
+                    // big integers pass the addition of 0, which checks
+                    ast::Prim::Check(Type::Int) if self.bigint => self.runtime_call(
+                        "snake_big_add",
+                        vec![args_imm[0].to_owned(), Immediate::integer(0)],
+                        dest,
+                        next,
+                    ),
+                    ast::Prim::Check(ty) => Self::assert_type(
+                        ty,
+                        &args_imm[0],
+                        BlockBody::Operation {
+                            // dest = imm
+                            dest,
+                            op: Operation::Immediate(args_imm[0].to_owned()),
+                            next: Box::new(next),
+                            ana: Nil,
+                        },
+                    ),
+                    ast::Prim::NewArray => {
+                        let len = self.names.fresh_var("len");
+                        let arr = self.names.fresh_var("arr");
+                        let tagged_arr = self.tagging(
+                            &Immediate::Var(arr.clone()),
+                            Type::Array,
+                            Continuation::Block(dest, next),
+                        );
+                        Self::assert_type(
+                            Type::Int,
+                            // assertInt(num)
+                            &args_imm[0],
+                            self.untagging(
+                                Type::Int,
+                                &args_imm[0],
+                                Continuation::Block(
+                                    len.clone(),
+                                    BlockBody::AssertLength {
+                                        // assertLength(len)
+                                        len: Immediate::Var(len.clone()),
+                                        next: Box::new(BlockBody::Operation {
+                                            dest: arr,
+                                            // arr = allocateArray(len)
+                                            op: Operation::AllocateArray {
+                                                len: Immediate::Var(len.to_owned()),
+                                            },
+                                            next: Box::new(tagged_arr),
+                                            ana: Nil,
+                                        }),
+                                        ana: Nil,
+                                    },
+                                ),
+                            ),
+                        )
+                    }
+                    ast::Prim::MakeArray => {
+                        let arr = self.names.fresh_var("arr");
+                        let len = Immediate::raw(args.len());
+                        let stores = |next| {
+                            (0..args.len()).rev().fold(next, |next, i| {
+                                let imm = args_imm[i].to_owned();
+                                BlockBody::Store {
+                                    // store(arr, i + 1, imm)
+                                    addr: Immediate::Var(arr.clone()),
+                                    offset: Immediate::raw(i + 1),
+                                    val: imm,
+                                    next: Box::new(next),
+                                    ana: Nil,
+                                }
+                            })
+                        };
+                        BlockBody::Operation {
+                            // allocateArray(len)
+                            dest: arr.clone(),
+                            op: Operation::AllocateArray {
+                                len: len.to_owned(),
+                            },
+                            next: Box::new(stores(self.tagging(
+                                &Immediate::Var(arr.clone()),
+                                Type::Array,
+                                Continuation::Block(dest, next),
+                            ))),
+                            ana: Nil,
+                        }
+                    }
+                    ast::Prim::ArrayGet => {
+                        let arr = self.names.fresh_var("arr");
+                        let len = self.names.fresh_var("len");
+                        let idx = self.names.fresh_var("idx");
+                        let off = self.names.fresh_var("off");
+                        let load_by_idx = self.untagging(
+                            Type::Int,
+                            &args_imm[1],
+                            Continuation::Block(
+                                idx.clone(),
+                                BlockBody::AssertInBounds {
+                                    bound: Immediate::Var(len.clone()),
+                                    // assertInBounds(len, idx)
+                                    arg: Immediate::Var(idx.clone()),
+                                    next: Box::new(BlockBody::Operation {
+                                        dest: off.clone(),
+                                        // off = idx + 1
+                                        op: Operation::Prim2(
+                                            ssa::Prim2::Add,
+                                            Immediate::Var(idx.clone()),
+                                            Immediate::raw(1),
+                                        ),
+                                        next: Box::new(BlockBody::Operation {
+                                            dest,
+                                            // res = load(arr, off)
+                                            op: Operation::Load {
+                                                addr: Immediate::Var(arr.clone()),
+                                                offset: Immediate::Var(off),
+                                            },
+                                            next: Box::new(next),
+                                            ana: Nil,
+                                        }),
+                                        ana: Nil,
+                                    }),
+                                    ana: Nil,
+                                },
+                            ),
+                        );
+
+                        Self::assert_types(
+                            // assertArray(imm0)
+                            // assertInt(imm1)
+                            [Type::Array, Type::Int],
+                            &args_imm,
+                            self.untagging(
+                                Type::Array,
+                                &args_imm[0],
+                                Continuation::Block(
+                                    arr.clone(),
+                                    BlockBody::Operation {
+                                        // len = load(arr, 0)
+                                        dest: len.clone(),
+                                        op: Operation::Load {
+                                            addr: Immediate::Var(arr.clone()),
+                                            offset: Immediate::raw(0),
+                                        },
+                                        next: Box::new(load_by_idx),
+                                        ana: Nil,
+                                    },
+                                ),
+                            ),
+                        )
+                    }
+                    ast::Prim::ArraySet => {
+                        let arr = self.names.fresh_var("arr");
+                        let len = self.names.fresh_var("len");
+                        let idx = self.names.fresh_var("idx");
+                        let off = self.names.fresh_var("off");
+                        let store_by_idx = self.untagging(
+                            Type::Int,
+                            &args_imm[1],
+                            Continuation::Block(
+                                idx.clone(),
+                                BlockBody::AssertInBounds {
+                                    // assertInBounds(len, idx)
+                                    bound: Immediate::Var(len.clone()),
+                                    arg: Immediate::Var(idx.clone()),
+                                    next: Box::new(BlockBody::Operation {
+                                        // off = idx + 1
+                                        dest: off.clone(),
+                                        op: Operation::Prim2(
+                                            ssa::Prim2::Add,
+                                            Immediate::Var(idx.clone()),
+                                            Immediate::raw(1),
+                                        ),
+                                        next: Box::new(BlockBody::Store {
+                                            // store(arr, off, imm2)
+                                            addr: Immediate::Var(arr.clone()),
+                                            offset: Immediate::Var(off),
+                                            val: args_imm[2].to_owned(),
+                                            next: Box::new(BlockBody::Operation {
+                                                // dest = imm2
+                                                dest,
+                                                op: Operation::Immediate(args_imm[2].to_owned()),
+                                                next: Box::new(next),
+                                                ana: Nil,
+                                            }),
+                                            ana: Nil,
+                                        }),
+                                        ana: Nil,
+                                    }),
+                                    ana: Nil,
+                                },
+                            ),
+                        );
+
+                        Self::assert_types(
+                            [Type::Array, Type::Int],
+                            &args_imm,
+                            self.untagging(
+                                Type::Array,
+                                &args_imm[0],
+                                Continuation::Block(
+                                    arr.clone(),
+                                    BlockBody::Operation {
+                                        dest: len.clone(),
+                                        // len = load(arr, 0)
+                                        op: Operation::Load {
+                                            addr: Immediate::Var(arr.clone()),
+                                            offset: Immediate::raw(0),
+                                        },
+                                        next: Box::new(store_by_idx),
+                                        ana: Nil,
+                                    },
+                                ),
+                            ),
+                        )
+                    }
+                    ast::Prim::Length => {
+                        let arr = self.names.fresh_var("arr");
+                        let len = self.names.fresh_var("len");
+                        let load_len_int = BlockBody::Operation {
+                            dest: len.clone(),
+                            op: Operation::Load {
+                                addr: Immediate::Var(arr.clone()),
+                                offset: Immediate::raw(0),
+                            },
+                            next: Box::new(self.tagging(
+                                &Immediate::Var(len),
+                                Type::Int,
+                                Continuation::Block(dest, next),
+                            )),
+                            ana: Nil,
+                        };
+                        Self::assert_type(
+                            Type::Array,
+                            &args_imm[0],
+                            self.untagging(
+                                Type::Array,
+                                &args_imm[0],
+                                Continuation::Block(arr, load_len_int),
+                            ),
+                        )
+                    }
+                    ast::Prim::GetEnv => {
+                        self.runtime_calls.insert("snake_getenv", 1);
+                        Self::assert_type(
+                            Type::Array,
+                            &args_imm[0],
+                            // dest = snake_getenv(name)
+                            BlockBody::Operation {
+                                dest,
+                                op: Operation::Call {
+                                    fun: FunName::unmangled("snake_getenv"),
+                                    args: vec![args_imm[0].to_owned()],
+                                },
+                                next: Box::new(next),
+                                ana: Nil,
+                            },
+                        )
+                    }
+                    ast::Prim::Clock => {
+                        self.runtime_calls.insert("snake_clock", 0);
+                        // dest = snake_clock()
+                        BlockBody::Operation {
+                            dest,
+                            op: Operation::Call {
+                                fun: FunName::unmangled("snake_clock"),
+                                args: vec![],
+                            },
+                            next: Box::new(next),
+                            ana: Nil,
+                        }
+                    }
+                    ast::Prim::Random => {
+                        self.runtime_calls.insert("snake_random", 1);
+                        Self::assert_type(
+                            Type::Int,
+                            &args_imm[0],
+                            // dest = snake_random(n)
+                            BlockBody::Operation {
+                                dest,
+                                op: Operation::Call {
+                                    fun: FunName::unmangled("snake_random"),
+                                    args: vec![args_imm[0].to_owned()],
+                                },
+                                next: Box::new(next),
+                                ana: Nil,
+                            },
+                        )
+                    }
+                    ast::Prim::ToStr => {
+                        // dest = snake_to_str(v)
+                        self.runtime_call("snake_to_str", args_imm.to_vec(), dest, next)
+                    }
+                    ast::Prim::Concat => {
+                        // dest = snake_str_concat(a, b)
+                        self.runtime_call("snake_str_concat", args_imm.to_vec(), dest, next)
+                    }
+                    ast::Prim::Yield | ast::Prim::Next => {
+                        unreachable!("generators are compiled away after resolution")
+                    }
+                };
+
+                // backwards, from the last argument
+                done.push(block);
+                let operands = args.into_iter().zip(args_var).collect();
+                return work.push(Lowering::Operands {
+                    operands,
+                    live,
+                    subst,
+                });
+            }
+            Expr::Let {
+                bindings,
+                body,
+                loc: _,
+            } => {
+                // a let whose body is a let lowers with it, without recursing down the chain
+                let mut lets = vec![bindings];
+                let mut body = *body;
+                while let Expr::Let {
+                    bindings,
+                    body: inner,
+                    loc: _,
+                } = body
+                {
+                    lets.push(bindings);
+                    body = *inner;
+                }
+
+                // collect the live variables up to this point
+                let live = live
+                    .iter()
+                    .cloned()
+                    .chain(lets.iter().flatten().flat_map(
+                        |Binding {
+                             var: Spanned { node: var, .. },
+                             elems,
+                             ..
+                         }| {
+                            std::iter::once(var.clone())
+                                .chain(elems.iter().flatten().map(|elem| elem.node.clone()))
+                        },
+                    ))
+                    .collect::<Vec<_>>();
+                let live = Live::from(live);
+
+                // backwards, here we go, and then the bindings reversed
+                work.push(Lowering::Bindings {
+                    bindings: lets.into_iter().flatten().collect(),
+                    live: live.clone(),
+                    subst: subst.clone(),
+                });
+                return work.push(Lowering::Expr {
+                    e: body,
+                    live,
+                    subst,
+                    k,
+                });
+            }
+            Expr::If {
+                cond,
+                thn,
+                els,
+                loc: _,
+            } => {
+                let cond_var = self.names.fresh_var("cond");
+                let flag_var = self.names.fresh_var("flag");
+                let thn_name = self.names.fresh_block("thn");
+                let els_name = self.names.fresh_block("els");
+                let untagged_cbr = self.untagging(
+                    Type::Bool,
+                    &Immediate::Var(cond_var.clone()),
+                    Continuation::Block(
+                        flag_var.clone(),
+                        BlockBody::Terminator(
+                            Terminator::ConditionalBranch {
+                                cond: Immediate::Var(flag_var.clone()),
+                                thn: thn_name.clone(),
+                                els: els_name.clone(),
+                            },
+                            Nil,
+                        ),
+                    ),
+                );
+                work.push(Lowering::If {
+                    thn: *thn,
+                    els: *els,
+                    labels: [thn_name, els_name],
+                    live: live.clone(),
+                    subst: subst.clone(),
+                    k,
+                });
+                return work.push(Lowering::Expr {
+                    e: *cond,
+                    live,
+                    subst,
+                    k: Continuation::Block(
+                        cond_var.clone(),
+                        Self::assert_type(
+                            Type::Bool,
+                            &Immediate::Var(cond_var.clone()),
+                            untagged_cbr,
+                        ),
+                    ),
+                });
+            }
+            Expr::Switch {
+                scrutinee,
+                cases,
+                default,
+                loc: _,
+            } => {
+                let scrut_var = self.names.fresh_var("scrutinee");
+                let default_name = self.names.fresh_block("default");
+                let mut arms: Vec<_> = cases
+                    .into_iter()
+                    .map(
+                        |Case {
+                             label: Spanned { node: n, .. },
+                             body,
+                         }| (n, self.names.fresh_block("case"), body),
+                    )
+                    .collect();
+                arms.sort_by_key(|(n, ..)| *n);
+                let labels: Vec<_> = arms.iter().map(|(n, name, _)| (*n, name.clone())).collect();
+
+                // dispatch on the scrutinee once it is known to be an integer
+                let mut dispatch_blocks = Vec::new();
+                let dispatch =
+                    self.lower_dispatch(&scrut_var, &labels, &default_name, &mut dispatch_blocks);
+                let dispatch = BlockBody::SubBlocks {
+                    blocks: dispatch_blocks,
+                    next: Box::new(Self::assert_type(
+                        Type::Int,
+                        &Immediate::Var(scrut_var.clone()),
+                        dispatch,
+                    )),
+                    ana: Nil,
+                };
+                let dispatch = if self.bigint {
+                    // big integers match none of the cases, which all fit in 63 bits
+                    self.lower_big_scrutinee(&scrut_var, &default_name, dispatch)
+                } else {
+                    dispatch
+                };
+                let arms = arms
+                    .into_iter()
+                    .map(|(_, name, body)| (name, body))
+                    .chain([(default_name, *default)])
+                    .collect();
+                work.push(Lowering::Switch {
+                    arms,
+                    live: live.clone(),
+                    subst: subst.clone(),
+                    k,
+                });
+                return work.push(Lowering::Expr {
+                    e: *scrutinee,
+                    live,
+                    subst,
+                    k: Continuation::Block(scrut_var, dispatch),
+                });
+            }
+            Expr::Match { .. } => unreachable!("matches are compiled before lowering"),
+            Expr::FunDefs {
+                decls,
+                body,
+                loc: _,
+            } => {
+                // create a block name for each function
+                for FunDecl { name: fun, .. } in decls.iter() {
+                    let block = self.names.fresh_block(fun.hint());
+                    self.fun_as_block.insert(fun.clone(), block);
+                    // collect the live variables up to this point
+                    self.fun_scopes.insert(fun.clone(), live.to_vec());
+                }
+                // lower the body, and then the functions
+                work.push(Lowering::Functions {
+                    decls: decls.into_iter().rev().collect(),
+                    live: live.clone(),
+                    subst: subst.clone(),
+                    next: None,
+                    blocks: Vec::new(),
+                    pending: None,
+                });
+                return work.push(Lowering::Expr {
+                    e: *body,
+                    live,
+                    subst,
+                    k,
+                });
+            }
+            Expr::Call { fun, args, loc: _ } => {
+                // prepare the arguments
+                let (args_var, args_imm): (Vec<_>, _) = args
+                    .iter()
+                    .enumerate()
+                    .map(|(i, _arg)| {
+                        // the arguments are named after the function name and the argument index
+                        let var = self.names.fresh_var(format!("{}_{}", fun.hint(), i));
+                        (var.clone(), Immediate::Var(var))
+                    })
+                    .unzip();
+                let block = if fun.is_unmangled() {
+                    // extern function. Always produce a call here, of its
+                    // shim if it has one
+                    let fun = self.shims.get(&fun).cloned().unwrap_or(fun);
+                    let (dest, next) = self.kont_to_block(k);
+                    BlockBody::Operation {
+                        dest,
+                        op: Operation::Call {
+                            fun,
+                            args: args_imm,
+                        },
+                        next: Box::new(next),
+                        ana: Nil,
+                    }
+                } else {
+                    let block = self.fun_as_block.get(&fun).cloned().expect("fun not found");
+                    if self.should_lift.contains(&fun) {
+                        let ambient = self
+                            .fun_scopes
+                            .get(&fun)
+                            .cloned()
+                            .expect("fun not found")
+                            .into_iter()
+                            .map(|v| subst.run(v));
+                        // the arguments are the ambient live variables and the arguments combined
+                        let args_imm = ambient
+                            .map(|v| Immediate::Var(v))
+                            .chain(args_imm)
+                            .collect::<Vec<_>>();
+
+                        match k {
+                            Continuation::Return => BlockBody::Terminator(
+                                Terminator::Branch(Branch {
+                                    target: block,
+                                    args: args_imm,
+                                }),
+                                Nil,
+                            ),
+                            Continuation::Block(dest, next) => BlockBody::Operation {
+                                dest,
+                                op: Operation::Call {
+                                    fun,
+                                    args: args_imm,
+                                },
+                                next: Box::new(next),
+                                ana: Nil,
+                            },
+                        }
+                    } else {
+                        // tail calls are compiled to a branch
+                        assert!(matches!(k, Continuation::Return));
+                        BlockBody::Terminator(
+                            Terminator::Branch(Branch {
+                                target: block,
+                                args: args_imm,
+                            }),
+                            Nil,
+                        )
+                    }
+                };
+                // backwards, from the last argument
+                done.push(block);
+                let operands = args.into_iter().zip(args_var).collect();
+                return work.push(Lowering::Operands {
+                    operands,
+                    live,
+                    subst,
+                });
+            }
+        };
+        done.push(block);
+    }
+
+    // shorthand for calling a runtime function, declared after lowering
+    fn runtime_call(
+        &mut self,
+        fun: &'static str,
+        args: Vec<Immediate<VarName>>,
+        dest: VarName,
+        next: BlockBody<VarName, Nil>,
+    ) -> BlockBody<VarName, Nil> {
+        self.runtime_calls.insert(fun, args.len());
+        BlockBody::Operation {
+            dest,
+            op: Operation::Call {
+                fun: FunName::unmangled(fun),
+                args,
+            },
+            next: Box::new(next),
+            ana: Nil,
+        }
+    }
+
+    /// Lowers the integer primitives under `--bigint`. The runtime checks
+    /// that the operands are numbers, small or big, and returns small
+    /// integers whenever the results fit.
+    fn lower_big_prim(
+        &mut self,
+        prim: &ast::Prim,
+        args: &[Immediate<VarName>],
+        dest: VarName,
+        next: BlockBody<VarName, Nil>,
+    ) -> BlockBody<VarName, Nil> {
+        let one = Immediate::integer(1);
+        let compare = |lowerer: &mut Lowerer, prim: ssa::Prim2, dest: VarName, next| {
+            // ord = snake_big_cmp(a, b), one of -1, 0 and 1
+            // dest = ord <prim> 0
+            let ord = lowerer.names.fresh_var("ord");
+            let tagged = lowerer.names.fresh_var("tagged");
+            let cmp = BlockBody::Operation {
+                dest: tagged.clone(),
+                op: Operation::Prim2(prim, Immediate::Var(ord.clone()), Immediate::Const(0)),
+                next: Box::new(lowerer.tagging(
+                    &Immediate::Var(tagged),
+                    Type::Bool,
+                    Continuation::Block(dest, next),
+                )),
+                ana: Nil,
+            };
+            lowerer.runtime_call("snake_big_cmp", args.to_vec(), ord, cmp)
+        };
+        match prim {
+            ast::Prim::Add1 => {
+                self.runtime_call("snake_big_add", vec![args[0].to_owned(), one], dest, next)
+            }
+            ast::Prim::Sub1 => {
+                self.runtime_call("snake_big_sub", vec![args[0].to_owned(), one], dest, next)
+            }
+            ast::Prim::Add => self.runtime_call("snake_big_add", args.to_vec(), dest, next),
+            ast::Prim::Sub => self.runtime_call("snake_big_sub", args.to_vec(), dest, next),
+            ast::Prim::Mul => self.runtime_call("snake_big_mul", args.to_vec(), dest, next),
+            ast::Prim::Lt => compare(self, ssa::Prim2::Lt, dest, next),
+            ast::Prim::Le => compare(self, ssa::Prim2::Le, dest, next),
+            ast::Prim::Gt => compare(self, ssa::Prim2::Gt, dest, next),
+            ast::Prim::Ge => compare(self, ssa::Prim2::Ge, dest, next),
+            // equal values are either both small or both big
+            ast::Prim::Eq => self.runtime_call("snake_big_eq", args.to_vec(), dest, next),
+            ast::Prim::Neq => {
+                // dest = snake_big_eq(a, b) ^ 100
+                let eq = self.names.fresh_var("eq");
+                let not = BlockBody::Operation {
+                    dest,
+                    op: Operation::Prim2(
+                        Prim2::BitXor,
+                        Immediate::Var(eq.clone()),
+                        Immediate::Const(0b100),
+                    ),
+                    next: Box::new(next),
+                    ana: Nil,
+                };
+                self.runtime_call("snake_big_eq", args.to_vec(), eq, not)
+            }
+            ast::Prim::IsType(Type::Int) => {
+                // dest = snake_big_is_int(a), as strings share the tag of big
+                // integers and only their header tells them apart
+                self.runtime_call("snake_big_is_int", args.to_vec(), dest, next)
+            }
+            _ => unreachable!("not an integer primitive"),
+        }
+    }
+
+    /// Binds `elems` to the elements of the array `var`, after one check
+    /// that it is an array long enough for all of them
+    fn destructure(
+        &mut self,
+        var: &VarName,
+        elems: Vec<Spanned<VarName>>,
+        next: BlockBody<VarName, Nil>,
+    ) -> BlockBody<VarName, Nil> {
+        let arr = self.names.fresh_var("arr");
+        let len = self.names.fresh_var("len");
+        let last = elems.len() - 1;
+        // elem_i = load(arr, i + 1)
+        let loads = elems
+            .into_iter()
+            .enumerate()
+            .rev()
+            .fold(next, |next, (i, elem)| BlockBody::Operation {
+                dest: elem.node,
+                op: Operation::Load {
+                    addr: Immediate::Var(arr.clone()),
+                    offset: Immediate::raw(i + 1),
+                },
+                next: Box::new(next),
+                ana: Nil,
+            });
+        let tuple = Immediate::Var(var.clone());
+        Self::assert_type(
+            // assertArray(var)
+            Type::Array,
+            &tuple,
+            self.untagging(
+                Type::Array,
+                &tuple,
+                Continuation::Block(
+                    arr.clone(),
+                    BlockBody::Operation {
+                        // len = load(arr, 0)
+                        dest: len.clone(),
+                        op: Operation::Load {
+                            addr: Immediate::Var(arr.clone()),
+                            offset: Immediate::raw(0),
+                        },
+                        next: Box::new(BlockBody::AssertInBounds {
+                            // assertInBounds(len, last), for all of the elements
+                            bound: Immediate::Var(len),
+                            arg: Immediate::raw(last),
+                            next: Box::new(loads),
+                            ana: Nil,
+                        }),
+                        ana: Nil,
+                    },
+                ),
+            ),
+        )
+    }
+
+    // shorthands for asserting types
+    fn assert_type(
+        ty: Type,
+        of: &Immediate<VarName>,
+        next: BlockBody<VarName, Nil>,
+    ) -> BlockBody<VarName, Nil> {
+        BlockBody::AssertType {
+            ty,
+            arg: of.to_owned(),
+            next: Box::new(next),
+            ana: Nil,
+        }
+    }
+
+    /// Jumps from the tagged integer `scrut` to the block of the matching
+    /// label, or to `default`. Dense labels index a jump table, sparse ones
+    /// are searched by a tree of comparisons, whose blocks go to `blocks`.
+    fn lower_dispatch(
+        &mut self,
+        scrut: &VarName,
+        labels: &[(i64, BlockName)],
+        default: &BlockName,
+        blocks: &mut Vec<BasicBlock<VarName, Nil>>,
+    ) -> BlockBody<VarName, Nil> {
+        let (Some((low, _)), Some((high, _))) = (labels.first(), labels.last()) else {
+            return BlockBody::Terminator(
+                Terminator::Branch(Branch {
+                    target: default.clone(),
+                    args: Vec::new(),
+                }),
+                Nil,
+            );
+        };
+        let range = (high - low) as u64 + 1;
+        if labels.len() >= JUMP_TABLE_MIN_CASES && range <= 2 * labels.len() as u64 {
+            let mut targets = vec![default.clone(); range as usize];
+            for (n, name) in labels {
+                targets[(n - low) as usize] = name.clone();
+            }
+            let index = self.names.fresh_var("index");
+            // index = scrut >> 1
+            return BlockBody::Operation {
+                dest: index.clone(),
+                op: Operation::Prim1(Prim1::BitSar(1), Immediate::Var(scrut.clone())),
+                next: Box::new(BlockBody::Terminator(
+                    Terminator::Switch {
+                        scrutinee: Immediate::Var(index),
+                        low: *low,
+                        targets,
+                        default: default.clone(),
+                    },
+                    Nil,
+                )),
+                ana: Nil,
+            };
+        }
+        // compare against the middle label, then search either half
+        let (cond, thn, els) = match labels {
+            [(n, name)] => (
+                Operation::Prim2(
+                    Prim2::Eq,
+                    Immediate::Var(scrut.clone()),
+                    Immediate::integer(*n),
+                ),
+                name.clone(),
+                default.clone(),
+            ),
+            _ => {
+                let (lower, upper) = labels.split_at(labels.len() / 2);
+                let mut half = |labels| {
+                    let label = self.names.fresh_block("dispatch");
+                    let body = self.lower_dispatch(scrut, labels, default, blocks);
+                    blocks.push(BasicBlock {
+                        label: label.clone(),
+                        params: Vec::new(),
+                        body,
+                        ana: Nil,
+                    });
+                    label
+                };
+                let (thn, els) = (half(lower), half(upper));
+                let cond = Operation::Prim2(
+                    Prim2::Lt,
+                    Immediate::Var(scrut.clone()),
+                    Immediate::integer(upper[0].0),
+                );
+                (cond, thn, els)
+            }
+        };
+        let flag = self.names.fresh_var("flag");
+        BlockBody::Operation {
+            dest: flag.clone(),
+            op: cond,
+            next: Box::new(BlockBody::Terminator(
+                Terminator::ConditionalBranch {
+                    cond: Immediate::Var(flag),
+                    thn,
+                    els,
+                },
+                Nil,
+            )),
+            ana: Nil,
+        }
+    }
+
+    /// Sends the big integers among the values of `scrut` to `default`
+    /// before continuing with `next`.
+    fn lower_big_scrutinee(
+        &mut self,
+        scrut: &VarName,
+        default: &BlockName,
+        next: BlockBody<VarName, Nil>,
+    ) -> BlockBody<VarName, Nil> {
+        let tag = self.names.fresh_var("tag");
+        let is_big = self.names.fresh_var("is_big");
+        let small = self.names.fresh_block("small");
+        BlockBody::SubBlocks {
+            blocks: vec![BasicBlock {
+                label: small.clone(),
+                params: Vec::new(),
+                body: next,
+                ana: Nil,
+            }],
+            // tag = scrut & 0b111; is_big = tag == 0b111
+            next: Box::new(BlockBody::Operation {
+                dest: tag.clone(),
+                op: Operation::Prim2(
+                    Prim2::BitAnd,
+                    Immediate::Var(scrut.clone()),
+                    Immediate::Const(0b111),
+                ),
+                next: Box::new(BlockBody::Operation {
+                    dest: is_big.clone(),
+                    op: Operation::Prim2(Prim2::Eq, Immediate::Var(tag), Immediate::Const(0b111)),
+                    next: Box::new(BlockBody::Terminator(
+                        Terminator::ConditionalBranch {
+                            cond: Immediate::Var(is_big),
+                            thn: default.clone(),
+                            els: small,
+                        },
+                        Nil,
+                    )),
+                    ana: Nil,
+                }),
+                ana: Nil,
+            }),
+            ana: Nil,
+        }
+    }
+    fn assert_type_multi(
+        ty: Type,
+        of: &[Immediate<VarName>],
+        next: BlockBody<VarName, Nil>,
+    ) -> BlockBody<VarName, Nil> {
+        of.into_iter()
+            .fold(next, |block, imm| BlockBody::AssertType {
+                ty,
+                arg: imm.to_owned(),
+                next: Box::new(block),
+                ana: Nil,
+            })
+    }
+    fn assert_types<'a>(
+        ty: impl IntoIterator<Item = Type>,
+        of: impl IntoIterator<Item = &'a Immediate<VarName>>,
+        next: BlockBody<VarName, Nil>,
+    ) -> BlockBody<VarName, Nil> {
+        ty.into_iter()
+            .zip(of)
+            .fold(next, |block, (ty, imm)| BlockBody::AssertType {
+                ty,
+                arg: imm.to_owned(),
+                next: Box::new(block),
+                ana: Nil,
+            })
+    }
+
+    // tagging and untagging
+    fn tagging(
+        &mut self,
+        imm: &Immediate<VarName>,
+        ty: Type,
+        k: Continuation,
+    ) -> BlockBody<VarName, Nil> {
+        let (dest, next) = self.kont_to_block(k);
+        match ty {
+            Type::Int | Type::Bool => {
+                let shifted = self.names.fresh_var("shifted");
+                // shifted = imm << <mask_length>
+                // dest = shifted | <tag>
+                BlockBody::Operation {
+                    dest: shifted.clone(),
+                    op: Operation::Prim1(Prim1::BitSal(ty.mask_length()), imm.to_owned()),
+                    next: Box::new(BlockBody::Operation {
+                        dest,
+                        op: Operation::Prim2(
+                            Prim2::BitOr,
+                            Immediate::Var(shifted),
+                            Immediate::raw(ty.tag() as usize),
+                        ),
+                        next: Box::new(next),
+                        ana: Nil,
+                    }),
+                    ana: Nil,
+                }
+            }
+            Type::Array => {
+                // dest = imm | <tag>
+                BlockBody::Operation {
+                    dest,
+                    op: Operation::Prim2(
+                        Prim2::BitOr,
+                        imm.to_owned(),
+                        Immediate::raw(ty.tag() as usize),
+                    ),
+                    next: Box::new(next),
+                    ana: Nil,
+                }
+            }
+        }
+    }
+    fn untagging(
+        &mut self,
+        ty: Type,
+        imm: &Immediate<VarName>,
+        k: Continuation,
+    ) -> BlockBody<VarName, Nil> {
+        let (dest, next) = self.kont_to_block(k);
+        match ty {
+            Type::Int | Type::Bool => {
+                // dest = imm >> <mask_length>
+                BlockBody::Operation {
+                    dest,
+                    op: Operation::Prim1(Prim1::BitSar(ty.mask_length()), imm.to_owned()),
+                    next: Box::new(next),
+                    ana: Nil,
+                }
+            }
+            Type::Array => {
+                // dest = imm ^ <tag>
+                BlockBody::Operation {
+                    dest,
+                    op: Operation::Prim2(
+                        Prim2::BitXor,
+                        imm.to_owned(),
+                        Immediate::raw(ty.tag() as usize),
+                    ),
+                    next: Box::new(next),
+                    ana: Nil,
+                }
+            }
+        }
+    }
+}
+
+/*
+ * A simple abstraction of sets of 64-bit integers for Assertion Removal.
+ * */
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum PossibleValues {
+    Any,  // The set of all i64 values, i.e., the value could be anything
+    Even, // The set of all *even* i64 values, i.e., the tagged integers
+    None, // The empty set, i.e., the value is never set
+}
+
+impl PossibleValues {
+    /// The least PossibleValues
+    fn bottom() -> Self {
+        todo!("PossibleValues::bottom")
+    }
+
+    /// mutably update self to be its least upper bound with other
+    fn lub_mut(&mut self, other: Self) {
+        todo!("PossibleValues::lub_mut")
+    }
+}
+
+// The PossibleValuesEnv is stored at every node
+// The HashMap not containing a name is considered equivalent to that name being mapped to Bottom.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PossibleValuesEnv(HashMap<VarName, PossibleValues>);
+
+impl PossibleValuesEnv {
+    fn bottom() -> Self {
+        todo!("PossibleValuesEnv::bottom")
+    }
+
+    /// mutably update self to be its least upper bound with other
+    fn lub_mut(&mut self, other: Self) {
+        todo!("PossibleValuesEnv::lub_mut")
+    }
+
+    // Produces the possible values an immediate may have based on the
+    // current environment information
+    fn possible_values(&self, imm: &Immediate<VarName>) -> PossibleValues {
+        todo!("PossibleValuesEnv::possible_values")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PVRoundSummary {
+    // maps each block to the latest assumptions
+    block_info: HashMap<BlockName, PossibleValuesEnv>,
+}
+
+impl Default for PVRoundSummary {
+    fn default() -> Self {
+        PVRoundSummary {
+            block_info: HashMap::new(),
+        }
+    }
+}
+
+pub struct AssertionRemover {
+    // The result of the previous round of analysis for blocks
+    previous: PVRoundSummary,
+    current: PVRoundSummary,
+    // The names of the parameters for every block in the program
+    block_arg_names: HashMap<BlockName, Vec<VarName>>,
+}
+
+impl AssertionRemover {
+    pub fn new<T>(prog: &Program<VarName, T>) -> Self {
+        fn find_names_prog<T>(
+            p: &Program<VarName, T>,
+            blocks: &mut HashMap<BlockName, Vec<VarName>>,
+        ) {
+            for block in p.blocks.iter() {
+                find_names_basic_block(&block, blocks);
+            }
+        }
+        fn find_names_block_body<T>(
+            b: &BlockBody<VarName, T>,
+            blocks: &mut HashMap<BlockName, Vec<VarName>>,
+        ) {
+            use BlockBody::*;
+            match b {
+                Terminator(..) => {}
+                Operation { next, .. }
+                | AssertType { next, .. }
+                | AssertLength { next, .. }
+                | AssertInBounds { next, .. }
+                | Store { next, .. } => find_names_block_body(next, blocks),
+                SubBlocks {
+                    blocks: sub_blocks,
+                    next,
+                    ..
+                } => {
+                    for block in sub_blocks.iter() {
+                        find_names_basic_block(&block, blocks);
+                    }
+                    find_names_block_body(next, blocks)
+                }
+            }
+        }
+        fn find_names_basic_block<T>(
+            b: &BasicBlock<VarName, T>,
+            blocks: &mut HashMap<BlockName, Vec<VarName>>,
+        ) {
+            blocks.insert(b.label.clone(), b.params.clone());
+            find_names_block_body(&b.body, blocks);
+        }
+        let mut block_arg_names = HashMap::new();
+        find_names_prog(prog, &mut block_arg_names);
+        let mut previous = PVRoundSummary::default();
+        for (l, _) in block_arg_names.iter() {
+            previous
+                .block_info
+                .insert(l.clone(), PossibleValuesEnv::bottom());
+        }
+        Self {
+            previous,
+            current: PVRoundSummary::default(),
+            block_arg_names,
+        }
+    }
+
+    // Removes assertions that can be proven to always succeed at runtime.
+    pub fn optimize(&mut self, prog: Program<VarName, Nil>) -> Program<VarName, Nil> {
+        let analyzed_prog = self.analyze(prog);
+        // println!("Analyzed:\n{}", analyzed_prog);
+        self.remove_assertions(analyzed_prog)
+    }
+
+    // Performs the possible-values analysis by fixed-point iteration
+    fn analyze(&mut self, prog: Program<VarName, Nil>) -> Program<VarName, PossibleValuesEnv> {
+        let mut prog = self.analyze_prog(prog);
+        while self.previous != self.current {
+            self.previous = std::mem::take(&mut self.current);
+            prog = self.analyze_prog(prog);
+        }
+        prog
+    }
+
+    // Fills in dataflow analysis information as well as updating self.current
+
+    // the input is generic in T because we don't need the previous
+    // round's liveness information everywhere, only the summary in
+    // self.previous.
+    fn analyze_prog<T>(
+        &mut self,
+        prog: Program<VarName, T>,
+    ) -> Program<VarName, PossibleValuesEnv> {
+        // println!("Summary: {:?}", self.previous);
+        // initialize the current summary to be trivial
+        for (l, _) in self.block_arg_names.iter() {
+            self.current
+                .block_info
+                .insert(l.clone(), PossibleValuesEnv::bottom());
+        }
+        for f in prog.funs.iter() {
+            self.analyze_fun(f);
+        }
+        Program {
+            blocks: prog
+                .blocks
+                .into_iter()
+                .map(|b| self.analyze_basic_block(b))
+                .collect(),
+            externs: prog.externs,
+            funs: prog.funs,
+        }
+    }
+
+    // Assumes all functions can have arbitrary (Any) inputs.
+    fn analyze_fun(&mut self, f: &FunBlock<VarName>) {
+        let args: Vec<PossibleValues> = f.params.iter().map(|_| PossibleValues::Any).collect();
+        self.flow_branch(&f.body.target, &args, &PossibleValuesEnv::bottom());
+    }
+
+    fn analyze_basic_block<T>(
+        &mut self,
+        b: BasicBlock<VarName, T>,
+    ) -> BasicBlock<VarName, PossibleValuesEnv> {
+        let pre = self.previous.block_info.get(&b.label).unwrap().clone();
+        let body = self.analyze_block_body(b.body, &pre);
+        BasicBlock {
+            label: b.label,
+            params: b.params,
+            ana: pre,
+            body,
+        }
+    }
+
+    // update the BlockBody's info, with pre being the information we
+    // know previous to runnning this BlockBody
+    fn analyze_block_body<T>(
+        &mut self,
+        b: BlockBody<VarName, T>,
+        pre: &PossibleValuesEnv,
+    ) -> BlockBody<VarName, PossibleValuesEnv> {
+        use ssa::BlockBody::*;
+        match b {
+            Terminator(t, _) => {
+                self.flow_terminator(&t, pre);
+                Terminator(t, pre.clone())
+            }
+            Operation { dest, op, next, .. } => {
+                let post = self.flow_operation(&dest, &op, pre);
+                Operation {
+                    dest,
+                    op,
+                    next: Box::new(self.analyze_block_body(*next, &post)),
+                    ana: post,
+                }
+            }
+            SubBlocks { blocks, next, .. } => SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|b| self.analyze_basic_block(b))
+                    .collect(),
+                next: Box::new(self.analyze_block_body(*next, pre)),
+                ana: pre.clone(),
+            },
+
+            AssertType { ty, arg, next, .. } => {
+                todo!("implement analysis for AssertType")
+            }
+            AssertLength { len, next, .. } => {
+                todo!("implement AssertLength analysis")
+            }
+            AssertInBounds {
+                bound, arg, next, ..
+            } => {
+                todo!("implement AssertInBounds analysis")
+            }
+            Store {
+                addr,
+                offset,
+                val,
+                next,
+                ..
+            } => {
+                todo!("implement Store analysis")
+            }
+        }
+    }
+
+    // Computes the flow function for a branch.
+
+    // Since this is a forwards dataflow analysis, this works by
+    // refining the information in the *target* of the branch.
+    //
+    // The arguments provided are the possible values of the arguments to the branch
+    fn flow_branch(
+        &mut self,
+        target: &BlockName,
+        args: &[PossibleValues],
+        pre: &PossibleValuesEnv,
+    ) {
+        let targ_params = self.block_arg_names.get(target).unwrap();
+        let mut post = pre.clone();
+        for (name, pv) in targ_params.iter().zip(args) {
+            post.0.insert(name.clone(), *pv);
+        }
+        let targ_info = self.current.block_info.get_mut(target).unwrap();
+        targ_info.lub_mut(post);
+    }
+
+    // Compute the flow function for the terminator.
+    //
+    // The flow function doesn't directly output anything, instead it
+    // should mutably update any blocks that the terminator may branch
+    // to.
+    fn flow_terminator(&mut self, t: &Terminator<VarName>, pre: &PossibleValuesEnv) {
+        use ssa::Terminator::*;
+        match t {
+            Return(_imm) => {}
+            Branch(b) => {
+                let arg_pvs: Vec<PossibleValues> =
+                    b.args.iter().map(|imm| pre.possible_values(imm)).collect();
+                self.flow_branch(&b.target, &arg_pvs, pre)
+            }
+            ConditionalBranch { cond, thn, els } => match pre.possible_values(cond) {
+                PossibleValues::None => {}
+                _ => {
+                    self.flow_branch(&thn, &[], pre);
+                    self.flow_branch(&els, &[], pre);
+                }
+            },
+            Switch {
+                scrutinee,
+                targets,
+                default,
+                ..
+            } => match pre.possible_values(scrutinee) {
+                PossibleValues::None => {}
+                _ => {
+                    for target in targets.iter().chain([default]) {
+                        self.flow_branch(target, &[], pre);
+                    }
+                }
+            },
+        }
+    }
+
+    // Compute the flow function for an operation.
+    fn flow_operation(
+        &mut self,
+        dest: &VarName,
+        op: &Operation<VarName>,
+        pre: &PossibleValuesEnv,
+    ) -> PossibleValuesEnv {
+        todo!("implement flow_operation")
+    }
+
+    /// Removes AssertInt assertions that are guaranteed by the dataflow analysis to succeed
+    fn remove_assertions(
+        &self,
+        prog: Program<VarName, PossibleValuesEnv>,
+    ) -> Program<VarName, Nil> {
+        todo!("implement remove_assertions")
+    }
+}
+
+/*
+ * Copy propagation
+ * */
+pub struct CopyPropagator {
+    // var_l = var_r, so all uses of var_l should be replaced with var_r
+    vars: HashMap<VarName, VarName>,
+}
+
+impl CopyPropagator {
+    pub fn new() -> Self {
+        Self {
+            vars: HashMap::new(),
+        }
+    }
+
+    pub fn query(&self, var: &VarName) -> Option<VarName> {
+        if let Some(subst) = self.vars.get(var) {
+            Some(self.query(subst).unwrap_or_else(|| subst.clone()))
+        } else {
+            None
+        }
+    }
+
+    pub fn run(&mut self, mut prog: Program<VarName, Nil>) -> Program<VarName, Nil> {
+        prog.blocks = prog
+            .blocks
+            .into_iter()
+            .map(|block| self.run_block(block))
+            .collect();
+        prog
+    }
+
+    fn run_block(&mut self, mut block: BasicBlock<VarName, Nil>) -> BasicBlock<VarName, Nil> {
+        block.body = self.run_block_body(block.body);
+        block
+    }
+
+    fn run_block_body(&mut self, body: BlockBody<VarName, Nil>) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::Terminator(terminator, ana) => {
+                BlockBody::Terminator(self.run_terminator(terminator), ana)
+            }
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => {
+                let op = match op {
+                    Operation::Immediate(imm) => match self.run_immediate(imm) {
+                        Immediate::Const(c) => Operation::Immediate(Immediate::Const(c)),
+                        Immediate::Var(v) => {
+                            self.vars.insert(dest.clone(), v.clone());
+                            return self.run_block_body(*next);
+                        }
+                    },
+                    Operation::Prim1(prim1, imm) => {
+                        Operation::Prim1(prim1, self.run_immediate(imm))
+                    }
+                    Operation::Prim2(prim2, imm1, imm2) => {
+                        Operation::Prim2(prim2, self.run_immediate(imm1), self.run_immediate(imm2))
+                    }
+                    Operation::Call { fun, args } => Operation::Call {
+                        fun,
+                        args: args
+                            .into_iter()
+                            .map(|imm| self.run_immediate(imm))
+                            .collect(),
+                    },
+                    Operation::AllocateArray { len } => Operation::AllocateArray {
+                        len: self.run_immediate(len),
+                    },
+                    Operation::Load { addr, offset } => Operation::Load {
+                        addr: self.run_immediate(addr),
+                        offset: self.run_immediate(offset),
+                    },
+                    Operation::Str(s) => Operation::Str(s),
+                };
+                BlockBody::Operation {
+                    dest,
+                    op,
+                    next: Box::new(self.run_block_body(*next)),
+                    ana,
+                }
+            }
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.run_block(block))
+                    .collect(),
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg: self.run_immediate(arg),
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len: self.run_immediate(len),
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound: self.run_immediate(bound),
+                arg: self.run_immediate(arg),
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr: self.run_immediate(addr),
+                offset: self.run_immediate(offset),
+                val: self.run_immediate(val),
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+        }
+    }
+
+    fn run_terminator(&mut self, terminator: Terminator<VarName>) -> Terminator<VarName> {
+        match terminator {
+            Terminator::Return(imm) => Terminator::Return(self.run_immediate(imm)),
+            Terminator::Branch(Branch { target, args }) => Terminator::Branch(Branch {
+                target,
+                args: args
+                    .into_iter()
+                    .map(|imm| self.run_immediate(imm))
+                    .collect(),
+            }),
+            Terminator::ConditionalBranch { cond, thn, els } => Terminator::ConditionalBranch {
+                cond: self.run_immediate(cond),
+                thn,
+                els,
+            },
+            Terminator::Switch {
+                scrutinee,
+                low,
+                targets,
+                default,
+            } => Terminator::Switch {
+                scrutinee: self.run_immediate(scrutinee),
+                low,
+                targets,
+                default,
+            },
+        }
+    }
+
+    fn run_immediate(&mut self, imm: Immediate<VarName>) -> Immediate<VarName> {
+        match imm {
+            Immediate::Var(var) => Immediate::Var(self.query(&var).unwrap_or(var)),
+            _ => imm,
+        }
+    }
+}
+
+/*
+ * Condition canonicalization
+ * */
+
+/// The negation `!x`, which lowers to `x ^ 0b100` after asserting that `x`
+/// is a boolean
+const NOT: i64 = 0b100;
+
+/// Normalizes negated conditions, so that the emitter and the branches see
+/// the comparisons themselves, and assertion removal the booleans they tag:
+/// - `!!x` is `x`,
+/// - `!(a < b)` is `a >= b`, and likewise for the other comparisons,
+/// - `!(x && y)` is `!x || !y` and `!(x || y)` is `!x && !y`, when the
+///   operands absorb their negations in turn, and
+/// - a branch on `!x` is a branch on `x` with the targets swapped.
+///
+/// Assertions that a negation is a boolean go too, since `!x` asserts `x`.
+/// Only values that nothing but the negation uses, besides assertions of
+/// their type, are negated in place, and no variables are introduced: the
+/// negation becomes a copy of its operand, which copy propagation removes.
+pub struct ConditionCanonicalizer {
+    /// The operation that defines each variable
+    defs: HashMap<VarName, Operation<VarName>>,
+    /// The number of uses of each variable, besides assertions of its type
+    uses: HashMap<VarName, usize>,
+    /// The new operations of the variables in this round
+    rewrites: HashMap<VarName, Operation<VarName>>,
+    /// The variables that the rewrites of this round read or write, which
+    /// the other rewrites wait for the next round to see
+    touched: HashSet<VarName>,
+    /// The flags of the conditional branches whose targets are swapped
+    swapped: HashSet<VarName>,
+}
+
+impl ConditionCanonicalizer {
+    pub fn new() -> Self {
+        Self {
+            defs: HashMap::new(),
+            uses: HashMap::new(),
+            rewrites: HashMap::new(),
+            touched: HashSet::new(),
+            swapped: HashSet::new(),
+        }
+    }
+
+    pub fn run(&mut self, mut prog: Program<VarName, Nil>) -> Program<VarName, Nil> {
+        loop {
+            self.defs.clear();
+            self.uses.clear();
+            self.rewrites.clear();
+            self.touched.clear();
+            self.swapped.clear();
+            for fun in prog.funs.iter() {
+                self.use_all(&fun.body.args);
+            }
+            for block in prog.blocks.iter() {
+                self.analyze(&block.body);
+            }
+            for block in prog.blocks.iter() {
+                self.canonicalize(&block.body);
+            }
+            let done = self.rewrites.is_empty() && self.swapped.is_empty();
+            prog.blocks = prog
+                .blocks
+                .into_iter()
+                .map(|block| self.rewrite_block(block))
+                .collect();
+            if done {
+                return prog;
+            }
+        }
+    }
+
+    fn use_all<'a>(&mut self, imms: impl IntoIterator<Item = &'a Immediate<VarName>>) {
+        for imm in imms {
+            if let Immediate::Var(var) = imm {
+                *self.uses.entry(var.clone()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    fn analyze(&mut self, body: &BlockBody<VarName, Nil>) {
+        match body {
+            BlockBody::Terminator(terminator, _) => match terminator {
+                Terminator::Return(imm) => self.use_all([imm]),
+                Terminator::Branch(branch) => self.use_all(&branch.args),
+                Terminator::ConditionalBranch { cond, .. } => self.use_all([cond]),
+                Terminator::Switch { scrutinee, .. } => self.use_all([scrutinee]),
+            },
+            BlockBody::Operation { dest, op, .. } => {
+                match op {
+                    Operation::Immediate(imm) | Operation::Prim1(_, imm) => self.use_all([imm]),
+                    Operation::Prim2(_, imm1, imm2) => self.use_all([imm1, imm2]),
+                    Operation::Call { args, .. } => self.use_all(args),
+                    Operation::AllocateArray { len } => self.use_all([len]),
+                    Operation::Load { addr, offset } => self.use_all([addr, offset]),
+                    Operation::Str(_) => {}
+                }
+                self.defs.insert(dest.clone(), op.clone());
+            }
+            BlockBody::SubBlocks { blocks, .. } => {
+                for block in blocks.iter() {
+                    self.analyze(&block.body);
+                }
+            }
+            BlockBody::AssertType { .. } => {}
+            BlockBody::AssertLength { len, .. } => self.use_all([len]),
+            BlockBody::AssertInBounds { bound, arg, .. } => self.use_all([bound, arg]),
+            BlockBody::Store {
+                addr, offset, val, ..
+            } => self.use_all([addr, offset, val]),
+        }
+        if let Some(next) = body.successor() {
+            self.analyze(next);
+        }
+    }
+
+    /// The definition of `var`, through copies
+    fn def<'a>(&'a self, mut var: &'a VarName) -> Option<&'a Operation<VarName>> {
+        loop {
+            match self.defs.get(var)? {
+                Operation::Immediate(Immediate::Var(copied)) => var = copied,
+                op => return Some(op),
+            }
+        }
+    }
+
+    /// The variable whose copy `var` is, if the value is used only once
+    fn sole<'a>(&'a self, mut var: &'a VarName) -> Option<&'a VarName> {
+        loop {
+            if self.uses.get(var) != Some(&1) {
+                return None;
+            }
+            match self.defs.get(var)? {
+                Operation::Immediate(Immediate::Var(copied)) => var = copied,
+                _ => return Some(var),
+            }
+        }
+    }
+
+    /// The rewrites that negate the boolean `var` in place
+    fn negation(&self, var: &VarName) -> Option<Vec<(VarName, Operation<VarName>)>> {
+        let var = self.sole(var)?;
+        match self.defs.get(var)? {
+            // !!x
+            Operation::Prim2(Prim2::BitXor, Immediate::Var(x), Immediate::Const(NOT)) => Some(
+                vec![(var.clone(), Operation::Immediate(Immediate::Var(x.clone())))],
+            ),
+            // (a < b) << 2 | 1
+            Operation::Prim2(Prim2::BitOr, Immediate::Var(shifted), Immediate::Const(tag))
+                if *tag == Type::Bool.tag() =>
+            {
+                let Operation::Prim1(Prim1::BitSal(by), Immediate::Var(cmp)) =
+                    self.defs.get(self.sole(shifted)?)?
+                else {
+                    return None;
+                };
+                let cmp = self.sole(cmp).filter(|_| *by == Type::Bool.mask_length())?;
+                let Operation::Prim2(prim, a, b) = self.defs.get(cmp)? else {
+                    return None;
+                };
+                let negated = Operation::Prim2(negated(*prim)?, a.clone(), b.clone());
+                Some(vec![(cmp.clone(), negated)])
+            }
+            // x && y and x || y
+            Operation::Prim2(
+                prim @ (Prim2::BitAnd | Prim2::BitOr),
+                Immediate::Var(x),
+                Immediate::Var(y),
+            ) if x != y => {
+                let mut rewrites = self.negation(x)?;
+                rewrites.extend(self.negation(y)?);
+                let dual = if *prim == Prim2::BitAnd {
+                    Prim2::BitOr
+                } else {
+                    Prim2::BitAnd
+                };
+                let op =
+                    Operation::Prim2(dual, Immediate::Var(x.clone()), Immediate::Var(y.clone()));
+                rewrites.push((var.clone(), op));
+                Some(rewrites)
+            }
+            _ => None,
+        }
+    }
+
+    /// Records `rewrites`, which read `reads`, unless another rewrite of
+    /// this round got to one of the variables first
+    fn apply(&mut self, rewrites: Vec<(VarName, Operation<VarName>)>, reads: &[&VarName]) -> bool {
+        let vars: Vec<_> = rewrites
+            .iter()
+            .map(|(var, _)| var)
+            .chain(reads.iter().copied())
+            .collect();
+        if vars.iter().any(|var| self.touched.contains(*var)) {
+            return false;
+        }
+        self.touched.extend(vars.into_iter().cloned());
+        self.rewrites.extend(rewrites);
+        true
+    }
+
+    fn canonicalize(&mut self, body: &BlockBody<VarName, Nil>) {
+        match body {
+            BlockBody::Operation {
+                dest,
+                op: Operation::Prim2(Prim2::BitXor, Immediate::Var(x), Immediate::Const(NOT)),
+                ..
+            } => {
+                let copy = |var: &VarName| Operation::Immediate(Immediate::Var(var.clone()));
+                if let Some(Operation::Prim2(
+                    Prim2::BitXor,
+                    Immediate::Var(y),
+                    Immediate::Const(NOT),
+                )) = self.def(x)
+                {
+                    // !!y is y, whoever else uses !y
+                    let (x, y) = (x.clone(), y.clone());
+                    self.apply(vec![(dest.clone(), copy(&y))], &[&x, &y]);
+                } else if let Some(mut rewrites) = self.negation(x) {
+                    rewrites.push((dest.clone(), copy(x)));
+                    let x = x.clone();
+                    self.apply(rewrites, &[&x]);
+                }
+            }
+            BlockBody::Terminator(
+                Terminator::ConditionalBranch {
+                    cond: Immediate::Var(flag),
+                    ..
+                },
+                _,
+            ) => {
+                // the flag of !x is that of x, negated
+                let Some(Operation::Prim1(Prim1::BitSar(by), Immediate::Var(not))) =
+                    self.sole(flag).and_then(|flag| self.defs.get(flag))
+                else {
+                    return;
+                };
+                let Some(Operation::Prim2(Prim2::BitXor, Immediate::Var(x), Immediate::Const(NOT))) =
+                    self.def(not)
+                else {
+                    return;
+                };
+                if *by == Type::Bool.mask_length() && self.sole(not).is_some() {
+                    let sar = Operation::Prim1(Prim1::BitSar(*by), Immediate::Var(x.clone()));
+                    let (flag, not, x) = (flag.clone(), not.clone(), x.clone());
+                    if self.apply(vec![(flag.clone(), sar)], &[&not, &x]) {
+                        self.swapped.insert(flag);
+                    }
+                }
+            }
+            BlockBody::SubBlocks { blocks, .. } => {
+                for block in blocks.iter() {
+                    self.canonicalize(&block.body);
+                }
+            }
+            _ => {}
+        }
+        if let Some(next) = body.successor() {
+            self.canonicalize(next);
+        }
+    }
+
+    /// Whether `var` is a negation, the boolean of which `!` already asserted
+    fn is_negation(&self, var: &VarName) -> bool {
+        matches!(
+            self.rewrites.get(var).or_else(|| self.defs.get(var)),
+            Some(Operation::Prim2(Prim2::BitXor, _, Immediate::Const(NOT)))
+        )
+    }
+
+    fn rewrite_block(&self, mut block: BasicBlock<VarName, Nil>) -> BasicBlock<VarName, Nil> {
+        block.body = self.rewrite(block.body);
+        block
+    }
+
+    fn rewrite(&self, body: BlockBody<VarName, Nil>) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::Terminator(
+                Terminator::ConditionalBranch {
+                    cond: Immediate::Var(flag),
+                    thn,
+                    els,
+                },
+                ana,
+            ) if self.swapped.contains(&flag) => BlockBody::Terminator(
+                Terminator::ConditionalBranch {
+                    cond: Immediate::Var(flag),
+                    thn: els,
+                    els: thn,
+                },
+                ana,
+            ),
+            BlockBody::Terminator(..) => body,
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => BlockBody::Operation {
+                op: self.rewrites.get(&dest).cloned().unwrap_or(op),
+                dest,
+                next: Box::new(self.rewrite(*next)),
+                ana,
+            },
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.rewrite_block(block))
+                    .collect(),
+                next: Box::new(self.rewrite(*next)),
+                ana,
+            },
+            BlockBody::AssertType {
+                ty: Type::Bool,
+                arg: Immediate::Var(var),
+                next,
+                ..
+            } if self.is_negation(&var) => self.rewrite(*next),
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.rewrite(*next)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.rewrite(*next)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.rewrite(*next)),
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.rewrite(*next)),
+                ana,
+            },
+        }
+    }
+}
+
+impl Default for ConditionCanonicalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The comparison that holds when `cmp` does not
+fn negated(cmp: Prim2) -> Option<Prim2> {
+    match cmp {
+        Prim2::Lt => Some(Prim2::Ge),
+        Prim2::Ge => Some(Prim2::Lt),
+        Prim2::Le => Some(Prim2::Gt),
+        Prim2::Gt => Some(Prim2::Le),
+        Prim2::Eq => Some(Prim2::Neq),
+        Prim2::Neq => Some(Prim2::Eq),
+        _ => None,
+    }
+}
+
+/*
+ * Refinement facts
+ * */
+
+/// Finds what the conditional branches tell their targets, by tracing their
+/// flags back through the tagging to the type tests and the comparisons,
+/// and what holds on entry to the blocks that a single block enters
+pub struct FactAnalyzer {
+    facts: Facts,
+    /// The operation that defines each variable
+    defs: HashMap<VarName, Operation<VarName>>,
+    /// The blocks that branch to each block, once per edge
+    preds: HashMap<BlockName, Vec<BlockName>>,
+    /// The blocks of functions, which the calls enter from anywhere
+    called: HashSet<BlockName>,
+    /// The types that the body of each block asserts
+    asserted: HashMap<BlockName, Vec<(VarName, Type)>>,
+    /// The conditional branches: the block, its flag and its targets
+    branches: Vec<(BlockName, VarName, BlockName, BlockName)>,
+}
+
+impl FactAnalyzer {
+    pub fn new<T>(prog: &Program<VarName, T>) -> Self {
+        let mut analyzer = Self {
+            facts: Facts::default(),
+            defs: defs(&prog.blocks),
+            preds: HashMap::new(),
+            called: prog
+                .funs
+                .iter()
+                .map(|fun| fun.body.target.clone())
+                .collect(),
+            asserted: HashMap::new(),
+            branches: Vec::new(),
+        };
+        for block in prog.blocks.iter() {
+            analyzer.collect(block);
+        }
+        analyzer.facts.copies = analyzer
+            .defs
+            .keys()
+            .filter_map(|var| Some((var.clone(), analyzer.copied(var)?.clone())))
+            .collect();
+        for (from, flag, thn, els) in std::mem::take(&mut analyzer.branches) {
+            if thn == els {
+                continue;
+            }
+            for (to, holds) in [(thn, true), (els, false)] {
+                let facts = analyzer.untagged(&flag, holds);
+                if !facts.is_empty() {
+                    analyzer.facts.edges.insert((from.clone(), to), facts);
+                }
+            }
+        }
+        let labels: Vec<_> = analyzer.preds.keys().cloned().collect();
+        for label in labels {
+            analyzer.enter(&label, &mut HashSet::new());
+        }
+        analyzer
+    }
+
+    /// The facts that `new` found
+    pub fn facts(&self) -> &Facts {
+        &self.facts
+    }
+
+    fn collect<T>(&mut self, block: &BasicBlock<VarName, T>) {
+        let mut body = &block.body;
+        loop {
+            match body {
+                BlockBody::SubBlocks { blocks, .. } => {
+                    for block in blocks.iter() {
+                        self.collect(block);
+                    }
+                }
+                BlockBody::AssertType {
+                    ty,
+                    arg: Immediate::Var(var),
+                    ..
+                } => self
+                    .asserted
+                    .entry(block.label.clone())
+                    .or_default()
+                    .push((var.clone(), *ty)),
+                BlockBody::Terminator(terminator, _) => {
+                    let targets = match terminator {
+                        Terminator::Return(_) => vec![],
+                        Terminator::Branch(Branch { target, .. }) => vec![target],
+                        Terminator::ConditionalBranch { cond, thn, els } => {
+                            if let Immediate::Var(flag) = cond {
+                                self.branches.push((
+                                    block.label.clone(),
+                                    flag.clone(),
+                                    thn.clone(),
+                                    els.clone(),
+                                ));
+                            }
+                            vec![thn, els]
+                        }
+                        Terminator::Switch {
+                            targets, default, ..
+                        } => targets.iter().chain([default]).collect(),
+                    };
+                    for target in targets {
+                        self.preds
+                            .entry(target.clone())
+                            .or_default()
+                            .push(block.label.clone());
+                    }
+                }
+                _ => {}
+            }
+            match body.successor() {
+                Some(next) => body = next,
+                None => break,
+            }
+        }
+    }
+
+    /// The variable that `var` is a copy of, through copies
+    fn copied<'a>(&'a self, var: &'a VarName) -> Option<&'a VarName> {
+        let mut root = None;
+        while let Some(Operation::Immediate(Immediate::Var(copied))) =
+            self.defs.get(root.unwrap_or(var))
+        {
+            root = Some(copied);
+        }
+        root
+    }
+
+    /// The definition of `var`, through copies
+    fn def(&self, var: &VarName) -> Option<&Operation<VarName>> {
+        self.defs.get(self.facts.root(var))
+    }
+
+    /// The root of `imm`, or the constant it copies
+    fn root_of(&self, imm: &Immediate<VarName>) -> Immediate<VarName> {
+        match imm {
+            Immediate::Var(var) => match self.def(var) {
+                Some(Operation::Immediate(Immediate::Const(n))) => Immediate::Const(*n),
+                _ => Immediate::Var(self.facts.root(var).clone()),
+            },
+            Immediate::Const(n) => Immediate::Const(*n),
+        }
+    }
+
+    /// The facts that hold when the untagged boolean `flag` is 1, if
+    /// `holds`, or 0
+    fn untagged(&self, flag: &VarName, holds: bool) -> Vec<Fact> {
+        match self.def(flag) {
+            Some(Operation::Prim1(Prim1::BitSar(by), Immediate::Var(cond)))
+                if *by == Type::Bool.mask_length() =>
+            {
+                self.tagged(cond, holds)
+            }
+            Some(Operation::Prim2(prim, Immediate::Var(a), b)) => {
+                // tag = a & mask; tag == <tag>
+                if let (
+                    Prim2::Eq | Prim2::Neq,
+                    Some(Operation::Prim2(
+                        Prim2::BitAnd,
+                        Immediate::Var(x),
+                        Immediate::Const(mask),
+                    )),
+                    Immediate::Const(tag),
+                ) = (prim, self.def(a), b)
+                {
+                    let ty = [Type::Int, Type::Bool, Type::Array]
+                        .into_iter()
+                        .find(|ty| ty.mask() == *mask && ty.tag() == *tag);
+                    if let Some(ty) = ty {
+                        let x = self.facts.root(x).clone();
+                        return if (*prim == Prim2::Eq) == holds {
+                            vec![Fact::Is(x, ty)]
+                        } else {
+                            vec![Fact::IsNot(x, ty)]
+                        };
+                    }
+                }
+                let prim = if holds { Some(*prim) } else { negated(*prim) };
+                match prim.filter(|prim| negated(*prim).is_some()) {
+                    Some(prim) => {
+                        vec![Fact::Cmp(self.facts.root(a).clone(), prim, self.root_of(b))]
+                    }
+                    None => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    /// The facts that hold when the tagged boolean `cond` is true, if
+    /// `holds`, or false
+    fn tagged(&self, cond: &VarName, holds: bool) -> Vec<Fact> {
+        match self.def(cond) {
+            // !x
+            Some(Operation::Prim2(Prim2::BitXor, Immediate::Var(x), Immediate::Const(NOT))) => {
+                self.tagged(x, !holds)
+            }
+            // flag << 2 | 1
+            Some(Operation::Prim2(
+                Prim2::BitOr,
+                Immediate::Var(shifted),
+                Immediate::Const(tag),
+            )) if *tag == Type::Bool.tag() => match self.def(shifted) {
+                Some(Operation::Prim1(Prim1::BitSal(by), Immediate::Var(flag)))
+                    if *by == Type::Bool.mask_length() =>
+                {
+                    self.untagged(flag, holds)
+                }
+                _ => vec![],
+            },
+            // both of x && y, or neither of x || y
+            Some(Operation::Prim2(
+                prim @ (Prim2::BitAnd | Prim2::BitOr),
+                Immediate::Var(x),
+                Immediate::Var(y),
+            )) if (*prim == Prim2::BitAnd) == holds => {
+                let mut facts = self.tagged(x, holds);
+                facts.extend(self.tagged(y, holds));
+                facts
+            }
+            // any other boolean is true or false
+            _ => {
+                let prim = if holds { Prim2::Eq } else { Prim2::Neq };
+                let cond = self.facts.root(cond).clone();
+                vec![Fact::Cmp(cond, prim, Immediate::boolean(true))]
+            }
+        }
+    }
+
+    /// Finds the facts on entry to `label`, which `visiting` enters
+    fn enter(&mut self, label: &BlockName, visiting: &mut HashSet<BlockName>) -> Vec<Fact> {
+        if let Some(facts) = self.facts.entries.get(label) {
+            return facts.clone();
+        }
+        let from = match self.preds.get(label).map(|preds| &preds[..]) {
+            Some([from]) if !self.called.contains(label) => from.clone(),
+            _ => return vec![],
+        };
+        if !visiting.insert(label.clone()) {
+            return vec![];
+        }
+        let mut facts = self.enter(&from, visiting);
+        let asserted = self.asserted.get(&from).into_iter().flatten();
+        let asserted: Vec<_> = asserted
+            .filter(|(var, _)| {
+                matches!(
+                    self.root_of(&Immediate::Var(var.clone())),
+                    Immediate::Var(_)
+                )
+            })
+            .map(|(var, ty)| Fact::Is(self.facts.root(var).clone(), *ty))
+            .collect();
+        for fact in asserted
+            .into_iter()
+            .chain(self.facts.on_edge(&from, label).iter().cloned())
+        {
+            if !facts.contains(&fact) {
+                facts.push(fact);
+            }
+        }
+        if !facts.is_empty() {
+            self.facts.entries.insert(label.clone(), facts.clone());
+        }
+        facts
+    }
+}
+
+/*
+ * Induction variables
+ * */
+
+/// Finds the induction variables of the loops, which are the blocks that
+/// branch to themselves, as the tail calls of a local function do: the
+/// parameters that each round steps by a constant, and the values that the
+/// loop computes from them by constants
+pub struct InductionAnalyzer {
+    vars: InductionVars,
+    /// The operation that defines each variable
+    defs: HashMap<VarName, Operation<VarName>>,
+    /// The branches, after the block they are in
+    branches: Vec<(BlockName, Branch<VarName>)>,
+    /// The blocks of functions, which the calls enter
+    called: HashSet<BlockName>,
+}
+
+impl InductionAnalyzer {
+    pub fn new<T>(prog: &Program<VarName, T>) -> Self {
+        let mut analyzer = Self {
+            vars: InductionVars::default(),
+            defs: defs(&prog.blocks),
+            branches: Vec::new(),
+            called: prog
+                .funs
+                .iter()
+                .map(|fun| fun.body.target.clone())
+                .collect(),
+        };
+        for block in prog.blocks.iter() {
+            analyzer.collect(block);
+        }
+        for block in prog.blocks.iter() {
+            analyzer.find_loops(block);
+        }
+        analyzer
+    }
+
+    /// The induction variables that `new` found
+    pub fn vars(&self) -> &InductionVars {
+        &self.vars
+    }
+
+    fn collect<T>(&mut self, block: &BasicBlock<VarName, T>) {
+        let mut body = &block.body;
+        loop {
+            match body {
+                BlockBody::SubBlocks { blocks, .. } => {
+                    for block in blocks.iter() {
+                        self.collect(block);
+                    }
+                }
+                BlockBody::Terminator(Terminator::Branch(branch), _) => {
+                    self.branches.push((block.label.clone(), branch.clone()))
+                }
+                _ => {}
+            }
+            match body.successor() {
+                Some(next) => body = next,
+                None => break,
+            }
+        }
+    }
+
+    /// The blocks in `block`, with it, and the variables they define in order
+    fn nested<'a, T>(
+        block: &'a BasicBlock<VarName, T>,
+        labels: &mut HashSet<BlockName>,
+        dests: &mut Vec<&'a VarName>,
+    ) {
+        labels.insert(block.label.clone());
+        let mut body = &block.body;
+        loop {
+            match body {
+                BlockBody::Operation { dest, .. } => dests.push(dest),
+                BlockBody::SubBlocks { blocks, .. } => {
+                    for block in blocks.iter() {
+                        Self::nested(block, labels, dests);
+                    }
+                }
+                _ => {}
+            }
+            match body.successor() {
+                Some(next) => body = next,
+                None => break,
+            }
+        }
+    }
+
+    fn find_loops<T>(&mut self, block: &BasicBlock<VarName, T>) {
+        let mut body = &block.body;
+        loop {
+            if let BlockBody::SubBlocks { blocks, .. } = body {
+                for block in blocks.iter() {
+                    self.find_loops(block);
+                }
+            }
+            match body.successor() {
+                Some(next) => body = next,
+                None => break,
+            }
+        }
+        let (mut labels, mut dests) = (HashSet::new(), Vec::new());
+        Self::nested(block, &mut labels, &mut dests);
+        let branches = self
+            .branches
+            .iter()
+            .filter(|(_, branch)| branch.target == block.label);
+        let (rounds, entries): (Vec<_>, Vec<_>) =
+            branches.partition(|(from, _)| labels.contains(from));
+        if rounds.is_empty() {
+            return;
+        }
+        let called = self.called.contains(&block.label);
+        let mut vars = Vec::new();
+        for (i, param) in block.params.iter().enumerate() {
+            let steps: Vec<_> = rounds
+                .iter()
+                .map(|(_, branch)| self.step(param, &branch.args[i]))
+                .collect();
+            match steps[..] {
+                [Some(step), ..] if step != 0 && steps.iter().all(|s| *s == Some(step)) => {
+                    let init = if called {
+                        Vec::new()
+                    } else {
+                        entries
+                            .iter()
+                            .map(|(_, branch)| self.resolve(&branch.args[i]))
+                            .collect()
+                    };
+                    vars.push((param.clone(), Induction::Basic { init, step }));
+                }
+                _ => {}
+            }
+        }
+        // the values computed from them, until no more are found
+        let mut changed = !vars.is_empty();
+        while changed {
+            changed = false;
+            for dest in dests.iter() {
+                if vars.iter().any(|(var, _)| var == *dest) {
+                    continue;
+                }
+                if let Some(derived) = self.derive(dest, &vars) {
+                    vars.push(((*dest).clone(), derived));
+                    changed = true;
+                }
+            }
+        }
+        self.vars.0.insert(block.label.clone(), vars);
+    }
+
+    /// The variable that `var` is a copy of, through copies, or `var`
+    fn root<'a>(&'a self, mut var: &'a VarName) -> &'a VarName {
+        while let Some(Operation::Immediate(Immediate::Var(copied))) = self.defs.get(var) {
+            var = copied;
+        }
+        var
+    }
+
+    /// The root of `imm`, or the constant it copies
+    fn resolve(&self, imm: &Immediate<VarName>) -> Immediate<VarName> {
+        match imm {
+            Immediate::Var(var) => match self.defs.get(self.root(var)) {
+                Some(Operation::Immediate(Immediate::Const(n))) => Immediate::Const(*n),
+                _ => Immediate::Var(self.root(var).clone()),
+            },
+            Immediate::Const(n) => Immediate::Const(*n),
+        }
+    }
+
+    /// What the argument `arg` adds to `param` to start the next round
+    fn step(&self, param: &VarName, arg: &Immediate<VarName>) -> Option<i64> {
+        let Immediate::Var(arg) = self.resolve(arg) else {
+            return None;
+        };
+        let Some(Operation::Prim2(prim, a, b)) = self.defs.get(&arg) else {
+            return None;
+        };
+        let is_param =
+            |imm: &Immediate<VarName>| self.resolve(imm) == Immediate::Var(param.clone());
+        match (prim, self.resolve(a), self.resolve(b)) {
+            (Prim2::Add, _, Immediate::Const(c)) if is_param(a) => Some(c),
+            (Prim2::Add, Immediate::Const(c), _) if is_param(b) => Some(c),
+            (Prim2::Sub, _, Immediate::Const(c)) if is_param(a) => c.checked_neg(),
+            _ => None,
+        }
+    }
+
+    /// How `dest` is computed from an induction variable of `vars`
+    fn derive(&self, dest: &VarName, vars: &[(VarName, Induction)]) -> Option<Induction> {
+        let linear = |imm: &Immediate<VarName>| {
+            let Immediate::Var(var) = self.resolve(imm) else {
+                return None;
+            };
+            match vars.iter().find(|(v, _)| *v == var)? {
+                (_, Induction::Basic { .. }) => Some((var, 1, 1, 0)),
+                (
+                    _,
+                    Induction::Derived {
+                        base,
+                        num,
+                        den,
+                        offset,
+                    },
+                ) => Some((base.clone(), *num, *den, *offset)),
+            }
+        };
+        let (base, num, den, offset) = match self.defs.get(dest)? {
+            Operation::Prim2(prim, a, b) => match (prim, self.resolve(a), self.resolve(b)) {
+                (Prim2::Add, _, Immediate::Const(c)) => {
+                    let (base, num, den, offset) = linear(a)?;
+                    (base, num, den, offset.checked_add(c)?)
+                }
+                (Prim2::Add, Immediate::Const(c), _) => {
+                    let (base, num, den, offset) = linear(b)?;
+                    (base, num, den, offset.checked_add(c)?)
+                }
+                (Prim2::Sub, _, Immediate::Const(c)) => {
+                    let (base, num, den, offset) = linear(a)?;
+                    (base, num, den, offset.checked_sub(c)?)
+                }
+                (Prim2::Mul, _, Immediate::Const(c)) => {
+                    let (base, num, den, offset) = linear(a)?;
+                    (base, num.checked_mul(c)?, den, offset.checked_mul(c)?)
+                }
+                (Prim2::Mul, Immediate::Const(c), _) => {
+                    let (base, num, den, offset) = linear(b)?;
+                    (base, num.checked_mul(c)?, den, offset.checked_mul(c)?)
+                }
+                _ => return None,
+            },
+            Operation::Prim1(Prim1::BitSal(by), a) => {
+                let (base, num, den, offset) = linear(a)?;
+                let by = 1i64.checked_shl(*by as u32)?;
+                (base, num.checked_mul(by)?, den, offset.checked_mul(by)?)
+            }
+            // exact, as the words of integers are even, if the offset is too
+            Operation::Prim1(Prim1::BitSar(by), a) => {
+                let (base, num, den, offset) = linear(a)?;
+                let by = 1i64.checked_shl(*by as u32)?;
+                let den = den.checked_mul(by)?;
+                if offset % by != 0 || (2 * num) % den != 0 {
+                    return None;
+                }
+                (base, num, den, offset / by)
+            }
+            _ => return None,
+        };
+        let shared = num.trailing_zeros().min(den.trailing_zeros());
+        Some(Induction::Derived {
+            base,
+            num: num >> shared,
+            den: den >> shared,
+            offset,
+        })
+    }
+}
+
+/*
+ * Recursion to loops
+ * */
+
+/// Turns the tail calls of a function to itself into branches to the block
+/// of its body, which loops: `r = f(x, y); ret r` in the body of `f` becomes
+/// `br f#1(x, y)`. The local functions are loops already, as the lowering
+/// compiles their tail calls to branches, but not the calls of the functions
+/// to themselves by name, as `main` does.
+pub struct Loopifier {
+    /// The functions of the blocks that they branch to, which other
+    /// functions' blocks may not loop in
+    funs: HashMap<BlockName, FunBlock<VarName>>,
+    looped: usize,
+    remarks: Vec<Remark>,
+}
+
+impl Loopifier {
+    const PASS: Optimization = Optimization::Loopify;
+
+    pub fn new() -> Self {
+        Self {
+            funs: HashMap::new(),
+            looped: 0,
+            remarks: Vec::new(),
+        }
+    }
+
+    /// The number of calls turned into branches
+    pub fn looped(&self) -> usize {
+        self.looped
+    }
+
+    pub fn remarks(&self) -> &[Remark] {
+        &self.remarks
+    }
+
+    pub fn run(&mut self, mut prog: Program<VarName, Nil>) -> Program<VarName, Nil> {
+        self.funs = prog
+            .funs
+            .iter()
+            .map(|fun| (fun.body.target.clone(), fun.clone()))
+            .collect();
+        prog.blocks = prog
+            .blocks
+            .into_iter()
+            .map(|block| self.loopify_block(block, None))
+            .collect();
+        prog
+    }
+
+    /// Loopifies the calls in `block` of the function whose body it is in
+    fn loopify_block(
+        &mut self,
+        mut block: BasicBlock<VarName, Nil>,
+        fun: Option<&FunBlock<VarName>>,
+    ) -> BasicBlock<VarName, Nil> {
+        let own = self.funs.get(&block.label).cloned();
+        let fun = own.as_ref().or(fun);
+        block.body = self.loopify(block.body, fun);
+        block
+    }
+
+    fn loopify(
+        &mut self,
+        body: BlockBody<VarName, Nil>,
+        fun: Option<&FunBlock<VarName>>,
+    ) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::Operation {
+                dest,
+                op: Operation::Call { fun: called, args },
+                next,
+                ana,
+            } if fun.is_some_and(|fun| fun.name == called) => {
+                let fun = fun.unwrap();
+                if matches!(&*next, BlockBody::Terminator(Terminator::Return(Immediate::Var(ret)), _) if *ret == dest)
+                {
+                    // the parameters of the function are the arguments of the call
+                    let bound: HashMap<_, _> = fun.params.iter().zip(args.iter()).collect();
+                    let args = fun
+                        .body
+                        .args
+                        .iter()
+                        .map(|arg| match arg {
+                            Immediate::Var(var) => bound.get(var).map_or(arg, |arg| *arg).clone(),
+                            Immediate::Const(_) => arg.clone(),
+                        })
+                        .collect();
+                    let note = format!("{} = {}(..) loops to {}", dest, called, fun.body.target);
+                    (self.remarks).push(Remark::passed(Self::PASS, &fun.name, note));
+                    self.looped += 1;
+                    return BlockBody::Terminator(
+                        Terminator::Branch(Branch {
+                            target: fun.body.target.clone(),
+                            args,
+                        }),
+                        ana,
+                    );
+                }
+                let note = format!("{} = {}(..) not looped: not a tail call", dest, called);
+                (self.remarks).push(Remark::missed(Self::PASS, &fun.name, note));
+                BlockBody::Operation {
+                    dest,
+                    op: Operation::Call { fun: called, args },
+                    next: Box::new(self.loopify(*next, Some(fun))),
+                    ana,
+                }
+            }
+            BlockBody::Terminator(..) => body,
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => BlockBody::Operation {
+                dest,
+                op,
+                next: Box::new(self.loopify(*next, fun)),
+                ana,
+            },
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.loopify_block(block, fun))
+                    .collect(),
+                next: Box::new(self.loopify(*next, fun)),
+                ana,
+            },
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.loopify(*next, fun)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.loopify(*next, fun)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.loopify(*next, fun)),
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.loopify(*next, fun)),
+                ana,
+            },
+        }
+    }
+}
+
+impl Default for Loopifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*
+ * Cold outlining
+ * */
+
+/// Outlines the blocks that certainly fail, as the arm of an `if` that adds
+/// `true` to a number, to blocks after all the others, so that the code
+/// around them is laid out without them. A block fails at an assertion of
+/// constants that cannot hold, or at a call of `snake_error`. Its code up to
+/// there moves to the outlined block, which takes the parameters of the
+/// block and the variables the code reads, and the block branches to it.
+pub struct ColdOutliner {
+    /// The operation that defines each variable
+    defs: HashMap<VarName, Operation<VarName>>,
+    outlined: Vec<BasicBlock<VarName, Nil>>,
+    /// The top-level block being outlined in
+    function: String,
+    remarks: Vec<Remark>,
+}
+
+impl ColdOutliner {
+    const PASS: Optimization = Optimization::ColdOutlining;
+
+    pub fn new() -> Self {
+        Self {
+            defs: HashMap::new(),
+            outlined: Vec::new(),
+            function: String::new(),
+            remarks: Vec::new(),
+        }
+    }
+
+    /// The number of blocks outlined
+    pub fn outlined(&self) -> usize {
+        self.outlined.len()
+    }
+
+    pub fn remarks(&self) -> &[Remark] {
+        &self.remarks
+    }
+
+    pub fn run(
+        &mut self,
+        mut prog: Program<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> Program<VarName, Nil> {
+        self.defs = defs(&prog.blocks);
+        prog.blocks = prog
+            .blocks
+            .into_iter()
+            .map(|mut block| {
+                self.function = block.label.to_string();
+                block.body = self.outline_in(block.body, names);
+                block
+            })
+            .collect();
+        prog.blocks.extend(self.outlined.iter().cloned());
+        prog
+    }
+
+    /// The value of `imm`, if it is computed from constants
+    fn constant(&self, imm: &Immediate<VarName>) -> Option<i64> {
+        let var = match imm {
+            Immediate::Const(c) => return Some(*c),
+            Immediate::Var(var) => var,
+        };
+        match self.defs.get(var)? {
+            Operation::Immediate(imm) => self.constant(imm),
+            Operation::Prim1(Prim1::BitSar(by), imm) => Some(self.constant(imm)? >> by),
+            Operation::Prim1(Prim1::BitSal(by), imm) => Some(self.constant(imm)? << by),
+            // the length of an array, which its allocation writes
+            Operation::Load {
+                addr: Immediate::Var(addr),
+                offset,
+            } if self.constant(offset)? == 0 => {
+                let Some(Operation::Prim2(Prim2::BitXor, Immediate::Var(array), _)) =
+                    self.defs.get(addr)
+                else {
+                    return None;
+                };
+                let Some(Operation::Prim2(Prim2::BitOr, Immediate::Var(arr), _)) =
+                    self.defs.get(array)
+                else {
+                    return None;
+                };
+                match self.defs.get(arr)? {
+                    Operation::AllocateArray { len } => self.constant(len),
+                    _ => None,
+                }
+            }
+            Operation::Prim2(prim, a, b) => {
+                let (a, b) = (self.constant(a)?, self.constant(b)?);
+                match prim {
+                    Prim2::BitAnd => Some(a & b),
+                    Prim2::BitOr => Some(a | b),
+                    Prim2::BitXor => Some(a ^ b),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The instruction at the head of `body`, if it certainly fails
+    fn failure(&self, body: &BlockBody<VarName, Nil>) -> Option<String> {
+        match body {
+            BlockBody::AssertType { ty, arg, .. } => {
+                let word = self.constant(arg)?;
+                (word & ty.mask() != ty.tag()).then(|| format!("assert{}({})", ty, arg))
+            }
+            BlockBody::AssertLength { len, .. } => {
+                (self.constant(len)? < 0).then(|| format!("assertLength({})", len))
+            }
+            BlockBody::AssertInBounds { bound, arg, .. } => {
+                // no index is in the bounds of an empty array
+                let bound = self.constant(bound)?;
+                let fails = bound <= 0 || self.constant(arg).is_some_and(|i| i < 0 || i >= bound);
+                fails.then(|| format!("assertInBounds({}, {})", bound, arg))
+            }
+            BlockBody::Operation {
+                op: Operation::Call { fun, .. },
+                ..
+            } if fun.is_unmangled() && fun.hint() == "snake_error" => Some(format!("{}(..)", fun)),
+            _ => None,
+        }
+    }
+
+    /// The first instruction of the code of `body` that certainly fails
+    fn fails(&self, mut body: &BlockBody<VarName, Nil>) -> Option<String> {
+        loop {
+            if let Some(failure) = self.failure(body) {
+                return Some(failure);
+            }
+            body = body.successor()?;
+        }
+    }
+
+    /// The code of `body` up to the instruction that fails, without the
+    /// blocks it would branch to after it
+    fn truncate(&self, body: BlockBody<VarName, Nil>) -> BlockBody<VarName, Nil> {
+        let unreachable = || {
+            Box::new(BlockBody::Terminator(
+                Terminator::Return(Immediate::Const(0)),
+                Nil,
+            ))
+        };
+        let failed = self.failure(&body).is_some();
+        match body {
+            BlockBody::SubBlocks { next, .. } => self.truncate(*next),
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => BlockBody::Operation {
+                dest,
+                op,
+                next: if failed {
+                    unreachable()
+                } else {
+                    Box::new(self.truncate(*next))
+                },
+                ana,
+            },
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: if failed {
+                    unreachable()
+                } else {
+                    Box::new(self.truncate(*next))
+                },
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: if failed {
+                    unreachable()
+                } else {
+                    Box::new(self.truncate(*next))
+                },
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: if failed {
+                    unreachable()
+                } else {
+                    Box::new(self.truncate(*next))
+                },
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.truncate(*next)),
+                ana,
+            },
+            BlockBody::Terminator(..) => body,
+        }
+    }
+
+    /// Outlines the failing blocks nested in `body`
+    fn outline_in(
+        &mut self,
+        body: BlockBody<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| match self.fails(&block.body) {
+                        Some(failure) => self.outline(block, failure, names),
+                        None => BasicBlock {
+                            body: self.outline_in(block.body, names),
+                            ..block
+                        },
+                    })
+                    .collect(),
+                next: Box::new(self.outline_in(*next, names)),
+                ana,
+            },
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => BlockBody::Operation {
+                dest,
+                op,
+                next: Box::new(self.outline_in(*next, names)),
+                ana,
+            },
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.outline_in(*next, names)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.outline_in(*next, names)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.outline_in(*next, names)),
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.outline_in(*next, names)),
+                ana,
+            },
+            BlockBody::Terminator(..) => body,
+        }
+    }
+
+    /// Moves the code of `block` to a new block, which `block` branches to
+    fn outline(
+        &mut self,
+        block: BasicBlock<VarName, Nil>,
+        failure: String,
+        names: &mut NameSupply,
+    ) -> BasicBlock<VarName, Nil> {
+        let label = names.fresh_block("cold");
+        let body = self.truncate(block.body);
+        // the parameters, and the variables that the code reads from around it
+        let mut defined: HashSet<VarName> = HashSet::new();
+        let mut chain = &body;
+        loop {
+            if let BlockBody::Operation { dest, .. } = chain {
+                defined.insert(dest.clone());
+            }
+            match chain.successor() {
+                Some(next) => chain = next,
+                None => break,
+            }
+        }
+        let mut passed = block.params.clone();
+        body.map_vars(&mut |var: &VarName| {
+            if !defined.contains(var) && !passed.contains(var) {
+                passed.push(var.clone());
+            }
+        });
+        let renamed: HashMap<_, _> = passed
+            .iter()
+            .map(|var| (var.clone(), names.fresh_var(var.hint())))
+            .collect();
+        let note = format!(
+            "{} fails at {}, outlined to {}",
+            block.label, failure, label
+        );
+        (self.remarks).push(Remark::passed(Self::PASS, &self.function, note));
+        self.outlined.push(BasicBlock {
+            label: label.clone(),
+            params: passed.iter().map(|var| renamed[var].clone()).collect(),
+            body: body.map_vars(&mut |var| renamed.get(var).unwrap_or(var).clone()),
+            ana: Nil,
+        });
+        BasicBlock {
+            body: BlockBody::Terminator(
+                Terminator::Branch(Branch {
+                    target: label,
+                    args: passed.into_iter().map(Immediate::Var).collect(),
+                }),
+                Nil,
+            ),
+            ..block
+        }
+    }
+}
+
+impl Default for ColdOutliner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*
+ * Bounds-check combining
+ * */
+
+/// The largest distance between the indices whose checks are combined,
+/// which keeps the index arithmetic between the accesses from overflowing
+const MAX_DISTANCE: i64 = 1 << 32;
+
+/// Combines the bounds checks of accesses to the same array at `i`, `i + 1`,
+/// ..., `i + d` in a row of operations: the check of `i` checks `i` against
+/// the length minus `d`, and the checks of the others go.
+///
+/// Between the checks, only code that cannot fail or call out runs, so that
+/// the combined check fails exactly when one of the checks would have. It
+/// fails before the accesses in between, however, reporting `i` rather
+/// than the index that was out of bounds; when `strict`, only the checks of
+/// the same index are combined, which keeps the reported errors.
+pub struct BoundsCheckCombiner {
+    /// The operation that defines each variable
+    defs: HashMap<VarName, Operation<VarName>>,
+    /// What the conditional branches tell the blocks they enter, so that the
+    /// assertions of the types they test do not end a row of checks
+    facts: Facts,
+    /// The number of bounds checks seen, in the order of the traversal
+    checks: usize,
+    /// The checks that also cover the indices above theirs, by how far
+    widened: HashMap<usize, i64>,
+    /// The checks that a previous one covers
+    removed: HashSet<usize>,
+    strict: bool,
+    remarks: Vec<Remark>,
+}
+
+/// An array and the variable part of an index into it
+type ArrayIndex = (Immediate<VarName>, Option<VarName>);
+
+/// The accesses whose checks the first one combines
+#[derive(Clone)]
+struct CheckGroup {
+    /// The number of the first check
+    check: usize,
+    key: ArrayIndex,
+    /// The constant part of the index of the first check
+    offset: i64,
+    /// How far above the first index the others reach
+    reach: i64,
+}
+
+/// What is known about the values in a row of operations
+#[derive(Default)]
+struct CheckWindow {
+    group: Option<CheckGroup>,
+    /// The types of the values that were asserted or computed
+    types: HashMap<VarName, Type>,
+    /// The values too small for their sums with constants to overflow
+    small: HashSet<VarName>,
+    /// The function that the row is in
+    function: String,
+    /// The last group, with what ended it
+    closed: Option<(CheckGroup, String)>,
+}
+
+/// e.g. `a[i + 1]`, with the tagged index `i`
+fn shown_access((array, root): &ArrayIndex, offset: i64) -> String {
+    match root {
+        Some(root) if offset > 0 => format!("{}[{} + {}]", array, root, offset),
+        Some(root) if offset < 0 => format!("{}[{} - {}]", array, root, -offset),
+        Some(root) => format!("{}[{}]", array, root),
+        None => format!("{}[{}]", array, offset),
+    }
+}
+
+impl BoundsCheckCombiner {
+    const PASS: Optimization = Optimization::BoundsCheckCombining;
+
+    pub fn new() -> Self {
+        Self {
+            defs: HashMap::new(),
+            facts: Facts::default(),
+            checks: 0,
+            widened: HashMap::new(),
+            removed: HashSet::new(),
+            strict: false,
+            remarks: Vec::new(),
+        }
+    }
+
+    /// Combines only the checks of the same index when `strict`, so that the
+    /// program reports the same error as without the combining
+    pub fn strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// The number of checks that the others now cover
+    pub fn merged(&self) -> usize {
+        self.removed.len()
+    }
+
+    /// The checks that were combined, and the ones that were not but for
+    /// the code between them
+    pub fn remarks(&self) -> &[Remark] {
+        &self.remarks
+    }
+
+    /// Combines the checks of `prog`, naming the new bounds from `names`
+    pub fn run(
+        &mut self,
+        mut prog: Program<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> Program<VarName, Nil> {
+        self.defs = defs(&prog.blocks);
+        self.facts = FactAnalyzer::new(&prog).facts().clone();
+        let functions = remarks::functions(&prog);
+        for block in prog.blocks.iter() {
+            let mut window = CheckWindow {
+                types: self.proven(&block.label),
+                function: functions[&block.label].clone(),
+                ..CheckWindow::default()
+            };
+            self.analyze(&block.body, &mut window);
+        }
+        self.checks = 0;
+        prog.blocks = prog
+            .blocks
+            .into_iter()
+            .map(|block| self.rewrite_block(block, names))
+            .collect();
+        prog
+    }
+
+    /// The constant `imm` is, if any
+    fn constant(&self, imm: &Immediate<VarName>) -> Option<i64> {
+        match imm {
+            Immediate::Const(c) => Some(*c),
+            Immediate::Var(var) => match self.defs.get(var)? {
+                Operation::Immediate(imm) => self.constant(imm),
+                _ => None,
+            },
+        }
+    }
+
+    /// `imm` as a variable plus a constant, if the constant is small
+    fn affine(&self, imm: &Immediate<VarName>) -> (Option<VarName>, i64) {
+        let var = match imm {
+            Immediate::Const(c) => return (None, *c),
+            Immediate::Var(var) => var,
+        };
+        let plus = |(root, offset): (Option<VarName>, i64), c: i64| {
+            Some((
+                root,
+                offset
+                    .checked_add(c)
+                    .filter(|sum| sum.unsigned_abs() < MAX_DISTANCE as u64)?,
+            ))
+        };
+        let sum = match self.defs.get(var) {
+            Some(Operation::Immediate(imm)) => Some(self.affine(imm)),
+            Some(Operation::Prim2(Prim2::Add, lhs, rhs)) => match self.constant(rhs) {
+                Some(c) => plus(self.affine(lhs), c),
+                None => self.constant(lhs).and_then(|c| plus(self.affine(rhs), c)),
+            },
+            Some(Operation::Prim2(Prim2::Sub, lhs, rhs)) => self
+                .constant(rhs)
+                .and_then(|c| plus(self.affine(lhs), c.checked_neg()?)),
+            _ => None,
+        };
+        sum.unwrap_or((Some(var.clone()), 0))
+    }
+
+    /// The array of the length `bound` and the index `arg`, as a variable
+    /// plus a constant
+    fn access(
+        &self,
+        bound: &Immediate<VarName>,
+        arg: &Immediate<VarName>,
+    ) -> Option<(ArrayIndex, i64)> {
+        let (Immediate::Var(len), Immediate::Var(idx)) = (bound, arg) else {
+            return None;
+        };
+        let Operation::Load {
+            addr: Immediate::Var(arr),
+            offset,
+        } = self.defs.get(len)?
+        else {
+            return None;
+        };
+        let Operation::Prim2(Prim2::BitXor, array, tag) = self.defs.get(arr)? else {
+            return None;
+        };
+        if self.constant(offset) != Some(0) || self.constant(tag) != Some(Type::Array.tag()) {
+            return None;
+        }
+        let array = match self.affine(array) {
+            (Some(var), 0) => Immediate::Var(var),
+            _ => return None,
+        };
+        let Operation::Prim1(Prim1::BitSar(by), tagged) = self.defs.get(idx)? else {
+            return None;
+        };
+        let (root, offset) = self.affine(tagged);
+        if *by != Type::Int.mask_length() || offset % 2 != 0 {
+            return None;
+        }
+        Some(((array, root), offset >> 1))
+    }
+
+    /// The type of `imm`, if known
+    fn type_of(&self, imm: &Immediate<VarName>, window: &CheckWindow) -> Option<Type> {
+        if let Some(c) = self.constant(imm) {
+            return [Type::Int, Type::Bool]
+                .into_iter()
+                .find(|ty| c & ty.mask() == ty.tag());
+        }
+        match imm {
+            Immediate::Var(var) => window
+                .types
+                .get(var)
+                .or_else(|| window.types.get(self.facts.root(var)))
+                .copied(),
+            Immediate::Const(_) => None,
+        }
+    }
+
+    /// The types that hold on entry to `block`, by the branches to it
+    fn proven(&self, block: &BlockName) -> HashMap<VarName, Type> {
+        self.facts
+            .at(block)
+            .iter()
+            .filter_map(|fact| match fact {
+                Fact::Is(var, ty) => Some((var.clone(), *ty)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn is_small(&self, imm: &Immediate<VarName>, window: &CheckWindow) -> bool {
+        match self.constant(imm) {
+            Some(c) => c.unsigned_abs() < MAX_DISTANCE as u64,
+            None => matches!(imm, Immediate::Var(var) if window.small.contains(var)),
+        }
+    }
+
+    /// Ends the combining of the checks in `window` at `end`
+    fn close(&mut self, window: &mut CheckWindow, end: impl ToString) {
+        if let Some(group) = window.group.take() {
+            if group.reach > 0 {
+                self.widened.insert(group.check, group.reach);
+            }
+            window.closed = Some((group, end.to_string()));
+        }
+    }
+
+    /// Why the check of `key` at `offset` is not combined with the previous
+    /// one of the same array and index, if there is one
+    fn missed(&self, key: &ArrayIndex, offset: i64, window: &CheckWindow) -> Option<String> {
+        let first = |group: &CheckGroup| shown_access(&group.key, group.offset);
+        match (&window.group, &window.closed) {
+            (Some(group), _) if group.key == *key => {
+                let distance = offset - group.offset;
+                Some(if distance < 0 {
+                    format!("the index is below the one of {}", first(group))
+                } else if distance >= MAX_DISTANCE {
+                    format!(
+                        "the index is {} or more above the one of {}",
+                        MAX_DISTANCE,
+                        first(group)
+                    )
+                } else {
+                    format!(
+                        "strict errors combine only the checks of the index of {}",
+                        first(group)
+                    )
+                })
+            }
+            (None, Some((group, end))) if group.key == *key => {
+                Some(format!("{} since the check of {}", end, first(group)))
+            }
+            _ => None,
+        }
+    }
+
+    fn analyze(&mut self, body: &BlockBody<VarName, Nil>, window: &mut CheckWindow) {
+        match body {
+            BlockBody::Terminator(..) => self.close(window, "the end of the block"),
+            BlockBody::Operation { dest, op, .. } => match op {
+                Operation::Immediate(imm) => {
+                    if let Some(ty) = self.type_of(imm, window) {
+                        window.types.insert(dest.clone(), ty);
+                    }
+                    if self.is_small(imm, window) {
+                        window.small.insert(dest.clone());
+                    }
+                }
+                Operation::Prim2(Prim2::Add | Prim2::Sub, lhs, rhs)
+                    if self.is_small(lhs, window) && self.is_small(rhs, window) =>
+                {
+                    let int = Some(Type::Int);
+                    if self.type_of(lhs, window) == int && self.type_of(rhs, window) == int {
+                        window.types.insert(dest.clone(), Type::Int);
+                    }
+                    window.small.insert(dest.clone());
+                }
+                Operation::Prim1(Prim1::BitSar(_), imm) if self.is_small(imm, window) => {
+                    window.small.insert(dest.clone());
+                }
+                // may overflow, run out of memory or call out
+                Operation::Prim2(Prim2::Add | Prim2::Sub | Prim2::Mul, ..) => {
+                    self.close(window, format!("{} may overflow", dest))
+                }
+                Operation::Call { fun, .. } => {
+                    self.close(window, format!("{} calls {}", dest, fun))
+                }
+                Operation::AllocateArray { .. } => {
+                    self.close(window, format!("the allocation of {} may fail", dest))
+                }
+                Operation::Prim1(..)
+                | Operation::Prim2(..)
+                | Operation::Load { .. }
+                | Operation::Str(_) => {}
+            },
+            BlockBody::SubBlocks { blocks, .. } => {
+                for block in blocks.iter() {
+                    let mut types = window.types.clone();
+                    types.extend(self.proven(&block.label));
+                    let small = window.small.clone();
+                    let mut inner = CheckWindow {
+                        group: None,
+                        types,
+                        small,
+                        function: window.function.clone(),
+                        closed: None,
+                    };
+                    self.analyze(&block.body, &mut inner);
+                }
+            }
+            BlockBody::AssertType { ty, arg, .. } => {
+                if self.type_of(arg, window) != Some(*ty) {
+                    self.close(
+                        window,
+                        format!("the assertion that {} is {} may fail", arg, ty),
+                    );
+                }
+                if let Immediate::Var(var) = arg {
+                    window.types.insert(var.clone(), *ty);
+                }
+            }
+            BlockBody::AssertLength { len, .. } => self.close(
+                window,
+                format!("the assertion that {} is a length may fail", len),
+            ),
+            BlockBody::AssertInBounds { bound, arg, .. } => {
+                let check = self.checks;
+                self.checks += 1;
+                let access = self.access(bound, arg);
+                let combined = match (&mut window.group, &access) {
+                    (Some(group), Some((key, offset))) if group.key == *key => {
+                        let distance = offset - group.offset;
+                        if distance == 0 || (!self.strict && (0..MAX_DISTANCE).contains(&distance))
+                        {
+                            group.reach = group.reach.max(distance);
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                    _ => false,
+                };
+                if combined {
+                    self.removed.insert(check);
+                    if let (Some(group), Some((key, offset))) = (&window.group, &access) {
+                        let message = format!(
+                            "combined the check of {} with the one of {}",
+                            shown_access(key, *offset),
+                            shown_access(&group.key, group.offset)
+                        );
+                        let remark = Remark::passed(Self::PASS, &window.function, message);
+                        self.remarks.push(remark);
+                    }
+                } else {
+                    if let Some((key, offset)) = &access {
+                        if let Some(reason) = self.missed(key, *offset, window) {
+                            let shown = shown_access(key, *offset);
+                            let message = format!("kept the check of {}: {}", shown, reason);
+                            let remark = Remark::missed(Self::PASS, &window.function, message);
+                            self.remarks.push(remark);
+                        }
+                    }
+                    let end = match &access {
+                        Some((key, offset)) => {
+                            format!("the check of {}", shown_access(key, *offset))
+                        }
+                        None => format!("the check of {}", arg),
+                    };
+                    self.close(window, end);
+                    window.group = access.map(|(key, offset)| CheckGroup {
+                        check,
+                        key,
+                        offset,
+                        reach: 0,
+                    });
+                }
+                // the index is now below the length of an array
+                if let Immediate::Var(var) = arg {
+                    window.small.insert(var.clone());
+                }
+                if let Some(CheckGroup {
+                    key: (_, Some(root)),
+                    ..
+                }) = &window.group
+                {
+                    window.small.insert(root.clone());
+                }
+            }
+            // the stores of a program that fails are not seen
+            BlockBody::Store { .. } => {}
+        }
+        if let Some(next) = body.successor() {
+            self.analyze(next, window);
+        }
+    }
+
+    fn rewrite_block(
+        &mut self,
+        mut block: BasicBlock<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> BasicBlock<VarName, Nil> {
+        block.body = self.rewrite(block.body, names);
+        block
+    }
+
+    fn rewrite(
+        &mut self,
+        body: BlockBody<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::Terminator(..) => body,
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => BlockBody::Operation {
+                dest,
+                op,
+                next: Box::new(self.rewrite(*next, names)),
+                ana,
+            },
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.rewrite_block(block, names))
+                    .collect(),
+                next: Box::new(self.rewrite(*next, names)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => {
+                let check = self.checks;
+                self.checks += 1;
+                if self.removed.contains(&check) {
+                    return self.rewrite(*next, names);
+                }
+                let next = Box::new(self.rewrite(*next, names));
+                match self.widened.get(&check) {
+                    Some(reach) => {
+                        // bound = len - reach
+                        let narrowed = names.fresh_var("bound");
+                        BlockBody::Operation {
+                            dest: narrowed.clone(),
+                            op: Operation::Prim2(Prim2::Sub, bound, Immediate::Const(*reach)),
+                            next: Box::new(BlockBody::AssertInBounds {
+                                bound: Immediate::Var(narrowed),
+                                arg,
+                                next,
+                                ana,
+                            }),
+                            ana: Nil,
+                        }
+                    }
+                    None => BlockBody::AssertInBounds {
+                        bound,
+                        arg,
+                        next,
+                        ana,
+                    },
+                }
+            }
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.rewrite(*next, names)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.rewrite(*next, names)),
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.rewrite(*next, names)),
+                ana,
+            },
+        }
+    }
+}
+
+impl Default for BoundsCheckCombiner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*
+ * Function cloning
+ * */
+
+/// Adds a copy of the function `original` named `name`, whose blocks and
+/// variables have fresh names
+fn clone_fun(
+    prog: &mut Program<VarName, Nil>,
+    original: &FunName,
+    name: FunName,
+    names: &mut NameSupply,
+) {
+    let fun = prog.funs.iter().find(|fun| &fun.name == original).unwrap();
+    let block = prog
+        .blocks
+        .iter()
+        .find(|b| b.label == fun.body.target)
+        .unwrap();
+    let (block, mut vars) = copy_block(block, names);
+    let params: Vec<_> = (fun.params.iter())
+        .map(|param| {
+            let copy = names.fresh_var(param.hint());
+            vars.insert(param.clone(), copy.clone());
+            copy
+        })
+        .collect();
+    let body = fun.body.map_vars(&mut |var| vars[var].clone());
+    let body = Branch {
+        target: block.label.clone(),
+        args: body.args,
+    };
+    prog.funs.push(FunBlock { name, params, body });
+    prog.blocks.push(block);
+}
+
+/// Makes the calls to the destinations in `redirects` call the functions there
+fn redirect_calls(
+    mut prog: Program<VarName, Nil>,
+    redirects: &HashMap<VarName, FunName>,
+) -> Program<VarName, Nil> {
+    prog.blocks = prog
+        .blocks
+        .into_iter()
+        .map(|block| redirect_block(block, redirects))
+        .collect();
+    prog
+}
+
+/// The number of instructions of `body` and its sub-blocks
+fn body_size(body: &BlockBody<VarName, Nil>) -> usize {
+    let blocks = match body {
+        BlockBody::SubBlocks { blocks, .. } => {
+            blocks.iter().map(|block| body_size(&block.body)).sum()
+        }
+        _ => 0,
+    };
+    blocks + 1 + body.successor().map_or(0, body_size)
+}
+
+fn redirect_block(
+    mut block: BasicBlock<VarName, Nil>,
+    redirects: &HashMap<VarName, FunName>,
+) -> BasicBlock<VarName, Nil> {
+    block.body = redirect_block_body(block.body, redirects);
+    block
+}
+
+fn redirect_block_body(
+    body: BlockBody<VarName, Nil>,
+    redirects: &HashMap<VarName, FunName>,
+) -> BlockBody<VarName, Nil> {
+    match body {
+        BlockBody::Terminator(..) => body,
+        BlockBody::Operation {
+            dest,
+            op,
+            next,
+            ana,
+        } => {
+            let op = match (op, redirects.get(&dest)) {
+                (Operation::Call { args, .. }, Some(clone)) => Operation::Call {
+                    fun: clone.clone(),
+                    args,
+                },
+                (op, _) => op,
+            };
+            BlockBody::Operation {
+                dest,
+                op,
+                next: Box::new(redirect_block_body(*next, redirects)),
+                ana,
+            }
+        }
+        BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+            blocks: blocks
+                .into_iter()
+                .map(|block| redirect_block(block, redirects))
+                .collect(),
+            next: Box::new(redirect_block_body(*next, redirects)),
+            ana,
+        },
+        BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+            ty,
+            arg,
+            next: Box::new(redirect_block_body(*next, redirects)),
+            ana,
+        },
+        BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+            len,
+            next: Box::new(redirect_block_body(*next, redirects)),
+            ana,
+        },
+        BlockBody::AssertInBounds {
+            bound,
+            arg,
+            next,
+            ana,
+        } => BlockBody::AssertInBounds {
+            bound,
+            arg,
+            next: Box::new(redirect_block_body(*next, redirects)),
+            ana,
+        },
+        BlockBody::Store {
+            addr,
+            offset,
+            val,
+            next,
+            ana,
+        } => BlockBody::Store {
+            addr,
+            offset,
+            val,
+            next: Box::new(redirect_block_body(*next, redirects)),
+            ana,
+        },
+    }
+}
+
+/// A copy of `block` with fresh names for its variables and its blocks,
+/// and the names of the variables
+fn copy_block(
+    block: &BasicBlock<VarName, Nil>,
+    names: &mut NameSupply,
+) -> (BasicBlock<VarName, Nil>, HashMap<VarName, VarName>) {
+    let mut vars = HashMap::new();
+    let block = block.map_vars(&mut |var: &VarName| {
+        let copy = vars.entry(var.clone());
+        copy.or_insert_with(|| names.fresh_var(var.hint())).clone()
+    });
+    let mut labels = HashMap::new();
+    block_labels(&block, &mut labels, names);
+    (relabel_block(block, &labels), vars)
+}
+
+fn block_labels(
+    block: &BasicBlock<VarName, Nil>,
+    labels: &mut HashMap<BlockName, BlockName>,
+    names: &mut NameSupply,
+) {
+    labels.insert(block.label.clone(), names.fresh_block(block.label.hint()));
+    let mut body = &block.body;
+    loop {
+        if let BlockBody::SubBlocks { blocks, .. } = body {
+            for block in blocks.iter() {
+                block_labels(block, labels, names);
+            }
+        }
+        match body.successor() {
+            Some(next) => body = next,
+            None => break,
+        }
+    }
+}
+
+fn relabel_block(
+    mut block: BasicBlock<VarName, Nil>,
+    labels: &HashMap<BlockName, BlockName>,
+) -> BasicBlock<VarName, Nil> {
+    block.label = labels[&block.label].clone();
+    block.body = relabel_block_body(block.body, labels);
+    block
+}
+
+fn relabel_block_body(
+    body: BlockBody<VarName, Nil>,
+    labels: &HashMap<BlockName, BlockName>,
+) -> BlockBody<VarName, Nil> {
+    let label = |label: BlockName| labels.get(&label).cloned().unwrap_or(label);
+    match body {
+        BlockBody::Terminator(terminator, ana) => {
+            let terminator = match terminator {
+                Terminator::Branch(Branch { target, args }) => Terminator::Branch(Branch {
+                    target: label(target),
+                    args,
+                }),
+                Terminator::ConditionalBranch { cond, thn, els } => Terminator::ConditionalBranch {
+                    cond,
+                    thn: label(thn),
+                    els: label(els),
+                },
+                Terminator::Switch {
+                    scrutinee,
+                    low,
+                    targets,
+                    default,
+                } => Terminator::Switch {
+                    scrutinee,
+                    low,
+                    targets: targets.into_iter().map(label).collect(),
+                    default: label(default),
+                },
+                terminator => terminator,
+            };
+            BlockBody::Terminator(terminator, ana)
+        }
+        BlockBody::Operation {
+            dest,
+            op,
+            next,
+            ana,
+        } => BlockBody::Operation {
+            dest,
+            op,
+            next: Box::new(relabel_block_body(*next, labels)),
+            ana,
+        },
+        BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+            blocks: blocks
+                .into_iter()
+                .map(|block| relabel_block(block, labels))
+                .collect(),
+            next: Box::new(relabel_block_body(*next, labels)),
+            ana,
+        },
+        BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+            ty,
+            arg,
+            next: Box::new(relabel_block_body(*next, labels)),
+            ana,
+        },
+        BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+            len,
+            next: Box::new(relabel_block_body(*next, labels)),
+            ana,
+        },
+        BlockBody::AssertInBounds {
+            bound,
+            arg,
+            next,
+            ana,
+        } => BlockBody::AssertInBounds {
+            bound,
+            arg,
+            next: Box::new(relabel_block_body(*next, labels)),
+            ana,
+        },
+        BlockBody::Store {
+            addr,
+            offset,
+            val,
+            next,
+            ana,
+        } => BlockBody::Store {
+            addr,
+            offset,
+            val,
+            next: Box::new(relabel_block_body(*next, labels)),
+            ana,
+        },
+    }
+}
+
+/*
+ * Interprocedural constant propagation
+ * */
+
+/// What a parameter is passed by all the calls and branches that reach it,
+/// e.g. a constant or a type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Passed<T> {
+    /// Nothing yet, e.g. in a function that is never called
+    Nothing,
+    Known(T),
+    Any,
+}
+
+impl<T: PartialEq> Passed<T> {
+    fn meet(self, other: Passed<T>) -> Passed<T> {
+        match (self, other) {
+            (Passed::Nothing, passed) | (passed, Passed::Nothing) => passed,
+            (Passed::Known(a), Passed::Known(b)) if a == b => Passed::Known(a),
+            _ => Passed::Any,
+        }
+    }
+}
+
+/// The arguments of a call that are constants
+type ConstantArgs = Vec<Option<i64>>;
+
+/// Specializes the functions for the constants that they are called with.
+///
+/// A parameter of a block that every call and branch passes the same
+/// constant is defined as the constant at the start of the block, which
+/// `CopyPropagator` propagates into the body. The parameters that are passed
+/// themselves, e.g. by recursive calls, do not stand in the way.
+///
+/// A function that is called with different constants is cloned for each list
+/// of constant arguments it is called with, and the calls with the list call
+/// the clone, for at most `max_clones` lists and functions of at most
+/// `max_size` instructions. The clone of a function calls the clones of the
+/// recursive calls that the function made with constants.
+pub struct ConstantSpecializer {
+    max_clones: usize,
+    max_size: usize,
+    /// What was specialized, and what was not
+    remarks: Vec<Remark>,
+    /// The function of the block being specialized
+    function: String,
+}
+
+impl ConstantSpecializer {
+    const PASS: Optimization = Optimization::InterproceduralConstantPropagation;
+
+    pub fn new() -> Self {
+        Self {
+            max_clones: 2,
+            max_size: 200,
+            remarks: Vec::new(),
+            function: String::new(),
+        }
+    }
+
+    /// The most clones made of a function
+    pub fn max_clones(mut self, max_clones: usize) -> Self {
+        self.max_clones = max_clones;
+        self
+    }
+
+    /// The most instructions of a function that is cloned
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// The functions that were cloned and the parameters that were specialized
+    pub fn notes(&self) -> Vec<String> {
+        self.remarks.iter().map(|r| r.message.clone()).collect()
+    }
+
+    pub fn remarks(&self) -> &[Remark] {
+        &self.remarks
+    }
+
+    pub fn run(
+        &mut self,
+        prog: Program<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> Program<VarName, Nil> {
+        let mut prog = self.clone_funs(prog, names);
+        let passed = Self::analyze(&prog);
+        let functions = remarks::functions(&prog);
+        prog.blocks = prog
+            .blocks
+            .into_iter()
+            .map(|block| {
+                self.function = functions[&block.label].clone();
+                self.specialize_block(block, &passed, names)
+            })
+            .collect();
+        prog
+    }
+
+    /// What each parameter of a function or a block is passed, as the
+    /// greatest fixpoint over the calls and branches
+    fn analyze(prog: &Program<VarName, Nil>) -> HashMap<VarName, Passed<i64>> {
+        let mut edges = Vec::new();
+        let mut copies = HashMap::new();
+        let mut params = HashMap::new();
+        fn collect_block<'a>(
+            block: &'a BasicBlock<VarName, Nil>,
+            params: &mut HashMap<&'a BlockName, &'a [VarName]>,
+        ) {
+            params.insert(&block.label, &block.params);
+            let mut body = &block.body;
+            loop {
+                if let BlockBody::SubBlocks { blocks, .. } = body {
+                    for block in blocks.iter() {
+                        collect_block(block, params);
+                    }
+                }
+                match body.successor() {
+                    Some(next) => body = next,
+                    None => break,
+                }
+            }
+        }
+        for block in prog.blocks.iter() {
+            collect_block(block, &mut params);
+        }
+        let funs: HashMap<_, _> = prog.funs.iter().map(|fun| (&fun.name, fun)).collect();
+        let mut passed = HashMap::new();
+        for fun in prog.funs.iter() {
+            // the entry is called with any arguments
+            let initial = if fun.name.is_unmangled() {
+                Passed::Any
+            } else {
+                Passed::Nothing
+            };
+            passed.extend(fun.params.iter().map(|param| (param.clone(), initial)));
+            let targets = params[&fun.body.target].iter();
+            edges.extend(targets.zip(fun.body.args.iter()));
+        }
+        for params in params.values() {
+            passed.extend(params.iter().map(|param| (param.clone(), Passed::Nothing)));
+        }
+        fn collect_body<'a>(
+            body: &'a BlockBody<VarName, Nil>,
+            params: &HashMap<&'a BlockName, &'a [VarName]>,
+            funs: &HashMap<&'a FunName, &'a FunBlock<VarName>>,
+            edges: &mut Vec<(&'a VarName, &'a Immediate<VarName>)>,
+            copies: &mut HashMap<&'a VarName, &'a Immediate<VarName>>,
+        ) {
+            match body {
+                BlockBody::Terminator(Terminator::Branch(branch), _) => {
+                    edges.extend(params[&branch.target].iter().zip(branch.args.iter()));
+                }
+                BlockBody::Operation {
+                    op: Operation::Call { fun, args },
+                    ..
+                } => {
+                    if let Some(fun) = funs.get(fun) {
+                        edges.extend(fun.params.iter().zip(args.iter()));
+                    }
+                }
+                BlockBody::Operation {
+                    dest,
+                    op: Operation::Immediate(imm),
+                    ..
+                } => {
+                    copies.insert(dest, imm);
+                }
+                BlockBody::SubBlocks { blocks, .. } => {
+                    for block in blocks.iter() {
+                        collect_body(&block.body, params, funs, edges, copies);
+                    }
+                }
+                _ => {}
+            }
+            if let Some(next) = body.successor() {
+                collect_body(next, params, funs, edges, copies);
+            }
+        }
+        for block in prog.blocks.iter() {
+            collect_body(&block.body, &params, &funs, &mut edges, &mut copies);
+        }
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (param, arg) in edges.iter() {
+                let arg = Self::value(arg, &copies, &passed);
+                let old = passed[*param];
+                let new = old.meet(arg);
+                if new != old {
+                    passed.insert((*param).clone(), new);
+                    changed = true;
+                }
+            }
+        }
+        passed
+    }
+
+    /// What `arg` is, through copies and the parameters it is a copy of
+    fn value<'a>(
+        mut arg: &'a Immediate<VarName>,
+        copies: &HashMap<&VarName, &'a Immediate<VarName>>,
+        passed: &HashMap<VarName, Passed<i64>>,
+    ) -> Passed<i64> {
+        loop {
+            match arg {
+                Immediate::Const(c) => return Passed::Known(*c),
+                Immediate::Var(var) => match copies.get(var) {
+                    Some(copied) => arg = copied,
+                    None => return passed.get(var).copied().unwrap_or(Passed::Any),
+                },
+            }
+        }
+    }
+
+    /// Defines the parameters of `block` and its sub-blocks that are always
+    /// passed the same constant as the constant
+    fn specialize_block(
+        &mut self,
+        mut block: BasicBlock<VarName, Nil>,
+        passed: &HashMap<VarName, Passed<i64>>,
+        names: &mut NameSupply,
+    ) -> BasicBlock<VarName, Nil> {
+        block.body = self.specialize_block_body(block.body, passed, names);
+        let mut constants = Vec::new();
+        for param in block.params.iter_mut() {
+            if let Some(Passed::Known(c)) = passed.get(param) {
+                let note = format!("{}: {} is always {}", block.label, param, c);
+                (self.remarks).push(Remark::passed(Self::PASS, &self.function, note));
+                let unused = names.fresh_var(param.hint());
+                constants.push((std::mem::replace(param, unused), *c));
+            }
+        }
+        for (param, c) in constants.into_iter().rev() {
+            block.body = BlockBody::Operation {
+                dest: param,
+                op: Operation::Immediate(Immediate::Const(c)),
+                next: Box::new(block.body),
+                ana: Nil,
+            };
+        }
+        block
+    }
+
+    fn specialize_block_body(
+        &mut self,
+        body: BlockBody<VarName, Nil>,
+        passed: &HashMap<VarName, Passed<i64>>,
+        names: &mut NameSupply,
+    ) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.specialize_block(block, passed, names))
+                    .collect(),
+                next,
+                ana,
+            },
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => BlockBody::Operation {
+                dest,
+                op,
+                next: Box::new(self.specialize_block_body(*next, passed, names)),
+                ana,
+            },
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.specialize_block_body(*next, passed, names)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.specialize_block_body(*next, passed, names)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.specialize_block_body(*next, passed, names)),
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.specialize_block_body(*next, passed, names)),
+                ana,
+            },
+            BlockBody::Terminator(..) => body,
+        }
+    }
+
+    /// Clones the functions called with different constants, and redirects
+    /// the calls with the constants of a clone to it
+    fn clone_funs(
+        &mut self,
+        prog: Program<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> Program<VarName, Nil> {
+        let passed = Self::analyze(&prog);
+        let mut copies = HashMap::new();
+        let mut calls = Vec::new();
+        for block in prog.blocks.iter() {
+            Self::calls(&block.body, &mut copies, &mut calls);
+        }
+        // the constant arguments of each call of each function
+        let mut lists: HashMap<&FunName, Vec<(&VarName, ConstantArgs)>> = HashMap::new();
+        for (dest, fun, args) in calls {
+            let args = args
+                .iter()
+                .map(|arg| match Self::value(arg, &copies, &passed) {
+                    Passed::Known(c) => Some(c),
+                    _ => None,
+                });
+            lists.entry(fun).or_default().push((dest, args.collect()));
+        }
+        let mut redirects: HashMap<VarName, FunName> = HashMap::new();
+        let mut clones = Vec::new();
+        for fun in prog.funs.iter() {
+            let Some(calls) = lists.get(&fun.name) else {
+                continue;
+            };
+            // the constant arguments that not all calls pass
+            let varying: Vec<_> = (fun.params.iter())
+                .map(|param| !matches!(passed[param], Passed::Known(_)))
+                .collect();
+            // the constants of each call that not all calls pass
+            let calls: Vec<(&VarName, ConstantArgs)> = (calls.iter())
+                .map(|(dest, list)| {
+                    let list = list.iter().zip(varying.iter());
+                    (
+                        *dest,
+                        list.map(|(c, varying)| c.filter(|_| *varying)).collect(),
+                    )
+                })
+                .collect();
+            let mut distinct: Vec<&ConstantArgs> = Vec::new();
+            for (_, list) in calls.iter() {
+                if !distinct.contains(&list) {
+                    distinct.push(list);
+                }
+            }
+            let block = prog.blocks.iter().find(|b| b.label == fun.body.target);
+            let Some(block) = block else {
+                continue;
+            };
+            if distinct.len() < 2 {
+                continue;
+            }
+            let size = body_size(&block.body);
+            if size > self.max_size {
+                let note = format!(
+                    "not cloned {}: {} instructions, more than {}",
+                    fun.name, size, self.max_size
+                );
+                self.remarks
+                    .push(Remark::missed(Self::PASS, &fun.name, note));
+                continue;
+            }
+            let shown = |list: &ConstantArgs| {
+                let list = list.iter().map(|c| match c {
+                    Some(c) => c.to_string(),
+                    None => "_".to_string(),
+                });
+                format!("{}({})", fun.name, list.collect::<Vec<_>>().join(", "))
+            };
+            let specialized = distinct
+                .into_iter()
+                .filter(|list| list.iter().any(Option::is_some));
+            for (i, list) in specialized.enumerate() {
+                if i >= self.max_clones {
+                    let note = format!(
+                        "not cloned {} for {}: at most {} clones",
+                        fun.name,
+                        shown(list),
+                        self.max_clones
+                    );
+                    self.remarks
+                        .push(Remark::missed(Self::PASS, &fun.name, note));
+                    continue;
+                }
+                let name = names.fresh_fun(fun.name.hint());
+                let note = format!("cloned {} as {} for {}", fun.name, name, shown(list));
+                self.remarks
+                    .push(Remark::passed(Self::PASS, &fun.name, note));
+                for (dest, _) in calls.iter().filter(|(_, args)| args == list) {
+                    redirects.insert((*dest).clone(), name.clone());
+                }
+                clones.push((fun.name.clone(), name));
+            }
+        }
+        if clones.is_empty() {
+            return prog;
+        }
+        let mut prog = redirect_calls(prog, &redirects);
+        for (original, name) in clones {
+            clone_fun(&mut prog, &original, name, names);
+        }
+        prog
+    }
+
+    /// The calls of `body`, with their destinations, and its copies
+    fn calls<'a>(
+        body: &'a BlockBody<VarName, Nil>,
+        copies: &mut HashMap<&'a VarName, &'a Immediate<VarName>>,
+        calls: &mut Vec<(&'a VarName, &'a FunName, &'a [Immediate<VarName>])>,
+    ) {
+        match body {
+            BlockBody::Operation {
+                dest,
+                op: Operation::Call { fun, args },
+                ..
+            } => calls.push((dest, fun, args)),
+            BlockBody::Operation {
+                dest,
+                op: Operation::Immediate(imm),
+                ..
+            } => {
+                copies.insert(dest, imm);
+            }
+            BlockBody::SubBlocks { blocks, .. } => {
+                for block in blocks.iter() {
+                    Self::calls(&block.body, copies, calls);
+                }
+            }
+            _ => {}
+        }
+        if let Some(next) = body.successor() {
+            Self::calls(next, copies, calls);
+        }
+    }
+}
+
+impl Default for ConstantSpecializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*
+ * Type specialization
+ * */
+
+/// The types of the arguments of a call that the callee asserts
+type TypeProfile = Vec<Option<Type>>;
+
+/// Specializes the functions for the types of their arguments.
+///
+/// The type of a value is evident from the lowering when the value is a
+/// constant, a tagged comparison or array, checked arithmetic on integers, or
+/// was asserted before, and a parameter has the type that all the calls and
+/// branches pass it. The type assertions on values of evident types are removed.
+///
+/// A function that asserts the type of a parameter, and is called with
+/// different types of it, is cloned for each profile of the types of the
+/// calls, and the calls with the profile call the clone, which then drops the
+/// assertions. The same limits as in `ConstantSpecializer` apply.
+pub struct TypeSpecializer {
+    max_clones: usize,
+    max_size: usize,
+    /// The operations that define the variables
+    defs: HashMap<VarName, Operation<VarName>>,
+    /// The types of the parameters
+    params: HashMap<VarName, Passed<Type>>,
+    /// The types of the variables where they are defined
+    typed: HashMap<VarName, Passed<Type>>,
+    removed: usize,
+    /// What was specialized, and what was not
+    remarks: Vec<Remark>,
+}
+
+impl TypeSpecializer {
+    const PASS: Optimization = Optimization::TypeSpecialization;
+
+    pub fn new() -> Self {
+        Self {
+            max_clones: 2,
+            max_size: 200,
+            defs: HashMap::new(),
+            params: HashMap::new(),
+            typed: HashMap::new(),
+            removed: 0,
+            remarks: Vec::new(),
+        }
+    }
+
+    /// The most clones made of a function
+    pub fn max_clones(mut self, max_clones: usize) -> Self {
+        self.max_clones = max_clones;
+        self
+    }
+
+    /// The most instructions of a function that is cloned
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// The number of type assertions removed
+    pub fn removed(&self) -> usize {
+        self.removed
+    }
+
+    /// The functions that were cloned
+    pub fn notes(&self) -> Vec<String> {
+        self.remarks.iter().map(|r| r.message.clone()).collect()
+    }
+
+    pub fn remarks(&self) -> &[Remark] {
+        &self.remarks
+    }
+
+    pub fn run(
+        &mut self,
+        prog: Program<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> Program<VarName, Nil> {
+        let mut prog = self.clone_funs(prog, names);
+        self.infer(&prog);
+        prog.blocks = prog
+            .blocks
+            .into_iter()
+            .map(|block| self.remove_block(block, HashMap::new()))
+            .collect();
+        prog
+    }
+
+    /// The type of `imm` after the assertions in `facts`
+    fn type_of(&self, imm: &Immediate<VarName>, facts: &HashMap<VarName, Type>) -> Passed<Type> {
+        match imm {
+            Immediate::Const(c) => [Type::Int, Type::Bool, Type::Array]
+                .into_iter()
+                .find(|ty| c & ty.mask() == ty.tag())
+                .map_or(Passed::Any, Passed::Known),
+            Immediate::Var(var) => match facts.get(var) {
+                Some(ty) => Passed::Known(*ty),
+                None => (self.typed.get(var))
+                    .or_else(|| self.params.get(var))
+                    .copied()
+                    .unwrap_or(Passed::Any),
+            },
+        }
+    }
+
+    /// The number of low bits of `imm` that are always zero
+    fn zero_bits(&self, imm: &Immediate<VarName>) -> u32 {
+        match imm {
+            Immediate::Const(c) => c.trailing_zeros(),
+            Immediate::Var(var) => match self.defs.get(var) {
+                Some(Operation::Immediate(imm)) => self.zero_bits(imm),
+                // arrays are aligned to 8 bytes
+                Some(Operation::AllocateArray { .. }) => 3,
+                Some(Operation::Prim1(Prim1::BitSal(by) | Prim1::BitShl(by), _)) => *by as u32,
+                _ => 0,
+            },
+        }
+    }
+
+    /// The type of the result of `op`
+    fn op_type(&self, op: &Operation<VarName>, facts: &HashMap<VarName, Type>) -> Passed<Type> {
+        let int = Passed::Known(Type::Int);
+        match op {
+            Operation::Immediate(imm) => self.type_of(imm, facts),
+            // the checked arithmetic on integers, where an even factor makes the product even
+            Operation::Prim2(prim @ (Prim2::Add | Prim2::Sub | Prim2::Mul), lhs, rhs) => {
+                let operands = [self.type_of(lhs, facts), self.type_of(rhs, facts)];
+                let mul = *prim == Prim2::Mul;
+                if (mul && operands.contains(&int)) || operands.iter().all(|ty| *ty == int) {
+                    int
+                } else if operands
+                    .iter()
+                    .all(|ty| matches!(ty, Passed::Nothing | Passed::Known(Type::Int)))
+                    || (mul && operands.contains(&Passed::Nothing))
+                {
+                    Passed::Nothing
+                } else {
+                    Passed::Any
+                }
+            }
+            // a tag on a value whose low bits are zero
+            Operation::Prim2(Prim2::BitOr, val, Immediate::Const(tag)) => {
+                let zero = self.zero_bits(val);
+                [Type::Int, Type::Bool, Type::Array]
+                    .into_iter()
+                    .find(|ty| ty.mask() < 1 << zero.min(62) && tag & ty.mask() == ty.tag())
+                    .map_or(Passed::Any, Passed::Known)
+            }
+            // the tag bits stay
+            Operation::Prim2(Prim2::BitXor, val, Immediate::Const(c)) => {
+                match self.type_of(val, facts) {
+                    Passed::Known(ty) if c & ty.mask() == 0 => Passed::Known(ty),
+                    Passed::Nothing => Passed::Nothing,
+                    _ => Passed::Any,
+                }
+            }
+            _ => Passed::Any,
+        }
+    }
+
+    /// Finds the types of the parameters, as the greatest fixpoint over the
+    /// calls and branches, and the types of the arguments of the calls
+    fn infer(
+        &mut self,
+        prog: &Program<VarName, Nil>,
+    ) -> Vec<(VarName, FunName, Vec<Passed<Type>>)> {
+        self.defs.clear();
+        let mut params = HashMap::new();
+        fn collect(
+            body: &BlockBody<VarName, Nil>,
+            defs: &mut HashMap<VarName, Operation<VarName>>,
+            params: &mut HashMap<BlockName, Vec<VarName>>,
+        ) {
+            match body {
+                BlockBody::Operation { dest, op, .. } => {
+                    defs.insert(dest.clone(), op.clone());
+                }
+                BlockBody::SubBlocks { blocks, .. } => {
+                    for block in blocks.iter() {
+                        params.insert(block.label.clone(), block.params.clone());
+                        collect(&block.body, defs, params);
+                    }
+                }
+                _ => {}
+            }
+            if let Some(next) = body.successor() {
+                collect(next, defs, params);
+            }
+        }
+        for block in prog.blocks.iter() {
+            params.insert(block.label.clone(), block.params.clone());
+            collect(&block.body, &mut self.defs, &mut params);
+        }
+        let funs: HashMap<_, _> = prog.funs.iter().map(|fun| (&fun.name, fun)).collect();
+        let mut initial: HashMap<_, _> = (params.values().flatten())
+            .map(|param| (param.clone(), Passed::Nothing))
+            .collect();
+        for fun in prog.funs.iter() {
+            // the entry is called with any arguments
+            let passed = if fun.name.is_unmangled() {
+                Passed::Any
+            } else {
+                Passed::Nothing
+            };
+            initial.extend(fun.params.iter().map(|param| (param.clone(), passed)));
+        }
+        self.params = initial.clone();
+        loop {
+            self.typed.clear();
+            let mut passed = initial.clone();
+            let mut calls = Vec::new();
+            for block in prog.blocks.iter() {
+                let mut walk = Walk {
+                    params: &params,
+                    funs: &funs,
+                    passed: &mut passed,
+                    calls: &mut calls,
+                };
+                self.walk(&block.body, &mut HashMap::new(), &mut walk);
+            }
+            for fun in prog.funs.iter() {
+                let args = fun.body.args.iter();
+                for (param, arg) in params[&fun.body.target].iter().zip(args) {
+                    let ty = self.type_of(arg, &HashMap::new());
+                    let old = passed[param];
+                    passed.insert(param.clone(), old.meet(ty));
+                }
+            }
+            // the types only get less precise, so that the loop ends
+            for (param, ty) in passed.iter_mut() {
+                *ty = ty.meet(self.params[param]);
+            }
+            if passed == self.params {
+                return calls;
+            }
+            self.params = passed;
+        }
+    }
+
+    fn walk(
+        &mut self,
+        body: &BlockBody<VarName, Nil>,
+        facts: &mut HashMap<VarName, Type>,
+        walk: &mut Walk,
+    ) {
+        match body {
+            BlockBody::Operation { dest, op, .. } => {
+                let ty = self.op_type(op, facts);
+                self.typed.insert(dest.clone(), ty);
+                if let Operation::Call { fun, args } = op {
+                    if let Some(fun) = walk.funs.get(fun) {
+                        let tys: Vec<_> = args.iter().map(|arg| self.type_of(arg, facts)).collect();
+                        for (param, ty) in fun.params.iter().zip(tys.iter()) {
+                            let old = walk.passed[param];
+                            walk.passed.insert(param.clone(), old.meet(*ty));
+                        }
+                        walk.calls.push((dest.clone(), fun.name.clone(), tys));
+                    }
+                }
+            }
+            BlockBody::AssertType {
+                ty,
+                arg: Immediate::Var(var),
+                ..
+            } => self.assert(var, *ty, facts),
+            BlockBody::SubBlocks { blocks, .. } => {
+                for block in blocks.iter() {
+                    self.walk(&block.body, &mut facts.clone(), walk);
+                }
+            }
+            BlockBody::Terminator(Terminator::Branch(branch), _) => {
+                let params = walk.params[&branch.target].iter();
+                for (param, arg) in params.zip(branch.args.iter()) {
+                    let ty = self.type_of(arg, facts);
+                    let old = walk.passed[param];
+                    walk.passed.insert(param.clone(), old.meet(ty));
+                }
+            }
+            _ => {}
+        }
+        if let Some(next) = body.successor() {
+            self.walk(next, facts, walk);
+        }
+    }
+
+    /// Records that `var`, and the variables it is a copy of, have type `ty`
+    fn assert(&self, var: &VarName, ty: Type, facts: &mut HashMap<VarName, Type>) {
+        facts.insert(var.clone(), ty);
+        if let Some(Operation::Immediate(Immediate::Var(copied))) = self.defs.get(var) {
+            self.assert(copied, ty, facts);
+        }
+    }
+
+    /// `var`, through copies
+    fn root<'a>(&'a self, mut var: &'a VarName) -> &'a VarName {
+        while let Some(Operation::Immediate(Immediate::Var(copied))) = self.defs.get(var) {
+            var = copied;
+        }
+        var
+    }
+
+    /// The types that the block of `fun` asserts each parameter of `fun` to have
+    fn asserted(
+        &self,
+        fun: &FunBlock<VarName>,
+        block: &BasicBlock<VarName, Nil>,
+    ) -> Vec<Vec<Type>> {
+        let mut asserted = vec![Vec::new(); fun.params.len()];
+        let mut stack = vec![&block.body];
+        while let Some(body) = stack.pop() {
+            match body {
+                BlockBody::AssertType {
+                    ty,
+                    arg: Immediate::Var(var),
+                    ..
+                } => {
+                    // the parameter of the function that the block is passed there
+                    let root = self.root(var);
+                    let param = block.params.iter().position(|param| param == root);
+                    let found = param.and_then(|param| match &fun.body.args[param] {
+                        Immediate::Var(arg) => fun.params.iter().position(|p| p == arg),
+                        Immediate::Const(_) => None,
+                    });
+                    if let Some(i) = found {
+                        if !asserted[i].contains(ty) {
+                            asserted[i].push(*ty);
+                        }
+                    }
+                }
+                BlockBody::SubBlocks { blocks, .. } => {
+                    stack.extend(blocks.iter().map(|block| &block.body));
+                }
+                _ => {}
+            }
+            stack.extend(body.successor());
+        }
+        asserted
+    }
+
+    /// Clones the functions called with different types of the parameters
+    /// they assert, and redirects the calls with the types of a clone to it
+    fn clone_funs(
+        &mut self,
+        prog: Program<VarName, Nil>,
+        names: &mut NameSupply,
+    ) -> Program<VarName, Nil> {
+        let calls = self.infer(&prog);
+        let mut redirects: HashMap<VarName, FunName> = HashMap::new();
+        let mut clones = Vec::new();
+        for fun in prog.funs.iter() {
+            let block = prog.blocks.iter().find(|b| b.label == fun.body.target);
+            let Some(block) = block else {
+                continue;
+            };
+            let asserted = self.asserted(fun, block);
+            // the types of the calls that the function asserts, but not all calls pass
+            let profiles: Vec<(&VarName, TypeProfile)> = (calls.iter())
+                .filter(|(_, callee, _)| *callee == fun.name)
+                .map(|(dest, _, tys)| {
+                    let profile = tys.iter().zip(fun.params.iter()).enumerate();
+                    let profile = profile.map(|(i, (ty, param))| match ty {
+                        Passed::Known(ty)
+                            if asserted[i].contains(ty)
+                                && self.params[param] != Passed::Known(*ty) =>
+                        {
+                            Some(*ty)
+                        }
+                        _ => None,
+                    });
+                    (dest, profile.collect())
+                })
+                .collect();
+            let mut distinct: Vec<&TypeProfile> = Vec::new();
+            for (_, profile) in profiles.iter() {
+                if !distinct.contains(&profile) {
+                    distinct.push(profile);
+                }
+            }
+            if distinct.len() < 2 {
+                continue;
+            }
+            let size = body_size(&block.body);
+            if size > self.max_size {
+                let note = format!(
+                    "not cloned {}: {} instructions, more than {}",
+                    fun.name, size, self.max_size
+                );
+                self.remarks
+                    .push(Remark::missed(Self::PASS, &fun.name, note));
+                continue;
+            }
+            let shown = |profile: &TypeProfile| {
+                let profile = profile.iter().map(|ty| match ty {
+                    Some(ty) => ty.to_string(),
+                    None => "_".to_string(),
+                });
+                format!("{}({})", fun.name, profile.collect::<Vec<_>>().join(", "))
+            };
+            let specialized = distinct
+                .into_iter()
+                .filter(|profile| profile.iter().any(Option::is_some));
+            for (i, profile) in specialized.enumerate() {
+                if i >= self.max_clones {
+                    let note = format!(
+                        "not cloned {} for {}: at most {} clones",
+                        fun.name,
+                        shown(profile),
+                        self.max_clones
+                    );
+                    self.remarks
+                        .push(Remark::missed(Self::PASS, &fun.name, note));
+                    continue;
+                }
+                let name = names.fresh_fun(fun.name.hint());
+                let note = format!("cloned {} as {} for {}", fun.name, name, shown(profile));
+                self.remarks
+                    .push(Remark::passed(Self::PASS, &fun.name, note));
+                for (dest, _) in profiles.iter().filter(|(_, p)| p == profile) {
+                    redirects.insert((*dest).clone(), name.clone());
+                }
+                clones.push((fun.name.clone(), name));
+            }
+        }
+        if clones.is_empty() {
+            return prog;
+        }
+        let mut prog = redirect_calls(prog, &redirects);
+        for (original, name) in clones {
+            clone_fun(&mut prog, &original, name, names);
+        }
+        prog
+    }
+
+    fn remove_block(
+        &mut self,
+        mut block: BasicBlock<VarName, Nil>,
+        mut facts: HashMap<VarName, Type>,
+    ) -> BasicBlock<VarName, Nil> {
+        block.body = self.remove_block_body(block.body, &mut facts);
+        block
+    }
+
+    /// Removes the type assertions on values of evident types
+    fn remove_block_body(
+        &mut self,
+        body: BlockBody<VarName, Nil>,
+        facts: &mut HashMap<VarName, Type>,
+    ) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::Terminator(..) => body,
+            BlockBody::AssertType { ty, arg, next, ana } => {
+                if self.type_of(&arg, facts) == Passed::Known(ty) {
+                    self.removed += 1;
+                    return self.remove_block_body(*next, facts);
+                }
+                if let Immediate::Var(var) = &arg {
+                    self.assert(var, ty, facts);
+                }
+                BlockBody::AssertType {
+                    ty,
+                    arg,
+                    next: Box::new(self.remove_block_body(*next, facts)),
+                    ana,
+                }
+            }
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => BlockBody::Operation {
+                dest,
+                op,
+                next: Box::new(self.remove_block_body(*next, facts)),
+                ana,
+            },
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.remove_block(block, facts.clone()))
+                    .collect(),
+                next: Box::new(self.remove_block_body(*next, facts)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.remove_block_body(*next, facts)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.remove_block_body(*next, facts)),
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.remove_block_body(*next, facts)),
+                ana,
+            },
+        }
+    }
+}
+
+impl Default for TypeSpecializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What `TypeSpecializer::walk` collects
+struct Walk<'a> {
+    params: &'a HashMap<BlockName, Vec<VarName>>,
+    funs: &'a HashMap<&'a FunName, &'a FunBlock<VarName>>,
+    passed: &'a mut HashMap<VarName, Passed<Type>>,
+    calls: &'a mut Vec<(VarName, FunName, Vec<Passed<Type>>)>,
+}
+
+/*
+ * Memory SSA
+ * */
+
+/// Numbers the versions of the memory, so that each load depends on the
+/// version it reads and each store, call and allocation on the version it
+/// overwrites, instead of on the order of the instructions. The blocks
+/// merge the versions they are entered with.
+pub struct MemoryAnalyzer {
+    ssa: MemorySsa,
+    /// The version at the entry of each block
+    entries: HashMap<BlockName, Memory>,
+    /// The blocks of functions, which the calls enter with any memory
+    called: HashSet<BlockName>,
+}
+
+impl MemoryAnalyzer {
+    pub fn new<T>(prog: &Program<VarName, T>) -> Self {
+        let mut analyzer = Self {
+            ssa: MemorySsa::default(),
+            entries: HashMap::new(),
+            called: prog
+                .funs
+                .iter()
+                .map(|fun| fun.body.target.clone())
+                .collect(),
+        };
+        for block in prog.blocks.iter() {
+            analyzer.enter(block);
+        }
+        analyzer
+    }
+
+    /// The definitions of the versions of the memory that `analyze` found
+    pub fn memory(&self) -> &MemorySsa {
+        &self.ssa
+    }
+
+    fn fresh(&mut self, def: MemoryDef) -> Memory {
+        let mem = Memory(self.ssa.0.len() as u32);
+        self.ssa.0.insert(mem, def);
+        mem
+    }
+
+    fn enter<T>(&mut self, block: &BasicBlock<VarName, T>) {
+        let mem = self.fresh(MemoryDef::Phi(block.label.clone(), Vec::new()));
+        self.entries.insert(block.label.clone(), mem);
+        let mut body = &block.body;
+        loop {
+            if let BlockBody::SubBlocks { blocks, .. } = body {
+                for block in blocks.iter() {
+                    self.enter(block);
+                }
+            }
+            match body.successor() {
+                Some(next) => body = next,
+                None => break,
+            }
+        }
+    }
+
+    /// Adds `mem` to the versions that `target` is entered with
+    fn branch(&mut self, target: &BlockName, mem: Memory) {
+        if self.called.contains(target) {
+            return;
+        }
+        if let Some(MemoryDef::Phi(_, incoming)) = self.ssa.0.get_mut(&self.entries[target]) {
+            if !incoming.contains(&mem) {
+                incoming.push(mem);
+            }
+        }
+    }
+
+    pub fn analyze<T>(&mut self, prog: Program<VarName, T>) -> Program<VarName, MemoryAccess> {
+        Program {
+            externs: prog.externs,
+            funs: prog.funs,
+            blocks: prog
+                .blocks
+                .into_iter()
+                .map(|block| self.analyze_block(block))
+                .collect(),
+        }
+    }
+
+    fn analyze_block<T>(
+        &mut self,
+        BasicBlock {
+            label,
+            params,
+            body,
+            ana: _,
+        }: BasicBlock<VarName, T>,
+    ) -> BasicBlock<VarName, MemoryAccess> {
+        let mem = self.entries[&label];
+        BasicBlock {
+            label,
+            params,
+            body: self.analyze_block_body(mem, body),
+            ana: MemoryAccess {
+                uses: mem,
+                defs: mem,
+            },
+        }
+    }
+
+    fn analyze_block_body<T>(
+        &mut self,
+        mem: Memory,
+        body: BlockBody<VarName, T>,
+    ) -> BlockBody<VarName, MemoryAccess> {
+        let same = MemoryAccess {
+            uses: mem,
+            defs: mem,
+        };
+        match body {
+            BlockBody::Terminator(terminator, _) => {
+                match &terminator {
+                    Terminator::Return(_) => {}
+                    Terminator::Branch(branch) => self.branch(&branch.target, mem),
+                    Terminator::ConditionalBranch { thn, els, .. } => {
+                        self.branch(thn, mem);
+                        self.branch(els, mem);
+                    }
+                    Terminator::Switch {
+                        targets, default, ..
+                    } => {
+                        for target in targets.iter().chain([default]) {
+                            self.branch(target, mem);
+                        }
+                    }
+                }
+                BlockBody::Terminator(terminator, same)
+            }
+            BlockBody::Operation { dest, op, next, .. } => {
+                let defs = match &op {
+                    Operation::Call { fun, .. } => self.fresh(MemoryDef::Call(fun.clone(), mem)),
+                    Operation::AllocateArray { len } => self.fresh(MemoryDef::Allocate {
+                        array: dest.clone(),
+                        len: len.clone(),
+                        prev: mem,
+                    }),
+                    _ => mem,
+                };
+                BlockBody::Operation {
+                    dest,
+                    op,
+                    next: Box::new(self.analyze_block_body(defs, *next)),
+                    ana: MemoryAccess { uses: mem, defs },
+                }
+            }
+            BlockBody::SubBlocks { blocks, next, .. } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.analyze_block(block))
+                    .collect(),
+                next: Box::new(self.analyze_block_body(mem, *next)),
+                ana: same,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ..
+            } => {
+                let defs = self.fresh(MemoryDef::Store {
+                    addr: addr.clone(),
+                    offset: offset.clone(),
+                    val: val.clone(),
+                    prev: mem,
+                });
+                BlockBody::Store {
+                    addr,
+                    offset,
+                    val,
+                    next: Box::new(self.analyze_block_body(defs, *next)),
+                    ana: MemoryAccess { uses: mem, defs },
+                }
+            }
+            BlockBody::AssertType { ty, arg, next, .. } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.analyze_block_body(mem, *next)),
+                ana: same,
+            },
+            BlockBody::AssertLength { len, next, .. } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.analyze_block_body(mem, *next)),
+                ana: same,
+            },
+            BlockBody::AssertInBounds {
+                bound, arg, next, ..
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.analyze_block_body(mem, *next)),
+                ana: same,
+            },
+        }
+    }
+}
+
+/*
+ * Alias analysis and store-to-load forwarding
+ * */
+
+/// What an address points into
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Base {
+    /// The array that `allocateArray` returned to the variable, which no
+    /// other allocation returns
+    Allocated(VarName),
+    /// Any array, e.g. a parameter, a loaded value or the result of a call
+    Unknown(VarName),
+}
+
+/// A word of memory: its base, and its offset as a multiple of a variable
+/// (halved, if the variable is tagged) plus a constant
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub base: Base,
+    pub offset: (Option<(VarName, bool)>, i64),
+}
+
+impl Location {
+    /// Whether the word is the length of its array, which never changes:
+    /// the stores all write at indices in bounds, past the length
+    pub fn is_length(&self) -> bool {
+        self.offset == (None, 0)
+    }
+}
+
+/// Whether two locations are the same word
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Alias {
+    No,
+    May,
+    Must,
+}
+
+/// A conservative alias analysis: arrays from different allocations never
+/// alias, while any others may alias all arrays. The words at the same base
+/// alias when their offsets are the same, and do not when the offsets differ
+/// by a constant.
+pub struct AliasAnalysis {
+    /// The operation that defines each variable
+    defs: HashMap<VarName, Operation<VarName>>,
+}
+
+impl AliasAnalysis {
+    pub fn new(prog: &Program<VarName, Nil>) -> Self {
+        Self {
+            defs: defs(&prog.blocks),
+        }
+    }
+
+    /// `imm`, through copies
+    fn resolve<'a>(&'a self, mut imm: &'a Immediate<VarName>) -> &'a Immediate<VarName> {
+        while let Immediate::Var(var) = imm {
+            match self.defs.get(var) {
+                Some(Operation::Immediate(copied)) => imm = copied,
+                _ => break,
+            }
+        }
+        imm
+    }
+
+    /// The array that the untagged `addr` points into
+    fn base(&self, addr: &Immediate<VarName>) -> Option<Base> {
+        let Immediate::Var(var) = self.resolve(addr) else {
+            return None;
+        };
+        match self.defs.get(var) {
+            Some(Operation::AllocateArray { .. }) => Some(Base::Allocated(var.clone())),
+            Some(Operation::Prim2(Prim2::BitXor, tagged, Immediate::Const(tag)))
+                if *tag == Type::Array.tag() =>
+            {
+                let Immediate::Var(tagged) = self.resolve(tagged) else {
+                    return None;
+                };
+                match self.defs.get(tagged) {
+                    Some(Operation::Prim2(Prim2::BitOr, addr, Immediate::Const(tag)))
+                        if *tag == Type::Array.tag() =>
+                    {
+                        self.base(addr)
+                    }
+                    _ => Some(Base::Unknown(tagged.clone())),
+                }
+            }
+            _ => Some(Base::Unknown(var.clone())),
+        }
+    }
+
+    /// `imm` as a multiple of a variable plus a constant
+    fn offset(&self, imm: &Immediate<VarName>) -> (Option<(VarName, bool)>, i64) {
+        let var = match self.resolve(imm) {
+            Immediate::Const(c) => return (None, *c),
+            Immediate::Var(var) => var,
+        };
+        let plus = |(root, offset): (Option<(VarName, bool)>, i64), c: Option<i64>| {
+            Some((root, offset.checked_add(c?)?))
+        };
+        let constant = |imm| match self.resolve(imm) {
+            Immediate::Const(c) => Some(*c),
+            Immediate::Var(_) => None,
+        };
+        let sum = match self.defs.get(var) {
+            Some(Operation::Prim2(Prim2::Add, lhs, rhs)) => match constant(rhs) {
+                Some(c) => plus(self.offset(lhs), Some(c)),
+                None => plus(self.offset(rhs), constant(lhs)),
+            },
+            Some(Operation::Prim2(Prim2::Sub, lhs, rhs)) => {
+                plus(self.offset(lhs), constant(rhs).and_then(i64::checked_neg))
+            }
+            // the index of a tagged integer
+            Some(Operation::Prim1(Prim1::BitSar(by), tagged)) if *by == Type::Int.mask_length() => {
+                match self.offset(tagged) {
+                    (None, c) if c % 2 == 0 => Some((None, c >> 1)),
+                    (Some((root, false)), c) if c % 2 == 0 => Some((Some((root, true)), c >> 1)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        sum.unwrap_or((Some((var.clone(), false)), 0))
+    }
+
+    /// The word at `offset` from the untagged `addr`
+    pub fn location(
+        &self,
+        addr: &Immediate<VarName>,
+        offset: &Immediate<VarName>,
+    ) -> Option<Location> {
+        Some(Location {
+            base: self.base(addr)?,
+            offset: self.offset(offset),
+        })
+    }
+
+    pub fn alias(&self, a: &Location, b: &Location) -> Alias {
+        let same_base = match (&a.base, &b.base) {
+            (Base::Allocated(a), Base::Allocated(b)) if a != b => return Alias::No,
+            (a, b) => a == b,
+        };
+        match (&a.offset, &b.offset) {
+            ((a_root, a_off), (b_root, b_off)) if a_root == b_root => {
+                if a_off != b_off {
+                    Alias::No
+                } else if same_base {
+                    Alias::Must
+                } else {
+                    Alias::May
+                }
+            }
+            _ => Alias::May,
+        }
+    }
+}
+
+/// Forwards the values stored to a word, or loaded from it, to the later
+/// loads of the word. A load follows the versions of the memory back from
+/// the one it reads, past the stores that cannot write the word, to a store
+/// or a load of the word. It stops at the entry of a block, at a call, unless
+/// the word is the length of an array, and at a store that may write the word.
+pub struct LoadForwarder {
+    aliases: AliasAnalysis,
+    memory: MemorySsa,
+    /// The words loaded in each version of the memory, with the variables
+    /// they were loaded to
+    loaded: HashMap<Memory, Vec<(Location, VarName)>>,
+    forwarded: usize,
+    /// The function of the block being forwarded in
+    function: String,
+    remarks: Vec<Remark>,
+}
+
+impl LoadForwarder {
+    const PASS: Optimization = Optimization::MemoryForwarding;
+
+    pub fn new(prog: &Program<VarName, Nil>) -> Self {
+        Self {
+            aliases: AliasAnalysis::new(prog),
+            memory: MemorySsa::default(),
+            loaded: HashMap::new(),
+            forwarded: 0,
+            function: String::new(),
+            remarks: Vec::new(),
+        }
+    }
+
+    /// The number of loads replaced by known values
+    pub fn forwarded(&self) -> usize {
+        self.forwarded
+    }
+
+    /// The loads that were replaced, and the ones that were not with what
+    /// stopped the lookup
+    pub fn remarks(&self) -> &[Remark] {
+        &self.remarks
+    }
+
+    pub fn run(&mut self, prog: Program<VarName, Nil>) -> Program<VarName, Nil> {
+        let mut analyzer = MemoryAnalyzer::new(&prog);
+        let prog = analyzer.analyze(prog);
+        self.memory = analyzer.memory().clone();
+        let functions = remarks::functions(&prog);
+        Program {
+            externs: prog.externs,
+            funs: prog.funs,
+            blocks: prog
+                .blocks
+                .into_iter()
+                .map(|block| {
+                    self.function = functions[&block.label].clone();
+                    self.run_block(block)
+                })
+                .collect(),
+        }
+    }
+
+    fn run_block(&mut self, block: BasicBlock<VarName, MemoryAccess>) -> BasicBlock<VarName, Nil> {
+        BasicBlock {
+            label: block.label,
+            params: block.params,
+            body: self.run_block_body(block.body),
+            ana: Nil,
+        }
+    }
+
+    /// The value of the word at `loc` in the version `mem` of the memory, or
+    /// what stops the lookup. A version is only read in the row of operations
+    /// that defines it, so that the loads in it come before.
+    fn lookup(&self, loc: &Location, mut mem: Memory) -> Result<Immediate<VarName>, String> {
+        loop {
+            let mut loaded = self.loaded.get(&mem).into_iter().flatten();
+            if let Some((_, var)) =
+                loaded.find(|(word, _)| self.aliases.alias(word, loc) == Alias::Must)
+            {
+                return Ok(Immediate::Var(var.clone()));
+            }
+            match self.memory.def(mem) {
+                MemoryDef::Phi(block, _) => {
+                    return Err(format!("the lookup stops at the entry of {}", block))
+                }
+                MemoryDef::Store {
+                    addr,
+                    offset,
+                    val,
+                    prev,
+                } => {
+                    // the stores never write the lengths
+                    let alias = match self.aliases.location(addr, offset) {
+                        _ if loc.is_length() => Alias::No,
+                        Some(word) => self.aliases.alias(&word, loc),
+                        None => Alias::May,
+                    };
+                    match alias {
+                        Alias::Must => return Ok(val.clone()),
+                        Alias::May => {
+                            return Err(format!(
+                                "the store of {} to {}[{}] may write it",
+                                val, addr, offset
+                            ))
+                        }
+                        Alias::No => mem = *prev,
+                    }
+                }
+                MemoryDef::Call(_, prev) if loc.is_length() => mem = *prev,
+                MemoryDef::Call(fun, _) => return Err(format!("the call to {} may write it", fun)),
+                MemoryDef::Allocate { array, len, prev } => {
+                    let word = Location {
+                        base: Base::Allocated(array.clone()),
+                        offset: (None, 0),
+                    };
+                    if self.aliases.alias(&word, loc) == Alias::Must {
+                        return Ok(len.clone());
+                    }
+                    mem = *prev;
+                }
+            }
+        }
+    }
+
+    fn run_block_body(
+        &mut self,
+        body: BlockBody<VarName, MemoryAccess>,
+    ) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::Terminator(terminator, _) => BlockBody::Terminator(terminator, Nil),
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => {
+                let op = match op {
+                    Operation::Load { addr, offset } => {
+                        let loc = self.aliases.location(&addr, &offset);
+                        match loc.as_ref().map(|loc| self.lookup(loc, ana.uses)) {
+                            Some(Ok(val)) => {
+                                self.forwarded += 1;
+                                let message = format!("forwarded {} to the load of {}", val, dest);
+                                self.remarks.push(Remark::passed(
+                                    Self::PASS,
+                                    &self.function,
+                                    message,
+                                ));
+                                Operation::Immediate(val)
+                            }
+                            lookup => {
+                                if let Some(Err(stop)) = lookup {
+                                    let message = format!("kept the load of {}: {}", dest, stop);
+                                    self.remarks.push(Remark::missed(
+                                        Self::PASS,
+                                        &self.function,
+                                        message,
+                                    ));
+                                }
+                                if let Some(loc) = loc {
+                                    let loaded = self.loaded.entry(ana.uses).or_default();
+                                    loaded.push((loc, dest.clone()));
+                                }
+                                Operation::Load { addr, offset }
+                            }
+                        }
+                    }
+                    op => op,
+                };
+                BlockBody::Operation {
+                    dest,
+                    op,
+                    next: Box::new(self.run_block_body(*next)),
+                    ana: Nil,
+                }
+            }
+            BlockBody::SubBlocks { blocks, next, .. } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.run_block(block))
+                    .collect(),
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ..
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+            BlockBody::AssertType { ty, arg, next, .. } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+            BlockBody::AssertLength { len, next, .. } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+            BlockBody::AssertInBounds {
+                bound, arg, next, ..
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.run_block_body(*next)),
+                ana: Nil,
+            },
+        }
+    }
+}
+
+/*
+ * Assertion hoisting
+ * */
+
+/// Hoists assertions upwards within straight-line code, so that they are
+/// checked as early as possible.
+///
+/// A failing program reports the first error it runs into, in the original
+/// program order, and optimizations must not change which error that is.
+/// Hence, an assertion is only moved across instructions that
+/// - cannot fail, so that no other error could have been reported first,
+/// - have no side effects, e.g. printing, that would be skipped, and
+/// - obviously terminate, so that a diverging program does not start to fail.
+///
+/// Such instructions are constants, non-overflowing primitives, loads,
+/// allocations and calls to functions in `EffectAnalysis::reorderable`.
+/// In particular, assertions are never reordered among themselves or across
+/// overflowing arithmetic. Note that dead code elimination may still remove
+/// errors of unused arithmetic, unless errors are strict.
+pub struct AssertionHoister {
+    reorderable: HashSet<FunName>,
+}
+
+impl AssertionHoister {
+    pub fn new<T>(prog: &Program<VarName, T>) -> Self {
+        Self {
+            reorderable: crate::backend::EffectAnalysis::reorderable(prog),
+        }
+    }
+
+    pub fn run(&mut self, mut prog: Program<VarName, Nil>) -> Program<VarName, Nil> {
+        prog.blocks = prog
+            .blocks
+            .into_iter()
+            .map(|block| self.run_block(block))
+            .collect();
+        prog
+    }
+
+    fn run_block(&mut self, mut block: BasicBlock<VarName, Nil>) -> BasicBlock<VarName, Nil> {
+        block.body = self.run_block_body(block.body);
+        block
+    }
+
+    fn run_block_body(&mut self, body: BlockBody<VarName, Nil>) -> BlockBody<VarName, Nil> {
+        match body {
+            BlockBody::Terminator(..) => body,
+            BlockBody::Operation {
+                dest,
+                op,
+                next,
+                ana,
+            } => {
+                let next = self.run_block_body(*next);
+                self.link(dest, op, ana, next)
+            }
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks
+                    .into_iter()
+                    .map(|block| self.run_block(block))
+                    .collect(),
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+            BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next,
+                ana,
+            } => BlockBody::Store {
+                addr,
+                offset,
+                val,
+                next: Box::new(self.run_block_body(*next)),
+                ana,
+            },
+        }
+    }
+
+    /// Builds the operation `dest = op` followed by `next`, moving the
+    /// assertions at the start of `next` in front of the operation where allowed.
+    fn link(
+        &self,
+        dest: VarName,
+        op: Operation<VarName>,
+        ana: Nil,
+        next: BlockBody<VarName, Nil>,
+    ) -> BlockBody<VarName, Nil> {
+        let args: Vec<&Immediate<VarName>> = match &next {
+            BlockBody::AssertType { arg, .. } => vec![arg],
+            BlockBody::AssertLength { len, .. } => vec![len],
+            BlockBody::AssertInBounds { bound, arg, .. } => vec![bound, arg],
+            _ => vec![],
+        };
+        let hoist = !args.is_empty()
+            && !args.contains(&&Immediate::Var(dest.clone()))
+            && self.reorderable_op(&op);
+        if !hoist {
+            return BlockBody::Operation {
+                dest,
+                op,
+                next: Box::new(next),
+                ana,
+            };
+        }
+        match next {
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty,
+                arg,
+                next: Box::new(self.link(dest, op, ana, *next)),
+                ana,
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len,
+                next: Box::new(self.link(dest, op, ana, *next)),
+                ana,
+            },
+            BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next,
+                ana,
+            } => BlockBody::AssertInBounds {
+                bound,
+                arg,
+                next: Box::new(self.link(dest, op, ana, *next)),
+                ana,
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Whether an assertion may be moved across `op`.
+    fn reorderable_op(&self, op: &Operation<VarName>) -> bool {
+        match op {
+            Operation::Immediate(_)
+            | Operation::Prim1(..)
+            | Operation::Load { .. }
+            | Operation::AllocateArray { .. }
+            | Operation::Str(_) => true,
+            // only the prims that cannot fail, so that a new one stays in place until it is
+            // known not to
+            Operation::Prim2(prim, ..) => matches!(
+                prim,
+                Prim2::WrappingAdd
+                    | Prim2::WrappingSub
+                    | Prim2::WrappingMul
+                    | Prim2::SaturatingAdd
+                    | Prim2::SaturatingSub
+                    | Prim2::SaturatingMul
+                    | Prim2::BitAnd
+                    | Prim2::BitOr
+                    | Prim2::BitXor
+                    | Prim2::Lt
+                    | Prim2::Le
+                    | Prim2::Gt
+                    | Prim2::Ge
+                    | Prim2::Eq
+                    | Prim2::Neq
+            ),
+            Operation::Call { fun, .. } => self.reorderable.contains(fun),
+        }
+    }
+}
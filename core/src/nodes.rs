@@ -0,0 +1,160 @@
+//! The ids of the nodes of the AST, and the side tables keyed by them.
+//!
+//! What a pass finds out about the nodes, such as the spans of the nodes or
+//! their inferred types, goes to a [`SideTable`] rather than to a field of
+//! each node, so that a new analysis leaves `ast.rs` as it is. The parser
+//! [`number`]s the nodes, whose ids the passes then carry along in their
+//! spans: the resolver keeps the ids of the nodes it binds, and the nodes
+//! that a desugaring derives from another take its span and so its id.
+
+use crate::ast::*;
+
+/// Information about some of the nodes of a program, by their ids
+#[derive(Clone, Debug)]
+pub struct SideTable<T> {
+    entries: Vec<Option<T>>,
+}
+
+impl<T> Default for SideTable<T> {
+    fn default() -> Self {
+        SideTable { entries: Vec::new() }
+    }
+}
+
+impl<T> SideTable<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `value` for `id`, returning the value recorded before; the
+    /// nodes of `NodeId::NONE` record nothing
+    pub fn insert(&mut self, id: NodeId, value: T) -> Option<T> {
+        if id == NodeId::NONE {
+            return None;
+        }
+        let i = id.0 as usize;
+        if i >= self.entries.len() {
+            self.entries.resize_with(i + 1, || None);
+        }
+        self.entries[i].replace(value)
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.entries.get(id.0 as usize).and_then(Option::as_ref)
+    }
+
+    /// The recorded nodes, by increasing id
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        let entries = self.entries.iter().enumerate();
+        entries.filter_map(|(i, value)| Some((NodeId(i as u32), value.as_ref()?)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> FromIterator<(NodeId, T)> for SideTable<T> {
+    fn from_iter<I: IntoIterator<Item = (NodeId, T)>>(iter: I) -> Self {
+        let mut table = SideTable::new();
+        for (id, value) in iter {
+            table.insert(id, value);
+        }
+        table
+    }
+}
+
+/// The innermost of the nodes whose span contains the offset `ix`
+pub fn at(spans: &SideTable<SrcLoc>, ix: usize) -> Option<NodeId> {
+    let containing = spans.iter().filter(|(_, loc)| loc.contains(ix));
+    containing.min_by_key(|(id, loc)| (loc.end_ix - loc.start_ix, *id)).map(|(id, _)| id)
+}
+
+/// Gives every node of `prog` an id, numbering them from 1 in pre-order,
+/// with the names that a node binds right after it, and returns their spans.
+/// Numbering a program again gives its nodes the same ids.
+pub fn number<Var, Fun>(prog: &mut Prog<Var, Fun>) -> SideTable<SrcLoc> {
+    let mut numbering = Numbering::default();
+    numbering.give(&mut prog.loc);
+    numbering.give(&mut prog.param.loc);
+    for ext in &mut prog.externs {
+        numbering.give(&mut ext.loc);
+        ext.params.iter_mut().for_each(|param| numbering.give(&mut param.loc));
+    }
+    for decl in &mut prog.enums {
+        numbering.give(&mut decl.loc);
+        for variant in &mut decl.variants {
+            numbering.give(&mut variant.loc);
+            variant.fields.iter_mut().for_each(|field| numbering.give(&mut field.loc));
+        }
+    }
+    numbering.expr(&mut prog.body);
+    numbering.spans
+}
+
+/// Gives every node of `e` an id, as [`number`] does those of a program
+pub fn number_expr<Var, Fun>(e: &mut Expr<Var, Fun>) -> SideTable<SrcLoc> {
+    let mut numbering = Numbering::default();
+    numbering.expr(e);
+    numbering.spans
+}
+
+#[derive(Default)]
+struct Numbering {
+    spans: SideTable<SrcLoc>,
+    next: u32,
+}
+
+impl Numbering {
+    fn give(&mut self, loc: &mut SrcLoc) {
+        self.next += 1;
+        loc.node = NodeId(self.next);
+        self.spans.insert(loc.node, *loc);
+    }
+
+    fn pattern<Var, Fun>(&mut self, pattern: &mut Pattern<Var, Fun>) {
+        let mut stack = vec![pattern];
+        while let Some(pattern) = stack.pop() {
+            match pattern {
+                Pattern::Wildcard(loc) | Pattern::Var(_, loc) => self.give(loc),
+                Pattern::Ctor { args, loc, .. } => {
+                    self.give(loc);
+                    stack.extend(args.iter_mut().rev());
+                }
+            }
+        }
+    }
+
+    fn expr<Var, Fun>(&mut self, e: &mut Expr<Var, Fun>) {
+        let mut stack = vec![e];
+        while let Some(e) = stack.pop() {
+            self.give(e.loc_mut());
+            match e {
+                Expr::Let { bindings, .. } => {
+                    for Binding { var, elems, .. } in bindings.iter_mut() {
+                        self.give(&mut var.loc);
+                        elems.iter_mut().flatten().for_each(|elem| self.give(&mut elem.loc));
+                    }
+                }
+                Expr::Switch { cases, .. } => {
+                    cases.iter_mut().for_each(|case| self.give(&mut case.label.loc))
+                }
+                Expr::Match { arms, .. } => {
+                    arms.iter_mut().for_each(|arm| self.pattern(&mut arm.pattern))
+                }
+                Expr::FunDefs { decls, .. } => {
+                    for FunDecl { params, loc, .. } in decls.iter_mut() {
+                        self.give(loc);
+                        params.iter_mut().for_each(|param| self.give(&mut param.loc));
+                    }
+                }
+                _ => {}
+            }
+            stack.extend(e.children_mut().into_iter().rev());
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! The enums of a program: the tags and arities of their variants, and
+//! whether the arms of a match cover every object of an enum.
+//!
+//! The exhaustiveness check is the usefulness algorithm of Maranget's
+//! "Warnings for pattern matching": a match misses the values of any
+//! pattern that is useful after its arms, which the recursion builds as
+//! the witness it reports.
+
+use crate::ast::*;
+use crate::identifiers::FunName;
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::once;
+
+/// A variant of an enum
+#[derive(Clone, Debug)]
+pub struct Sig {
+    /// The first word of the objects of the variant, unique in the program
+    pub tag: i64,
+    pub arity: usize,
+    /// The constructors of all the variants of the enum, in order
+    pub siblings: Vec<FunName>,
+    /// The name of the enum
+    pub of: String,
+}
+
+/// The variants of the enums of a program, by their constructors
+#[derive(Clone, Debug, Default)]
+pub struct Enums(HashMap<FunName, Sig>);
+
+/// Constructors of two different enums, by the names of the enums, in the
+/// same position of the patterns of a match
+pub struct Mixed(pub String, pub String);
+
+impl Enums {
+    /// Tags the variants by their positions among all the variants of the
+    /// program
+    pub fn new(enums: &[BoundEnumDecl]) -> Self {
+        let mut sigs = HashMap::new();
+        for decl in enums {
+            let siblings: Vec<_> = decl.variants.iter().map(|v| v.name.clone()).collect();
+            for variant in &decl.variants {
+                let sig = Sig {
+                    tag: sigs.len() as i64,
+                    arity: variant.fields.len(),
+                    siblings: siblings.clone(),
+                    of: decl.name.clone(),
+                };
+                sigs.insert(variant.name.clone(), sig);
+            }
+        }
+        Enums(sigs)
+    }
+
+    pub fn get(&self, ctor: &FunName) -> &Sig {
+        &self.0[ctor]
+    }
+
+    /// A pattern of the values that none of `patterns` match, if any
+    pub fn missing(&self, patterns: &[&BoundPattern]) -> Result<Option<String>, Mixed> {
+        let rows = patterns.iter().map(|p| vec![Pat::of(p)]).collect();
+        Ok(self.useful(rows, 1)?.map(|mut witness| witness.remove(0).to_string()))
+    }
+
+    /// The values of `width` columns that no row matches, as patterns
+    fn useful<'a>(
+        &'a self, rows: Vec<Vec<Pat<'a>>>, width: usize,
+    ) -> Result<Option<Vec<Pat<'a>>>, Mixed> {
+        if width == 0 {
+            return Ok(rows.is_empty().then(Vec::new));
+        }
+        let mut heads: Vec<&FunName> = vec![];
+        for row in &rows {
+            if let Pat::Ctor(ctor, _) = &row[0] {
+                if !heads.contains(ctor) {
+                    heads.push(ctor);
+                }
+            }
+        }
+        let Some(sig) = heads.first().map(|ctor| self.get(ctor)) else {
+            let rows = rows.into_iter().map(|row| row[1..].to_vec()).collect();
+            return Ok(self
+                .useful(rows, width - 1)?
+                .map(|rest| once(Pat::Any).chain(rest).collect()));
+        };
+        if let Some(other) = heads.iter().find(|ctor| self.get(ctor).siblings != sig.siblings) {
+            return Err(Mixed(sig.of.clone(), self.get(other).of.clone()));
+        }
+        match sig.siblings.iter().find(|ctor| !heads.contains(ctor)) {
+            // the heads are complete, so the values are missing below one of them
+            None => {
+                for ctor in &sig.siblings {
+                    let arity = self.get(ctor).arity;
+                    let rows = rows.iter().filter_map(|row| specialize(row, ctor, arity)).collect();
+                    if let Some(mut witness) = self.useful(rows, arity + width - 1)? {
+                        let rest = witness.split_off(arity);
+                        return Ok(Some(once(Pat::Ctor(ctor, witness)).chain(rest).collect()));
+                    }
+                }
+                Ok(None)
+            }
+            // a missing head is missing whatever follows it that the
+            // wildcards miss
+            Some(ctor) => {
+                let rows = (rows.into_iter())
+                    .filter(|row| matches!(row[0], Pat::Any))
+                    .map(|row| row[1..].to_vec())
+                    .collect();
+                let head = Pat::Ctor(ctor, vec![Pat::Any; self.get(ctor).arity]);
+                Ok(self.useful(rows, width - 1)?.map(|rest| once(head).chain(rest).collect()))
+            }
+        }
+    }
+}
+
+/// A pattern, as far as the values it matches go
+#[derive(Clone)]
+enum Pat<'a> {
+    Any,
+    Ctor(&'a FunName, Vec<Pat<'a>>),
+}
+
+impl<'a> Pat<'a> {
+    fn of(pattern: &'a BoundPattern) -> Self {
+        match pattern {
+            Pattern::Wildcard(_) | Pattern::Var(..) => Pat::Any,
+            Pattern::Ctor { ctor, args, .. } => Pat::Ctor(ctor, args.iter().map(Pat::of).collect()),
+        }
+    }
+}
+
+/// The row without its first column, which is `ctor` with `arity` fields,
+/// but with the patterns of the fields in front, if it matches `ctor`
+fn specialize<'a>(row: &[Pat<'a>], ctor: &FunName, arity: usize) -> Option<Vec<Pat<'a>>> {
+    let fields = match &row[0] {
+        Pat::Any => vec![Pat::Any; arity],
+        Pat::Ctor(head, args) if *head == ctor => args.clone(),
+        Pat::Ctor(..) => return None,
+    };
+    Some(fields.into_iter().chain(row[1..].iter().cloned()).collect())
+}
+
+impl fmt::Display for Pat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pat::Any => write!(f, "_"),
+            Pat::Ctor(ctor, args) if args.is_empty() => write!(f, "{}", ctor.hint()),
+            Pat::Ctor(ctor, args) => {
+                write!(f, "{}(", ctor.hint())?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
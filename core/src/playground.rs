@@ -0,0 +1,98 @@
+//! The compiler core for an online playground.
+//!
+//! The core crate neither assembles nor runs executables, so it builds for
+//! `wasm32-unknown-unknown`:
+//!
+//! ```text
+//! cargo build -p snake-core --release --target wasm32-unknown-unknown \
+//!     --features wasm
+//! ```
+//!
+//! A playground compiles programs to assembly and runs them in the
+//! interpreters instead, with a bound on the steps so that a program that
+//! does not terminate cannot hang the page. With the `wasm` feature the
+//! functions here are exported to JavaScript by `wasm-bindgen`.
+
+use crate::compile;
+use crate::conf::{CompilerConf, Optimization, OptimizationCollection, Verbosity};
+use crate::error::Error;
+use crate::frontend::Resolver;
+use crate::interp::{self, Services, Status};
+use crate::txt::FileInfo;
+use std::collections::HashSet;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// The interpreter that runs a program
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Engine {
+    /// The AST interpreter on the resolved program
+    Ast,
+    /// The SSA interpreter on the optimized program
+    Ssa,
+}
+
+/// The optimizations of `-O`, none if `s` is empty
+fn optimizations(s: &str) -> Result<HashSet<Optimization>, String> {
+    if s.is_empty() {
+        return Ok(HashSet::new());
+    }
+    Ok(s.parse::<OptimizationCollection>()?.into_iter().collect())
+}
+
+/// The assembly of `src` as `snake` emits it; `optimizations` is the value of
+/// `-O` and `bigint` is `--bigint`.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = compileToAsm))]
+pub fn compile_to_asm(src: &str, optimizations: &str, bigint: bool) -> Result<String, String> {
+    let optimizations = self::optimizations(optimizations)?;
+    let (resolver, ast) = compile::frontend_with(src, Resolver::new().bigint(bigint))
+        .map_err(|e| e.render(&FileInfo::new(src)))?;
+    let (lowerer, ssa) = compile::middle_end_with(resolver, ast, &optimizations)?;
+    let conf = CompilerConf::new(optimizations, Verbosity::Minimalistic);
+    Ok(compile::backend_with(lowerer, ssa, &conf))
+}
+
+/// The value of `src` on the whitespace-separated `args`, run by `engine` for
+/// at most `steps` steps; `clock` and `random` are seeded with `seed`. The
+/// SSA interpreter runs the program optimized by `optimizations` as in
+/// `compile_to_asm`.
+#[cfg_attr(feature = "wasm", wasm_bindgen(js_name = runInInterpreter))]
+pub fn run_in_interpreter(
+    src: &str, args: &str, engine: Engine, optimizations: &str, bigint: bool, seed: u32,
+    steps: usize,
+) -> Result<String, String> {
+    let optimizations = self::optimizations(optimizations)?;
+    let file_info = FileInfo::new(src);
+    let prog = compile::parse(src).map_err(Error::from)?;
+    if !prog.externs.is_empty() {
+        Err("the interpreters do not support extern functions".to_string())?
+    }
+    let mut resolver = Resolver::new().bigint(bigint);
+    let ast = resolver.resolve_prog(prog).map_err(|e| Error::from(e).render(&file_info))?;
+    let args = args.split_whitespace();
+    let services = Services::new(Some(seed as u64)).bigint(bigint);
+    let status = match engine {
+        Engine::Ast => interp::ast::Machine::start(&ast, args, services)
+            .and_then(|mut machine| machine.resume(steps))
+            .map_err(|e| format!("Error interpreting program: {}", e))?,
+        Engine::Ssa => {
+            let (_, ssa) = compile::middle_end_with(resolver, ast, &optimizations)?;
+            let ssa = if optimizations.contains(&Optimization::DeadCodeElimination) {
+                let conf = CompilerConf::new(optimizations, Verbosity::Minimalistic);
+                compile::dead_code_elimination(ssa, &conf)
+            } else {
+                ssa
+            };
+            let mut interp = interp::ssa::Interp::new().with_services(services);
+            interp
+                .start(&ssa, args)
+                .and_then(|()| interp.resume(steps))
+                .map_err(|e| format!("Error interpreting program: {}", e))?
+        }
+    };
+    match status {
+        Status::Done(value) => Ok(value.to_string()),
+        Status::Suspended => Err(format!("the program did not finish within {} steps", steps)),
+    }
+}
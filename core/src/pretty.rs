@@ -0,0 +1,1477 @@
+use std::fmt;
+
+/* ------------------------------- Combinators ------------------------------ */
+
+pub struct Indent<'a, T: ?Sized>(pub usize, pub &'a T);
+impl<'a, T: ?Sized> Indent<'a, T> {
+    fn inner(&self) -> (usize, &'a T) {
+        (self.0, self.1)
+    }
+}
+
+pub struct Separated<'a, T, Iter>(pub &'a Iter, pub &'static str)
+where
+    Iter: Iterator<Item = T> + Clone;
+
+impl<T: fmt::Debug, Iter: Iterator<Item = T> + Clone> fmt::Debug for Separated<'_, T, Iter> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0.clone().enumerate())
+            .map(|(i, t)| {
+                if i > 0 {
+                    fmt::Display::fmt(self.1, f)?;
+                }
+                t.fmt(f)
+            })
+            .collect()
+    }
+}
+
+impl<T: fmt::Display, Iter: Iterator<Item = T> + Clone> fmt::Display for Separated<'_, T, Iter> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0.clone().enumerate())
+            .map(|(i, t)| {
+                if i > 0 {
+                    fmt::Display::fmt(self.1, f)?;
+                }
+                t.fmt(f)
+            })
+            .collect()
+    }
+}
+
+pub struct Surfix<'a, T, Iter>(pub &'a Iter, pub &'static str)
+where
+    Iter: Iterator<Item = T> + Clone;
+
+impl<T: fmt::Debug, Iter: Iterator<Item = T> + Clone> fmt::Debug for Surfix<'_, T, Iter> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .clone()
+            .map(|t| {
+                t.fmt(f)?;
+                fmt::Display::fmt(self.1, f)
+            })
+            .collect()
+    }
+}
+
+impl<T: fmt::Display, Iter: Iterator<Item = T> + Clone> fmt::Display for Surfix<'_, T, Iter> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .clone()
+            .map(|t| {
+                t.fmt(f)?;
+                self.1.fmt(f)
+            })
+            .collect()
+    }
+}
+
+pub struct Comma<'a, T, Iter>(pub &'a Iter)
+where
+    Iter: Iterator<Item = T> + Clone;
+
+impl<T: fmt::Debug, Iter: Iterator<Item = T> + Clone> fmt::Debug for Comma<'_, T, Iter> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Separated(self.0, ", ").fmt(f)
+    }
+}
+
+impl<T: fmt::Display, Iter: Iterator<Item = T> + Clone> fmt::Display for Comma<'_, T, Iter> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Separated(self.0, ", ").fmt(f)
+    }
+}
+
+pub struct LineBreaks<'a, T, Iter>(pub &'a Iter)
+where
+    Iter: Iterator<Item = T> + Clone;
+
+impl<T: fmt::Debug, Iter: Iterator<Item = T> + Clone> fmt::Debug for LineBreaks<'_, T, Iter> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Surfix(self.0, "\n").fmt(f)
+    }
+}
+
+impl<T: fmt::Display, Iter: Iterator<Item = T> + Clone> fmt::Display for LineBreaks<'_, T, Iter> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Surfix(self.0, "\n").fmt(f)
+    }
+}
+
+/* ------------------------------- Annotations ------------------------------ */
+
+/// Displays an SSA program analyzed by `LivenessAnalyzer`, with the live-in
+/// and live-out sets of each instruction as end-of-line comments.
+pub struct LivenessAnnotated<'a>(
+    pub &'a crate::ssa::Program<crate::identifiers::VarName, crate::ana::LiveSet>,
+);
+
+/// Displays an SSA program analyzed by `MemoryAnalyzer`, with the versions of
+/// the memory that the loads and branches read and that the other accesses
+/// define as end-of-line comments.
+pub struct MemoryAnnotated<'a>(
+    pub &'a crate::ssa::Program<crate::identifiers::VarName, crate::ana::MemoryAccess>,
+    pub &'a crate::ana::MemorySsa,
+);
+
+/// Displays the SSA program, interference graph, elimination order or
+/// coloring with the variables renamed as in `ReadableNames`.
+pub struct Readable<'a, T>(pub &'a crate::identifiers::ReadableNames, pub &'a T);
+
+/* ----------------------------- Implementations ---------------------------- */
+
+/// Pretty ugly printing of the (Resolved) AST
+mod impl_ast {
+    use super::*;
+    use crate::ast::*;
+
+    impl<Var: fmt::Display, Fun: fmt::Display> fmt::Display for Prog<Var, Fun> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Prog { externs, enums, name, param: Spanned { node: param, .. }, body, .. } = self;
+            let (externs, enums) = (LineBreaks(&externs.iter()), LineBreaks(&enums.iter()));
+            write!(f, "{}{}def {}({}): {}", externs, enums, name, param, body)
+        }
+    }
+
+    impl<Fun: fmt::Display> fmt::Display for EnumDecl<Fun> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "enum {}: {}", self.name, Separated(&self.variants.iter(), " | "))
+        }
+    }
+
+    impl<Fun: fmt::Display> fmt::Display for Variant<Fun> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.fields.is_empty() {
+                true => write!(f, "{}", self.name),
+                false => {
+                    write!(f, "{}({})", self.name, Comma(&self.fields.iter().map(|x| &x.node)))
+                }
+            }
+        }
+    }
+
+    impl<Var: fmt::Display, Fun: fmt::Display> fmt::Display for Pattern<Var, Fun> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Pattern::Wildcard(_) => write!(f, "_"),
+                Pattern::Var(var, _) => write!(f, "{}", var),
+                Pattern::Ctor { ctor, args, .. } => write!(f, "{}({})", ctor, Comma(&args.iter())),
+            }
+        }
+    }
+
+    impl<Var: fmt::Display, Fun: fmt::Display> fmt::Display for ExtDecl<Var, Fun> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let params = self.params.iter().enumerate();
+            let params = Comma(&params.map(|(i, p)| self.signature.param(i, &p.node)));
+            write!(f, "extern {}({}){}{} and", self.name, params, self.signature, self.contract)
+        }
+    }
+
+    /// A piece of an expression that its `Display` has yet to write
+    enum Piece<'a, Var, Fun> {
+        Text(&'static str),
+        Owned(String),
+        Expr(&'a Expr<Var, Fun>),
+    }
+
+    impl<Var: fmt::Display, Fun: fmt::Display> fmt::Display for Expr<Var, Fun> {
+        /// Writes the pieces of each expression from a work stack, so that
+        /// deeply nested expressions do not recurse as deep
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut stack = vec![Piece::Expr(self)];
+            while let Some(piece) = stack.pop() {
+                match piece {
+                    Piece::Text(s) => f.write_str(s)?,
+                    Piece::Owned(s) => f.write_str(&s)?,
+                    Piece::Expr(e) => stack.extend(pieces(e).into_iter().rev()),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// The pieces that `e` writes, in order
+    fn pieces<Var: fmt::Display, Fun: fmt::Display>(
+        e: &Expr<Var, Fun>,
+    ) -> Vec<Piece<'_, Var, Fun>> {
+        use Piece::{Expr as E, Owned, Text};
+        fn commas<Var, Fun>(args: &[Expr<Var, Fun>]) -> impl Iterator<Item = Piece<'_, Var, Fun>> {
+            let args = args.iter().enumerate();
+            args.flat_map(|(i, arg)| [Piece::Text(if i > 0 { ", " } else { "" }), Piece::Expr(arg)])
+        }
+        match e {
+            Expr::Num(n, _) => vec![Owned(n.to_string())],
+            Expr::BigNum(n, _) => vec![Owned(n.to_string())],
+            Expr::Bool(b, _) => vec![Owned(b.to_string())],
+            Expr::Var(v, _) => vec![Owned(v.to_string())],
+            Expr::Prim { prim, args, loc: _ } => match prim {
+                Prim::Add1
+                | Prim::Sub1
+                | Prim::IsType(_)
+                | Prim::Check(_)
+                | Prim::IsNone
+                | Prim::NewArray
+                | Prim::Length
+                | Prim::GetEnv
+                | Prim::Random
+                | Prim::Yield
+                | Prim::Next
+                | Prim::WrappingAdd
+                | Prim::WrappingSub
+                | Prim::WrappingMul
+                | Prim::SaturatingAdd
+                | Prim::SaturatingSub
+                | Prim::SaturatingMul
+                | Prim::ToStr
+                | Prim::Concat
+                | Prim::Clock => {
+                    let open = [Owned(format!("{}(", prim))].into_iter();
+                    open.chain(commas(args)).chain([Text(")")]).collect()
+                }
+                Prim::None | Prim::Str(_) => vec![Owned(prim.to_string())],
+                Prim::Add
+                | Prim::Sub
+                | Prim::Mul
+                | Prim::And
+                | Prim::Or
+                | Prim::Lt
+                | Prim::Le
+                | Prim::Gt
+                | Prim::Ge
+                | Prim::Eq
+                | Prim::Neq => {
+                    let op = Owned(format!(" {} ", prim));
+                    vec![Text("("), E(&args[0]), op, E(&args[1]), Text(")")]
+                }
+                Prim::Not => vec![Text("!("), E(&args[0]), Text(")")],
+                Prim::MakeArray => {
+                    [Text("[")].into_iter().chain(commas(args)).chain([Text("]")]).collect()
+                }
+                Prim::ArrayGet => vec![E(&args[0]), Text("["), E(&args[1]), Text("]")],
+                Prim::ArraySet => {
+                    vec![E(&args[0]), Text("["), E(&args[1]), Text("] := "), E(&args[2])]
+                }
+            },
+            Expr::Let { bindings, body, loc: _ } => {
+                let mut pieces = vec![Text("let ")];
+                for (i, binding) in bindings.iter().enumerate() {
+                    let comma = if i > 0 { ", " } else { "" };
+                    pieces.extend([Text(comma), Owned(binding.head()), E(&binding.expr)]);
+                }
+                pieces.extend([Text(" in "), E(body)]);
+                pieces
+            }
+            Expr::If { cond, thn, els, loc: _ } => {
+                vec![Text("if "), E(cond), Text(": "), E(thn), Text(" else: "), E(els)]
+            }
+            Expr::Switch { scrutinee, cases, default, loc: _ } => {
+                let mut pieces = vec![Text("switch "), E(scrutinee), Text(":")];
+                for Case { label: Spanned { node: n, .. }, body } in cases {
+                    pieces.extend([Owned(format!(" | {}: ", n)), E(body)]);
+                }
+                pieces.extend([Text(" | _: "), E(default)]);
+                pieces
+            }
+            Expr::Match { scrutinee, arms, loc: _ } => {
+                let mut pieces = vec![Text("match "), E(scrutinee), Text(":")];
+                for Arm { pattern, body } in arms {
+                    pieces.extend([Owned(format!(" | {}: ", pattern)), E(body)]);
+                }
+                pieces.push(Text(" end"));
+                pieces
+            }
+            Expr::FunDefs { decls, body, loc: _ } => {
+                let mut pieces = vec![];
+                for (i, decl) in decls.iter().enumerate() {
+                    let and = if i > 0 { " and " } else { "" };
+                    pieces.extend([Text(and), Owned(decl.head()), E(&decl.body)]);
+                }
+                pieces.extend([Text(" in "), E(body)]);
+                pieces
+            }
+            Expr::Call { fun, args, loc: _ } => {
+                let open = [Owned(format!("{}(", fun))].into_iter();
+                open.chain(commas(args)).chain([Text(")")]).collect()
+            }
+        }
+    }
+
+    impl<Var: fmt::Display, Fun: fmt::Display> Binding<Var, Fun> {
+        /// What the binding writes before its expression
+        fn head(&self) -> String {
+            match &self.elems {
+                Some(elems) => format!("[{}] = ", Comma(&elems.iter().map(|x| &x.node))),
+                None => match self.ty {
+                    Some(ty) => format!("{}: {} = ", self.var.node, ty),
+                    None => format!("{} = ", self.var.node),
+                },
+            }
+        }
+    }
+
+    impl<Var: fmt::Display, Fun: fmt::Display> fmt::Display for Binding<Var, Fun> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}{}", self.head(), self.expr)
+        }
+    }
+
+    impl<Var: fmt::Display, Fun: fmt::Display> FunDecl<Var, Fun> {
+        /// What the declaration writes before its body
+        fn head(&self) -> String {
+            let params = self.params.iter().enumerate();
+            format!(
+                "def {}{} ({}){}: ",
+                if self.gen { "gen " } else { "" },
+                self.name,
+                Comma(&params.map(|(i, p)| self.signature.param(i, &p.node))),
+                self.signature,
+            )
+        }
+    }
+
+    impl<Var: fmt::Display, Fun: fmt::Display> fmt::Display for FunDecl<Var, Fun> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}{}", self.head(), self.body)
+        }
+    }
+
+    impl fmt::Debug for Prim {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Prim::Add => write!(f, "add"),
+                Prim::Sub => write!(f, "sub"),
+                Prim::Mul => write!(f, "mul"),
+                Prim::Not => write!(f, "not"),
+                Prim::And => write!(f, "and"),
+                Prim::Or => write!(f, "or"),
+                Prim::Lt => write!(f, "lt"),
+                Prim::Le => write!(f, "le"),
+                Prim::Gt => write!(f, "gt"),
+                Prim::Ge => write!(f, "ge"),
+                Prim::Eq => write!(f, "eq"),
+                Prim::Neq => write!(f, "neq"),
+                Prim::Add1
+                | Prim::Sub1
+                | Prim::WrappingAdd
+                | Prim::WrappingSub
+                | Prim::WrappingMul
+                | Prim::SaturatingAdd
+                | Prim::SaturatingSub
+                | Prim::SaturatingMul
+                | Prim::IsType(_)
+                | Prim::Check(_)
+                | Prim::None
+                | Prim::IsNone
+                | Prim::Str(_)
+                | Prim::ToStr
+                | Prim::Concat
+                | Prim::NewArray
+                | Prim::MakeArray
+                | Prim::ArrayGet
+                | Prim::ArraySet
+                | Prim::Length
+                | Prim::GetEnv
+                | Prim::Clock
+                | Prim::Random
+                | Prim::Yield
+                | Prim::Next => fmt::Display::fmt(self, f),
+            }
+        }
+    }
+
+    impl fmt::Display for Prim {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Prim::Add1 => write!(f, "add1"),
+                Prim::Sub1 => write!(f, "sub1"),
+                Prim::Not => write!(f, "!"),
+                Prim::Add => write!(f, "+"),
+                Prim::Sub => write!(f, "-"),
+                Prim::Mul => write!(f, "*"),
+                Prim::WrappingAdd => write!(f, "wrapping_add"),
+                Prim::WrappingSub => write!(f, "wrapping_sub"),
+                Prim::WrappingMul => write!(f, "wrapping_mul"),
+                Prim::SaturatingAdd => write!(f, "saturating_add"),
+                Prim::SaturatingSub => write!(f, "saturating_sub"),
+                Prim::SaturatingMul => write!(f, "saturating_mul"),
+                Prim::And => write!(f, "&&"),
+                Prim::Or => write!(f, "||"),
+                Prim::Lt => write!(f, "<"),
+                Prim::Le => write!(f, "<="),
+                Prim::Gt => write!(f, ">"),
+                Prim::Ge => write!(f, ">="),
+                Prim::Eq => write!(f, "=="),
+                Prim::Neq => write!(f, "!="),
+                Prim::IsType(ty) => write!(f, "is{}", ty),
+                Prim::Check(ty) => write!(f, "check{}", ty),
+                Prim::None => write!(f, "none"),
+                Prim::IsNone => write!(f, "isNone"),
+                Prim::Str(s) => write!(f, "{}", escape(s)),
+                Prim::ToStr => write!(f, "toString"),
+                Prim::Concat => write!(f, "concat"),
+                Prim::NewArray => write!(f, "newArray"),
+                Prim::MakeArray => write!(f, "mkArray"),
+                Prim::ArrayGet => write!(f, "arrayGet"),
+                Prim::ArraySet => write!(f, "arraySet"),
+                Prim::Length => write!(f, "length"),
+                Prim::GetEnv => write!(f, "getenv"),
+                Prim::Clock => write!(f, "clock"),
+                Prim::Random => write!(f, "random"),
+                Prim::Yield => write!(f, "yield"),
+                Prim::Next => write!(f, "next"),
+            }
+        }
+    }
+}
+
+mod impl_ssa {
+    use super::*;
+    use crate::ast::escape;
+    use crate::ssa::*;
+
+    impl<Var, Ana> fmt::Debug for Program<Var, Ana>
+    where
+        Var: fmt::Display,
+        Ana: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Program { externs, funs, blocks } = self;
+            LineBreaks(&externs.iter()).fmt(f)?;
+            LineBreaks(&funs.iter()).fmt(f)?;
+            LineBreaks(&blocks.iter().map(|b| Indent(0, b))).fmt(f)?;
+            Ok(())
+        }
+    }
+
+    impl<Var, Ana> fmt::Display for Program<Var, Ana>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Program { externs, funs, blocks } = self;
+            LineBreaks(&externs.iter()).fmt(f)?;
+            LineBreaks(&funs.iter()).fmt(f)?;
+            LineBreaks(&blocks.iter().map(|b| Indent(0, b))).fmt(f)?;
+            Ok(())
+        }
+    }
+
+    impl<Var> fmt::Debug for Extern<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Extern { name, params, contract } = self;
+            write!(f, "extern {}({}){}", name, Comma(&params.iter()), contract)
+        }
+    }
+
+    impl<Var> fmt::Display for Extern<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Extern { name, params, contract } = self;
+            write!(f, "extern {}({}){}", name, Comma(&params.iter()), contract)
+        }
+    }
+
+    impl<Var> fmt::Debug for FunBlock<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let FunBlock { name, params, body } = self;
+            write!(f, "fun {}({}):\n", name, Comma(&params.iter()))?;
+            write!(f, "  br {}", body)
+        }
+    }
+
+    impl<Var> fmt::Display for FunBlock<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let FunBlock { name, params, body } = self;
+            write!(f, "fun {}({}):\n", name, Comma(&params.iter()))?;
+            write!(f, "  br {}", body)
+        }
+    }
+
+    impl<Var, Ana> fmt::Debug for Indent<'_, BasicBlock<Var, Ana>>
+    where
+        Var: fmt::Display,
+        Ana: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let (indent, BasicBlock { label, params, body, ana }) = self.inner();
+            write!(f, "{}", "  ".repeat(indent))?;
+            write!(f, "block {} {} ({}):", label, ana, Comma(&params.iter()))?;
+            writeln!(f)?;
+            Indent(indent + 1, body).fmt(f)?;
+            Ok(())
+        }
+    }
+
+    impl<Var, Ana> fmt::Display for Indent<'_, BasicBlock<Var, Ana>>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let (indent, BasicBlock { label, params, body, .. }) = self.inner();
+            write!(f, "{}", "  ".repeat(indent))?;
+            write!(f, "block {}({}):", label, Comma(&params.iter()))?;
+            writeln!(f)?;
+            Indent(indent + 1, body).fmt(f)?;
+            Ok(())
+        }
+    }
+
+    impl<Var, Ana> fmt::Debug for Indent<'_, BlockBody<Var, Ana>>
+    where
+        Var: fmt::Display,
+        Ana: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let (indent, body) = self.inner();
+            match body {
+                BlockBody::Terminator(terminator, ana) => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    ana.fmt(f)?;
+                    writeln!(f)?;
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    terminator.fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::Operation { dest, op, next, ana } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    ana.fmt(f)?;
+                    writeln!(f)?;
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "{} = {}", dest, op)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::SubBlocks { blocks, next, ana } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    ana.fmt(f)?;
+                    writeln!(f)?;
+                    LineBreaks(&blocks.iter().map(|b| Indent(indent, b))).fmt(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::AssertType { ty, arg: of, next, ana } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    ana.fmt(f)?;
+                    writeln!(f)?;
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "assert{}({})", ty, of)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::AssertLength { len, next, ana } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    ana.fmt(f)?;
+                    writeln!(f)?;
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "assertLength({})", len)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::AssertInBounds { bound, arg: of, next, ana } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    ana.fmt(f)?;
+                    writeln!(f)?;
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "assertInBounds({}, {})", bound, of,)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::Store { addr, offset: off, val, next, ana } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    ana.fmt(f)?;
+                    writeln!(f)?;
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "store({}, {}, {})", addr, off, val)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    impl<Var, Ana> fmt::Display for Indent<'_, BlockBody<Var, Ana>>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let (indent, body) = self.inner();
+            match body {
+                BlockBody::Terminator(terminator, ..) => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    terminator.fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::Operation { dest, op, next, .. } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "{} = {}", dest, op)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::SubBlocks { blocks, next, .. } => {
+                    LineBreaks(&blocks.iter().map(|b| Indent(indent, b))).fmt(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::AssertType { ty, arg: of, next, .. } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "assert{}({})", ty, of)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::AssertLength { len, next, .. } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "assertLength({})", len)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::AssertInBounds { bound, arg: of, next, .. } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "assertInBounds({}, {})", bound, of,)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+                BlockBody::Store { addr, offset: off, val, next, .. } => {
+                    write!(f, "{}", "  ".repeat(indent))?;
+                    write!(f, "store({}, {}, {})", addr, off, val)?;
+                    writeln!(f)?;
+                    Indent(indent, next.as_ref()).fmt(f)?;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    impl<Var> fmt::Debug for Terminator<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Terminator::Return(imm) => write!(f, "ret {}", imm),
+                Terminator::Branch(branch) => write!(f, "br {}", branch),
+                Terminator::ConditionalBranch { cond, thn, els } => {
+                    write!(f, "cbr {} {} {}", cond, thn, els)
+                }
+                Terminator::Switch { scrutinee, low, targets, default } => {
+                    write!(
+                        f,
+                        "switch {} - {} [{}] {}",
+                        scrutinee,
+                        low,
+                        Comma(&targets.iter()),
+                        default
+                    )
+                }
+            }
+        }
+    }
+
+    impl<Var> fmt::Display for Terminator<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Terminator::Return(imm) => write!(f, "ret {}", imm),
+                Terminator::Branch(branch) => write!(f, "br {}", branch),
+                Terminator::ConditionalBranch { cond, thn, els } => {
+                    write!(f, "cbr {} {} {}", cond, thn, els)
+                }
+                Terminator::Switch { scrutinee, low, targets, default } => {
+                    write!(
+                        f,
+                        "switch {} - {} [{}] {}",
+                        scrutinee,
+                        low,
+                        Comma(&targets.iter()),
+                        default
+                    )
+                }
+            }
+        }
+    }
+
+    impl<Var> fmt::Debug for Branch<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Branch { target, args } = self;
+            write!(f, "{}({})", target, Comma(&args.iter()))
+        }
+    }
+
+    impl<Var> fmt::Display for Branch<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Branch { target, args } = self;
+            write!(f, "{}({})", target, Comma(&args.iter()))
+        }
+    }
+
+    impl<Var> fmt::Debug for Operation<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Operation::Immediate(imm) => write!(f, "{}", imm),
+                Operation::Prim1(prim @ Prim1::BitNot, imm) => write!(f, "{} {}", prim, imm),
+                Operation::Prim1(prim, imm) => write!(f, "{} {}", imm, prim),
+                Operation::Prim2(prim, imm1, imm2) => write!(f, "{} {} {}", imm1, prim, imm2),
+                Operation::Call { fun, args } => write!(f, "{}({})", fun, Comma(&args.iter())),
+                Operation::AllocateArray { len } => write!(f, "allocateArray({})", len),
+                Operation::Load { addr, offset: off } => write!(f, "load({}, {})", addr, off),
+                Operation::Str(s) => write!(f, "{}", escape(s)),
+            }
+        }
+    }
+
+    impl<Var> fmt::Display for Operation<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Operation::Immediate(imm) => write!(f, "{}", imm),
+                Operation::Prim1(prim @ Prim1::BitNot, imm) => write!(f, "{} {}", prim, imm),
+                Operation::Prim1(prim, imm) => write!(f, "{} {}", imm, prim),
+                Operation::Prim2(prim, imm1, imm2) => write!(f, "{} {} {}", imm1, prim, imm2),
+                Operation::Call { fun, args } => write!(f, "{}({})", fun, Comma(&args.iter())),
+                Operation::AllocateArray { len } => write!(f, "allocateArray({})", len),
+                Operation::Load { addr, offset: off } => write!(f, "load({}, {})", addr, off),
+                Operation::Str(s) => write!(f, "{}", escape(s)),
+            }
+        }
+    }
+
+    impl fmt::Debug for Prim1 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Prim1::BitNot => write!(f, "bit_not"),
+                Prim1::BitSal(n) => write!(f, "bit_sal/{}", n),
+                Prim1::BitSar(n) => write!(f, "bit_sar/{}", n),
+                Prim1::BitShl(n) => write!(f, "bit_shl/{}", n),
+                Prim1::BitShr(n) => write!(f, "bit_shr/{}", n),
+            }
+        }
+    }
+
+    impl fmt::Display for Prim1 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Prim1::BitNot => write!(f, "~"),
+                Prim1::BitSal(n) => write!(f, "<< {}", n),
+                Prim1::BitSar(n) => write!(f, ">> {}", n),
+                Prim1::BitShl(n) => write!(f, "<<< {}", n),
+                Prim1::BitShr(n) => write!(f, ">>> {}", n),
+            }
+        }
+    }
+
+    impl fmt::Debug for Prim2 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Prim2::Add => write!(f, "add"),
+                Prim2::Sub => write!(f, "sub"),
+                Prim2::Mul => write!(f, "mul"),
+                Prim2::WrappingAdd => write!(f, "wrapping_add"),
+                Prim2::WrappingSub => write!(f, "wrapping_sub"),
+                Prim2::WrappingMul => write!(f, "wrapping_mul"),
+                Prim2::SaturatingAdd => write!(f, "saturating_add"),
+                Prim2::SaturatingSub => write!(f, "saturating_sub"),
+                Prim2::SaturatingMul => write!(f, "saturating_mul"),
+                Prim2::BitAnd => write!(f, "bit_and"),
+                Prim2::BitOr => write!(f, "bit_or"),
+                Prim2::BitXor => write!(f, "bit_xor"),
+                Prim2::Lt => write!(f, "lt"),
+                Prim2::Le => write!(f, "le"),
+                Prim2::Gt => write!(f, "gt"),
+                Prim2::Ge => write!(f, "ge"),
+                Prim2::Eq => write!(f, "eq"),
+                Prim2::Neq => write!(f, "neq"),
+            }
+        }
+    }
+
+    impl fmt::Display for Prim2 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Prim2::Add => write!(f, "+"),
+                Prim2::Sub => write!(f, "-"),
+                Prim2::Mul => write!(f, "*"),
+                Prim2::WrappingAdd => write!(f, "+%"),
+                Prim2::WrappingSub => write!(f, "-%"),
+                Prim2::WrappingMul => write!(f, "*%"),
+                Prim2::SaturatingAdd => write!(f, "+|"),
+                Prim2::SaturatingSub => write!(f, "-|"),
+                Prim2::SaturatingMul => write!(f, "*|"),
+                Prim2::BitAnd => write!(f, "&"),
+                Prim2::BitOr => write!(f, "|"),
+                Prim2::BitXor => write!(f, "^"),
+                Prim2::Lt => write!(f, "<"),
+                Prim2::Le => write!(f, "<="),
+                Prim2::Gt => write!(f, ">"),
+                Prim2::Ge => write!(f, ">="),
+                Prim2::Eq => write!(f, "=="),
+                Prim2::Neq => write!(f, "!="),
+            }
+        }
+    }
+
+    impl<Var> fmt::Debug for Immediate<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Immediate::Const(c) => write!(f, "{}", c),
+                Immediate::Var(v) => write!(f, "{}", v),
+            }
+        }
+    }
+
+    impl<Var> fmt::Display for Immediate<Var>
+    where
+        Var: fmt::Display,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Immediate::Const(c) => write!(f, "{}", c),
+                Immediate::Var(v) => write!(f, "{}", v),
+            }
+        }
+    }
+}
+
+mod impl_annotated {
+    use super::*;
+    use crate::ana::LiveSet;
+    use crate::backend::Liveness;
+    use crate::identifiers::VarName;
+    use crate::ssa::*;
+
+    impl fmt::Display for LivenessAnnotated<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let LivenessAnnotated(Program { externs, funs, blocks }) = self;
+            let live = Liveness::new(self.0);
+            LineBreaks(&externs.iter()).fmt(f)?;
+            LineBreaks(&funs.iter()).fmt(f)?;
+            for block in blocks {
+                fmt_basic_block(f, &live, 0, block)?;
+                writeln!(f)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn fmt_basic_block(
+        f: &mut fmt::Formatter<'_>, live: &Liveness, indent: usize,
+        BasicBlock { label, params, body, ana }: &BasicBlock<VarName, LiveSet>,
+    ) -> fmt::Result {
+        write!(f, "{}", "  ".repeat(indent))?;
+        writeln!(f, "block {}({}):  # in: {}", label, Comma(&params.iter()), ana)?;
+        fmt_block_body(f, live, indent + 1, body)
+    }
+
+    fn fmt_block_body(
+        f: &mut fmt::Formatter<'_>, live: &Liveness, indent: usize,
+        body: &BlockBody<VarName, LiveSet>,
+    ) -> fmt::Result {
+        let line = match body {
+            BlockBody::Terminator(terminator, ..) => terminator.to_string(),
+            BlockBody::Operation { dest, op, .. } => format!("{} = {}", dest, op),
+            BlockBody::SubBlocks { blocks, next, .. } => {
+                for block in blocks {
+                    fmt_basic_block(f, live, indent, block)?;
+                    writeln!(f)?;
+                }
+                return fmt_block_body(f, live, indent, next);
+            }
+            BlockBody::AssertType { ty, arg: of, .. } => format!("assert{}({})", ty, of),
+            BlockBody::AssertLength { len, .. } => format!("assertLength({})", len),
+            BlockBody::AssertInBounds { bound, arg: of, .. } => {
+                format!("assertInBounds({}, {})", bound, of)
+            }
+            BlockBody::Store { addr, offset: off, val, .. } => {
+                format!("store({}, {}, {})", addr, off, val)
+            }
+        };
+        write!(f, "{}", "  ".repeat(indent))?;
+        write!(f, "{}  # in: {} out: {}", line, live.live_in(body), live.live_out(body))?;
+        match body.successor() {
+            Some(next) => {
+                writeln!(f)?;
+                fmt_block_body(f, live, indent, next)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+mod impl_memory_annotated {
+    use super::*;
+    use crate::ana::{MemoryAccess, MemorySsa};
+    use crate::identifiers::VarName;
+    use crate::ssa::*;
+
+    impl fmt::Display for MemoryAnnotated<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let MemoryAnnotated(Program { externs, funs, blocks }, memory) = self;
+            LineBreaks(&externs.iter()).fmt(f)?;
+            LineBreaks(&funs.iter()).fmt(f)?;
+            for block in blocks {
+                fmt_basic_block(f, memory, 0, block)?;
+                writeln!(f)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn fmt_basic_block(
+        f: &mut fmt::Formatter<'_>, memory: &MemorySsa, indent: usize,
+        BasicBlock { label, params, body, ana }: &BasicBlock<VarName, MemoryAccess>,
+    ) -> fmt::Result {
+        write!(f, "{}", "  ".repeat(indent))?;
+        let entry = memory.def(ana.defs);
+        writeln!(f, "block {}({}):  # {} = {}", label, Comma(&params.iter()), ana.defs, entry)?;
+        fmt_block_body(f, memory, indent + 1, body)
+    }
+
+    fn fmt_block_body(
+        f: &mut fmt::Formatter<'_>, memory: &MemorySsa, indent: usize,
+        body: &BlockBody<VarName, MemoryAccess>,
+    ) -> fmt::Result {
+        let MemoryAccess { uses, defs } = *body.analysis();
+        let line = match body {
+            BlockBody::Terminator(terminator, ..) => terminator.to_string(),
+            BlockBody::Operation { dest, op, .. } => format!("{} = {}", dest, op),
+            BlockBody::SubBlocks { blocks, next, .. } => {
+                for block in blocks {
+                    fmt_basic_block(f, memory, indent, block)?;
+                    writeln!(f)?;
+                }
+                return fmt_block_body(f, memory, indent, next);
+            }
+            BlockBody::AssertType { ty, arg: of, .. } => format!("assert{}({})", ty, of),
+            BlockBody::AssertLength { len, .. } => format!("assertLength({})", len),
+            BlockBody::AssertInBounds { bound, arg: of, .. } => {
+                format!("assertInBounds({}, {})", bound, of)
+            }
+            BlockBody::Store { addr, offset: off, val, .. } => {
+                format!("store({}, {}, {})", addr, off, val)
+            }
+        };
+        write!(f, "{}{}", "  ".repeat(indent), line)?;
+        let reads = matches!(
+            body,
+            BlockBody::Operation { op: Operation::Load { .. }, .. }
+                | BlockBody::Terminator(Terminator::Branch(_), _)
+                | BlockBody::Terminator(Terminator::ConditionalBranch { .. }, _)
+                | BlockBody::Terminator(Terminator::Switch { .. }, _)
+        );
+        if uses != defs {
+            write!(f, "  # {} = {}", defs, memory.def(defs))?;
+        } else if reads {
+            write!(f, "  # {}", uses)?;
+        }
+        match body.successor() {
+            Some(next) => {
+                writeln!(f)?;
+                fmt_block_body(f, memory, indent, next)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+mod impl_readable {
+    use super::*;
+    use crate::ana::{Coloring, Graph, PerfectEliminationOrder};
+    use crate::identifiers::VarName;
+    use crate::ssa::*;
+
+    impl<Ana: Clone> fmt::Display for Readable<'_, Program<VarName, Ana>> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Readable(names, prog) = self;
+            prog.map_vars(&mut |var| names.get(var)).fmt(f)
+        }
+    }
+
+    impl fmt::Display for Readable<'_, Graph<VarName>> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Readable(names, graph) = self;
+            let mut vertices = graph.vertices();
+            vertices.sort();
+            for v in vertices.iter() {
+                writeln!(f, "vertex {}", names.get(v))?;
+            }
+            for v1 in vertices.iter() {
+                let mut v2s: Vec<_> = graph.neighbors(v1).into_iter().flatten().collect();
+                v2s.sort();
+                for v2 in v2s {
+                    writeln!(f, "edge {} {}", names.get(v1), names.get(v2))?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl fmt::Display for Readable<'_, PerfectEliminationOrder> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Readable(names, order) = self;
+            Separated(&order.iter().map(|v| names.get(v)), " -> ").fmt(f)
+        }
+    }
+
+    impl fmt::Display for Readable<'_, Coloring> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let Readable(names, coloring) = self;
+            let mut assignment: Vec<_> = coloring.iter().collect();
+            assignment.sort_by_key(|(v, _)| *v);
+            for (v, a) in assignment {
+                writeln!(f, "{} -> {}", names.get(v), a)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Layouts of the surface AST that parse back to the same AST, and of SSA
+/// that are the `Display` of the program where lines fit
+mod impl_layout {
+    use super::*;
+    use crate::layout::{Layout, Pretty};
+
+    fn text(t: impl fmt::Display) -> Layout {
+        Layout::text(t.to_string())
+    }
+
+    mod ast {
+        use super::*;
+        use crate::ast::*;
+
+        /// The `##` lines of a doc comment, each followed by a line break
+        fn doc(doc: &Option<String>) -> Layout {
+            let lines = doc.iter().flat_map(|doc| doc.split('\n'));
+            Layout::concat(lines.map(|line| {
+                let line = if line.is_empty() { "##".to_string() } else { format!("## {}", line) };
+                Layout::text(line) + Layout::HardLine
+            }))
+        }
+
+        /// How tightly an expression binds, as in the grammar: an operand
+        /// of a lower precedence than required is parenthesized
+        fn precedence<Var, Fun>(e: &Expr<Var, Fun>) -> u8 {
+            match e {
+                Expr::Let { .. }
+                | Expr::If { .. }
+                | Expr::Switch { .. }
+                | Expr::Match { .. }
+                | Expr::FunDefs { .. } => 0,
+                Expr::Prim { prim: Prim::ArraySet, .. } => 1,
+                Expr::Prim { prim: Prim::And | Prim::Or, .. } => 2,
+                Expr::Prim {
+                    prim: Prim::Lt | Prim::Le | Prim::Gt | Prim::Ge | Prim::Eq | Prim::Neq,
+                    ..
+                } => 3,
+                Expr::Prim { prim: Prim::Add | Prim::Sub, .. } => 4,
+                Expr::Prim { prim: Prim::Mul, .. } => 5,
+                Expr::Prim { prim: Prim::Not, .. } => 6,
+                _ => 7,
+            }
+        }
+
+        fn operand<Var: fmt::Display, Fun: fmt::Display>(
+            e: &Expr<Var, Fun>, required: u8,
+        ) -> Layout {
+            if precedence(e) < required {
+                Layout::text("(") + e.layout().nest(1) + Layout::text(")")
+            } else {
+                e.layout()
+            }
+        }
+
+        impl<Var: fmt::Display, Fun: fmt::Display> Pretty for Prog<Var, Fun> {
+            fn layout(&self) -> Layout {
+                let Prog { externs, enums, name, param, body, doc: main_doc, .. } = self;
+                let externs = externs.iter().map(|ext| ext.layout() + Layout::HardLine);
+                let enums = enums.iter().map(|decl| decl.layout() + Layout::HardLine);
+                let header = text(format!("def {}({}):", name, param.node));
+                Layout::concat(externs.chain(enums))
+                    + doc(main_doc)
+                    + (header + (Layout::line() + body.layout()).nest(2)).group()
+            }
+        }
+
+        impl<Var: fmt::Display, Fun: fmt::Display> Pretty for ExtDecl<Var, Fun> {
+            fn layout(&self) -> Layout {
+                let params = self.params.iter().enumerate();
+                let params = params.map(|(i, p)| text(self.signature.param(i, &p.node)));
+                doc(&self.doc)
+                    + text(format!("extern {}", self.name))
+                    + Layout::delimited("(", params, ")", 4)
+                    + text(format!("{}{}", self.signature, self.contract))
+            }
+        }
+
+        impl<Fun: fmt::Display> Pretty for EnumDecl<Fun> {
+            fn layout(&self) -> Layout {
+                let variants = self.variants.iter().map(|v| Layout::line() + text(v));
+                let variants = Layout::join(variants, Layout::text(" |"));
+                doc(&self.doc) + (text(format!("enum {}:", self.name)) + variants.nest(4)).group()
+            }
+        }
+
+        impl<Var: fmt::Display, Fun: fmt::Display> Pretty for FunDecl<Var, Fun> {
+            fn layout(&self) -> Layout {
+                let gen = if self.gen { "gen " } else { "" };
+                let params = self.params.iter().enumerate();
+                let params = params.map(|(i, p)| text(self.signature.param(i, &p.node)));
+                let header = text(format!("def {}{}", gen, self.name))
+                    + Layout::delimited("(", params, ")", 4)
+                    + text(format!("{}:", self.signature));
+                doc(&self.doc) + (header + (Layout::line() + self.body.layout()).nest(2)).group()
+            }
+        }
+
+        impl<Var: fmt::Display, Fun: fmt::Display> Pretty for Binding<Var, Fun> {
+            fn layout(&self) -> Layout {
+                let value = Layout::line() + self.expr.layout();
+                let pattern = match &self.elems {
+                    Some(elems) => format!("[{}]", Comma(&elems.iter().map(|x| &x.node))),
+                    None => match self.ty {
+                        Some(ty) => format!("{}: {}", self.var.node, ty),
+                        None => self.var.node.to_string(),
+                    },
+                };
+                (text(format!("{} =", pattern)) + value.nest(2)).group()
+            }
+        }
+
+        impl<Var: fmt::Display, Fun: fmt::Display> Pretty for Expr<Var, Fun> {
+            fn layout(&self) -> Layout {
+                // what comes before the bodies of `let`s and definitions, which long programs
+                // chain, collected in a loop and grouped with the bodies from the innermost out
+                let mut heads = Vec::new();
+                let mut e = self;
+                loop {
+                    e = match e {
+                        Expr::Let { bindings, body, loc: _ } => {
+                            let bindings = Layout::join(
+                                bindings.iter().map(Pretty::layout),
+                                Layout::text(",") + Layout::line(),
+                            );
+                            let head = Layout::text("let")
+                                + (Layout::line() + bindings).nest(2)
+                                + Layout::line()
+                                + Layout::text("in");
+                            heads.push(head.group() + Layout::line());
+                            body
+                        }
+                        Expr::FunDefs { decls, body, loc: _ } => {
+                            let decls = decls.iter().enumerate().map(|(i, decl)| match i {
+                                0 => decl.layout(),
+                                // the doc comment of a declaration goes after its `and`
+                                _ if decl.doc.is_some() => {
+                                    Layout::line()
+                                        + Layout::text("and")
+                                        + Layout::HardLine
+                                        + decl.layout()
+                                }
+                                _ => Layout::line() + Layout::text("and ") + decl.layout(),
+                            });
+                            heads.push(
+                                Layout::concat(decls)
+                                    + Layout::line()
+                                    + Layout::text("in")
+                                    + Layout::line(),
+                            );
+                            body
+                        }
+                        _ => break,
+                    };
+                }
+                let body = match e {
+                    Expr::Num(n, _) => text(n),
+                    Expr::BigNum(n, _) => text(n),
+                    Expr::Bool(b, _) => text(b),
+                    Expr::Var(v, _) => text(v),
+                    Expr::Prim { prim, args, loc: _ } => match prim {
+                        Prim::None | Prim::Str(_) => text(prim),
+                        Prim::Add1
+                        | Prim::Sub1
+                        | Prim::IsType(_)
+                        | Prim::Check(_)
+                        | Prim::IsNone
+                        | Prim::NewArray
+                        | Prim::Length
+                        | Prim::GetEnv
+                        | Prim::Random
+                        | Prim::Yield
+                        | Prim::Next
+                        | Prim::Clock
+                        | Prim::WrappingAdd
+                        | Prim::WrappingSub
+                        | Prim::WrappingMul
+                        | Prim::SaturatingAdd
+                        | Prim::SaturatingSub
+                        | Prim::SaturatingMul
+                        | Prim::ToStr
+                        | Prim::Concat => {
+                            text(prim)
+                                + Layout::delimited("(", args.iter().map(Pretty::layout), ")", 2)
+                        }
+                        Prim::Add
+                        | Prim::Sub
+                        | Prim::Mul
+                        | Prim::And
+                        | Prim::Or
+                        | Prim::Lt
+                        | Prim::Le
+                        | Prim::Gt
+                        | Prim::Ge
+                        | Prim::Eq
+                        | Prim::Neq => {
+                            let level = precedence(self);
+                            // `&&` and `||` associate to the right, the others to the left
+                            let (left, right) = match prim {
+                                Prim::And | Prim::Or => (level + 1, level),
+                                _ => (level, level + 1),
+                            };
+                            let right = Layout::line() + operand(&args[1], right);
+                            (operand(&args[0], left) + text(format!(" {}", prim)) + right.nest(2))
+                                .group()
+                        }
+                        Prim::Not => text("!") + operand(&args[0], 7),
+                        Prim::MakeArray => {
+                            Layout::delimited("[", args.iter().map(Pretty::layout), "]", 2)
+                        }
+                        Prim::ArrayGet => {
+                            operand(&args[0], 7)
+                                + Layout::text("[")
+                                + args[1].layout()
+                                + Layout::text("]")
+                        }
+                        Prim::ArraySet => {
+                            let value = Layout::line() + operand(&args[2], 1);
+                            let place = operand(&args[0], 7)
+                                + Layout::text("[")
+                                + args[1].layout()
+                                + Layout::text("] :=");
+                            (place + value.nest(2)).group()
+                        }
+                    },
+                    Expr::If { cond, thn, els, loc: _ } => (Layout::text("if ")
+                        + cond.layout()
+                        + Layout::text(":")
+                        + (Layout::line() + thn.layout()).nest(2)
+                        + Layout::line()
+                        + Layout::text("else:")
+                        + (Layout::line() + els.layout()).nest(2))
+                    .group(),
+                    Expr::Switch { scrutinee, cases, default, loc: _ } => {
+                        let case = |label: String, body: &Expr<Var, Fun>| {
+                            let body = Layout::line() + body.layout();
+                            Layout::line() + (text(format!("| {}:", label)) + body.nest(4)).group()
+                        };
+                        let cases = cases.iter().map(|c| case(c.label.node.to_string(), &c.body));
+                        (Layout::text("switch ")
+                            + scrutinee.layout()
+                            + Layout::text(":")
+                            + Layout::concat(cases)
+                            + case("_".to_string(), default))
+                        .group()
+                    }
+                    Expr::Match { scrutinee, arms, loc: _ } => {
+                        let arms = arms.iter().map(|Arm { pattern, body }| {
+                            let body = Layout::line() + body.layout();
+                            Layout::line()
+                                + (text(format!("| {}:", pattern)) + body.nest(4)).group()
+                        });
+                        (Layout::text("match ")
+                            + scrutinee.layout()
+                            + Layout::text(":")
+                            + Layout::concat(arms)
+                            + Layout::line()
+                            + Layout::text("end"))
+                        .group()
+                    }
+                    Expr::Let { .. } | Expr::FunDefs { .. } => unreachable!("laid out by the loop"),
+                    Expr::Call { fun, args, loc: _ } => {
+                        text(fun) + Layout::delimited("(", args.iter().map(Pretty::layout), ")", 2)
+                    }
+                };
+                heads.into_iter().rev().fold(body, |body, head| (head + body).group())
+            }
+        }
+    }
+
+    mod ssa {
+        use super::*;
+        use crate::identifiers::VarName;
+        use crate::ssa::*;
+
+        fn operands<'a, Var: fmt::Display + 'a>(
+            open: &str, imms: impl IntoIterator<Item = &'a Immediate<Var>>, close: &str,
+        ) -> Layout {
+            Layout::delimited(open, imms.into_iter().map(text), close, 4)
+        }
+
+        impl<Var: fmt::Display, Ana> Pretty for Program<Var, Ana> {
+            fn layout(&self) -> Layout {
+                let Program { externs, funs, blocks } = self;
+                let externs = externs.iter().map(|ext| {
+                    let params = Layout::delimited("(", ext.params.iter().map(text), ")", 4);
+                    text(format!("extern {}", ext.name))
+                        + params
+                        + text(ext.contract.to_string())
+                        + Layout::HardLine
+                });
+                let funs = funs.iter().map(|FunBlock { name, params, body }| {
+                    let params = Layout::delimited("(", params.iter().map(text), ")", 4);
+                    let body = Layout::HardLine + Layout::text("br ") + body.layout();
+                    text(format!("fun {}", name))
+                        + params
+                        + Layout::text(":")
+                        + body.nest(2)
+                        + Layout::HardLine
+                });
+                let blocks = blocks.iter().map(|block| block.layout() + Layout::HardLine);
+                Layout::concat(externs.chain(funs).chain(blocks))
+            }
+        }
+
+        impl<Var: fmt::Display, Ana> Pretty for BasicBlock<Var, Ana> {
+            fn layout(&self) -> Layout {
+                let BasicBlock { label, params, body, .. } = self;
+                let params = Layout::delimited("(", params.iter().map(text), ")", 4);
+                let body = Layout::HardLine + body.layout();
+                text(format!("block {}", label)) + params + Layout::text(":") + body.nest(2)
+            }
+        }
+
+        impl<Var: fmt::Display, Ana> Pretty for BlockBody<Var, Ana> {
+            fn layout(&self) -> Layout {
+                let (line, next) = match self {
+                    BlockBody::Terminator(terminator, ..) => return terminator.layout(),
+                    BlockBody::Operation { dest, op, next, .. } => {
+                        let op = match op {
+                            Operation::Call { fun, args } => text(fun) + operands("(", args, ")"),
+                            Operation::AllocateArray { len } => {
+                                operands("allocateArray(", [len], ")")
+                            }
+                            Operation::Load { addr, offset } => {
+                                operands("load(", [addr, offset], ")")
+                            }
+                            op => text(op),
+                        };
+                        (text(format!("{} = ", dest)) + op, next)
+                    }
+                    BlockBody::SubBlocks { blocks, next, .. } => {
+                        let blocks = blocks.iter().map(|block| block.layout() + Layout::HardLine);
+                        return Layout::concat(blocks) + next.layout();
+                    }
+                    BlockBody::AssertType { ty, arg, next, .. } => {
+                        (operands(&format!("assert{}(", ty), [arg], ")"), next)
+                    }
+                    BlockBody::AssertLength { len, next, .. } => {
+                        (operands("assertLength(", [len], ")"), next)
+                    }
+                    BlockBody::AssertInBounds { bound, arg, next, .. } => {
+                        (operands("assertInBounds(", [bound, arg], ")"), next)
+                    }
+                    BlockBody::Store { addr, offset, val, next, .. } => {
+                        (operands("store(", [addr, offset, val], ")"), next)
+                    }
+                };
+                line + Layout::HardLine + next.layout()
+            }
+        }
+
+        impl<Var: fmt::Display> Pretty for Branch<Var> {
+            fn layout(&self) -> Layout {
+                text(&self.target) + operands("(", &self.args, ")")
+            }
+        }
+
+        impl<Var: fmt::Display> Pretty for Terminator<Var> {
+            fn layout(&self) -> Layout {
+                match self {
+                    Terminator::Return(imm) => text(format!("ret {}", imm)),
+                    Terminator::Branch(branch) => Layout::text("br ") + branch.layout(),
+                    Terminator::ConditionalBranch { cond, thn, els } => {
+                        text(format!("cbr {} {} {}", cond, thn, els))
+                    }
+                    Terminator::Switch { scrutinee, low, targets, default } => {
+                        let targets = Layout::delimited("[", targets.iter().map(text), "]", 4);
+                        let default = Layout::line() + text(default);
+                        (text(format!("switch {} - {} ", scrutinee, low))
+                            + targets
+                            + default.nest(4))
+                        .group()
+                    }
+                }
+            }
+        }
+
+        impl<Ana: Clone> Pretty for Readable<'_, Program<VarName, Ana>> {
+            fn layout(&self) -> Layout {
+                let Readable(names, prog) = self;
+                prog.map_vars(&mut |var| names.get(var)).layout()
+            }
+        }
+    }
+}
+
+mod impl_asm {
+    use super::*;
+    use crate::asm::*;
+
+    impl fmt::Display for Reg {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Reg::Rax => write!(f, "rax"),
+                Reg::Rbx => write!(f, "rbx"),
+                Reg::Rdx => write!(f, "rdx"),
+                Reg::Rcx => write!(f, "rcx"),
+                Reg::Rsi => write!(f, "rsi"),
+                Reg::Rdi => write!(f, "rdi"),
+                Reg::Rsp => write!(f, "rsp"),
+                Reg::Rbp => write!(f, "rbp"),
+                Reg::R8 => write!(f, "r8"),
+                Reg::R9 => write!(f, "r9"),
+                Reg::R10 => write!(f, "r10"),
+                Reg::R11 => write!(f, "r11"),
+                Reg::R12 => write!(f, "r12"),
+                Reg::R13 => write!(f, "r13"),
+                Reg::R14 => write!(f, "r14"),
+                Reg::R15 => write!(f, "r15"),
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Memoized stages of the pipeline for the batch driver, `snake test`, whose
+//! test files are often the same program checked at other optimizations or
+//! in another dump.
+//!
+//! A program is keyed by the hash of its printed syntax tree, which leaves
+//! out the comments and the layout, where the directives of the test files
+//! live. Its SSA is kept after every prefix of `compile::PIPELINE` that ran
+//! on it, so that a run at other optimizations starts from the longest
+//! prefix that it shares with an earlier one, and its assembly is kept for
+//! every configuration of the backend. Programs that fail to compile are not
+//! kept, as their errors point into their own source.
+//!
+//! The unit is the program rather than the function: the names of the SSA
+//! are numbered across the whole program, and the interprocedural passes
+//! make the code of a function depend on its callees.
+
+use crate::ana::Nil;
+use crate::compile::{self, PIPELINE};
+use crate::conf::{CompilerConf, Optimization};
+use crate::error::Error;
+use crate::frontend::Resolver;
+use crate::identifiers::{NameSupply, VarName};
+use crate::middle_end::Lowerer;
+use crate::ssa::Program;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// The SSA of a program after some passes, with the names that the next
+/// passes draw from
+#[derive(Clone)]
+struct Lowered {
+    names: NameSupply,
+    ssa: Program<VarName, Nil>,
+}
+
+/// The memo tables, which the threads that check a batch share
+#[derive(Default)]
+pub struct Queries {
+    /// The SSA of each program after each prefix of the passes that ran on it
+    ssa: Mutex<HashMap<(u64, Vec<Optimization>), Lowered>>,
+    /// The assembly of each program for each configuration of the backend
+    asm: Mutex<HashMap<(u64, u64), String>>,
+    /// How many stages were answered from the tables instead of run
+    hits: AtomicUsize,
+}
+
+impl Queries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many stages were answered from the tables instead of run
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// The SSA of `src` after the SSA passes among `optimizations`, as
+    /// `compile::middle_end_with` gives it
+    pub fn ssa(
+        &self, src: &str, optimizations: &HashSet<Optimization>,
+    ) -> Result<Program<VarName, Nil>, Error> {
+        let prog = compile::parse(src)?;
+        let key = hash(&prog.to_string());
+        let passes: Vec<_> =
+            PIPELINE.into_iter().filter(|pass| optimizations.contains(pass)).collect();
+        // the longest prefix of the passes that ran on the program before
+        let found = (0..=passes.len()).rev().find_map(|done| {
+            let ssa = self.ssa.lock().unwrap();
+            ssa.get(&(key, passes[..done].to_vec())).map(|lowered| (done, lowered.clone()))
+        });
+        let (done, mut lowered) = match found {
+            Some(found) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                found
+            }
+            None => {
+                let mut resolver = Resolver::new();
+                let ast = resolver.resolve_prog(prog)?;
+                let mut lowerer = Lowerer::from(resolver);
+                let ssa = lowerer.lower_prog(ast);
+                let lowered = Lowered { names: lowerer.names, ssa };
+                self.ssa.lock().unwrap().insert((key, vec![]), lowered.clone());
+                (0, lowered)
+            }
+        };
+        for (i, pass) in passes.iter().enumerate().skip(done) {
+            lowered.ssa = compile::run_pass(*pass, lowered.ssa, &mut lowered.names);
+            self.ssa.lock().unwrap().insert((key, passes[..=i].to_vec()), lowered.clone());
+        }
+        Ok(lowered.ssa)
+    }
+
+    /// The assembly of `src` as configured in `conf`, as
+    /// `compile::backend_with` emits it
+    pub fn asm(&self, src: &str, conf: &CompilerConf) -> Result<String, Error> {
+        let key = (hash(&compile::parse(src)?.to_string()), hash(conf));
+        if let Some(asm) = self.asm.lock().unwrap().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(asm.clone());
+        }
+        let asm = compile::assemble(self.ssa(src, &conf.optimizations)?, conf);
+        self.asm.lock().unwrap().insert(key, asm.clone());
+        Ok(asm)
+    }
+}
+
+fn hash(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,112 @@
+//! Optimization remarks for `--remarks`: what each pass did, and where it
+//! declined to, with the reason.
+//!
+//! As the remarks of LLVM, a remark is `passed` when the pass transformed
+//! the code and `missed` when it could not, and the remarks can be written as
+//! JSON, one object per line, for tools to consume.
+
+use crate::conf::Optimization;
+use crate::identifiers::*;
+use crate::ssa::Program;
+use std::collections::HashMap;
+use std::fmt;
+
+/// How `--remarks` writes the remarks
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum RemarkFormat {
+    /// e.g. `missed bcc in main: ...`
+    Text,
+    /// One JSON object per line, with the fields `kind`, `pass`, `function`
+    /// and `message`
+    Json,
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum RemarkKind {
+    /// The pass transformed the code
+    Passed,
+    /// The pass could have transformed the code, but did not
+    Missed,
+}
+
+impl fmt::Display for RemarkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemarkKind::Passed => write!(f, "passed"),
+            RemarkKind::Missed => write!(f, "missed"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Remark {
+    pub kind: RemarkKind,
+    pub pass: Optimization,
+    /// The function, or the block, that the remark is about
+    pub function: String,
+    pub message: String,
+}
+
+impl Remark {
+    pub fn passed(pass: Optimization, function: impl ToString, message: String) -> Self {
+        Self { kind: RemarkKind::Passed, pass, function: function.to_string(), message }
+    }
+
+    pub fn missed(pass: Optimization, function: impl ToString, message: String) -> Self {
+        Self { kind: RemarkKind::Missed, pass, function: function.to_string(), message }
+    }
+
+    /// e.g. `{"kind":"missed","pass":"ipcp","function":"f","message":"..."}`
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"kind\":\"{}\",\"pass\":\"{}\",\"function\":\"{}\",\"message\":\"{}\"}}",
+            self.kind,
+            self.pass.name(),
+            escape(&self.function),
+            escape(&self.message)
+        )
+    }
+
+    pub fn write(&self, format: RemarkFormat) -> String {
+        match format {
+            RemarkFormat::Text => self.to_string(),
+            RemarkFormat::Json => self.to_json(),
+        }
+    }
+}
+
+/// e.g. `missed bcc in main: ...`
+impl fmt::Display for Remark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} in {}: {}", self.kind, self.pass.name(), self.function, self.message)
+    }
+}
+
+/// `s` as the contents of a JSON string
+fn escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The functions whose bodies the top-level blocks of `prog` are; the other
+/// top-level blocks stand for themselves
+pub fn functions<T>(prog: &Program<VarName, T>) -> HashMap<BlockName, String> {
+    let mut functions = HashMap::new();
+    for block in prog.blocks.iter() {
+        functions.insert(block.label.clone(), block.label.to_string());
+    }
+    for fun in prog.funs.iter() {
+        functions.insert(fun.body.target.clone(), fun.name.to_string());
+    }
+    functions
+}
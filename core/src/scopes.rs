@@ -0,0 +1,167 @@
+//! The scopes of a resolved program for `--target scopes`: the variables
+//! that each function, binding of a `let` and arm of a `match` brings into
+//! scope, and the variables of the same name that they shadow.
+//!
+//! A binding of a `let` is in scope of the bindings after it, so each one
+//! opens a scope nested in the scope of the binding before it. Functions
+//! see the variables of their definitions, which so shadow theirs.
+
+use crate::ast::*;
+use crate::identifiers::*;
+use crate::span::Spanned;
+use crate::txt::FileInfo;
+
+#[derive(Clone, Debug)]
+pub struct Scope {
+    /// `def f`, `let` or `match`
+    pub kind: String,
+    pub vars: Vec<Spanned<VarName>>,
+    pub children: Vec<Scope>,
+}
+
+/// A variable bound where a variable of the same name is in scope
+#[derive(Clone, Debug)]
+pub struct Shadowing {
+    pub var: Spanned<VarName>,
+    pub shadowed: Spanned<VarName>,
+}
+
+impl Shadowing {
+    /// e.g. `warning[shadow]: variable "x" shadows the one bound at 1:10-1:11: 2:6-2:7`
+    pub fn report(&self, file_info: &FileInfo) -> String {
+        format!(
+            "warning[shadow]: variable \"{}\" shadows the one bound at {}: {}",
+            self.var.node.hint(),
+            file_info.span1_to_span2(self.shadowed.loc),
+            file_info.span1_to_span2(self.var.loc)
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Scopes {
+    /// The scope of `main`, which holds every other
+    pub root: Scope,
+    /// In the order the variables are bound
+    pub shadowings: Vec<Shadowing>,
+}
+
+/// The scope tree of `prog`
+pub fn scopes(prog: &BoundProg) -> Scopes {
+    let mut walk = Walk { in_scope: Vec::new(), shadowings: Vec::new() };
+    let kind = format!("def {}", prog.name);
+    let root = walk.scope(kind, vec![prog.param.clone()], |w, c| w.expr(&prog.body, c));
+    Scopes { root, shadowings: walk.shadowings }
+}
+
+impl Scopes {
+    /// The scopes, one per line indented by their depth, with the variables
+    /// each binds after its span, e.g. `  let: x%2 (3:6-3:7) shadows x%0`
+    pub fn render(&self, file: &FileInfo) -> String {
+        let mut lines = Vec::new();
+        let mut stack = vec![(&self.root, 0)];
+        while let Some((scope, depth)) = stack.pop() {
+            let vars: Vec<_> = (scope.vars.iter())
+                .map(|var| {
+                    let mut s = format!("{} ({})", var.node, file.span1_to_span2(var.loc));
+                    if let Some(shadowing) = self.shadowings.iter().find(|s| s.var == *var) {
+                        s += &format!(" shadows {}", shadowing.shadowed.node);
+                    }
+                    s
+                })
+                .collect();
+            lines.push(format!("{}{}: {}", "  ".repeat(depth), scope.kind, vars.join(", ")));
+            stack.extend(scope.children.iter().rev().map(|child| (child, depth + 1)));
+        }
+        lines.join("\n")
+    }
+}
+
+struct Walk {
+    in_scope: Vec<Spanned<VarName>>,
+    shadowings: Vec<Shadowing>,
+}
+
+impl Walk {
+    /// The scope of `vars`, whose children `inner` collects while they are in scope
+    fn scope(
+        &mut self, kind: String, vars: Vec<Spanned<VarName>>,
+        inner: impl FnOnce(&mut Self, &mut Vec<Scope>),
+    ) -> Scope {
+        for var in vars.iter() {
+            let hint = var.node.hint();
+            if let Some(shadowed) = self.in_scope.iter().rev().find(|v| v.node.hint() == hint) {
+                self.shadowings.push(Shadowing { var: var.clone(), shadowed: shadowed.clone() });
+            }
+        }
+        let outer = self.in_scope.len();
+        self.in_scope.extend(vars.iter().cloned());
+        let mut children = Vec::new();
+        inner(self, &mut children);
+        self.in_scope.truncate(outer);
+        Scope { kind, vars, children }
+    }
+
+    fn expr(&mut self, e: &BoundExpr, scopes: &mut Vec<Scope>) {
+        match e {
+            Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => {}
+            Expr::Prim { args, .. } | Expr::Call { args, .. } => {
+                args.iter().for_each(|arg| self.expr(arg, scopes))
+            }
+            Expr::Let { bindings, body, .. } => self.bindings(bindings, body, scopes),
+            Expr::If { cond, thn, els, .. } => {
+                [cond, thn, els].into_iter().for_each(|e| self.expr(e, scopes))
+            }
+            Expr::Switch { scrutinee, cases, default, .. } => {
+                self.expr(scrutinee, scopes);
+                cases.iter().for_each(|case| self.expr(&case.body, scopes));
+                self.expr(default, scopes);
+            }
+            Expr::Match { scrutinee, arms, .. } => {
+                self.expr(scrutinee, scopes);
+                for arm in arms {
+                    let vars = pattern_vars(&arm.pattern);
+                    if vars.is_empty() {
+                        self.expr(&arm.body, scopes);
+                    } else {
+                        let body = |w: &mut Self, c: &mut _| w.expr(&arm.body, c);
+                        scopes.push(self.scope("match".to_string(), vars, body));
+                    }
+                }
+            }
+            Expr::FunDefs { decls, body, .. } => {
+                for decl in decls {
+                    let kind = format!("def {}", decl.name);
+                    let body = |w: &mut Self, c: &mut _| w.expr(&decl.body, c);
+                    scopes.push(self.scope(kind, decl.params.clone(), body));
+                }
+                self.expr(body, scopes);
+            }
+        }
+    }
+
+    /// The first of `bindings` in the scope of those before it, and the rest
+    /// and `body` in its own
+    fn bindings(&mut self, bindings: &[BoundBinding], body: &BoundExpr, scopes: &mut Vec<Scope>) {
+        let Some((binding, rest)) = bindings.split_first() else {
+            return self.expr(body, scopes);
+        };
+        self.expr(&binding.expr, scopes);
+        // the array of a destructuring binding is not in scope
+        let vars = match &binding.elems {
+            Some(elems) => elems.clone(),
+            None => vec![binding.var.clone()],
+        };
+        let scope = self.scope("let".to_string(), vars, |w, c| w.bindings(rest, body, c));
+        scopes.push(scope);
+    }
+}
+
+/// The variables of `pattern` with their spans, from left to right
+fn pattern_vars(pattern: &BoundPattern) -> Vec<Spanned<VarName>> {
+    match pattern {
+        Pattern::Wildcard(_) => vec![],
+        Pattern::Var(var, loc) => vec![Spanned::new(var.clone(), *loc)],
+        Pattern::Ctor { args, .. } => args.iter().flat_map(pattern_vars).collect(),
+    }
+}
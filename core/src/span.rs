@@ -0,0 +1,82 @@
+//! A Span is a region of source code.
+
+/// The id of a node of the AST, by which the side tables of
+/// [`crate::nodes`] are keyed.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NodeId(pub u32);
+impl NodeId {
+    /// The id of the spans that no parsed node has, such as those of errors
+    pub const NONE: NodeId = NodeId(0);
+}
+
+/// 1-dimensional span of source locations.
+///
+/// This is what the parser outputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SrcLoc {
+    pub start_ix: usize,
+    pub end_ix: usize, // exclusive
+    /// The node parsed over the span. The passes carry the span of a node
+    /// over to the nodes they derive from it, which so share its id.
+    pub node: NodeId,
+}
+impl SrcLoc {
+    pub fn new(start_ix: usize, end_ix: usize) -> Self {
+        Self { start_ix, end_ix, node: NodeId::NONE }
+    }
+
+    /// The smallest span covering both spans.
+    pub fn merge(self, other: SrcLoc) -> SrcLoc {
+        SrcLoc::new(self.start_ix.min(other.start_ix), self.end_ix.max(other.end_ix))
+    }
+
+    /// Whether the offset `ix` lies within the span.
+    pub fn contains(&self, ix: usize) -> bool {
+        (self.start_ix..self.end_ix).contains(&ix)
+    }
+
+    /// Moves the span `by` bytes later, from a part of the source parsed
+    /// on its own to the whole source.
+    pub fn shift(&mut self, by: usize) {
+        self.start_ix += by;
+        self.end_ix += by;
+    }
+}
+
+/// A node of the AST together with its span.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub loc: SrcLoc,
+}
+impl<T> Spanned<T> {
+    pub fn new(node: T, loc: SrcLoc) -> Self {
+        Self { node, loc }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U> {
+        Spanned { node: f(self.node), loc: self.loc }
+    }
+
+    pub fn as_ref(&self) -> Spanned<&T> {
+        Spanned { node: &self.node, loc: self.loc }
+    }
+}
+
+/// 2-dimensional span of source locations.
+///
+/// This is what we use in error messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span2 {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize, // inclusive
+    pub end_col: usize,  // exclusive
+}
+
+impl std::fmt::Display for Span2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Span2 { start_line, start_col, end_line, end_col } = self;
+        write!(f, "{start_line}:{start_col}-{end_line}:{end_col}",)
+    }
+}
@@ -0,0 +1,408 @@
+use crate::identifiers::*;
+use crate::types::*;
+
+#[derive(Clone)]
+pub struct Program<Var, Ana> {
+    pub externs: Vec<Extern<Var>>,
+    pub funs: Vec<FunBlock<Var>>,
+    pub blocks: Vec<BasicBlock<Var, Ana>>,
+}
+
+#[derive(Clone)]
+pub struct Extern<Var> {
+    pub name: FunName,
+    pub params: Vec<Var>,
+    pub contract: Contract,
+}
+
+#[derive(Clone)]
+pub struct FunBlock<Var> {
+    pub name: FunName,
+    pub params: Vec<Var>,
+    pub body: Branch<Var>,
+}
+
+#[derive(Clone)]
+pub struct BasicBlock<Var, Ana> {
+    pub label: BlockName,
+    pub params: Vec<Var>,
+    pub body: BlockBody<Var, Ana>,
+    pub ana: Ana,
+}
+
+#[derive(Clone)]
+pub enum BlockBody<Var, Ana> {
+    Terminator(Terminator<Var>, Ana),
+    Operation {
+        dest: Var,
+        op: Operation<Var>,
+        next: Box<BlockBody<Var, Ana>>,
+        ana: Ana,
+    },
+    SubBlocks {
+        blocks: Vec<BasicBlock<Var, Ana>>,
+        next: Box<BlockBody<Var, Ana>>,
+        ana: Ana,
+    },
+    /// arg: tagged
+    AssertType {
+        ty: Type,
+        arg: Immediate<Var>,
+        next: Box<BlockBody<Var, Ana>>,
+        ana: Ana,
+    },
+    /// len: untagged
+    AssertLength {
+        len: Immediate<Var>,
+        next: Box<BlockBody<Var, Ana>>,
+        ana: Ana,
+    },
+    /// bound: untagged, arg: untagged
+    AssertInBounds {
+        bound: Immediate<Var>,
+        arg: Immediate<Var>,
+        next: Box<BlockBody<Var, Ana>>,
+        ana: Ana,
+    },
+    /// addr: untagged, offset: untagged, val: either
+    Store {
+        addr: Immediate<Var>,
+        offset: Immediate<Var>,
+        val: Immediate<Var>,
+        next: Box<BlockBody<Var, Ana>>,
+        ana: Ana,
+    },
+}
+
+#[derive(Clone)]
+pub enum Terminator<Var> {
+    Return(Immediate<Var>),
+    Branch(Branch<Var>),
+    ConditionalBranch {
+        cond: Immediate<Var>,
+        thn: BlockName,
+        els: BlockName,
+    },
+    /// Jumps to `targets[scrutinee - low]`, or to `default` outside of them.
+    /// scrutinee: untagged
+    Switch {
+        scrutinee: Immediate<Var>,
+        low: i64,
+        targets: Vec<BlockName>,
+        default: BlockName,
+    },
+}
+
+#[derive(Clone)]
+pub struct Branch<Var> {
+    pub target: BlockName,
+    pub args: Vec<Immediate<Var>>,
+}
+
+#[derive(Clone)]
+pub enum Operation<Var> {
+    Immediate(Immediate<Var>),
+    Prim1(Prim1, Immediate<Var>),
+    Prim2(Prim2, Immediate<Var>, Immediate<Var>),
+    Call {
+        fun: FunName,
+        args: Vec<Immediate<Var>>,
+    },
+    /// len: untagged
+    AllocateArray {
+        len: Immediate<Var>,
+    },
+    /// addr: untagged, offset: untagged
+    Load {
+        addr: Immediate<Var>,
+        offset: Immediate<Var>,
+    },
+    /// The tagged string literal, the same for equal literals
+    Str(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Prim1 {
+    BitNot,
+    // shift
+    BitSal(u8),
+    BitSar(u8),
+    BitShl(u8),
+    BitShr(u8),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Prim2 {
+    // arithmetic
+    Add,
+    Sub,
+    Mul,
+    // arithmetic that never raises an overflow error
+    WrappingAdd,
+    WrappingSub,
+    WrappingMul,
+    SaturatingAdd,
+    SaturatingSub,
+    SaturatingMul,
+    // logical
+    BitAnd,
+    BitOr,
+    BitXor,
+    // comparison
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Neq,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Immediate<Var> {
+    Const(i64),
+    Var(Var),
+}
+
+/* -------------------------------- Analysis -------------------------------- */
+
+pub trait Analysis<Ana> {
+    fn analysis(&self) -> &Ana;
+}
+
+impl<Var, Ana> Analysis<Ana> for BasicBlock<Var, Ana> {
+    fn analysis(&self) -> &Ana {
+        &self.ana
+    }
+}
+
+impl<Var, Ana> Analysis<Ana> for BlockBody<Var, Ana> {
+    fn analysis(&self) -> &Ana {
+        match self {
+            BlockBody::Terminator(.., ana) => ana,
+            BlockBody::Operation { ana, .. } => ana,
+            BlockBody::SubBlocks { ana, .. } => ana,
+            BlockBody::AssertType { ana, .. } => ana,
+            BlockBody::AssertLength { ana, .. } => ana,
+            BlockBody::AssertInBounds { ana, .. } => ana,
+            BlockBody::Store { ana, .. } => ana,
+        }
+    }
+}
+
+/* -------------------------------- Successor ------------------------------- */
+pub trait Successor {
+    type Succ;
+    fn successor(&self) -> Option<&Self::Succ>;
+}
+
+impl<Var, Ana> Successor for BasicBlock<Var, Ana> {
+    type Succ = BlockBody<Var, Ana>;
+    fn successor(&self) -> Option<&Self::Succ> {
+        Some(&self.body)
+    }
+}
+
+impl<Var, Ana> Successor for BlockBody<Var, Ana> {
+    type Succ = BlockBody<Var, Ana>;
+
+    fn successor(&self) -> Option<&Self::Succ> {
+        match self {
+            BlockBody::Terminator(..) => None,
+            BlockBody::Operation { next, .. } => Some(next),
+            BlockBody::SubBlocks { next, .. } => Some(next),
+            BlockBody::AssertType { next, .. } => Some(next),
+            BlockBody::AssertLength { next, .. } => Some(next),
+            BlockBody::AssertInBounds { next, .. } => Some(next),
+            BlockBody::Store { next, .. } => Some(next),
+        }
+    }
+}
+
+impl<Var, Ana> BlockBody<Var, Ana> {
+    /// The instruction after this one, to splice another chain in
+    pub fn successor_mut(&mut self) -> Option<&mut Box<Self>> {
+        match self {
+            BlockBody::Terminator(..) => None,
+            BlockBody::Operation { next, .. }
+            | BlockBody::SubBlocks { next, .. }
+            | BlockBody::AssertType { next, .. }
+            | BlockBody::AssertLength { next, .. }
+            | BlockBody::AssertInBounds { next, .. }
+            | BlockBody::Store { next, .. } => Some(next),
+        }
+    }
+
+    /// Drops `self` an instruction at a time, where dropping the boxes of a
+    /// long chain would recurse as deep
+    pub fn dismantle(self) {
+        let mut stack = vec![self];
+        while let Some(body) = stack.pop() {
+            match body {
+                BlockBody::Terminator(..) => {}
+                BlockBody::SubBlocks { blocks, next, .. } => {
+                    stack.extend(blocks.into_iter().map(|block| block.body));
+                    stack.push(*next);
+                }
+                BlockBody::Operation { next, .. }
+                | BlockBody::AssertType { next, .. }
+                | BlockBody::AssertLength { next, .. }
+                | BlockBody::AssertInBounds { next, .. }
+                | BlockBody::Store { next, .. } => stack.push(*next),
+            }
+        }
+    }
+}
+
+/* -------------------------------- Renaming -------------------------------- */
+
+impl<Var, Ana: Clone> Program<Var, Ana> {
+    /// The same program with every variable replaced by `f` of it.
+    pub fn map_vars<W>(&self, f: &mut impl FnMut(&Var) -> W) -> Program<W, Ana> {
+        let Program { externs, funs, blocks } = self;
+        Program {
+            externs: (externs.iter())
+                .map(|Extern { name, params, contract }| Extern {
+                    name: name.clone(),
+                    params: params.iter().map(&mut *f).collect(),
+                    contract: *contract,
+                })
+                .collect(),
+            funs: (funs.iter())
+                .map(|FunBlock { name, params, body }| FunBlock {
+                    name: name.clone(),
+                    params: params.iter().map(&mut *f).collect(),
+                    body: body.map_vars(f),
+                })
+                .collect(),
+            blocks: blocks.iter().map(|block| block.map_vars(f)).collect(),
+        }
+    }
+
+    /// All variables of the program, parameters included.
+    pub fn vars(&self) -> std::collections::BTreeSet<Var>
+    where
+        Var: Clone + Ord,
+    {
+        let mut vars = std::collections::BTreeSet::new();
+        self.map_vars(&mut |var: &Var| {
+            vars.insert(var.clone());
+        });
+        vars
+    }
+}
+
+impl<Var, Ana: Clone> BasicBlock<Var, Ana> {
+    pub fn map_vars<W>(&self, f: &mut impl FnMut(&Var) -> W) -> BasicBlock<W, Ana> {
+        let BasicBlock { label, params, body, ana } = self;
+        BasicBlock {
+            label: label.clone(),
+            params: params.iter().map(&mut *f).collect(),
+            body: body.map_vars(f),
+            ana: ana.clone(),
+        }
+    }
+}
+
+impl<Var, Ana: Clone> BlockBody<Var, Ana> {
+    pub fn map_vars<W>(&self, f: &mut impl FnMut(&Var) -> W) -> BlockBody<W, Ana> {
+        match self {
+            BlockBody::Terminator(terminator, ana) => {
+                BlockBody::Terminator(terminator.map_vars(f), ana.clone())
+            }
+            BlockBody::Operation { dest, op, next, ana } => BlockBody::Operation {
+                dest: f(dest),
+                op: op.map_vars(f),
+                next: Box::new(next.map_vars(f)),
+                ana: ana.clone(),
+            },
+            BlockBody::SubBlocks { blocks, next, ana } => BlockBody::SubBlocks {
+                blocks: blocks.iter().map(|block| block.map_vars(f)).collect(),
+                next: Box::new(next.map_vars(f)),
+                ana: ana.clone(),
+            },
+            BlockBody::AssertType { ty, arg, next, ana } => BlockBody::AssertType {
+                ty: *ty,
+                arg: arg.map_vars(f),
+                next: Box::new(next.map_vars(f)),
+                ana: ana.clone(),
+            },
+            BlockBody::AssertLength { len, next, ana } => BlockBody::AssertLength {
+                len: len.map_vars(f),
+                next: Box::new(next.map_vars(f)),
+                ana: ana.clone(),
+            },
+            BlockBody::AssertInBounds { bound, arg, next, ana } => BlockBody::AssertInBounds {
+                bound: bound.map_vars(f),
+                arg: arg.map_vars(f),
+                next: Box::new(next.map_vars(f)),
+                ana: ana.clone(),
+            },
+            BlockBody::Store { addr, offset, val, next, ana } => BlockBody::Store {
+                addr: addr.map_vars(f),
+                offset: offset.map_vars(f),
+                val: val.map_vars(f),
+                next: Box::new(next.map_vars(f)),
+                ana: ana.clone(),
+            },
+        }
+    }
+}
+
+impl<Var> Terminator<Var> {
+    pub fn map_vars<W>(&self, f: &mut impl FnMut(&Var) -> W) -> Terminator<W> {
+        match self {
+            Terminator::Return(imm) => Terminator::Return(imm.map_vars(f)),
+            Terminator::Branch(branch) => Terminator::Branch(branch.map_vars(f)),
+            Terminator::ConditionalBranch { cond, thn, els } => Terminator::ConditionalBranch {
+                cond: cond.map_vars(f),
+                thn: thn.clone(),
+                els: els.clone(),
+            },
+            Terminator::Switch { scrutinee, low, targets, default } => Terminator::Switch {
+                scrutinee: scrutinee.map_vars(f),
+                low: *low,
+                targets: targets.clone(),
+                default: default.clone(),
+            },
+        }
+    }
+}
+
+impl<Var> Branch<Var> {
+    pub fn map_vars<W>(&self, f: &mut impl FnMut(&Var) -> W) -> Branch<W> {
+        Branch {
+            target: self.target.clone(),
+            args: self.args.iter().map(|arg| arg.map_vars(f)).collect(),
+        }
+    }
+}
+
+impl<Var> Operation<Var> {
+    pub fn map_vars<W>(&self, f: &mut impl FnMut(&Var) -> W) -> Operation<W> {
+        match self {
+            Operation::Immediate(imm) => Operation::Immediate(imm.map_vars(f)),
+            Operation::Prim1(prim, imm) => Operation::Prim1(*prim, imm.map_vars(f)),
+            Operation::Prim2(prim, imm1, imm2) => {
+                Operation::Prim2(*prim, imm1.map_vars(f), imm2.map_vars(f))
+            }
+            Operation::Call { fun, args } => Operation::Call {
+                fun: fun.clone(),
+                args: args.iter().map(|arg| arg.map_vars(f)).collect(),
+            },
+            Operation::AllocateArray { len } => Operation::AllocateArray { len: len.map_vars(f) },
+            Operation::Load { addr, offset } => {
+                Operation::Load { addr: addr.map_vars(f), offset: offset.map_vars(f) }
+            }
+            Operation::Str(s) => Operation::Str(s.clone()),
+        }
+    }
+}
+
+impl<Var> Immediate<Var> {
+    pub fn map_vars<W>(&self, f: &mut impl FnMut(&Var) -> W) -> Immediate<W> {
+        match self {
+            Immediate::Const(c) => Immediate::Const(*c),
+            Immediate::Var(var) => Immediate::Var(f(var)),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Programs that stress the register allocator, for `snake bench`.
+//!
+//! Unlike the fuzz targets, which look for programs that the frontend
+//! mishandles, the programs here are well-formed and only shaped to make
+//! allocation hard: every function binds `live` values that all stay live
+//! until its result sums them up, and a share of the bindings are calls, across
+//! which the values live in registers that the calls clobber or in the
+//! registers that the callee saves. The programs terminate and their values
+//! stay small, so that they can also be run.
+//!
+//! [`AllocStats`] measures what the allocator makes of such a program: the
+//! size of the interference graph, the registers it colors with, the spills,
+//! and how long `ConflictAnalysis` and the coloring take.
+
+use crate::ana::{Allocation, Graph, LiveSet};
+use crate::asm::Reg;
+use crate::backend::{ConflictAnalysis, RegisterAllocator};
+use crate::identifiers::*;
+use crate::ssa::Program;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// The shape of a generated program
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Shape {
+    /// The number of values live at once in each function
+    pub live: usize,
+    /// The percentage of the bindings that call a function
+    pub calls: u8,
+    /// The number of functions, besides `main` and `least`, which they call
+    pub funs: usize,
+    /// The seed of the choices, so that a shape and a seed make one program
+    pub seed: u64,
+}
+
+impl Shape {
+    /// The source of the program, e.g. for `live: 3`, functions of the form
+    ///
+    /// ```text
+    /// and def f0(a, b):
+    ///   let x0 = a + 1,
+    ///       x1 = least(x0, b),
+    ///       x2 = x0 + 3
+    ///   in
+    ///   x0 + x1 + x2
+    /// ```
+    pub fn program(&self) -> String {
+        let mut rng = self.seed;
+        let mut src = String::new();
+        let _ = writeln!(src, "# live: {}, calls: {}%, seed: {}", self.live, self.calls, self.seed);
+        src.push_str("def main(args):\n  def least(a, b):\n    if a < b: a else: b\n");
+        for f in 0..self.funs {
+            let _ = writeln!(src, "  and def f{}(a, b):", f);
+            if self.live == 0 {
+                src.push_str("    a + b\n");
+                continue;
+            }
+            for x in 0..self.live {
+                let keyword = if x == 0 { "    let" } else { "       " };
+                let call = next(&mut rng) % 100 < self.calls as u64;
+                // the operands are the parameters or any earlier binding
+                let mut operand = || match next(&mut rng) % (x as u64 + 2) {
+                    0 => "a".to_string(),
+                    1 => "b".to_string(),
+                    i => format!("x{}", i - 2),
+                };
+                let value = if call {
+                    format!("least({}, {})", operand(), operand())
+                } else {
+                    // adding the index keeps the values linear in the arguments
+                    format!("{} + {}", operand(), x + 1)
+                };
+                let sep = if x + 1 == self.live { "" } else { "," };
+                let _ = writeln!(src, "{} x{} = {}{}", keyword, x, value, sep);
+            }
+            let sum: Vec<String> = (0..self.live).map(|x| format!("x{}", x)).collect();
+            let _ = writeln!(src, "    in\n    {}", sum.join(" + "));
+        }
+        // the length of the arguments keeps the calls from being constant
+        let calls: Vec<String> =
+            (0..self.funs).map(|f| format!("f{}(length(args), {})", f, f)).collect();
+        let _ = writeln!(src, "  in\n  [{}]", calls.join(", "));
+        src
+    }
+}
+
+/// The next number of a splitmix64 generator at `state`, as `Services::random`
+fn next(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// What the allocator made of a program
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllocStats {
+    /// The vertices of the interference graph
+    pub vars: usize,
+    /// The edges of the interference graph, each counted once
+    pub edges: usize,
+    /// The registers that some variable is colored with
+    pub registers: usize,
+    /// The variables that are spilled
+    pub spills: usize,
+    /// The time taken by the conflict analysis and the coloring
+    pub time: Duration,
+}
+
+impl AllocStats {
+    /// Allocates `registers` to the variables of `prog`, as the backend does
+    pub fn new(prog: &Program<VarName, LiveSet>, registers: &[Reg]) -> Self {
+        let start = std::time::Instant::now();
+        let conflicts = ConflictAnalysis::new(prog);
+        let (vars, edges) = size(&conflicts.interference);
+        let mut allocator = RegisterAllocator::new();
+        allocator.graph_color(conflicts, registers, false);
+        let time = start.elapsed();
+        let allocations = allocator.assignment.0.values();
+        let used: HashSet<Reg> = allocations.clone().filter_map(|loc| loc.as_reg()).collect();
+        let spills = allocations.filter(|loc| matches!(loc, Allocation::Spill(_))).count();
+        AllocStats { vars, edges, registers: used.len(), spills, time }
+    }
+
+    /// The header of the table that `Display` writes the rows of
+    pub const HEADER: &'static str = "    vars     edges  registers  spills     time (ms)";
+}
+
+/// The numbers of vertices and of edges of `g`
+fn size(g: &Graph<VarName>) -> (usize, usize) {
+    let degrees: usize =
+        g.vertices().iter().map(|v| g.neighbors(v).map_or(0, |ns| ns.len())).sum();
+    (g.num_vertices(), degrees / 2)
+}
+
+impl std::fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:>8}  {:>8}  {:>9}  {:>6}  {:>12.3}",
+            self.vars,
+            self.edges,
+            self.registers,
+            self.spills,
+            self.time.as_secs_f64() * 1000.0
+        )
+    }
+}
@@ -0,0 +1,676 @@
+//! Checks emitted code against the SSA it came from, block by block.
+//!
+//! The instructions of a block run on symbolic values: each register and
+//! stack slot starts out holding the variable allocated to it, if that
+//! variable is live, and every instruction builds a term over what it reads.
+//! The SSA block is evaluated to terms of the same shape, and at the
+//! terminator the returned value, the condition and the arguments and live
+//! variables of the branch targets must sit where the allocation says they
+//! do. A register that the emitter reads from the wrong place or clobbers
+//! while its variable is still live then shows up as a different term.
+//!
+//! Only straight-line code is modeled: blocks that call functions or
+//! allocate arrays, which clobber the volatile registers, and saturating
+//! arithmetic, which is branchless but not a single operation, are skipped.
+//! Overflow checks and assertions are side exits and do not end a block.
+
+use crate::ana::{Allocation, Coloring, LiveSet};
+use crate::asm::*;
+use crate::backend::{Liveness, SnakeErr};
+use crate::identifiers::*;
+use crate::ssa::*;
+use std::collections::HashMap;
+use std::fmt;
+
+/// An emitted block that does not do what its SSA does
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The label of the block, or the name of the function
+    pub block: String,
+    pub message: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.block, self.message)
+    }
+}
+
+/// Checks the blocks and functions of `prog` that the emitter turned into
+/// `asm` with the allocation `assignment`; the number of checked blocks, or
+/// the mismatches.
+pub fn check(
+    prog: &Program<VarName, LiveSet>, assignment: &Coloring, asm: &Asm,
+) -> Result<usize, Vec<Mismatch>> {
+    let checker = Checker::new(prog, assignment, asm);
+    let mut mismatches = Vec::new();
+    let mut checked = 0;
+    for fun in prog.funs.iter() {
+        checked += 1;
+        if let Err(message) = checker.check_fun(fun) {
+            mismatches.push(Mismatch { block: fun.name.to_string(), message });
+        }
+    }
+    for block in checker.blocks.values() {
+        match checker.check_block(block) {
+            Ok(true) => checked += 1,
+            Ok(false) => {}
+            Err(message) => {
+                checked += 1;
+                mismatches.push(Mismatch { block: block.label.to_string(), message })
+            }
+        }
+    }
+    if mismatches.is_empty() {
+        Ok(checked)
+    } else {
+        mismatches.sort_by(|a, b| a.block.cmp(&b.block));
+        Err(mismatches)
+    }
+}
+
+/// A register, or a stack slot at an offset from rsp
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+enum Location {
+    Reg(Reg),
+    Stack(i32),
+}
+
+impl From<Allocation> for Location {
+    fn from(alloc: Allocation) -> Self {
+        match alloc {
+            Allocation::Reg(reg) => Location::Reg(reg),
+            Allocation::Spill(slot) => Location::Stack(-8 * slot),
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Reg(reg) => write!(f, "{}", reg_to_string(*reg)),
+            Location::Stack(offset) => write!(f, "[rsp{:+}]", offset),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    And,
+    Or,
+    Xor,
+    Not,
+    Sal(u8),
+    Sar(u8),
+    Shl(u8),
+    Shr(u8),
+    Set(ConditionCode),
+    /// The third argument if the first two compare by the condition, else the fourth
+    Select(ConditionCode),
+    Load,
+}
+
+/// A value in terms of the variables live at the start of the block
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Sym {
+    Var(VarName),
+    Const(i64),
+    Op(Op, Vec<Sym>),
+    /// Whatever was in a location that holds no live variable
+    Unknown(Location),
+}
+
+impl Sym {
+    /// `op(args)`, with `xor x, -1` as `not x`, and a tagged comparison as
+    /// the selection of the tagged booleans
+    fn op(op: Op, args: Vec<Sym>) -> Sym {
+        match (op, &args[..]) {
+            (Op::Xor, [x, Sym::Const(-1)]) => Sym::Op(Op::Not, vec![x.clone()]),
+            (Op::Or, [Sym::Op(Op::Sal(by), shifted), Sym::Const(tag)])
+                if *by < 32 && (0..1 << by).contains(tag) =>
+            {
+                match &shifted[..] {
+                    [Sym::Op(Op::Set(cc), cmp)] => {
+                        let (t, f) = (Sym::Const(1 << by | tag), Sym::Const(*tag));
+                        Sym::Op(Op::Select(*cc), vec![cmp[0].clone(), cmp[1].clone(), t, f])
+                    }
+                    _ => Sym::Op(op, args),
+                }
+            }
+            _ => Sym::Op(op, args),
+        }
+    }
+}
+
+impl fmt::Display for Sym {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sym::Var(var) => write!(f, "{}", var),
+            Sym::Const(n) => write!(f, "{}", n),
+            Sym::Op(op, args) => {
+                let mut args: Vec<_> = args.iter().map(|arg| arg.to_string()).collect();
+                let name = match op {
+                    Op::Add => "add",
+                    Op::Sub => "sub",
+                    Op::Mul => "mul",
+                    Op::And => "and",
+                    Op::Or => "or",
+                    Op::Xor => "xor",
+                    Op::Not => "not",
+                    Op::Load => "load",
+                    Op::Sal(n) | Op::Sar(n) | Op::Shl(n) | Op::Shr(n) => {
+                        args.push(n.to_string());
+                        match op {
+                            Op::Sal(_) => "sal",
+                            Op::Sar(_) => "sar",
+                            Op::Shl(_) => "shl",
+                            _ => "shr",
+                        }
+                    }
+                    Op::Set(cc) => return write!(f, "set{}({})", cc, args.join(", ")),
+                    Op::Select(cc) => return write!(f, "select{}({})", cc, args.join(", ")),
+                };
+                write!(f, "{}({})", name, args.join(", "))
+            }
+            Sym::Unknown(loc) => write!(f, "?{}", loc),
+        }
+    }
+}
+
+struct Checker<'a> {
+    assignment: &'a Coloring,
+    liveness: Liveness<'a>,
+    symbols: &'a Symbols,
+    /// Every block, including the nested ones
+    blocks: HashMap<&'a BlockName, &'a BasicBlock<VarName, LiveSet>>,
+    /// The instructions after each label, up to the end of its section
+    regions: HashMap<&'a str, &'a [Instr]>,
+    /// The index of the instruction at each label; folded code has several
+    /// labels at the same one
+    addresses: HashMap<&'a str, usize>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(prog: &'a Program<VarName, LiveSet>, assignment: &'a Coloring, asm: &'a Asm) -> Self {
+        let Asm { instrs, symbols } = asm;
+        fn collect<'a>(
+            b: &'a BlockBody<VarName, LiveSet>,
+            blocks: &mut HashMap<&'a BlockName, &'a BasicBlock<VarName, LiveSet>>,
+        ) {
+            if let BlockBody::SubBlocks { blocks: sub_blocks, .. } = b {
+                for block in sub_blocks.iter() {
+                    blocks.insert(&block.label, block);
+                    collect(&block.body, blocks);
+                }
+            }
+            if let Some(next) = b.successor() {
+                collect(next, blocks);
+            }
+        }
+        let mut blocks = HashMap::new();
+        for block in prog.blocks.iter() {
+            blocks.insert(&block.label, block);
+            collect(&block.body, &mut blocks);
+        }
+        let mut regions = HashMap::new();
+        let mut addresses = HashMap::new();
+        for (i, instr) in instrs.iter().enumerate() {
+            if let Instr::Label(label) = instr {
+                let at = instrs[i..]
+                    .iter()
+                    .position(|instr| !matches!(instr, Instr::Label(_) | Instr::Comment(_)))
+                    .map_or(instrs.len(), |n| i + n);
+                addresses.insert(symbols.name(*label), at);
+                let len = instrs[i + 1..]
+                    .iter()
+                    .position(|instr| matches!(instr, Instr::Section(_)))
+                    .unwrap_or(instrs.len() - i - 1);
+                regions.insert(symbols.name(*label), &instrs[i + 1..i + 1 + len]);
+            }
+        }
+        Checker { assignment, liveness: Liveness::new(prog), symbols, blocks, regions, addresses }
+    }
+
+    /// Whether `label` is at the code of `block`
+    fn labels(&self, label: &Label, block: &BlockName) -> bool {
+        let at = self.addresses.get(self.symbols.name(*label));
+        at.is_some_and(|at| self.addresses.get(block.to_string().as_str()) == Some(at))
+    }
+
+    fn location(&self, var: &VarName) -> Result<Location, String> {
+        match self.assignment.get(var) {
+            Some(alloc) => Ok((*alloc).into()),
+            None => Err(format!("{} is not allocated", var)),
+        }
+    }
+
+    fn region(&self, label: &str) -> Result<&'a [Instr], String> {
+        self.regions.get(label).copied().ok_or_else(|| format!("no code is labeled {}", label))
+    }
+
+    fn check_fun(&self, fun: &FunBlock<VarName>) -> Result<(), String> {
+        let mut machine = Machine::new(self.symbols);
+        for (i, param) in fun.params.iter().enumerate() {
+            let loc = match Reg::ARGS.get(i) {
+                Some(reg) => Location::Reg(*reg),
+                // above the return address, as `emit_fun_block` finds them
+                None => Location::Stack(8 * (i + 1 - Reg::ARGS.len()) as i32),
+            };
+            machine.state.insert(loc, Sym::Var(param.clone()));
+        }
+        let exit = Exit::Branch(&fun.body);
+        self.run(&mut machine, self.region(&fun.name.to_string())?, &HashMap::new(), &exit)
+    }
+
+    /// Whether `block` was checked, i.e. is straight-line code
+    fn check_block(&self, block: &BasicBlock<VarName, LiveSet>) -> Result<bool, String> {
+        let mut env = HashMap::new();
+        let Some(exit) = Self::evaluate(&block.body, &mut env) else {
+            return Ok(false);
+        };
+        let mut machine = Machine::new(self.symbols);
+        let live = block.body.analysis();
+        let entry = block.params.iter().filter(|param| live.contains(*param));
+        for var in entry.chain(self.block_live_in(&block.label)) {
+            let loc = self.location(var)?;
+            if let Some(Sym::Var(other)) = machine.state.get(&loc) {
+                if other != var {
+                    return Err(format!("{} and {} are both live in {}", other, var, loc));
+                }
+            }
+            machine.state.insert(loc, Sym::Var(var.clone()));
+        }
+        self.run(&mut machine, self.region(&block.label.to_string())?, &env, &exit)?;
+        Ok(true)
+    }
+
+    fn block_live_in(&self, label: &BlockName) -> impl Iterator<Item = &'a VarName> {
+        self.liveness.block_live_in(label).into_iter().flat_map(|live| live.iter())
+    }
+
+    /// The values of the operations of `b` in terms of the variables live
+    /// before it, and how it exits; none if it is not straight-line code.
+    fn evaluate<'b>(
+        b: &'b BlockBody<VarName, LiveSet>, env: &mut HashMap<VarName, Sym>,
+    ) -> Option<Exit<'b>> {
+        match b {
+            BlockBody::Terminator(Terminator::Return(imm), _) => Some(Exit::Return(imm)),
+            BlockBody::Terminator(Terminator::Branch(branch), _) => Some(Exit::Branch(branch)),
+            BlockBody::Terminator(Terminator::ConditionalBranch { cond, thn, els }, _) => {
+                Some(Exit::Conditional { cond, thn, els })
+            }
+            BlockBody::Terminator(Terminator::Switch { scrutinee, low, targets, default }, _) => {
+                Some(Exit::Switch { scrutinee, low: *low, targets, default })
+            }
+            BlockBody::Operation { dest, op, next, .. } => {
+                let value = Self::evaluate_op(op, env)?;
+                env.insert(dest.clone(), value);
+                Self::evaluate(next, env)
+            }
+            BlockBody::SubBlocks { next, .. }
+            | BlockBody::AssertType { next, .. }
+            | BlockBody::AssertLength { next, .. }
+            | BlockBody::AssertInBounds { next, .. }
+            | BlockBody::Store { next, .. } => Self::evaluate(next, env),
+        }
+    }
+
+    fn evaluate_op(op: &Operation<VarName>, env: &HashMap<VarName, Sym>) -> Option<Sym> {
+        let imm = |imm: &Immediate<VarName>| value(imm, env);
+        Some(match op {
+            Operation::Immediate(x) => imm(x),
+            Operation::Prim1(prim, x) => {
+                let op = match prim {
+                    Prim1::BitNot => Op::Not,
+                    Prim1::BitSal(n) => Op::Sal(*n),
+                    Prim1::BitSar(n) => Op::Sar(*n),
+                    Prim1::BitShl(n) => Op::Shl(*n),
+                    Prim1::BitShr(n) => Op::Shr(*n),
+                };
+                Sym::op(op, vec![imm(x)])
+            }
+            Operation::Prim2(prim, x, y) => {
+                let op = match prim {
+                    Prim2::Add | Prim2::WrappingAdd => Op::Add,
+                    Prim2::Sub | Prim2::WrappingSub => Op::Sub,
+                    Prim2::Mul | Prim2::WrappingMul => Op::Mul,
+                    Prim2::BitAnd => Op::And,
+                    Prim2::BitOr => Op::Or,
+                    Prim2::BitXor => Op::Xor,
+                    Prim2::Lt => Op::Set(ConditionCode::L),
+                    Prim2::Gt => Op::Set(ConditionCode::G),
+                    Prim2::Le => Op::Set(ConditionCode::LE),
+                    Prim2::Ge => Op::Set(ConditionCode::GE),
+                    Prim2::Eq => Op::Set(ConditionCode::E),
+                    Prim2::Neq => Op::Set(ConditionCode::NE),
+                    Prim2::SaturatingAdd | Prim2::SaturatingSub | Prim2::SaturatingMul => {
+                        return None
+                    }
+                };
+                Sym::op(op, vec![imm(x), imm(y)])
+            }
+            // arrays are words, addressed by untagged offsets
+            Operation::Load { addr, offset } => {
+                let offset = Sym::op(Op::Mul, vec![imm(offset), Sym::Const(8)]);
+                Sym::op(Op::Load, vec![Sym::op(Op::Add, vec![offset, imm(addr)])])
+            }
+            Operation::Call { .. } | Operation::AllocateArray { .. } | Operation::Str(_) => {
+                return None
+            }
+        })
+    }
+
+    /// Runs `instrs` up to the exit of the block, which must leave the
+    /// values of `env` where `exit` expects them.
+    fn run(
+        &self, machine: &mut Machine, instrs: &[Instr], env: &HashMap<VarName, Sym>, exit: &Exit,
+    ) -> Result<(), String> {
+        let errors: Vec<_> = (0..SnakeErr::COUNT).map(|i| SnakeErr::from(i).to_string()).collect();
+        for instr in instrs {
+            match (instr, exit) {
+                (Instr::JCC(_, label), _)
+                    if errors.iter().any(|e| e == self.symbols.name(*label)) => {}
+                (Instr::Ret, Exit::Return(imm)) => {
+                    return machine.expect(Location::Reg(Reg::Rax), &value(imm, env), "returned")
+                }
+                (Instr::Jmp(label), Exit::Branch(branch)) if self.labels(label, &branch.target) => {
+                    return self.check_branch(machine, env, branch)
+                }
+                // the jump to the next label is dropped when laying out for size
+                (Instr::Label(label), Exit::Branch(branch))
+                    if self.labels(label, &branch.target) =>
+                {
+                    return self.check_branch(machine, env, branch)
+                }
+                // e.g. the labels of the code folded into this
+                (Instr::Label(_), _) => {}
+                (Instr::JCC(ConditionCode::NE, label), Exit::Conditional { cond, thn, els })
+                    if self.labels(label, thn) =>
+                {
+                    machine.expect_flags(&value(cond, env), &Sym::Const(0), ConditionCode::NE)?;
+                    return self.check_live_in(machine, env, [*thn, *els]);
+                }
+                (
+                    Instr::JCC(ConditionCode::AE, label),
+                    Exit::Switch { scrutinee, low, targets, default },
+                ) if self.labels(label, default) => {
+                    let index = match low {
+                        0 => value(scrutinee, env),
+                        low => Sym::op(Op::Sub, vec![value(scrutinee, env), Sym::Const(*low)]),
+                    };
+                    let len = Sym::Const(targets.len() as i64);
+                    machine.expect_flags(&index, &len, ConditionCode::AE)?;
+                    return self.check_live_in(machine, env, targets.iter().chain([*default]));
+                }
+                (Instr::Ret | Instr::Jmp(_) | Instr::JCC(..) | Instr::JmpReg(_), _) => {
+                    return Err(format!(
+                        "exits with `{}` instead of {}",
+                        instr_to_string(instr, self.symbols),
+                        exit
+                    ))
+                }
+                (instr, _) => machine.step(instr)?,
+            }
+        }
+        Err(format!("runs off the end instead of {}", exit))
+    }
+
+    fn check_branch(
+        &self, machine: &Machine, env: &HashMap<VarName, Sym>, branch: &Branch<VarName>,
+    ) -> Result<(), String> {
+        let Some(target) = self.blocks.get(&branch.target) else {
+            return Err(format!("branches to the unknown block {}", branch.target));
+        };
+        let live = target.body.analysis();
+        for (param, arg) in target.params.iter().zip(branch.args.iter()) {
+            if live.contains(param) {
+                let what = format!("passed to {} of {}", param, target.label);
+                machine.expect(self.location(param)?, &value(arg, env), &what)?;
+            }
+        }
+        self.check_live_in(machine, env, [&branch.target])
+    }
+
+    fn check_live_in<'b>(
+        &self, machine: &Machine, env: &HashMap<VarName, Sym>,
+        targets: impl IntoIterator<Item = &'b BlockName>,
+    ) -> Result<(), String> {
+        for target in targets {
+            for var in self.block_live_in(target) {
+                let what = format!("live in {} as {}", target, var);
+                let expected = env.get(var).cloned().unwrap_or_else(|| Sym::Var(var.clone()));
+                machine.expect(self.location(var)?, &expected, &what)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn value(imm: &Immediate<VarName>, env: &HashMap<VarName, Sym>) -> Sym {
+    match imm {
+        Immediate::Const(n) => Sym::Const(*n),
+        Immediate::Var(var) => env.get(var).cloned().unwrap_or_else(|| Sym::Var(var.clone())),
+    }
+}
+
+/// How a block ends
+enum Exit<'a> {
+    Return(&'a Immediate<VarName>),
+    Branch(&'a Branch<VarName>),
+    Conditional {
+        cond: &'a Immediate<VarName>,
+        thn: &'a BlockName,
+        els: &'a BlockName,
+    },
+    Switch {
+        scrutinee: &'a Immediate<VarName>,
+        low: i64,
+        targets: &'a [BlockName],
+        default: &'a BlockName,
+    },
+}
+
+impl fmt::Display for Exit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exit::Return(imm) => write!(f, "returning {}", imm),
+            Exit::Branch(branch) => write!(f, "branching to {}", branch.target),
+            Exit::Conditional { thn, els, .. } => write!(f, "branching to {} or {}", thn, els),
+            Exit::Switch { default, .. } => write!(f, "switching with default {}", default),
+        }
+    }
+}
+
+/// The registers, stack slots and flags of straight-line code
+struct Machine<'a> {
+    symbols: &'a Symbols,
+    state: HashMap<Location, Sym>,
+    /// The operands of the last comparison, if the flags still hold it, and
+    /// which of the flags do
+    flags: Option<(Sym, Sym, EFlags)>,
+}
+
+impl<'a> Machine<'a> {
+    fn new(symbols: &'a Symbols) -> Self {
+        Machine { symbols, state: HashMap::new(), flags: None }
+    }
+
+    fn get(&self, loc: Location) -> Sym {
+        self.state.get(&loc).cloned().unwrap_or(Sym::Unknown(loc))
+    }
+
+    fn set(&mut self, loc: Location, sym: Sym) -> Result<(), String> {
+        if loc == Location::Reg(Reg::Rsp) {
+            return Err("moves the stack pointer".to_string());
+        }
+        self.state.insert(loc, sym);
+        Ok(())
+    }
+
+    fn expect(&self, loc: Location, expected: &Sym, what: &str) -> Result<(), String> {
+        match self.get(loc) {
+            found if found == *expected => Ok(()),
+            found => Err(format!("{} holds {} instead of {}, {}", loc, found, expected, what)),
+        }
+    }
+
+    fn expect_flags(&self, lhs: &Sym, rhs: &Sym, cc: ConditionCode) -> Result<(), String> {
+        match &self.flags {
+            Some((l, r, flags)) if l == lhs && r == rhs && flags.contains(cc.reads()) => Ok(()),
+            Some((l, r, flags)) if flags.contains(cc.reads()) => {
+                Err(format!("compares {} to {} instead of {} to {}", l, r, lhs, rhs))
+            }
+            _ => Err(format!("branches on flags that do not compare {} to {}", lhs, rhs)),
+        }
+    }
+
+    /// The location of a memory operand, if it is on the stack
+    fn mem(mem: MemRef) -> Option<Location> {
+        (mem.reg == Reg::Rsp).then_some(Location::Stack(mem.offset))
+    }
+
+    fn read_mem(&self, mem: MemRef) -> Sym {
+        match Self::mem(mem) {
+            Some(loc) => self.get(loc),
+            None => {
+                let base = self.get(Location::Reg(mem.reg));
+                match mem.offset {
+                    0 => Sym::op(Op::Load, vec![base]),
+                    n => {
+                        Sym::op(Op::Load, vec![Sym::op(Op::Add, vec![base, Sym::Const(n as i64)])])
+                    }
+                }
+            }
+        }
+    }
+
+    fn write_mem(&mut self, mem: MemRef, sym: Sym) -> Result<(), String> {
+        match Self::mem(mem) {
+            Some(loc) => self.set(loc, sym),
+            // the heap is not modeled
+            None => Ok(()),
+        }
+    }
+
+    fn arg32(&self, arg: Arg32) -> Sym {
+        match arg {
+            Arg32::Reg(reg) => self.get(Location::Reg(reg)),
+            Arg32::Signed(n) => Sym::Const(n as i64),
+            Arg32::Unsigned(n) => Sym::Const(n as i64),
+            Arg32::Mem(mem) => self.read_mem(mem),
+        }
+    }
+
+    fn reg32(&self, arg: Reg32) -> Sym {
+        match arg {
+            Reg32::Reg(reg) => self.get(Location::Reg(reg)),
+            Reg32::Imm(n) => Sym::Const(n as i64),
+        }
+    }
+
+    /// `dst = dst op src`, or the operands of a comparison without `op`
+    fn binary(&mut self, op: Option<Op>, args: BinArgs) -> Result<(), String> {
+        let (dst, src) = match args {
+            BinArgs::ToReg(reg, src) => (self.get(Location::Reg(reg)), self.arg32(src)),
+            BinArgs::ToMem(mem, src) => (self.read_mem(mem), self.reg32(src)),
+        };
+        let Some(op) = op else {
+            self.flags = Some((dst, src, EFlags::ALL));
+            return Ok(());
+        };
+        self.flags = None;
+        let result = Sym::op(op, vec![dst, src]);
+        match args {
+            BinArgs::ToReg(reg, _) => self.set(Location::Reg(reg), result),
+            BinArgs::ToMem(mem, _) => self.write_mem(mem, result),
+        }
+    }
+
+    fn shift(&mut self, op: Op, ShArgs { reg, .. }: ShArgs) -> Result<(), String> {
+        self.flags = None;
+        let result = Sym::op(op, vec![self.get(Location::Reg(reg))]);
+        self.set(Location::Reg(reg), result)
+    }
+
+    fn step(&mut self, instr: &Instr) -> Result<(), String> {
+        self.execute(instr)?;
+        // arithmetic also compares its result to 0
+        if let Some((reg, flags)) = result_flags(instr) {
+            self.flags = Some((self.get(Location::Reg(reg)), Sym::Const(0), flags));
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, instr: &Instr) -> Result<(), String> {
+        match instr {
+            Instr::Mov(MovArgs::ToReg(reg, src)) => {
+                let sym = match *src {
+                    Arg64::Reg(src) => self.get(Location::Reg(src)),
+                    Arg64::Signed(n) => Sym::Const(n),
+                    Arg64::Unsigned(n) => Sym::Const(n as i64),
+                    Arg64::Mem(mem) => self.read_mem(mem),
+                };
+                self.set(Location::Reg(*reg), sym)
+            }
+            Instr::Mov(MovArgs::ToMem(mem, src)) => self.write_mem(*mem, self.reg32(*src)),
+            Instr::Add(args) => self.binary(Some(Op::Add), *args),
+            Instr::Sub(args) => self.binary(Some(Op::Sub), *args),
+            Instr::IMul(args) => self.binary(Some(Op::Mul), *args),
+            Instr::And(args) => self.binary(Some(Op::And), *args),
+            Instr::Or(args) => self.binary(Some(Op::Or), *args),
+            Instr::Xor(args) => self.binary(Some(Op::Xor), *args),
+            Instr::Cmp(args) => self.binary(None, *args),
+            Instr::Sal(args) => self.shift(Op::Sal(args.by), *args),
+            Instr::Sar(args) => self.shift(Op::Sar(args.by), *args),
+            Instr::Shl(args) => self.shift(Op::Shl(args.by), *args),
+            Instr::Shr(args) => self.shift(Op::Shr(args.by), *args),
+            Instr::Xchg(a, b) => {
+                let (a, b) = (Location::Reg(*a), Location::Reg(*b));
+                let (x, y) = (self.get(a), self.get(b));
+                self.set(a, y)?;
+                self.set(b, x)
+            }
+            // only a register just cleared by `mov reg, 0` holds the flag
+            Instr::SetCC(cc, reg8) => {
+                let reg =
+                    Reg::ALL.into_iter().find(|reg| Into::<Reg8>::into(*reg) == *reg8).ok_or_else(
+                        || format!("`{}` sets a high byte", instr_to_string(instr, self.symbols)),
+                    )?;
+                let sym = match (&self.flags, self.get(Location::Reg(reg))) {
+                    (Some((l, r, flags)), Sym::Const(0)) if flags.contains(cc.reads()) => {
+                        Sym::op(Op::Set(*cc), vec![l.clone(), r.clone()])
+                    }
+                    _ => {
+                        return Err(format!(
+                            "`{}` does not set a cleared register",
+                            instr_to_string(instr, self.symbols)
+                        ))
+                    }
+                };
+                self.set(Location::Reg(reg), sym)
+            }
+            Instr::CMovCC(cc, BinArgs::ToReg(reg, src)) => {
+                let sym = match &self.flags {
+                    Some((l, r, flags)) if flags.contains(cc.reads()) => {
+                        let (src, dst) = (self.arg32(*src), self.get(Location::Reg(*reg)));
+                        Sym::op(Op::Select(*cc), vec![l.clone(), r.clone(), src, dst])
+                    }
+                    _ => {
+                        return Err(format!(
+                            "`{}` moves on flags that no comparison set",
+                            instr_to_string(instr, self.symbols)
+                        ))
+                    }
+                };
+                self.set(Location::Reg(*reg), sym)
+            }
+            Instr::Comment(_) => Ok(()),
+            instr => {
+                Err(format!("`{}` is not straight-line code", instr_to_string(instr, self.symbols)))
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::frontend::ResolveError;
+use crate::span::{Span2, SrcLoc};
+#[derive(Clone, Debug)]
+pub struct FileInfo {
+    newlines: Vec<usize>,
+    len: usize,
+}
+
+impl FileInfo {
+    pub fn new(s: &str) -> Self {
+        FileInfo {
+            newlines: s.char_indices().filter(|(_i, c)| *c == '\n').map(|(i, _c)| i).collect(),
+            len: s.len(),
+        }
+    }
+
+    /// The offsets at which the lines start, followed by the length of the file
+    fn line_starts(&self) -> Vec<usize> {
+        let mut v = vec![0];
+        v.extend(self.newlines.iter().map(|ix| ix + 1));
+        v.push(self.len);
+        v
+    }
+
+    /// The line, from 1, and column, from 0, of the offset `ix`
+    pub fn line_col(&self, ix: usize) -> (usize, usize) {
+        Self::offset_to_line_col(&self.line_starts(), ix)
+    }
+
+    pub fn span1_to_span2(&self, offsets: SrcLoc) -> Span2 {
+        let v = self.line_starts();
+        let (start_line, start_col) = Self::offset_to_line_col(&v, offsets.start_ix);
+        let (end_line, end_col) = Self::offset_to_line_col(&v, offsets.end_ix - 1);
+        Span2 { start_line, start_col, end_line, end_col: end_col + 1 }
+    }
+
+    fn offset_to_line_col(newlines: &[usize], offset: usize) -> (usize, usize) {
+        let mut win = newlines.windows(2).enumerate();
+        while let Some((line, &[start, end])) = win.next() {
+            if start <= offset && offset < end {
+                return (line + 1, offset - start);
+            }
+        }
+        panic!("internal error: offset_to_line_col. Send this to the professor");
+    }
+
+    pub fn report_error(&self, err: ResolveError) -> String {
+        err.render(self)
+    }
+}
@@ -0,0 +1,167 @@
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Type {
+    Int,
+    Bool,
+    Array,
+}
+
+/// The word of `none`: the tag of big integers on a null pointer, which no
+/// big integer has, so that `none` fails the check of every type
+pub const NONE: i64 = 0b111;
+
+/// The tag of string literals, which point to a header word with `STR_FLAG`
+/// set, unlike the size of big integers, followed by the UTF-8 bytes
+pub const STR_TAG: i32 = 0b111;
+
+/// Set in the header of strings, whose other bits are the number of bytes
+pub const STR_FLAG: i64 = i64::MIN;
+
+/// What the calls of an extern promise, as annotated after its parameters,
+/// e.g. `extern hash(x) pure(true) ret(always) throws(false)`. Without an
+/// annotation, a call may have side effects, never return or fail.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Contract {
+    /// `pure(true)`: has no side effects
+    pub pure: bool,
+    /// `ret(always)`: returns, rather than `ret(any)`
+    pub returns: bool,
+    /// `throws(false)`: never reports a runtime error
+    pub nothrow: bool,
+}
+
+/// The types that an extern declares for its parameters and its result,
+/// e.g. `extern sqrt(x: Int) -> Int`. The calls check the typed arguments
+/// and pass them untagged: integers as machine integers, booleans as 0 or
+/// 1 and arrays as pointers to their length. A typed result is tagged
+/// likewise, where any integer but 0 is true. Untyped values pass as they are.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Signature {
+    pub params: Vec<Option<Type>>,
+    pub ret: Option<Type>,
+}
+
+mod impls {
+    use super::*;
+    use std::fmt;
+
+    impl Type {
+        pub fn tag(self) -> i64 {
+            match self {
+                Type::Int => 0b0,
+                Type::Bool => 0b01,
+                Type::Array => 0b011,
+            }
+        }
+        /// Arrays are aligned to 8 bytes, which leaves tag 0b111 to the big
+        /// integers of `--bigint`
+        pub fn mask(self) -> i64 {
+            match self {
+                Type::Int => 0b01,
+                Type::Bool => 0b11,
+                Type::Array => 0b111,
+            }
+        }
+        /// The type written `name` in a signature
+        pub fn named(name: &str) -> Option<Self> {
+            [Type::Int, Type::Bool, Type::Array].into_iter().find(|ty| ty.to_string() == name)
+        }
+        pub fn mask_length(self) -> u8 {
+            match self {
+                Type::Int => 1,
+                Type::Bool | Type::Array => 2,
+            }
+        }
+    }
+
+    impl fmt::Debug for Type {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Int => write!(f, "Integer"),
+                Self::Bool => write!(f, "Boolean"),
+                Self::Array => write!(f, "Array"),
+            }
+        }
+    }
+
+    impl Contract {
+        /// The annotations with their values, the first of which makes the promise
+        pub const ANNOTATIONS: [(&'static str, [&'static str; 2]); 3] = [
+            ("pure", ["true", "false"]),
+            ("ret", ["always", "any"]),
+            ("throws", ["false", "true"]),
+        ];
+
+        /// The values of the annotation `key`, if there is one
+        pub fn values(key: &str) -> Option<[&'static str; 2]> {
+            Self::ANNOTATIONS.iter().find(|(k, _)| *k == key).map(|(_, values)| *values)
+        }
+
+        /// Annotates `key(value)`, where `value` is among the `values` of `key`
+        pub fn annotate(&mut self, key: &str, value: &str) {
+            let promise = Self::values(key).is_some_and(|values| values[0] == value);
+            match key {
+                "pure" => self.pure = promise,
+                "ret" => self.returns = promise,
+                _ => self.nothrow = promise,
+            }
+        }
+
+        fn promises(&self) -> [bool; 3] {
+            [self.pure, self.returns, self.nothrow]
+        }
+    }
+
+    impl Signature {
+        /// Whether the calls need marshalling
+        pub fn is_typed(&self) -> bool {
+            self.ret.is_some() || self.params.iter().any(Option::is_some)
+        }
+
+        /// The signature with `n` more untyped parameters in front, as lifting
+        /// a function adds them
+        pub fn shifted(mut self, n: usize) -> Self {
+            self.params.splice(0..0, std::iter::repeat_n(None, n));
+            self
+        }
+
+        /// The `i`th parameter `param`, with its type if it has one
+        pub fn param(&self, i: usize, param: impl fmt::Display) -> String {
+            match self.params.get(i).copied().flatten() {
+                Some(ty) => format!("{}: {}", param, ty),
+                None => param.to_string(),
+            }
+        }
+    }
+
+    /// The result type after an arrow, if there is one
+    impl fmt::Display for Signature {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.ret {
+                Some(ty) => write!(f, " -> {}", ty),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// The annotations that make promises, each after a space
+    impl fmt::Display for Contract {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            for ((key, values), promise) in Self::ANNOTATIONS.iter().zip(self.promises()) {
+                if promise {
+                    write!(f, " {}({})", key, values[0])?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl fmt::Display for Type {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Int => write!(f, "Int"),
+                Self::Bool => write!(f, "Bool"),
+                Self::Array => write!(f, "Array"),
+            }
+        }
+    }
+}
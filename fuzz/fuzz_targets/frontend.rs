@@ -0,0 +1,9 @@
+//! Parses and resolves arbitrary bytes.
+//!
+//!     cargo +nightly fuzz run frontend -- \
+//!         -artifact_prefix=../tests/parser_regressions/frontend-
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| snake_core::fuzz::frontend(data));
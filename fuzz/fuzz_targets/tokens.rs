@@ -0,0 +1,9 @@
+//! Parses and resolves streams of the tokens of the grammar.
+//!
+//!     cargo +nightly fuzz run tokens -- \
+//!         -artifact_prefix=../tests/parser_regressions/tokens-
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| snake_core::fuzz::tokens(data));
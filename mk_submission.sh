@@ -1,4 +1,4 @@
 #!/usr/bin/env bash
 
-zip -r submission.zip src/middle_end.rs src/backend.rs examples/ tests/examples.rs
+zip -r submission.zip core/src/middle_end.rs core/src/backend.rs examples/ tests/examples.rs
 
@@ -25,8 +25,32 @@ pub const FULL_MASK: u64 = 0b11;
 pub const INT_TAG: u64 = 0b00;
 pub const BOOL_TAG: u64 = 0b01;
 pub const ARRAY_TAG: u64 = 0b11;
+/// arrays are aligned to 8 bytes, which leaves tag 0b111 to big integers
+pub const BIG_MASK: u64 = 0b111;
+pub const BIG_TAG: u64 = 0b111;
 pub const SNAKE_TRU: SnakeValue = SnakeValue(0b101);
 pub const SNAKE_FLS: SnakeValue = SnakeValue(0b001);
+/// `none`, the big integer tag on a null pointer, which no big integer has
+pub const SNAKE_NONE: SnakeValue = SnakeValue(BIG_TAG);
+/// set in the header of string literals, which is the size of big integers
+pub const STR_FLAG: u64 = 1 << 63;
+
+/// The string a value points to, if it is a string literal
+pub fn load_snake_str(v: SnakeValue) -> Option<&'static str> {
+    if v.0 & BIG_MASK != BIG_TAG || v == SNAKE_NONE {
+        return None;
+    }
+    let p = (v.0 - BIG_TAG) as *const u64;
+    unsafe {
+        let header = *p;
+        if header & STR_FLAG == 0 {
+            return None;
+        }
+        let len = (header & !STR_FLAG) as usize;
+        let bytes = std::slice::from_raw_parts(p.add(1) as *const u8, len);
+        Some(std::str::from_utf8_unchecked(bytes))
+    }
+}
 
 /// reinterprets the bytes of an unsigned number to a signed number
 pub fn unsigned_to_signed(x: u64) -> i64 {
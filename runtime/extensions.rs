@@ -1,10 +1,12 @@
-use common::*;
+use crate::bignum::Big;
+use crate::common::*;
 use std::collections::HashSet;
 
 /* ----------------------------- Error Handling ----------------------------- */
 
 #[repr(u64)]
 #[allow(unused)]
+#[derive(Clone, Copy)]
 pub enum SnakeErr {
     ArithmeticOverflow = 0,
     ExpectedNum = 1,
@@ -16,6 +18,7 @@ pub enum SnakeErr {
 
 #[export_name = "\x01snake_error"]
 pub extern "C" fn snake_error(ecode: SnakeErr, v: SnakeValue) -> SnakeValue {
+    crate::trace_call("snake_error", || vec![(ecode as u64).to_string(), sprint_snake_val(v)]);
     match ecode {
         SnakeErr::ArithmeticOverflow => eprintln!("arithmetic operation overflowed"),
         SnakeErr::ExpectedNum => eprintln!("expected a number, got {}", sprint_snake_val(v)),
@@ -24,15 +27,41 @@ pub extern "C" fn snake_error(ecode: SnakeErr, v: SnakeValue) -> SnakeValue {
         SnakeErr::NegativeLength => eprintln!("length {} is negative", sprint_snake_val(v)),
         SnakeErr::IndexOutOfBounds => eprintln!("index {} out of bounds", sprint_snake_val(v)),
     }
+    let backtrace = crate::shadow_backtrace();
+    if !backtrace.is_empty() {
+        eprintln!("backtrace, most recent call first:");
+        for (i, name) in backtrace.iter().enumerate() {
+            eprintln!("    {}: {}", i, name);
+        }
+    }
     std::process::exit(1)
 }
 
+/* ------------------------------ Big Integers ------------------------------ */
+
+/* A big integer is stored like an array whose first element is its sign,
+ * followed by the 32-bit limbs of its magnitude, one per word.
+**/
+pub fn load_snake_big(v: SnakeValue) -> Big {
+    let arr = load_snake_array((v.0 - BIG_TAG) as *const u64);
+    let word = |i: usize| unsafe { (*arr.elts.add(i)).0 };
+    Big::new(word(0) != 0, (1..arr.size as usize).map(|i| word(i) as u32).collect())
+}
+
 /* ---------------------------- Print Snake Value --------------------------- */
 
 fn sprint_snake_val_loop(v: SnakeValue, buf: &mut String, mut parents: HashSet<*const u64>) {
-    if v.0 & INT_MASK == INT_TAG {
+    if v == SNAKE_NONE {
+        buf.push_str("none")
+    } else if v.0 & INT_MASK == INT_TAG {
         // it's a signed 63-bit integer
         buf.push_str(&format!("{}", unsigned_to_signed(v.0) >> 1))
+    } else if let Some(s) = load_snake_str(v) {
+        // string literal, printed as it is
+        buf.push_str(s)
+    } else if v.0 & BIG_MASK == BIG_TAG {
+        // big integer
+        buf.push_str(&format!("{}", load_snake_big(v)))
     } else if v.0 & FULL_MASK == ARRAY_TAG {
         // array
         let addr = (v.0 - ARRAY_TAG) as *const u64;
@@ -0,0 +1,701 @@
+;;; The runtime of freestanding snake executables for x86-64 Linux.
+;;;
+;;; Appended to the compiled code by `--freestanding`, it replaces the Rust
+;;; stub with system calls only: `_start` parses the arguments, calls `entry`,
+;;; prints the result and exits; arrays are allocated by bumping the program
+;;; break (brk), and values are printed through a buffered write(2).
+;;;
+;;; All routines follow the System V calling convention.
+
+        section .bss
+snake_rt_heap_ptr:
+        resq 1
+snake_rt_heap_end:
+        resq 1
+snake_rt_envp:
+        resq 1
+snake_rt_rng:
+        resq 1
+snake_rt_seeded:
+        resq 1
+snake_rt_ticks:
+        resq 1
+snake_rt_start:
+        resq 1
+snake_rt_out_fd:
+        resq 1
+snake_rt_out_len:
+        resq 1
+snake_rt_out_buf:
+        resb 4096
+
+        section .rodata
+snake_rt_str_true:
+        db "true"
+snake_rt_str_false:
+        db "false"
+snake_rt_str_loop:
+        db "<loop>"
+snake_rt_str_invalid:
+        db "<invalid snake value>"
+snake_rt_str_oom:
+        db "out of memory", 10
+snake_rt_str_bad_arg:
+        db "invalid basic snake value: "
+snake_rt_str_overflow:
+        db "arithmetic operation overflowed"
+snake_rt_str_num:
+        db "expected a number, got "
+snake_rt_str_bool:
+        db "expected a boolean, got "
+snake_rt_str_array:
+        db "expected an array, got "
+snake_rt_str_length:
+        db "length "
+snake_rt_str_negative:
+        db " is negative"
+snake_rt_str_index:
+        db "index "
+snake_rt_str_bounds:
+        db " out of bounds"
+snake_rt_str_seed:
+        db "SNAKE_SEED="
+snake_rt_str_empty:
+
+        section .text
+        global _start
+
+;;; The process entry point: rsp points to argc, followed by argv and envp.
+_start:
+        mov rbx, [rsp]
+        dec rbx
+        lea r12, [rsp + 16]
+        lea rax, [r12 + rbx * 8 + 8]
+        mov [rel snake_rt_envp], rax
+        call snake_rt_init_services
+        mov qword [rel snake_rt_out_fd], 1
+        ;; allocate and fill the array of arguments
+        mov rdi, rbx
+        call snake_new_array
+        mov r13, rax
+        xor r14, r14
+.args:
+        cmp r14, rbx
+        jae .run
+        mov rdi, [r12 + r14 * 8]
+        call snake_rt_parse_val
+        mov [r13 + 8 + r14 * 8], rax
+        inc r14
+        jmp .args
+.run:
+        lea rdi, [r13 + 3]
+        call entry
+        mov rdi, rax
+        xor esi, esi
+        call snake_rt_put_val
+        mov edi, 10
+        call snake_rt_put_char
+        call snake_rt_flush
+        xor edi, edi
+        jmp snake_rt_exit
+
+;;; exit(rdi), flushing nothing
+snake_rt_exit:
+        mov eax, 60
+        syscall
+
+;;; snake_new_array(len) -> pointer to the length, followed by len zeros
+snake_new_array:
+        mov rax, [rel snake_rt_heap_ptr]
+        test rax, rax
+        jnz .allocate
+        ;; the first allocation starts the heap at the initial break
+        push rdi
+        mov eax, 12
+        xor edi, edi
+        syscall
+        pop rdi
+        add rax, 7
+        and rax, -8
+        mov [rel snake_rt_heap_ptr], rax
+        mov [rel snake_rt_heap_end], rax
+.allocate:
+        lea rdx, [rax + rdi * 8 + 8]
+        cmp rdx, [rel snake_rt_heap_end]
+        jbe .fits
+        ;; grow the heap, beyond what is needed to save system calls;
+        ;; the kernel hands out zeroed pages
+        push rdi
+        push rdx
+        lea rdi, [rdx + 65536]
+        mov eax, 12
+        syscall
+        pop rdx
+        pop rdi
+        cmp rax, rdx
+        jb .out_of_memory
+        mov [rel snake_rt_heap_end], rax
+        mov rax, [rel snake_rt_heap_ptr]
+.fits:
+        mov [rel snake_rt_heap_ptr], rdx
+        mov [rax], rdi
+        ret
+.out_of_memory:
+        lea rsi, [rel snake_rt_str_oom]
+        mov edx, 14
+        jmp snake_rt_fail
+
+;;; print(val) -> val
+print:
+        push rbx
+        mov rbx, rdi
+        xor esi, esi
+        call snake_rt_put_val
+        mov edi, 10
+        call snake_rt_put_char
+        mov rax, rbx
+        pop rbx
+        ret
+
+;;; snake_getenv(name) -> the value of the environment variable named by the
+;;; array of bytes, as an array of bytes, or false if it is unset
+snake_getenv:
+        push rbx
+        push r12
+        push r13
+        push r14
+        lea rbx, [rdi - 3]
+        mov r12, [rbx]
+        ;; the name must be a non-empty array of bytes other than NUL and `=`
+        test r12, r12
+        jz .unset
+        xor ecx, ecx
+.check:
+        cmp rcx, r12
+        jae .search
+        mov rax, [rbx + 8 + rcx * 8]
+        test rax, 1
+        jnz .unset
+        sar rax, 1
+        jz .unset
+        cmp rax, 255
+        ja .unset
+        cmp rax, "="
+        je .unset
+        inc rcx
+        jmp .check
+.search:
+        ;; r13: the next entry of envp, r14: the current one, `NAME=value`
+        mov r13, [rel snake_rt_envp]
+.entry:
+        mov r14, [r13]
+        test r14, r14
+        jz .unset
+        add r13, 8
+        xor ecx, ecx
+.compare:
+        cmp rcx, r12
+        jae .equals
+        mov rax, [rbx + 8 + rcx * 8]
+        sar rax, 1
+        movzx edx, byte [r14 + rcx]
+        cmp rax, rdx
+        jne .entry
+        inc rcx
+        jmp .compare
+.equals:
+        cmp byte [r14 + rcx], "="
+        jne .entry
+        lea r14, [r14 + rcx + 1]
+        xor r12, r12
+.length:
+        cmp byte [r14 + r12], 0
+        je .copy
+        inc r12
+        jmp .length
+.copy:
+        mov rdi, r12
+        call snake_new_array
+        xor ecx, ecx
+.byte:
+        cmp rcx, r12
+        jae .found
+        movzx edx, byte [r14 + rcx]
+        shl rdx, 1
+        mov [rax + 8 + rcx * 8], rdx
+        inc rcx
+        jmp .byte
+.found:
+        add rax, 3
+        jmp .return
+.unset:
+        mov eax, 1
+.return:
+        pop r14
+        pop r13
+        pop r12
+        pop rbx
+        ret
+
+;;; snake_clock() -> the milliseconds since the start, or the number of
+;;; previous calls if seeded
+snake_clock:
+        cmp qword [rel snake_rt_seeded], 0
+        je .time
+        mov rax, [rel snake_rt_ticks]
+        lea rcx, [rax + 1]
+        mov [rel snake_rt_ticks], rcx
+        jmp .tag
+.time:
+        call snake_rt_millis
+        sub rax, [rel snake_rt_start]
+.tag:
+        shl rax, 1
+        ret
+
+;;; snake_random(n) -> a number in [0, n), or 0 if n is not positive, drawn
+;;; from a splitmix64 generator
+snake_random:
+        mov rax, [rel snake_rt_rng]
+        mov rcx, 0x9e3779b97f4a7c15
+        add rax, rcx
+        mov [rel snake_rt_rng], rax
+        mov rcx, rax
+        shr rcx, 30
+        xor rax, rcx
+        mov rcx, 0xbf58476d1ce4e5b9
+        imul rax, rcx
+        mov rcx, rax
+        shr rcx, 27
+        xor rax, rcx
+        mov rcx, 0x94d049bb133111eb
+        imul rax, rcx
+        mov rcx, rax
+        shr rcx, 31
+        xor rax, rcx
+        sar rdi, 1
+        test rdi, rdi
+        jle .zero
+        xor edx, edx
+        div rdi
+        lea rax, [rdx + rdx]
+        ret
+.zero:
+        xor eax, eax
+        ret
+
+;;; snake_error(ecode, val) reports the runtime error and exits with 1
+snake_error:
+        mov rbx, rdi
+        mov r12, rsi
+        call snake_rt_flush
+        mov qword [rel snake_rt_out_fd], 2
+        ;; rsi, rdx: the message before the value; r13, r14: the one after
+        lea r13, [rel snake_rt_str_empty]
+        xor r14, r14
+        cmp rbx, 0
+        je .overflow
+        cmp rbx, 1
+        je .num
+        cmp rbx, 2
+        je .bool
+        cmp rbx, 3
+        je .array
+        cmp rbx, 4
+        je .length
+        lea rsi, [rel snake_rt_str_index]
+        mov edx, 6
+        lea r13, [rel snake_rt_str_bounds]
+        mov r14, 14
+        jmp .report
+.overflow:
+        lea rsi, [rel snake_rt_str_overflow]
+        mov edx, 31
+        call snake_rt_put_str
+        jmp .done
+.num:
+        lea rsi, [rel snake_rt_str_num]
+        mov edx, 23
+        jmp .report
+.bool:
+        lea rsi, [rel snake_rt_str_bool]
+        mov edx, 24
+        jmp .report
+.array:
+        lea rsi, [rel snake_rt_str_array]
+        mov edx, 23
+        jmp .report
+.length:
+        lea rsi, [rel snake_rt_str_length]
+        mov edx, 7
+        lea r13, [rel snake_rt_str_negative]
+        mov r14, 12
+.report:
+        call snake_rt_put_str
+        mov rdi, r12
+        xor esi, esi
+        call snake_rt_put_val
+        mov rsi, r13
+        mov rdx, r14
+        call snake_rt_put_str
+.done:
+        mov edi, 10
+        call snake_rt_put_char
+        call snake_rt_flush
+        mov edi, 1
+        jmp snake_rt_exit
+
+;;; records the start time and seeds the generator by SNAKE_SEED, a decimal
+;;; number, or else by the time
+snake_rt_init_services:
+        push rbx
+        call snake_rt_millis
+        mov [rel snake_rt_start], rax
+        ;; clock_gettime(CLOCK_REALTIME), in nanoseconds
+        sub rsp, 16
+        mov eax, 228
+        xor edi, edi
+        mov rsi, rsp
+        syscall
+        mov rax, [rsp]
+        imul rax, rax, 1000000000
+        add rax, [rsp + 8]
+        add rsp, 16
+        mov [rel snake_rt_rng], rax
+        mov rbx, [rel snake_rt_envp]
+.entry:
+        mov rsi, [rbx]
+        test rsi, rsi
+        jz .done
+        add rbx, 8
+        lea rdi, [rel snake_rt_str_seed]
+        xor ecx, ecx
+.compare:
+        mov al, [rdi + rcx]
+        cmp al, [rsi + rcx]
+        jne .entry
+        inc rcx
+        cmp rcx, 11
+        jb .compare
+        ;; at least one digit, and no overflow
+        cmp byte [rsi + rcx], 0
+        je .done
+        xor eax, eax
+        mov r8d, 10
+.digit:
+        movzx r9d, byte [rsi + rcx]
+        test r9d, r9d
+        jz .seeded
+        sub r9d, "0"
+        cmp r9d, 9
+        ja .done
+        mul r8
+        jc .done
+        add rax, r9
+        jc .done
+        inc rcx
+        jmp .digit
+.seeded:
+        mov [rel snake_rt_rng], rax
+        mov qword [rel snake_rt_seeded], 1
+.done:
+        pop rbx
+        ret
+
+;;; the milliseconds of clock_gettime(CLOCK_MONOTONIC)
+snake_rt_millis:
+        sub rsp, 24
+        mov eax, 228
+        mov edi, 1
+        mov rsi, rsp
+        syscall
+        mov rax, [rsp + 8]
+        xor edx, edx
+        mov ecx, 1000000
+        div rcx
+        mov rcx, [rsp]
+        imul rcx, rcx, 1000
+        add rax, rcx
+        add rsp, 24
+        ret
+
+;;; writes the message at rsi of length rdx to stderr and exits with 1
+snake_rt_fail:
+        push rsi
+        push rdx
+        call snake_rt_flush
+        pop rdx
+        pop rsi
+        mov eax, 1
+        mov edi, 2
+        syscall
+        mov edi, 1
+        jmp snake_rt_exit
+
+;;; parses the C string at rdi as `true`, `false` or a 63-bit integer
+snake_rt_parse_val:
+        push rbx
+        mov rbx, rdi
+        mov eax, 5
+        cmp dword [rbx], "true"
+        jne .not_true
+        cmp byte [rbx + 4], 0
+        je .return
+.not_true:
+        mov eax, 1
+        cmp dword [rbx], "fals"
+        jne .integer
+        cmp word [rbx + 4], "e"
+        je .return
+.integer:
+        ;; rax: the value, rcx: the index, r8: whether it is negative
+        xor eax, eax
+        xor ecx, ecx
+        xor r8d, r8d
+        cmp byte [rbx], "-"
+        jne .digits
+        inc rcx
+        inc r8
+.digits:
+        ;; at least one digit
+        movzx edx, byte [rbx + rcx]
+        sub edx, "0"
+        cmp edx, 9
+        ja .invalid
+.digit:
+        imul rax, rax, 10
+        jo .invalid
+        add rax, rdx
+        jo .invalid
+        inc rcx
+        movzx edx, byte [rbx + rcx]
+        test edx, edx
+        jz .sign
+        sub edx, "0"
+        cmp edx, 9
+        ja .invalid
+        jmp .digit
+.sign:
+        test r8, r8
+        jz .tag
+        neg rax
+.tag:
+        ;; the integer must survive the tag shift
+        mov rdx, rax
+        sal rax, 1
+        mov rcx, rax
+        sar rcx, 1
+        cmp rcx, rdx
+        jne .invalid
+.return:
+        pop rbx
+        ret
+.invalid:
+        call snake_rt_flush
+        mov qword [rel snake_rt_out_fd], 2
+        lea rsi, [rel snake_rt_str_bad_arg]
+        mov edx, 27
+        call snake_rt_put_str
+.invalid_char:
+        movzx edi, byte [rbx]
+        test edi, edi
+        jz .invalid_done
+        call snake_rt_put_char
+        inc rbx
+        jmp .invalid_char
+.invalid_done:
+        mov edi, 10
+        call snake_rt_put_char
+        call snake_rt_flush
+        mov edi, 1
+        jmp snake_rt_exit
+
+;;; prints the snake value rdi, given the list rsi of the arrays that contain
+;;; it, as nodes of [array, next]
+snake_rt_put_val:
+        push rbx
+        push r12
+        push r13
+        push r14
+        push r15
+        mov rbx, rdi
+        mov r12, rsi
+        test rbx, 1
+        jnz .not_int
+        mov rdi, rbx
+        sar rdi, 1
+        call snake_rt_put_int
+        jmp .done
+.not_int:
+        ;; a string literal: tag 0b111 on a header with the top bit set,
+        ;; followed by the bytes
+        mov rax, rbx
+        and rax, 7
+        cmp rax, 7
+        jne .not_str
+        cmp rbx, 7
+        je .not_str
+        lea r13, [rbx - 7]
+        mov rdx, [r13]
+        test rdx, rdx
+        jns .not_str
+        shl rdx, 1
+        shr rdx, 1
+        lea rsi, [r13 + 8]
+        call snake_rt_put_str
+        jmp .done
+.not_str:
+        mov rax, rbx
+        and rax, 3
+        cmp rax, 3
+        jne .not_array
+        lea r13, [rbx - 3]
+        mov rax, r12
+.search:
+        test rax, rax
+        jz .fresh
+        cmp [rax], r13
+        je .loop
+        mov rax, [rax + 8]
+        jmp .search
+.loop:
+        lea rsi, [rel snake_rt_str_loop]
+        mov edx, 6
+        call snake_rt_put_str
+        jmp .done
+.fresh:
+        sub rsp, 16
+        mov [rsp], r13
+        mov [rsp + 8], r12
+        mov r12, rsp
+        mov edi, "["
+        call snake_rt_put_char
+        mov r14, [r13]
+        xor r15, r15
+.elements:
+        cmp r15, r14
+        jae .close
+        test r15, r15
+        jz .element
+        mov edi, ","
+        call snake_rt_put_char
+        mov edi, " "
+        call snake_rt_put_char
+.element:
+        mov rdi, [r13 + 8 + r15 * 8]
+        mov rsi, r12
+        call snake_rt_put_val
+        inc r15
+        jmp .elements
+.close:
+        mov edi, "]"
+        call snake_rt_put_char
+        add rsp, 16
+        jmp .done
+.not_array:
+        cmp rbx, 5
+        jne .not_true
+        lea rsi, [rel snake_rt_str_true]
+        mov edx, 4
+        call snake_rt_put_str
+        jmp .done
+.not_true:
+        cmp rbx, 1
+        jne .invalid
+        lea rsi, [rel snake_rt_str_false]
+        mov edx, 5
+        call snake_rt_put_str
+        jmp .done
+.invalid:
+        lea rsi, [rel snake_rt_str_invalid]
+        mov edx, 21
+        call snake_rt_put_str
+.done:
+        pop r15
+        pop r14
+        pop r13
+        pop r12
+        pop rbx
+        ret
+
+;;; prints the signed integer rdi in decimal
+snake_rt_put_int:
+        push rbx
+        sub rsp, 32
+        mov rax, rdi
+        test rax, rax
+        jns .positive
+        push rax
+        mov edi, "-"
+        call snake_rt_put_char
+        pop rax
+        neg rax
+.positive:
+        ;; the digits go backwards from the end of the buffer at rsp
+        lea rbx, [rsp + 32]
+        mov ecx, 10
+.digit:
+        xor edx, edx
+        div rcx
+        add edx, "0"
+        dec rbx
+        mov [rbx], dl
+        test rax, rax
+        jnz .digit
+        mov rsi, rbx
+        lea rdx, [rsp + 32]
+        sub rdx, rbx
+        call snake_rt_put_str
+        add rsp, 32
+        pop rbx
+        ret
+
+;;; prints rdx bytes from rsi
+snake_rt_put_str:
+        push rbx
+        push r12
+        push r13
+        mov rbx, rsi
+        mov r12, rdx
+        xor r13, r13
+.byte:
+        cmp r13, r12
+        jae .done
+        movzx edi, byte [rbx + r13]
+        call snake_rt_put_char
+        inc r13
+        jmp .byte
+.done:
+        pop r13
+        pop r12
+        pop rbx
+        ret
+
+;;; buffers the byte dil for the current output file
+snake_rt_put_char:
+        mov rax, [rel snake_rt_out_len]
+        cmp rax, 4096
+        jb .store
+        push rdi
+        call snake_rt_flush
+        pop rdi
+        xor eax, eax
+.store:
+        lea rcx, [rel snake_rt_out_buf]
+        mov [rcx + rax], dil
+        inc rax
+        mov [rel snake_rt_out_len], rax
+        ret
+
+;;; writes the buffered output to the current output file
+snake_rt_flush:
+        mov rdx, [rel snake_rt_out_len]
+        test rdx, rdx
+        jz .done
+        mov eax, 1
+        mov rdi, [rel snake_rt_out_fd]
+        lea rsi, [rel snake_rt_out_buf]
+        syscall
+        mov qword [rel snake_rt_out_len], 0
+.done:
+        ret
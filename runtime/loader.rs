@@ -0,0 +1,108 @@
+/* ---------------------------------- Loader --------------------------------- */
+
+/* With the hermetic feature of snake, the executable is this stub followed by
+ * the image of the compiled code, see core/src/encode.rs, then the length of
+ * the image and the magic number SNAKEIMG. The loader maps the image, fills
+ * in the addresses of the runtime functions it uses and runs it there.
+**/
+use crate::common::SnakeValue;
+
+extern "C" {
+    fn mmap(addr: *mut u8, len: usize, prot: i32, flags: i32, fd: i32, offset: i64) -> *mut u8;
+    fn mprotect(addr: *mut u8, len: usize, prot: i32) -> i32;
+}
+
+const PROT_READ: i32 = 1;
+const PROT_WRITE: i32 = 2;
+const PROT_EXEC: i32 = 4;
+const MAP_PRIVATE: i32 = 2;
+const MAP_ANONYMOUS: i32 = 0x20;
+
+/* The address of the runtime symbol `name` */
+fn symbol(name: &str) -> Option<*const ()> {
+    let addr = match name {
+        "print" => crate::print as *const (),
+        "big_fun_nine" => crate::big_fun_nine as *const (),
+        "isqrt" => crate::isqrt as *const (),
+        "is_square" => crate::is_square as *const (),
+        "sum_array" => crate::sum_array as *const (),
+        "snake_new_array" => crate::snake_new_array as *const (),
+        "snake_getenv" => crate::snake_getenv as *const (),
+        "snake_clock" => crate::snake_clock as *const (),
+        "snake_random" => crate::snake_random as *const (),
+        "snake_to_str" => crate::snake_to_str as *const (),
+        "snake_str_concat" => crate::snake_str_concat as *const (),
+        "snake_big_add" => crate::snake_big_add as *const (),
+        "snake_big_sub" => crate::snake_big_sub as *const (),
+        "snake_big_mul" => crate::snake_big_mul as *const (),
+        "snake_big_cmp" => crate::snake_big_cmp as *const (),
+        "snake_big_eq" => crate::snake_big_eq as *const (),
+        "snake_big_is_int" => crate::snake_big_is_int as *const (),
+        "snake_error" => crate::extensions::snake_error as *const (),
+        "snake_shadow_sp" => unsafe { &crate::SHADOW_SP as *const _ as *const () },
+        _ => return None,
+    };
+    Some(addr)
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1)
+}
+
+fn quad(bytes: &[u8], at: usize) -> usize {
+    let mut quad = [0; 8];
+    quad.copy_from_slice(&bytes[at..at + 8]);
+    u64::from_le_bytes(quad) as usize
+}
+
+fn load() -> extern "sysv64" fn(SnakeValue) -> SnakeValue {
+    let exe = std::env::current_exe()
+        .and_then(std::fs::read)
+        .unwrap_or_else(|e| fail(format!("cannot read the executable: {}", e)));
+    if exe.len() < 16 || &exe[exe.len() - 8..] != b"SNAKEIMG" {
+        fail("the executable holds no compiled code".to_string())
+    }
+    let end = exe.len() - 16;
+    let image = &exe[end - quad(&exe, end)..end];
+    let (len, entry) = (quad(image, 0), quad(image, 8));
+    let (imports, relocations) = (quad(image, 16), quad(image, 24));
+    let mut code = image[32..32 + len].to_vec();
+    let mut at = 32 + len;
+    for _ in 0..imports {
+        let (slot, name_len) = (quad(image, at), quad(image, at + 8));
+        let name = String::from_utf8_lossy(&image[at + 16..at + 16 + name_len]);
+        let addr =
+            symbol(&name).unwrap_or_else(|| fail(format!("undefined reference to `{}`", name)));
+        code[slot..slot + 8].copy_from_slice(&(addr as u64).to_le_bytes());
+        at += 16 + name_len;
+    }
+    unsafe {
+        let prot = PROT_READ | PROT_WRITE;
+        let base = mmap(
+            std::ptr::null_mut(),
+            len.max(1),
+            prot,
+            MAP_PRIVATE | MAP_ANONYMOUS,
+            -1,
+            0,
+        );
+        if base as isize == -1 {
+            fail("cannot map the compiled code".to_string())
+        }
+        for i in 0..relocations {
+            let offset = quad(image, at + 8 * i);
+            let addr = base as u64 + quad(&code, offset) as u64;
+            code[offset..offset + 8].copy_from_slice(&addr.to_le_bytes());
+        }
+        std::ptr::copy_nonoverlapping(code.as_ptr(), base, len);
+        if mprotect(base, len.max(1), PROT_READ | PROT_EXEC) != 0 {
+            fail("cannot map the compiled code".to_string())
+        }
+        std::mem::transmute(base.add(entry))
+    }
+}
+
+pub unsafe fn entry(param: SnakeValue) -> SnakeValue {
+    load()(param)
+}
@@ -1,18 +1,81 @@
 #![allow(static_mut_refs)]
 
+#[path = "../core/src/bignum.rs"]
+mod bignum;
 mod common;
 mod extensions;
+use bignum::Big;
 use common::*;
-use extensions::sprint_snake_val;
+use extensions::{load_snake_big, snake_error, sprint_snake_val, SnakeErr};
 
 static HEAP_SIZE: u64 = 100000;
 static mut HEAP_START: [u64; 100000] = [0; 100000];
 static mut HEAP_PTR: *mut u64 = unsafe { HEAP_START.as_mut_ptr() };
 
+/* --------------------------------- Tracing -------------------------------- */
+
+/* SNAKE_TRACE=calls,allocs logs the entries of the runtime on stderr: with
+ * `calls`, the calls of the externs and the errors raised, and with `allocs`,
+ * the arrays allocated. The compiled code keeps no source locations, so a
+ * call shows its arguments only.
+**/
+static mut TRACE_CALLS: bool = false;
+static mut TRACE_ALLOCS: bool = false;
+
+fn init_trace() {
+    let trace = std::env::var("SNAKE_TRACE").unwrap_or_default();
+    for kind in trace.split(',').map(str::trim).filter(|kind| !kind.is_empty()) {
+        match kind {
+            "calls" => unsafe { TRACE_CALLS = true },
+            "allocs" => unsafe { TRACE_ALLOCS = true },
+            _ => eprintln!("[trace] unknown kind {}, expected calls or allocs", kind),
+        }
+    }
+}
+
+/* Logs the call of `name` under SNAKE_TRACE=calls, only showing its arguments
+ * if traced.
+**/
+pub fn trace_call(name: &str, args: impl FnOnce() -> Vec<String>) {
+    if unsafe { TRACE_CALLS } {
+        eprintln!("[trace] call {}({})", name, args().join(", "));
+    }
+}
+
+/* ------------------------------ Shadow Stack ------------------------------ */
+
+/* Code compiled with -g pushes the address of its name, a C string, on entry
+ * to a function and pops it on return; `snake_shadow_sp` points to the top
+ * entry. The first entry stays empty, so that the stack is empty when the two
+ * coincide. Every call also pushes its return address on the machine stack,
+ * so the 8 MiB one overflows before the shadow stack does.
+**/
+const SHADOW_DEPTH: usize = 1 << 20;
+static mut SHADOW_STACK: [*const std::os::raw::c_char; SHADOW_DEPTH] =
+    [std::ptr::null(); SHADOW_DEPTH];
+#[export_name = "\x01snake_shadow_sp"]
+pub static mut SHADOW_SP: *mut *const std::os::raw::c_char =
+    unsafe { SHADOW_STACK.as_mut_ptr() };
+
+/* The functions on the shadow stack, most recent first.
+**/
+pub fn shadow_backtrace() -> Vec<String> {
+    let mut names = Vec::new();
+    unsafe {
+        let mut entry = SHADOW_SP;
+        while entry > SHADOW_STACK.as_mut_ptr() {
+            names.push(std::ffi::CStr::from_ptr(*entry).to_string_lossy().into_owned());
+            entry = entry.sub(1);
+        }
+    }
+    names
+}
+
 /* --------------------------- External Functions --------------------------- */
 
 #[export_name = "\x01print"]
 extern "sysv64" fn print(val: SnakeValue) -> SnakeValue {
+    trace_call("print", || vec![sprint_snake_val(val)]);
     println!("{}", sprint_snake_val(val));
     val
 }
@@ -29,6 +92,9 @@ extern "sysv64" fn big_fun_nine(
     x8: SnakeValue,
     x9: SnakeValue,
 ) -> SnakeValue {
+    trace_call("big_fun_nine", || {
+        [x1, x2, x3, x4, x5, x6, x7, x8, x9].iter().map(|x| sprint_snake_val(*x)).collect()
+    });
     println!(
         "x1: {}\nx2: {}\nx3: {}\nx4: {}\nx5: {}\nx6: {}\nx7: {}\nx8: {}\nx9: {}",
         sprint_snake_val(x1),
@@ -54,6 +120,30 @@ extern "sysv64" fn big_fun_nine(
     ))
 }
 
+/* The externs of examples/typed_extern.dbk, whose signature has them take
+ * and return plain machine values instead of tagged words.
+**/
+#[export_name = "\x01isqrt"]
+extern "sysv64" fn isqrt(n: i64) -> i64 {
+    trace_call("isqrt", || vec![n.to_string()]);
+    (n.max(0) as f64).sqrt() as i64
+}
+
+#[export_name = "\x01is_square"]
+extern "sysv64" fn is_square(n: i64) -> i64 {
+    trace_call("is_square", || vec![n.to_string()]);
+    let root = isqrt(n);
+    (root * root == n) as i64
+}
+
+#[export_name = "\x01sum_array"]
+extern "sysv64" fn sum_array(arr: *const u64, scale: i64) -> i64 {
+    trace_call("sum_array", || vec![format!("{:p}", arr), scale.to_string()]);
+    let arr = load_snake_array(arr);
+    let elts = (0..arr.size as usize).map(|i| unsafe { *arr.elts.add(i) });
+    elts.map(|v| unsigned_to_signed(v.0) >> 1).sum::<i64>() * scale
+}
+
 /* ----------------------------- Array Allocator ---------------------------- */
 
 /* Implement the following function for array allocation.
@@ -64,7 +154,9 @@ extern "sysv64" fn snake_new_array(size: u64) -> *mut u64 {
     let arr_ptr = unsafe { HEAP_PTR as u64 };
     unsafe {
         *HEAP_PTR = size;
-        if arr_ptr + 8 * (size + 1) >= (HEAP_START.as_ptr() as u64) + 8 * HEAP_SIZE {
+        let heap_end = (HEAP_START.as_ptr() as u64) + 8 * HEAP_SIZE;
+        let bytes = size.checked_add(1).and_then(|words| words.checked_mul(8));
+        if bytes.map_or(true, |bytes| bytes >= heap_end - arr_ptr) {
             eprintln!("out of memory");
             std::process::exit(1);
         }
@@ -72,11 +164,231 @@ extern "sysv64" fn snake_new_array(size: u64) -> *mut u64 {
             HEAP_PTR = HEAP_PTR.add(1);
             *HEAP_PTR = 0;
         }
-        HEAP_PTR = HEAP_PTR.add(1)
+        HEAP_PTR = HEAP_PTR.add(1);
+        if TRACE_ALLOCS {
+            let word = (arr_ptr - HEAP_START.as_ptr() as u64) / 8;
+            eprintln!("[trace] alloc {} elements at word {} of the heap", size, word);
+        }
     }
     arr_ptr as *mut u64
 }
 
+/* ------------------------------- Environment ------------------------------ */
+
+/* Looks up the environment variable named by the string `name`, an array of
+ * bytes: its value as an array of bytes, or false if it is unset or `name`
+ * holds anything but bytes.
+**/
+#[export_name = "\x01snake_getenv"]
+extern "sysv64" fn snake_getenv(name: SnakeValue) -> SnakeValue {
+    trace_call("snake_getenv", || vec![sprint_snake_val(name)]);
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+    let arr = load_snake_array((name.0 - ARRAY_TAG) as *const u64);
+    let name: Option<Vec<u8>> = (0..arr.size as usize)
+        .map(|i| {
+            let v = unsigned_to_signed(unsafe { (*arr.elts.add(i)).0 });
+            (v as u64 & INT_MASK == INT_TAG && (0..=255).contains(&(v >> 1))).then(|| (v >> 1) as u8)
+        })
+        .collect();
+    let Some(val) = name.and_then(|name| std::env::var_os(std::ffi::OsStr::from_bytes(&name)))
+    else {
+        return SNAKE_FLS;
+    };
+    let bytes = val.into_vec();
+    let ptr = snake_new_array(bytes.len() as u64);
+    let arr = load_snake_array(ptr);
+    for (i, b) in bytes.into_iter().enumerate() {
+        unsafe { *arr.elts.add(i) = SnakeValue((b as u64) << 1) }
+    }
+    SnakeValue(ptr as u64 | ARRAY_TAG)
+}
+
+/* ---------------------------- Time and Random ---------------------------- */
+
+/* Seeding by SNAKE_SEED makes both services deterministic: `random` draws
+ * from a splitmix64 generator started at the seed, and `clock` counts its
+ * calls instead of reading the time.
+**/
+static mut RNG: u64 = 0;
+static mut SEEDED: bool = false;
+static mut TICKS: u64 = 0;
+static mut START: Option<std::time::Instant> = None;
+
+fn init_services() {
+    let seed = std::env::var("SNAKE_SEED").ok().and_then(|s| s.parse::<u64>().ok());
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+    unsafe {
+        SEEDED = seed.is_some();
+        RNG = seed.unwrap_or_else(|| now.map_or(0, |d| d.as_nanos() as u64));
+        START = Some(std::time::Instant::now());
+    }
+}
+
+/* The milliseconds since the start, or the number of previous calls if seeded */
+#[export_name = "\x01snake_clock"]
+extern "sysv64" fn snake_clock() -> SnakeValue {
+    trace_call("snake_clock", Vec::new);
+    let ms = unsafe {
+        if SEEDED {
+            TICKS += 1;
+            TICKS - 1
+        } else {
+            START.map_or(0, |start| start.elapsed().as_millis() as u64)
+        }
+    };
+    SnakeValue(ms << 1)
+}
+
+/* A number in [0, n), or 0 if n is not positive */
+#[export_name = "\x01snake_random"]
+extern "sysv64" fn snake_random(n: SnakeValue) -> SnakeValue {
+    trace_call("snake_random", || vec![sprint_snake_val(n)]);
+    let n = unsigned_to_signed(n.0) >> 1;
+    let mut z = unsafe {
+        RNG = RNG.wrapping_add(0x9e3779b97f4a7c15);
+        RNG
+    };
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^= z >> 31;
+    if n <= 0 {
+        SnakeValue(0)
+    } else {
+        SnakeValue((z % n as u64) << 1)
+    }
+}
+
+/* ------------------------------ Big Integers ------------------------------ */
+
+/* The arithmetic of --bigint: the operands are small or big integers, and
+ * results that fit in 63 bits are small again.
+**/
+/* Whether v is a big integer rather than none or a string, of the same tag */
+fn is_big(v: SnakeValue) -> bool {
+    v.0 & BIG_MASK == BIG_TAG && v != SNAKE_NONE && load_snake_str(v).is_none()
+}
+
+fn to_big(v: SnakeValue) -> Big {
+    if v.0 & INT_MASK == INT_TAG {
+        Big::from_i64(unsigned_to_signed(v.0) >> 1)
+    } else if is_big(v) {
+        load_snake_big(v)
+    } else {
+        snake_error(SnakeErr::ExpectedNum, v);
+        unreachable!()
+    }
+}
+
+fn from_big(n: Big) -> SnakeValue {
+    if let Some(n) = n.to_small() {
+        return SnakeValue(signed_to_unsigned(n << 1));
+    }
+    let ptr = snake_new_array(n.mag.len() as u64 + 1);
+    let arr = load_snake_array(ptr);
+    unsafe {
+        *arr.elts = SnakeValue(n.neg as u64);
+        for (i, limb) in n.mag.iter().enumerate() {
+            *arr.elts.add(i + 1) = SnakeValue(*limb as u64);
+        }
+    }
+    SnakeValue(ptr as u64 | BIG_TAG)
+}
+
+#[export_name = "\x01snake_big_add"]
+extern "sysv64" fn snake_big_add(a: SnakeValue, b: SnakeValue) -> SnakeValue {
+    trace_call("snake_big_add", || vec![sprint_snake_val(a), sprint_snake_val(b)]);
+    from_big(to_big(a).add(&to_big(b)))
+}
+
+#[export_name = "\x01snake_big_sub"]
+extern "sysv64" fn snake_big_sub(a: SnakeValue, b: SnakeValue) -> SnakeValue {
+    trace_call("snake_big_sub", || vec![sprint_snake_val(a), sprint_snake_val(b)]);
+    from_big(to_big(a).sub(&to_big(b)))
+}
+
+#[export_name = "\x01snake_big_mul"]
+extern "sysv64" fn snake_big_mul(a: SnakeValue, b: SnakeValue) -> SnakeValue {
+    trace_call("snake_big_mul", || vec![sprint_snake_val(a), sprint_snake_val(b)]);
+    from_big(to_big(a).mul(&to_big(b)))
+}
+
+/* -1, 0 or 1 as a is less than, equal to or greater than b */
+#[export_name = "\x01snake_big_cmp"]
+extern "sysv64" fn snake_big_cmp(a: SnakeValue, b: SnakeValue) -> SnakeValue {
+    trace_call("snake_big_cmp", || vec![sprint_snake_val(a), sprint_snake_val(b)]);
+    SnakeValue(signed_to_unsigned((to_big(a).cmp(&to_big(b)) as i64) << 1))
+}
+
+/* Equal values are either both small or both big; equal strings are the
+ * same literal */
+#[export_name = "\x01snake_big_eq"]
+extern "sysv64" fn snake_big_eq(a: SnakeValue, b: SnakeValue) -> SnakeValue {
+    trace_call("snake_big_eq", || vec![sprint_snake_val(a), sprint_snake_val(b)]);
+    let eq = a == b || (is_big(a) && is_big(b) && load_snake_big(a) == load_snake_big(b));
+    if eq {
+        SNAKE_TRU
+    } else {
+        SNAKE_FLS
+    }
+}
+
+/* Whether v is a small or a big integer */
+#[export_name = "\x01snake_big_is_int"]
+extern "sysv64" fn snake_big_is_int(v: SnakeValue) -> SnakeValue {
+    trace_call("snake_big_is_int", || vec![sprint_snake_val(v)]);
+    if v.0 & INT_MASK == INT_TAG || is_big(v) {
+        SNAKE_TRU
+    } else {
+        SNAKE_FLS
+    }
+}
+
+/* --------------------------------- Strings -------------------------------- */
+
+/* The strings of the program by their contents. The first string made of some
+ * contents stands for every later one, so that equal strings are equal words
+ * like the literals, which go through `snake_to_str` once the program makes
+ * strings.
+**/
+static mut STRINGS: Option<std::collections::HashMap<&'static str, SnakeValue>> = None;
+
+/* The string of the contents `s`: the first one made, else `lit`, a string of
+ * them already, else a new one on the heap.
+**/
+fn intern(s: &str, lit: Option<SnakeValue>) -> SnakeValue {
+    let strings = unsafe { STRINGS.get_or_insert_with(Default::default) };
+    if let Some(v) = strings.get(s) {
+        return *v;
+    }
+    let v = lit.unwrap_or_else(|| {
+        let ptr = snake_new_array((s.len() as u64).div_ceil(8));
+        unsafe {
+            *ptr = STR_FLAG | s.len() as u64;
+            std::ptr::copy_nonoverlapping(s.as_ptr(), ptr.add(1) as *mut u8, s.len());
+        }
+        SnakeValue(ptr as u64 | BIG_TAG)
+    });
+    strings.insert(load_snake_str(v).unwrap(), v);
+    v
+}
+
+/* The string that print shows of v, which is v itself if a string */
+#[export_name = "\x01snake_to_str"]
+extern "sysv64" fn snake_to_str(v: SnakeValue) -> SnakeValue {
+    trace_call("snake_to_str", || vec![sprint_snake_val(v)]);
+    match load_snake_str(v) {
+        Some(s) => intern(s, Some(v)),
+        None => intern(&sprint_snake_val(v), None),
+    }
+}
+
+/* The strings that print shows of a and b, one after the other */
+#[export_name = "\x01snake_str_concat"]
+extern "sysv64" fn snake_str_concat(a: SnakeValue, b: SnakeValue) -> SnakeValue {
+    trace_call("snake_str_concat", || vec![sprint_snake_val(a), sprint_snake_val(b)]);
+    intern(&(sprint_snake_val(a) + &sprint_snake_val(b)), None)
+}
+
 /* ---------------------------- Parse Snake Value --------------------------- */
 
 fn parse_snake_basic_val(s: &str) -> SnakeValue {
@@ -85,8 +397,8 @@ fn parse_snake_basic_val(s: &str) -> SnakeValue {
         SNAKE_TRU
     } else if s == "false" {
         SNAKE_FLS
-    } else if let Ok(x) = s.parse::<i64>() {
-        SnakeValue(signed_to_unsigned(x << 1))
+    } else if let Some(x) = Big::parse(s) {
+        from_big(x)
     } else {
         panic!("invalid basic snake value: {}", s)
     }
@@ -94,12 +406,21 @@ fn parse_snake_basic_val(s: &str) -> SnakeValue {
 
 /* ------------------------------- Entry Point ------------------------------ */
 
+#[cfg(not(feature = "hermetic"))]
 #[link(name = "compiled_code", kind = "static")]
 extern "sysv64" {
     #[link_name = "\x01entry"]
     fn entry(param: SnakeValue) -> SnakeValue;
 }
 
+/* Built by cargo with the hermetic feature, the stub instead loads the code
+ * that snake appended to it.
+**/
+#[cfg(feature = "hermetic")]
+mod loader;
+#[cfg(feature = "hermetic")]
+use loader::entry;
+
 fn main() {
     unsafe {
         if HEAP_START.as_ptr() as u64 & FULL_MASK != 0 {
@@ -107,6 +428,8 @@ fn main() {
             std::process::exit(1);
         }
     }
+    init_trace();
+    init_services();
     let args: Vec<String> = std::env::args().skip(1).collect();
     let snake_args: Vec<SnakeValue> = args.iter().map(|s| parse_snake_basic_val(s)).collect();
     let snake_arg_array_ptr = snake_new_array(snake_args.len() as u64);
@@ -1,6 +1,6 @@
-use crate::asm::Reg;
+use crate::dump::Format;
 use clap::ValueEnum;
-use std::collections::HashSet;
+pub use snake_core::conf::*;
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum Target {
@@ -8,8 +8,17 @@ pub enum Target {
     AST,
     /// Resolved AST
     ResolvedAST,
+    /// Resolved AST, followed by the variables each scope binds and those they shadow
+    Scopes,
+    /// Types of the functions, inferred as by --types infer, each after its span
+    Types,
     /// SSA
     SSA,
+    /// Induction variables of the loops of the optimized SSA
+    #[value(name = "indvars")]
+    IndVars,
+    /// Register pressure of the functions, and which of them are leaves
+    Pressure,
     /// Interference Graph
     Graph,
     /// Elimination Order
@@ -20,176 +29,143 @@ pub enum Target {
     Asm,
     /// Binary executable
     Exe,
+    /// x86_64 Assembly Code of a bare expression, as the function `entry` of --params
+    ExprAsm,
 }
 pub use Target::*;
 
-pub struct CompilerConf {
-    pub optimizations: HashSet<Optimization>,
-    pub verbose: Verbosity,
-}
+impl Target {
+    /// File extension used when the target is written to a path derived from `-o`.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AST => "ast",
+            ResolvedAST => "resolved.ast",
+            Scopes => "scopes",
+            Types => "types",
+            SSA => "ssa",
+            IndVars => "indvars",
+            Pressure => "pressure",
+            Graph => "graph",
+            ElimOrder => "elim",
+            Coloring => "coloring",
+            Asm => "asm",
+            Exe => "exe",
+            ExprAsm => "asm",
+        }
+    }
 
-impl CompilerConf {
-    pub fn new(optimizations: impl IntoIterator<Item = Optimization>, verbose: Verbosity) -> Self {
-        Self { optimizations: optimizations.into_iter().collect(), verbose }
+    /// The format of the dump, whose header the target starts with
+    pub fn format(&self) -> Option<Format> {
+        match self {
+            AST => Some(Format::Ast),
+            ResolvedAST => Some(Format::ResolvedAst),
+            SSA => Some(Format::Ssa),
+            Graph => Some(Format::Graph),
+            ElimOrder => Some(Format::ElimOrder),
+            Coloring => Some(Format::Coloring),
+            Scopes | Types | IndVars | Pressure | Asm | Exe | ExprAsm => None,
+        }
     }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-pub enum Optimization {
-    /// Copy Propagation - replace `x` with `y` if an instruction `x = y` exists
-    #[value(name = "cp")]
-    CopyPropagation,
-    /// Assertion Removal - removes integer type assertions
-    #[value(name = "ar")]
-    AssertionRemoval,
-    /// Dead Code Elimination - remove unused variables and parameters
-    #[value(name = "dce")]
-    DeadCodeElimination,
-    /// Variable Lifetime Splitting - variable lifetime splitting
-    #[value(name = "vls")]
-    VariableLifetimeSplitting,
+pub enum Annotation {
+    /// Live-in and live-out sets of each instruction
+    Liveness,
+    /// Versions of the memory that each load, store, call and allocation reads or defines
+    Memory,
 }
-impl Optimization {
-    pub fn all() -> HashSet<Optimization> {
-        [
-            Optimization::AssertionRemoval,
-            Optimization::CopyPropagation,
-            Optimization::DeadCodeElimination,
-            Optimization::VariableLifetimeSplitting,
-        ]
-        .into()
-    }
+
+/// The warnings that `-W` turns on
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, ValueEnum)]
+pub enum Warning {
+    /// A variable bound where a variable of the same name is in scope
+    Shadow,
 }
 
-#[derive(Debug, Clone)]
-pub struct OptimizationCollection {
-    optimizations: HashSet<Optimization>,
+/// The platforms that the compiled code can be assembled and linked for.
+/// All of them are x86-64; they differ in object format and runtime.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, ValueEnum)]
+pub enum TargetTriple {
+    #[value(name = "x86_64-unknown-linux-gnu")]
+    LinuxGnu,
+    #[value(name = "x86_64-unknown-linux-musl")]
+    LinuxMusl,
+    #[value(name = "x86_64-apple-darwin")]
+    Darwin,
 }
-impl std::str::FromStr for OptimizationCollection {
-    type Err = String;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // split on commas
-        if s == "all" {
-            return Ok(OptimizationCollection { optimizations: Optimization::all() });
+impl TargetTriple {
+    /// The platform the compiler runs on, if it is a supported target
+    pub fn host() -> Option<Self> {
+        if cfg!(all(target_arch = "x86_64", target_os = "linux", target_env = "musl")) {
+            Some(TargetTriple::LinuxMusl)
+        } else if cfg!(all(target_arch = "x86_64", target_os = "linux")) {
+            Some(TargetTriple::LinuxGnu)
+        } else if cfg!(all(target_arch = "x86_64", target_os = "macos")) {
+            Some(TargetTriple::Darwin)
+        } else {
+            None
         }
-        let optimizations: Vec<&str> = s.split(',').collect();
-        let optimizations = optimizations
-            .into_iter()
-            .map(|o| {
-                Optimization::from_str(o, true).map_err(|e| format!("Invalid optimization: {}", e))
-            })
-            .collect::<Result<_, _>>()?;
-        Ok(OptimizationCollection { optimizations })
     }
-}
-impl IntoIterator for OptimizationCollection {
-    type Item = Optimization;
-    type IntoIter = std::collections::hash_set::IntoIter<Optimization>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.optimizations.into_iter()
+    pub fn triple(&self) -> &'static str {
+        match self {
+            TargetTriple::LinuxGnu => "x86_64-unknown-linux-gnu",
+            TargetTriple::LinuxMusl => "x86_64-unknown-linux-musl",
+            TargetTriple::Darwin => "x86_64-apple-darwin",
+        }
     }
-}
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub enum Verbosity {
-    /// Only print the final output.
-    ///
-    /// Provides a canonical output for autograder.
-    Minimalistic,
-    /// Print the final output and crucial intermediate steps.
-    ///
-    /// Suitable for learning and debugging.
-    Moderate,
-    /// Print the final output and all intermediate steps.
-    ///
-    /// Suitable for learning and debugging.
-    Mouthful,
-}
+    /// The object format passed to nasm
+    pub fn nasm_format(&self) -> &'static str {
+        match self {
+            TargetTriple::LinuxGnu | TargetTriple::LinuxMusl => "elf64",
+            TargetTriple::Darwin => "macho64",
+        }
+    }
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-pub enum RegisterCollection {
-    #[value(name = "all")]
-    AllocatableAll,
-    #[value(name = "volatile", alias = "caller")]
-    AllocatableVolatile,
-    #[value(name = "non-volatile", alias = "callee")]
-    AllocatableNonVolatile,
-    #[value(name = "none")]
-    None,
+    pub fn is_linux(&self) -> bool {
+        matches!(self, TargetTriple::LinuxGnu | TargetTriple::LinuxMusl)
+    }
+
+    /// Whether executables for this target run directly on `host`
+    pub fn runs_on(&self, host: TargetTriple) -> bool {
+        *self == host || (self.is_linux() && host.is_linux())
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct RegisterSelection {
-    base: RegisterCollection,
-    additions: Vec<Reg>,
-    removals: Vec<Reg>,
+impl std::fmt::Display for TargetTriple {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.triple())
+    }
 }
 
-impl RegisterSelection {
-    pub fn to_registers(&self) -> Vec<Reg> {
-        // Start with the base collection
-        let mut regs: Vec<Reg> = match self.base {
-            RegisterCollection::AllocatableAll => Reg::ALLOCATABLE.to_vec(),
-            RegisterCollection::AllocatableVolatile => Reg::ALLOCATABLE_VOLATILE.to_vec(),
-            RegisterCollection::AllocatableNonVolatile => Reg::ALLOCATABLE_NON_VOLATILE.to_vec(),
-            RegisterCollection::None => vec![],
-        };
-
-        // Add any registers in the additions list
-        for reg in &self.additions {
-            if !regs.contains(reg) {
-                regs.push(*reg);
-            }
-        }
+/// The `K/N` part of test files that a CI job runs: the K-th of N shards,
+/// counting from 1, deals out the files round-robin in the order they are found
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Shard {
+    pub index: usize,
+    pub count: usize,
+}
 
-        // Remove any registers in the removals list
-        for reg in &self.removals {
-            if let Some(i) = regs.iter().position(|r| r == reg) {
-                regs.remove(i);
-            }
-        }
-        regs
+impl Shard {
+    /// The items of `items` that belong to this shard
+    pub fn select<T>(&self, items: Vec<T>) -> Vec<T> {
+        items.into_iter().skip(self.index - 1).step_by(self.count).collect()
     }
 }
 
-impl std::str::FromStr for RegisterSelection {
+impl std::str::FromStr for Shard {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use regex::Regex;
-
-        let re = Regex::new(r"([^\+-]+)|([\+-][^\+-]+)").unwrap();
-        // Split on + or - but keep the delimiters
-        let parts: Vec<&str> = re.find_iter(s).map(|m| m.as_str()).collect();
-        if parts.is_empty() {
-            return Err("Empty register selection".to_string());
+        let (index, count) = s.split_once('/').ok_or(format!("Expected K/N, found {}", s))?;
+        let index: usize = index.parse().map_err(|_| format!("Invalid shard index: {}", index))?;
+        let count: usize = count.parse().map_err(|_| format!("Invalid shard count: {}", count))?;
+        if index == 0 || index > count {
+            return Err(format!("Shard {} is not between 1 and {}", index, count));
         }
-
-        // Parse the base collection
-        let base = RegisterCollection::from_str(parts[0], true)
-            .map_err(|_| format!("Invalid register collection: {}", parts[0]))?;
-
-        let mut selection = RegisterSelection { base, additions: Vec::new(), removals: Vec::new() };
-
-        // Process the rest of the parts
-        for part in &parts[1..] {
-            if part.starts_with('+') {
-                let reg_name = &part[1..];
-                let reg = Reg::from_str(reg_name, true)
-                    .map_err(|_| format!("Invalid register to add: {}", reg_name))?;
-                selection.additions.push(reg);
-            } else if part.starts_with('-') {
-                let reg_name = &part[1..];
-                let reg = Reg::from_str(reg_name, true)
-                    .map_err(|_| format!("Invalid register to remove: {}", reg_name))?;
-                selection.removals.push(reg);
-            } else {
-                return Err(format!("Invalid modifier format: {}", part));
-            }
-        }
-
-        Ok(selection)
+        Ok(Shard { index, count })
     }
 }
@@ -0,0 +1,189 @@
+//! A FileCheck-like facility for regression tests of individual passes.
+//!
+//! A test file is an ordinary program whose comments carry directives:
+//!
+//! - `# OPT: cp,dce` selects the optimizations to run (none by default)
+//! - `# CHECK-SSA: <pattern>` expects a line of the SSA dump to match
+//! - `# CHECK-SSA-NOT: <pattern>` expects no line of the SSA dump to match
+//! - `# CHECK-ASM: <pattern>` and `# CHECK-ASM-NOT: <pattern>` do the same
+//!   for the assembly dump
+//!
+//! Patterns are matched literally against a substring of a line, except
+//! that `{{...}}` embeds a regular expression, e.g. `y{{%[0-9]+}} = 5`.
+//!
+//! The positive checks of a dump must match in order, each on a line
+//! after the one matched by the previous check. A negative check must not
+//! match any line between the matches of its neighbouring positive checks.
+
+use crate::cli::{CompilerConf, Optimization, OptimizationCollection, Verbosity};
+use crate::query::Queries;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum Dump {
+    SSA,
+    Asm,
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub dump: Dump,
+    pub negated: bool,
+    /// the pattern as written in the test file
+    pub pattern: String,
+    /// the line of the directive in the test file, starting from 1
+    pub line: usize,
+    regex: Regex,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckFile {
+    pub optimizations: HashSet<Optimization>,
+    pub checks: Vec<Check>,
+}
+
+impl Check {
+    fn new(dump: Dump, negated: bool, pattern: &str, line: usize) -> Result<Self, String> {
+        let mut re = String::new();
+        let mut rest = pattern;
+        while let Some(start) = rest.find("{{") {
+            let end = rest[start..]
+                .find("}}")
+                .ok_or_else(|| format!("line {}: unterminated `{{{{` in pattern", line))?;
+            re.push_str(&regex::escape(&rest[..start]));
+            re.push_str(&format!("(?:{})", &rest[start + 2..start + end]));
+            rest = &rest[start + end + 2..];
+        }
+        re.push_str(&regex::escape(rest));
+        let regex =
+            Regex::new(&re).map_err(|e| format!("line {}: invalid pattern: {}", line, e))?;
+        Ok(Check { dump, negated, pattern: pattern.to_string(), line, regex })
+    }
+
+    fn directive(&self) -> String {
+        let dump = match self.dump {
+            Dump::SSA => "SSA",
+            Dump::Asm => "ASM",
+        };
+        format!("CHECK-{}{}", dump, if self.negated { "-NOT" } else { "" })
+    }
+}
+
+impl CheckFile {
+    /// Collects the directives in the comments of `src`.
+    pub fn parse(src: &str) -> Result<Self, String> {
+        let mut optimizations = HashSet::new();
+        let mut checks = Vec::new();
+        for (i, line) in src.lines().enumerate() {
+            let line_no = i + 1;
+            let Some((_, comment)) = line.split_once('#') else { continue };
+            let comment = comment.trim_start();
+            let Some((directive, arg)) = comment.split_once(':') else { continue };
+            let arg = arg.trim();
+            let (dump, negated) = match directive {
+                "OPT" => {
+                    if !arg.is_empty() {
+                        let opts: OptimizationCollection =
+                            arg.parse().map_err(|e| format!("line {}: {}", line_no, e))?;
+                        optimizations.extend(opts);
+                    }
+                    continue;
+                }
+                "CHECK-SSA" => (Dump::SSA, false),
+                "CHECK-SSA-NOT" => (Dump::SSA, true),
+                "CHECK-ASM" => (Dump::Asm, false),
+                "CHECK-ASM-NOT" => (Dump::Asm, true),
+                _ => continue,
+            };
+            if arg.is_empty() {
+                Err(format!("line {}: empty pattern", line_no))?
+            }
+            checks.push(Check::new(dump, negated, arg, line_no)?);
+        }
+        Ok(CheckFile { optimizations, checks })
+    }
+
+    pub fn needs(&self, dump: Dump) -> bool {
+        self.checks.iter().any(|c| c.dump == dump)
+    }
+
+    /// Matches the checks for `dump` against its textual form `txt`.
+    pub fn verify(&self, dump: Dump, txt: &str) -> Result<(), String> {
+        let lines: Vec<&str> = txt.lines().collect();
+        let mut pos = 0;
+        let mut pending: Vec<&Check> = Vec::new();
+        let verify_nots = |pending: &mut Vec<&Check>, range: &[&str]| {
+            for check in pending.drain(..) {
+                if let Some(found) = range.iter().find(|l| check.regex.is_match(l)) {
+                    Err(format!(
+                        "line {}: {}: `{}` matched `{}`",
+                        check.line,
+                        check.directive(),
+                        check.pattern,
+                        found.trim()
+                    ))?
+                }
+            }
+            Ok::<_, String>(())
+        };
+        for check in self.checks.iter().filter(|c| c.dump == dump) {
+            if check.negated {
+                pending.push(check);
+                continue;
+            }
+            let Some(found) = lines[pos..].iter().position(|l| check.regex.is_match(l)) else {
+                Err(format!(
+                    "line {}: {}: `{}` not found{}",
+                    check.line,
+                    check.directive(),
+                    check.pattern,
+                    if pos == 0 { String::new() } else { format!(" after `{}`", lines[pos - 1]) }
+                ))?
+            };
+            verify_nots(&mut pending, &lines[pos..pos + found])?;
+            pos += found + 1;
+        }
+        verify_nots(&mut pending, &lines[pos..])
+    }
+
+    /// Compiles `src` as configured and verifies every check.
+    pub fn run(&self, src: &str) -> Result<(), String> {
+        self.run_with(src, &Queries::new())
+    }
+
+    /// Runs the checks on `src`, reusing the stages that `queries` kept
+    /// from the earlier files of a batch.
+    pub fn run_with(&self, src: &str, queries: &Queries) -> Result<(), String> {
+        let with_dump = |res: Result<(), String>, txt: &str| {
+            res.map_err(|e| format!("{}\n[[dump]]\n{}", e, txt))
+        };
+        if self.needs(Dump::SSA) {
+            let txt = queries.ssa(src, &self.optimizations)?.to_string();
+            with_dump(self.verify(Dump::SSA, &txt), &txt)?;
+        }
+        if self.needs(Dump::Asm) {
+            let conf = CompilerConf::new(self.optimizations.clone(), Verbosity::Minimalistic);
+            let txt = queries.asm(src, &conf)?;
+            with_dump(self.verify(Dump::Asm, &txt), &txt)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the checks in the file at `path`, returning how many there were.
+pub fn check_file(path: &Path) -> Result<usize, String> {
+    check_file_with(path, &Queries::new())
+}
+
+/// Runs the checks in the file at `path` with the memo tables of a batch.
+pub fn check_file_with(path: &Path, queries: &Queries) -> Result<usize, String> {
+    let src = std::fs::read_to_string(path).map_err(|e| format!("Error reading file: {}", e))?;
+    let file = CheckFile::parse(&src)?;
+    if file.checks.is_empty() {
+        Err("no CHECK directives".to_string())?
+    }
+    file.run_with(&src, queries)?;
+    Ok(file.checks.len())
+}
@@ -0,0 +1,130 @@
+//! Reports of internal compiler errors: the panic hook of `snake` names the
+//! pass that panicked and bundles what it takes to reproduce the panic, i.e.
+//! the program, the flags and the SSA before the pass, into a tar archive.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+#[derive(Default)]
+struct Context {
+    /// The program being compiled
+    input: Option<String>,
+    /// The pass being run
+    pass: Option<&'static str>,
+    /// The SSA that the pass started from, if it runs on SSA
+    ssa: Option<String>,
+    /// The pass to panic in, to test the reports
+    panic_at: Option<String>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::new(Context::default());
+}
+
+/// Records `src` as the program being compiled
+pub fn input(src: &str) {
+    CONTEXT.with(|cx| cx.borrow_mut().input = Some(src.to_string()));
+}
+
+/// Records that `pass` runs, on the AST or the assembly
+pub fn enter(pass: &'static str) {
+    enter_with(pass, None);
+}
+
+/// Records that `pass` runs on `ssa`
+pub fn enter_ssa(pass: &'static str, ssa: &impl Display) {
+    enter_with(pass, Some(ssa.to_string()));
+}
+
+fn enter_with(pass: &'static str, ssa: Option<String>) {
+    let panic = CONTEXT.with(|cx| {
+        let mut cx = cx.borrow_mut();
+        cx.pass = Some(pass);
+        if ssa.is_some() {
+            cx.ssa = ssa;
+        }
+        cx.panic_at.as_deref() == Some(pass)
+    });
+    if panic {
+        panic!("panicking in `{}` as requested", pass);
+    }
+}
+
+/// Panics when `pass` is entered
+pub fn panic_at(pass: &str) {
+    CONTEXT.with(|cx| cx.borrow_mut().panic_at = Some(pass.to_string()));
+}
+
+/// Reports panics as internal compiler errors after the message of the
+/// default hook, and writes the bundle `lekans-ice-<time>-<pid>.tar` to
+/// `dir`, or to the temporary directory without one.
+pub fn install_hook(dir: Option<PathBuf>) {
+    let dir = dir.unwrap_or_else(std::env::temp_dir);
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default(info);
+        let (pass, files) = CONTEXT.with(|cx| bundle(&cx.borrow(), &info.to_string()));
+        let pass = pass.map_or(String::new(), |pass| format!(" in the pass `{}`", pass));
+        eprintln!("error: internal compiler error{}", pass);
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let path = dir.join(format!("lekans-ice-{}-{}.tar", time, std::process::id()));
+        match std::fs::write(&path, tar(&files)) {
+            Ok(()) => eprintln!(
+                "note: please attach {} to the bug report; it holds the program, the flags and \
+                 the SSA before the pass",
+                path.display()
+            ),
+            Err(e) => eprintln!("note: could not write {}: {}", path.display(), e),
+        }
+    }));
+}
+
+/// The pass that panicked with `message`, and the files of its bundle
+fn bundle(cx: &Context, message: &str) -> (Option<&'static str>, Vec<(String, Vec<u8>)>) {
+    let flags: Vec<String> = std::env::args().skip(1).collect();
+    let mut report = format!("pass: {}\n", cx.pass.unwrap_or("(none)"));
+    report.push_str(&format!("flags: {}\n", flags.join(" ")));
+    report.push_str(&format!("{}\n", message));
+    let mut files = vec![("report.txt".to_string(), report.into_bytes())];
+    if let Some(input) = &cx.input {
+        files.push(("program.dbk".to_string(), input.clone().into_bytes()));
+    }
+    if let Some(ssa) = &cx.ssa {
+        files.push(("pre-pass.ssa".to_string(), ssa.clone().into_bytes()));
+    }
+    (cx.pass, files)
+}
+
+/// The ustar archive of `files`, each a name of at most 100 bytes with its
+/// contents
+pub fn tar(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mut archive = Vec::new();
+    for (name, contents) in files {
+        let mut header = [0u8; 512];
+        let mut field = |offset: usize, value: &[u8]| {
+            header[offset..offset + value.len()].copy_from_slice(value);
+        };
+        field(0, name.as_bytes());
+        field(100, b"0000644\0");
+        field(108, b"0000000\0");
+        field(116, b"0000000\0");
+        field(124, format!("{:011o}\0", contents.len()).as_bytes());
+        field(136, b"00000000000\0");
+        // the checksum counts its own field as spaces
+        field(148, b"        ");
+        field(156, b"0");
+        field(257, b"ustar\0");
+        field(263, b"00");
+        let checksum: u32 = header.iter().map(|b| *b as u32).sum();
+        header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+        archive.extend(header);
+        archive.extend(contents);
+        archive.resize(archive.len().next_multiple_of(512), 0);
+    }
+    // two empty records end the archive
+    archive.resize(archive.len() + 1024, 0);
+    archive
+}
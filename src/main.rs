@@ -1,57 +1,161 @@
-use clap::{ArgAction, Parser};
-use snake::asm::{instrs_to_string, Reg};
+use clap::{ArgAction, Parser, Subcommand};
+use snake::asm::Reg;
 use snake::backend::{
-    ConflictAnalysis, Emitter, LivenessAnalyzer, RegisterAllocator, UnusedRemover,
+    ConflictAnalysis, DominatorAnalysis, EffectAnalysis, Emitter, LeafAnalysis, LivenessAnalyzer,
+    PressureReport, RegisterAllocator, SizeReport, StackReport, UnusedRemover,
 };
 use snake::cli::*;
+use snake::compile;
+use snake::consteval;
+use snake::dump::Versioned;
+use snake::error::{AllocError, Error, LinkError, ParseError};
+use snake::filecheck;
 use snake::frontend::Resolver;
+use snake::ice;
+use snake::identifiers::ReadableNames;
 use snake::interp;
-use snake::middle_end::{AssertionRemover, CopyPropagator, Lowerer};
-use snake::parser::ProgParser;
+use snake::layout::{self, Width};
+use snake::lint::{self, Rule};
+use snake::middle_end::{
+    AssertionHoister, AssertionRemover, BoundsCheckCombiner, ColdOutliner, ConditionCanonicalizer,
+    ConstantSpecializer, CopyPropagator, InductionAnalyzer, LoadForwarder, Loopifier, Lowerer,
+    MemoryAnalyzer, TypeSpecializer,
+};
+use snake::pretty::{LivenessAnnotated, MemoryAnnotated, Readable};
+use snake::query::Queries;
+use snake::remarks::{Remark, RemarkFormat};
 use snake::runner::*;
+use snake::scopes;
+use snake::selfcheck;
+use snake::stress;
 use snake::txt::FileInfo;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Display;
 use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
-#[command(about, long_about = None)]
+#[command(about, long_about = None, args_conflicts_with_subcommands = true, after_help = EXIT_CODES)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// File containing the input program; defaults to stdin
     input_file: Option<String>,
 
-    /// Optional target type; defaults to asm
+    /// Optional target type; defaults to asm.
+    /// Can be repeated (e.g. `-t asm -t graph -t coloring`) to emit several targets from a single
+    /// compilation; each is then written to the output path with the target's extension
     #[arg(value_enum, short, long, value_name = "target")]
-    target: Option<Target>,
+    target: Vec<Target>,
+
+    /// The parameters of the function that target expr-asm compiles the expression into, in
+    /// order; e.g. `--params x,y` for a function of two arguments
+    #[arg(long, value_name = "param", value_delimiter = ',')]
+    params: Vec<String>,
 
     /// Optional output file.
-    /// For target exe, defaults to runtime/stub.exe, otherwise if not present prints to stdout
+    /// For target exe, defaults to runtime/stub.exe, otherwise if not present prints to stdout.
+    /// With multiple targets, serves as the base of the derived paths (e.g. out.asm, out.graph);
+    /// defaults to the input file
     #[arg(short, long, value_name = "output")]
     output: Option<PathBuf>,
 
     /// Apply specified optional optimizations; defaults to apply no optimizations
-    /// To apply all optimizations, use "all"
+    /// To apply all optimizations, use "all"; to apply those of a level, use 0 to 3
     /// Format: [<optimization>, ...]
     /// Example:
     ///  - "-O": apply no optimization
     ///  - "-O=cp,ar": apply only copy propagation and assertion removal
     ///  - "-O=all": apply all optimizations
+    ///  - "-O2": apply the optimizations of level 2, as --explain-opts lists them
     #[arg(short = 'O', long, alias = "opts", value_name = "optimization", num_args = 0..)]
     optimizations: Option<OptimizationCollection>,
 
+    /// Optimizations not to apply, even if -O selects them, e.g. `-O2 --disable loopify` to apply
+    /// level 2 without loopify; can be repeated or separated by commas
+    #[arg(long, value_name = "optimization", value_delimiter = ',')]
+    disable: Vec<Optimization>,
+
+    /// If set, prints the optimization levels with their passes and what each pass does, and
+    /// exits
+    #[arg(long)]
+    explain_opts: bool,
+
+    /// If set, annotates each instruction of the SSA target with the analysis result as an
+    /// end-of-line comment
+    #[arg(value_enum, long, value_name = "annotation")]
+    annotate: Option<Annotation>,
+
+    /// If set, displays variables in the SSA, graph, elimination order and coloring targets by
+    /// their source name and a counter per name (e.g. `x.3`) instead of a unique index (`x%27`)
+    #[arg(long, conflicts_with = "annotate")]
+    readable_names: bool,
+
+    /// The width the AST and SSA targets are laid out in; constructs that do not fit on a line are
+    /// broken over several
+    #[arg(long, value_name = "columns", default_value_t = layout::DEFAULT_WIDTH)]
+    width: usize,
+
     /// If set, generates the interference graph in svg format to a file
     #[arg(long, value_name = "interference")]
     interference: Option<PathBuf>,
 
+    /// If set, the interference graph shows the register assigned to each variable by color and
+    /// marks spilled variables; requires --interference
+    #[arg(long, requires = "interference")]
+    interference_colored: bool,
+
+    /// If set, generates the dominator tree of the blocks of each function in svg format to a file
+    #[arg(long, value_name = "dom-tree")]
+    dom_tree: Option<PathBuf>,
+
+    /// If set, prints the number of instructions and bytes of machine code of each function to
+    /// stderr
+    #[arg(long)]
+    size_report: bool,
+
+    /// If set, prints a bound on the bytes of stack that each function uses, with its calls, to
+    /// stderr, and warns about the recursion that leaves it unbounded
+    #[arg(long)]
+    stack_report: bool,
+
+    /// If set, prints to stderr what the optimizations did, and where they declined to with the
+    /// reason, as text or as JSON objects, one per line
+    #[arg(long, value_name = "format", value_enum)]
+    remarks: Option<RemarkFormat>,
+
+    /// How comparisons whose result is only tagged become booleans; compare the lowerings with
+    /// --size-report or by timing the executables
+    #[arg(long, value_name = "lowering", value_enum, default_value_t = BoolLowering::Setcc)]
+    bool_lowering: BoolLowering,
+
+    /// If set, dead code elimination does not trust the contracts of externs, keeping unused
+    /// calls to those declared `pure(true)` too; unused calls that may fail are always kept
+    #[arg(long)]
+    conservative_effects: bool,
+
+    /// If set, optimizations keep the runtime error reported by the unoptimized program, i.e.
+    /// dead code elimination keeps unused operations that may fail
+    #[arg(long)]
+    strict_errors: bool,
+
+    /// Warnings to report to stderr, e.g. `-Wshadow` for the variables that shadow another of the
+    /// same name, as target scopes shows them; can be repeated
+    #[arg(value_enum, short = 'W', long = "warn", value_name = "warning")]
+    warnings: Vec<Warning>,
+
     /// Specify the set of allocatable registers
     /// Format: <collection>[+reg1][-reg2] ...
     /// Example: -R=volatile+rbx-r9
     ///
     /// The register selection starts with a base collection:
     /// - all: All allocatable registers (default)
-    /// - volatile: Only volatile registers
-    /// - non-volatile: Only non-volatile registers
+    /// - volatile (or caller): Only volatile registers
+    /// - non-volatile (or callee): Only non-volatile registers
     /// - none: No registers
     ///
-    /// You can then modify this set by adding or removing specific registers:
+    /// You can then modify this set by adding or removing specific registers,
+    /// from left to right:
     /// - +reg: Add a register (e.g., +r8)
     /// - -reg: Remove a register (e.g., -r9)
     ///
@@ -60,25 +164,702 @@ struct Cli {
     /// - "non-volatile+r8": Use non-volatile registers plus r8
     /// - "all-r9-r10": Use all allocatable registers except r9 and r10
     #[arg(short = 'R', long, alias = "regs", value_name = "register")]
-    registers: Option<RegisterSelection>,
+    registers: Option<RegisterSet>,
 
     /// If set, executes the output program, rather than displaying it.
     /// For asm or exe, executes the binary; for other targets, runs an interpreter
     #[arg(short = 'x', long, value_name = "execute", allow_hyphen_values = true, num_args = 0..)]
     execute: Option<Vec<String>>,
 
+    /// Argument of the program known at compile time. Can be repeated (e.g. `--const-arg=3
+    /// --const-arg=true`), or given without a value for a program run without arguments. A program
+    /// that only computes then runs while compiling, and compiles to one that returns its result;
+    /// others compile as usual
+    #[arg(long = "const-arg", value_name = "arg", num_args = 0..=1, require_equals = true)]
+    const_args: Option<Vec<String>>,
+
+    /// The number of steps the program may run at compile time with --const-arg
+    #[arg(long, value_name = "steps", default_value_t = consteval::DEFAULT_FUEL)]
+    const_fuel: usize,
+
+    /// How deeply the expressions of the program may nest, each binding of a `let` nesting its
+    /// body; deeper programs are rejected rather than risk running the compiler out of stack
+    #[arg(long, value_name = "depth", default_value_t = Limits::default().depth)]
+    max_depth: usize,
+
+    /// The size in bytes of the largest program that is compiled
+    #[arg(long, value_name = "bytes", default_value_t = Limits::default().size)]
+    max_size: usize,
+
+    /// Seeds `random` and makes `clock` count its calls, so that runs are reproducible, by
+    /// setting SNAKE_SEED for the interpreters and the executed program
+    #[arg(long, value_name = "seed")]
+    seed: Option<u64>,
+
+    /// How the types of the program are checked: `gradual` checks the annotations, at runtime
+    /// where their types are not evident; `infer` also infers a type for every value, with
+    /// homogeneous arrays, and rejects the program where two places need different types
+    #[arg(long, value_name = "mode", value_enum, default_value_t = TypeMode::Gradual)]
+    types: TypeMode,
+
+    /// If set, integers that overflow 63 bits are promoted to big integers handled by the
+    /// runtime, rather than reporting an overflow, and literals may be arbitrarily large
+    #[arg(long, conflicts_with = "freestanding")]
+    bigint: bool,
+
     /// Optional runtime file; defaults to runtime/stub.rs
     #[arg(short, long, value_name = "runtime")]
     runtime: Option<PathBuf>,
 
+    /// If set, links the program without the Rust stub: a `_start` wrapper and small routines
+    /// for allocation, printing and errors are appended to the assembly, using Linux system
+    /// calls only. Of the externs, only `print` is available
+    #[arg(long, conflicts_with = "runtime")]
+    freestanding: bool,
+
+    /// If set, the executable prints the functions it was running, most recent first, when it
+    /// fails; the compiled code keeps no source locations, so the functions come without lines
+    #[arg(short = 'g', long, conflicts_with = "freestanding")]
+    debug: bool,
+
+    /// Whether functions keep rbp as a frame pointer for unwinders to follow, or allocate it and
+    /// come with `.eh_frame` unwind tables for ELF targets instead; by default, neither
+    #[arg(long, value_name = "mode", value_enum)]
+    frame_pointer: Option<FramePointer>,
+
+    /// If set, each function goes into a section `.text.<name>` of its own, and the linker
+    /// discards those that nothing calls with --gc-sections; ELF targets only
+    #[arg(long)]
+    function_sections: bool,
+
+    /// If set, calls whose result is returned are emitted as calls rather than jumps to the
+    /// callee, which keeps the frames of their callers for debuggers, but lets deep recursion
+    /// through them overflow the stack; implied by --debug
+    #[arg(long)]
+    no_tail_calls: bool,
+
+    /// If set, checks that no two variables live at once share a register or a slot, and that
+    /// the variables are allocated to the selected registers, before generating code; a broken
+    /// allocation is an internal compiler error. Always on in debug builds of the compiler
+    #[arg(long)]
+    verify_alloc: bool,
+
+    /// If set, checks that pushes, pops and the moves of rsp balance, that calls are made with
+    /// rsp on a multiple of 16, and that no slot of a frame is read before it is written on
+    /// every path there, in the emitted code; a violation is an internal compiler error. Always
+    /// on in debug builds of the compiler
+    #[arg(long)]
+    verify_stack: bool,
+
+    /// Directory that the bundles of internal compiler errors are written to; defaults to the
+    /// temporary directory of the system
+    #[arg(long, value_name = "dir")]
+    ice_dir: Option<PathBuf>,
+
+    /// Panics in the pass with the given name, to test the reports of internal compiler errors
+    #[arg(long, hide = true, value_name = "pass")]
+    panic_at: Option<String>,
+
+    /// Linker used to build the executable; rustc's default for the OS, or `ld` with
+    /// --freestanding
+    #[arg(long, value_name = "path")]
+    linker: Option<PathBuf>,
+
+    /// Extra argument passed to the linker. Can be repeated
+    #[arg(long = "link-arg", value_name = "arg", allow_hyphen_values = true)]
+    link_args: Vec<String>,
+
+    /// If set, links the C runtime statically (linux only); freestanding executables are always
+    /// static
+    #[arg(long = "static")]
+    static_crt: bool,
+
+    /// Platform to assemble and link for; defaults to the host
+    #[arg(long, value_name = "triple")]
+    target_triple: Option<TargetTriple>,
+
+    /// Emulator that runs the executable with -x, e.g. qemu-x86_64; required when the target's
+    /// executables do not run on the host
+    #[arg(long, value_name = "emulator")]
+    run_under: Option<PathBuf>,
+
     /// If set, prints verbose output. Can be repeated (e.g. -vv) for more verbosity
     #[arg(short, long, action = ArgAction::Count)]
     verbose: u8,
 }
 
-fn run_cli(cli: &Cli) -> Result<(), String> {
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run FileCheck-style regression tests.
+    ///
+    /// Test files carry `# CHECK-SSA:` / `# CHECK-ASM:` patterns (and their `-NOT` variants) in
+    /// comments that must match the corresponding dump; `# OPT:` selects the optimizations
+    Test {
+        /// Test files, or directories searched for `.snake` and `.dbk` files with checks
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Number of files checked at once; defaults to the available parallelism
+        #[arg(short, long, value_name = "n")]
+        jobs: Option<std::num::NonZeroUsize>,
+        /// Only check the K-th of every N files found, counting from 1
+        #[arg(long, value_name = "K/N")]
+        shard: Option<Shard>,
+    },
+    /// Document the functions of a program.
+    ///
+    /// Lists the externs and functions with their signatures and the `##` doc comments before
+    /// their declarations
+    Doc {
+        /// File containing the program
+        file: PathBuf,
+        /// Output format
+        #[arg(value_enum, short, long, default_value = "markdown")]
+        format: DocFormat,
+        /// Optional output file; prints to stdout if not present
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Generate the skeleton of a runtime for a program.
+    ///
+    /// Defines a Rust function for every extern that the program declares, with the types of
+    /// its signature, and for the services of the runtime that it uses, next to the tagging
+    /// helpers; the result compiles and links with `--runtime` once the externs are implemented
+    GenRuntime {
+        /// File containing the program
+        file: PathBuf,
+        /// Optional output file; prints to stdout if not present
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Compile with --bigint, whose arithmetic calls the runtime
+        #[arg(long)]
+        bigint: bool,
+    },
+    /// Generate the interface of a program for a host in another language.
+    ///
+    /// Declares `entry`, which the object assembled from `-t asm` exports, and the externs and
+    /// services of the runtime that the host defines, with helpers to tag and untag values
+    Bindgen {
+        /// File containing the program
+        file: PathBuf,
+        /// Language of the host
+        #[arg(value_enum, short, long, default_value = "c")]
+        lang: BindgenLang,
+        /// Optional output file; prints to stdout if not present
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Compile with --bigint, whose arithmetic calls the runtime
+        #[arg(long)]
+        bigint: bool,
+    },
+    /// Report likely mistakes in a program.
+    ///
+    /// Every rule reports a warning by default; the program fails the lint if a denied rule
+    /// reports anything
+    Lint {
+        /// File containing the program
+        file: PathBuf,
+        /// Rules that report nothing
+        #[arg(value_enum, short = 'A', long, value_name = "rule")]
+        allow: Vec<Rule>,
+        /// Rules whose reports are errors
+        #[arg(value_enum, short = 'D', long, value_name = "rule")]
+        deny: Vec<Rule>,
+    },
+    /// Check that transformations which keep the behavior of programs keep it once compiled.
+    ///
+    /// Each program is run on the arguments of its `# ARGS:` lines, and so is each of its
+    /// mutants: with renamed variables, split or reordered `let`s, and reassociated arithmetic
+    Selfcheck {
+        /// Programs, or directories searched for `.snake` and `.dbk` files
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Optimizations to compile with, as for the compiler
+        #[arg(short = 'O', long, value_name = "optimization", num_args = 0..)]
+        optimizations: Option<OptimizationCollection>,
+        /// Compile with --bigint, which makes `+` and `*` associative
+        #[arg(long)]
+        bigint: bool,
+        /// Interpret the optimized SSA instead of running executables, which leaves out the
+        /// backend but needs neither nasm nor a linker
+        #[arg(long)]
+        interpret: bool,
+        /// Number of files checked at once; defaults to the available parallelism
+        #[arg(short, long, value_name = "n")]
+        jobs: Option<std::num::NonZeroUsize>,
+        /// Only check the K-th of every N files found, counting from 1
+        #[arg(long, value_name = "K/N")]
+        shard: Option<Shard>,
+    },
+    /// Measure the register allocator on generated programs.
+    ///
+    /// Generates a program for each combination of the numbers of live values and call densities,
+    /// in which every function keeps that many values live at once, and reports the interference
+    /// graph, the registers, the spills and the time of the allocation of each
+    Bench {
+        /// Numbers of values live at once in each function
+        #[arg(long, value_name = "n", value_delimiter = ',', default_values_t = [8, 16, 32])]
+        live: Vec<usize>,
+        /// Percentages of the bindings that call a function
+        #[arg(long, value_name = "percent", value_delimiter = ',', default_values_t = [0, 25, 50],
+              value_parser = clap::value_parser!(u8).range(0..=100))]
+        calls: Vec<u8>,
+        /// Number of functions of each program
+        #[arg(long, value_name = "n", default_value_t = 2)]
+        funs: usize,
+        /// Seed of the generated programs
+        #[arg(long, value_name = "n", default_value_t = 0)]
+        seed: u64,
+        /// Registers to allocate, as for the compiler
+        #[arg(short = 'R', long, alias = "regs", value_name = "register")]
+        registers: Option<RegisterSet>,
+        /// Optimizations to compile with, as for the compiler
+        #[arg(short = 'O', long, value_name = "optimization", num_args = 0..)]
+        optimizations: Option<OptimizationCollection>,
+        /// Directory to write the generated programs to, e.g. to run them
+        #[arg(long, value_name = "dir")]
+        corpus: Option<PathBuf>,
+    },
+}
+
+fn run_test(paths: &[PathBuf], jobs: usize, shard: Option<Shard>) -> Result<(), String> {
+    let mut files =
+        collect_programs(paths, |path| read_file(path).is_ok_and(|src| src.contains("# CHECK-")))?;
+    if let Some(shard) = shard {
+        files = shard.select(files);
+    }
+    let (queries, mut failed) = (Queries::new(), 0);
+    let check = |file: &Path| filecheck::check_file_with(file, &queries);
+    in_parallel(&files, jobs, check, |file, res| match res {
+        Ok(n) => println!("PASS {} ({} checks)", file.display(), n),
+        Err(e) => {
+            failed += 1;
+            println!("FAIL {}", file.display());
+            println!("{}", e);
+        }
+    });
+    println!();
+    println!("{} passed; {} failed", files.len() - failed, failed);
+    if failed > 0 {
+        Err(format!("{} test file(s) failed", failed))?
+    }
+    Ok(())
+}
+
+/// Runs `check` on `jobs` threads over `files`, and `report` on the main thread with each
+/// result as soon as it is done, so the order of the reports is that of completion
+fn in_parallel<T: Send>(
+    files: &[PathBuf], jobs: usize, check: impl Fn(&Path) -> T + Sync,
+    mut report: impl FnMut(&Path, T),
+) {
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(files.len()) {
+            let sender = sender.clone();
+            let (next, check) = (&next, &check);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let Some(file) = files.get(i) else { break };
+                if sender.send((file, check(file))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+        for (file, res) in receiver {
+            report(file, res);
+        }
+    });
+}
+
+/// The number of jobs to run at once unless `--jobs` says otherwise
+fn default_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+fn run_doc(file: &Path, format: DocFormat, output: Option<&Path>) -> Result<(), Failure> {
+    let src = read_file(file).map_err(|e| format!("Error reading {}: {}", file.display(), e))?;
+    let prog = compile::parse(&src)?;
+    let title = file
+        .file_stem()
+        .map_or_else(|| file.display().to_string(), |stem| stem.to_string_lossy().into_owned());
+    let doc = snake::doc::render(&title, &prog, format);
+    match output {
+        Some(path) => std::fs::write(path, doc)
+            .map_err(|e| Failure::Other(format!("Error writing {}: {}", path.display(), e))),
+        None => {
+            print!("{}", doc);
+            Ok(())
+        }
+    }
+}
+
+fn run_gen_runtime(file: &Path, output: Option<&Path>, bigint: bool) -> Result<(), Failure> {
+    let src = read_file(file).map_err(|e| format!("Error reading {}: {}", file.display(), e))?;
+    let file_info = FileInfo::new(&src);
+    let prog = compile::parse(&src)?;
+    let resolver = Resolver::new().bigint(bigint);
+    let (resolver, ast) =
+        compile::frontend_with(&src, resolver).map_err(|e| Failure::compile(e, &file_info))?;
+    let (_, ssa) = compile::middle_end_with(resolver, ast, &Default::default())?;
+    let title = file.display().to_string();
+    let runtime = snake::bindgen::rust_runtime(&title, &prog, &ssa.externs);
+    match output {
+        Some(path) => std::fs::write(path, runtime)
+            .map_err(|e| Failure::Other(format!("Error writing {}: {}", path.display(), e))),
+        None => {
+            print!("{}", runtime);
+            Ok(())
+        }
+    }
+}
+
+fn run_bindgen(
+    file: &Path, lang: BindgenLang, output: Option<&Path>, bigint: bool,
+) -> Result<(), Failure> {
+    let src = read_file(file).map_err(|e| format!("Error reading {}: {}", file.display(), e))?;
+    let file_info = FileInfo::new(&src);
+    let prog = compile::parse(&src)?;
+    let resolver = Resolver::new().bigint(bigint);
+    let (resolver, ast) =
+        compile::frontend_with(&src, resolver).map_err(|e| Failure::compile(e, &file_info))?;
+    let (_, ssa) = compile::middle_end_with(resolver, ast, &Default::default())?;
+    let title = file.display().to_string();
+    let interface = match lang {
+        BindgenLang::C => snake::bindgen::c_header(&title, &prog, &ssa.externs),
+        BindgenLang::Python => snake::bindgen::python_module(&title, &prog, &ssa.externs),
+    };
+    match output {
+        Some(path) => std::fs::write(path, interface)
+            .map_err(|e| Failure::Other(format!("Error writing {}: {}", path.display(), e))),
+        None => {
+            print!("{}", interface);
+            Ok(())
+        }
+    }
+}
+
+fn run_lint(file: &Path, allow: &[Rule], deny: &[Rule]) -> Result<(), Failure> {
+    let src = read_file(file).map_err(|e| format!("Error reading {}: {}", file.display(), e))?;
+    let file_info = FileInfo::new(&src);
+    let (_, prog) = compile::frontend(&src).map_err(|e| Failure::compile(e, &file_info))?;
+    let mut errors = 0;
+    for diagnostic in lint::lint(&prog).iter().filter(|d| !allow.contains(&d.rule)) {
+        let denied = deny.contains(&diagnostic.rule);
+        errors += denied as usize;
+        eprintln!("{}", diagnostic.report(&file_info, denied));
+    }
+    if errors > 0 {
+        Err(format!("{} denied lint(s) reported", errors))?
+    }
+    Ok(())
+}
+
+fn run_bench(
+    shapes: &[stress::Shape], registers: &[Reg], optimizations: &HashSet<Optimization>,
+    corpus: Option<&Path>,
+) -> Result<(), Failure> {
+    RegisterAllocator::check_registers(registers)?;
+    if let Some(dir) = corpus {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Error creating {}: {}", dir.display(), e))?;
+    }
+    println!("    live  calls  {}", stress::AllocStats::HEADER);
+    for shape in shapes {
+        let src = shape.program();
+        if let Some(dir) = corpus {
+            let path = dir.join(format!("live{}_calls{}.dbk", shape.live, shape.calls));
+            std::fs::write(&path, &src)
+                .map_err(|e| format!("Error writing {}: {}", path.display(), e))?;
+        }
+        let file_info = FileInfo::new(&src);
+        let (resolver, prog) =
+            compile::frontend(&src).map_err(|e| Failure::compile(e, &file_info))?;
+        let (_, ssa) = compile::middle_end_with(resolver, prog, optimizations)
+            .map_err(|e| Failure::compile(e, &file_info))?;
+        let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+        let stats = stress::AllocStats::new(&ssa, registers);
+        println!("{:>8}  {:>4}%  {}", shape.live, shape.calls, stats);
+    }
+    Ok(())
+}
+
+/// The programs among `paths`, searching directories for `.snake` and `.dbk` files
+/// that satisfy `wanted`
+fn collect_programs(
+    paths: &[PathBuf], wanted: impl Fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            for entry in walkdir::WalkDir::new(path).sort_by_file_name() {
+                let entry =
+                    entry.map_err(|e| format!("Error walking {}: {}", path.display(), e))?;
+                let is_src = matches!(
+                    entry.path().extension().and_then(|e| e.to_str()),
+                    Some("snake" | "dbk")
+                );
+                if is_src && wanted(entry.path()) {
+                    files.push(entry.into_path());
+                }
+            }
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn run_selfcheck(
+    paths: &[PathBuf], conf: &selfcheck::Config, jobs: usize, shard: Option<Shard>,
+) -> Result<(), String> {
+    std::env::set_var("SNAKE_SEED", "0");
+    let mut files = collect_programs(paths, |_| true)?;
+    if let Some(shard) = shard {
+        files = shard.select(files);
+    }
+    let check = |file: &Path| {
+        let src =
+            read_file(file).map_err(|e| format!("Error reading {}: {}", file.display(), e))?;
+        Ok(selfcheck::check(&src, conf))
+    };
+    let (mut passed, mut failed, mut error) = (0, 0, None);
+    in_parallel(&files, jobs, check, |file, res: Result<_, String>| {
+        let outcomes = match res {
+            Ok(Ok(outcomes)) => outcomes,
+            Ok(Err(e)) => {
+                println!("SKIP {}: {}", file.display(), e.lines().next().unwrap_or_default());
+                return;
+            }
+            Err(e) => {
+                error.get_or_insert(e);
+                return;
+            }
+        };
+        for (mutation, outcome) in outcomes {
+            match outcome {
+                selfcheck::Outcome::Pass => {
+                    passed += 1;
+                    println!("PASS {} ({})", file.display(), mutation.name());
+                }
+                selfcheck::Outcome::Skip => {}
+                selfcheck::Outcome::Fail { args, expected, found } => {
+                    failed += 1;
+                    println!(
+                        "FAIL {} ({}) with arguments {:?}",
+                        file.display(),
+                        mutation.name(),
+                        args
+                    );
+                    println!("  expected: {}", expected.trim());
+                    println!("  found:    {}", found.trim());
+                }
+            }
+        }
+    });
+    if let Some(e) = error {
+        Err(e)?
+    }
+    println!();
+    println!("{} passed; {} failed", passed, failed);
+    if failed > 0 {
+        Err(format!("{} mutant(s) behave differently", failed))?
+    }
+    Ok(())
+}
+
+/// Where the requested targets are emitted to.
+struct Outputs {
+    targets: BTreeSet<Target>,
+    /// the base of the derived output paths; `None` if the only target goes to stdout
+    base: Option<PathBuf>,
+}
+
+impl Outputs {
+    fn new(cli: &Cli) -> Result<Self, String> {
+        let mut targets: BTreeSet<Target> = cli.target.iter().copied().collect();
+        if targets.is_empty() {
+            targets.insert(Asm);
+        }
+        if targets.len() == 1 {
+            let base = match targets.first() {
+                Some(Exe) => None,
+                _ => cli.output.clone(),
+            };
+            return Ok(Outputs { targets, base });
+        }
+        if cli.execute.is_some() {
+            Err("Cannot execute with multiple targets".to_string())?
+        }
+        if targets.contains(&ExprAsm) {
+            Err("Target expr-asm cannot be combined with other targets".to_string())?
+        }
+        let base = match (&cli.output, &cli.input_file) {
+            (Some(output), _) => output.clone(),
+            (None, Some(input)) => PathBuf::from(input),
+            (None, None) => Err("Multiple targets require an output file".to_string())?,
+        };
+        Ok(Outputs { targets, base: Some(base) })
+    }
+    fn wants(&self, target: Target) -> bool {
+        self.targets.contains(&target)
+    }
+    /// Whether the pipeline can stop after producing `target`.
+    fn done(&self, target: Target) -> bool {
+        self.targets.last() == Some(&target)
+    }
+    fn to_stdout(&self) -> bool {
+        self.base.is_none()
+    }
+    /// The path `target` is written to, if not printed to stdout.
+    fn path(&self, target: Target) -> Option<PathBuf> {
+        let base = self.base.as_ref()?;
+        if self.targets.len() == 1 {
+            Some(base.clone())
+        } else {
+            Some(base.with_extension(target.extension()))
+        }
+    }
+    fn emit(&self, target: Target, txt: impl Display) -> Result<(), String> {
+        let txt = match target.format() {
+            Some(format) => Versioned(format, txt).to_string(),
+            None => txt.to_string(),
+        };
+        match self.path(target) {
+            Some(path) => std::fs::write(&path, format!("{}\n", txt))
+                .map_err(|e| format!("Error writing {}: {}", path.display(), e)),
+            None => {
+                println!("{}", txt);
+                Ok(())
+            }
+        }
+    }
+}
+
+const EXIT_CODES: &str = "\
+Exit codes:
+  0  success
+  1  any other failure, e.g. reading the input, linking, or a runtime error of the interpreters
+  2  the program does not parse
+  3  the program does not resolve, e.g. an unbound variable
+  4  the program does not type check
+  5  an internal compiler error, bundled for the bug report into lekans-ice-*.tar in --ice-dir
+  With -x and an executable, the exit code of the program, or 128 plus the signal ending it";
+
+/// How `snake` fails, which the exit code tells apart
+enum Failure {
+    /// A program that does not compile, with its diagnostic; boxed, as the
+    /// errors are much larger than the other failures
+    Compile(Box<Error>, String),
+    /// The program that was run with -x exited with the code
+    Exit(i32),
+    /// A panic of the compiler
+    Internal,
+    Other(String),
+}
+
+impl Failure {
+    /// The failure of compiling the program of `file`
+    fn compile(err: Error, file: &FileInfo) -> Self {
+        let message = err.render(file);
+        Failure::Compile(Box::new(err), message)
+    }
+
+    fn code(&self) -> i32 {
+        match self {
+            Failure::Compile(err, _) => match **err {
+                Error::Parse(_) => 2,
+                Error::Resolve(_) => 3,
+                Error::Type(_) => 4,
+                Error::Alloc(_) | Error::Link(_) => 1,
+            },
+            Failure::Exit(code) => *code,
+            Failure::Internal => 5,
+            Failure::Other(_) => 1,
+        }
+    }
+}
+
+macro_rules! compile_failure {
+    ($($err:ty),*) => {
+        $(
+            impl From<$err> for Failure {
+                fn from(err: $err) -> Self {
+                    let err = Error::from(err);
+                    let message = err.to_string();
+                    Failure::Compile(Box::new(err), message)
+                }
+            }
+        )*
+    };
+}
+
+compile_failure!(Error, ParseError, AllocError, LinkError);
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Failure::Other(message)
+    }
+}
+
+impl From<&str> for Failure {
+    fn from(message: &str) -> Self {
+        Failure::Other(message.to_string())
+    }
+}
+
+/// The exit code of a program that `status` tells failed
+fn exit_code(status: std::process::ExitStatus) -> i32 {
+    use std::os::unix::process::ExitStatusExt;
+    status.code().or(status.signal().map(|signal| 128 + signal)).unwrap_or(1)
+}
+
+/// Displays `t` with the readable `names`, if any
+fn named<'a, T>(names: Option<&'a ReadableNames>, t: &'a T) -> Box<dyn Display + 'a>
+where
+    T: Display,
+    Readable<'a, T>: Display,
+{
+    match names {
+        Some(names) => Box::new(Readable(names, t)),
+        None => Box::new(t),
+    }
+}
+
+/// Runs `prog` on the AST interpreter, reporting the backtrace if it fails
+fn interpret_ast<Var, Fun>(
+    prog: &snake::ast::Prog<Var, Fun>, args: &[String], services: interp::Services,
+    file_info: &FileInfo,
+) -> Result<interp::Value, String>
+where
+    Var: std::hash::Hash + Eq + Clone + Display,
+    Fun: std::hash::Hash + Eq + Clone + Display,
+{
+    let mut machine = interp::ast::Machine::start(prog, args, services)
+        .map_err(|e| format!("Error interpreting program: {}", e))?;
+    loop {
+        match machine.resume(usize::MAX) {
+            Ok(interp::Status::Done(value)) => break Ok(value),
+            Ok(interp::Status::Suspended) => {}
+            Err(e) => {
+                let trace = interp::render_backtrace(&machine.backtrace(), file_info);
+                break Err(format!("Error interpreting program: {}\n{}", e, trace));
+            }
+        }
+    }
+}
+
+fn run_cli(cli: &Cli) -> Result<(), Failure> {
+    if cli.explain_opts {
+        print!("{}", explain_optimizations());
+        return Ok(());
+    }
+    let outs = Outputs::new(cli)?;
+    if let Some(seed) = cli.seed {
+        std::env::set_var("SNAKE_SEED", seed.to_string());
+    }
     let conf = {
-        let conf = CompilerConf::new(
+        let mut conf = CompilerConf::new(
             cli.optimizations.clone().into_iter().flatten(),
             match cli.verbose {
                 0 => Verbosity::Minimalistic,
@@ -90,6 +871,17 @@ fn run_cli(cli: &Cli) -> Result<(), String> {
                 }
             },
         );
+        conf.disable(cli.disable.iter().copied());
+        conf.conservative_effects = cli.conservative_effects;
+        conf.strict_errors = cli.strict_errors;
+        conf.freestanding = cli.freestanding;
+        conf.bool_lowering = cli.bool_lowering;
+        conf.shadow_stack = cli.debug;
+        conf.frame_pointer = cli.frame_pointer;
+        conf.function_sections = cli.function_sections;
+        conf.tail_calls = !cli.no_tail_calls;
+        conf.verify_alloc = cli.verify_alloc;
+        conf.verify_stack = cli.verify_stack;
         conf
     };
 
@@ -102,91 +894,282 @@ fn run_cli(cli: &Cli) -> Result<(), String> {
             .map_err(|e| format!("Error reading stdin: {}", e))?,
     };
     let file_info = FileInfo::new(&inp);
-    let raw_ast =
-        ProgParser::new().parse(&inp).map_err(|e| format!("Error parsing program: {}", e))?;
-    match cli.target {
-        Some(AST) => {
-            if let Some(ref args) = cli.execute {
-                let value = interp::ast::Machine::run(&raw_ast, args)
-                    .map_err(|e| format!("Error interpreting program: {}", e))?;
-                println!("{}", value);
-            } else {
-                println!("{}", raw_ast);
-            }
-            return Ok(());
+    ice::input(&inp);
+    if let Some(ref pass) = cli.panic_at {
+        ice::panic_at(pass);
+    }
+
+    // a bare expression rather than a program
+    if outs.wants(ExprAsm) {
+        if cli.execute.is_some() {
+            Err("Cannot execute target expr-asm; link it into a program that calls `entry`")?
         }
-        _ => {}
+        ice::enter("expression compilation");
+        let asm = compile::compile_expr(&inp, &cli.params, &conf)
+            .map_err(|e| Failure::compile(e, &file_info))?;
+        outs.emit(ExprAsm, asm)?;
+        return Ok(());
     }
 
-    // frontend: resolve
-    let mut resolver = Resolver::new();
-    let resolved_ast = resolver
-        .resolve_prog(raw_ast)
-        .map_err(|e| format!("Error resolving ast: {}", file_info.report_error(e)))?;
+    ice::enter("parsing");
+    let limits = Limits { depth: cli.max_depth, size: cli.max_size };
+    let raw_ast = compile::parse_with(&inp, &limits)?;
+    if outs.wants(AST) {
+        if let Some(ref args) = cli.execute {
+            let services = interp::Services::from_env().bigint(cli.bigint);
+            let value = interpret_ast(&raw_ast, args, services, &file_info)?;
+            println!("{}", value);
+        } else {
+            outs.emit(AST, Width(cli.width, &raw_ast))?;
+        }
+    }
+    if outs.done(AST) {
+        return Ok(());
+    }
 
-    match cli.target {
-        Some(ResolvedAST) => {
-            if let Some(ref args) = cli.execute {
-                let value = interp::ast::Machine::run(&resolved_ast, args)
-                    .map_err(|e| format!("Error interpreting program: {}", e))?;
-                println!("{}", value);
-            } else {
-                println!("{}", resolved_ast);
-            }
-            return Ok(());
+    // frontend: resolve, and infer the types
+    ice::enter("resolution");
+    let types = if outs.wants(Types) { TypeMode::Infer } else { cli.types };
+    let mut resolver = Resolver::new().bigint(cli.bigint).types(types);
+    let mut resolved_ast =
+        resolver.resolve_prog(raw_ast).map_err(|e| Failure::compile(e.into(), &file_info))?;
+    let inferred = match resolver.inferred.take() {
+        Some(inferred) => Some(inferred.map_err(|e| Failure::compile(e.into(), &file_info))?),
+        None => None,
+    };
+
+    let scopes = (outs.wants(Scopes) || cli.warnings.contains(&Warning::Shadow))
+        .then(|| scopes::scopes(&resolved_ast));
+    if let Some(scopes) = scopes.as_ref().filter(|_| cli.warnings.contains(&Warning::Shadow)) {
+        for shadowing in scopes.shadowings.iter() {
+            eprintln!("{}", shadowing.report(&file_info));
+        }
+    }
+
+    // frontend: evaluate with the arguments known at compile time
+    if let Some(ref args) = cli.const_args {
+        ice::enter("compile-time evaluation");
+        match consteval::evaluate(&resolved_ast, args, cli.const_fuel, cli.bigint) {
+            Ok(body) => resolved_ast.body = body,
+            Err(e) => eprintln!("note: not evaluated at compile time: {}", e),
+        }
+    }
+
+    if outs.wants(ResolvedAST) {
+        if let Some(ref args) = cli.execute {
+            let services = interp::Services::from_env().bigint(cli.bigint);
+            let value = interpret_ast(&resolved_ast, args, services, &file_info)?;
+            println!("{}", value);
+        } else {
+            outs.emit(ResolvedAST, Width(cli.width, &resolved_ast))?;
+        }
+    }
+    if outs.done(ResolvedAST) {
+        return Ok(());
+    }
+
+    if let Some(scopes) = scopes.as_ref().filter(|_| outs.wants(Scopes)) {
+        if cli.execute.is_some() {
+            Err("Cannot execute target scopes")?
+        }
+        let tree = scopes.render(&file_info);
+        outs.emit(Scopes, format!("{}\n\n{}", Width(cli.width, &resolved_ast), tree))?;
+    }
+    if outs.done(Scopes) {
+        return Ok(());
+    }
+
+    if let Some(inferred) = inferred.as_ref().filter(|_| outs.wants(Types)) {
+        if cli.execute.is_some() {
+            Err("Cannot execute target types")?
         }
-        _ => {}
+        outs.emit(Types, inferred.render(&file_info))?;
+    }
+    if outs.done(Types) {
+        return Ok(());
     }
 
     // middle-end: lower to SSA
+    ice::enter("lowering");
     let mut lowerer = Lowerer::from(resolver);
     let ssa = lowerer.lower_prog(resolved_ast);
+    // optimizations introduce no variables but the bounds of combined checks and the copies
+    // of specialized functions, which keep their unique names, so the names stay the same in
+    // later outputs
+    let names = cli.readable_names.then(|| ReadableNames::new(&ssa.vars()));
+    let names = names.as_ref();
 
     // middle-end: optimizations on SSA
+    let mut remarks: Vec<Remark> = Vec::new();
     let ssa = {
         let mut fixed = ssa;
+        if conf.verbose >= Verbosity::Moderate && !conf.optimizations.is_empty() {
+            println!("[[error ordering]]");
+            println!("{}", conf.error_ordering());
+            println!();
+        }
         if conf.verbose >= Verbosity::Moderate {
             println!("[[lowering]]");
-            println!("{}", fixed);
+            println!("{}", named(names, &fixed));
+        }
+        if conf.optimizations.contains(&Optimization::Loopify) {
+            ice::enter_ssa("loopify", &fixed);
+            let mut loopifier = Loopifier::new();
+            fixed = loopifier.run(fixed);
+            remarks.extend_from_slice(loopifier.remarks());
+            if conf.verbose >= Verbosity::Moderate {
+                println!("[[loopify]]");
+                println!("looped calls: {}", loopifier.looped());
+                println!("{}", named(names, &fixed));
+            }
+        }
+        if conf.optimizations.contains(&Optimization::ConditionCanonicalization) {
+            ice::enter_ssa("condition canonicalization", &fixed);
+            fixed = ConditionCanonicalizer::new().run(fixed);
+            if conf.verbose >= Verbosity::Moderate {
+                println!("[[condition canonicalization]]");
+                println!("{}", named(names, &fixed));
+            }
+        }
+        if conf.optimizations.contains(&Optimization::MemoryForwarding) {
+            ice::enter_ssa("memory forwarding", &fixed);
+            let mut forwarder = LoadForwarder::new(&fixed);
+            fixed = forwarder.run(fixed);
+            remarks.extend_from_slice(forwarder.remarks());
+            if conf.verbose >= Verbosity::Moderate {
+                println!("[[memory forwarding]]");
+                println!("forwarded loads: {}", forwarder.forwarded());
+                println!("{}", named(names, &fixed));
+            }
+        }
+        if conf.optimizations.contains(&Optimization::InterproceduralConstantPropagation) {
+            ice::enter_ssa("interprocedural constant propagation", &fixed);
+            let mut specializer = ConstantSpecializer::new();
+            fixed = specializer.run(fixed, &mut lowerer.names);
+            remarks.extend_from_slice(specializer.remarks());
+            if conf.verbose >= Verbosity::Moderate {
+                println!("[[interprocedural constant propagation]]");
+                for note in specializer.notes() {
+                    println!("{}", note);
+                }
+                println!("{}", named(names, &fixed));
+            }
+        }
+        if conf.optimizations.contains(&Optimization::TypeSpecialization) {
+            ice::enter_ssa("type specialization", &fixed);
+            let mut specializer = TypeSpecializer::new();
+            fixed = specializer.run(fixed, &mut lowerer.names);
+            remarks.extend_from_slice(specializer.remarks());
+            if conf.verbose >= Verbosity::Moderate {
+                println!("[[type specialization]]");
+                for note in specializer.notes() {
+                    println!("{}", note);
+                }
+                println!("removed type assertions: {}", specializer.removed());
+                println!("{}", named(names, &fixed));
+            }
         }
         if conf.optimizations.contains(&Optimization::CopyPropagation) {
+            ice::enter_ssa("copy propagation", &fixed);
             fixed = CopyPropagator::new().run(fixed);
             if conf.verbose >= Verbosity::Moderate {
                 println!("[[copy propagation]]");
-                println!("{}", fixed);
+                println!("{}", named(names, &fixed));
+            }
+        }
+        if conf.optimizations.contains(&Optimization::BoundsCheckCombining) {
+            ice::enter_ssa("bounds-check combining", &fixed);
+            let mut combiner = BoundsCheckCombiner::new().strict(conf.strict_errors);
+            fixed = combiner.run(fixed, &mut lowerer.names);
+            remarks.extend_from_slice(combiner.remarks());
+            if conf.verbose >= Verbosity::Moderate {
+                println!("[[bounds-check combining]]");
+                println!("merged bounds checks: {}", combiner.merged());
+                println!("{}", named(names, &fixed));
             }
         }
         if conf.optimizations.contains(&Optimization::AssertionRemoval) {
+            ice::enter_ssa("assertion removal", &fixed);
             fixed = AssertionRemover::new(&fixed).optimize(fixed);
             if conf.verbose >= Verbosity::Moderate {
                 println!("[[assertion removal]]");
-                println!("{}", fixed);
+                println!("{}", named(names, &fixed));
+            }
+        }
+        if conf.optimizations.contains(&Optimization::AssertionHoisting) {
+            ice::enter_ssa("assertion hoisting", &fixed);
+            fixed = AssertionHoister::new(&fixed).run(fixed);
+            if conf.verbose >= Verbosity::Moderate {
+                println!("[[assertion hoisting]]");
+                println!("{}", named(names, &fixed));
+            }
+        }
+        if conf.optimizations.contains(&Optimization::ColdOutlining) {
+            ice::enter_ssa("cold outlining", &fixed);
+            let mut outliner = ColdOutliner::new();
+            fixed = outliner.run(fixed, &mut lowerer.names);
+            remarks.extend_from_slice(outliner.remarks());
+            if conf.verbose >= Verbosity::Moderate {
+                println!("[[cold outlining]]");
+                println!("outlined blocks: {}", outliner.outlined());
+                println!("{}", named(names, &fixed));
             }
         }
         fixed
     };
+    if let Some(format) = cli.remarks {
+        for remark in remarks.iter() {
+            eprintln!("{}", remark.write(format));
+        }
+    }
+
+    if let Some(ref path) = cli.dom_tree {
+        DominatorAnalysis::new(&ssa).dot(path);
+    }
 
-    match cli.target {
-        Some(SSA) => {
-            if let Some(ref args) = cli.execute {
-                let mut interp = interp::ssa::Interp::new();
-                let value = interp
-                    .run(&ssa, args)
-                    .map_err(|e| format!("Error interpreting program: {}", e))?;
-                println!("{}", value);
-            } else {
-                // only print SSA if not printed above under higher verbosity
-                if conf.verbose < Verbosity::Moderate {
-                    println!("{}", ssa);
+    if outs.wants(SSA) {
+        if let Some(ref args) = cli.execute {
+            ice::enter_ssa("SSA interpretation", &ssa);
+            let mut interp = interp::ssa::Interp::new();
+            let value = interp.run(&ssa, args).map_err(|e| {
+                let trace = interp::render_backtrace(&interp.backtrace(), &file_info);
+                format!("Error interpreting program: {}\n{}", e, trace)
+            })?;
+            println!("{}", value);
+        } else if let Some(Annotation::Liveness) = cli.annotate {
+            let live = LivenessAnalyzer::new(&ssa).analyze(ssa.clone());
+            outs.emit(SSA, LivenessAnnotated(&live))?;
+        } else if let Some(Annotation::Memory) = cli.annotate {
+            let mut analyzer = MemoryAnalyzer::new(&ssa);
+            let annotated = analyzer.analyze(ssa.clone());
+            outs.emit(SSA, MemoryAnnotated(&annotated, analyzer.memory()))?;
+        } else {
+            // only print SSA if not printed above under higher verbosity
+            if !outs.to_stdout() || conf.verbose < Verbosity::Moderate {
+                match names {
+                    Some(names) => outs.emit(SSA, Width(cli.width, &Readable(names, &ssa)))?,
+                    None => outs.emit(SSA, Width(cli.width, &ssa))?,
                 }
             }
-            return Ok(());
         }
-        _ => {}
+    }
+    if outs.done(SSA) {
+        return Ok(());
+    }
+
+    if outs.wants(IndVars) {
+        if cli.execute.is_some() {
+            Err("Cannot execute target indvars")?
+        }
+        outs.emit(IndVars, InductionAnalyzer::new(&ssa).vars())?;
+    }
+    if outs.done(IndVars) {
+        return Ok(());
     }
 
     // backend: analysis: liveness analysis (initial)
     // from this step on, the correct liveness analysis result is always attached to ssa
+    ice::enter_ssa("liveness analysis", &ssa);
     let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
 
     // backend: optimization: dead code elimination
@@ -197,9 +1180,21 @@ fn run_cli(cli: &Cli) -> Result<(), String> {
         if conf.optimizations.contains(&Optimization::DeadCodeElimination) {
             let mut round = 0;
             loop {
-                let mut remover = UnusedRemover::new();
+                ice::enter_ssa("dead code elimination", &live);
+                let effects = EffectAnalysis::new(&live, conf.conservative_effects);
+                if conf.verbose >= Verbosity::Moderate {
+                    println!("[[pure functions]]");
+                    println!("{}", effects);
+                    println!();
+                }
+                let mut remover = UnusedRemover::with_pure(effects.pure).strict(conf.strict_errors);
                 let ssa = remover.run(live);
                 live = LivenessAnalyzer::new(&ssa).analyze(ssa);
+                if conf.verbose >= Verbosity::Moderate && !remover.signatures().is_empty() {
+                    println!("[[changed signatures]]");
+                    println!("{}", remover.signatures());
+                    println!();
+                }
 
                 match remover.progress() {
                     None => {
@@ -238,34 +1233,68 @@ fn run_cli(cli: &Cli) -> Result<(), String> {
         println!("{:?}", ssa);
     }
 
+    // backend: analysis: leaf functions and register pressure
+    let leaves = LeafAnalysis::new(&ssa);
+    if outs.wants(Pressure) {
+        if cli.execute.is_some() {
+            Err("Cannot execute target pressure")?
+        }
+        outs.emit(Pressure, PressureReport::new(&ssa, &leaves))?;
+    }
+    if outs.done(Pressure) {
+        return Ok(());
+    }
+
     // backend: optimization: register allocation
+    ice::enter_ssa("register allocation", &ssa);
     let conflicts = ConflictAnalysis::new(&ssa);
+    let registers = cli.registers.clone().unwrap_or_default().registers().to_vec();
+    RegisterAllocator::check_registers(&registers)?;
+    // the frame pointer takes rbp out of any selection
+    let keep_fp = conf.frame_pointer == Some(FramePointer::Keep);
+    let registers: Vec<Reg> =
+        registers.into_iter().filter(|reg| !keep_fp || *reg != Reg::Rbp).collect();
+    if conf.frame_pointer == Some(FramePointer::Omit) {
+        if let Some(TargetTriple::Darwin) = cli.target_triple.or(TargetTriple::host()) {
+            Err("--frame-pointer omit only writes the unwind tables of ELF targets")?
+        }
+    }
+    if cli.function_sections {
+        if let Some(TargetTriple::Darwin) = cli.target_triple.or(TargetTriple::host()) {
+            Err("--function-sections only supports ELF targets")?
+        }
+    }
     if let Some(ref path) = cli.interference {
-        conflicts.interference.dot(path);
+        if cli.interference_colored {
+            // color on the side, since the requested targets may end before the allocation
+            let mut allocator = RegisterAllocator::new();
+            allocator.graph_color(ConflictAnalysis::new(&ssa), &registers, false);
+            conflicts.interference.dot_colored(path, &allocator.assignment);
+        } else {
+            conflicts.interference.dot(path);
+        }
     }
-    match cli.target {
-        Some(Graph) => {
-            // backend: optimization: register allocation - interference graph
-            if conf.verbose >= Verbosity::Moderate {
-                println!("[[interference graph]]");
-            }
-            println!("{}", conflicts.interference);
-            return Ok(());
+    if outs.wants(Graph) {
+        // backend: optimization: register allocation - interference graph
+        if outs.to_stdout() && conf.verbose >= Verbosity::Moderate {
+            println!("[[interference graph]]");
         }
-        Some(ElimOrder) => {
-            // backend: optimization: register allocation - perfect elimination order
-            if conf.verbose >= Verbosity::Moderate {
-                println!("[[perfect elimination order]]");
-            }
-            println!("{}", conflicts.order);
-            return Ok(());
+        outs.emit(Graph, named(names, &conflicts.interference))?;
+    }
+    if outs.wants(ElimOrder) {
+        // backend: optimization: register allocation - perfect elimination order
+        if outs.to_stdout() && conf.verbose >= Verbosity::Moderate {
+            println!("[[perfect elimination order]]");
         }
-        _ => {}
+        outs.emit(ElimOrder, named(names, &conflicts.order))?;
+    }
+    if outs.done(Graph) || outs.done(ElimOrder) {
+        return Ok(());
     }
     if conf.verbose >= Verbosity::Moderate {
         println!("[[interference graph]]");
         if conf.verbose >= Verbosity::Mouthful {
-            println!("{}", conflicts.interference);
+            println!("{}", named(names, &conflicts.interference));
         } else {
             println!("(omitted)");
             println!();
@@ -273,15 +1302,11 @@ fn run_cli(cli: &Cli) -> Result<(), String> {
     }
     if conf.verbose >= Verbosity::Moderate {
         println!("[[perfect elimination order]]");
-        println!("{}", conflicts.order);
+        println!("{}", named(names, &conflicts.order));
         println!();
     }
 
     // backend: optimization: register allocation - registers available
-    let registers = match &cli.registers {
-        Some(selection) => selection.to_registers(),
-        None => Reg::ALLOCATABLE.to_vec(),
-    };
     if conf.verbose >= Verbosity::Moderate {
         println!("[[registers available]]");
         if registers.is_empty() {
@@ -294,45 +1319,104 @@ fn run_cli(cli: &Cli) -> Result<(), String> {
     }
 
     // backend: optimization: register allocation - graph coloring
-    let mut allocator = RegisterAllocator::new();
+    let leaf = conf.optimizations.contains(&Optimization::LeafFunctions);
+    let mut allocator = RegisterAllocator::new().keep_frame_pointer(keep_fp);
+    if leaf {
+        allocator = allocator.prefer_volatile(leaves.leaf_vars());
+    }
     if conf.verbose >= Verbosity::Mouthful {
         println!("[[coloring trace]]");
     }
-    allocator.graph_color(
-        conflicts,
-        &registers,
-        matches!(cli.target, Some(Coloring)) || conf.verbose >= Verbosity::Mouthful,
-    );
-    match cli.target {
-        Some(Coloring) => {
+    let log = (outs.to_stdout() && outs.wants(Coloring)) || conf.verbose >= Verbosity::Mouthful;
+    if log && names.is_some() {
+        // the allocator's own trace would show the unique names
+        println!("Elimination order:\n{}", named(names, &conflicts.order));
+        println!("Register order:\n{:?}", registers);
+    }
+    allocator.graph_color(conflicts, &registers, log && names.is_none());
+    if conf.verify_alloc || cfg!(debug_assertions) {
+        ice::enter("allocation verification");
+        if let Err(problems) = allocator.verify(&ssa, &registers) {
+            panic!("the allocation is broken:\n{}", problems.join("\n"));
+        }
+    }
+    if outs.wants(Coloring) {
+        if outs.to_stdout() {
             println!();
             if conf.verbose >= Verbosity::Moderate {
                 println!("[[coloring]]");
             }
-            println!("{}", allocator.assignment);
-            return Ok(());
         }
-        _ => {}
+        outs.emit(Coloring, named(names, &allocator.assignment))?;
+    }
+    if outs.done(Coloring) {
+        return Ok(());
     }
     if conf.verbose >= Verbosity::Moderate {
         println!();
         println!("[[coloring]]");
-        println!("{}", allocator.assignment);
+        println!("{}", named(names, &allocator.assignment));
     }
 
     // backend: code generation
-    let mut emitter = Emitter::from(allocator);
+    ice::enter("code generation");
+    if conf.freestanding {
+        compile::check_freestanding(&ssa)?;
+    }
+    let frameless = if leaf { leaves.frameless(&allocator.assignment) } else { HashSet::new() };
+    if leaf && conf.verbose >= Verbosity::Moderate {
+        println!("[[leaf functions]]");
+        println!("blocks without a frame: {}", frameless.len());
+        println!();
+    }
+    let mut emitter = Emitter::from(allocator)
+        .frameless(frameless)
+        .for_size(conf.optimizations.contains(&Optimization::Size))
+        .freestanding(conf.freestanding)
+        .bool_lowering(conf.bool_lowering)
+        .shadow_stack(conf.shadow_stack)
+        .frame_pointer(conf.frame_pointer)
+        .function_sections(conf.function_sections)
+        .tail_calls(conf.tail_calls);
     emitter.emit_prog(&ssa);
-    let asm = emitter.to_asm();
-    let txt = instrs_to_string(&asm);
+    let mut asm = emitter.to_asm();
+    if conf.optimizations.contains(&Optimization::Scheduling) {
+        ice::enter("instruction scheduling");
+        asm.schedule();
+    }
+    if conf.optimizations.contains(&Optimization::IdenticalCodeFolding) {
+        ice::enter("identical code folding");
+        asm.fold_identical();
+    }
+    if conf.verify_stack || cfg!(debug_assertions) {
+        ice::enter("stack verification");
+        if let Err(problems) = asm.check_stack(&Emitter::NO_RETURN_EXTERNS) {
+            panic!("the emitted code breaks the stack discipline:\n{}", problems.join("\n"));
+        }
+    }
+    if cli.size_report {
+        eprintln!("{}", SizeReport::new(&ssa, &asm));
+    }
+    if cli.stack_report {
+        eprint!("{}", StackReport::new(&ssa, &asm));
+    }
+    // the rows of the tables are labelled, also between the moves of rsp
+    // and the calls, so that the reports are made before
+    if conf.frame_pointer == Some(FramePointer::Omit) {
+        ice::enter("unwind tables");
+        asm.add_unwind_tables();
+    }
+    let mut txt = asm.to_string();
+    if conf.freestanding {
+        txt.push_str(compile::FREESTANDING_RUNTIME);
+    }
 
-    match (cli.target, &cli.execute) {
-        // Assembly and not execute
-        (Some(Asm) | None, None) => {
-            println!("{}", txt);
+    // Assembly and not execute
+    if outs.wants(Asm) && cli.execute.is_none() {
+        outs.emit(Asm, &txt)?;
+        if outs.done(Asm) {
             return Ok(());
         }
-        _ => {}
     }
     // if the target is assembly and execute is true, we treat it the same as executable execute.
 
@@ -342,22 +1426,104 @@ fn run_cli(cli: &Cli) -> Result<(), String> {
     }
     let rt = cli.runtime.clone().unwrap_or(PathBuf::from("runtime/stub.rs"));
     let o_dir = PathBuf::from("runtime");
-    let exe_fname = cli.output.clone().unwrap_or(PathBuf::from("runtime/stub.exe"));
-    link(&txt, &rt, &o_dir, &exe_fname)?;
+    let exe_fname = match outs.path(Exe) {
+        Some(path) => path,
+        None => cli.output.clone().unwrap_or(PathBuf::from("runtime/stub.exe")),
+    };
+    let link_conf = LinkConf {
+        linker: cli.linker.clone(),
+        args: cli.link_args.clone(),
+        static_crt: cli.static_crt,
+        target: cli.target_triple,
+        gc_sections: cli.function_sections,
+    };
+    if cli.execute.is_some() {
+        check_executable(link_conf.target()?, cli.run_under.as_deref())?;
+    }
+    if conf.freestanding {
+        link_freestanding(&txt, &o_dir, &exe_fname, &link_conf)?;
+    } else {
+        link(&txt, &rt, &o_dir, &exe_fname, &link_conf)?;
+    }
     // if execute is set, run the executable
     if let Some(ref args) = cli.execute {
-        run(&exe_fname, args, &mut std::io::stdout())?;
+        let (status, stderr) =
+            run_status(cli.run_under.as_deref(), &exe_fname, args, &[], &mut std::io::stdout())?;
+        eprint!("{}", stderr);
+        if !status.success() {
+            Err(Failure::Exit(exit_code(status)))?
+        }
     }
     Ok(())
 }
+/// The stack of the thread that compiles, whose passes recurse over expressions as deep as
+/// `--max-depth` lets them nest
+const STACK_SIZE: usize = 256 << 20;
+
 fn main() {
     let cli = Cli::parse();
+    ice::install_hook(cli.ice_dir.clone());
 
-    match run_cli(&cli) {
-        Ok(()) => {}
-        Err(e) => {
-            eprintln!("{}", e);
-            std::process::exit(1);
-        }
+    // the panic hook prints the message of internal compiler errors
+    let compile = move || {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| match cli.command {
+            Some(Command::Test { ref paths, jobs, shard }) => {
+                run_test(paths, jobs.map_or_else(default_jobs, |n| n.get()), shard)
+                    .map_err(Failure::from)
+            }
+            Some(Command::Selfcheck {
+                ref paths,
+                jobs,
+                shard,
+                ref optimizations,
+                bigint,
+                interpret,
+            }) => {
+                let conf = selfcheck::Config {
+                    optimizations: optimizations.clone().into_iter().flatten().collect(),
+                    bigint,
+                    engine: if interpret { selfcheck::Engine::Ssa } else { selfcheck::Engine::Exe },
+                };
+                let jobs = jobs.map_or_else(default_jobs, |n| n.get());
+                run_selfcheck(paths, &conf, jobs, shard).map_err(Failure::from)
+            }
+            Some(Command::Lint { ref file, ref allow, ref deny }) => run_lint(file, allow, deny),
+            Some(Command::Bench {
+                ref live,
+                ref calls,
+                funs,
+                seed,
+                ref registers,
+                ref optimizations,
+                ref corpus,
+            }) => {
+                let registers = registers.clone().unwrap_or_default();
+                let optimizations = optimizations.clone().into_iter().flatten().collect();
+                let shapes = (live.iter())
+                    .flat_map(|&live| calls.iter().map(move |&calls| (live, calls)))
+                    .map(|(live, calls)| stress::Shape { live, calls, funs, seed })
+                    .collect::<Vec<_>>();
+                run_bench(&shapes, registers.registers(), &optimizations, corpus.as_deref())
+            }
+            Some(Command::GenRuntime { ref file, ref output, bigint }) => {
+                run_gen_runtime(file, output.as_deref(), bigint)
+            }
+            Some(Command::Bindgen { ref file, lang, ref output, bigint }) => {
+                run_bindgen(file, lang, output.as_deref(), bigint)
+            }
+            Some(Command::Doc { ref file, format, ref output }) => {
+                run_doc(file, format, output.as_deref())
+            }
+            None => run_cli(&cli),
+        }))
     };
+    let compiler = std::thread::Builder::new().name("main".to_string()).stack_size(STACK_SIZE);
+    let res =
+        compiler.spawn(compile).expect("cannot start the compiler").join().and_then(|res| res);
+    if let Err(failure) = res.unwrap_or(Err(Failure::Internal)) {
+        if let Failure::Compile(_, message) | Failure::Other(message) = &failure {
+            eprintln!("{}", message);
+        }
+        std::process::exit(failure.code());
+    }
 }
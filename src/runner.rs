@@ -1,16 +1,20 @@
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::{BufReader, Read, Write};
-use std::path::Path;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
 
 use crate::ana::Nil;
 use crate::ast::BoundProg;
+use crate::cli::TargetTriple;
 use crate::compile;
+use crate::encode;
+use crate::error::LinkError;
 use crate::frontend::Resolver;
 use crate::identifiers::VarName;
 use crate::middle_end::Lowerer;
 use crate::ssa::Program;
+use crate::txt::FileInfo;
 
 fn handle_errs(r: Result<String, String>) {
     match r {
@@ -24,8 +28,8 @@ fn handle_errs(r: Result<String, String>) {
 
 /// used in student tests
 pub fn emit_ast(p: &Path) -> Result<(Resolver, BoundProg), String> {
-    let (resolver, ast) =
-        compile::frontend(&read_file(p).map_err(|e| format!("Error reading file: {}", e))?)?;
+    let src = read_file(p).map_err(|e| format!("Error reading file: {}", e))?;
+    let (resolver, ast) = compile::frontend(&src).map_err(|e| e.render(&FileInfo::new(&src)))?;
     Ok((resolver, ast))
 }
 
@@ -52,7 +56,8 @@ where
 }
 
 fn compile_file(p: &Path) -> Result<String, String> {
-    compile::compile(&read_file(p).map_err(|e| format!("Error reading file: {}", e))?)
+    let src = read_file(p).map_err(|e| format!("Error reading file: {}", e))?;
+    compile::compile(&src).map_err(|e| e.render(&FileInfo::new(&src)))
 }
 
 pub fn read_file(p: &Path) -> Result<String, std::io::Error> {
@@ -62,25 +67,16 @@ pub fn read_file(p: &Path) -> Result<String, std::io::Error> {
     Ok(buf)
 }
 
-pub fn link(
-    assembly: &str, runtime_file: &Path, dir: &Path, exe_fname: &Path,
-) -> Result<(), String> {
-    let (nasm_format, lib_name) = if cfg!(target_os = "linux") {
-        ("elf64", "libcompiled_code.a")
-    } else if cfg!(target_os = "macos") {
-        ("macho64", "libcompiled_code.a")
-    } else {
-        panic!("Runner script only supports linux and macos")
-    };
-
+/// Assembles `assembly` with nasm into the object file `dir/compiled_code.o`.
+fn assemble(assembly: &str, nasm_format: &str, dir: &Path) -> Result<PathBuf, LinkError> {
     let asm_fname = dir.join("compiled_code.s");
     let obj_fname = dir.join("compiled_code.o");
-    let lib_fname = dir.join(lib_name);
 
     // first put the assembly in a new file compiled_code.s
-    let mut asm_file = File::create(&asm_fname).map_err(|e| e.to_string())?;
-    asm_file.write(assembly.as_bytes()).map_err(|e| e.to_string())?;
-    asm_file.flush().map_err(|e| e.to_string())?;
+    let io = |e: std::io::Error| LinkError::Io(e.to_string());
+    let mut asm_file = File::create(&asm_fname).map_err(io)?;
+    asm_file.write(assembly.as_bytes()).map_err(io)?;
+    asm_file.flush().map_err(io)?;
 
     // nasm -fFORMAT -o compiled_code.o compiled_code.s
     let nasm_out = Command::new("nasm")
@@ -90,63 +86,189 @@ pub fn link(
         .arg(&obj_fname)
         .arg(&asm_fname)
         .output()
-        .map_err(|e| format!("nasm err: {}", e))?;
-    if !nasm_out.status.success() {
-        return Err(format!(
-            "Failure in nasm call: {}\n{}",
-            nasm_out.status,
-            std::str::from_utf8(&nasm_out.stderr).expect("nasm produced invalid UTF-8")
-        ));
+        .map_err(|e| spawn_err("nasm", e))?;
+    check_status("nasm", &nasm_out)?;
+    Ok(obj_fname)
+}
+
+/// How the compiled code is linked with its runtime into an executable.
+#[derive(Debug, Clone, Default)]
+pub struct LinkConf {
+    /// The linker used by rustc, or instead of `ld` for freestanding executables
+    pub linker: Option<PathBuf>,
+    /// Extra arguments passed to the linker
+    pub args: Vec<String>,
+    /// Whether to link the C runtime statically
+    pub static_crt: bool,
+    /// The platform to link for; defaults to the host
+    pub target: Option<TargetTriple>,
+    /// Whether the linker discards the sections that nothing refers to
+    pub gc_sections: bool,
+}
+
+impl LinkConf {
+    /// The platform to link for
+    pub fn target(&self) -> Result<TargetTriple, LinkError> {
+        self.target.or(TargetTriple::host()).ok_or_else(|| {
+            let message = "the host is not a supported target; select one with --target-triple";
+            LinkError::Unsupported(message.to_string())
+        })
+    }
+}
+
+/// Checks that executables for `target` can be run on the host, directly or
+/// through the emulator `run_under`.
+pub fn check_executable(target: TargetTriple, run_under: Option<&Path>) -> Result<(), String> {
+    match TargetTriple::host() {
+        _ if run_under.is_some() => Ok(()),
+        Some(host) if target.runs_on(host) => Ok(()),
+        host => Err(format!(
+            "cannot execute {} executables on {}; pass --run-under <emulator>",
+            target,
+            host.map_or("this host".to_string(), |h| h.to_string())
+        )),
+    }
+}
+
+pub fn link(
+    assembly: &str, runtime_file: &Path, dir: &Path, exe_fname: &Path, conf: &LinkConf,
+) -> Result<(), LinkError> {
+    if cfg!(feature = "hermetic") {
+        return link_hermetic(assembly, runtime_file, exe_fname, conf);
+    }
+    let target = conf.target()?;
+    if conf.static_crt && target == TargetTriple::Darwin {
+        Err(LinkError::Unsupported("--static is not supported on macos".to_string()))?
     }
 
+    let lib_fname = dir.join("libcompiled_code.a");
+    let obj_fname = assemble(assembly, target.nasm_format(), dir)?;
+
     // ar r libcompiled_code.a compiled_code.o
     let ar_out = Command::new("ar")
         .arg("rus")
         .arg(lib_fname)
         .arg(&obj_fname)
         .output()
-        .map_err(|e| (format!("ar err: {}", e)))?;
-    if !ar_out.status.success() {
-        return Err(format!(
-            "Failure in ar call:\n{}\n{}",
-            ar_out.status,
-            std::str::from_utf8(&ar_out.stderr).expect("ar produced invalid UTF-8")
-        ));
-    }
+        .map_err(|e| spawn_err("ar", e))?;
+    check_status("ar", &ar_out)?;
 
     // rustc stub.rs -L tmp
-    let rustc_out = if cfg!(target_os = "macos") {
-        Command::new("rustc")
-            .arg(runtime_file)
-            .arg("--target")
-            .arg("x86_64-apple-darwin")
-            .arg("-C")
-            .arg("panic=abort")
-            .arg("-L")
-            .arg(dir)
-            .arg("-o")
-            .arg(&exe_fname)
-            .output()
-            .map_err(|e| (format!("rustc err: {}", e)))?
-    } else {
-        Command::new("rustc")
-            .arg(runtime_file)
-            .arg("-L")
-            .arg(dir)
-            .arg("-o")
-            .arg(&exe_fname)
-            .output()
-            .map_err(|e| (format!("rustc err: {}", e)))?
-    };
-    if !rustc_out.status.success() {
-        Err(format!(
-            "Failure in rustc call: {}\n{}",
-            rustc_out.status,
-            std::str::from_utf8(&rustc_out.stderr).expect("rustc produced invalid UTF-8")
-        ))
-    } else {
-        Ok(())
+    let mut rustc = Command::new("rustc");
+    rustc.arg(runtime_file);
+    if target == TargetTriple::Darwin {
+        rustc.arg("--target").arg(target.triple()).arg("-C").arg("panic=abort");
+    } else if conf.target.is_some() {
+        rustc.arg("--target").arg(target.triple());
+    }
+    if let Some(linker) = &conf.linker {
+        rustc.arg("-C").arg(format!("linker={}", linker.display()));
+    }
+    for arg in &conf.args {
+        rustc.arg("-C").arg(format!("link-arg={}", arg));
+    }
+    if conf.static_crt {
+        rustc.arg("-C").arg("target-feature=+crt-static");
+    }
+    if conf.gc_sections {
+        rustc.arg("-C").arg("link-arg=-Wl,--gc-sections");
+    }
+    let rustc_out = rustc
+        .arg("-L")
+        .arg(dir)
+        .arg("-o")
+        .arg(exe_fname)
+        .output()
+        .map_err(|e| spawn_err("rustc", e))?;
+    check_status("rustc", &rustc_out)
+}
+
+/// Links `assembly`, which includes the freestanding runtime, into a static
+/// executable without the Rust stub.
+pub fn link_freestanding(
+    assembly: &str, dir: &Path, exe_fname: &Path, conf: &LinkConf,
+) -> Result<(), LinkError> {
+    if !conf.target()?.is_linux() {
+        Err(LinkError::Unsupported("--freestanding only supports x86-64 linux".to_string()))?
+    }
+    if cfg!(feature = "hermetic") {
+        let message = "--freestanding needs nasm and ld, which the hermetic build does without";
+        Err(LinkError::Unsupported(message.to_string()))?
     }
+    let obj_fname = assemble(assembly, "elf64", dir)?;
+
+    // ld -o main.exe compiled_code.o
+    let linker = conf.linker.clone().unwrap_or(PathBuf::from("ld"));
+    let mut ld = Command::new(&linker);
+    if conf.gc_sections {
+        ld.arg("--gc-sections");
+    }
+    let ld_out = ld
+        .args(&conf.args)
+        .arg("-o")
+        .arg(exe_fname)
+        .arg(&obj_fname)
+        .output()
+        .map_err(|e| spawn_err(&linker.display().to_string(), e))?;
+    check_status(&linker.display().to_string(), &ld_out)
+}
+
+/// Links `assembly` without a toolchain: encodes it with the assembler of
+/// `snake_core::encode` and appends the image to the runtime that cargo
+/// builds from `runtime/stub.rs` with the hermetic feature, which loads it.
+fn link_hermetic(
+    assembly: &str, runtime_file: &Path, exe_fname: &Path, conf: &LinkConf,
+) -> Result<(), LinkError> {
+    if runtime_file != Path::new("runtime/stub.rs") {
+        let message = "the hermetic build only links runtime/stub.rs";
+        Err(LinkError::Unsupported(message.to_string()))?
+    }
+    if !conf.target()?.is_linux() {
+        let message = "the hermetic build only supports x86-64 linux";
+        Err(LinkError::Unsupported(message.to_string()))?
+    }
+    if conf.linker.is_some() || !conf.args.is_empty() || conf.static_crt || conf.gc_sections {
+        let message = "the hermetic build takes no linker options, as it runs no linker";
+        Err(LinkError::Unsupported(message.to_string()))?
+    }
+    let asm = encode::parse(assembly).map_err(LinkError::Encode)?;
+    let image = encode::encode(&asm).map_err(LinkError::Encode)?.to_bytes();
+
+    let io = |e: std::io::Error| LinkError::Io(e.to_string());
+    let mut exe = std::fs::read(hermetic_runtime()?).map_err(io)?;
+    exe.extend(&image);
+    exe.extend((image.len() as u64).to_le_bytes());
+    exe.extend(encode::IMAGE_MAGIC);
+    std::fs::write(exe_fname, exe).map_err(io)?;
+    let executable = std::os::unix::fs::PermissionsExt::from_mode(0o755);
+    std::fs::set_permissions(exe_fname, executable).map_err(io)
+}
+
+/// The runtime of the hermetic build, which cargo puts next to `snake` and
+/// one directory above the test executables
+fn hermetic_runtime() -> Result<PathBuf, LinkError> {
+    let exe = std::env::current_exe().map_err(|e| LinkError::Io(e.to_string()))?;
+    let dirs = exe.ancestors().skip(1).take(2);
+    dirs.map(|dir| dir.join("snake-runtime")).find(|path| path.exists()).ok_or_else(|| {
+        let message = "not found next to snake; build it with --features hermetic";
+        spawn_err("snake-runtime", std::io::Error::other(message))
+    })
+}
+
+fn spawn_err(tool: &str, e: std::io::Error) -> LinkError {
+    LinkError::Spawn { tool: tool.to_string(), message: e.to_string() }
+}
+
+/// Fails if the tool that produced `out` failed
+fn check_status(tool: &str, out: &std::process::Output) -> Result<(), LinkError> {
+    if out.status.success() {
+        return Ok(());
+    }
+    Err(LinkError::Failed {
+        tool: tool.to_string(),
+        status: out.status.to_string(),
+        stderr: String::from_utf8_lossy(&out.stderr).into_owned(),
+    })
 }
 
 pub fn run<W, S>(
@@ -156,8 +278,49 @@ where
     W: std::io::Write,
     S: Into<String>,
 {
-    let mut child = Command::new(&exe_fname)
+    run_under(None, exe_fname, args, out)
+}
+
+/// Runs the executable, through `emulator` if given, e.g. `qemu-x86_64`.
+pub fn run_under<W, S>(
+    emulator: Option<&Path>, exe_fname: &Path, args: impl IntoIterator<Item = S>, out: &mut W,
+) -> Result<(), String>
+where
+    W: std::io::Write,
+    S: Into<String>,
+{
+    let (status, stderr) = run_status(emulator, exe_fname, args, &[], out)?;
+    if !status.success() {
+        return Err(format!(
+            "Error code {} when running compiled code Stderr:\n{}",
+            status, stderr
+        ));
+    }
+    Ok(())
+}
+
+/// Runs the executable as `run_under` does, with `envs` added to its
+/// environment, but returns how it exited and its standard error instead of
+/// failing with them.
+pub fn run_status<W, S>(
+    emulator: Option<&Path>, exe_fname: &Path, args: impl IntoIterator<Item = S>,
+    envs: &[(&str, &str)], out: &mut W,
+) -> Result<(ExitStatus, String), String>
+where
+    W: std::io::Write,
+    S: Into<String>,
+{
+    let mut cmd = match emulator {
+        Some(emulator) => {
+            let mut cmd = Command::new(emulator);
+            cmd.arg(exe_fname);
+            cmd
+        }
+        None => Command::new(exe_fname),
+    };
+    let mut child = cmd
         .args(args.into_iter().map(|s| s.into()))
+        .envs(envs.iter().copied())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -173,17 +336,11 @@ where
     }
 
     let status = child.wait().map_err(|e| (format!("Error waiting for child process {}", e)))?;
-    if !status.success() {
-        let mut stderr = String::new();
-        for line in compiled_err.lines() {
-            stderr.push_str(&format!("{}\n", line.unwrap()));
-        }
-        return Err(format!(
-            "Error code {} when running compiled code Stderr:\n{}",
-            status, stderr
-        ));
+    let mut stderr = String::new();
+    for line in compiled_err.lines() {
+        stderr.push_str(&format!("{}\n", line.unwrap()));
     }
-    Ok(())
+    Ok((status, stderr))
 }
 
 pub fn link_and_run<W, S>(
@@ -194,6 +351,6 @@ where
     S: Into<String>,
 {
     let exe_fname = dir.join("main.exe");
-    link(assembly, runtime_file, dir, &exe_fname)?;
+    link(assembly, runtime_file, dir, &exe_fname, &LinkConf::default())?;
     run(&exe_fname, args, out)
 }
@@ -0,0 +1,444 @@
+//! Mutation testing of the compiler for `snake selfcheck`.
+//!
+//! Every program of a corpus is rewritten by transformations that keep its
+//! behavior, and the rewritten program must behave exactly like the
+//! original once compiled: same output, same runtime error. A difference
+//! points at a pass that depends on what the transformation changed, e.g.
+//! the order of bindings or the shape of an arithmetic expression.
+//!
+//! The arguments of a program are given by `# ARGS:` lines, one run each;
+//! without any, the program runs once without arguments. Every run must
+//! terminate.
+
+use crate::ast::*;
+use crate::cli::{CompilerConf, Optimization, Verbosity};
+use crate::compile;
+use crate::frontend::Resolver;
+use crate::interp;
+use crate::runner;
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mutation {
+    /// Renames every variable and local function
+    Rename,
+    /// Splits each `let` of several bindings into nested `let`s
+    SplitLets,
+    /// Swaps the adjacent bindings of a `let` that neither fail nor
+    /// depend on each other
+    CommuteLets,
+    /// Rewrites `(a + b) + c` into `a + (b + c)` where that cannot change an
+    /// overflow: for wrapping arithmetic, and for `+` and `*` under `--bigint`
+    Reassociate,
+}
+
+impl Mutation {
+    pub const ALL: [Mutation; 4] =
+        [Mutation::Rename, Mutation::SplitLets, Mutation::CommuteLets, Mutation::Reassociate];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mutation::Rename => "rename",
+            Mutation::SplitLets => "split-lets",
+            Mutation::CommuteLets => "commute-lets",
+            Mutation::Reassociate => "reassociate",
+        }
+    }
+
+    /// The mutant of `prog`, or `None` if the mutation applies nowhere
+    pub fn apply(&self, prog: &SurfProg, bigint: bool) -> Option<SurfProg> {
+        let mut prog = prog.clone();
+        let changed = match self {
+            Mutation::Rename => {
+                let mut kept: HashSet<_> =
+                    prog.externs.iter().map(|ext| ext.name.clone()).collect();
+                let variants = prog.enums.iter().flat_map(|decl| decl.variants.iter());
+                let ctors: HashSet<_> = variants.map(|v| v.name.clone()).collect();
+                // renamed names never clash with each other, only with the externs
+                // and the constructors, which are kept, and a name that
+                // shadows a constructor would no longer
+                let names = names(&prog.body);
+                let clash = (names.iter())
+                    .any(|x| kept.contains(&renamed(x)) || ctors.contains(&renamed(x)))
+                    || names.iter().any(|x| ctors.contains(x));
+                if clash {
+                    return None;
+                }
+                kept.insert(prog.name.clone());
+                kept.extend(ctors.iter().cloned());
+                prog.param.node = renamed(&prog.param.node);
+                rename(&mut prog.body, &kept, &ctors);
+                true
+            }
+            Mutation::SplitLets => split_lets(&mut prog.body),
+            Mutation::CommuteLets => commute_lets(&mut prog.body),
+            Mutation::Reassociate => reassociate(&mut prog.body, bigint),
+        };
+        changed.then_some(prog)
+    }
+}
+
+/// How programs are run to compare their behavior
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Engine {
+    /// Compiled into an executable, which needs nasm and a linker
+    Exe,
+    /// Lowered to SSA, optimized and interpreted, which leaves out the backend
+    Ssa,
+}
+
+pub struct Config {
+    pub optimizations: HashSet<Optimization>,
+    pub bigint: bool,
+    pub engine: Engine,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    /// The mutation applies nowhere in the program
+    Skip,
+    Fail {
+        args: Vec<String>,
+        expected: String,
+        found: String,
+    },
+}
+
+/// The `# ARGS:` of `src`
+pub fn arg_lines(src: &str) -> Vec<Vec<String>> {
+    let runs: Vec<Vec<String>> = (src.lines())
+        .filter_map(|line| line.trim().strip_prefix("# ARGS:"))
+        .map(|args| args.split_whitespace().map(String::from).collect())
+        .collect();
+    if runs.is_empty() {
+        vec![Vec::new()]
+    } else {
+        runs
+    }
+}
+
+/// Checks every mutation of the program `src`; fails if the program
+/// itself does not compile
+pub fn check(src: &str, conf: &Config) -> Result<Vec<(Mutation, Outcome)>, String> {
+    let prog = compile::parse(src)?;
+    if conf.engine == Engine::Ssa && !prog.externs.is_empty() {
+        Err("the SSA interpreter does not support extern functions")?
+    }
+    let runs = arg_lines(src);
+    let expected = (runs.iter())
+        .map(|args| behavior(prog.clone(), args, conf))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut outcomes = Vec::new();
+    for mutation in Mutation::ALL {
+        let Some(mutant) = mutation.apply(&prog, conf.bigint) else {
+            outcomes.push((mutation, Outcome::Skip));
+            continue;
+        };
+        let mut outcome = Outcome::Pass;
+        for (args, expected) in runs.iter().zip(expected.iter()) {
+            let found = behavior(mutant.clone(), args, conf)
+                .unwrap_or_else(|e| format!("the mutant does not compile: {}", e));
+            if &found != expected {
+                let (args, expected) = (args.clone(), expected.clone());
+                outcome = Outcome::Fail { args, expected, found };
+                break;
+            }
+        }
+        outcomes.push((mutation, outcome));
+    }
+    Ok(outcomes)
+}
+
+/// The output of `prog` on `args`, or its runtime error; fails if `prog`
+/// does not compile
+fn behavior(prog: SurfProg, args: &[String], conf: &Config) -> Result<String, String> {
+    let mut resolver = Resolver::new().bigint(conf.bigint);
+    let ast = resolver.resolve_prog(prog)?;
+    let (lowerer, ssa) = compile::middle_end_with(resolver, ast, &conf.optimizations)?;
+    // optimizations may otherwise report another one of several errors
+    let mut compiler_conf = CompilerConf::new(conf.optimizations.clone(), Verbosity::Minimalistic);
+    compiler_conf.strict_errors = true;
+    match conf.engine {
+        Engine::Ssa => {
+            let ssa = if conf.optimizations.contains(&Optimization::DeadCodeElimination) {
+                compile::dead_code_elimination(ssa, &compiler_conf)
+            } else {
+                ssa
+            };
+            let services = interp::Services::new(Some(0)).bigint(conf.bigint);
+            let mut interp = interp::ssa::Interp::new().with_services(services);
+            Ok(match interp.run(&ssa, args.iter().cloned()) {
+                Ok(value) => value.to_string(),
+                Err(e) => format!("error: {}", e),
+            })
+        }
+        Engine::Exe => {
+            let asm = compile::backend_with(lowerer, ssa, &compiler_conf);
+            let dir = tempfile::TempDir::new().map_err(|e| format!("I/O error: {}", e))?;
+            let exe = dir.path().join("main.exe");
+            let link_conf = runner::LinkConf::default();
+            runner::link(&asm, Path::new("runtime/stub.rs"), dir.path(), &exe, &link_conf)?;
+            let mut out = Vec::new();
+            let res = runner::run(&exe, args.iter().cloned(), &mut out);
+            let out = String::from_utf8_lossy(&out).into_owned();
+            Ok(match res {
+                Ok(()) => out,
+                Err(e) => format!("{}error: {}", out, e),
+            })
+        }
+    }
+}
+
+/* ----------------------------- Transformations ---------------------------- */
+
+fn renamed(x: &str) -> String {
+    format!("r_{}", x)
+}
+
+/// The variables and functions bound in `e`
+fn names(e: &SurfExpr) -> HashSet<String> {
+    let mut names = HashSet::new();
+    each_expr(e, &mut |e| match e {
+        Expr::Let { bindings, .. } => {
+            for b in bindings {
+                names.insert(b.var.node.clone());
+                names.extend(b.elems.iter().flatten().map(|x| x.node.clone()));
+            }
+        }
+        Expr::Match { arms, .. } => {
+            for arm in arms {
+                names.extend(arm.pattern.vars().into_iter().cloned());
+            }
+        }
+        Expr::FunDefs { decls, .. } => {
+            for decl in decls {
+                names.insert(decl.name.clone());
+                names.extend(decl.params.iter().map(|p| p.node.clone()));
+            }
+        }
+        _ => {}
+    });
+    names
+}
+
+/// Calls `f` on `e` and on each of its subexpressions
+fn each_expr(e: &SurfExpr, f: &mut impl FnMut(&SurfExpr)) {
+    f(e);
+    match e {
+        Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => {}
+        Expr::Prim { args, .. } | Expr::Call { args, .. } => {
+            args.iter().for_each(|arg| each_expr(arg, f));
+        }
+        Expr::Let { bindings, body, .. } => {
+            bindings.iter().for_each(|b| each_expr(&b.expr, f));
+            each_expr(body, f);
+        }
+        Expr::If { cond, thn, els, .. } => {
+            each_expr(cond, f);
+            each_expr(thn, f);
+            each_expr(els, f);
+        }
+        Expr::Switch { scrutinee, cases, default, .. } => {
+            each_expr(scrutinee, f);
+            cases.iter().for_each(|case| each_expr(&case.body, f));
+            each_expr(default, f);
+        }
+        Expr::Match { scrutinee, arms, .. } => {
+            each_expr(scrutinee, f);
+            arms.iter().for_each(|arm| each_expr(&arm.body, f));
+        }
+        Expr::FunDefs { decls, body, .. } => {
+            decls.iter().for_each(|decl| each_expr(&decl.body, f));
+            each_expr(body, f);
+        }
+    }
+}
+
+/// Calls `f` on each subexpression of `e`, innermost first, which
+/// returns whether it changed anything
+fn each_expr_mut(e: &mut SurfExpr, f: &mut impl FnMut(&mut SurfExpr) -> bool) -> bool {
+    let changed = match e {
+        Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => false,
+        Expr::Prim { args, .. } | Expr::Call { args, .. } => {
+            args.iter_mut().fold(false, |changed, arg| each_expr_mut(arg, f) | changed)
+        }
+        Expr::Let { bindings, body, .. } => {
+            let changed = bindings
+                .iter_mut()
+                .fold(false, |changed, b| each_expr_mut(&mut b.expr, f) | changed);
+            each_expr_mut(body, f) | changed
+        }
+        Expr::If { cond, thn, els, .. } => {
+            each_expr_mut(cond, f) | each_expr_mut(thn, f) | each_expr_mut(els, f)
+        }
+        Expr::Switch { scrutinee, cases, default, .. } => {
+            let changed = each_expr_mut(scrutinee, f);
+            let changed = (cases.iter_mut())
+                .fold(changed, |changed, case| each_expr_mut(&mut case.body, f) | changed);
+            each_expr_mut(default, f) | changed
+        }
+        Expr::Match { scrutinee, arms, .. } => {
+            let changed = each_expr_mut(scrutinee, f);
+            (arms.iter_mut())
+                .fold(changed, |changed, arm| each_expr_mut(&mut arm.body, f) | changed)
+        }
+        Expr::FunDefs { decls, body, .. } => {
+            let changed = (decls.iter_mut())
+                .fold(false, |changed, decl| each_expr_mut(&mut decl.body, f) | changed);
+            each_expr_mut(body, f) | changed
+        }
+    };
+    f(e) | changed
+}
+
+/// Renames every name but the functions `kept` and the constructors `ctors`
+fn rename(e: &mut SurfExpr, kept: &HashSet<String>, ctors: &HashSet<String>) {
+    fn rename_pattern(pattern: &mut SurfPattern, ctors: &HashSet<String>) {
+        match pattern {
+            Pattern::Wildcard(_) => {}
+            Pattern::Var(x, _) if !ctors.contains(x) => *x = renamed(x),
+            Pattern::Var(..) => {}
+            Pattern::Ctor { args, .. } => {
+                args.iter_mut().for_each(|arg| rename_pattern(arg, ctors))
+            }
+        }
+    }
+    each_expr_mut(e, &mut |e| {
+        match e {
+            Expr::Var(x, _) if !ctors.contains(x) => *x = renamed(x),
+            Expr::Let { bindings, .. } => {
+                for b in bindings {
+                    b.var.node = renamed(&b.var.node);
+                    b.elems.iter_mut().flatten().for_each(|x| x.node = renamed(&x.node));
+                }
+            }
+            Expr::FunDefs { decls, .. } => {
+                for decl in decls {
+                    decl.name = renamed(&decl.name);
+                    decl.params.iter_mut().for_each(|p| p.node = renamed(&p.node));
+                }
+            }
+            Expr::Match { arms, .. } => {
+                arms.iter_mut().for_each(|arm| rename_pattern(&mut arm.pattern, ctors))
+            }
+            Expr::Call { fun, .. } if !kept.contains(fun) => *fun = renamed(fun),
+            _ => {}
+        }
+        true
+    });
+}
+
+fn split_lets(e: &mut SurfExpr) -> bool {
+    each_expr_mut(e, &mut |e| match e {
+        Expr::Let { bindings, body, loc } if bindings.len() > 1 => {
+            let rest = bindings.split_off(1);
+            let mut inner = Expr::Let { bindings: rest, body: body.clone(), loc: *loc };
+            // the inner `let` may split further
+            split_lets(&mut inner);
+            **body = inner;
+            true
+        }
+        _ => false,
+    })
+}
+
+/// Whether evaluating `e` can neither fail nor have an effect
+fn is_total(e: &SurfExpr) -> bool {
+    match e {
+        Expr::Num(..) | Expr::BigNum(..) | Expr::Bool(..) | Expr::Var(..) => true,
+        Expr::Prim { prim: Prim::IsType(_) | Prim::None | Prim::IsNone, args, .. } => {
+            args.iter().all(is_total)
+        }
+        _ => false,
+    }
+}
+
+fn mentions(e: &SurfExpr, x: &str) -> bool {
+    let mut found = false;
+    each_expr(e, &mut |e| found |= matches!(e, Expr::Var(y, _) if y == x));
+    found
+}
+
+fn commute_lets(e: &mut SurfExpr) -> bool {
+    each_expr_mut(e, &mut |e| {
+        let Expr::Let { bindings, .. } = e else {
+            return false;
+        };
+        let mut changed = false;
+        let mut i = 0;
+        while i + 1 < bindings.len() {
+            let (a, b) = (&bindings[i], &bindings[i + 1]);
+            // destructuring may fail, and binds more than `var`, and the
+            // annotations may fail too
+            if is_total(&a.expr)
+                && is_total(&b.expr)
+                && a.elems.is_none()
+                && b.elems.is_none()
+                && a.ty.is_none()
+                && b.ty.is_none()
+                && !mentions(&a.expr, &b.var.node)
+                && !mentions(&b.expr, &a.var.node)
+            {
+                bindings.swap(i, i + 1);
+                changed = true;
+                i += 2;
+            } else {
+                i += 1;
+            }
+        }
+        changed
+    })
+}
+
+/// Whether `e` evaluates to an integer whenever it does not fail
+fn is_int(e: &SurfExpr) -> bool {
+    matches!(
+        e,
+        Expr::Num(..)
+            | Expr::BigNum(..)
+            | Expr::Prim {
+                prim: Prim::Add1
+                    | Prim::Sub1
+                    | Prim::Add
+                    | Prim::Sub
+                    | Prim::Mul
+                    | Prim::WrappingAdd
+                    | Prim::WrappingSub
+                    | Prim::WrappingMul
+                    | Prim::SaturatingAdd
+                    | Prim::SaturatingSub
+                    | Prim::SaturatingMul
+                    | Prim::Length,
+                ..
+            }
+    )
+}
+
+/// `(a + b) + c` into `a + (b + c)`, which evaluates `a`, `b` and `c` in the
+/// same order; their sums cannot overflow, and they are all integers, so
+/// neither sum can fail
+fn reassociate(e: &mut SurfExpr, bigint: bool) -> bool {
+    each_expr_mut(e, &mut |e| {
+        let Expr::Prim { prim, args, loc } = e else {
+            return false;
+        };
+        let associative = match prim {
+            Prim::WrappingAdd | Prim::WrappingMul => !bigint,
+            Prim::Add | Prim::Mul => bigint,
+            _ => false,
+        };
+        let [Expr::Prim { prim: inner, args: inner_args, .. }, c] = args.as_mut_slice() else {
+            return false;
+        };
+        if !associative || inner != prim || !inner_args.iter().chain([&*c]).all(is_int) {
+            return false;
+        }
+        let [a, b] = [inner_args.remove(0), inner_args.remove(0)];
+        let c = std::mem::replace(c, Expr::Bool(false, *loc));
+        let bc = Expr::Prim { prim: prim.clone(), args: vec![b, c], loc: *loc };
+        *args = vec![a, bc];
+        true
+    })
+}
@@ -45,6 +45,37 @@ macro_rules! mk_dyn_fail_test {
     };
 }
 
+/* The following are like mk_test, mk_middle_end_test and mk_dyn_fail_test,
+ * but optimize at -O3, which leaves out assertion removal since its analysis
+ * is not implemented yet
+ */
+macro_rules! mk_o3_test {
+    ($test_name:ident, $file_name:expr, $input:expr, $expected_output:expr) => {
+        #[test]
+        fn $test_name() -> std::io::Result<()> {
+            test_example_o3($file_name, $input, $expected_output)
+        }
+    };
+}
+
+macro_rules! mk_o3_middle_end_test {
+    ($test_name:ident, $file_name:expr, $input:expr, $expected_output:expr) => {
+        #[test]
+        fn $test_name() -> std::io::Result<()> {
+            test_example_o3_middle_end($file_name, $input, $expected_output)
+        }
+    };
+}
+
+macro_rules! mk_o3_dyn_fail_test {
+    ($test_name:ident, $file_name:expr, $input:expr, $expected_output:expr) => {
+        #[test]
+        fn $test_name() -> std::io::Result<()> {
+            test_example_o3_fail($file_name, $input, $expected_output)
+        }
+    };
+}
+
 /*
  * YOUR TESTS GO HERE
  */
@@ -124,239 +155,5071 @@ mod public_diamondback {
     );
 }
 
-mod ana;
-mod graph_parser;
-mod public_optimizations {
-    use super::*;
-    use graph_parser::*;
-    use snake::{backend::*, frontend::*, middle_end::*, parser::*, runner::*, txt::*};
+mod multiple_targets {
+    use std::process::Command;
 
-    mod assertion_removal {
-        use super::*;
+    /// Each target of one compilation is written next to `-o` with its extension, as a run
+    /// for that target alone writes it
+    #[test]
+    fn written_per_target() -> Result<(), String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let snake = |args: &[&str]| -> Result<(), String> {
+            let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+                .arg("examples/switch.dbk")
+                .args(args)
+                .output()
+                .map_err(|e| e.to_string())?;
+            assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+            Ok(())
+        };
+        let base = dir.path().join("out");
+        let targets = ["ssa", "graph", "coloring", "asm"];
+        let mut args = vec!["-o", base.to_str().unwrap()];
+        for target in targets {
+            args.extend(["-t", target]);
+        }
+        snake(&args)?;
+        let read = |path| std::fs::read_to_string(path).map_err(|e| e.to_string());
+        for target in targets {
+            let single = dir.path().join(format!("single.{}", target));
+            snake(&["-t", target, "-o", single.to_str().unwrap()])?;
+            let (txt, single) = (read(base.with_extension(target))?, read(single)?);
+            assert!(!txt.trim().is_empty(), "empty {} output", target);
+            assert_eq!(txt, single, "{} differs from the output of -t {}", target, target);
+        }
+        Ok(())
+    }
+}
 
-        fn test(
-            src_file: impl Into<PathBuf>, expected_ssa_file: impl Into<PathBuf>,
-        ) -> Result<(), String> {
-            let inp =
-                read_file(&src_file.into()).map_err(|e| format!("Error reading file: {}", e))?;
-            let file_info = FileInfo::new(&inp);
-            let raw_ast = ProgParser::new()
-                .parse(&inp)
-                .map_err(|e| format!("Error parsing program: {}", e))?;
+mod builtins {
+    use super::*;
+    use snake::cli::Optimization;
 
-            let mut resolver = Resolver::new();
-            let resolved_ast = resolver
-                .resolve_prog(raw_ast)
-                .map_err(|e| format!("Error resolving ast: {}", file_info.report_error(e)))?;
+    /// Runs the SSA interpreter after copy propagation only
+    fn run_ssa(file: &str) -> Result<String, String> {
+        let (resolver, ast) = runner::emit_ast(Path::new(&format!("examples/{}", file)))?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let mut interp = interp::ssa::Interp::new();
+        Ok(interp.run(&ssa, Vec::<String>::new()).map_err(|e| format!("{}", e))?.to_string())
+    }
 
-            let mut lowerer = Lowerer::from(resolver);
-            let ssa = lowerer.lower_prog(resolved_ast);
-            let ssa = AssertionRemover::new(&ssa).optimize(ssa);
+    #[test]
+    fn seeded_random() -> Result<(), String> {
+        use snake::interp::Services;
+        let (resolver, ast) = runner::emit_ast(Path::new("examples/random.dbk"))?;
+        let by_ast = interp::ast::Machine::run_with(&ast, ["10"], Services::new(Some(7)))
+            .map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new()
+            .with_services(Services::new(Some(7)))
+            .run(&ssa, ["10"])
+            .map_err(|e| e.to_string())?;
+        // the seeded clock reads 0 on its first call
+        assert_eq!(by_ast.to_string(), "5090");
+        assert_eq!(by_ssa.to_string(), "5090");
+        Ok(())
+    }
 
-            let ssa_str = format!("{}", ssa);
-            let expected_ssa_str = read_file(&expected_ssa_file.into())
-                .map_err(|e| format!("Error reading file: {}", e))?;
+    #[test]
+    fn getenv() -> Result<(), String> {
+        // both runs in one test, as the environment is shared by the test threads
+        std::env::set_var("SNAKE_GETENV", "hello");
+        test_example_frontend("getenv.dbk", [], "5").map_err(|e| e.to_string())?;
+        assert_eq!(run_ssa("getenv.dbk")?, "5");
+        std::env::remove_var("SNAKE_GETENV");
+        test_example_frontend("getenv.dbk", [], "-1").map_err(|e| e.to_string())?;
+        assert_eq!(run_ssa("getenv.dbk")?, "-1");
+        Ok(())
+    }
 
-            assert_eq!(ssa_str.trim(), expected_ssa_str.trim());
-            Ok(())
+    #[test]
+    fn runtime_prims_arity() -> Result<(), String> {
+        use snake::identifiers::{FunName, VarName};
+        use snake::interp::{PrimSemantics, RUNTIME_PRIMS};
+        let mut checked = 0;
+        for (file, bigint) in [("random.dbk", false), ("getenv.dbk", false), ("bigint.dbk", true)] {
+            let src = runner::read_file(Path::new(&format!("examples/{}", file)))
+                .map_err(|e| e.to_string())?;
+            let resolver = snake::frontend::Resolver::new().bigint(bigint);
+            let (resolver, ast) = snake::compile::frontend_with(&src, resolver)?;
+            let opts = [Optimization::CopyPropagation].into();
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+            for ext in ssa.externs {
+                let runtime = RUNTIME_PRIMS.iter().find(|(name, ..)| ext.name.hint() == *name);
+                if let Some((name, prim, _)) = runtime {
+                    let arity = PrimSemantics::<VarName, FunName>::of(prim).arity;
+                    assert_eq!(arity, Some(ext.params.len()), "for {}", name);
+                    checked += 1;
+                }
+            }
         }
+        // clock, random, getenv and the big arithmetic
+        assert_eq!(checked, 6);
+        Ok(())
+    }
+}
 
-        #[test]
-        fn simple_add_3() -> Result<(), String> {
-            test("examples/assertions/simple_add.dbk", "examples/assertions/simple_add.ssa")
-        }
-        #[test]
-        fn loop1_3() -> Result<(), String> {
-            test("examples/assertions/loop1.dbk", "examples/assertions/loop1.ssa")
+mod reference;
+mod prim_vectors {
+    use super::*;
+    use snake::cli::Optimization;
+    use snake::interp::InterpErr;
+
+    /// A vector of `examples/prim_vectors.toml`: the result of `expr` on `x`,
+    /// or the kind of its error
+    struct Vector {
+        expr: String,
+        x: String,
+        expected: Result<String, String>,
+    }
+
+    /// The vectors of the corpus, whose lines are `[[vector]]` headers,
+    /// `key = "value"` pairs and comments
+    fn vectors() -> Result<Vec<Vector>, String> {
+        let src = runner::read_file(Path::new("examples/prim_vectors.toml"))
+            .map_err(|e| e.to_string())?;
+        let mut vectors = Vec::new();
+        for line in src.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line == "[[vector]]" {
+                let expected = Err("no expected outcome".to_string());
+                vectors.push(Vector { expr: String::new(), x: String::new(), expected });
+                continue;
+            }
+            let (key, value) = (line.split_once(" = "))
+                .and_then(|(key, value)| Some((key, value.strip_prefix('"')?.strip_suffix('"')?)))
+                .ok_or_else(|| format!("invalid line: {}", line))?;
+            let vector = vectors.last_mut().ok_or_else(|| format!("no vector for {}", line))?;
+            match key {
+                "expr" => vector.expr = value.to_string(),
+                "x" => vector.x = value.to_string(),
+                "result" => vector.expected = Ok(value.to_string()),
+                "error" => vector.expected = Err(value.to_string()),
+                _ => Err(format!("unknown key: {}", key))?,
+            }
         }
-        #[test]
-        fn extern0_3() -> Result<(), String> {
-            test("examples/assertions/extern0.dbk", "examples/assertions/extern0.ssa")
+        Ok(vectors)
+    }
+
+    fn src(vector: &Vector) -> String {
+        format!("def main(args): let x = args[0] in {}", vector.expr)
+    }
+
+    /// The kind of an error of the interpreters
+    pub fn kind<Var, Fun>(e: InterpErr<Var, Fun>) -> String {
+        match e {
+            InterpErr::Overflow => "overflow".to_string(),
+            InterpErr::AssertTypeFailed(_) => "type".to_string(),
+            InterpErr::AssertInBoundsFailed { .. } => "bounds".to_string(),
+            InterpErr::AssertLength => "length".to_string(),
+            InterpErr::OutOfMemory => "memory".to_string(),
+            _ => "other".to_string(),
         }
     }
 
-    mod liveness_and_conflict_analyses {
-        use super::*;
+    /// The outcome of a vector, or why it could not run
+    type Outcome = Result<Result<String, String>, String>;
 
-        fn test(
-            src_file: impl Into<PathBuf>, graph_file: impl Into<PathBuf>,
-        ) -> Result<(), String> {
-            let inp =
-                read_file(&src_file.into()).map_err(|e| format!("Error reading file: {}", e))?;
-            let file_info = FileInfo::new(&inp);
+    /// Runs every vector, and lists those whose outcome is not the expected one
+    fn check(run: impl Fn(&Vector) -> Outcome) -> Result<(), String> {
+        let mut wrong = Vec::new();
+        for vector in vectors()? {
+            let outcome = run(&vector)?;
+            if outcome != vector.expected {
+                wrong.push(format!("{} on {}: {:?}", vector.expr, vector.x, outcome));
+            }
+        }
+        assert!(wrong.is_empty(), "unexpected outcomes:\n{}", wrong.join("\n"));
+        Ok(())
+    }
 
-            let raw_ast = ProgParser::new()
-                .parse(&inp)
-                .map_err(|e| format!("Error parsing program: {}", e))?;
+    #[test]
+    fn ast() -> Result<(), String> {
+        check(|vector| {
+            let (_, ast) = snake::compile::frontend(&src(vector))?;
+            let value = interp::ast::Machine::run(&ast, [vector.x.as_str()]);
+            Ok(value.map(|v| v.to_string()).map_err(kind))
+        })
+    }
 
-            let mut resolver = Resolver::new();
-            let resolved_ast = resolver
-                .resolve_prog(raw_ast)
-                .map_err(|e| format!("Error resolving ast: {}", file_info.report_error(e)))?;
+    #[test]
+    fn reference() -> Result<(), String> {
+        check(|vector| Ok(super::reference::run(&src(vector), &[&vector.x]).map_err(|e| e.kind())))
+    }
 
-            let mut lowerer = Lowerer::from(resolver);
-            let ssa = lowerer.lower_prog(resolved_ast);
-            let ssa = CopyPropagator::new().run(ssa);
+    #[test]
+    fn ssa() -> Result<(), String> {
+        check(|vector| {
+            let (resolver, ast) = snake::compile::frontend(&src(vector))?;
+            let opts = [Optimization::CopyPropagation].into();
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+            let value = interp::ssa::Interp::new().run(&ssa, [vector.x.as_str()]);
+            Ok(value.map(|v| v.to_string()).map_err(kind))
+        })
+    }
 
-            let live_ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
-            let conflicts = ConflictAnalysis::new(&live_ssa);
+    #[test]
+    fn compiled() -> Result<(), String> {
+        // the kinds of errors by the messages of the runtime
+        let kinds = [
+            ("overflowed", "overflow"),
+            ("expected a", "type"),
+            ("out of bounds", "bounds"),
+            ("is negative", "length"),
+            ("out of memory", "memory"),
+        ];
+        let tmp_dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        check(|vector| {
+            let file = tmp_dir.path().join("vector.dbk");
+            std::fs::write(&file, src(vector)).map_err(|e| e.to_string())?;
+            let mut buf = Vec::new();
+            let args = [vector.x.as_str()];
+            // unoptimized, as dead code elimination may drop a failing primitive
+            match compile_and_run_at(&file, OptLevel::O0, tmp_dir.path(), args, &mut buf) {
+                Ok(()) => Ok(Ok(String::from_utf8_lossy(&buf).trim().to_string())),
+                Err(e) => {
+                    let kind = kinds.iter().find(|(msg, _)| e.contains(msg));
+                    Ok(Err(kind.map_or(e.clone(), |(_, kind)| kind.to_string())))
+                }
+            }
+        })
+    }
+}
 
-            let graph = GraphParser::new()
-                .parse(&format!("graph {}", conflicts.interference))
-                .map_err(|e| format!("Error parsing graph: {}", e))?;
-            let expected_graph = GraphParser::new()
-                .parse(&format!(
-                    "graph {}",
-                    read_file(&graph_file.into())
-                        .map_err(|e| format!("Error reading file: {}", e))?
-                ))
-                .map_err(|e| format!("Error parsing graph: {}", e))?;
+/// Runs the examples on the reference evaluator and on the interpreters, and compares
+mod differential {
+    use super::*;
+    use reference::Failure;
+    use snake::cli::Optimization;
+    use snake::interp::{InterpErr, Services, Status, Value};
 
-            ana::should_be_same_graph(&graph, &expected_graph)?;
-            Ok(())
-        }
+    /// The steps that the interpreters take for the programs that the reference evaluator
+    /// finishes, which are many more than its evaluations
+    const STEPS: usize = 100_000_000;
 
-        #[test]
-        fn chain_1() -> Result<(), String> {
-            test("examples/graphs/chain.dbk", "examples/graphs/chain.graph")
-        }
+    type Outcome = Result<String, String>;
 
-        #[test]
-        fn itail_reuse_test_1() -> Result<(), String> {
-            test("examples/graphs/itail_reuse.dbk", "examples/graphs/itail_reuse.graph")
-        }
-        #[test]
-        fn if_test_1() -> Result<(), String> {
-            test("examples/graphs/if.dbk", "examples/graphs/if.graph")
-        }
-        #[test]
-        fn param_test_1() -> Result<(), String> {
-            test("examples/graphs/param.dbk", "examples/graphs/param.graph")
+    fn outcome<Var, Fun>(value: Result<Status, InterpErr<Var, Fun>>) -> Outcome {
+        match value {
+            Ok(Status::Done(Value::FatPtr(_))) => Ok("<array>".to_string()),
+            Ok(Status::Done(value)) => Ok(value.to_string()),
+            Ok(Status::Suspended) => Err("diverged".to_string()),
+            Err(e) => Err(super::prim_vectors::kind(e)),
         }
     }
 
-    mod graph_coloring {
-        use super::*;
-
-        const NO_REG: &'static str = "none";
-        const ONE_REG: &'static str = "none+r8";
-        const TWO_REGS: &'static str = "none+r8+r9";
-        const THREE_REGS: &'static str = "none+r8+r9+r10";
-        const FOUR_REGS: &'static str = "none+r8+r9+r10+r11";
-        const FIVE_REGS: &'static str = "none+r8+r9+r10+r11+r12";
-
-        fn test(
-            regs: &'static str, allow_spills: bool, src_file: impl Into<PathBuf>,
-            expected_graph_file: impl Into<PathBuf>,
-        ) -> Result<(), String> {
-            use snake::cli::RegisterSelection;
-            use std::str::FromStr;
-            let inp =
-                read_file(&src_file.into()).map_err(|e| format!("Error reading file: {}", e))?;
-            let file_info = FileInfo::new(&inp);
+    fn ast(src: &str, args: &[&str]) -> Result<Outcome, String> {
+        let (_, ast) = snake::compile::frontend(src)?;
+        let machine = interp::ast::Machine::start(&ast, args.iter().copied(), Services::new(None));
+        Ok(outcome(machine.and_then(|mut machine| machine.resume(STEPS))))
+    }
 
-            let raw_ast = ProgParser::new()
-                .parse(&inp)
-                .map_err(|e| format!("Error parsing program: {}", e))?;
+    fn ssa(src: &str, args: &[&str], opts: &[Optimization]) -> Result<Outcome, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let opts = opts.iter().cloned().collect();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let mut interp = interp::ssa::Interp::new().with_services(Services::new(None));
+        let status = interp.start(&ssa, args.iter().copied());
+        Ok(outcome(status.and_then(|()| interp.resume(STEPS))))
+    }
 
-            let mut resolver = Resolver::new();
-            let resolved_ast = resolver
-                .resolve_prog(raw_ast)
-                .map_err(|e| format!("Error resolving ast: {}", file_info.report_error(e)))?;
+    #[test]
+    fn examples() -> Result<(), String> {
+        use Optimization::*;
+        // all but assertion removal, which is yours to implement
+        let all = Optimization::VARIANTS.into_iter().filter(|o| *o != AssertionRemoval);
+        let all: Vec<_> = all.collect();
+        let mut files: Vec<_> = std::fs::read_dir("examples")
+            .map_err(|e| e.to_string())?
+            .map(|entry| entry.map(|entry| entry.path()).map_err(|e| e.to_string()))
+            .collect::<Result<_, _>>()?;
+        files.retain(|file| file.extension().is_some_and(|ext| ext == "dbk"));
+        files.sort();
+        let (mut compared, mut wrong) = (0, Vec::new());
+        for file in files.iter() {
+            let src = runner::read_file(file).map_err(|e| e.to_string())?;
+            // programs that do not resolve are the business of the frontend tests
+            if snake::compile::frontend(&src).is_err() {
+                continue;
+            }
+            for args in [&[][..], &["0"], &["1"], &["5"], &["-3"], &["true"], &["2", "3"]] {
+                let expected = match reference::run(&src, args) {
+                    Err(Failure::Unsupported(_) | Failure::Diverged) => continue,
+                    expected => expected.map_err(|e| e.kind()),
+                };
+                compared += 1;
+                let outcomes = [
+                    ("ast", ast(&src, args)?),
+                    ("ssa", ssa(&src, args, &[CopyPropagation])?),
+                    ("ssa -O", ssa(&src, args, &all)?),
+                ];
+                for (engine, outcome) in outcomes {
+                    if outcome != expected {
+                        let run = format!("{} {:?} by {}", file.display(), args, engine);
+                        wrong.push(format!("{}: {:?}, not {:?}", run, outcome, expected));
+                    }
+                }
+            }
+        }
+        assert!(compared > 100, "only {} runs compared", compared);
+        assert!(wrong.is_empty(), "outcomes unlike the reference:\n{}", wrong.join("\n"));
+        Ok(())
+    }
+}
 
-            let mut lowerer = Lowerer::from(resolver);
-            let ssa = lowerer.lower_prog(resolved_ast);
-            let ssa = CopyPropagator::new().run(ssa);
+mod trace {
+    use super::*;
 
-            let live_ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
-            let conflicts = ConflictAnalysis::new(&live_ssa);
+    #[test]
+    fn calls_and_allocs() -> Result<(), String> {
+        let tmp_dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let asm = compile_at(Path::new("examples/out_of_bounds.dbk"), OptLevel::O3)?;
+        let exe = tmp_dir.path().join("main.exe");
+        let stub = Path::new("runtime/stub.rs");
+        runner::link(&asm, stub, tmp_dir.path(), &exe, &Default::default())?;
+        // only the program is traced, not those of the other tests
+        let envs = [("SNAKE_TRACE", "calls,allocs")];
+        let (status, e) = runner::run_status(None, &exe, [] as [&str; 0], &envs, &mut vec![])?;
+        assert!(!status.success());
+        // the arguments, then the array
+        assert!(e.contains("[trace] alloc 0 elements at word 0 of the heap"), "{}", e);
+        assert!(e.contains("[trace] alloc 3 elements"), "{}", e);
+        assert!(e.contains("[trace] call snake_error(5, 4)"), "{}", e);
+        Ok(())
+    }
+}
 
-            let mut allocator = RegisterAllocator::new();
-            allocator.graph_color(
-                conflicts,
-                &RegisterSelection::from_str(regs).unwrap().to_registers(),
-                false,
-            );
-            let coloring =
-                allocator.assignment.iter().map(|(k, v)| (k.to_string(), v.to_owned())).collect();
-            let graph = GraphParser::new()
-                .parse(&format!(
-                    "graph {}",
-                    read_file(&expected_graph_file.into())
-                        .map_err(|e| format!("Error reading file: {}", e))?
-                ))
-                .map_err(|e| format!("Error parsing graph: {}", e))?;
+mod backtrace {
+    use super::*;
+    use snake::cli::{CompilerConf, Verbosity};
+    use snake::interp::Services;
+    use snake::txt::FileInfo;
 
-            let () = crate::ana::valid_coloring(&graph, &coloring)?;
-            assert!(
-                allow_spills || (crate::ana::count_spills(&coloring) == 0),
-                "You spilled when you didn't need to"
-            );
+    const FILE: &str = "examples/backtrace.dbk";
 
-            Ok(())
-        }
+    #[test]
+    fn ast_machine() -> Result<(), String> {
+        let src = runner::read_file(Path::new(FILE)).map_err(|e| e.to_string())?;
+        let ast = snake::compile::parse(&src).map_err(|e| e.to_string())?;
+        let mut machine = interp::ast::Machine::start(&ast, ["2"], Services::new(Some(0)))
+            .map_err(|e| e.to_string())?;
+        assert!(machine.resume(usize::MAX).is_err());
+        let trace = interp::render_backtrace(&machine.backtrace(), &FileInfo::new(&src));
+        let frames: Vec<_> = trace.lines().skip(1).map(str::trim).collect();
+        assert_eq!(frames, ["0: get at line 3", "1: sum at line 6", "2: main at line 8"]);
+        Ok(())
+    }
 
-        #[test]
-        fn chain_1() -> Result<(), String> {
-            test(THREE_REGS, false, "examples/graphs/chain.dbk", "examples/graphs/chain.graph")
-        }
+    #[test]
+    fn ssa_interp() -> Result<(), String> {
+        let (resolver, ast) = runner::emit_ast(Path::new(FILE))?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let mut interp = interp::ssa::Interp::new();
+        assert!(interp.run(&ssa, ["2"]).is_err());
+        let trace = interp.backtrace();
+        let funs: Vec<_> = trace.iter().map(|(fun, _)| fun.hint()).collect();
+        assert_eq!(funs, ["get", "sum", "entry"]);
+        assert!(trace.iter().all(|(_, loc)| loc.is_none()));
+        Ok(())
+    }
 
-        #[test]
-        fn chain_spill_1() -> Result<(), String> {
-            test(TWO_REGS, true, "examples/graphs/chain.dbk", "examples/graphs/chain.graph")
+    #[test]
+    fn shadow_stack() -> Result<(), String> {
+        let (resolver, ast) = runner::emit_ast(Path::new(FILE))?;
+        let (lowerer, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let mut conf = CompilerConf::new([], Verbosity::Minimalistic);
+        conf.shadow_stack = true;
+        let txt = snake::compile::backend_with(lowerer, ssa, &conf);
+        assert!(txt.contains("extern snake_shadow_sp"));
+        for fun in ["get", "sum", "entry"] {
+            assert!(txt.contains(&format!("db `{}`, 0", fun)), "the name of {} is missing", fun);
         }
+        // each function pushes its name on entry and pops it before returning
+        let pushes = txt.matches("add QWORD [r10 + 0], 8").count();
+        let pops = txt.matches("sub QWORD [r10 + 0], 8").count();
+        assert_eq!(pushes, 3);
+        assert_eq!(pops, txt.lines().filter(|l| l.trim() == "ret").count());
+        Ok(())
+    }
+}
 
-        #[test]
-        fn chain_spill_all_1() -> Result<(), String> {
-            test(NO_REG, true, "examples/graphs/chain.dbk", "examples/graphs/chain.graph")
-        }
+mod bigint {
+    use super::*;
+    use snake::cli::Optimization;
+    use snake::frontend::Resolver;
+    use snake::interp::Services;
 
-        #[test]
-        fn if_1() -> Result<(), String> {
-            test(THREE_REGS, false, "examples/graphs/if.dbk", "examples/graphs/if.graph")
-        }
+    #[test]
+    fn interpreters_agree() -> Result<(), String> {
+        let src = runner::read_file(Path::new("examples/bigint.dbk")).map_err(|e| e.to_string())?;
+        let (resolver, ast) = snake::compile::frontend_with(&src, Resolver::new().bigint(true))?;
+        let services = Services::new(None).bigint(true);
+        let by_ast = interp::ast::Machine::run_with(&ast, ["25"], services)
+            .map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, ["25"]).map_err(|e| e.to_string())?;
+        // 25! - 2^64
+        assert_eq!(by_ast.to_string(), "15511191596586912274448384");
+        assert_eq!(by_ssa.to_string(), "15511191596586912274448384");
+        Ok(())
+    }
 
-        #[test]
-        fn itail_reuse_1() -> Result<(), String> {
-            test(
-                ONE_REG,
-                false,
-                "examples/graphs/itail_reuse.dbk",
-                "examples/graphs/itail_reuse.graph",
-            )
-        }
+    #[test]
+    fn small_results_stay_small() -> Result<(), String> {
+        let src = "def main(x): (4611686018427387904 - 1) == 4611686018427387903";
+        let (resolver, ast) = snake::compile::frontend_with(src, Resolver::new().bigint(true))?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let val = interp::ssa::Interp::new().run(&ssa, ["0"]).map_err(|e| e.to_string())?;
+        assert_eq!(val.to_string(), "true");
+        Ok(())
+    }
 
-        #[test]
-        fn itail_reuse_spill_all_1() -> Result<(), String> {
-            test(
-                NO_REG,
-                true,
-                "examples/graphs/itail_reuse.dbk",
-                "examples/graphs/itail_reuse.graph",
-            )
-        }
+    /// The results of `body` on the argument 1 under `--bigint` by the AST and SSA
+    /// interpreters and the compiled program
+    fn run_all(body: &str) -> Result<[String; 3], String> {
+        let src = format!("def main(x): {}", body);
+        let (resolver, ast) = snake::compile::frontend_with(&src, Resolver::new().bigint(true))?;
+        let services = Services::new(None).bigint(true);
+        let by_ast =
+            interp::ast::Machine::run_with(&ast, ["1"], services).map_err(|e| e.to_string())?;
+        let opts = OptLevel::O3.optimizations();
+        let (lowerer, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, ["1"]).map_err(|e| e.to_string())?;
+        let conf = CompilerConf::new(opts, Verbosity::Minimalistic);
+        let asm = snake::compile::backend_with(lowerer, ssa, &conf);
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let mut buf = Vec::new();
+        runner::link_and_run(&asm, Path::new("runtime/stub.rs"), dir.path(), ["1"], &mut buf)?;
+        let compiled = String::from_utf8_lossy(&buf).trim().to_string();
+        Ok([by_ast.to_string(), by_ssa.to_string(), compiled])
+    }
 
-        #[test]
-        fn param_1() -> Result<(), String> {
-            test(TWO_REGS, false, "examples/graphs/param.dbk", "examples/graphs/param.graph")
+    /// Strings share the tag of big integers, but are no integers in any engine
+    #[test]
+    fn strings_are_not_integers() -> Result<(), String> {
+        for (body, expected) in [
+            ("isInt(\"a\")", "false"),
+            ("isBool(\"a\")", "false"),
+            ("isArray(\"a\")", "false"),
+            ("isInt(x[0])", "true"),
+            ("isInt(18446744073709551616)", "true"),
+        ] {
+            assert_eq!(run_all(body)?, [expected; 3].map(String::from), "for {}", body);
         }
+        Ok(())
+    }
 
-        #[test]
-        fn param_spill_1() -> Result<(), String> {
-            test(ONE_REG, true, "examples/graphs/param.dbk", "examples/graphs/param.graph")
-        }
+    #[test]
+    fn literal_overflows_without_flag() {
+        let err = snake::compile::frontend("def main(x): 18446744073709551616").err();
+        assert!(matches!(err, Some(Error::Resolve(ResolveError::IntegerOverflow(i, _)))
+            if i == "18446744073709551616"));
     }
 }
-/*
- * YOUR TESTS END HERE
- */
 
-/* ----------------------- Test Implementation Details ---------------------- */
+mod wrapping {
+    use super::*;
+    use snake::cli::Optimization;
 
-use snake::{interp, runner};
-use std::path::{Path, PathBuf};
+    mk_o3_test!(
+        lcg_and_saturation,
+        "wrapping.dbk",
+        ["10"],
+        "[-1245780700264390457, 4611686018427387903, -4611686018427387904, -4611686018427387904, 1000]"
+    );
 
-fn test_example_file(
+    /// Runs `body` on the arguments with both interpreters, which must agree
+    fn run_both(body: &str, args: [&'static str; 2]) -> Result<String, String> {
+        let src = format!("def main(x): let a = x[0], b = x[1] in {}", body);
+        let (resolver, ast) = snake::compile::frontend(&src)?;
+        let by_ast = interp::ast::Machine::run(&ast, args).map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, args).map_err(|e| e.to_string())?;
+        assert_eq!(by_ast.to_string(), by_ssa.to_string());
+        Ok(by_ast.to_string())
+    }
+
+    #[test]
+    fn interpreters_agree() -> Result<(), String> {
+        let max = "4611686018427387903";
+        let min = "-4611686018427387904";
+        assert_eq!(run_both("wrapping_add(a, b)", [max, "1"])?, min);
+        assert_eq!(run_both("wrapping_sub(a, b)", [min, "1"])?, max);
+        assert_eq!(run_both("wrapping_mul(a, b)", [max, "2"])?, "-2");
+        assert_eq!(run_both("saturating_add(a, b)", [max, "1"])?, max);
+        assert_eq!(run_both("saturating_sub(a, b)", ["-2", max])?, min);
+        assert_eq!(run_both("saturating_mul(a, b)", ["-2147483648", "2147483648"])?, min);
+        assert_eq!(run_both("saturating_mul(a, b)", ["2147483648", "2147483648"])?, max);
+        assert_eq!(
+            run_both("saturating_mul(a, b)", ["-2147483648", "-2147483647"])?,
+            "4611686016279904256"
+        );
+        Ok(())
+    }
+}
+
+mod switch {
+    use super::*;
+    use snake::cli::Optimization;
+
+    mk_o3_test!(days_in_months, "switch.dbk", ["12", "2000"], "[366, 0, 0]");
+
+    /// Runs `main` on the argument with both interpreters, which must agree
+    fn run_both(src: &str, arg: &'static str) -> Result<String, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let by_ast = interp::ast::Machine::run(&ast, [arg]).map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, [arg]).map_err(|e| e.to_string())?;
+        assert_eq!(by_ast.to_string(), by_ssa.to_string());
+        Ok(by_ast.to_string())
+    }
+
+    #[test]
+    fn jump_table() -> Result<(), String> {
+        let src = "def main(x): switch x[0]: | 0: 10 | 1: 11 | 2: 12 | 4: 14 | _: 99";
+        assert_eq!(run_both(src, "2")?, "12");
+        assert_eq!(run_both(src, "3")?, "99");
+        assert_eq!(run_both(src, "-1")?, "99");
+        assert_eq!(run_both(src, "5")?, "99");
+        Ok(())
+    }
+
+    #[test]
+    fn comparison_tree() -> Result<(), String> {
+        let src = "def main(x): switch x[0]: | -100: 1 | 7: 2 | 1000: 3 | _: 4";
+        assert_eq!(run_both(src, "-100")?, "1");
+        assert_eq!(run_both(src, "1000")?, "3");
+        assert_eq!(run_both(src, "8")?, "4");
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_case() {
+        let err = snake::compile::frontend("def main(x): switch x: | 1: 2 | 1: 3 | _: 4").err();
+        assert!(matches!(err, Some(Error::Resolve(ResolveError::DuplicateCase(1, _)))));
+    }
+}
+
+mod doc {
+    use super::*;
+    use snake::cli::DocFormat;
+    use snake::parser::ProgParser;
+    use snake::runner::read_file;
+
+    mk_o3_test!(doc_comments_are_comments, "doc.dbk", ["3"], "14\n14");
+
+    fn render(format: DocFormat) -> Result<String, String> {
+        let src = read_file(Path::new("examples/doc.dbk")).map_err(|e| e.to_string())?;
+        let prog = ProgParser::new().parse(&src).map_err(|e| e.to_string())?;
+        Ok(snake::doc::render("doc", &prog, format))
+    }
+
+    #[test]
+    fn markdown() -> Result<(), String> {
+        let expected = "# doc
+
+## `extern print(x)`
+
+Prints `x` on a line of its own and returns it.
+
+## `def main(args)`
+
+Prints the sum of the first `args[0]` squares.
+
+The squares come from a local helper.
+
+### `def sum(i)`
+
+The sum of the squares from `i` down to 1.
+
+#### `def square(x)`
+
+`x` squared.
+
+### `def twice(x)`
+";
+        assert_eq!(render(DocFormat::Markdown)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn html() -> Result<(), String> {
+        let html = render(DocFormat::Html)?;
+        assert!(html.contains("<h4><code>def square(x)</code></h4>\n<p>`x` squared.</p>"));
+        assert!(html.contains("<p>Prints the sum of the first `args[0]` squares.</p>\n<p>"));
+        assert!(html.contains("<h3><code>def twice(x)</code></h3>\n</body>"));
+        Ok(())
+    }
+}
+
+mod lint {
+    use super::*;
+    use snake::lint::{lint, Rule};
+    use snake::runner::read_file;
+    use snake::txt::FileInfo;
+
+    fn reports(src: &str) -> Result<Vec<String>, String> {
+        let (_, prog) = snake::compile::frontend(src)?;
+        let file_info = FileInfo::new(src);
+        Ok(lint(&prog).iter().map(|d| d.report(&file_info, d.rule == Rule::NoBaseCase)).collect())
+    }
+
+    #[test]
+    fn every_rule() -> Result<(), String> {
+        let src = read_file(Path::new("examples/lint.dbk")).map_err(|e| e.to_string())?;
+        let expected = [
+            "warning[unused-binding]: variable \"unused\" is never used: 3:6-3:12",
+            "error[no-base-case]: function \"spin\" calls itself on every path: 4:2-4:26",
+            "warning[constant-condition]: condition is always false: 8:2-9:79",
+            "warning[index-out-of-bounds]: index 3 is out of bounds of an array of length 3: 8:12-8:16",
+            "warning[constant-condition]: switch on a value that is always 3: 9:8-9:79",
+            "error[no-base-case]: function \"down\" calls itself on every path for (2): 9:41-9:48",
+            "warning[index-out-of-bounds]: index -1 is out of bounds of an array of length 3: 9:61-9:66",
+        ];
+        assert_eq!(reports(&src)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_values_are_not_reported() -> Result<(), String> {
+        let src = "def main(x):
+          let a = newArray(x[0]), n = x[1] in
+          def count(i): if i == n: a else: count(i + 1) in
+          if n > 0: count(0)[n] else: a";
+        assert_eq!(reports(src)?, Vec::<String>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn destructuring() -> Result<(), String> {
+        let src = "def main(x): let [a, b] = [1] in a";
+        let expected = [
+            "warning[index-out-of-bounds]: destructures 2 elements of an array of length 1: \
+             1:17-1:23",
+            "warning[unused-binding]: variable \"b\" is never used: 1:21-1:22",
+        ];
+        assert_eq!(reports(src)?, expected);
+        Ok(())
+    }
+}
+
+mod scopes {
+    use snake::scopes::scopes;
+    use snake::txt::FileInfo;
+    use std::process::Command;
+
+    const SRC: &str =
+        "def main(x):\n  let x = x[0], y = x + 1 in\n  def f(y): let x = y in x in\n  f(x)";
+
+    #[test]
+    fn tree() -> Result<(), String> {
+        let (_, prog) = snake::compile::frontend(SRC)?;
+        let file_info = FileInfo::new(SRC);
+        let expected = [
+            "def entry: x%0 (1:9-1:10)",
+            "  let: x%1 (2:6-2:7) shadows x%0",
+            "    let: y%2 (2:16-2:17)",
+            "      def f@0: y%3 (3:8-3:9) shadows y%2",
+            "        let: x%4 (3:16-3:17) shadows x%1",
+        ];
+        assert_eq!(scopes(&prog).render(&file_info), expected.join("\n"));
+        // neither the array of a destructuring let nor the sibling arms are in scope
+        let src = "enum O: N(a) | S(a)\ndef main(x):\n  let [a, b] = x in\n  \
+                   match S(a):\n  | N(b): b\n  | S(a): a\n  end";
+        let (_, prog) = snake::compile::frontend(src)?;
+        let shadowed: Vec<_> = (scopes(&prog).shadowings.iter())
+            .map(|s| (s.var.node.to_string(), s.shadowed.node.to_string()))
+            .collect();
+        let pairs = [("b%4", "b%2"), ("a%5", "a%1")];
+        assert_eq!(shadowed, pairs.map(|(v, s)| (v.to_string(), s.to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn cli() -> Result<(), String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let file = dir.path().join("prog.dbk");
+        std::fs::write(&file, SRC).map_err(|e| e.to_string())?;
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .arg(&file)
+            .args(["-t", "scopes"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert!(stdout.contains("let x%1 = x%0[0]"), "{}", stdout);
+        assert!(stdout.contains("\n  let: x%1 (2:6-2:7) shadows x%0\n"), "{}", stdout);
+        assert!(out.stderr.is_empty());
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .arg(&file)
+            .args(["-t", "ssa", "-Wshadow"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        let expected = "warning[shadow]: variable \"x\" shadows the one bound at 1:9-1:10: 2:6-2:7";
+        assert_eq!(stderr.lines().next(), Some(expected));
+        assert_eq!(stderr.lines().count(), 3);
+        Ok(())
+    }
+}
+
+mod selfcheck {
+    use snake::cli::Optimization;
+    use snake::runner::read_file;
+    use snake::selfcheck::*;
+    use std::path::Path;
+
+    fn interpreted(optimizations: &[Optimization], bigint: bool) -> Config {
+        Config { optimizations: optimizations.iter().cloned().collect(), bigint, engine: Engine::Ssa }
+    }
+
+    #[test]
+    fn every_mutation_passes() -> Result<(), String> {
+        let src = read_file(Path::new("examples/selfcheck.dbk")).map_err(|e| e.to_string())?;
+        assert_eq!(arg_lines(&src).len(), 3);
+        let optimizations = [Optimization::CopyPropagation, Optimization::DeadCodeElimination];
+        for conf in [interpreted(&[], false), interpreted(&optimizations, false)] {
+            let outcomes = check(&src, &conf)?;
+            let expected: Vec<_> = Mutation::ALL.into_iter().map(|m| (m, Outcome::Pass)).collect();
+            assert_eq!(outcomes, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn reassociation_depends_on_bigint() -> Result<(), String> {
+        let src = "def main(x): (add1(x[0]) + 1) + 2";
+        let reassociated = |bigint| -> Result<Outcome, String> {
+            let outcomes = check(src, &interpreted(&[], bigint))?;
+            Ok(outcomes.into_iter().find(|(m, _)| *m == Mutation::Reassociate).unwrap().1)
+        };
+        // `+` may overflow on either side unless integers are unbounded
+        assert_eq!(reassociated(false)?, Outcome::Skip);
+        assert_eq!(reassociated(true)?, Outcome::Pass);
+        Ok(())
+    }
+
+    #[test]
+    fn mutants_keep_externs_and_main() -> Result<(), String> {
+        let src = "extern print(x)
+            def main(x): let y = print(x) in if y == 0: 0 else: main(0)";
+        let prog = snake::parser::ProgParser::new().parse(src).map_err(|e| e.to_string())?;
+        let mutant = Mutation::Rename.apply(&prog, false).unwrap();
+        let snake::ast::Expr::Let { bindings, body, .. } = mutant.body else { panic!() };
+        assert_eq!(bindings[0].var.node, "r_y");
+        assert!(matches!(bindings[0].expr, snake::ast::Expr::Call { ref fun, .. } if fun == "print"));
+        assert!(matches!(*body, snake::ast::Expr::If { ref els, .. }
+            if matches!(**els, snake::ast::Expr::Call { ref fun, .. } if fun == "main")));
+        Ok(())
+    }
+}
+
+mod spans {
+    use snake::ast::*;
+    use snake::parser::ProgParser;
+    use snake::txt::FileInfo;
+
+    #[test]
+    fn every_node_has_a_span() -> Result<(), String> {
+        let src = "def main(x):\n  let y = x[0] in\n  switch y: | 1: y | _: 0";
+        let prog = ProgParser::new().parse(src).map_err(|e| e.to_string())?;
+        let at = |loc: SrcLoc| &src[loc.start_ix..loc.end_ix];
+        assert_eq!(at(prog.param.loc), "x");
+        let Expr::Let { bindings, body, loc } = &prog.body else { panic!() };
+        assert_eq!(at(bindings[0].var.loc), "y");
+        assert_eq!(at(bindings[0].expr.loc()), "x[0]");
+        let Expr::Switch { cases, default, .. } = &**body else { panic!() };
+        assert_eq!(at(cases[0].label.loc), "1");
+        assert_eq!(at(bindings[0].var.loc.merge(default.loc())), &src[19..]);
+        assert!(loc.contains(body.loc().start_ix) && !body.loc().contains(loc.start_ix));
+        let file_info = FileInfo::new(src);
+        assert_eq!(file_info.line_col(cases[0].label.loc.start_ix), (3, 14));
+        Ok(())
+    }
+}
+
+mod layout {
+    use snake::layout::Width;
+    use snake::parser::ProgParser;
+    use snake::runner::read_file;
+    use std::path::PathBuf;
+
+    fn examples() -> Vec<(PathBuf, String)> {
+        let mut examples: Vec<_> = (std::fs::read_dir("examples").unwrap())
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "dbk"))
+            .map(|path| {
+                let src = read_file(&path).unwrap();
+                (path, src)
+            })
+            .collect();
+        examples.sort();
+        examples
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), String> {
+        for (path, src) in examples() {
+            let Ok(ast) = ProgParser::new().parse(&src) else { continue };
+            for width in [0, 20, 40, 80, 1000] {
+                let printed = Width(width, &ast).to_string();
+                let reparsed = ProgParser::new().parse(&printed).map_err(|e| {
+                    format!("{} at width {} does not parse: {}\n{}", path.display(), width, e, printed)
+                })?;
+                // the one-line `Display` leaves out the spans and parenthesizes every operator
+                assert_eq!(reparsed.to_string(), ast.to_string(), "{}", path.display());
+                assert_eq!(Width(width, &reparsed).to_string(), printed, "{}", path.display());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn breaks_what_does_not_fit() -> Result<(), String> {
+        let src = "def main(x): let a = x[0] + (x[1] * x[2]), b = !(a < 1) in if b: [a, a] else: a[0] := 3";
+        let ast = ProgParser::new().parse(src).map_err(|e| e.to_string())?;
+        let expected = "def main(x): let a = x[0] + x[1] * x[2], b = !(a < 1) in if b: [a, a] else: a[0] := 3";
+        assert_eq!(Width(100, &ast).to_string(), expected);
+        let expected = "\
+def main(x):
+  let
+    a = x[0] + x[1] * x[2],
+    b = !(a < 1)
+  in
+  if b: [a, a] else: a[0] := 3";
+        assert_eq!(Width(40, &ast).to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn ssa_on_one_line_is_display() -> Result<(), String> {
+        for (path, _) in examples() {
+            let Ok((resolver, ast)) = snake::runner::emit_ast(&path) else { continue };
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+            assert_eq!(Width(1 << 20, &ssa).to_string(), ssa.to_string(), "{}", path.display());
+        }
+        Ok(())
+    }
+}
+
+mod resumable {
+    use super::*;
+    use snake::interp::{Services, Status};
+
+    /// Resumes until done, `steps` at a time; the value and how often it was suspended
+    fn drive(
+        mut resume: impl FnMut(usize) -> Result<Status, String>, steps: usize,
+    ) -> Result<(String, usize), String> {
+        let mut suspended = 0;
+        loop {
+            match resume(steps)? {
+                Status::Done(value) => break Ok((value.to_string(), suspended)),
+                Status::Suspended => suspended += 1,
+            }
+        }
+    }
+
+    #[test]
+    fn ast_machine() -> Result<(), String> {
+        let (_, ast) = runner::emit_ast(Path::new("examples/non_tail_factorial.dbk"))?;
+        let mut machine = interp::ast::Machine::start(&ast, ["5"], Services::new(Some(0)))
+            .map_err(|e| e.to_string())?;
+        let (value, suspended) = drive(|n| machine.resume(n).map_err(|e| e.to_string()), 10)?;
+        assert_eq!(value, "120");
+        assert!(suspended > 5, "{}", suspended);
+        // the same value once done
+        assert!(matches!(machine.resume(1), Ok(Status::Done(v)) if v.to_string() == "120"));
+        Ok(())
+    }
+
+    #[test]
+    fn ssa_interp() -> Result<(), String> {
+        let (resolver, ast) = runner::emit_ast(Path::new("examples/non_tail_factorial.dbk"))?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let mut interp = interp::ssa::Interp::new();
+        interp.start(&ssa, ["5"]).map_err(|e| e.to_string())?;
+        let (value, suspended) = drive(|n| interp.resume(n).map_err(|e| e.to_string()), 1)?;
+        assert_eq!(value, "120");
+        let steps = suspended + 1;
+        assert!(matches!(interp.resume(1), Ok(Status::Done(v)) if v.to_string() == "120"));
+        // a larger budget stops less often
+        let mut interp = interp::ssa::Interp::new();
+        interp.start(&ssa, ["5"]).map_err(|e| e.to_string())?;
+        let (_, suspended) = drive(|n| interp.resume(n).map_err(|e| e.to_string()), 8)?;
+        assert_eq!(suspended, (steps - 1) / 8);
+        Ok(())
+    }
+
+    #[test]
+    fn errors_end_the_run() -> Result<(), String> {
+        let src = "def main(x): let a = [1] in a[x[0]]";
+        let (_, ast) = snake::compile::frontend(src)?;
+        let mut machine = interp::ast::Machine::start(&ast, ["3"], Services::new(Some(0)))
+            .map_err(|e| e.to_string())?;
+        let err = drive(|n| machine.resume(n).map_err(|e| e.to_string()), 2).unwrap_err();
+        assert_eq!(err, "3 is out of bounds of [0, 1)");
+        Ok(())
+    }
+}
+
+mod register_sets {
+    use snake::asm::Reg;
+    use snake::conf::{RegisterCollection, RegisterSet, RegisterSetError};
+    use std::str::FromStr;
+
+    #[test]
+    fn parsed() -> Result<(), RegisterSetError> {
+        let set = RegisterSet::from_str("non-volatile+r8-rbp")?;
+        assert_eq!(set.registers(), [Reg::Rbx, Reg::R12, Reg::R13, Reg::R14, Reg::R15, Reg::R8]);
+        let callee = RegisterSet::from_str("callee")?;
+        assert_eq!(callee, RegisterCollection::AllocatableNonVolatile.into());
+        assert_eq!(RegisterSet::from_str("ALL-R9")?.registers().len(), 12);
+        // from left to right
+        assert!(RegisterSet::from_str("none-r8+r8")?.contains(Reg::R8));
+        assert!(!RegisterSet::from_str("none+r8-r8")?.contains(Reg::R8));
+        let set = RegisterSet::from_str("volatile+rbx")?;
+        assert_eq!(RegisterSet::from_str(&set.to_string())?, set);
+        Ok(())
+    }
+
+    #[test]
+    fn rejected() {
+        let err = RegisterSet::from_str("all+r16").unwrap_err();
+        assert_eq!(err, RegisterSetError::UnknownRegister("r16".to_string()));
+        assert!(err.to_string().starts_with("unknown register `r16`, expected one of rax, rbx,"));
+        let err = RegisterSet::from_str("some+r8").unwrap_err();
+        assert_eq!(err, RegisterSetError::UnknownCollection("some+r8".to_string()));
+        assert!(err.to_string().ends_with("all, volatile, caller, non-volatile, callee, none"));
+        assert!(RegisterSet::from_str("all+").is_err());
+        assert!(RegisterSet::from_str("").is_err());
+    }
+
+    #[test]
+    fn algebra() {
+        let all = RegisterSet::default();
+        let volatile = RegisterSet::from(RegisterCollection::AllocatableVolatile);
+        let non_volatile = RegisterSet::from(RegisterCollection::AllocatableNonVolatile);
+        assert_eq!(volatile.union(&non_volatile), all);
+        assert_eq!(all.difference(&volatile), non_volatile);
+        assert_eq!(all.intersection(&volatile), volatile);
+        assert!(volatile.is_subset(&all) && !all.is_subset(&volatile));
+        assert_eq!(volatile.intersection(&non_volatile), RegisterCollection::None.into());
+    }
+}
+
+mod opt_levels {
+    use snake::conf::{CompilerConf, OptLevel, Optimization, OptimizationCollection, Verbosity};
+    use std::collections::HashSet;
+    use std::process::Command;
+    use std::str::FromStr;
+
+    #[test]
+    fn nested() -> Result<(), String> {
+        assert!(OptLevel::O0.optimizations().is_empty());
+        for levels in OptLevel::VARIANTS.windows(2) {
+            assert!(levels[0].optimizations().is_subset(&levels[1].optimizations()));
+            assert_ne!(levels[0].optimizations(), levels[1].optimizations());
+        }
+        let o2: HashSet<_> = OptimizationCollection::from_str("2")?.into_iter().collect();
+        assert_eq!(o2, OptLevel::O2.optimizations());
+        assert!(o2.contains(&Optimization::Loopify));
+        assert!(!o2.contains(&Optimization::TypeSpecialization));
+        assert!(OptimizationCollection::from_str("4").is_err());
+        Ok(())
+    }
+
+    /// `-O2` runs through the whole pipeline, and `--explain-opts` needs no
+    /// program
+    #[test]
+    fn cli() -> Result<(), String> {
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .args(["examples/switch.dbk", "-O2", "-t", "asm"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .arg("--explain-opts")
+            .output()
+            .map_err(|e| e.to_string())?;
+        let txt = String::from_utf8_lossy(&out.stdout);
+        assert!(txt.contains("runs those of the level below and ipcp, tspec"), "{}", txt);
+        for opt in Optimization::VARIANTS {
+            assert!(txt.contains(&format!("  {:<8} ", opt.name())), "{}", txt);
+        }
+        Ok(())
+    }
+
+    /// A level with `--disable` runs the same passes as naming the rest of them
+    #[test]
+    fn disabled() -> Result<(), String> {
+        let mut conf = CompilerConf::new(OptLevel::O2.optimizations(), Verbosity::Minimalistic);
+        conf.disable([Optimization::Loopify, Optimization::CopyPropagation]);
+        assert!(!conf.optimizations.contains(&Optimization::Loopify));
+        assert_eq!(conf.optimizations.len(), OptLevel::O2.optimizations().len() - 2);
+        let ssa = |args: &[&str]| -> Result<String, String> {
+            let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+                .args(["examples/loopify.dbk", "-t", "ssa"])
+                .args(args)
+                .output()
+                .map_err(|e| e.to_string())?;
+            assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+            Ok(String::from_utf8_lossy(&out.stdout).to_string())
+        };
+        let rest = "-O=cp,canon,dce,ah,bcc,mem,cold,leaf";
+        assert_eq!(ssa(&["-O2", "--disable", "loopify", "--disable=sched"])?, ssa(&[rest])?);
+        assert_ne!(ssa(&["-O2"])?, ssa(&[rest])?);
+        Ok(())
+    }
+}
+
+mod errors {
+    use super::*;
+    use snake::asm::Reg;
+    use snake::backend::RegisterAllocator;
+    use snake::error::{AllocError, LinkError, ParseError};
+    use snake::txt::FileInfo;
+
+    #[test]
+    fn stages() {
+        let err = snake::compile::frontend("def main(x): (x").err();
+        assert!(matches!(err, Some(Error::Parse(ParseError { loc: Some(_), .. }))), "{:?}", err);
+        let err = RegisterAllocator::check_registers(&[Reg::Rdi, Reg::R10]).unwrap_err();
+        assert_eq!(err, AllocError::ReservedRegister(Reg::R10));
+        assert!(RegisterAllocator::check_registers(&Reg::ALLOCATABLE).is_ok());
+    }
+
+    #[test]
+    fn rendered_with_lines() {
+        let src = "def main(x):\n  y";
+        let err = snake::compile::frontend(src).err().unwrap();
+        assert_eq!(err.to_string(), "Error resolving ast: variable \"y\" unbound: bytes 15..16");
+        let rendered = err.render(&FileInfo::new(src));
+        assert_eq!(rendered, "Error resolving ast: variable \"y\" unbound: 2:2-2:3");
+    }
+
+    /// Built-in operators and keywords used as names are named in the error
+    #[test]
+    fn reserved_names() {
+        let message = |src: &str| match snake::compile::parse(src) {
+            Err(ParseError { message, loc: Some(loc) }) => Some((message, loc.start_ix)),
+            _ => None,
+        };
+        let builtin = "`length` is a built-in operator and cannot be used as a name here";
+        let found = message("def main(x): let length = 1 in length");
+        assert_eq!(found, Some((format!("{}, found at 17:23", builtin), 17)));
+        let found = message("def main(x): def isInt(y): y in isInt(1)").unwrap();
+        assert!(found.0.starts_with("`isInt` is a built-in operator"), "{}", found.0);
+        let found = message("def main(x): let [a, next] = x in a").unwrap();
+        assert!(found.0.starts_with("`next` is a built-in operator"), "{}", found.0);
+        let found = message("def main(x): let y = 1, random = 2 in y").unwrap();
+        assert!(found.0.starts_with("`random` is a built-in operator"), "{}", found.0);
+        let found = message("def main(x): let if = 1 in 2").unwrap();
+        assert!(found.0.starts_with("`if` is a keyword"), "{}", found.0);
+        // used as a variable rather than called, up to the end of the program too
+        let called = "`length` is a built-in operator and cannot be used as a name, only called as \
+                      `length(...)`";
+        let found = message("def main(x): let y = 1 in length + y");
+        assert_eq!(found, Some((format!("{}, found at 26:32", called), 26)));
+        assert_eq!(message("def main(x): length").map(|(_, at)| at), Some(13));
+        // but other errors list the expected tokens as before
+        let found = message("def main(x): let y = 1 in end").unwrap();
+        assert!(found.0.starts_with("Unrecognized token `end`"), "{}", found.0);
+    }
+
+    /// A binding whose value uses its own variable is told apart from other
+    /// unbound variables, unless an outer variable of the name is shadowed
+    #[test]
+    fn self_reference() {
+        let src = "def main(n):\n  let x = n, y = y + x in\n  y";
+        let err = snake::compile::frontend(src).err().unwrap();
+        assert_eq!(
+            err.render(&FileInfo::new(src)),
+            "Error resolving ast: binding of \"y\" refers to itself: 2:17-2:18, bound at 2:13-2:14"
+        );
+        let src = "def main(n):\n  let [a, b] = [1, b] in\n  a";
+        let err = snake::compile::frontend(src).err();
+        assert!(
+            matches!(err, Some(Error::Resolve(ResolveError::SelfReference { ref name, binding, .. }))
+                if name == "b" && binding.start_ix == 23),
+            "{:?}",
+            err
+        );
+        assert!(snake::compile::frontend("def main(n):\n  let n = n + 1 in\n  n").is_ok());
+        let err = snake::compile::frontend("def main(n):\n  let [a, b] = tuple in\n  a").err();
+        assert!(matches!(err, Some(Error::Resolve(ResolveError::UnboundVariable(..)))), "{:?}", err);
+    }
+
+    #[test]
+    fn unavailable_extern() -> Result<(), String> {
+        let (resolver, ast) = runner::emit_ast(Path::new("examples/extern_big_nine.dbk"))?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let err = snake::compile::check_freestanding(&ssa).unwrap_err();
+        assert!(matches!(err, LinkError::UnavailableExtern(_)), "{:?}", err);
+        Ok(())
+    }
+}
+
+mod consteval {
+    use super::*;
+    use snake::ast::{BoundExpr, Expr, Prim};
+    use snake::consteval::{evaluate, NotConstant, DEFAULT_FUEL};
+    use snake::runner::read_file;
+
+    fn evaluated(file: &str, args: &[&str], fuel: usize) -> Result<BoundExpr, NotConstant> {
+        let src = read_file(Path::new(file)).unwrap();
+        let (_, prog) = snake::compile::frontend(&src).unwrap();
+        let args: Vec<_> = args.iter().map(|arg| arg.to_string()).collect();
+        evaluate(&prog, &args, fuel, false)
+    }
+
+    #[test]
+    fn folds_to_the_result() -> Result<(), String> {
+        let src = read_file(Path::new("examples/pow.dbk")).map_err(|e| e.to_string())?;
+        let (resolver, mut prog) = snake::compile::frontend(&src)?;
+        prog.body = evaluate(&prog, &["3".to_string()], DEFAULT_FUEL, false).unwrap();
+        assert!(matches!(prog.body, Expr::Num(6561, _)));
+        // the arguments of the run no longer matter
+        let (_, ssa) = snake::compile::middle_end_with(resolver, prog, &Default::default())?;
+        let value = snake::interp::ssa::Interp::new().run(&ssa, ["1"]).unwrap();
+        assert_eq!(value.to_string(), "6561");
+        Ok(())
+    }
+
+    #[test]
+    fn not_constant() {
+        let err = |file, args, fuel| evaluated(file, args, fuel).unwrap_err();
+        // `args[0]` is out of bounds
+        assert!(matches!(err("examples/pow.dbk", &[], DEFAULT_FUEL), NotConstant::Failed(_)));
+        assert_eq!(err("examples/pow.dbk", &["3"], 10), NotConstant::OutOfFuel(10));
+        let service = NotConstant::Service(Prim::Clock);
+        assert_eq!(err("examples/random.dbk", &["3"], DEFAULT_FUEL), service);
+        assert_eq!(err("examples/print.dbk", &[], DEFAULT_FUEL), NotConstant::Externs);
+    }
+}
+
+mod typed_externs {
+    use super::*;
+
+    mk_o3_test!(square, "typed_extern.dbk", ["16"], "4\n4");
+    mk_o3_test!(not_square, "typed_extern.dbk", ["10"], "28");
+    mk_o3_dyn_fail_test!(untyped_argument, "typed_extern.dbk", ["true"], "expected a number");
+
+    #[test]
+    fn signatures() {
+        let src = snake::runner::read_file(Path::new("examples/typed_extern.dbk")).unwrap();
+        let prog = snake::compile::parse(&src).unwrap();
+        let signatures: Vec<_> = prog.externs.iter().map(|ext| ext.to_string()).collect();
+        assert_eq!(signatures[0], "extern isqrt(n: Int) -> Int and");
+        assert_eq!(signatures[2], "extern sum_array(arr: Array, scale: Int) -> Int and");
+        assert_eq!(signatures[3], "extern print(x) and");
+        // the types are no keywords
+        assert!(snake::compile::parse("def main(Int): let Bool = Int in Bool").is_ok());
+        let err = snake::compile::parse("extern f(x: Num)\ndef main(x): 1").unwrap_err();
+        assert!(err.to_string().contains("Int"), "{}", err);
+    }
+
+    #[test]
+    fn shims() -> Result<(), String> {
+        let src = "extern isqrt(n: Int) -> Int\nextern print(x)\ndef main(x): print(isqrt(x))";
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let ssa = ssa.to_string();
+        // the calls go through the shim, which untags and tags
+        assert!(ssa.contains("= isqrt_shim@"), "{}", ssa);
+        assert!(ssa.contains("assertInt(n%"), "{}", ssa);
+        assert!(ssa.contains("= isqrt(untagged%"), "{}", ssa);
+        // untyped externs are called directly
+        assert!(ssa.contains("= print(") && !ssa.contains("print_shim"), "{}", ssa);
+        Ok(())
+    }
+}
+
+mod stack_report {
+    use snake::asm::Reg;
+    use snake::backend::*;
+    use snake::runner::read_file;
+    use std::path::Path;
+
+    fn report(src: &str) -> Result<StackReport, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+        let mut allocator = RegisterAllocator::new();
+        allocator.graph_color(ConflictAnalysis::new(&ssa), &Reg::ALLOCATABLE, false);
+        let mut emitter = Emitter::from(allocator);
+        emitter.emit_prog(&ssa);
+        Ok(StackReport::new(&ssa, &emitter.to_asm()))
+    }
+
+    fn bound(report: &StackReport, fun: &str) -> Option<usize> {
+        let row = report.rows.iter().find(|(name, ..)| name.starts_with(fun));
+        row.unwrap_or_else(|| panic!("no {} in\n{}", fun, report)).2
+    }
+
+    #[test]
+    fn calls() -> Result<(), String> {
+        let src = read_file(Path::new("examples/local_non_tail_call.dbk")).unwrap();
+        let report = report(&src)?;
+        // each call reserves a frame and pushes the return address
+        let (foo, entry) = (bound(&report, "foo").unwrap(), bound(&report, "entry").unwrap());
+        assert!(entry >= foo + 16, "{}", report);
+        assert!(report.rows.iter().all(|(.., externs)| *externs), "{}", report);
+        assert!(report.recursive.is_empty(), "{}", report);
+        Ok(())
+    }
+
+    #[test]
+    fn recursion() -> Result<(), String> {
+        let src = read_file(Path::new("examples/non_tail_factorial.dbk")).unwrap();
+        let report = report(&src)?;
+        assert_eq!(bound(&report, "factorial"), None, "{}", report);
+        assert_eq!(bound(&report, "entry"), None, "{}", report);
+        assert!(report.recursive[0].starts_with("factorial"), "{}", report);
+        assert!(report.to_string().contains("may recurse"), "{}", report);
+        Ok(())
+    }
+
+    #[test]
+    fn tail_calls_stay_bounded() -> Result<(), String> {
+        let src = "def main(x):\n\
+                   def even(n): if n == 0: true else: odd(n - 1)\n\
+                   and def odd(n): if n == 0: false else: even(n - 1)\n\
+                   in let e = even(x[0]) in e";
+        let report = report(src)?;
+        assert!(report.recursive.is_empty(), "{}", report);
+        let (even, entry) = (bound(&report, "even").unwrap(), bound(&report, "entry").unwrap());
+        assert_eq!(bound(&report, "odd"), Some(even), "{}", report);
+        assert!(entry > even, "{}", report);
+        Ok(())
+    }
+}
+
+mod gen_runtime {
+    use snake::runner::read_file;
+    use std::path::Path;
+    use std::process::Command;
+
+    fn generate(file: &str, bigint: bool) -> Result<String, String> {
+        let src = read_file(Path::new(file)).map_err(|e| e.to_string())?;
+        let prog = snake::compile::parse(&src)?;
+        let resolver = snake::frontend::Resolver::new().bigint(bigint);
+        let (resolver, ast) = snake::compile::frontend_with(&src, resolver)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        Ok(snake::bindgen::rust_runtime(file, &prog, &ssa.externs))
+    }
+
+    /// Type checks `runtime` as rustc compiles runtimes, without linking it
+    fn compiles(runtime: &str) -> Result<(), String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let file = dir.path().join("runtime.rs");
+        std::fs::write(&file, runtime).map_err(|e| e.to_string())?;
+        let out = Command::new("rustc")
+            .args(["--emit=metadata", "--crate-type=bin", "--out-dir"])
+            .arg(dir.path())
+            .arg(&file)
+            .output()
+            .map_err(|e| e.to_string())?;
+        match out.status.success() {
+            true => Ok(()),
+            false => Err(String::from_utf8_lossy(&out.stderr).into_owned()),
+        }
+    }
+
+    #[test]
+    fn typed_externs() -> Result<(), String> {
+        let runtime = generate("examples/typed_extern.dbk", false)?;
+        for expected in [
+            "#[export_name = \"\\x01isqrt\"]",
+            "fn isqrt(n: i64) -> i64",
+            "fn is_square(n: i64) -> i64",
+            "fn sum_array(arr: *const u64, scale: i64) -> i64",
+            "fn print(x: SnakeValue) -> SnakeValue",
+            "/// the integer square root, in the runtime",
+        ] {
+            assert!(runtime.contains(expected), "{} not in\n{}", expected, runtime);
+        }
+        compiles(&runtime)
+    }
+
+    #[test]
+    fn services_and_keywords() -> Result<(), String> {
+        let runtime = generate("examples/random.dbk", true)?;
+        assert!(runtime.contains("fn snake_random(arg0: SnakeValue) -> SnakeValue"), "{}", runtime);
+        assert!(runtime.contains("fn snake_big_add("), "{}", runtime);
+        compiles(&runtime)?;
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let file = dir.path().join("keywords.dbk");
+        let src = "extern type(self, fn, crate)\ndef main(x): type(1, 2, 3)";
+        std::fs::write(&file, src).map_err(|e| e.to_string())?;
+        let runtime = generate(file.to_str().unwrap(), false)?;
+        let signature = "fn r#type(self_: SnakeValue, r#fn: SnakeValue, crate_: SnakeValue)";
+        assert!(runtime.contains(signature), "{}", runtime);
+        compiles(&runtime)
+    }
+}
+
+mod contracts {
+    use snake::ast::Contract;
+    use snake::backend::EffectAnalysis;
+    use snake::cli::{CompilerConf, Optimization, Verbosity};
+
+    const SRC: &str = "extern hash(x) pure(true) ret(always)\n\
+                       extern check(x) pure(true) ret(always) throws(false)\n\
+                       extern print(x)\n\
+                       def main(x): let h = hash(1), c = check(2), p = print(3) in 5";
+
+    #[test]
+    fn annotations() {
+        let prog = snake::compile::parse(SRC).unwrap();
+        let contracts: Vec<_> = prog.externs.iter().map(|ext| ext.contract).collect();
+        let promised = Contract { pure: true, returns: true, nothrow: false };
+        assert_eq!(contracts[0], promised);
+        assert_eq!(contracts[1], Contract { nothrow: true, ..promised });
+        assert_eq!(contracts[2], Contract::default());
+        assert_eq!(contracts[1].to_string(), " pure(true) ret(always) throws(false)");
+        // the annotations are no keywords
+        assert!(snake::compile::parse("def main(pure): let ret = pure in ret").is_ok());
+        let err = snake::compile::parse("extern f(x) ret(never)\ndef main(x): 1").unwrap_err();
+        assert!(err.to_string().contains("always or any"), "{}", err);
+        let err = snake::compile::parse("extern f(x) total(true)\ndef main(x): 1").unwrap_err();
+        assert!(err.to_string().contains("pure, ret or throws"), "{}", err);
+    }
+
+    #[test]
+    fn pure_externs() -> Result<(), String> {
+        let (resolver, ast) = snake::compile::frontend(SRC)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let pure = |conservative| {
+            let mut pure: Vec<_> = EffectAnalysis::new(&ssa, conservative)
+                .pure
+                .iter()
+                .map(|f| f.hint().to_string())
+                .filter(|f| ["hash", "check", "print"].contains(&f.as_str()))
+                .collect();
+            pure.sort();
+            pure
+        };
+        // hash may throw, and conservative mode trusts no contract
+        assert_eq!(pure(false), ["check"]);
+        assert_eq!(pure(true), Vec::<String>::new());
+        // so dead code elimination removes its unused calls
+        let conf = CompilerConf::new([Optimization::DeadCodeElimination], Verbosity::Minimalistic);
+        let ssa = snake::compile::dead_code_elimination(ssa, &conf).to_string();
+        assert!(!ssa.contains("= check("), "{}", ssa);
+        assert!(ssa.contains("= hash(") && ssa.contains("= print("), "{}", ssa);
+        Ok(())
+    }
+}
+
+mod threads {
+    use super::*;
+    use snake::backend::*;
+    use snake::cli::{CompilerConf, Verbosity};
+    use snake::frontend::Resolver;
+    use snake::identifiers::{FunName, VarName};
+    use snake::interp::{InterpErr, Services, Status, Value};
+    use snake::middle_end::*;
+    use snake::runner::read_file;
+    use snake::{ana, lint, selfcheck};
+
+    fn send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn library_types_are_send_and_sync() {
+        send_sync::<Resolver>();
+        send_sync::<Lowerer>();
+        send_sync::<CopyPropagator>();
+        send_sync::<AssertionRemover>();
+        send_sync::<AssertionHoister>();
+        send_sync::<LivenessAnalyzer>();
+        send_sync::<EffectAnalysis>();
+        send_sync::<DominatorAnalysis>();
+        send_sync::<UnusedRemover>();
+        send_sync::<ConflictAnalysis>();
+        send_sync::<RegisterAllocator>();
+        send_sync::<Emitter>();
+        send_sync::<ana::Coloring>();
+        send_sync::<SizeReport>();
+        send_sync::<lint::Diagnostic>();
+        send_sync::<selfcheck::Outcome>();
+        send_sync::<Error>();
+        send_sync::<Value>();
+        send_sync::<Status>();
+        send_sync::<InterpErr<VarName, FunName>>();
+        send_sync::<Services>();
+        send_sync::<interp::ast::Machine<VarName, FunName>>();
+        send_sync::<interp::ssa::Interp<ana::Nil>>();
+    }
+
+    #[test]
+    fn compile_on_threads() -> Result<(), String> {
+        let src = read_file(Path::new("examples/non_tail_factorial.dbk"))
+            .map_err(|e| e.to_string())?;
+        let compile = |src: &str| -> Result<String, String> {
+            let (resolver, ast) = snake::compile::frontend(src)?;
+            let (lowerer, ssa) =
+                snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+            let conf = CompilerConf::new([], Verbosity::Minimalistic);
+            Ok(snake::compile::backend_with(lowerer, ssa, &conf))
+        };
+        let expected = compile(&src)?;
+        let (_, ast) = snake::compile::frontend(&src)?;
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let services = Services::new(Some(0));
+                        let value = interp::ast::Machine::run_with(&ast, ["5"], services);
+                        (compile(&src), value.map(|v| v.to_string()))
+                    })
+                })
+                .collect();
+            for handle in handles {
+                let (asm, value) = handle.join().unwrap();
+                assert_eq!(asm?, expected);
+                assert_eq!(value.map_err(|e| e.to_string())?, "120");
+            }
+            Ok(())
+        })
+    }
+}
+
+mod playground {
+    use super::*;
+    use snake::cli::{CompilerConf, Verbosity};
+    use snake::playground::{compile_to_asm, run_in_interpreter, Engine};
+    use snake::runner::read_file;
+
+    #[test]
+    fn compiles_like_the_pipeline() -> Result<(), String> {
+        let src = read_file(Path::new("examples/non_tail_factorial.dbk"))
+            .map_err(|e| e.to_string())?;
+        let (resolver, ast) = snake::compile::frontend(&src)?;
+        let (lowerer, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let conf = CompilerConf::new([], Verbosity::Minimalistic);
+        let asm = snake::compile::backend_with(lowerer, ssa, &conf);
+        assert_eq!(compile_to_asm(&src, "", false)?, asm);
+        let err = compile_to_asm(&src, "nope", false).unwrap_err();
+        assert!(err.starts_with("Invalid optimization"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn both_engines() -> Result<(), String> {
+        let src = read_file(Path::new("examples/non_tail_factorial.dbk"))
+            .map_err(|e| e.to_string())?;
+        for engine in [Engine::Ast, Engine::Ssa] {
+            assert_eq!(run_in_interpreter(&src, " 5 ", engine, "cp", false, 0, 10_000)?, "120");
+            let err = run_in_interpreter(&src, "5", engine, "", false, 0, 10).unwrap_err();
+            assert_eq!(err, "the program did not finish within 10 steps");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn errors_are_reported() {
+        let src = "def main(x): def loop(n): loop(n + 1) in loop(x[0])";
+        let err = run_in_interpreter(src, "true", Engine::Ast, "", false, 0, 1_000).unwrap_err();
+        assert!(err.starts_with("Error interpreting program"), "{}", err);
+        let src = "def main(x): y";
+        let err = run_in_interpreter(src, "", Engine::Ssa, "", false, 0, 1_000).unwrap_err();
+        assert!(err.starts_with("Error resolving ast"), "{}", err);
+        let src = read_file(Path::new("examples/extern_big_nine.dbk")).unwrap();
+        let err = run_in_interpreter(&src, "", Engine::Ast, "", false, 0, 1_000).unwrap_err();
+        assert_eq!(err, "the interpreters do not support extern functions");
+    }
+}
+
+mod ana;
+mod graph_parser;
+mod public_optimizations {
+    use super::*;
+    use graph_parser::*;
+    use snake::{backend::*, frontend::*, middle_end::*, parser::*, runner::*, txt::*};
+
+    mod assertion_removal {
+        use super::*;
+
+        fn test(
+            src_file: impl Into<PathBuf>, expected_ssa_file: impl Into<PathBuf>,
+        ) -> Result<(), String> {
+            let inp =
+                read_file(&src_file.into()).map_err(|e| format!("Error reading file: {}", e))?;
+            let file_info = FileInfo::new(&inp);
+            let raw_ast = ProgParser::new()
+                .parse(&inp)
+                .map_err(|e| format!("Error parsing program: {}", e))?;
+
+            let mut resolver = Resolver::new();
+            let resolved_ast = resolver
+                .resolve_prog(raw_ast)
+                .map_err(|e| format!("Error resolving ast: {}", file_info.report_error(e)))?;
+
+            let mut lowerer = Lowerer::from(resolver);
+            let ssa = lowerer.lower_prog(resolved_ast);
+            let ssa = AssertionRemover::new(&ssa).optimize(ssa);
+
+            let ssa_str = format!("{}", ssa);
+            let expected_ssa_str = read_file(&expected_ssa_file.into())
+                .map_err(|e| format!("Error reading file: {}", e))?;
+
+            assert_eq!(ssa_str.trim(), expected_ssa_str.trim());
+            Ok(())
+        }
+
+        #[test]
+        fn simple_add_3() -> Result<(), String> {
+            test("examples/assertions/simple_add.dbk", "examples/assertions/simple_add.ssa")
+        }
+        #[test]
+        fn loop1_3() -> Result<(), String> {
+            test("examples/assertions/loop1.dbk", "examples/assertions/loop1.ssa")
+        }
+        #[test]
+        fn extern0_3() -> Result<(), String> {
+            test("examples/assertions/extern0.dbk", "examples/assertions/extern0.ssa")
+        }
+    }
+
+    mod liveness_and_conflict_analyses {
+        use super::*;
+
+        fn test(
+            src_file: impl Into<PathBuf>, graph_file: impl Into<PathBuf>,
+        ) -> Result<(), String> {
+            let inp =
+                read_file(&src_file.into()).map_err(|e| format!("Error reading file: {}", e))?;
+            let file_info = FileInfo::new(&inp);
+
+            let raw_ast = ProgParser::new()
+                .parse(&inp)
+                .map_err(|e| format!("Error parsing program: {}", e))?;
+
+            let mut resolver = Resolver::new();
+            let resolved_ast = resolver
+                .resolve_prog(raw_ast)
+                .map_err(|e| format!("Error resolving ast: {}", file_info.report_error(e)))?;
+
+            let mut lowerer = Lowerer::from(resolver);
+            let ssa = lowerer.lower_prog(resolved_ast);
+            let ssa = CopyPropagator::new().run(ssa);
+
+            let live_ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+            let conflicts = ConflictAnalysis::new(&live_ssa);
+
+            let graph = GraphParser::new()
+                .parse(&format!("graph {}", conflicts.interference))
+                .map_err(|e| format!("Error parsing graph: {}", e))?;
+            let expected_graph = GraphParser::new()
+                .parse(&format!(
+                    "graph {}",
+                    read_file(&graph_file.into())
+                        .map_err(|e| format!("Error reading file: {}", e))?
+                ))
+                .map_err(|e| format!("Error parsing graph: {}", e))?;
+
+            ana::should_be_same_graph(&graph, &expected_graph)?;
+            Ok(())
+        }
+
+        #[test]
+        fn chain_1() -> Result<(), String> {
+            test("examples/graphs/chain.dbk", "examples/graphs/chain.graph")
+        }
+
+        #[test]
+        fn itail_reuse_test_1() -> Result<(), String> {
+            test("examples/graphs/itail_reuse.dbk", "examples/graphs/itail_reuse.graph")
+        }
+        #[test]
+        fn if_test_1() -> Result<(), String> {
+            test("examples/graphs/if.dbk", "examples/graphs/if.graph")
+        }
+        #[test]
+        fn param_test_1() -> Result<(), String> {
+            test("examples/graphs/param.dbk", "examples/graphs/param.graph")
+        }
+
+        #[test]
+        fn live_in_out_1() -> Result<(), String> {
+            use snake::cli::Optimization;
+            use snake::compile;
+            use snake::ssa::*;
+
+            let inp = read_file(Path::new("examples/graphs/if.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = compile::frontend(&inp)?;
+            let opts = [Optimization::CopyPropagation].into();
+            let (_, ssa) = compile::middle_end_with(resolver, ast, &opts)?;
+            let live_ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+            let live = Liveness::new(&live_ssa);
+
+            let mut body = &live_ssa.blocks[0].body;
+            while let Some(next) = body.successor() {
+                assert_eq!(live.live_out(body).to_string(), live.live_in(next).to_string());
+                body = next;
+            }
+            // the conditional branch keeps both branches' variables alive
+            assert_eq!(live.live_in(body).to_string(), "[x%2, y%3, flag%9]");
+            assert_eq!(live.live_out(body).to_string(), "[x%2, y%3]");
+            Ok(())
+        }
+    }
+
+    mod unused_removal {
+        use super::*;
+        use snake::ssa::*;
+
+        #[test]
+        fn keeps_exported_signatures() -> Result<(), String> {
+            let inp = read_file(Path::new("examples/unused_fp.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let opts = [snake::cli::Optimization::CopyPropagation].into();
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+            let mut ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+            let mut changed = Vec::new();
+            loop {
+                let mut remover = UnusedRemover::new();
+                let fixed = remover.run(ssa);
+                changed.extend(remover.signatures().keys().map(|f| f.to_string()));
+                ssa = LivenessAnalyzer::new(&fixed).analyze(fixed);
+                if remover.progress().is_none() {
+                    break;
+                }
+            }
+            changed.sort();
+            assert_eq!(changed, ["f@0", "g@1"]);
+            for FunBlock { name, params, .. } in ssa.funs.iter() {
+                let arity = if name.is_unmangled() { 1 } else { 0 };
+                assert_eq!(params.len(), arity, "unexpected signature for {}", name);
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn pure_functions() -> Result<(), String> {
+            let inp = read_file(Path::new("examples/filecheck/pure_calls.snake"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let opts = [snake::cli::Optimization::CopyPropagation].into();
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+            let pure = |conservative| {
+                let mut pure: Vec<_> = EffectAnalysis::new(&ssa, conservative)
+                    .pure
+                    .into_iter()
+                    .filter(|f| !f.is_unmangled())
+                    .map(|f| f.hint().to_string())
+                    .collect();
+                pure.sort();
+                pure
+            };
+            // fails may overflow
+            assert_eq!(pure(false), ["pure"]);
+            assert_eq!(pure(true), ["pure"]);
+            Ok(())
+        }
+    }
+
+    mod assertion_hoisting {
+        use super::*;
+        use snake::cli::Optimization;
+
+        fn interp(file: &str, opts: &[Optimization], args: &[&'static str]) -> String {
+            let run = || {
+                let inp = read_file(Path::new(&format!("examples/{}", file)))
+                    .map_err(|e| format!("Error reading file: {}", e))?;
+                let (resolver, ast) = snake::compile::frontend(&inp)?;
+                let opts = opts.iter().cloned().collect();
+                let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+                let mut interp = interp::ssa::Interp::new();
+                interp.run(&ssa, args.iter().cloned()).map_err(|e| format!("{}", e))
+            };
+            match run() {
+                Ok(v) => v.to_string(),
+                Err(e) => e,
+            }
+        }
+
+        #[test]
+        fn same_errors() {
+            use Optimization::*;
+            let inputs: [&[_]; 5] =
+                [&["1", "true"], &["true", "1"], &["1", "2"], &["true", "false"], &["5", "true"]];
+            for args in inputs {
+                let plain = interp("hoisting_errors.dbk", &[CopyPropagation], args);
+                let hoisted =
+                    interp("hoisting_errors.dbk", &[CopyPropagation, AssertionHoisting], args);
+                assert_eq!(plain, hoisted, "different outcome for {:?}", args);
+            }
+        }
+    }
+
+    mod condition_canonicalization {
+        use super::*;
+        use snake::cli::{CompilerConf, Optimization, Verbosity};
+        use snake::{identifiers::VarName, ssa::Program};
+
+        fn ssa(opts: &[Optimization]) -> Result<Program<VarName, snake::ana::Nil>, String> {
+            let inp = read_file(Path::new("examples/conditions.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let opts = opts.iter().cloned().collect();
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+            let conf = CompilerConf::new(Optimization::all(), Verbosity::Minimalistic);
+            Ok(snake::compile::dead_code_elimination(ssa, &conf))
+        }
+
+        #[test]
+        fn same_results() -> Result<(), String> {
+            use Optimization::*;
+            let plain = ssa(&[CopyPropagation])?;
+            let canonical = ssa(&[ConditionCanonicalization, CopyPropagation])?;
+            let inputs =
+                [["1", "2", "true"], ["2", "1", "false"], ["0", "0", "true"], ["-1", "0", "3"]];
+            for args in inputs {
+                for case in ["0", "1", "2", "3", "4"] {
+                    let args = args.iter().cloned().chain([case]);
+                    let run = |ssa| match interp::ssa::Interp::new().run(ssa, args.clone()) {
+                        Ok(v) => v.to_string(),
+                        Err(e) => e.to_string(),
+                    };
+                    assert_eq!(run(&plain), run(&canonical), "different outcome for {:?}", args);
+                }
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn no_negations() -> Result<(), String> {
+            use Optimization::*;
+            let plain = ssa(&[CopyPropagation])?.to_string();
+            let canonical = ssa(&[ConditionCanonicalization, CopyPropagation])?.to_string();
+            assert!(plain.contains(" ^ 4"));
+            // the branches on `!p` swap their targets instead
+            assert!(!canonical.contains(" ^ 4"), "{}", canonical);
+            Ok(())
+        }
+    }
+
+    mod bounds_check_combining {
+        use super::*;
+        use snake::{ana::Nil, identifiers::VarName, ssa::Program};
+
+        /// The program of `adjacent_accesses.dbk` and the number of checks merged
+        fn ssa(combine: Option<bool>) -> Result<(Program<VarName, Nil>, usize), String> {
+            let inp = read_file(Path::new("examples/adjacent_accesses.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let mut lowerer = Lowerer::from(resolver);
+            let ssa = CopyPropagator::new().run(lowerer.lower_prog(ast));
+            let Some(strict) = combine else { return Ok((ssa, 0)) };
+            let mut combiner = BoundsCheckCombiner::new().strict(strict);
+            let ssa = combiner.run(ssa, &mut lowerer.names);
+            Ok((ssa, combiner.merged()))
+        }
+
+        fn run(ssa: &Program<VarName, Nil>, i: &str) -> Result<String, String> {
+            let value = interp::ssa::Interp::new().run(ssa, [i]).map_err(|e| e.to_string())?;
+            Ok(value.to_string())
+        }
+
+        #[test]
+        fn merged() -> Result<(), String> {
+            let (plain, _) = ssa(None)?;
+            let (relaxed, merged) = ssa(Some(false))?;
+            // a[i + 1] with a[i], a[i] with a[i] := _ and a[i + 3] with a[i + 1] := _
+            assert_eq!(merged, 3);
+            for i in ["-2", "-1", "0", "1", "2", "3", "4", "5", "6"] {
+                match (run(&plain, i), run(&relaxed, i)) {
+                    (Ok(plain), Ok(relaxed)) => assert_eq!(plain, relaxed, "for {}", i),
+                    // the combined check of `a[i]` reports `i` when `a[i + 1]` is out of bounds
+                    (Err(_), Err(_)) => {}
+                    (plain, relaxed) => panic!("{:?} and {:?} for {}", plain, relaxed, i),
+                }
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn strict_keeps_errors() -> Result<(), String> {
+            let (plain, _) = ssa(None)?;
+            let (strict, merged) = ssa(Some(true))?;
+            // only the checks of a[i] in a row
+            assert_eq!(merged, 1);
+            for i in ["-2", "-1", "0", "1", "2", "3", "4", "5", "6"] {
+                assert_eq!(run(&plain, i), run(&strict, i), "for {}", i);
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn guarded_assertions() -> Result<(), String> {
+            let src = "def main(x):\n  let a = [1, 2, 3], i = x[0], b = x[1] in\n  \
+                       if isBool(b): [a[i], !b, a[i + 1]] else: false";
+            let (resolver, ast) = snake::compile::frontend(src)?;
+            let mut lowerer = Lowerer::from(resolver);
+            let plain = CopyPropagator::new().run(lowerer.lower_prog(ast));
+            let mut combiner = BoundsCheckCombiner::new();
+            let combined = combiner.run(plain.clone(), &mut lowerer.names);
+            // x[1] with x[0], and a[i + 1] with a[i], as the guard proves the
+            // assertion of `!b` between them
+            assert_eq!(combiner.merged(), 2);
+            for i in ["-1", "0", "1", "2"] {
+                for b in ["true", "1"] {
+                    let run = |ssa| interp::ssa::Interp::new().run(ssa, [i, b]).is_ok();
+                    assert_eq!(run(&plain), run(&combined), "for {} and {}", i, b);
+                }
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn large_constants() -> Result<(), String> {
+            use snake::cli::Optimization::BoundsCheckCombining;
+            let inp = read_file(Path::new("examples/wrapping.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let opts = [BoundsCheckCombining].into_iter().collect();
+            // the constants of `wrapping_mul` are far from any offset of an access
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+            assert!(run(&ssa, "0").is_ok());
+            Ok(())
+        }
+    }
+
+    mod memory_forwarding {
+        use super::*;
+        use snake::cli::Optimization;
+
+        fn run(opts: &[Optimization], i: &str) -> String {
+            let run = || {
+                let inp = read_file(Path::new("examples/forwarding.dbk"))
+                    .map_err(|e| format!("Error reading file: {}", e))?;
+                let (resolver, ast) = snake::compile::frontend(&inp)?;
+                let opts = opts.iter().cloned().collect();
+                let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+                interp::ssa::Interp::new().run(&ssa, [i]).map_err(|e| format!("{}", e))
+            };
+            match run() {
+                Ok(v) => v.to_string(),
+                Err(e) => e,
+            }
+        }
+
+        #[test]
+        fn same_results() {
+            use Optimization::*;
+            for i in ["-1", "0", "1", "2", "3"] {
+                let plain = run(&[CopyPropagation], i);
+                let forwarded = run(&[MemoryForwarding, CopyPropagation], i);
+                assert_eq!(plain, forwarded, "different outcome for {}", i);
+            }
+        }
+
+        #[test]
+        fn forwarded() -> Result<(), String> {
+            let inp = read_file(Path::new("examples/forwarding.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let ssa = CopyPropagator::new().run(Lowerer::from(resolver).lower_prog(ast));
+            let mut forwarder = LoadForwarder::new(&ssa);
+            let ssa = CopyPropagator::new().run(forwarder.run(ssa));
+            assert!(forwarder.forwarded() > 0);
+            // the lengths of `a` and `b`, and `b[0]`, which may be the `a[0]` stored before
+            let f = ssa.to_string();
+            let f = &f[f.find("block f").unwrap()..];
+            let f = &f[..f[1..].find("\nblock").map_or(f.len(), |end| end + 1)];
+            assert_eq!(f.matches("load(").count(), 3, "{}", f);
+            Ok(())
+        }
+    }
+
+    mod memory_ssa {
+        use super::*;
+        use snake::ana::MemoryDef;
+        use snake::cli::Optimization;
+        use snake::middle_end::MemoryAnalyzer;
+
+        #[test]
+        fn loop_entry_merges() -> Result<(), String> {
+            let inp = read_file(Path::new("examples/memory_loop.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let ssa = Lowerer::from(resolver).lower_prog(ast);
+            let mut analyzer = MemoryAnalyzer::new(&ssa);
+            analyzer.analyze(ssa);
+            let memory = analyzer.memory();
+            let incoming = memory.0.values().find_map(|def| match def {
+                MemoryDef::Phi(label, incoming) if label.hint() == "fill" => Some(incoming),
+                _ => None,
+            });
+            // the memory before the loop and after a round, both after stores
+            let incoming = incoming.ok_or("no entry of fill")?;
+            assert_eq!(incoming.len(), 2);
+            for mem in incoming {
+                assert!(matches!(memory.def(*mem), MemoryDef::Store { .. }), "{}", mem);
+            }
+            // functions are entered with any memory
+            let entries = memory.0.values().filter(|def| match def {
+                MemoryDef::Phi(_, incoming) => incoming.is_empty(),
+                _ => false,
+            });
+            assert_eq!(entries.count(), 1);
+            Ok(())
+        }
+
+        #[test]
+        fn loop_same_results() -> Result<(), String> {
+            use Optimization::*;
+            for i in ["0", "2", "5"] {
+                let run = |opts: &[Optimization]| -> Result<String, String> {
+                    let inp = read_file(Path::new("examples/memory_loop.dbk"))
+                        .map_err(|e| format!("Error reading file: {}", e))?;
+                    let (resolver, ast) = snake::compile::frontend(&inp)?;
+                    let opts = opts.iter().cloned().collect();
+                    let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+                    let value = interp::ssa::Interp::new().run(&ssa, [i]);
+                    Ok(value.map_err(|e| e.to_string())?.to_string())
+                };
+                assert_eq!(run(&[CopyPropagation])?, run(&[MemoryForwarding, CopyPropagation])?);
+            }
+            Ok(())
+        }
+    }
+
+    mod interprocedural_constant_propagation {
+        use super::*;
+        use snake::cli::Optimization;
+        use snake::middle_end::ConstantSpecializer;
+
+        fn run(opts: &[Optimization], i: &str) -> String {
+            let run = || {
+                let inp = read_file(Path::new("examples/constant_args.dbk"))
+                    .map_err(|e| format!("Error reading file: {}", e))?;
+                let (resolver, ast) = snake::compile::frontend(&inp)?;
+                let opts = opts.iter().cloned().collect();
+                let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+                interp::ssa::Interp::new().run(&ssa, [i]).map_err(|e| format!("{}", e))
+            };
+            match run() {
+                Ok(v) => v.to_string(),
+                Err(e) => e,
+            }
+        }
+
+        /// The functions of the program and the notes of `specializer` on it
+        fn specialize(
+            mut specializer: ConstantSpecializer,
+        ) -> Result<(usize, Vec<String>), String> {
+            let inp = read_file(Path::new("examples/constant_args.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let mut lowerer = Lowerer::from(resolver);
+            let ssa = lowerer.lower_prog(ast);
+            let ssa = specializer.run(ssa, &mut lowerer.names);
+            Ok((ssa.funs.len(), specializer.notes().to_vec()))
+        }
+
+        #[test]
+        fn same_results() {
+            use Optimization::*;
+            for i in ["-2", "0", "1", "3"] {
+                let plain = run(&[CopyPropagation], i);
+                let specialized = run(&[InterproceduralConstantPropagation, CopyPropagation], i);
+                assert_eq!(plain, specialized, "different outcome for {}", i);
+            }
+        }
+
+        #[test]
+        fn specialized() -> Result<(), String> {
+            let (funs, notes) = specialize(ConstantSpecializer::new())?;
+            // entry, f, g and two clones of f
+            assert_eq!(funs, 5, "{:?}", notes);
+            let always = |hint: &str, c: i64| {
+                let note = |n: &&String| n.starts_with(hint) && n.ends_with(&format!(" {}", c));
+                notes.iter().any(|n| note(&n))
+            };
+            // tagged 2 in g, and 3 in f(2, 3)
+            assert!(always("g#", 4), "{:?}", notes);
+            assert!(always("f#", 6), "{:?}", notes);
+            assert_eq!(notes.iter().filter(|n| n.starts_with("cloned")).count(), 2);
+            Ok(())
+        }
+
+        #[test]
+        fn limits() -> Result<(), String> {
+            let (funs, notes) = specialize(ConstantSpecializer::new().max_clones(0))?;
+            assert_eq!(funs, 3);
+            assert!(notes.iter().any(|n| n.ends_with("at most 0 clones")), "{:?}", notes);
+            let (funs, notes) = specialize(ConstantSpecializer::new().max_size(4))?;
+            assert_eq!(funs, 3);
+            assert!(notes.iter().any(|n| n.ends_with("more than 4")), "{:?}", notes);
+            Ok(())
+        }
+    }
+
+    mod type_specialization {
+        use super::*;
+        use snake::cli::Optimization;
+        use snake::middle_end::TypeSpecializer;
+
+        fn run(opts: &[Optimization], args: [&str; 2]) -> String {
+            let run = || {
+                let inp = read_file(Path::new("examples/type_profiles.dbk"))
+                    .map_err(|e| format!("Error reading file: {}", e))?;
+                let (resolver, ast) = snake::compile::frontend(&inp)?;
+                let opts = opts.iter().cloned().collect();
+                let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+                interp::ssa::Interp::new().run(&ssa, args).map_err(|e| format!("{}", e))
+            };
+            match run() {
+                Ok(v) => v.to_string(),
+                Err(e) => e,
+            }
+        }
+
+        /// The program after `specializer`, with the notes and the number of removed assertions
+        fn specialize(
+            mut specializer: TypeSpecializer,
+        ) -> Result<(String, Vec<String>, usize), String> {
+            let inp = read_file(Path::new("examples/type_profiles.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let mut lowerer = Lowerer::from(resolver);
+            let ssa = lowerer.lower_prog(ast);
+            let ssa = specializer.run(ssa, &mut lowerer.names);
+            Ok((ssa.to_string(), specializer.notes().to_vec(), specializer.removed()))
+        }
+
+        #[test]
+        fn same_results() {
+            use Optimization::*;
+            for args in [["1", "true"], ["3", "false"], ["1", "2"], ["true", "1"]] {
+                let plain = run(&[CopyPropagation], args);
+                let specialized = run(&[TypeSpecialization, CopyPropagation], args);
+                assert_eq!(plain, specialized, "different outcome for {:?}", args);
+            }
+        }
+
+        #[test]
+        fn specialized() -> Result<(), String> {
+            let (ssa, notes, removed) = specialize(TypeSpecializer::new())?;
+            assert_eq!(notes.len(), 2, "{:?}", notes);
+            assert!(removed > 0);
+            // the clones of add and sel assert nothing
+            let funs = ssa.matches("\nfun ").count();
+            assert_eq!(funs, 5, "{}", ssa);
+            let clones = &ssa[ssa.rfind("\nblock sel").unwrap()..];
+            assert!(!clones.contains("assert"), "{}", clones);
+            Ok(())
+        }
+
+        #[test]
+        fn limits() -> Result<(), String> {
+            let (_, notes, _) = specialize(TypeSpecializer::new().max_clones(0))?;
+            assert_eq!(notes.len(), 2);
+            assert!(notes.iter().all(|n| n.ends_with("at most 0 clones")), "{:?}", notes);
+            let (_, notes, _) = specialize(TypeSpecializer::new().max_size(2))?;
+            assert_eq!(notes.len(), 2);
+            assert!(notes.iter().all(|n| n.ends_with("more than 2")), "{:?}", notes);
+            Ok(())
+        }
+    }
+
+    mod strict_errors {
+        use super::*;
+        use snake::cli::{CompilerConf, Optimization, Verbosity};
+
+        fn interp(file: &str, conf: Option<&CompilerConf>, args: &[&'static str]) -> String {
+            let run = || {
+                let inp = read_file(Path::new(&format!("examples/{}", file)))
+                    .map_err(|e| format!("Error reading file: {}", e))?;
+                let (resolver, ast) = snake::compile::frontend(&inp)?;
+                let opts = [Optimization::CopyPropagation].into();
+                let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+                let ssa = match conf {
+                    Some(conf) => snake::compile::dead_code_elimination(ssa, conf),
+                    None => ssa,
+                };
+                let mut interp = interp::ssa::Interp::new();
+                interp.run(&ssa, args.iter().cloned()).map_err(|e| format!("{}", e))
+            };
+            match run() {
+                Ok(v) => v.to_string(),
+                Err(e) => e,
+            }
+        }
+
+        const INPUTS: [&[&str]; 4] =
+            [&["1", "true"], &["9999999", "true"], &["1", "2"], &["9999999", "2"]];
+
+        #[test]
+        fn strict_keeps_errors() {
+            let mut conf = CompilerConf::new(Optimization::all(), Verbosity::Minimalistic);
+            conf.strict_errors = true;
+            for args in INPUTS {
+                let plain = interp("strict_errors.dbk", None, args);
+                let strict = interp("strict_errors.dbk", Some(&conf), args);
+                assert_eq!(plain, strict, "different outcome for {:?}", args);
+            }
+        }
+
+        #[test]
+        fn relaxed_drops_errors() {
+            let conf = CompilerConf::new(Optimization::all(), Verbosity::Minimalistic);
+            for args in INPUTS {
+                let plain = interp("strict_errors.dbk", None, args);
+                let relaxed = interp("strict_errors.dbk", Some(&conf), args);
+                if args[0] == "9999999" {
+                    // the overflowing product is unused and removed
+                    assert_ne!(plain, relaxed, "same outcome for {:?}", args);
+                } else {
+                    assert_eq!(plain, relaxed, "different outcome for {:?}", args);
+                }
+            }
+        }
+    }
+
+    mod dominators {
+        use super::*;
+
+        #[test]
+        fn loop_1() -> Result<(), String> {
+            let inp = read_file(Path::new("examples/live_loop.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let opts = [snake::cli::Optimization::CopyPropagation].into();
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+            let dom = DominatorAnalysis::new(&ssa);
+            assert_eq!(dom.to_string(), "entry#0\n  f#1\n    loop#2\n      thn#3\n      els#4\n",);
+            let label = |hint: &str| dom.idom.keys().find(|b| b.hint() == hint).unwrap();
+            assert!(dom.dominates(label("loop"), label("els")));
+            assert!(!dom.dominates(label("thn"), label("els")));
+            Ok(())
+        }
+    }
+
+    mod graph_coloring {
+        use super::*;
+
+        const NO_REG: &'static str = "none";
+        const ONE_REG: &'static str = "none+r8";
+        const TWO_REGS: &'static str = "none+r8+r9";
+        const THREE_REGS: &'static str = "none+r8+r9+r10";
+        const FOUR_REGS: &'static str = "none+r8+r9+r10+r11";
+        const FIVE_REGS: &'static str = "none+r8+r9+r10+r11+r12";
+
+        fn test(
+            regs: &'static str, allow_spills: bool, src_file: impl Into<PathBuf>,
+            expected_graph_file: impl Into<PathBuf>,
+        ) -> Result<(), String> {
+            use snake::cli::RegisterSet;
+            use std::str::FromStr;
+            let inp =
+                read_file(&src_file.into()).map_err(|e| format!("Error reading file: {}", e))?;
+            let file_info = FileInfo::new(&inp);
+
+            let raw_ast = ProgParser::new()
+                .parse(&inp)
+                .map_err(|e| format!("Error parsing program: {}", e))?;
+
+            let mut resolver = Resolver::new();
+            let resolved_ast = resolver
+                .resolve_prog(raw_ast)
+                .map_err(|e| format!("Error resolving ast: {}", file_info.report_error(e)))?;
+
+            let mut lowerer = Lowerer::from(resolver);
+            let ssa = lowerer.lower_prog(resolved_ast);
+            let ssa = CopyPropagator::new().run(ssa);
+
+            let live_ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+            let conflicts = ConflictAnalysis::new(&live_ssa);
+
+            let mut allocator = RegisterAllocator::new();
+            allocator.graph_color(
+                conflicts,
+                RegisterSet::from_str(regs).unwrap().registers(),
+                false,
+            );
+            let coloring =
+                allocator.assignment.iter().map(|(k, v)| (k.to_string(), v.to_owned())).collect();
+            let graph = GraphParser::new()
+                .parse(&format!(
+                    "graph {}",
+                    read_file(&expected_graph_file.into())
+                        .map_err(|e| format!("Error reading file: {}", e))?
+                ))
+                .map_err(|e| format!("Error parsing graph: {}", e))?;
+
+            let () = crate::ana::valid_coloring(&graph, &coloring)?;
+            assert!(
+                allow_spills || (crate::ana::count_spills(&coloring) == 0),
+                "You spilled when you didn't need to"
+            );
+
+            Ok(())
+        }
+
+        #[test]
+        fn chain_1() -> Result<(), String> {
+            test(THREE_REGS, false, "examples/graphs/chain.dbk", "examples/graphs/chain.graph")
+        }
+
+        #[test]
+        fn chain_spill_1() -> Result<(), String> {
+            test(TWO_REGS, true, "examples/graphs/chain.dbk", "examples/graphs/chain.graph")
+        }
+
+        #[test]
+        fn chain_spill_all_1() -> Result<(), String> {
+            test(NO_REG, true, "examples/graphs/chain.dbk", "examples/graphs/chain.graph")
+        }
+
+        #[test]
+        fn if_1() -> Result<(), String> {
+            test(THREE_REGS, false, "examples/graphs/if.dbk", "examples/graphs/if.graph")
+        }
+
+        #[test]
+        fn itail_reuse_1() -> Result<(), String> {
+            test(
+                ONE_REG,
+                false,
+                "examples/graphs/itail_reuse.dbk",
+                "examples/graphs/itail_reuse.graph",
+            )
+        }
+
+        #[test]
+        fn itail_reuse_spill_all_1() -> Result<(), String> {
+            test(
+                NO_REG,
+                true,
+                "examples/graphs/itail_reuse.dbk",
+                "examples/graphs/itail_reuse.graph",
+            )
+        }
+
+        #[test]
+        fn param_1() -> Result<(), String> {
+            test(TWO_REGS, false, "examples/graphs/param.dbk", "examples/graphs/param.graph")
+        }
+
+        #[test]
+        fn param_spill_1() -> Result<(), String> {
+            test(ONE_REG, true, "examples/graphs/param.dbk", "examples/graphs/param.graph")
+        }
+
+        #[test]
+        fn colored_svg() -> Result<(), String> {
+            use snake::cli::RegisterSet;
+            use std::str::FromStr;
+            let inp = read_file(Path::new("examples/graphs/chain.dbk"))
+                .map_err(|e| format!("Error reading file: {}", e))?;
+            let (resolver, ast) = snake::compile::frontend(&inp)?;
+            let opts = [snake::cli::Optimization::CopyPropagation].into();
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+            let live_ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+            let conflicts = ConflictAnalysis::new(&live_ssa);
+            let interference = conflicts.interference.clone();
+            let mut allocator = RegisterAllocator::new();
+            let regs = RegisterSet::from_str(ONE_REG).unwrap().registers().to_vec();
+            allocator.graph_color(conflicts, &regs, false);
+            let tmp_dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+            let path = tmp_dir.path().join("chain.svg");
+            interference.dot_colored(&path, &allocator.assignment);
+            let svg = read_file(&path).map_err(|e| format!("Error reading file: {}", e))?;
+            for (v, a) in allocator.assignment.iter() {
+                let label = format!("{}: {}", v, a).replace('<', "&lt;").replace('>', "&gt;");
+                assert!(svg.contains(&label), "{} not found", label);
+            }
+            Ok(())
+        }
+    }
+}
+
+mod filecheck {
+    use snake::filecheck::*;
+    use std::path::Path;
+
+    fn test(file: &str) -> Result<(), String> {
+        check_file(Path::new(&format!("examples/filecheck/{}", file))).map(|_| ())
+    }
+
+    #[test]
+    fn copy_prop() -> Result<(), String> {
+        test("copy_prop.snake")
+    }
+    #[test]
+    fn no_copy_prop() -> Result<(), String> {
+        test("no_copy_prop.snake")
+    }
+    #[test]
+    fn dce() -> Result<(), String> {
+        test("dce.snake")
+    }
+    #[test]
+    fn pure_calls() -> Result<(), String> {
+        test("pure_calls.snake")
+    }
+    #[test]
+    fn assertion_hoisting() -> Result<(), String> {
+        test("assertion_hoisting.snake")
+    }
+    #[test]
+    fn size_layout() -> Result<(), String> {
+        test("size_layout.snake")
+    }
+    #[test]
+    fn failing_checks() -> Result<(), String> {
+        let file = CheckFile::parse(
+            "# CHECK-SSA: b\n# CHECK-SSA-NOT: c\n# CHECK-SSA: {{[0-9]+}}\n# CHECK-SSA: a",
+        )?;
+        assert!(file.verify(Dump::SSA, "a\nb\n1\na").is_ok());
+        assert!(file.verify(Dump::SSA, "b\nc\n1\na").is_err());
+        assert!(file.verify(Dump::SSA, "a\nb\n1").is_err());
+        assert!(file.verify(Dump::SSA, "1\na\nb").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn shards() -> Result<(), String> {
+        use snake::cli::Shard;
+        let shard: Shard = "2/3".parse()?;
+        assert_eq!(shard.select((0..8).collect()), [1, 4, 7]);
+        assert!("0/3".parse::<Shard>().is_err());
+        assert!("4/3".parse::<Shard>().is_err());
+        assert!("3".parse::<Shard>().is_err());
+        Ok(())
+    }
+
+    /// The files that `snake test` reports on with `args`
+    fn tested(args: &[&str]) -> Result<Vec<String>, String> {
+        let out = std::process::Command::new(env!("CARGO_BIN_EXE_snake"))
+            .args(["test", "examples/filecheck"])
+            .args(args)
+            .output()
+            .map_err(|e| e.to_string())?;
+        assert!(out.status.success());
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        let mut files: Vec<_> = (stdout.lines())
+            .filter_map(|line| line.strip_prefix("PASS "))
+            .map(|line| line.split(' ').next().unwrap_or_default().to_string())
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+
+    #[test]
+    fn shards_partition_the_files() -> Result<(), String> {
+        let all = tested(&["-j", "3"])?;
+        let mut sharded = tested(&["-j", "2", "--shard", "1/2"])?;
+        sharded.extend(tested(&["-j", "2", "--shard", "2/2"])?);
+        sharded.sort();
+        assert_eq!(sharded, all);
+        assert!(all.len() > 1);
+        Ok(())
+    }
+}
+mod query {
+    use snake::cli::{CompilerConf, Optimization, Verbosity};
+    use snake::compile;
+    use snake::query::Queries;
+    use std::collections::HashSet;
+
+    const SRC: &str = "def main(x):\n  def f(x): x + 1 in\n  let y = f(x) in let z = y in z * 2";
+
+    /// The SSA and assembly of `src` at `optimizations`, without the memo tables
+    fn stages(
+        src: &str, optimizations: &HashSet<Optimization>,
+    ) -> Result<(String, String), String> {
+        let (resolver, ast) = compile::frontend(src)?;
+        let (lowerer, ssa) = compile::middle_end_with(resolver, ast, optimizations)?;
+        let conf = CompilerConf::new(optimizations.clone(), Verbosity::Minimalistic);
+        Ok((ssa.to_string(), compile::backend_with(lowerer, ssa.clone(), &conf)))
+    }
+
+    #[test]
+    fn comments_share_the_stages() -> Result<(), String> {
+        let queries = Queries::new();
+        let commented = format!("# OPT: cp\n# CHECK-SSA: z\n{}\n", SRC);
+        queries.ssa(SRC, &HashSet::new())?;
+        assert_eq!(queries.hits(), 0);
+        queries.ssa(&commented, &HashSet::new())?;
+        assert_eq!(queries.hits(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn passes_start_from_the_longest_prefix() -> Result<(), String> {
+        let queries = Queries::new();
+        let cp: HashSet<_> = [Optimization::CopyPropagation].into();
+        let loopify_cp: HashSet<_> = [Optimization::Loopify, Optimization::CopyPropagation].into();
+        assert_eq!(queries.ssa(SRC, &cp)?.to_string(), stages(SRC, &cp)?.0);
+        assert_eq!(queries.hits(), 0);
+        assert_eq!(queries.ssa(SRC, &loopify_cp)?.to_string(), stages(SRC, &loopify_cp)?.0);
+        assert_eq!(queries.hits(), 1);
+        assert_eq!(queries.ssa(SRC, &cp)?.to_string(), stages(SRC, &cp)?.0);
+        assert_eq!(queries.hits(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn dumps_share_the_assembly() -> Result<(), String> {
+        let queries = Queries::new();
+        let opts: HashSet<_> = [Optimization::CopyPropagation].into();
+        let conf = CompilerConf::new(opts.clone(), Verbosity::Minimalistic);
+        let (ssa, asm) = stages(SRC, &opts)?;
+        assert_eq!(queries.ssa(SRC, &opts)?.to_string(), ssa);
+        assert_eq!(queries.asm(SRC, &conf)?, asm);
+        assert_eq!(queries.hits(), 1);
+        assert_eq!(queries.asm(SRC, &conf)?, asm);
+        assert_eq!(queries.hits(), 2);
+        let verbose = CompilerConf::new(opts, Verbosity::Mouthful);
+        queries.asm(SRC, &verbose)?;
+        assert_eq!(queries.hits(), 3);
+        Ok(())
+    }
+}
+mod readable_names {
+    use snake::identifiers::ReadableNames;
+    use snake::pretty::Readable;
+
+    #[test]
+    fn numbered_per_source_name() -> Result<(), String> {
+        let src = "def main(x):\n  def f(x): x + 1 in\n  let y = f(x) in let y = f(y) in y";
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let opts = [snake::cli::Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let names = ReadableNames::new(&ssa.vars());
+        let txt = Readable(&names, &ssa).to_string();
+        assert!(!txt.contains('%'), "unique names left in:\n{}", txt);
+        for name in ["x.0", "x.1", "y.0", "y.1"] {
+            assert!(txt.contains(name), "{} not found in:\n{}", name, txt);
+        }
+        Ok(())
+    }
+}
+mod name_supply {
+    #[test]
+    fn fresh_after_lowering() -> Result<(), String> {
+        let src = "def main(x):\n  def f(x): x + 1 in\n  let y = f(x) in let tmp = f(y) in tmp";
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let (mut lowerer, ssa) = snake::compile::middle_end_with(resolver, ast, &[].into())?;
+        let vars = ssa.vars();
+        let funs: Vec<_> = ssa.funs.iter().map(|f| &f.name).collect();
+        let blocks: Vec<_> = ssa.blocks.iter().map(|b| &b.label).collect();
+        for _ in 0..vars.len() {
+            let var = lowerer.names.fresh_var("tmp");
+            assert!(!vars.contains(&var), "{} generated twice", var);
+        }
+        let fun = lowerer.names.fresh_fun("f");
+        assert!(!funs.contains(&&fun), "{} generated twice", fun);
+        let block = lowerer.names.fresh_block("main");
+        assert!(!blocks.contains(&&block), "{} generated twice", block);
+        Ok(())
+    }
+}
+mod symbolic {
+    use super::*;
+    use snake::asm::{Asm, BinArgs, ConditionCode, Instr, Reg};
+    use snake::backend::*;
+    use snake::cli::{BoolLowering, Optimization};
+    use snake::runner::read_file;
+    use snake::symbolic::{check, Mismatch};
+    use std::collections::HashSet;
+
+    /// Emits `src` with `registers`, lets `corrupt` change the code, and
+    /// checks it against the SSA
+    fn emit_checked(
+        src: &str, opts: &HashSet<Optimization>, registers: &[Reg], size: bool,
+        lowering: BoolLowering, corrupt: impl Fn(&mut Asm),
+    ) -> Result<Result<usize, Vec<Mismatch>>, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, opts)?;
+        let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+        let mut allocator = RegisterAllocator::new();
+        allocator.graph_color(ConflictAnalysis::new(&ssa), registers, false);
+        let assignment = allocator.assignment.clone();
+        let mut emitter = Emitter::from(allocator).for_size(size).bool_lowering(lowering);
+        emitter.emit_prog(&ssa);
+        let mut asm = emitter.to_asm();
+        corrupt(&mut asm);
+        Ok(check(&ssa, &assignment, &asm))
+    }
+
+    #[test]
+    fn examples() -> Result<(), String> {
+        let mut files: Vec<_> = std::fs::read_dir("examples")
+            .map_err(|e| e.to_string())?
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "dbk"))
+            .collect();
+        files.sort();
+        let configs = [HashSet::new(), [Optimization::CopyPropagation].into()];
+        // whether to lay out for size, to fold identical code and to
+        // schedule, and how to lower comparisons
+        let modes = [
+            (false, false, false, BoolLowering::Setcc),
+            (true, false, false, BoolLowering::Setcc),
+            (false, true, false, BoolLowering::Setcc),
+            (true, true, false, BoolLowering::Setcc),
+            (false, false, true, BoolLowering::Setcc),
+            (false, false, false, BoolLowering::Cmov),
+            (true, true, true, BoolLowering::Cmov),
+        ];
+        let (mut checked, mut failures) = (0, Vec::new());
+        for file in files.iter() {
+            let src = read_file(file).map_err(|e| e.to_string())?;
+            if snake::compile::frontend(&src).is_err() {
+                continue;
+            }
+            for opts in configs.iter() {
+                for registers in [&Reg::ALLOCATABLE[..], &[Reg::Rbx, Reg::Rdi]] {
+                    for (size, fold, sched, lowering) in modes {
+                        let fold = |asm: &mut Asm| {
+                            if sched {
+                                asm.schedule();
+                            }
+                            if fold {
+                                asm.fold_identical();
+                            }
+                        };
+                        match emit_checked(&src, opts, registers, size, lowering, fold)? {
+                            Ok(blocks) => checked += blocks,
+                            Err(mismatches) => failures.extend(
+                                mismatches.iter().map(|m| format!("{}: {}", file.display(), m)),
+                            ),
+                        }
+                    }
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+        assert!(checked > 0);
+        Ok(())
+    }
+
+    /// The mismatches of the first block of `file` that `corrupt` changes
+    fn corrupted(file: &str, corrupt: impl Fn(&mut Asm)) -> Result<Vec<Mismatch>, String> {
+        let src = read_file(Path::new(file)).map_err(|e| e.to_string())?;
+        let lowering = BoolLowering::Setcc;
+        match emit_checked(&src, &HashSet::new(), &Reg::ALLOCATABLE, false, lowering, corrupt)? {
+            Ok(_) => Err(format!("the corruption of {} went unnoticed", file)),
+            Err(mismatches) => Ok(mismatches),
+        }
+    }
+
+    #[test]
+    fn wrong_operation() -> Result<(), String> {
+        let mismatches = corrupted("examples/arith.dbk", |asm| {
+            let i = asm.instrs.iter().position(|i| matches!(i, Instr::Add(BinArgs::ToReg(..))));
+            let Instr::Add(args) = asm.instrs[i.unwrap()] else { unreachable!() };
+            asm.instrs[i.unwrap()] = Instr::Sub(args);
+        })?;
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].message.contains("sub("), "{}", mismatches[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn clobbered_register() -> Result<(), String> {
+        // without the first swap of a cycle, one parameter gets a wrong argument
+        let mismatches = corrupted("examples/mov_cycles.dbk", |asm| {
+            let i = asm.instrs.iter().position(|i| matches!(i, Instr::Xchg(..)));
+            asm.instrs.remove(i.unwrap());
+        })?;
+        assert!(mismatches.iter().all(|m| m.message.contains("passed to")), "{:?}", mismatches);
+        Ok(())
+    }
+
+    #[test]
+    fn dead_definitions_keep_live_registers() -> Result<(), String> {
+        // `z` is dead but must not take the register of `x`, which is returned
+        let src = read_file(Path::new("examples/def_not_use.dbk")).map_err(|e| e.to_string())?;
+        let lowering = BoolLowering::Setcc;
+        match emit_checked(&src, &HashSet::new(), &Reg::ALLOCATABLE, false, lowering, |_| {})? {
+            Ok(checked) => assert!(checked >= 2),
+            Err(mismatches) => panic!("{:?}", mismatches),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn conditional_moves() -> Result<(), String> {
+        let src = read_file(Path::new("examples/pow.dbk")).map_err(|e| e.to_string())?;
+        let (opts, registers) = (HashSet::new(), Reg::ALLOCATABLE);
+        let check = |corrupt: fn(&mut Asm)| {
+            emit_checked(&src, &opts, &registers, false, BoolLowering::Cmov, |asm| {
+                let moves = asm.instrs.iter().filter(|i| matches!(i, Instr::CMovCC(..)));
+                assert_eq!(moves.count(), 1);
+                assert!(!asm.instrs.iter().any(|i| matches!(i, Instr::SetCC(..))));
+                corrupt(asm);
+            })
+        };
+        assert!(check(|_| {})?.is_ok());
+        // the condition negated
+        let mismatches = check(|asm| {
+            for instr in asm.instrs.iter_mut() {
+                if let Instr::CMovCC(cc, _) = instr {
+                    let (e, ne) = (ConditionCode::E, ConditionCode::NE);
+                    *cc = if *cc == e { ne } else { e };
+                }
+            }
+        })?
+        .unwrap_err();
+        assert!(mismatches[0].message.contains("select"), "{:?}", mismatches);
+        Ok(())
+    }
+}
+
+mod symbols {
+    use snake::asm::*;
+
+    #[test]
+    fn fresh_labels_are_unique() {
+        let mut symbols = Symbols::new();
+        let taken = symbols.local("jump_table#1");
+        let labels: Vec<_> = (0..3).map(|_| symbols.fresh("jump_table")).collect();
+        let names: Vec<_> = labels.iter().map(|l| symbols.name(*l)).collect();
+        assert_eq!(names, ["jump_table#0", "jump_table#2", "jump_table#3"]);
+        assert_eq!(symbols.get("jump_table#1"), Some(taken));
+        assert_eq!(symbols.local("jump_table#0"), labels[0]);
+    }
+
+    #[test]
+    fn bindings() {
+        let mut symbols = Symbols::new();
+        let entry = symbols.local("entry");
+        assert_eq!(symbols.global("entry"), entry);
+        let print = symbols.external("print");
+        assert_eq!(symbols.local("print"), print);
+        assert_eq!(symbols.binding(entry), Binding::Global);
+        assert_eq!(symbols.binding(print), Binding::Extern);
+    }
+
+    #[test]
+    fn verify() {
+        let mut symbols = Symbols::new();
+        let (entry, done) = (symbols.global("entry"), symbols.local("done"));
+        let print = symbols.external("print");
+        let instrs = vec![
+            Instr::Global(entry),
+            Instr::Extern(print),
+            Instr::Label(entry),
+            Instr::Call(print),
+            Instr::Jmp(done),
+            Instr::Label(print),
+        ];
+        let mut asm = Asm { instrs, symbols };
+        let problems = asm.verify().unwrap_err();
+        let expected = ["external symbol `print` is defined", "label `done` is never defined"];
+        assert_eq!(problems, expected);
+        asm.instrs.pop();
+        asm.instrs.extend([Instr::Label(done), Instr::Ret, Instr::Label(done)]);
+        assert_eq!(asm.verify(), Err(vec!["label `done` is defined 2 times".to_string()]));
+        asm.instrs.pop();
+        assert_eq!(asm.verify(), Ok(()));
+    }
+}
+
+mod icf {
+    use super::*;
+    use snake::asm::*;
+    use snake::backend::*;
+    use snake::runner::read_file;
+    use std::collections::HashSet;
+
+    fn emit(file: &str, size: bool) -> Result<Asm, String> {
+        let src = read_file(Path::new(file)).map_err(|e| e.to_string())?;
+        let (resolver, ast) = snake::compile::frontend(&src)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &HashSet::new())?;
+        let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+        let mut allocator = RegisterAllocator::new();
+        allocator.graph_color(ConflictAnalysis::new(&ssa), &Reg::ALLOCATABLE, false);
+        let mut emitter = Emitter::from(allocator).for_size(size);
+        emitter.emit_prog(&ssa);
+        Ok(emitter.to_asm())
+    }
+
+    /// Whether only labels and comments separate the labels named `a` and `b`
+    fn same_address(asm: &Asm, a: &str, b: &str) -> bool {
+        let at = |name| {
+            let label = Instr::Label(asm.symbols.get(name).unwrap());
+            asm.instrs.iter().position(|instr| *instr == label)
+        };
+        let (a, b) = (at(a).unwrap(), at(b).unwrap());
+        asm.instrs[a.min(b)..a.max(b)]
+            .iter()
+            .all(|instr| matches!(instr, Instr::Label(_) | Instr::Comment(_)))
+    }
+
+    #[test]
+    fn identical_functions() -> Result<(), String> {
+        for size in [false, true] {
+            let mut asm = emit("examples/identical.dbk", size)?;
+            let bytes = |asm: &Asm| asm.instrs.iter().map(instr_size).sum::<usize>();
+            let before = bytes(&asm);
+            assert_eq!(asm.fold_identical(), 6);
+            assert_eq!(asm.verify(), Ok(()));
+            assert!(bytes(&asm) < before);
+            assert!(same_address(&asm, "sum@0", "total@1"));
+            assert!(same_address(&asm, "twice@2", "double@3"));
+            assert!(!same_address(&asm, "sum@0", "twice@2"));
+            assert_eq!(asm.fold_identical(), 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn jump_tables_stay() -> Result<(), String> {
+        let mut asm = emit("examples/switch.dbk", false)?;
+        let data = |asm: &Asm| {
+            let tables = asm.instrs.iter().filter(|i| matches!(i, Instr::RelOffset(..)));
+            tables.cloned().collect::<Vec<_>>()
+        };
+        let tables = data(&asm);
+        assert!(!tables.is_empty());
+        asm.fold_identical();
+        assert_eq!(asm.verify(), Ok(()));
+        assert_eq!(data(&asm), tables);
+        Ok(())
+    }
+}
+
+mod sched {
+    use super::*;
+    use snake::asm::*;
+    use snake::backend::*;
+    use snake::runner::read_file;
+    use std::collections::HashSet;
+
+    fn emit(file: &Path) -> Result<Option<Asm>, String> {
+        let src = read_file(file).map_err(|e| e.to_string())?;
+        let Ok((resolver, ast)) = snake::compile::frontend(&src) else { return Ok(None) };
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &HashSet::new())?;
+        let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+        let mut allocator = RegisterAllocator::new();
+        allocator.graph_color(ConflictAnalysis::new(&ssa), &Reg::ALLOCATABLE, false);
+        let mut emitter = Emitter::from(allocator);
+        emitter.emit_prog(&ssa);
+        Ok(Some(emitter.to_asm()))
+    }
+
+    fn code(instrs: Vec<Instr>) -> Asm {
+        Asm { instrs, symbols: Symbols::new() }
+    }
+
+    fn stack(offset: i32) -> MemRef {
+        MemRef { reg: Reg::Rsp, offset }
+    }
+
+    #[test]
+    fn examples() -> Result<(), String> {
+        let mut moved = 0;
+        for entry in std::fs::read_dir("examples").map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().is_none_or(|ext| ext != "dbk") {
+                continue;
+            }
+            let Some(mut asm) = emit(&path)? else { continue };
+            let before = asm.instrs.clone();
+            moved += asm.schedule();
+            assert_eq!(asm.check_schedule(&before), Ok(()), "{}", path.display());
+            assert_eq!(asm.verify(), Ok(()), "{}", path.display());
+        }
+        assert!(moved > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn independent_code_fills_the_load() {
+        let load = Instr::Mov(MovArgs::ToReg(Reg::Rax, Arg64::Mem(stack(-8))));
+        let use_load = Instr::Add(BinArgs::ToReg(Reg::Rax, Arg32::Signed(2)));
+        let other = Instr::Mov(MovArgs::ToReg(Reg::Rbx, Arg64::Signed(4)));
+        let mut asm = code(vec![load.clone(), use_load.clone(), other.clone(), Instr::Ret]);
+        assert_eq!(asm.schedule(), 2);
+        assert_eq!(asm.instrs, [load, other, use_load, Instr::Ret]);
+    }
+
+    /// Whether the check accepts `instrs` with the instructions at `a` and `b` swapped
+    fn swappable(instrs: &[Instr], a: usize, b: usize) -> bool {
+        let mut asm = code(instrs.to_vec());
+        asm.instrs.swap(a, b);
+        asm.check_schedule(instrs).is_ok()
+    }
+
+    #[test]
+    fn flags() {
+        let instrs = [
+            Instr::Cmp(BinArgs::ToReg(Reg::Rax, Arg32::Reg(Reg::Rbx))),
+            Instr::Add(BinArgs::ToReg(Reg::Rcx, Arg32::Signed(2))),
+            Instr::SetCC(ConditionCode::L, Reg8::Dl),
+            Instr::Shl(ShArgs { reg: Reg::Rsi, by: 0 }),
+        ];
+        assert!(!swappable(&instrs, 0, 1));
+        assert!(!swappable(&instrs, 1, 2));
+        assert!(swappable(&instrs, 2, 3));
+        let mut asm = code(instrs.to_vec());
+        asm.instrs.swap(1, 2);
+        let problem = asm.check_schedule(&instrs).unwrap_err();
+        assert_eq!(problem, "`setl dl` moved before `add rcx, 2`, which it depends on");
+    }
+
+    #[test]
+    fn memory() {
+        let heap = MemRef { reg: Reg::Rdi, offset: 8 };
+        let instrs = [
+            Instr::Mov(MovArgs::ToMem(stack(-8), Reg32::Reg(Reg::Rcx))),
+            Instr::Mov(MovArgs::ToReg(Reg::Rdx, Arg64::Mem(stack(-8)))),
+            Instr::Mov(MovArgs::ToReg(Reg::Rsi, Arg64::Mem(stack(-16)))),
+            Instr::Mov(MovArgs::ToMem(heap, Reg32::Imm(0))),
+            Instr::Mov(MovArgs::ToReg(Reg::R8, Arg64::Mem(MemRef { reg: Reg::R9, offset: 16 }))),
+        ];
+        assert!(!swappable(&instrs, 0, 1));
+        assert!(swappable(&instrs, 1, 2));
+        assert!(swappable(&instrs, 2, 3));
+        // arrays may be anywhere on the heap
+        assert!(!swappable(&instrs, 3, 4));
+    }
+
+    #[test]
+    fn regions() {
+        let mut symbols = Symbols::new();
+        let done = symbols.local("done");
+        let instrs = vec![
+            Instr::Mov(MovArgs::ToReg(Reg::Rax, Arg64::Signed(1))),
+            Instr::Jmp(done),
+            Instr::Label(done),
+            Instr::Mov(MovArgs::ToReg(Reg::Rbx, Arg64::Signed(2))),
+        ];
+        let mut asm = Asm { instrs: instrs.clone(), symbols };
+        asm.instrs.swap(0, 3);
+        assert!(asm.check_schedule(&instrs).is_err());
+        asm.instrs = instrs.clone();
+        asm.instrs.pop();
+        assert_eq!(asm.check_schedule(&instrs), Err("`mov rbx, 2` is missing".to_string()));
+    }
+}
+
+mod flags {
+    use snake::asm::*;
+
+    fn sar(reg: Reg) -> Instr {
+        Instr::Sar(ShArgs { reg, by: 1 })
+    }
+
+    fn mov(dst: Reg, src: Reg) -> Instr {
+        Instr::Mov(MovArgs::ToReg(dst, Arg64::Reg(src)))
+    }
+
+    #[test]
+    fn model() {
+        assert_eq!(ConditionCode::L.reads(), EFlags::SF | EFlags::OF);
+        assert_eq!(flags_read(&Instr::SetCC(ConditionCode::E, Reg8::Al)), EFlags::ZF);
+        assert_eq!(flags_written(&Instr::Shl(ShArgs { reg: Reg::Rax, by: 0 })), EFlags::NONE);
+        assert_eq!(flags_written(&Instr::Xchg(Reg::Rax, Reg::Rbx)), EFlags::NONE);
+        assert!(flags_written(&Instr::IMul(BinArgs::ToReg(Reg::Rax, Arg32::Signed(3)))).contains(
+            EFlags::ZF | EFlags::OF
+        ));
+        let and = Instr::And(BinArgs::ToReg(Reg::Rax, Arg32::Signed(1)));
+        assert!(result_flags(&and).unwrap().1.contains(EFlags::ZF | EFlags::CF | EFlags::OF));
+        let add = Instr::Add(BinArgs::ToReg(Reg::Rax, Arg32::Signed(1)));
+        assert!(!result_flags(&add).unwrap().1.contains(EFlags::OF));
+    }
+
+    #[test]
+    fn reuse() {
+        let and = Instr::And(BinArgs::ToReg(Reg::Rax, Arg32::Signed(1)));
+        let add = Instr::Add(BinArgs::ToReg(Reg::Rax, Arg32::Signed(1)));
+        let imul = Instr::IMul(BinArgs::ToReg(Reg::Rax, Arg32::Signed(3)));
+        let and = [and];
+        assert_eq!(reuse_flags(&and, Reg::Rax, ConditionCode::NE), Some(ConditionCode::NE));
+        assert_eq!(reuse_flags(&and, Reg::Rax, ConditionCode::G), Some(ConditionCode::G));
+        let add = [add];
+        assert_eq!(reuse_flags(&add, Reg::Rax, ConditionCode::L), Some(ConditionCode::S));
+        assert_eq!(reuse_flags(&add, Reg::Rax, ConditionCode::G), None);
+        assert_eq!(reuse_flags(&[imul], Reg::Rax, ConditionCode::E), None);
+        // the result may be copied, but not changed
+        let copied = [sar(Reg::Rsi), mov(Reg::Rax, Reg::Rsi), mov(Reg::Rsi, Reg::Rdi)];
+        assert_eq!(reuse_flags(&copied, Reg::Rax, ConditionCode::L), Some(ConditionCode::S));
+        let changed = [sar(Reg::Rsi), mov(Reg::Rsi, Reg::Rdi), mov(Reg::Rax, Reg::Rsi)];
+        assert_eq!(reuse_flags(&changed, Reg::Rax, ConditionCode::L), None);
+        let other = [sar(Reg::Rsi), mov(Reg::Rax, Reg::Rdi)];
+        assert_eq!(reuse_flags(&other, Reg::Rax, ConditionCode::L), None);
+        let mut symbols = Symbols::new();
+        let joined = [sar(Reg::Rax), Instr::Label(symbols.local("join"))];
+        assert_eq!(reuse_flags(&joined, Reg::Rax, ConditionCode::E), None);
+    }
+
+    #[test]
+    fn assertions_reuse_the_flags() -> Result<(), String> {
+        use snake::backend::*;
+        let src = snake::runner::read_file(std::path::Path::new("examples/arith.dbk"))
+            .map_err(|e| e.to_string())?;
+        let (resolver, ast) = snake::compile::frontend(&src)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+        let mut allocator = RegisterAllocator::new();
+        allocator.graph_color(ConflictAnalysis::new(&ssa), &Reg::ALLOCATABLE, false);
+        let mut emitter = Emitter::from(allocator);
+        emitter.emit_prog(&ssa);
+        let asm = emitter.to_asm();
+        let instrs = asm.instrs.iter().filter(|i| !matches!(i, Instr::Comment(_)));
+        let instrs: Vec<_> = instrs.collect();
+        let and = Instr::And(BinArgs::ToReg(Reg::Rax, Arg32::Signed(1)));
+        let checks: Vec<_> = instrs.windows(2).filter(|w| *w[0] == and).collect();
+        assert!(!checks.is_empty());
+        assert!(checks.iter().all(|w| !matches!(w[1], Instr::Cmp(_))), "{}", asm);
+        Ok(())
+    }
+}
+
+mod size_report {
+    use snake::asm::*;
+
+    #[test]
+    fn instr_sizes() {
+        let rsp = |offset| MemRef { reg: Reg::Rsp, offset };
+        let mut symbols = Symbols::new();
+        let entry = symbols.global("entry");
+        let cases = [
+            // mov eax, 5
+            (Instr::Mov(MovArgs::ToReg(Reg::Rax, Arg64::Signed(5))), 5),
+            // mov r8d, 5
+            (Instr::Mov(MovArgs::ToReg(Reg::R8, Arg64::Signed(5))), 6),
+            (Instr::Mov(MovArgs::ToReg(Reg::Rax, Arg64::Signed(-1))), 7),
+            (Instr::Mov(MovArgs::ToReg(Reg::Rax, Arg64::Signed(1 << 40))), 10),
+            (Instr::Mov(MovArgs::ToMem(rsp(-8), Reg32::Reg(Reg::Rdi))), 5),
+            (Instr::Mov(MovArgs::ToReg(Reg::Rdi, Arg64::Mem(rsp(-1024)))), 8),
+            (Instr::Sub(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(8))), 4),
+            (Instr::Add(BinArgs::ToReg(Reg::Rax, Arg32::Signed(1024))), 6),
+            (Instr::Sal(ShArgs { reg: Reg::Rax, by: 1 }), 3),
+            (Instr::JCC(ConditionCode::O, symbols.local("overflow")), 6),
+            (Instr::Label(entry), 0),
+            (Instr::Quad(-1), 8),
+            (Instr::QuadAddr(entry), 8),
+            (Instr::Asciz("snake".to_string()), 6),
+            (Instr::Align(8), 0),
+            (Instr::Ret, 1),
+        ];
+        for (instr, size) in cases {
+            let txt = instr_to_string(&instr, &symbols);
+            assert_eq!(instr_size(&instr), size, "size of `{}`", txt.trim());
+        }
+    }
+
+    #[test]
+    fn data_directives() {
+        let mut symbols = Symbols::new();
+        let pool = symbols.local("pool");
+        let data = [
+            Instr::Section(".data".to_string()),
+            Instr::Align(8),
+            Instr::Label(pool),
+            Instr::Quad(-2),
+            Instr::QuadAddr(pool),
+            Instr::Asciz("a `b`\\\n".to_string()),
+        ];
+        let txt: Vec<_> =
+            data.iter().map(|instr| instr_to_string(instr, &symbols).trim().to_string()).collect();
+        assert_eq!(
+            txt,
+            ["section .data", "align 8", "pool:", "dq -2", "dq pool", "db `a \\`b\\`\\\\\\x0a`, 0"]
+        );
+    }
+
+    #[test]
+    fn totals() -> Result<(), String> {
+        use snake::backend::*;
+        use snake::cli::{CompilerConf, Optimization, Verbosity};
+        let src = "def main(x):\n  def inc(n): n + 1 in\n  let y = inc(x) in y * 2";
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let conf = CompilerConf::new(opts, Verbosity::Minimalistic);
+        let ssa =
+            LivenessAnalyzer::new(&ssa).analyze(snake::compile::dead_code_elimination(ssa, &conf));
+        let mut allocator = RegisterAllocator::new();
+        allocator.graph_color(ConflictAnalysis::new(&ssa), &Reg::ALLOCATABLE, false);
+        let mut emitter = Emitter::from(allocator);
+        emitter.emit_prog(&ssa);
+        let asm = emitter.to_asm();
+        let report = SizeReport::new(&ssa, &asm);
+        let names: Vec<_> = report.0.iter().map(|(name, ..)| name.as_str()).collect();
+        assert_eq!(names.len(), 3, "unexpected rows {:?}", names);
+        assert!(names.contains(&"entry") && names.contains(&"(error handlers)"));
+        let bytes: usize = report.0.iter().map(|(.., bytes)| bytes).sum();
+        assert_eq!(bytes, asm.instrs.iter().map(instr_size).sum::<usize>());
+        Ok(())
+    }
+}
+
+mod freestanding {
+    use snake::cli::{CompilerConf, Verbosity};
+    use snake::compile;
+
+    fn asm(file: &str) -> Result<String, String> {
+        let (resolver, ast) = snake::runner::emit_ast(std::path::Path::new(file))?;
+        let (lowerer, ssa) = compile::middle_end_with(resolver, ast, &Default::default())?;
+        compile::check_freestanding(&ssa)?;
+        let mut conf = CompilerConf::new([], Verbosity::Minimalistic);
+        conf.freestanding = true;
+        Ok(compile::backend_with(lowerer, ssa, &conf))
+    }
+
+    #[test]
+    fn runtime_appended() -> Result<(), String> {
+        let txt = asm("examples/print.dbk")?;
+        assert!(txt.lines().any(|l| l.trim() == "global _start"));
+        for service in ["print", "snake_error", "snake_new_array"] {
+            assert!(!txt.contains(&format!("extern {}", service)), "`{}` is declared", service);
+            assert!(txt.contains(&format!("\n{}:", service)), "`{}` is missing", service);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unavailable_extern() {
+        let Err(e) = asm("examples/extern_big_nine.dbk") else { panic!("expected an error") };
+        assert_eq!(e, "extern `big_fun_nine` is not available with --freestanding");
+    }
+}
+mod target_triple {
+    use snake::cli::TargetTriple;
+    use snake::runner::check_executable;
+    use std::path::Path;
+
+    #[test]
+    fn runs_on() {
+        use TargetTriple::*;
+        assert!(LinuxMusl.runs_on(LinuxGnu) && LinuxGnu.runs_on(LinuxMusl));
+        assert!(!Darwin.runs_on(LinuxGnu) && !LinuxGnu.runs_on(Darwin));
+    }
+
+    #[test]
+    fn foreign_needs_emulator() {
+        let Some(host) = TargetTriple::host() else { return };
+        let foreign = if host == TargetTriple::Darwin {
+            TargetTriple::LinuxGnu
+        } else {
+            TargetTriple::Darwin
+        };
+        let Err(e) = check_executable(foreign, None) else { panic!("expected an error") };
+        assert!(e.contains("--run-under"), "unexpected error: {}", e);
+        assert_eq!(check_executable(foreign, Some(Path::new("qemu-x86_64"))), Ok(()));
+        assert_eq!(check_executable(host, None), Ok(()));
+    }
+}
+mod frame_pointer {
+    use snake::cli::{CompilerConf, FramePointer, Verbosity};
+    use snake::compile;
+    use std::process::Command;
+
+    /// Uses all the non-volatile registers across its calls
+    const FILE: &str = "examples/frame_pointer.dbk";
+
+    fn asm(frame_pointer: FramePointer) -> Result<String, String> {
+        let (resolver, ast) = snake::runner::emit_ast(std::path::Path::new(FILE))?;
+        let (lowerer, ssa) = compile::middle_end_with(resolver, ast, &Default::default())?;
+        let mut conf = CompilerConf::new([], Verbosity::Minimalistic);
+        conf.frame_pointer = Some(frame_pointer);
+        Ok(compile::backend_with(lowerer, ssa, &conf))
+    }
+
+    #[test]
+    fn keep() -> Result<(), String> {
+        let txt = asm(FramePointer::Keep)?;
+        let lines: Vec<_> = txt.lines().map(str::trim).collect();
+        let saves = lines.iter().filter(|l| **l == "mov QWORD [rsp + -8], rbp").count();
+        let restores = lines.iter().filter(|l| **l == "mov rbp, QWORD [rsp + -8]").count();
+        assert_eq!(saves, 3);
+        assert_eq!(restores, lines.iter().filter(|l| **l == "ret").count());
+        // rbp is never allocated
+        let writes = lines.iter().filter(|l| l.starts_with("mov rbp,")).count();
+        assert_eq!(writes, saves + restores);
+        assert!(!txt.contains(".cfi") && !txt.contains(".eh_frame"));
+        Ok(())
+    }
+
+    #[test]
+    fn omit() -> Result<(), String> {
+        let txt = asm(FramePointer::Omit)?;
+        assert!(txt.contains("section .eh_frame progbits alloc noexec nowrite align=8"));
+        // an entry for the code, relative to itself
+        assert!(txt.contains("dd cfi#0 - $"));
+        // rbp is allocated, and each function tells where it saves the registers
+        assert!(txt.contains(";;; .cfi_same_value rbp"));
+        // once for the error handlers and once per function after the saves
+        assert_eq!(txt.matches(";;; .cfi_offset rbx").count(), 4);
+        assert_eq!(txt.matches(";;; .cfi_same_value rbx").count(), 3);
+        Ok(())
+    }
+
+    /// Assembles the tables and reads them back, if nasm and readelf are installed
+    #[test]
+    fn readelf() -> Result<(), String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let (asm_file, obj_file) = (dir.path().join("fp.s"), dir.path().join("fp.o"));
+        std::fs::write(&asm_file, asm(FramePointer::Omit)?).map_err(|e| e.to_string())?;
+        let mut nasm = Command::new("nasm");
+        let nasm = nasm.arg("-felf64").arg(&asm_file).arg("-o").arg(&obj_file).output();
+        let Ok(nasm) = nasm else { return Ok(()) };
+        assert!(nasm.status.success(), "{}", String::from_utf8_lossy(&nasm.stderr));
+        let readelf = Command::new("readelf").arg("--debug-dump=frames").arg(&obj_file).output();
+        let Ok(readelf) = readelf else { return Ok(()) };
+        let frames = String::from_utf8_lossy(&readelf.stdout);
+        for expected in [
+            "Augmentation:          \"zR\"",
+            "DW_CFA_def_cfa: r7 (rsp) ofs 8",
+            "DW_CFA_def_cfa_offset: 16",
+            "DW_CFA_same_value: r3 (rbx)",
+            "DW_CFA_offset: r3 (rbx) at cfa-",
+        ] {
+            assert!(frames.contains(expected), "{} not in\n{}", expected, frames);
+        }
+        assert_eq!(frames.matches(" FDE ").count(), 1, "{}", frames);
+        Ok(())
+    }
+}
+mod function_sections {
+    use snake::cli::{CompilerConf, Verbosity};
+    use snake::compile;
+    use snake::error::LinkError;
+    use snake::runner::{self, LinkConf};
+    use std::path::Path;
+    use std::process::Command;
+
+    fn asm(file: &str) -> Result<String, String> {
+        let (resolver, ast) = runner::emit_ast(Path::new(file))?;
+        let (lowerer, ssa) = compile::middle_end_with(resolver, ast, &Default::default())?;
+        let mut conf = CompilerConf::new([], Verbosity::Minimalistic);
+        conf.function_sections = true;
+        Ok(compile::backend_with(lowerer, ssa, &conf))
+    }
+
+    #[test]
+    fn sections() -> Result<(), String> {
+        let txt = asm("examples/switch.dbk")?;
+        let sections: Vec<_> = txt.lines().filter_map(|l| l.strip_prefix("section ")).collect();
+        assert_eq!(
+            sections,
+            [
+                ".text",
+                ".text.days@0 progbits alloc exec nowrite align=16",
+                ".rodata.days@0 progbits alloc noexec nowrite align=8",
+                ".text.sum@1 progbits alloc exec nowrite align=16",
+                ".text.entry progbits alloc exec nowrite align=16",
+            ]
+        );
+        Ok(())
+    }
+
+    /// Links the functions that are called only, if nasm is installed and
+    /// the build is not hermetic, which runs no linker
+    #[test]
+    fn gc_sections() -> Result<(), String> {
+        let txt = asm("examples/gc_sections.dbk")?;
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let exe = dir.path().join("gc.exe");
+        let conf = LinkConf { gc_sections: true, ..Default::default() };
+        match runner::link(&txt, Path::new("runtime/stub.rs"), dir.path(), &exe, &conf) {
+            Err(LinkError::Spawn { tool, .. }) if tool == "nasm" => return Ok(()),
+            Err(LinkError::Unsupported(_)) if cfg!(feature = "hermetic") => return Ok(()),
+            linked => linked.map_err(|e| e.to_string())?,
+        }
+        let nm = Command::new("nm").arg(&exe).output().map_err(|e| e.to_string())?;
+        let symbols = String::from_utf8_lossy(&nm.stdout);
+        assert!(symbols.contains(" used#"), "{}", symbols);
+        assert!(!symbols.contains(" unused@"), "{}", symbols);
+        let out = Command::new(&exe).arg("4").output().map_err(|e| e.to_string())?;
+        assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "5");
+        Ok(())
+    }
+}
+mod fragments {
+    use snake::asm::{stitch, Fragment};
+    use snake::cli::{CompilerConf, Verbosity};
+    use snake::compile;
+    use snake::encode::{encode, parse};
+    use snake::runner;
+    use std::path::Path;
+
+    fn fragments(file: &str, freestanding: bool) -> Result<Vec<Fragment>, String> {
+        let (resolver, ast) = runner::emit_ast(Path::new(file))?;
+        let (lowerer, ssa) = compile::middle_end_with(resolver, ast, &Default::default())?;
+        let mut conf = CompilerConf::new([], Verbosity::Minimalistic);
+        conf.freestanding = freestanding;
+        Ok(compile::backend_fragments(lowerer, ssa, &conf))
+    }
+
+    /// The first fragment declares the externs and each other one holds a
+    /// function with its block; stitched in any order they encode as one
+    #[test]
+    fn stitched() -> Result<(), String> {
+        let fragments = fragments("examples/switch.dbk", false)?;
+        let names: Vec<_> = fragments[1..].iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["days@0", "sum@1", "entry"]);
+        assert!(fragments[0].externs.contains(&"snake_error".to_string()));
+        assert!(fragments[1].needs.iter().all(|name| !fragments[1].defines.contains(name)));
+        let mut reversed = fragments.clone();
+        reversed.reverse();
+        for fragments in [fragments, reversed] {
+            let txt = stitch(&fragments).map_err(|problems| problems.join("\n"))?;
+            encode(&parse(&txt)?)?;
+        }
+        Ok(())
+    }
+
+    /// A function that is called but missing, or defined twice, is reported
+    /// by its label
+    #[test]
+    fn unresolved() -> Result<(), String> {
+        let fragments = fragments("examples/switch.dbk", false)?;
+        let missing: Vec<_> = fragments.iter().filter(|f| f.name != "days@0").cloned().collect();
+        assert_eq!(stitch(&missing), Err(vec!["label `days@0` is never defined".to_string()]));
+        let twice = [fragments.clone(), vec![fragments[1].clone()]].concat();
+        let problems = stitch(&twice).expect_err("days@0 is defined twice");
+        assert!(problems.contains(&"label `days@0` is defined 2 times".to_string()));
+        Ok(())
+    }
+
+    /// The freestanding runtime is a fragment that defines the externs that
+    /// the code leaves undeclared
+    #[test]
+    fn freestanding() -> Result<(), String> {
+        let fragments = fragments("examples/switch.dbk", true)?;
+        let txt = stitch(&fragments).map_err(|problems| problems.join("\n"))?;
+        assert!(txt.ends_with(compile::FREESTANDING_RUNTIME));
+        Ok(())
+    }
+}
+mod exit_codes {
+    use std::path::Path;
+    use std::process::Command;
+
+    /// The exit code of `snake` on the program `src` with `args`
+    fn snake(src: &str, args: &[&str]) -> Result<Option<i32>, String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let file = dir.path().join("prog.dbk");
+        std::fs::write(&file, src).map_err(|e| e.to_string())?;
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .arg(&file)
+            .args(args)
+            .output()
+            .map_err(|e| e.to_string())?;
+        Ok(out.status.code())
+    }
+
+    #[test]
+    fn compiler() -> Result<(), String> {
+        assert_eq!(snake("def main(x): x", &["-t", "ast"])?, Some(0));
+        assert_eq!(snake("def main(x): (", &["-t", "ast"])?, Some(2));
+        assert_eq!(snake("def main(x): y", &["-t", "ssa"])?, Some(3));
+        let ill_typed = "def main(x): if x: x + 1 else: 0";
+        assert_eq!(snake(ill_typed, &["-t", "ssa"])?, Some(0));
+        assert_eq!(snake(ill_typed, &["--types", "infer", "-t", "ssa"])?, Some(4));
+        // a runtime error of the interpreters
+        assert_eq!(snake("def main(x): x[0][0]", &["-t", "ast", "-x", "1"])?, Some(1));
+        let mut missing = Command::new(env!("CARGO_BIN_EXE_snake"));
+        let missing = missing.arg("examples/missing.dbk").output().map_err(|e| e.to_string())?;
+        assert_eq!(missing.status.code(), Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn program() -> Result<(), String> {
+        let mut out = Vec::new();
+        let sh = Some(Path::new("sh"));
+        let script = ["echo 1; echo 2 >&2; exit 7"];
+        let (status, stderr) =
+            snake::runner::run_status(sh, Path::new("-c"), script, &[], &mut out)?;
+        assert_eq!((status.code(), stderr.as_str()), (Some(7), "2\n"));
+        assert_eq!(String::from_utf8_lossy(&out), "1\n");
+        Ok(())
+    }
+}
+mod ice {
+    use std::process::Command;
+
+    /// The files of the ustar archive `tar`, with their contents
+    fn files(tar: &[u8]) -> Vec<(String, String)> {
+        let mut files = Vec::new();
+        let mut at = 0;
+        while tar[at] != 0 {
+            let field = |range: std::ops::Range<usize>| {
+                String::from_utf8_lossy(&tar[at + range.start..at + range.end])
+                    .trim_end_matches('\0')
+                    .to_string()
+            };
+            let name = field(0..100);
+            let size = usize::from_str_radix(&field(124..135), 8).unwrap();
+            let contents = String::from_utf8_lossy(&tar[at + 512..at + 512 + size]).into_owned();
+            files.push((name, contents));
+            at += 512 + size.next_multiple_of(512);
+        }
+        files
+    }
+
+    /// The files in `dir`
+    fn entries(dir: &std::path::Path) -> Result<Vec<std::path::PathBuf>, String> {
+        let entries = std::fs::read_dir(dir).map_err(|e| e.to_string())?;
+        Ok(entries.map(|entry| entry.unwrap().path()).collect())
+    }
+
+    #[test]
+    fn bundle() -> Result<(), String> {
+        let (cwd, tmp) = (tempfile::TempDir::new(), tempfile::TempDir::new());
+        let (cwd, tmp) = (cwd.map_err(|e| e.to_string())?, tmp.map_err(|e| e.to_string())?);
+        let ice_dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let file = std::fs::canonicalize("examples/backtrace.dbk").map_err(|e| e.to_string())?;
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .current_dir(cwd.path())
+            .env("TMPDIR", tmp.path())
+            .arg(&file)
+            .args(["-t", "ssa", "-O=cp", "--panic-at", "copy propagation", "--ice-dir"])
+            .arg(ice_dir.path())
+            .output()
+            .map_err(|e| e.to_string())?;
+        assert_eq!(out.status.code(), Some(5));
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains("internal compiler error in the pass `copy propagation`"));
+        assert_eq!(entries(cwd.path())?, Vec::<std::path::PathBuf>::new());
+        assert_eq!(entries(tmp.path())?, Vec::<std::path::PathBuf>::new());
+        let bundles = entries(ice_dir.path())?;
+        assert_eq!(bundles.len(), 1, "{:?}", bundles);
+        let name = bundles[0].file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with("lekans-ice-") && name.ends_with(".tar"));
+        assert!(stderr.contains(&bundles[0].display().to_string()), "{}", stderr);
+
+        let tar = std::fs::read(&bundles[0]).map_err(|e| e.to_string())?;
+        let files = files(&tar);
+        let names: Vec<_> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["report.txt", "program.dbk", "pre-pass.ssa"]);
+        assert!(files[0].1.starts_with("pass: copy propagation\nflags: "));
+        assert!(files[0].1.contains("-O=cp --panic-at copy propagation"));
+        let src = std::fs::read_to_string(&file).map_err(|e| e.to_string())?;
+        assert_eq!(files[1].1, src);
+        assert!(files[2].1.contains("fun sum@1("), "{}", files[2].1);
+        Ok(())
+    }
+
+    /// Without --ice-dir, the bundle goes to the temporary directory
+    #[test]
+    fn bundle_in_temp_dir() -> Result<(), String> {
+        let (cwd, tmp) = (tempfile::TempDir::new(), tempfile::TempDir::new());
+        let (cwd, tmp) = (cwd.map_err(|e| e.to_string())?, tmp.map_err(|e| e.to_string())?);
+        let file = std::fs::canonicalize("examples/backtrace.dbk").map_err(|e| e.to_string())?;
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .current_dir(cwd.path())
+            .env("TMPDIR", tmp.path())
+            .arg(&file)
+            .args(["-t", "ssa", "--panic-at", "lowering"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        assert_eq!(out.status.code(), Some(5));
+        assert_eq!(entries(cwd.path())?, Vec::<std::path::PathBuf>::new());
+        let bundles = entries(tmp.path())?;
+        assert_eq!(bundles.len(), 1, "{:?}", bundles);
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains(&bundles[0].display().to_string()), "{}", stderr);
+        Ok(())
+    }
+}
+mod remarks {
+    use super::*;
+    use snake::middle_end::{CopyPropagator, LoadForwarder, Lowerer};
+    use snake::remarks::RemarkKind;
+    use std::process::Command;
+
+    #[test]
+    fn json() -> Result<(), String> {
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .arg("examples/adjacent_accesses.dbk")
+            .args(["-t", "ssa", "-O=tspec,cp,bcc", "--remarks", "json"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        assert!(out.status.success());
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        let bcc: Vec<_> = stderr.lines().filter(|l| l.contains(r#""pass":"bcc""#)).collect();
+        assert!(stderr.lines().all(|l| l.starts_with(r#"{"kind":""#) && l.ends_with("\"}")));
+        // a[i + 1] with a[i], a[i] with a[i] := _ and a[i + 3] with a[i + 1] := _
+        let passed = bcc.iter().filter(|l| l.starts_with(r#"{"kind":"passed""#));
+        assert_eq!(passed.count(), 3, "{}", stderr);
+        let missed = |reason: &str| bcc.iter().any(|l| l.contains(reason));
+        assert!(missed("i%2 - 1]: the index is below the one of a%1[i%2 + 1]"), "{}", stderr);
+        assert!(missed("the assertion that mul_0%44 is Int may fail"), "{}", stderr);
+        Ok(())
+    }
+
+    #[test]
+    fn forwarding() -> Result<(), String> {
+        let inp = runner::read_file(Path::new("examples/forwarding.dbk"))
+            .map_err(|e| format!("Error reading file: {}", e))?;
+        let (resolver, ast) = snake::compile::frontend(&inp)?;
+        let ssa = CopyPropagator::new().run(Lowerer::from(resolver).lower_prog(ast));
+        let mut forwarder = LoadForwarder::new(&ssa);
+        forwarder.run(ssa);
+        let remarks = forwarder.remarks();
+        let passed = remarks.iter().filter(|r| r.kind == RemarkKind::Passed);
+        assert_eq!(passed.count(), forwarder.forwarded());
+        // the elements of `a` after the calls to `f`, which stores to its arguments
+        let call = remarks.iter().find(|r| r.message.contains("the call to f@0 may write it"));
+        let call = call.ok_or(format!("{:?}", remarks))?;
+        assert_eq!((call.kind, call.function.as_str()), (RemarkKind::Missed, "entry"));
+        Ok(())
+    }
+}
+mod dump {
+    use super::*;
+    use snake::ana::{Coloring, Graph, Nil, PerfectEliminationOrder};
+    use snake::ast::SurfProg;
+    use snake::dump::{Format, Versioned};
+    use snake::identifiers::VarName;
+    use snake::layout::{Width, DEFAULT_WIDTH};
+    use snake::middle_end::{CopyPropagator, Lowerer};
+    use snake::ssa::Program;
+    use std::fmt::Display;
+    use std::process::Command;
+
+    /// The SSA of every example loads back from its dump, also with the lines broken
+    #[test]
+    fn ssa() -> Result<(), String> {
+        let mut loaded = 0;
+        for entry in std::fs::read_dir("examples").map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().is_none_or(|ext| ext != "dbk") {
+                continue;
+            }
+            let Ok((resolver, ast)) = runner::emit_ast(&path) else { continue };
+            let ssa = CopyPropagator::new().run(Lowerer::from(resolver).lower_prog(ast));
+            for width in [DEFAULT_WIDTH, 20] {
+                let dump = Versioned(Format::Ssa, Width(width, &ssa)).to_string();
+                let prog: Program<VarName, Nil> = dump.parse()?;
+                assert_eq!(prog.to_string(), ssa.to_string(), "{}", path.display());
+            }
+            loaded += 1;
+        }
+        assert!(loaded > 40, "loaded {}", loaded);
+        Ok(())
+    }
+
+    /// Each dump of `snake -t` loads and writes back as it was
+    #[test]
+    fn targets() -> Result<(), String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let base = dir.path().join("switch");
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .args(["examples/switch.dbk", "-O=cp,tspec,bcc", "-o"])
+            .arg(&base)
+            .args(["-t", "ast", "-t", "ssa", "-t", "graph", "-t", "elim-order"])
+            .args(["-t", "coloring"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        let read = |ext: &str| std::fs::read_to_string(base.with_extension(ext));
+        let read = |ext: &str| read(ext).map_err(|e| format!("{}: {}", ext, e));
+        let written = |format, dump: &dyn Display| format!("{}\n", Versioned(format, dump));
+
+        let ast = read("ast")?;
+        let prog: SurfProg = ast.parse()?;
+        assert_eq!(written(Format::Ast, &Width(DEFAULT_WIDTH, &prog)), ast);
+        let ssa = read("ssa")?;
+        let prog: Program<VarName, Nil> = ssa.parse()?;
+        assert_eq!(written(Format::Ssa, &Width(DEFAULT_WIDTH, &prog)), ssa);
+        let graph = read("graph")?;
+        assert_eq!(written(Format::Graph, &graph.parse::<Graph<VarName>>()?), graph);
+        let order = read("elim")?;
+        assert_eq!(written(Format::ElimOrder, &order.parse::<PerfectEliminationOrder>()?), order);
+        let coloring = read("coloring")?;
+        assert_eq!(written(Format::Coloring, &coloring.parse::<Coloring>()?), coloring);
+        Ok(())
+    }
+
+    #[test]
+    fn versions() {
+        let load = |dump: &str| dump.parse::<Coloring>().map(|_| ()).map_err(|e| e.to_string());
+        assert_eq!(load("# format_version: coloring 1.0\nx%0 -> rax\ny%1 -> <2>"), Ok(()));
+        let missing = "line 1: missing the header `# format_version: <format> <major>.<minor>`";
+        assert_eq!(load("x%0 -> rax"), Err(missing.to_string()));
+        let unsupported = |v| format!("line 1: coloring {} is not supported: this snake reads \
+                                       coloring 1.x up to 1.0", v);
+        assert_eq!(load("# format_version: coloring 2.0\n"), Err(unsupported("2.0")));
+        assert_eq!(load("# format_version: coloring 1.1\n"), Err(unsupported("1.1")));
+        let graph = load("# format_version: graph 1.0\nvertex x%0");
+        assert_eq!(graph, Err("line 1: expected a dump of coloring, found graph".to_string()));
+        let ssa = "# format_version: ssa 1.0\nblock b#0():\n  x%1 = y\n  ret x%1";
+        let err = ssa.parse::<Program<VarName, Nil>>().map(|_| ()).map_err(|e| e.to_string());
+        assert_eq!(err, Err("line 3: `y` is not a variable `hint%n`".to_string()));
+    }
+}
+mod expr_asm {
+    use super::*;
+    use snake::identifiers::FunName;
+    use std::collections::HashSet;
+    use std::process::Command;
+
+    const EXPR: &str = "if x < y: y * 2 else: x + 1";
+
+    #[test]
+    fn fragment() -> Result<(), String> {
+        let params = ["x".to_string(), "y".to_string()];
+        let (_, ssa) = snake::compile::fragment(EXPR, &params, &HashSet::new())?;
+        let entry = ssa.funs.iter().find(|fun| fun.name == FunName::unmangled("entry"));
+        let entry = entry.ok_or(ssa.to_string())?;
+        assert_eq!(entry.params.len(), 2);
+        assert_eq!(entry.body.args.len(), 2);
+        let err = snake::compile::fragment(EXPR, &params[..1], &HashSet::new()).err();
+        assert!(matches!(err, Some(Error::Resolve(ResolveError::UnboundVariable(..)))));
+        Ok(())
+    }
+
+    #[test]
+    fn target() -> Result<(), String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let input = dir.path().join("expr");
+        std::fs::write(&input, EXPR).map_err(|e| e.to_string())?;
+        let snake = |args: &[&str]| {
+            Command::new(env!("CARGO_BIN_EXE_snake")).arg(&input).args(args).output()
+        };
+        let out = snake(&["-t", "expr-asm", "--params", "x,y"]).map_err(|e| e.to_string())?;
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        assert!(String::from_utf8_lossy(&out.stdout).contains("global entry"));
+        let out = snake(&["-t", "expr-asm", "--params", "x"]).map_err(|e| e.to_string())?;
+        assert_eq!(out.status.code(), Some(3));
+        let out = snake(&["-t", "expr-asm", "-t", "ssa"]).map_err(|e| e.to_string())?;
+        assert!(String::from_utf8_lossy(&out.stderr).contains("cannot be combined"));
+        Ok(())
+    }
+}
+mod generators {
+    use super::*;
+    use snake::cli::Optimization;
+
+    mk_o3_test!(squares, "generators.dbk", ["1", "4"], "[30, -1, -1]");
+
+    /// Runs `main` on `args` with both interpreters, which must agree
+    fn run_both(src: &str, args: &[&str]) -> Result<String, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let by_ast = interp::ast::Machine::run(&ast, args.iter().copied());
+        let by_ast = by_ast.map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, args.iter().copied());
+        let by_ssa = by_ssa.map_err(|e| e.to_string())?;
+        assert_eq!(by_ast.to_string(), by_ssa.to_string());
+        Ok(by_ast.to_string())
+    }
+
+    #[test]
+    fn streams() -> Result<(), String> {
+        let src = std::fs::read_to_string("examples/generators.dbk").map_err(|e| e.to_string())?;
+        // the sum and the value of the finished stream, as the interpreters print no arrays
+        let result = "[sum(stream, 0, args[1]), next(stream), next(stream)]";
+        let src = src.replace(result, "sum(stream, 0, args[1]) * 10 + next(stream)");
+        assert_eq!(run_both(&src, &["1", "4"])?, "299");
+        assert_eq!(run_both(&src, &["2", "3"])?, "289");
+        Ok(())
+    }
+
+    #[test]
+    fn yields_within_expressions() -> Result<(), String> {
+        let src = "def main(args):
+  def gen pairs(a, b):
+    let s = yield(a) + yield(b) in
+    if yield(s) > 10: switch yield(s * 2): | 0: 100 | _: 200 else: [yield(a * b), 7][1]
+  and def drain(g, acc, k):
+    if k == 0: acc else: drain(g, acc * 100 + next(g), k - 1)
+  in drain(pairs(args[0], args[1]), 0, 6)";
+        assert_eq!(run_both(src, &["2", "3"])?, "20305060707");
+        assert_eq!(run_both(src, &["5", "9"])?, "50914300200");
+        Ok(())
+    }
+
+    #[test]
+    fn nested_generators() -> Result<(), String> {
+        let src = "def main(args):
+  def gen outer(n):
+    def gen inner(m):
+      let _ = yield(m + n) in m * n
+    in
+    let g = inner(n + 1) in
+    let _ = yield(next(g)) in
+    yield(next(g))
+  in
+  let o = outer(args[0]) in
+  let a = next(o), b = next(o), c = next(o) in
+  a * 10000 + b * 100 + c";
+        assert_eq!(run_both(src, &["3"])?, "71212");
+        Ok(())
+    }
+
+    #[test]
+    fn next_of_a_non_generator() {
+        let err = run_both("def main(args): next(args)", &["1"]).unwrap_err();
+        assert!(err.contains("out of bounds"), "{}", err);
+    }
+
+    #[test]
+    fn misplaced_yields() {
+        let err = snake::compile::frontend("def main(x): let y = yield(1) in y").err();
+        assert!(matches!(err, Some(Error::Resolve(ResolveError::YieldOutsideGenerator(_)))));
+        let src = "def main(x):
+  def gen g(n):
+    def step(i): yield(i)
+    in step(n) + 1
+  in next(g(1))";
+        match snake::compile::frontend(src).err() {
+            Some(Error::Resolve(ResolveError::NonTailYield(f, _))) => assert_eq!(f, "step"),
+            err => panic!("expected a non-tail call of a function that yields: {:?}", err),
+        }
+    }
+}
+mod destructuring {
+    use super::*;
+    use snake::cli::Optimization;
+
+    mk_o3_test!(bounds, "destructuring.dbk", ["5", "-3", "9", "2"], "[-3, 9, 12]");
+    mk_o3_dyn_fail_test!(too_few_elements, "destructuring.dbk", [], "index 0 out of bounds");
+
+    /// Runs `main` on `args` with both interpreters, which must agree
+    fn run_both(src: &str, args: &[&str]) -> Result<String, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let by_ast = interp::ast::Machine::run(&ast, args.iter().copied());
+        let by_ast = by_ast.map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, args.iter().copied());
+        let by_ssa = by_ssa.map_err(|e| e.to_string())?;
+        assert_eq!(by_ast.to_string(), by_ssa.to_string());
+        Ok(by_ast.to_string())
+    }
+
+    #[test]
+    fn elements_in_scope() -> Result<(), String> {
+        // the elements are in scope of the later bindings and of the functions
+        let src = "def main(args):
+  let [a, b] = args, [c, d, e] = [b, a, a * b] in
+  def f(x): x * c + d in
+  f(e)";
+        assert_eq!(run_both(src, &["3", "4"])?, "51");
+        Ok(())
+    }
+
+    #[test]
+    fn longer_arrays() -> Result<(), String> {
+        // the elements are a prefix of the array
+        assert_eq!(run_both("def main(args): let [a] = args in a", &["7", "8"])?, "7");
+        let err = run_both("def main(args): let [a, b, c] = args in a", &["7", "8"]).unwrap_err();
+        assert!(err.contains("2 is out of bounds"), "{}", err);
+        let err = run_both("def main(args): let [a] = 5 in a", &[]).unwrap_err();
+        assert!(err.contains("Array assertion failed"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn within_generators() -> Result<(), String> {
+        let src = "def main(args):
+  def gen pairs(n):
+    let [a, b] = [yield(n), n + 1] in a + b
+  in
+  let g = pairs(args[0]) in
+  let x = next(g), y = next(g) in x * 100 + y";
+        assert_eq!(run_both(src, &["4"])?, "409");
+        Ok(())
+    }
+
+    #[test]
+    fn prints_back() -> Result<(), String> {
+        let src = "def main(x): let [a, b] = x, c = a in (b + c)";
+        let prog = snake::compile::parse(src).map_err(|e| e.to_string())?;
+        assert_eq!(prog.to_string(), src);
+        let err = snake::compile::frontend("def main(x): let [a, a] = x in a").err();
+        assert!(matches!(err, Some(Error::Resolve(ResolveError::DuplicateVariable(..)))));
+        Ok(())
+    }
+}
+mod options {
+    use super::*;
+    use snake::cli::Optimization;
+
+    mk_o3_test!(positions, "options.dbk", ["4", "1", "4"], "[2, 5]");
+    mk_o3_test!(missing, "options.dbk", ["4", "1", "2"], "[none, 3]");
+
+    /// Runs `main` on `args` with both interpreters, which must agree
+    fn run_both(src: &str, args: &[&str]) -> Result<String, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let by_ast = interp::ast::Machine::run(&ast, args.iter().copied());
+        let by_ast = by_ast.map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, args.iter().copied());
+        let by_ssa = by_ssa.map_err(|e| e.to_string())?;
+        assert_eq!(by_ast.to_string(), by_ssa.to_string());
+        Ok(by_ast.to_string())
+    }
+
+    #[test]
+    fn ifsome() -> Result<(), String> {
+        let src = "def main(args):
+  def at(i): if i < length(args): args[i] else: none in
+  ifsome x = at(args[0]): x * 10 else: isNone(x)";
+        assert_eq!(run_both(src, &["1", "7"])?, "70");
+        assert_eq!(run_both(src, &["2", "7"])?, "true");
+        Ok(())
+    }
+
+    #[test]
+    fn sentinel() -> Result<(), String> {
+        assert_eq!(run_both("def main(x): none", &[])?, "none");
+        let src = "def main(x): none == none && !(none == false || isNone(0) || isNone([]))";
+        assert_eq!(run_both(src, &[])?, "true");
+        // `none` is of no type
+        let src = "def main(x): isInt(none) || isBool(none) || isArray(none)";
+        assert_eq!(run_both(src, &[])?, "false");
+        let err = run_both("def main(x): if none: 1 else: 2", &[]).unwrap_err();
+        assert!(err.contains("Bool assertion failed"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn prints_back() -> Result<(), String> {
+        let prog = snake::compile::parse("def main(x): ifsome y = x: y else: none")
+            .map_err(|e| e.to_string())?;
+        assert_eq!(prog.to_string(), "def main(x): let y = x in if isNone(y): none else: y");
+        Ok(())
+    }
+}
+mod strings {
+    use super::*;
+    use snake::cli::Optimization;
+
+    mk_o3_test!(hello, "strings.dbk", ["0"], "[hello, true, true, [hello, hi \"there\", ]]");
+    mk_o3_test!(quoted, "strings.dbk", ["1"], "[hi \"there\", false, true, [hello, hi \"there\", ]]");
+    mk_o3_test!(
+        interpolated,
+        "interpolation.dbk",
+        ["1"],
+        "[snake counts 1, 2 and [1, true], true, true, {1}]"
+    );
+
+    /// Runs `main` on `args` with both interpreters, which must agree
+    fn run_both(src: &str, args: &[&str]) -> Result<String, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let by_ast = interp::ast::Machine::run(&ast, args.iter().copied());
+        let by_ast = by_ast.map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, args.iter().copied());
+        let by_ssa = by_ssa.map_err(|e| e.to_string())?;
+        assert_eq!(by_ast.to_string(), by_ssa.to_string());
+        Ok(by_ast.to_string())
+    }
+
+    #[test]
+    fn interpreted() -> Result<(), String> {
+        let src = r#"def main(x): "tab\there \"quoted\"""#;
+        assert_eq!(run_both(src, &[])?, "tab\there \"quoted\"");
+        let src = r#"def main(x): let s = ["a", "b"][x[0]] in s == "a" && s != "b" && "" == """#;
+        assert_eq!(run_both(src, &["0"])?, "true");
+        assert_eq!(run_both(src, &["1"])?, "false");
+        // strings are of no type
+        let src = r#"def main(x): isInt("a") || isBool("a") || isArray("a") || isNone("a")"#;
+        assert_eq!(run_both(src, &[])?, "false");
+        let err = run_both(r#"def main(x): "a" + 1"#, &[]).unwrap_err();
+        assert!(err.contains("Int assertion failed"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn interpolated_by_both() -> Result<(), String> {
+        let src = r#"def main(x):
+  let s = "{x[0]}-{[x[0], none]}" in toString([s, s == "7-[7, none]"])"#;
+        assert_eq!(run_both(src, &["7"])?, "[7-[7, none], true]");
+        let src = r#"def main(x): let a = [1, 2], _ = a[1] := a in concat("{a}", concat(1, true))"#;
+        assert_eq!(run_both(src, &[])?, "[1, <loop>]1true");
+        // the strings made are the literals of the same text
+        let src = r#"def main(x): let s = "b" in concat("a", s) == "ab" && "{s}" == s"#;
+        assert_eq!(run_both(src, &[])?, "true");
+        Ok(())
+    }
+
+    /// The spans of the interpolated expressions point into the literal
+    #[test]
+    fn interpolated_spans() {
+        let src = r#"def main(x): "a{y}b""#;
+        let prog = snake::compile::parse(src).unwrap();
+        let err = snake::frontend::Resolver::new().resolve_prog(prog).unwrap_err();
+        assert_eq!(err.loc().start_ix, src.find('y').unwrap());
+        let src = r#"def main(x): "a{1 +}b""#;
+        let err = snake::compile::parse(src).unwrap_err();
+        assert_eq!(err.loc.map(|loc| loc.start_ix), src.find('}'));
+        let src = r#"def main(x): "{}""#;
+        assert!(snake::compile::parse(src).is_err());
+    }
+
+    #[test]
+    fn prints_back() -> Result<(), String> {
+        let src = r#"def main(x): ["a\\b", "\"", "line\n"]"#;
+        let prog = snake::compile::parse(src).map_err(|e| e.to_string())?;
+        assert_eq!(prog.to_string(), src);
+        let err = snake::compile::parse(r#"def main(x): "\q""#).unwrap_err().to_string();
+        assert!(err.contains("unknown escape in a string literal"), "{}", err);
+        // escaped braces are text, and interpolations print as the calls they stand for
+        let src = r#"def main(x): "\{x\}""#;
+        assert_eq!(snake::compile::parse(src).map_err(|e| e.to_string())?.to_string(), src);
+        let prog = snake::compile::parse(r#"def main(x): "a{x}""#).map_err(|e| e.to_string())?;
+        assert_eq!(prog.to_string(), r#"def main(x): concat("a", toString(x))"#);
+        Ok(())
+    }
+}
+mod trailing_commas {
+    /// Each program with trailing commas parses as the one without, and so
+    /// prints back without them
+    #[test]
+    fn print_without() -> Result<(), String> {
+        let progs = [
+            ("def main(x): f(1, 2,)", "def main(x): f(1, 2)"),
+            ("def main(x): [1, [2,],]", "def main(x): [1, [2]]"),
+            ("extern f(a, b: Int,)\ndef main(x): 0", "extern f(a, b: Int)\ndef main(x): 0"),
+            ("def main(x): let a = 1, b = 2, in a", "def main(x): let a = 1, b = 2 in a"),
+            ("def main(x): let [a, b,] = x in a", "def main(x): let [a, b] = x in a"),
+            ("def main(x): add1(length(x,),)", "def main(x): add1(length(x))"),
+            ("def main(x): wrapping_add(x, 1,)", "def main(x): wrapping_add(x, 1)"),
+            ("def main(x): def f(a,): a in f(1,)", "def main(x): def f(a): a in f(1)"),
+        ];
+        let parse = |src| snake::compile::parse(src).map_err(|e| format!("{}: {}", src, e));
+        for (with, without) in progs {
+            assert_eq!(parse(with)?.to_string(), parse(without)?.to_string());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn only_after_an_element() {
+        for src in ["def main(x): f(,)", "def main(x): [1,,]", "def main(x): let a = 1,, in a"] {
+            assert!(snake::compile::parse(src).is_err(), "{}", src);
+        }
+        assert!(snake::compile::parse("def main(x): let , in 1").is_err());
+    }
+}
+mod blocks {
+    use snake::layout::{Width, DEFAULT_WIDTH};
+
+    /// The program as the formatter writes it
+    fn parse(src: &str) -> Result<String, String> {
+        let prog = snake::compile::parse(src).map_err(|e| format!("{}: {}", src, e))?;
+        Ok(Width(DEFAULT_WIDTH, &prog).to_string())
+    }
+
+    /// A form that ends with a block nests as the parenthesized form, which
+    /// is the one that the formatter writes
+    #[test]
+    fn close_their_form() -> Result<(), String> {
+        let progs = [
+            ("def main(x): if x: { 1 } else: { 2 } + 3", "def main(x): (if x: 1 else: 2) + 3"),
+            ("def main(x): let y = 1 in { y } * 2", "def main(x): (let y = 1 in y) * 2"),
+            ("def main(x): def f(y): y in { f(1) } - 1", "def main(x): (def f(y): y in f(1)) - 1"),
+            ("def main(x): [let y = 1 in { y }][0]", "def main(x): [let y = 1 in y][0]"),
+        ];
+        for (braced, parenthesized) in progs {
+            let canonical = parse(parenthesized)?;
+            assert_eq!(parse(braced)?, canonical);
+            assert_eq!(parse(&canonical)?, canonical);
+        }
+        Ok(())
+    }
+
+    /// The bodies that a keyword closes may be blocks too, which then close nothing
+    #[test]
+    fn in_bodies() -> Result<(), String> {
+        let src = "def main(x): {
+  match x: | _: { if x: { 1 } else: 2 + 3 } end
+}";
+        assert_eq!(parse(src)?, parse("def main(x): match x: | _: if x: 1 else: 2 + 3 end")?);
+        let src = "def main(x): def f(y): { y + 1 } and def g(y): { y } in f(1)";
+        assert_eq!(parse(src)?, parse("def main(x): def f(y): y + 1 and def g(y): y in f(1)")?);
+        let src = "def main(x): switch x: | 1: { 2 } | _: { 3 }";
+        assert_eq!(parse(src)?, parse("def main(x): switch x: | 1: 2 | _: 3")?);
+        Ok(())
+    }
+
+    #[test]
+    fn only_as_bodies() {
+        let srcs = ["def main(x): 1 + { 2 }", "def main(x): let y = { 1 } in y", "def main(x): {}"];
+        for src in srcs {
+            assert!(snake::compile::parse(src).is_err(), "{}", src);
+        }
+    }
+}
+mod stress {
+    use snake::asm::Reg;
+    use snake::backend::LivenessAnalyzer;
+    use snake::interp;
+    use snake::stress::{AllocStats, Shape};
+
+    fn shape(live: usize, calls: u8) -> Shape {
+        Shape { live, calls, funs: 2, seed: 7 }
+    }
+
+    /// The generated programs compile and both interpreters agree on them
+    #[test]
+    fn run() -> Result<(), String> {
+        for (live, calls) in [(0, 0), (1, 100), (6, 0), (6, 50), (12, 100)] {
+            let src = shape(live, calls).program();
+            let (resolver, ast) = snake::compile::frontend(&src).map_err(|e| e.to_string())?;
+            let by_ast = interp::ast::Machine::run(&ast, ["1"]).map_err(|e| e.to_string())?;
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+            let by_ssa = interp::ssa::Interp::new().run(&ssa, ["1"]).map_err(|e| e.to_string())?;
+            assert_eq!(by_ast.to_string(), by_ssa.to_string(), "{}", src);
+        }
+        assert_eq!(shape(4, 25).program(), shape(4, 25).program());
+        assert_ne!(shape(4, 25).program(), Shape { seed: 8, ..shape(4, 25) }.program());
+        Ok(())
+    }
+
+    /// More live values take more registers, until they spill
+    #[test]
+    fn pressure() -> Result<(), String> {
+        let stats = |live, registers: &[Reg]| -> Result<AllocStats, String> {
+            let src = shape(live, 25).program();
+            let (resolver, ast) = snake::compile::frontend(&src).map_err(|e| e.to_string())?;
+            let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+            Ok(AllocStats::new(&LivenessAnalyzer::new(&ssa).analyze(ssa), registers))
+        };
+        let (few, many) = (stats(2, &Reg::ALLOCATABLE)?, stats(24, &Reg::ALLOCATABLE)?);
+        assert_eq!(few.spills, 0);
+        assert!(few.registers < many.registers && many.spills > 0, "{:?} {:?}", few, many);
+        assert!(few.vars < many.vars && few.edges < many.edges);
+        let none = stats(2, &[])?;
+        assert_eq!((none.registers, none.spills), (0, none.vars));
+        Ok(())
+    }
+}
+mod verify_alloc {
+    use snake::ana::{Allocation, LiveSet};
+    use snake::asm::Reg;
+    use snake::backend::{ConflictAnalysis, LivenessAnalyzer, RegisterAllocator};
+    use snake::identifiers::VarName;
+    use snake::ssa::Program;
+    use snake::stress::Shape;
+
+    /// A generated program that keeps many values live, with its allocation
+    fn allocated(
+        registers: &[Reg], keep_fp: bool,
+    ) -> Result<(Program<VarName, LiveSet>, RegisterAllocator), String> {
+        let src = Shape { live: 12, calls: 30, funs: 2, seed: 3 }.program();
+        let (resolver, ast) = snake::compile::frontend(&src)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+        let mut allocator = RegisterAllocator::new().keep_frame_pointer(keep_fp);
+        allocator.graph_color(ConflictAnalysis::new(&ssa), registers, false);
+        Ok((ssa, allocator))
+    }
+
+    #[test]
+    fn colorings_pass() -> Result<(), String> {
+        let volatile: Vec<Reg> = Reg::ALLOCATABLE.into_iter().filter(Reg::is_volatile).collect();
+        for registers in [&Reg::ALLOCATABLE[..], &volatile, &[]] {
+            for keep_fp in [false, true] {
+                let registers: Vec<Reg> =
+                    registers.iter().copied().filter(|reg| !keep_fp || *reg != Reg::Rbp).collect();
+                let (ssa, allocator) = allocated(&registers, keep_fp)?;
+                assert_eq!(allocator.verify(&ssa, &registers), Ok(()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Each kind of broken allocation is reported with where it happens
+    #[test]
+    fn broken_reported() -> Result<(), String> {
+        let (ssa, mut allocator) = allocated(&Reg::ALLOCATABLE, true)?;
+        // all in one register
+        let vars: Vec<VarName> = allocator.assignment.keys().cloned().collect();
+        let mut shared = RegisterAllocator::new();
+        for var in vars.iter() {
+            shared.assignment.insert(var.clone(), Allocation::Reg(Reg::Rbx));
+        }
+        let problems = shared.verify(&ssa, &Reg::ALLOCATABLE).unwrap_err();
+        assert!(problems.iter().any(|p| p.contains(" share rbx while both live in block ")));
+        assert!(problems.iter().any(|p| p.contains(" is written to rbx in block ")));
+        // registers outside the selection, and the slot of rbp
+        let problems = allocator.verify(&ssa, &[Reg::Rbx]).unwrap_err();
+        assert!(problems.iter().any(|p| p.ends_with(", which is not available")));
+        allocator.assignment.insert(vars[0].clone(), Allocation::Spill(1));
+        let problems = allocator.verify(&ssa, &Reg::ALLOCATABLE).unwrap_err();
+        assert!(problems.contains(&format!("{} is spilled to the reserved slot 1", vars[0])));
+        // and variables left out
+        allocator.assignment.remove(&vars[0]);
+        let problems = allocator.verify(&ssa, &Reg::ALLOCATABLE).unwrap_err();
+        assert!(problems.iter().any(|p| p.starts_with(&format!("{} in block ", vars[0]))));
+        Ok(())
+    }
+}
+mod verify_stack {
+    use snake::asm::*;
+    use snake::backend::{ConflictAnalysis, Emitter, LivenessAnalyzer, RegisterAllocator};
+    use snake::conf::FramePointer;
+    use snake::stress::Shape;
+
+    fn stack(offset: i32) -> MemRef {
+        MemRef { reg: Reg::Rsp, offset }
+    }
+
+    /// The code of a generated program that spills around many calls, in
+    /// each layout of the frames
+    #[test]
+    fn emitted_passes() -> Result<(), String> {
+        let src = Shape { live: 12, calls: 30, funs: 3, seed: 5 }.program();
+        for frame_pointer in [None, Some(FramePointer::Keep), Some(FramePointer::Omit)] {
+            for registers in [&Reg::ALLOCATABLE[..], &[Reg::Rbx, Reg::Rcx], &[]] {
+                let (resolver, ast) = snake::compile::frontend(&src)?;
+                let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+                let ssa = LivenessAnalyzer::new(&ssa).analyze(ssa);
+                let keep_fp = frame_pointer == Some(FramePointer::Keep);
+                let registers: Vec<Reg> =
+                    registers.iter().copied().filter(|reg| !keep_fp || *reg != Reg::Rbp).collect();
+                let mut allocator = RegisterAllocator::new().keep_frame_pointer(keep_fp);
+                allocator.graph_color(ConflictAnalysis::new(&ssa), &registers, false);
+                let mut emitter = Emitter::from(allocator).frame_pointer(frame_pointer);
+                emitter.emit_prog(&ssa);
+                let asm = emitter.to_asm();
+                assert_eq!(asm.check_stack(&Emitter::NO_RETURN_EXTERNS), Ok(()));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn misaligned_call_and_unbalanced_ret() {
+        let mut symbols = Symbols::new();
+        let entry = symbols.global("entry");
+        let f = symbols.local("f");
+        let instrs = vec![
+            Instr::Global(entry),
+            Instr::Label(entry),
+            Instr::Sub(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(16))),
+            Instr::Call(f),
+            Instr::Add(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(16))),
+            Instr::Ret,
+            Instr::Label(f),
+            Instr::Push(Arg32::Reg(Reg::Rbx)),
+            Instr::Ret,
+        ];
+        let asm = Asm { instrs, symbols };
+        assert_eq!(
+            asm.check_stack(&[]),
+            Err(vec![
+                "`call f` in entry is made with rsp 8 bytes off a multiple of 16".to_string(),
+                "`ret` in f leaves rsp 8 bytes below the entry".to_string(),
+            ])
+        );
+    }
+
+    /// A slot written on one path only
+    #[test]
+    fn paths() {
+        let mut symbols = Symbols::new();
+        let entry = symbols.global("entry");
+        let (thn, join) = (symbols.local("thn"), symbols.local("join"));
+        let instrs = vec![
+            Instr::Global(entry),
+            Instr::Label(entry),
+            Instr::Cmp(BinArgs::ToReg(Reg::Rdi, Arg32::Signed(0))),
+            Instr::JCC(ConditionCode::E, thn),
+            Instr::Mov(MovArgs::ToMem(stack(-8), Reg32::Reg(Reg::Rdi))),
+            Instr::Mov(MovArgs::ToMem(stack(-16), Reg32::Reg(Reg::Rdi))),
+            Instr::Jmp(join),
+            Instr::Label(thn),
+            Instr::Mov(MovArgs::ToMem(stack(-16), Reg32::Imm(0))),
+            Instr::Label(join),
+            Instr::Mov(MovArgs::ToReg(Reg::Rax, Arg64::Mem(stack(-16)))),
+            Instr::Add(BinArgs::ToReg(Reg::Rax, Arg32::Mem(stack(-8)))),
+            // the arguments past the sixth are the caller's
+            Instr::Add(BinArgs::ToReg(Reg::Rax, Arg32::Mem(stack(8)))),
+            Instr::Ret,
+        ];
+        let asm = Asm { instrs, symbols };
+        assert_eq!(
+            asm.check_stack(&[]),
+            Err(vec![
+                "`add rax, QWORD [rsp + -8]` in join reads the slot 8 bytes below the entry \
+                 before some path writes it"
+                    .to_string()
+            ])
+        );
+    }
+
+    /// Paths that join with rsp moved apart, past an error handler that does not return
+    #[test]
+    fn joins() {
+        let mut symbols = Symbols::new();
+        let entry = symbols.global("entry");
+        let (fail, thn) = (symbols.local("fail"), symbols.local("thn"));
+        let join = symbols.local("join");
+        let error = symbols.external("snake_error");
+        let instrs = vec![
+            Instr::Global(entry),
+            Instr::Label(entry),
+            Instr::Cmp(BinArgs::ToReg(Reg::Rdi, Arg32::Signed(0))),
+            Instr::JCC(ConditionCode::E, fail),
+            Instr::JCC(ConditionCode::L, thn),
+            Instr::Push(Arg32::Reg(Reg::Rdi)),
+            Instr::Jmp(join),
+            Instr::Label(fail),
+            Instr::Sub(BinArgs::ToReg(Reg::Rsp, Arg32::Signed(8))),
+            Instr::Call(error),
+            Instr::Label(thn),
+            Instr::Jmp(join),
+            Instr::Label(join),
+            Instr::Ret,
+        ];
+        let asm = Asm { instrs, symbols };
+        assert_eq!(
+            asm.check_stack(&["snake_error"]),
+            Err(vec![
+                "`ret` in join leaves rsp 8 bytes below the entry".to_string(),
+                "join is reached from thn with rsp 0 bytes below the entry, and elsewhere 8"
+                    .to_string(),
+            ])
+        );
+        let problems = asm.check_stack(&[]).unwrap_err();
+        assert!(problems.contains(
+            &"thn is reached from fail with rsp 8 bytes below the entry, and elsewhere 0"
+                .to_string()
+        ));
+    }
+}
+mod enums {
+    use super::*;
+    use snake::cli::Optimization;
+
+    mk_o3_test!(lists_and_shapes, "enums.dbk", ["1", "2", "3", "4", "5"], "[15, 15, 234]");
+    mk_o3_test!(empty_list, "enums.dbk", [], "[0, 0, 9]");
+
+    /// Runs `main` on `args` with both interpreters, which must agree: the
+    /// AST interpreter tries the arms in order, the SSA one runs decision trees
+    fn run_both(src: &str, args: &[&str]) -> Result<String, String> {
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let by_ast = interp::ast::Machine::run(&ast, args.iter().copied());
+        let by_ast = by_ast.map_err(|e| e.to_string())?;
+        let opts = [Optimization::CopyPropagation].into();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &opts)?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, args.iter().copied());
+        let by_ssa = by_ssa.map_err(|e| e.to_string())?;
+        assert_eq!(by_ast.to_string(), by_ssa.to_string());
+        Ok(by_ast.to_string())
+    }
+
+    const LIST: &str = "enum List: Nil | Cons(head, tail)\n";
+
+    #[test]
+    fn nested_patterns() -> Result<(), String> {
+        // the wildcard arm is reached from several leaves of the tree
+        let src = format!(
+            "{}def main(args):
+  def f(p):
+    match p:
+    | Cons(Nil, _): 1
+    | Cons(_, Nil): 2
+    | Cons(Cons(a, _), Cons(b, _)): a * 10 + b
+    | _: 0
+    end
+  in
+  let l = Cons(Cons(args[0], Nil), Cons(args[1], Nil)) in
+  ((f(Cons(Nil, Nil)) * 10 + f(Cons(l, Nil))) * 100 + f(l)) * 10 + f(Nil)",
+            LIST
+        );
+        assert_eq!(run_both(&src, &["3", "4"])?, "12340");
+        Ok(())
+    }
+
+    #[test]
+    fn trees() -> Result<(), String> {
+        let src = "enum Tree: Leaf | Node(l, v, r)
+def main(args):
+  def insert(t, x):
+    match t:
+    | Leaf: Node(Leaf, x, Leaf)
+    | Node(l, v, r): if x < v: Node(insert(l, x), v, r) else: Node(l, v, insert(r, x))
+    end
+  and def build(i, t): if i == length(args): t else: build(i + 1, insert(t, args[i]))
+  and def digits(t, acc):
+    match t:
+    | Leaf: acc
+    | Node(l, v, r): digits(r, digits(l, acc) * 10 + v)
+    end
+  in digits(build(0, Leaf), 0)";
+        assert_eq!(run_both(src, &["3", "1", "4", "1", "5"])?, "11345");
+        assert_eq!(run_both(src, &[])?, "0");
+        Ok(())
+    }
+
+    #[test]
+    fn objects_are_arrays() -> Result<(), String> {
+        // the tag, numbered across the enums, then the fields
+        let body = "let p = Pair(7, Nil) in p[0] * 100 + p[1] * 10 + length(p)";
+        let src = format!("{}enum Pair: Pair(a, b)\ndef main(x): {}", LIST, body);
+        assert_eq!(run_both(&src, &[])?, "273");
+        let src = format!("{}def main(x): match 5: | Nil: 0 | Cons(_, _): 1 end", LIST);
+        let err = run_both(&src, &[]).unwrap_err();
+        assert!(err.contains("Array assertion failed"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn within_generators() -> Result<(), String> {
+        let src = format!(
+            "{}def main(args):
+  def gen walk(l):
+    def loop(l): match l: | Nil: 0 | Cons(x, rest): let _ = yield(x) in loop(rest) end
+    in loop(l)
+  in
+  let g = walk(Cons(4, Cons(5, Nil))) in
+  let a = next(g), b = next(g), c = next(g) in a * 100 + b * 10 + c",
+            LIST
+        );
+        assert_eq!(run_both(&src, &[])?, "450");
+        Ok(())
+    }
+
+    fn resolve_error(body: &str) -> Option<ResolveError> {
+        let src = format!("{}enum Shape: Circle(r)\ndef main(x): {}", LIST, body);
+        match snake::compile::frontend(&src) {
+            Err(Error::Resolve(err)) => Some(err),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn exhaustiveness() {
+        let err = resolve_error("match x: | Cons(a, Cons(b, r)): 1 | Nil: 0 end");
+        assert!(matches!(err, Some(ResolveError::NonExhaustiveMatch(p, _)) if p == "Cons(_, Nil)"));
+        let err = resolve_error("match x: | Cons(_, Nil): 1 | Nil: 0 end");
+        let missing = "Cons(_, Cons(_, _))";
+        assert!(matches!(err, Some(ResolveError::NonExhaustiveMatch(p, _)) if p == missing));
+        assert!(resolve_error("match x: | Cons(_, Nil): 1 | _: 0 end").is_none());
+        assert!(resolve_error("match x: | y: y end").is_none());
+        let err = resolve_error("match x: | Nil: 0 | Circle(r): 1 | _: 2 end");
+        assert!(matches!(err, Some(ResolveError::MixedEnums { .. })));
+        let err = resolve_error("match x: | Square(s): 0 end");
+        assert!(matches!(err, Some(ResolveError::UnboundConstructor(..))));
+        let err = resolve_error("Cons(1)");
+        assert!(matches!(err, Some(ResolveError::ArityMismatch { .. })));
+        let err = snake::compile::frontend("enum A: X | Y\nenum B: X\ndef main(x): 1").err();
+        assert!(matches!(err, Some(Error::Resolve(ResolveError::DuplicateConstructor(..)))));
+    }
+
+    #[test]
+    fn prints_back() -> Result<(), String> {
+        let src = "enum List: Nil | Cons(head, tail)
+def main(x): match x: | Cons(a, Nil()): a | _: 0 end";
+        let prog = snake::compile::parse(src).map_err(|e| e.to_string())?;
+        assert_eq!(prog.to_string(), src);
+        Ok(())
+    }
+}
+mod annotations {
+    use super::*;
+
+    mk_o3_test!(checked_sum, "annotations.dbk", ["5"], "20");
+    mk_frontend_test!(checked_sum_frontend, "annotations.dbk", ["-3"], "12");
+    mk_o3_middle_end_test!(checked_sum_middle_end, "annotations.dbk", [], "16");
+    mk_o3_dyn_fail_test!(unchecked_bool, "annotations.dbk", ["true"], "expected a number");
+
+    fn resolved(body: &str) -> Result<String, String> {
+        let src = format!("def main(x):\n  {}", body);
+        let (_, ast) = snake::compile::frontend(&src)?;
+        Ok(ast.to_string())
+    }
+
+    #[test]
+    fn checks_what_is_not_evident() -> Result<(), String> {
+        // the argument of `f` and the result of `g` are evident, `x` is not
+        let ast = resolved(
+            "def f(n: Int) -> Int: n * 2 and def g(b) -> Bool: !b in f(if g(x): 1 else: 2)",
+        )?;
+        assert!(!ast.contains("check"), "{}", ast);
+        let ast = resolved("def f(n: Int) -> Int: n in f(x)")?;
+        assert_eq!(ast.matches("checkInt").count(), 1, "{}", ast);
+        // the tails of a body are checked where they are not evident
+        let ast = resolved("def f(n) -> Int: if n: 1 else: x[0] in let y: Array = x in f(y)")?;
+        assert_eq!(ast.matches("checkInt(x").count(), 1, "{}", ast);
+        assert_eq!(ast.matches("checkArray(x").count(), 1, "{}", ast);
+        Ok(())
+    }
+
+    #[test]
+    fn evident_mismatches() {
+        let mismatch = |body: &str| match resolved(body) {
+            Err(err) => err.contains("expected a value of type"),
+            Ok(_) => false,
+        };
+        assert!(mismatch("let y: Int = true in y"));
+        assert!(mismatch("def f(b: Bool): b in f(1 + x)"));
+        assert!(mismatch("def f(n) -> Array: if n: [] else: n == 1 in f(x)"));
+        assert!(!mismatch("def f(n) -> Array: if n: [] else: x in f(x)"));
+    }
+
+    #[test]
+    fn prints_back() -> Result<(), String> {
+        let src = "def main(x):
+  def f(n: Int, m) -> Bool: n == m in
+  let y: Array = [x], z = 1 in f(z, y[0])";
+        let prog = snake::compile::parse(src).map_err(|e| e.to_string())?;
+        let again = snake::compile::parse(&prog.to_string()).map_err(|e| e.to_string())?;
+        assert_eq!(prog.to_string(), again.to_string());
+        assert!(prog.to_string().contains("f (n: Int, m) -> Bool:"), "{}", prog);
+        assert!(prog.to_string().contains("y: Array = [x]"), "{}", prog);
+        Ok(())
+    }
+}
+mod gradual {
+    use super::*;
+    use snake::middle_end::Lowerer;
+
+    mk_o3_test!(scaled, "gradual.dbk", ["5"], "50");
+    mk_frontend_test!(scaled_frontend, "gradual.dbk", ["-3"], "-30");
+    mk_o3_dyn_fail_test!(scaled_bool, "gradual.dbk", ["true"], "expected a number");
+
+    /// The type assertions of the block `label` of the lowered `file`
+    fn asserts(file: &str, label: &str) -> Result<usize, String> {
+        let (resolver, ast) = runner::emit_ast(Path::new(&format!("examples/{}", file)))?;
+        let ssa = Lowerer::from(resolver).lower_prog(ast).to_string();
+        let block = &ssa[ssa.find(&format!("block {}", label)).ok_or("no block")?..];
+        let block = &block[..block[1..].find("\nblock").map_or(block.len(), |end| end + 1)];
+        Ok(block.matches("assert").count())
+    }
+
+    #[test]
+    fn checks_once() -> Result<(), String> {
+        // `m` is checked for the first call to `scale`, which also checks it
+        // for the second call and for the addition, and only the inner sum
+        // is asserted
+        assert_eq!(asserts("gradual.dbk", "twice")?, 2);
+        // the annotations prove `n`, and only the literal and the product
+        // are asserted
+        assert_eq!(asserts("gradual.dbk", "scale")?, 2);
+        Ok(())
+    }
+}
+mod inference {
+    use super::*;
+    use snake::conf::TypeMode;
+    use snake::runner::read_file;
+    use snake::frontend::Resolver;
+    use snake::txt::FileInfo;
+
+    /// The inferred types of the program `src`, or the type error
+    fn inferred(src: &str) -> Result<String, String> {
+        let prog = snake::compile::parse(src).map_err(|e| e.to_string())?;
+        let mut resolver = Resolver::new().types(TypeMode::Infer);
+        resolver.resolve_prog(prog).map_err(|e| e.to_string())?;
+        let file = FileInfo::new(src);
+        match resolver.inferred.ok_or("no types inferred")? {
+            Ok(inferred) => Ok(inferred.render(&file)),
+            Err(err) => Err(err.render(&file)),
+        }
+    }
+
+    #[test]
+    fn generalizes_functions() -> Result<(), String> {
+        let types = inferred("def main(args):\n  def id(x): x in\n  if id(true): id(1) else: 0")?;
+        assert!(types.contains("id(x: 'a) -> 'a"), "{}", types);
+        assert!(types.contains("main(args: Array<'a>) -> Int"), "{}", types);
+        // but not within their group
+        let err = inferred("def main(args):\n  def f(x): x and def g(y): f(1) + f(y) in g(true)");
+        assert!(err.is_err(), "{:?}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn arrays_and_generators() -> Result<(), String> {
+        let src = read_file(Path::new("examples/generators.dbk")).map_err(|e| e.to_string())?;
+        let types = inferred(&src)?;
+        assert!(types.contains("count(from: Int) -> Gen<Int>"), "{}", types);
+        assert!(types.contains("sum(stream: Gen<Int>, acc: Int, k: Int) -> Int"), "{}", types);
+        let types = inferred("def main(args):\n  let a = newArray(2), _ = a[0] := [true] in a")?;
+        assert!(types.contains("-> Array<Array<Bool>>"), "{}", types);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_both_spans() {
+        // where `x` becomes a boolean, and where it is added
+        let err = inferred("def main(args):\n  let x = args[0] in\n  if x: x + 1 else: 0");
+        let expected = "expected a value of type Int, found Bool: 3:5-3:6, expected at 3:8-3:13";
+        assert_eq!(err, Err(expected.to_string()));
+        let err = inferred("def main(args):\n  let a = [1, true] in a");
+        assert!(err.is_err_and(|err| err.contains("found Bool: 2:14-2:18, expected at 2:11-2:12")));
+        let err = inferred("def main(args):\n  let a = newArray(1) in a[0] := a");
+        assert!(err.is_err_and(|err| err.contains("which contains it")));
+    }
+
+    #[test]
+    fn gradual_by_default() -> Result<(), String> {
+        let src = "def main(x): if x: x + 1 else: 0";
+        let prog = snake::compile::parse(src).map_err(|e| e.to_string())?;
+        let mut resolver = Resolver::new();
+        resolver.resolve_prog(prog).map_err(|e| e.to_string())?;
+        assert!(resolver.inferred.is_none());
+        let enums = inferred("enum B: T | F\ndef main(x): T");
+        assert!(enums.is_err_and(|err| err.contains("does not cover enums")));
+        Ok(())
+    }
+}
+mod facts {
+    use super::*;
+    use snake::ana::Facts;
+    use snake::middle_end::{ConditionCanonicalizer, CopyPropagator, FactAnalyzer, Lowerer};
+    use snake::runner::read_file;
+    use snake::types::Type;
+
+    /// The facts on entry to each block, with the variables by their hints
+    fn entries(facts: &Facts) -> Vec<Vec<String>> {
+        let hinted = |fact: &snake::ana::Fact| {
+            fact.to_string().replace(&fact.var().to_string(), fact.var().hint())
+        };
+        facts.entries.values().map(|at| at.iter().map(hinted).collect()).collect()
+    }
+
+    fn holds(entries: &[Vec<String>], expected: &[&str]) -> bool {
+        entries.iter().any(|at| expected.iter().all(|fact| at.iter().any(|f| f == fact)))
+    }
+
+    #[test]
+    fn guards_refine_branches() -> Result<(), String> {
+        let inp = read_file(Path::new("examples/refine.dbk"))
+            .map_err(|e| format!("Error reading file: {}", e))?;
+        let (resolver, ast) = snake::compile::frontend(&inp)?;
+        let ssa = Lowerer::from(resolver).lower_prog(ast);
+        for ssa in [ssa.clone(), CopyPropagator::new().run(ssa)] {
+            let analyzer = FactAnalyzer::new(&ssa);
+            let facts = analyzer.facts();
+            let entries = entries(facts);
+            // the integer 10 is tagged as 20
+            assert!(holds(&entries, &["x: Int", "x < 20"]), "{:?}", entries);
+            assert!(holds(&entries, &["x: Int", "x >= 20"]), "{:?}", entries);
+            assert!(holds(&entries, &["x: !Int", "x: Bool"]), "{:?}", entries);
+            assert!(holds(&entries, &["x: !Int", "x: !Bool"]), "{:?}", entries);
+            let mut vars = facts.entries.values().flatten().map(|fact| fact.var());
+            let x = vars.find(|x| x.hint() == "x").ok_or("no facts about x")?;
+            let proven = facts.entries.keys().filter(|label| facts.proves(label, x, Type::Int));
+            assert_eq!(proven.count(), 3);
+            let refuted = facts.entries.keys().filter(|label| facts.refutes(label, x, Type::Bool));
+            assert_eq!(refuted.count(), 4);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn negated_guards() -> Result<(), String> {
+        let src = "def main(x): if !(isBool(x)): 0 else: if x: 1 else: 2";
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let ssa = Lowerer::from(resolver).lower_prog(ast);
+        let canonical = CopyPropagator::new().run(ConditionCanonicalizer::new().run(ssa.clone()));
+        for ssa in [ssa, canonical] {
+            let entries = entries(FactAnalyzer::new(&ssa).facts());
+            assert!(holds(&entries, &["x: !Bool"]), "{:?}", entries);
+            assert!(holds(&entries, &["x: Bool", "x == 5"]), "{:?}", entries);
+            assert!(holds(&entries, &["x: Bool", "x != 5"]), "{:?}", entries);
+        }
+        Ok(())
+    }
+}
+mod indvars {
+    use super::*;
+    use snake::ana::Induction;
+    use snake::middle_end::{InductionAnalyzer, Lowerer};
+    use snake::runner::read_file;
+    use std::process::Command;
+
+    #[test]
+    fn steps_and_offsets() -> Result<(), String> {
+        let inp = read_file(Path::new("examples/indvars.dbk"))
+            .map_err(|e| format!("Error reading file: {}", e))?;
+        let (resolver, ast) = snake::compile::frontend(&inp)?;
+        let ssa = Lowerer::from(resolver).lower_prog(ast);
+        let analyzer = InductionAnalyzer::new(&ssa);
+        let vars = analyzer.vars();
+        let named = |hint: &str| {
+            let mut all = vars.0.values().flatten();
+            all.find(|(var, _)| var.hint() == hint).map(|(var, _)| var.clone())
+        };
+        // `sum` is called, `down` entered by a tail call
+        let i = named("i").ok_or("no i")?;
+        let basic = |var| match vars.get(var) {
+            Some(Induction::Basic { init, step }) => Some((init.len(), *step)),
+            _ => None,
+        };
+        assert_eq!(basic(&i), Some((0, 2)));
+        let n = named("n").ok_or("no n")?;
+        assert_eq!(basic(&n), Some((1, -4)));
+        assert!(named("acc").is_none());
+        // the index of `a[i]`, untagged, and the offset of the element
+        let idx = named("idx").ok_or("no idx")?;
+        let derived = matches!(vars.get(&idx), Some(Induction::Derived { num: 1, den: 2, .. }));
+        assert!(derived);
+        assert_eq!(vars.step(&idx), Some(1));
+        assert_eq!(vars.step(&named("off").ok_or("no off")?), Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn target() -> Result<(), String> {
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .args(["examples/indvars.dbk", "-t", "indvars", "-O=cp"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        let dump = String::from_utf8_lossy(&out.stdout);
+        assert!(dump.starts_with("loop sum#"), "{}", dump);
+        assert!(dump.contains(": from ") && dump.contains(", step -4"), "{}", dump);
+        assert!(dump.contains(" * 1/2 + 1"), "{}", dump);
+        Ok(())
+    }
+}
+mod loopify {
+    use super::*;
+    use snake::ana::Induction;
+    use snake::middle_end::{InductionAnalyzer, Loopifier, Lowerer};
+    use snake::remarks::RemarkKind;
+    use snake::runner::read_file;
+
+    #[test]
+    fn tail_calls_loop() -> Result<(), String> {
+        let inp = read_file(Path::new("examples/loopify.dbk"))
+            .map_err(|e| format!("Error reading file: {}", e))?;
+        let (resolver, ast) = snake::compile::frontend(&inp)?;
+        let ssa = Lowerer::from(resolver).lower_prog(ast);
+        let mut loopifier = Loopifier::new();
+        let looped = loopifier.run(ssa.clone());
+        assert_eq!(loopifier.looped(), 1);
+        let kinds: Vec<_> = loopifier.remarks().iter().map(|remark| remark.kind).collect();
+        assert_eq!(kinds, [RemarkKind::Passed, RemarkKind::Missed, RemarkKind::Missed]);
+        for arg in ["5", "true", "-3"] {
+            let run = |ssa| interp::ssa::Interp::new().run(ssa, [arg]).map_err(|e| e.to_string());
+            let run = |ssa| run(ssa).map(|v| v.to_string());
+            assert_eq!(run(&looped), run(&ssa));
+        }
+        // which the loop analyses see
+        let analyzer = InductionAnalyzer::new(&looped);
+        let mut vars = analyzer.vars().0.values().flatten();
+        let n = vars.find(|(_, induction)| matches!(induction, Induction::Basic { step: -4, .. }));
+        assert_eq!(n.map(|(var, _)| var.hint()), Some("n"));
+        Ok(())
+    }
+}
+mod tail_calls {
+    use super::*;
+    use snake::cli::{CompilerConf, FramePointer, Verbosity};
+    use snake::runner::read_file;
+
+    mk_o3_test!(count_down, "tail_calls.dbk", [], "true");
+
+    /// The assembly of `src` without optimizations, with the options of `conf`
+    fn asm(src: &str, conf: impl FnOnce(&mut CompilerConf)) -> Result<String, String> {
+        let mut compiler = CompilerConf::new([], Verbosity::Minimalistic);
+        conf(&mut compiler);
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let (lowerer, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        Ok(snake::compile::backend_with(lowerer, ssa, &compiler))
+    }
+
+    /// Whether `txt` has a line of the instruction `instr`
+    fn emits(txt: &str, instr: &str) -> bool {
+        txt.lines().any(|line| line.trim() == instr)
+    }
+
+    /// The calls whose result is returned jump, unless turned off
+    #[test]
+    fn jump() -> Result<(), String> {
+        let src = read_file(Path::new("examples/tail_calls.dbk")).map_err(|e| e.to_string())?;
+        let txt = asm(&src, |_| {})?;
+        assert!(emits(&txt, "jmp entry") && !emits(&txt, "call entry"), "{}", txt);
+        let txt = asm(&src, |conf| conf.tail_calls = false)?;
+        assert!(emits(&txt, "call entry"));
+        // the shadow stack keeps every caller for the backtraces
+        let txt = asm(&src, |conf| conf.shadow_stack = true)?;
+        assert!(emits(&txt, "call entry"));
+        // the frame is taken down as for a return
+        let txt = asm(&src, |conf| conf.frame_pointer = Some(FramePointer::Keep))?;
+        assert!(txt.contains("        mov rbp, QWORD [rsp + -8]\n        jmp entry\n"), "{}", txt);
+        Ok(())
+    }
+
+    /// Externs are jumped to as well, but only with their arguments in registers
+    #[test]
+    fn externs() -> Result<(), String> {
+        let txt = asm("extern print(x)\ndef main(x): print(x)", |_| {})?;
+        assert!(emits(&txt, "jmp print") && !emits(&txt, "call print"), "{}", txt);
+        let src = "extern nine(a, b, c, d, e, f, g, h, i)\n\
+                   def main(x): nine(1, 2, 3, 4, 5, 6, 7, 8, x)";
+        let txt = asm(src, |_| {})?;
+        assert!(emits(&txt, "call nine") && !emits(&txt, "jmp nine"), "{}", txt);
+        Ok(())
+    }
+}
+mod cold {
+    use super::*;
+    use snake::middle_end::{ColdOutliner, CopyPropagator, Lowerer};
+    use snake::runner::read_file;
+
+    #[test]
+    fn failing_arms_outlined() -> Result<(), String> {
+        let inp = read_file(Path::new("examples/cold.dbk"))
+            .map_err(|e| format!("Error reading file: {}", e))?;
+        let (resolver, ast) = snake::compile::frontend(&inp)?;
+        let mut lowerer = Lowerer::from(resolver);
+        let ssa = CopyPropagator::new().run(lowerer.lower_prog(ast));
+        let mut outliner = ColdOutliner::new();
+        let outlined = outliner.run(ssa.clone(), &mut lowerer.names);
+        assert_eq!(outliner.outlined(), 2);
+        // after all the other blocks, with the variables they read
+        let last: Vec<_> = outlined.blocks.iter().rev().take(2).collect();
+        assert!(last.iter().all(|block| block.label.hint() == "cold"));
+        assert_eq!(last.iter().map(|block| block.params.len()).sum::<usize>(), 1);
+        for args in [&[][..], &["1", "2"], &["1", "2", "3", "4", "5", "6"]] {
+            let run = |ssa| interp::ssa::Interp::new().run(ssa, args.iter().copied());
+            let run = |ssa| run(ssa).map(|v| v.to_string()).map_err(|e| e.to_string());
+            assert_eq!(run(&outlined), run(&ssa));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fails_the_same() -> Result<(), String> {
+        let src = "def main(x): if x: let y = [x] in y[1] else: x + true";
+        let (resolver, ast) = snake::compile::frontend(src)?;
+        let mut lowerer = Lowerer::from(resolver);
+        let ssa = CopyPropagator::new().run(lowerer.lower_prog(ast));
+        let mut outliner = ColdOutliner::new();
+        let outlined = outliner.run(ssa.clone(), &mut lowerer.names);
+        assert_eq!(outliner.outlined(), 2);
+        for arg in ["true", "false"] {
+            let run = |ssa| interp::ssa::Interp::new().run(ssa, [arg]).map(|v| v.to_string());
+            let (found, expected) = (run(&outlined), run(&ssa));
+            assert_eq!(found.map_err(|e| e.to_string()), expected.map_err(|e| e.to_string()));
+        }
+        Ok(())
+    }
+}
+mod leaf {
+    use snake::cli::{CompilerConf, FramePointer, Optimization, Verbosity};
+    use snake::compile;
+    use std::process::Command;
+
+    #[test]
+    fn classified() -> Result<(), String> {
+        let out = Command::new(env!("CARGO_BIN_EXE_snake"))
+            .args(["examples/leaf.dbk", "-t", "pressure", "-O=cp"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        let dump = String::from_utf8_lossy(&out.stdout);
+        let kind = |fun: &str| {
+            let row = dump.lines().find(|row| row.starts_with(fun));
+            row.and_then(|row| row.split_whitespace().last())
+        };
+        assert_eq!(kind("sq@"), Some("leaf"), "{}", dump);
+        assert_eq!(kind("step@"), Some("leaf"), "{}", dump);
+        assert_eq!(kind("sum@"), Some("calls"), "{}", dump);
+        assert_eq!(kind("entry"), Some("calls"), "{}", dump);
+        Ok(())
+    }
+
+    /// `get` of the example makes no calls, while `sum` uses all the non-volatile registers
+    fn asm(leaf: bool) -> Result<String, String> {
+        let file = std::path::Path::new("examples/frame_pointer.dbk");
+        let (resolver, ast) = snake::runner::emit_ast(file)?;
+        let (lowerer, ssa) = compile::middle_end_with(resolver, ast, &Default::default())?;
+        let optimizations = leaf.then_some(Optimization::LeafFunctions);
+        let mut conf = CompilerConf::new(optimizations, Verbosity::Minimalistic);
+        conf.frame_pointer = Some(FramePointer::Keep);
+        Ok(compile::backend_with(lowerer, ssa, &conf))
+    }
+
+    #[test]
+    fn frameless() -> Result<(), String> {
+        let count = |txt: &str, line: &str| txt.lines().filter(|l| l.trim() == line).count();
+        let (with, without) = (asm(true)?, asm(false)?);
+        assert_eq!(count(&without, "mov QWORD [rsp + -8], rbp"), 3);
+        assert_eq!(count(&with, "mov QWORD [rsp + -8], rbp"), 2);
+        let restores = |txt: &str| count(txt, "mov rbp, QWORD [rsp + -8]");
+        assert_eq!(restores(&with) + 1, restores(&without));
+        // nor does it save the others
+        let saves = |txt: &str| txt.lines().filter(|l| l.trim().ends_with("], r15")).count();
+        assert_eq!(saves(&with) + 1, saves(&without));
+        Ok(())
+    }
+}
+
+mod bindgen {
+    use snake::runner::read_file;
+    use std::path::Path;
+    use std::process::Command;
+
+    fn header(file: &Path) -> Result<String, String> {
+        let src = read_file(file).map_err(|e| e.to_string())?;
+        let prog = snake::compile::parse(&src)?;
+        let (resolver, ast) = snake::compile::frontend(&src)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        Ok(snake::bindgen::c_header("host.dbk", &prog, &ssa.externs))
+    }
+
+    /// Type checks a host of `header` with the C compiler, if one is installed
+    fn compiles(header: &str) -> Result<(), String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        std::fs::write(dir.path().join("snake.h"), header).map_err(|e| e.to_string())?;
+        let host = "#include \"snake.h\"\n\
+                    int main(void) {\n\
+                    \x20   snake_value v = snake_entry(SNAKE_FROM_INT(-3));\n\
+                    \x20   return SNAKE_IS_INT(v) ? (int)SNAKE_AS_INT(v) : SNAKE_AS_BOOL(v);\n\
+                    }\n";
+        let file = dir.path().join("host.c");
+        std::fs::write(&file, host).map_err(|e| e.to_string())?;
+        let mut cc = Command::new("cc");
+        cc.args(["-std=c11", "-Wall", "-Werror", "-fsyntax-only"]).arg(&file);
+        let Ok(out) = cc.output() else { return Ok(()) };
+        match out.status.success() {
+            true => Ok(()),
+            false => Err(String::from_utf8_lossy(&out.stderr).into_owned()),
+        }
+    }
+
+    #[test]
+    fn typed_externs() -> Result<(), String> {
+        let header = header(Path::new("examples/typed_extern.dbk"))?;
+        for expected in [
+            "#ifndef SNAKE_HOST_DBK_H",
+            "typedef uint64_t snake_value;",
+            "snake_value snake_entry(snake_value args) __asm__(\"entry\");",
+            "int64_t isqrt(int64_t n) __asm__(\"isqrt\");",
+            "int64_t sum_array(const uint64_t *arr, int64_t scale) __asm__(\"sum_array\");",
+            "/* the integer square root, in the runtime */",
+            "uint64_t *snake_new_array(uint64_t len) __asm__(\"snake_new_array\");",
+        ] {
+            assert!(header.contains(expected), "{} not in\n{}", expected, header);
+        }
+        compiles(&header)
+    }
+
+    #[test]
+    fn keywords() -> Result<(), String> {
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        let file = dir.path().join("keywords.dbk");
+        let src = "extern int(char, x)\ndef main(static): int(1, static)";
+        std::fs::write(&file, src).map_err(|e| e.to_string())?;
+        let header = header(&file)?;
+        let signature = "snake_value int_(snake_value char_, snake_value x) __asm__(\"int\");";
+        assert!(header.contains(signature), "{}", header);
+        assert!(header.contains("snake_entry(snake_value static_)"), "{}", header);
+        compiles(&header)
+    }
+
+    /// Converts Python values through a host whose `entry` returns its arguments, if the C
+    /// compiler and Python are installed
+    #[test]
+    fn python() -> Result<(), String> {
+        let src = read_file(Path::new("examples/doc.dbk")).map_err(|e| e.to_string())?;
+        let prog = snake::compile::parse(&src)?;
+        let (resolver, ast) = snake::compile::frontend(&src)?;
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &Default::default())?;
+        let module = snake::bindgen::python_module("doc.dbk", &prog, &ssa.externs);
+        assert!(module.contains("HOST = [\"print\", \"snake_error\", \"snake_new_array\"]"));
+        assert!(module.contains("Prints the sum of the first `args[0]` squares."), "{}", module);
+        let dir = tempfile::TempDir::new().map_err(|e| e.to_string())?;
+        std::fs::write(dir.path().join("prog.py"), module).map_err(|e| e.to_string())?;
+        let host = "#include <stdint.h>\n#include <stdlib.h>\n\
+                    uint64_t *snake_new_array(uint64_t len) {\n\
+                    \x20   uint64_t *ptr = malloc(8 * (len + 1));\n\
+                    \x20   *ptr = len;\n\
+                    \x20   return ptr;\n\
+                    }\n\
+                    uint64_t snake_error(uint64_t code, uint64_t v) { exit(1); }\n\
+                    uint64_t print(uint64_t v) { return v; }\n\
+                    uint64_t entry(uint64_t args) { return args; }\n";
+        std::fs::write(dir.path().join("host.c"), host).map_err(|e| e.to_string())?;
+        let mut cc = Command::new("cc");
+        cc.current_dir(dir.path()).args(["-shared", "-fPIC", "host.c", "-o", "libprog.so"]);
+        let Ok(out) = cc.output() else { return Ok(()) };
+        assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+        let script = "import prog\n\
+                      p = prog.Program('./libprog.so')\n\
+                      assert p.main(1, [True, -2], []) == [1, [True, -2], []]\n\
+                      assert p.main(-(1 << 62), False) == [-(1 << 62), False]\n\
+                      print('ok')\n";
+        let python = Command::new("python3").current_dir(dir.path()).args(["-c", script]).output();
+        let Ok(python) = python else { return Ok(()) };
+        assert!(python.status.success(), "{}", String::from_utf8_lossy(&python.stderr));
+        assert_eq!(String::from_utf8_lossy(&python.stdout), "ok\n");
+        Ok(())
+    }
+
+    #[test]
+    fn python_names() -> Result<(), String> {
+        let mut prog = snake::compile::parse("## four quotes: \"\"\"\"\ndef main(x): x")?;
+        prog.name = "lambda".to_string();
+        let module = snake::bindgen::python_module("names.dbk", &prog, &[]);
+        assert!(module.contains("    def lambda_(self, *args):"), "{}", module);
+        assert!(module.contains(r#"four quotes: \"\"\"\""#), "{}", module);
+        // the module parses, if Python is installed
+        let mut python = Command::new("python3");
+        python.args(["-c", "import ast, sys; ast.parse(sys.argv[1])", &module]);
+        let Ok(python) = python.output() else { return Ok(()) };
+        assert!(python.status.success(), "{}", String::from_utf8_lossy(&python.stderr));
+        Ok(())
+    }
+}
+
+mod encode {
+    use snake::cli::{CompilerConf, FramePointer, Optimization, Verbosity};
+    use snake::compile;
+    use snake::encode::{encode, parse};
+    use snake::runner::read_file;
+
+    /// The assembly of `file`, with copy propagation and the options of `conf`
+    fn asm(file: &str, conf: impl FnOnce(&mut CompilerConf)) -> Result<String, String> {
+        let src = read_file(std::path::Path::new(file)).map_err(|e| e.to_string())?;
+        let optimizations = [Optimization::CopyPropagation];
+        let mut compiler = CompilerConf::new(optimizations, Verbosity::Minimalistic);
+        conf(&mut compiler);
+        let (resolver, ast) = compile::frontend(&src)?;
+        let (lowerer, ssa) = compile::middle_end_with(resolver, ast, &compiler.optimizations)?;
+        Ok(compile::backend_with(lowerer, ssa, &compiler))
+    }
+
+    /// The parser reads back what the backend prints, jump tables, shadow
+    /// stacks and unwind tables included, and all of it can be encoded
+    #[test]
+    fn round_trip() -> Result<(), String> {
+        let texts = [
+            asm("examples/switch.dbk", |_| {})?,
+            asm("examples/extern_big_nine.dbk", |_| {})?,
+            asm("examples/backtrace.dbk", |conf| conf.shadow_stack = true)?,
+            asm("examples/leaf.dbk", |conf| conf.frame_pointer = Some(FramePointer::Omit))?,
+            asm("examples/switch.dbk", |conf| conf.function_sections = true)?,
+        ];
+        for txt in texts {
+            let asm = parse(&txt)?;
+            assert_eq!(asm.to_string(), txt);
+            encode(&asm)?;
+        }
+        Ok(())
+    }
+
+    /// Bases that need a SIB byte or a displacement, byte registers that need
+    /// a REX prefix, 64-bit immediates, and calls through the slot of an extern
+    #[test]
+    fn encodings() -> Result<(), String> {
+        let txt = "section .text\n        global entry\n        extern print\nentry:\n\
+                   \x20       mov QWORD [rsp + -8], rdi\n\
+                   \x20       mov rax, QWORD [r13 + 0]\n\
+                   \x20       sete sil\n\
+                   \x20       mov rcx, 6074001000\n\
+                   \x20       call print\n\
+                   \x20       ret\n";
+        let image = encode(&parse(txt)?)?;
+        let code = [
+            0x48, 0x89, 0x7c, 0x24, 0xf8,
+            0x49, 0x8b, 0x45, 0x00,
+            0x40, 0x0f, 0x94, 0xc6,
+            0x48, 0xb9, 0x68, 0xe6, 0x09, 0x6a, 0x01, 0x00, 0x00, 0x00,
+            // the slot follows the code at the next QWORD, 3 bytes after the call
+            0xff, 0x15, 0x03, 0x00, 0x00, 0x00,
+            0xc3,
+        ];
+        assert_eq!(image.bytes[..code.len()], code);
+        assert_eq!(image.imports, [(32, "print".to_string())]);
+        assert_eq!(image.bytes.len(), 40);
+        assert_eq!(image.entry, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_syntax() {
+        let err = parse("entry:\n        mov rax, [rdi]\n").unwrap_err();
+        assert_eq!(err, "line 2: cannot assemble `mov rax, [rdi]`");
+    }
+}
+mod parser_regressions {
+    use snake::fuzz;
+
+    /// The inputs that the fuzz targets found, each of which the target in
+    /// the prefix of its name runs on
+    #[test]
+    fn findings() -> Result<(), String> {
+        let dir = std::fs::read_dir("tests/parser_regressions").map_err(|e| e.to_string())?;
+        let mut n = 0;
+        for entry in dir {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            match name.split('-').next() {
+                Some("frontend") => fuzz::frontend(&data),
+                Some("tokens") => fuzz::tokens(&data),
+                _ => Err(format!("{} names no fuzz target", path.display()))?,
+            }
+            n += 1;
+        }
+        assert!(n > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn tokens_spell_the_grammar() {
+        assert_eq!(fuzz::spell(&[1, 5, 0, 15, 2, 3, 70, 3]), "and else def entry in let y let");
+        let src = fuzz::spell(&[0, 17, 44, 69, 45, 49, 69, 55, 73]);
+        assert_eq!(src, "def main ( x ) : x + 1");
+        assert!(snake::compile::frontend(&src).is_ok());
+    }
+}
+mod limits {
+    use snake::cli::Limits;
+    use snake::compile;
+    use snake::frontend::Resolver;
+    use snake::layout::Width;
+
+    /// `main` returning `x` plus `n`, one `let` at a time
+    fn let_chain(n: usize) -> String {
+        let lets = (0..n).map(|i| format!("let a{} = a{} + 1 in\n", i + 1, i));
+        format!("def main(a0):\n{}a{}", lets.collect::<String>(), n)
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected() {
+        let n = 100_000;
+        let src = format!("def main(x): {}x{}", "add1(".repeat(n), ")".repeat(n));
+        let err = compile::parse(&src).unwrap_err();
+        assert!(err.message.starts_with("expression nested deeper than the limit of 1000"));
+        assert!(err.loc.is_some());
+        let limits = Limits { size: 100, ..Limits::default() };
+        let err = compile::parse_with(&src, &limits).unwrap_err();
+        assert_eq!(err.message, format!("program of {} bytes exceeds the limit of 100", src.len()));
+    }
+
+    #[test]
+    fn long_let_chains() -> Result<(), String> {
+        let src = let_chain(5000);
+        assert!(compile::parse(&src).is_err());
+        let limits = Limits { depth: 10_000, ..Limits::default() };
+        let prog = compile::parse_with(&src, &limits).map_err(|e| e.to_string())?;
+        assert!(Width(80, &prog).to_string().ends_with("let a5000 = a4999 + 1 in a5000"));
+        let prog = Resolver::new().resolve_prog(prog).map_err(|e| e.to_string())?;
+        assert!(prog.body.to_string().contains("in a5000"));
+        prog.body.dismantle();
+        Ok(())
+    }
+}
+mod deep_nesting {
+    use snake::cli::Limits;
+    use snake::compile;
+    use snake::frontend::Resolver;
+    use snake::interp;
+    use std::collections::HashSet;
+
+    const N: usize = 50_000;
+
+    fn limits() -> Limits {
+        Limits { depth: 2 * N, ..Limits::default() }
+    }
+
+    #[test]
+    fn nested_calls_print_and_run() -> Result<(), String> {
+        let src = format!("def main(x): {}x[0]{}", "add1(".repeat(N), ")".repeat(N));
+        let prog = compile::parse_with(&src, &limits()).map_err(|e| e.to_string())?;
+        assert!(prog.body.to_string().ends_with(&format!("add1(x[0]{}", ")".repeat(N))));
+        let value = interp::ast::Machine::run(&prog, ["1"]).map_err(|e| e.to_string())?;
+        assert_eq!(value.to_string(), (N + 1).to_string());
+        prog.body.dismantle();
+        Ok(())
+    }
+
+    #[test]
+    fn let_chains_lower_and_run() -> Result<(), String> {
+        let lets: String = (0..N).map(|i| format!("let a{} = a{} + 1 in\n", i + 1, i)).collect();
+        let src = format!("def main(args):\nlet a0 = args[0] in\n{}a{}", lets, N);
+        let prog = compile::parse_with(&src, &limits()).map_err(|e| e.to_string())?;
+        // the resolver still recurses, so it runs on a stack as large as that of main
+        let resolve = move || {
+            let mut resolver = Resolver::new();
+            resolver.resolve_prog(prog).map(|prog| (resolver, prog)).map_err(|e| e.to_string())
+        };
+        let thread = std::thread::Builder::new().stack_size(256 << 20).spawn(resolve);
+        let (resolver, prog) = thread.map_err(|e| e.to_string())?.join().unwrap()?;
+        assert!(prog.body.to_string().contains(&format!(" in a{}%", N)));
+        let by_ast = interp::ast::Machine::run(&prog, ["1"]).map_err(|e| e.to_string())?;
+        let (_, ssa) = compile::middle_end_with(resolver, prog, &HashSet::new())?;
+        let by_ssa = interp::ssa::Interp::new().run(&ssa, ["1"]).map_err(|e| e.to_string())?;
+        assert_eq!(by_ast.to_string(), (N + 1).to_string());
+        assert_eq!(by_ssa.to_string(), (N + 1).to_string());
+        ssa.blocks.into_iter().for_each(|block| block.body.dismantle());
+        Ok(())
+    }
+}
+mod nodes {
+    use snake::compile;
+    use snake::conf::TypeMode;
+    use snake::frontend::Resolver;
+    use snake::nodes;
+
+    const SRC: &str = "def main(args):\n  let x = args[0] in\n  def f(y): [y] in\n  f(add1(x))";
+
+    #[test]
+    fn numbered_by_the_parser() -> Result<(), String> {
+        let mut prog = compile::parse(SRC).map_err(|e| e.to_string())?;
+        let id = prog.body.loc().node;
+        let spans = nodes::number(&mut prog);
+        assert_eq!(prog.body.loc().node, id);
+        assert_eq!(spans.get(id), Some(&prog.body.loc()));
+        let at = |pat: &str| {
+            let id = nodes::at(&spans, SRC.find(pat)?)?;
+            spans.get(id).map(|loc| &SRC[loc.start_ix..loc.end_ix])
+        };
+        assert_eq!(at("x ="), Some("x"));
+        assert_eq!(at("[0]"), Some("args[0]"));
+        assert_eq!(at("add1"), Some("add1(x)"));
+        Ok(())
+    }
+
+    #[test]
+    fn inferred_types_by_node() -> Result<(), String> {
+        let mut prog = compile::parse(SRC).map_err(|e| e.to_string())?;
+        let spans = nodes::number(&mut prog);
+        let mut resolver = Resolver::new().types(TypeMode::Infer);
+        resolver.resolve_prog(prog).map_err(|e| e.to_string())?;
+        let inferred = resolver.inferred.ok_or("no types inferred")?.map_err(|e| e.to_string())?;
+        let type_at = |pat: &str| {
+            let id = nodes::at(&spans, SRC.find(pat)?)?;
+            inferred.nodes.get(id).map(|ty| ty.to_string())
+        };
+        assert_eq!(type_at("args[").as_deref(), Some("Array<Int>"));
+        assert_eq!(type_at("x =").as_deref(), Some("Int"));
+        assert_eq!(type_at("y)").as_deref(), Some("'a"));
+        assert_eq!(type_at("[y]").as_deref(), Some("Array<'a>"));
+        assert_eq!(type_at("f(add1").as_deref(), Some("Array<Int>"));
+        Ok(())
+    }
+}
+/*
+ * YOUR TESTS END HERE
+ */
+
+/* ----------------------- Test Implementation Details ---------------------- */
+
+use snake::conf::{CompilerConf, OptLevel, Verbosity};
+use snake::error::{Error, ResolveError};
+use snake::{interp, runner};
+use std::path::{Path, PathBuf};
+
+fn test_example_file(
     f: &str, args: impl IntoIterator<Item = &'static str>, expected: &str,
 ) -> std::io::Result<()> {
     let tmp_dir = tempfile::TempDir::new()?;
@@ -431,3 +5294,74 @@ fn test_example_fail(
     }
     Ok(())
 }
+
+/// Compiles the file `p` with the optimizations of `level`, which leave out
+/// assertion removal
+fn compile_at(p: &Path, level: OptLevel) -> Result<String, String> {
+    let optimizations = level.optimizations();
+    let (resolver, ast) = runner::emit_ast(p)?;
+    let (lowerer, ssa) = snake::compile::middle_end_with(resolver, ast, &optimizations)?;
+    let conf = CompilerConf::new(optimizations, Verbosity::Minimalistic);
+    Ok(snake::compile::backend_with(lowerer, ssa, &conf))
+}
+
+/// Compiles the file `p` at `level` and runs it as `runner::compile_and_run_file` does
+fn compile_and_run_at<W, S>(
+    p: &Path, level: OptLevel, dir: &Path, args: impl IntoIterator<Item = S>, out: &mut W,
+) -> Result<(), String>
+where
+    W: std::io::Write,
+    S: Into<String>,
+{
+    let asm = compile_at(p, level)?;
+    runner::link_and_run(&asm, Path::new("runtime/stub.rs"), dir, args, out)
+}
+
+fn test_example_o3(
+    f: &str, args: impl IntoIterator<Item = &'static str>, expected: &str,
+) -> std::io::Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut buf = Vec::new();
+    let file = format!("examples/{}", f);
+    match compile_and_run_at(Path::new(&file), OptLevel::O3, tmp_dir.path(), args, &mut buf) {
+        Ok(()) => assert_eq!(String::from_utf8_lossy(&buf).trim(), expected.trim()),
+        Err(e) => panic!("Expected {}, got an error: {}", expected, e),
+    }
+    Ok(())
+}
+
+fn test_example_o3_middle_end(
+    f: &str, args: impl IntoIterator<Item = &'static str>, expected: &str,
+) -> std::io::Result<()> {
+    let res = runner::emit_ast(Path::new(&format!("examples/{}", f))).and_then(|(resolver, ast)| {
+        let optimizations = OptLevel::O3.optimizations();
+        let (_, ssa) = snake::compile::middle_end_with(resolver, ast, &optimizations)?;
+        let mut interp = interp::ssa::Interp::new();
+        interp.run(&ssa, args).map_err(|e| format!("{}", e))
+    });
+    match res {
+        Ok(v) => assert_eq!(v.to_string(), expected),
+        Err(e) => panic!("Expected {}, got an error: {}", expected, e),
+    }
+    Ok(())
+}
+
+fn test_example_o3_fail(
+    f: &str, args: impl IntoIterator<Item = &'static str>, includes: &str,
+) -> std::io::Result<()> {
+    let tmp_dir = tempfile::TempDir::new()?;
+    let mut buf = Vec::new();
+    let file = format!("examples/{}", f);
+    match compile_and_run_at(Path::new(&file), OptLevel::O3, tmp_dir.path(), args, &mut buf) {
+        Ok(()) => {
+            panic!("Expected a failure but got: {}", String::from_utf8_lossy(&buf).trim())
+        }
+        Err(e) => assert!(
+            e.contains(includes),
+            "Expected error message to include the string \"{}\" but got the error: {}",
+            includes,
+            e
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,470 @@
+//! A reference evaluator of snake for differential testing: a naive big-step
+//! evaluator over the surface AST, written apart from `snake::interp` and
+//! sharing none of its code, so that a bug in the evaluation shared by the
+//! interpreters cannot hide behind agreeing results. Only the parser is
+//! shared.
+//!
+//! The evaluator knows the 63-bit integers, booleans, strings, arrays and the
+//! objects of enums, which are arrays of their tag and their fields, and gives up
+//! on generators, and on big integers, externs and the runtime services, whose
+//! results are not reproducible anyway.
+
+use snake::ast::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The bounds of the 63-bit integers
+const MIN: i64 = -(1 << 62);
+const MAX: i64 = (1 << 62) - 1;
+
+/// The words of the heap of the runtime, which no array fits in with its length
+const HEAP_WORDS: i64 = 100_000;
+
+/// The evaluations after which a program is deemed to diverge
+const FUEL: usize = 1_000_000;
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Int(i64),
+    Bool(bool),
+    Array(Rc<RefCell<Vec<Value>>>),
+    None,
+    Str(Rc<str>),
+}
+
+/// How a program fails
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    Overflow,
+    /// A value of another type than the expected one
+    Type,
+    Bounds,
+    /// A negative length of a new array
+    Length,
+    Memory,
+    /// The program ran out of fuel
+    Diverged,
+    /// What the evaluator does not know
+    Unsupported(String),
+}
+
+impl Failure {
+    /// The kind of the failure, as in `examples/prim_vectors.toml`
+    pub fn kind(&self) -> String {
+        match self {
+            Failure::Overflow => "overflow".to_string(),
+            Failure::Type => "type".to_string(),
+            Failure::Bounds => "bounds".to_string(),
+            Failure::Length => "length".to_string(),
+            Failure::Memory => "memory".to_string(),
+            Failure::Diverged => "diverged".to_string(),
+            Failure::Unsupported(what) => format!("unsupported: {}", what),
+        }
+    }
+}
+
+/// The result of a program as the interpreters print it, arrays aside, which
+/// print as `<array>`
+pub fn show(value: &Value) -> String {
+    match value {
+        Value::Int(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Array(_) => "<array>".to_string(),
+        Value::None => "none".to_string(),
+        Value::Str(s) => s.to_string(),
+    }
+}
+
+/// The value as `print` shows it, arrays with their elements, or `<loop>` for
+/// an array within itself
+fn printed(value: &Value, parents: &mut Vec<*const RefCell<Vec<Value>>>) -> String {
+    match value {
+        Value::Array(elements) => {
+            if parents.contains(&Rc::as_ptr(elements)) {
+                return "<loop>".to_string();
+            }
+            parents.push(Rc::as_ptr(elements));
+            let shown: Vec<_> = elements.borrow().iter().map(|e| printed(e, parents)).collect();
+            parents.pop();
+            format!("[{}]", shown.join(", "))
+        }
+        _ => show(value),
+    }
+}
+
+/// Evaluates the program `src` on `args`, on a thread of its own with a stack
+/// deep enough for the recursion of the program
+pub fn run(src: &str, args: &[&str]) -> Result<String, Failure> {
+    let prog = snake::compile::parse(src).map_err(|e| Failure::Unsupported(e.to_string()))?;
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    let thread = std::thread::Builder::new()
+        .stack_size(1 << 31)
+        .spawn(move || evaluate(&prog, &args).map(|value| show(&value)))
+        .map_err(|e| Failure::Unsupported(e.to_string()))?;
+    thread.join().unwrap_or_else(|_| Err(Failure::Unsupported("panic".to_string())))
+}
+
+/// The functions defined together, with the scope they were defined in
+struct Group {
+    decls: Vec<SurfFunDecl>,
+    scope: Scope,
+}
+
+/// The variables and the functions in scope
+#[derive(Clone, Default)]
+struct Scope {
+    vars: HashMap<String, Value>,
+    funs: HashMap<String, Rc<Group>>,
+}
+
+struct Evaluator {
+    fuel: usize,
+    /// The tag and the number of fields of each constructor
+    ctors: HashMap<String, (i64, usize)>,
+}
+
+pub fn evaluate(prog: &SurfProg, args: &[String]) -> Result<Value, Failure> {
+    if !prog.externs.is_empty() {
+        return Err(Failure::Unsupported("externs".to_string()));
+    }
+    let mut values = Vec::new();
+    for arg in args {
+        values.push(match arg.trim() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            n => match n.parse::<i64>() {
+                Ok(n) if (MIN..=MAX).contains(&n) => Value::Int(n),
+                _ => return Err(Failure::Unsupported(format!("argument {}", arg))),
+            },
+        });
+    }
+    let main = SurfFunDecl {
+        name: prog.name.clone(),
+        params: vec![prog.param.clone()],
+        signature: Signature::default(),
+        body: prog.body.clone(),
+        loc: prog.loc,
+        doc: None,
+        gen: false,
+    };
+    let group = Rc::new(Group { decls: vec![main], scope: Scope::default() });
+    let args = Value::Array(Rc::new(RefCell::new(values)));
+    let variants = prog.enums.iter().flat_map(|decl| decl.variants.iter());
+    let ctors = (variants.enumerate())
+        .map(|(tag, variant)| (variant.name.clone(), (tag as i64, variant.fields.len())))
+        .collect();
+    Evaluator { fuel: FUEL, ctors }.call(&group, &prog.name, vec![args])
+}
+
+fn int(value: &Value) -> Result<i64, Failure> {
+    match value {
+        Value::Int(n) => Ok(*n),
+        _ => Err(Failure::Type),
+    }
+}
+
+fn boolean(value: &Value) -> Result<bool, Failure> {
+    match value {
+        Value::Bool(b) => Ok(*b),
+        _ => Err(Failure::Type),
+    }
+}
+
+fn array(value: &Value) -> Result<Rc<RefCell<Vec<Value>>>, Failure> {
+    match value {
+        Value::Array(elements) => Ok(elements.clone()),
+        _ => Err(Failure::Type),
+    }
+}
+
+/// `n` if it is a 63-bit integer
+fn checked(n: i128) -> Result<Value, Failure> {
+    if (MIN as i128..=MAX as i128).contains(&n) {
+        Ok(Value::Int(n as i64))
+    } else {
+        Err(Failure::Overflow)
+    }
+}
+
+/// `n` wrapped around to 63 bits
+fn wrapped(n: i128) -> Value {
+    let n = n.rem_euclid(1 << 63);
+    Value::Int(if n > MAX as i128 { (n - (1 << 63)) as i64 } else { n as i64 })
+}
+
+/// `n` clamped to 63 bits
+fn clamped(n: i128) -> Value {
+    Value::Int(n.clamp(MIN as i128, MAX as i128) as i64)
+}
+
+/// The index of `a[i]` in the elements of `a`
+fn index(elements: &[Value], i: &Value) -> Result<usize, Failure> {
+    let i = int(i)?;
+    if 0 <= i && (i as usize) < elements.len() {
+        Ok(i as usize)
+    } else {
+        Err(Failure::Bounds)
+    }
+}
+
+/// `value`, if it has the type `ty` that an annotation requires
+fn typed(value: Value, ty: Option<Type>) -> Result<Value, Failure> {
+    match (ty, &value) {
+        (None, _)
+        | (Some(Type::Int), Value::Int(_))
+        | (Some(Type::Bool), Value::Bool(_))
+        | (Some(Type::Array), Value::Array(_)) => Ok(value),
+        _ => Err(Failure::Type),
+    }
+}
+
+/// `value` as the `i`th argument of a call of `fun`, checked against the
+/// annotation of the parameter; out of `eval`, like `bind`
+#[inline(never)]
+fn argument(scope: &Scope, fun: &str, i: usize, value: Value) -> Result<Value, Failure> {
+    let group = scope.funs.get(fun);
+    let decl = group.and_then(|group| group.decls.iter().find(|decl| decl.name == fun));
+    typed(value, decl.and_then(|decl| decl.signature.params.get(i).copied().flatten()))
+}
+
+/// Binds the variable of `binding`, or the elements that it destructures, to `value`;
+/// out of `eval`, whose frames the recursion of the programs piles up
+fn bind(scope: &mut Scope, binding: &SurfBinding, value: Value) -> Result<(), Failure> {
+    let value = typed(value, binding.ty)?;
+    match &binding.elems {
+        // one check of the last element covers all of them
+        Some(elems) => {
+            let elements = array(&value)?;
+            let elements = elements.borrow();
+            index(&elements, &Value::Int(elems.len() as i64 - 1))?;
+            for (elem, value) in elems.iter().zip(elements.iter()) {
+                scope.vars.insert(elem.node.clone(), value.clone());
+            }
+        }
+        None => {
+            scope.vars.insert(binding.var.node.clone(), value);
+        }
+    }
+    Ok(())
+}
+
+/// Whether `value` matches `pattern`, with the variables that it binds
+fn matches(
+    ctors: &HashMap<String, (i64, usize)>, pattern: &SurfPattern, value: &Value,
+    bound: &mut Vec<(String, Value)>,
+) -> Result<bool, Failure> {
+    let (ctor, args) = match pattern {
+        Pattern::Wildcard(_) => return Ok(true),
+        Pattern::Var(x, _) if !ctors.contains_key(x) => {
+            bound.push((x.clone(), value.clone()));
+            return Ok(true);
+        }
+        Pattern::Var(ctor, _) => (ctor, &[][..]),
+        Pattern::Ctor { ctor, args, .. } => (ctor, &args[..]),
+    };
+    let elements = array(value)?;
+    let elements = elements.borrow();
+    let tag = int(&elements[index(&elements, &Value::Int(0))?])?;
+    if tag != ctors[ctor].0 {
+        return Ok(false);
+    }
+    for (i, arg) in args.iter().enumerate() {
+        let field = &elements[index(&elements, &Value::Int(i as i64 + 1))?];
+        if !matches(ctors, arg, field, bound)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The object of the constructor `name` with the fields `args`, for a name
+/// that is neither a variable nor a function in scope
+#[inline(never)]
+fn construct(
+    ctors: &HashMap<String, (i64, usize)>, name: &str, args: Vec<Value>,
+) -> Result<Value, Failure> {
+    let (tag, _) =
+        (ctors.get(name)).ok_or_else(|| Failure::Unsupported(format!("unbound name {}", name)))?;
+    let elements = std::iter::once(Value::Int(*tag)).chain(args).collect();
+    Ok(Value::Array(Rc::new(RefCell::new(elements))))
+}
+
+/// Structural equality, as `==` compares arrays element by element
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => a == b,
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        (Value::Array(a), Value::Array(b)) => {
+            let (a, b) = (a.borrow(), b.borrow());
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| equal(a, b))
+        }
+        (Value::None, Value::None) => true,
+        (Value::Str(a), Value::Str(b)) => a == b,
+        _ => false,
+    }
+}
+
+impl Evaluator {
+    fn eval(&mut self, expr: &SurfExpr, scope: &Scope) -> Result<Value, Failure> {
+        self.fuel = self.fuel.checked_sub(1).ok_or(Failure::Diverged)?;
+        match expr {
+            Expr::Num(n, _) => Ok(Value::Int(*n)),
+            Expr::BigNum(..) => Err(Failure::Unsupported("big integers".to_string())),
+            Expr::Bool(b, _) => Ok(Value::Bool(*b)),
+            Expr::Var(x, _) => match scope.vars.get(x) {
+                Some(value) => Ok(value.clone()),
+                None => construct(&self.ctors, x, Vec::new()),
+            },
+            Expr::Prim { prim, args, .. } => {
+                let mut values = Vec::new();
+                for arg in args {
+                    values.push(self.eval(arg, scope)?);
+                }
+                prim_op(prim, &values)
+            }
+            Expr::Let { bindings, body, .. } => {
+                let mut scope = scope.clone();
+                for binding in bindings {
+                    let value = self.eval(&binding.expr, &scope)?;
+                    bind(&mut scope, binding, value)?;
+                }
+                self.eval(body, &scope)
+            }
+            Expr::If { cond, thn, els, .. } => {
+                if boolean(&self.eval(cond, scope)?)? {
+                    self.eval(thn, scope)
+                } else {
+                    self.eval(els, scope)
+                }
+            }
+            Expr::Switch { scrutinee, cases, default, .. } => {
+                let n = int(&self.eval(scrutinee, scope)?)?;
+                match cases.iter().find(|case| case.label.node == n) {
+                    Some(case) => self.eval(&case.body, scope),
+                    None => self.eval(default, scope),
+                }
+            }
+            Expr::Match { scrutinee, arms, .. } => self.matching(scrutinee, arms, scope),
+            Expr::FunDefs { decls, body, .. } => {
+                let group = Rc::new(Group { decls: decls.clone(), scope: scope.clone() });
+                let mut scope = scope.clone();
+                for decl in decls {
+                    scope.funs.insert(decl.name.clone(), group.clone());
+                }
+                self.eval(body, &scope)
+            }
+            Expr::Call { fun, args, .. } => {
+                let mut values = Vec::new();
+                for (i, arg) in args.iter().enumerate() {
+                    let value = self.eval(arg, scope)?;
+                    values.push(argument(scope, fun, i, value)?);
+                }
+                match scope.funs.get(fun) {
+                    Some(group) => self.call(&group.clone(), fun, values),
+                    None => construct(&self.ctors, fun, values),
+                }
+            }
+        }
+    }
+
+    /// Calls the function `name` of `group` on `args`, in the scope of the
+    /// group with all of its functions
+    fn call(&mut self, group: &Rc<Group>, name: &str, args: Vec<Value>) -> Result<Value, Failure> {
+        let decl = group.decls.iter().find(|decl| decl.name == name).unwrap();
+        if decl.gen {
+            return Err(Failure::Unsupported("generators".to_string()));
+        }
+        if decl.params.len() != args.len() {
+            return Err(Failure::Unsupported(format!("arity of {}", name)));
+        }
+        let mut scope = group.scope.clone();
+        for decl in group.decls.iter() {
+            scope.funs.insert(decl.name.clone(), group.clone());
+        }
+        for (param, arg) in decl.params.iter().zip(args) {
+            scope.vars.insert(param.node.clone(), arg);
+        }
+        typed(self.eval(&decl.body, &scope)?, decl.signature.ret)
+    }
+
+    /// Evaluates the first of `arms` whose pattern matches the value of
+    /// `scrutinee`, out of line to keep the frames of `eval` small
+    #[inline(never)]
+    fn matching(
+        &mut self, scrutinee: &SurfExpr, arms: &[SurfArm], scope: &Scope,
+    ) -> Result<Value, Failure> {
+        let value = self.eval(scrutinee, scope)?;
+        for arm in arms {
+            let mut bound = Vec::new();
+            if matches(&self.ctors, &arm.pattern, &value, &mut bound)? {
+                let mut scope = scope.clone();
+                scope.vars.extend(bound);
+                return self.eval(&arm.body, &scope);
+            }
+        }
+        Err(Failure::Unsupported("no arm matches".to_string()))
+    }
+}
+
+/// Applies `prim` to the values of its arguments
+fn prim_op(prim: &Prim, args: &[Value]) -> Result<Value, Failure> {
+    let ints = || -> Result<(i128, i128), Failure> {
+        Ok((int(&args[0])? as i128, int(&args[1])? as i128))
+    };
+    match prim {
+        Prim::Add1 => checked(int(&args[0])? as i128 + 1),
+        Prim::Sub1 => checked(int(&args[0])? as i128 - 1),
+        Prim::Add => ints().and_then(|(n, m)| checked(n + m)),
+        Prim::Sub => ints().and_then(|(n, m)| checked(n - m)),
+        Prim::Mul => ints().and_then(|(n, m)| checked(n * m)),
+        Prim::WrappingAdd => ints().map(|(n, m)| wrapped(n + m)),
+        Prim::WrappingSub => ints().map(|(n, m)| wrapped(n - m)),
+        Prim::WrappingMul => ints().map(|(n, m)| wrapped(n * m)),
+        Prim::SaturatingAdd => ints().map(|(n, m)| clamped(n + m)),
+        Prim::SaturatingSub => ints().map(|(n, m)| clamped(n - m)),
+        Prim::SaturatingMul => ints().map(|(n, m)| clamped(n * m)),
+        Prim::Not => Ok(Value::Bool(!boolean(&args[0])?)),
+        Prim::And => Ok(Value::Bool(boolean(&args[0])? & boolean(&args[1])?)),
+        Prim::Or => Ok(Value::Bool(boolean(&args[0])? | boolean(&args[1])?)),
+        Prim::Lt => ints().map(|(n, m)| Value::Bool(n < m)),
+        Prim::Le => ints().map(|(n, m)| Value::Bool(n <= m)),
+        Prim::Gt => ints().map(|(n, m)| Value::Bool(n > m)),
+        Prim::Ge => ints().map(|(n, m)| Value::Bool(n >= m)),
+        Prim::Eq => Ok(Value::Bool(equal(&args[0], &args[1]))),
+        Prim::Neq => Ok(Value::Bool(!equal(&args[0], &args[1]))),
+        Prim::IsType(ty) => Ok(Value::Bool(typed(args[0].clone(), Some(*ty)).is_ok())),
+        // only the resolver inserts checks
+        Prim::Check(ty) => typed(args[0].clone(), Some(*ty)),
+        Prim::None => Ok(Value::None),
+        Prim::IsNone => Ok(Value::Bool(matches!(args[0], Value::None))),
+        Prim::Str(s) => Ok(Value::Str(s.as_str().into())),
+        Prim::ToStr => Ok(Value::Str(printed(&args[0], &mut Vec::new()).into())),
+        Prim::Concat => {
+            let s = printed(&args[0], &mut Vec::new()) + &printed(&args[1], &mut Vec::new());
+            Ok(Value::Str(s.into()))
+        }
+        Prim::NewArray => match int(&args[0])? {
+            n if n < 0 => Err(Failure::Length),
+            n if n >= HEAP_WORDS => Err(Failure::Memory),
+            n => Ok(Value::Array(Rc::new(RefCell::new(vec![Value::Int(0); n as usize])))),
+        },
+        Prim::MakeArray => Ok(Value::Array(Rc::new(RefCell::new(args.to_vec())))),
+        Prim::ArrayGet => {
+            let elements = array(&args[0])?;
+            let elements = elements.borrow();
+            Ok(elements[index(&elements, &args[1])?].clone())
+        }
+        Prim::ArraySet => {
+            let elements = array(&args[0])?;
+            let i = index(&elements.borrow(), &args[1])?;
+            elements.borrow_mut()[i] = args[2].clone();
+            Ok(args[2].clone())
+        }
+        Prim::Length => Ok(Value::Int(array(&args[0])?.borrow().len() as i64)),
+        Prim::GetEnv | Prim::Clock | Prim::Random => {
+            Err(Failure::Unsupported("runtime services".to_string()))
+        }
+        Prim::Yield | Prim::Next => Err(Failure::Unsupported("generators".to_string())),
+    }
+}